@@ -0,0 +1,88 @@
+//! Event parsing: hook payloads and rollout lines into `AgentEvent`s
+//!
+//! Uses the golden-test fixtures, so the inputs are real adapter traffic.
+//! Measures the hook adapter (payload JSON → events), the daemon's read of
+//! an event line, and the Codex rollout parser. Run with
+//! `cargo bench --bench event_parsing`.
+
+use aura::agents::claude_code::{self, HookAgent};
+use aura::agents::codex::sessions::parse_rollout;
+use aura::ipc::StampedEvent;
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use serde_json::Value;
+use std::hint::black_box;
+use std::path::Path;
+
+fn fixture(name: &str) -> Vec<String> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name);
+    std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("can't read {}: {e}", path.display()))
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn hook_payloads(c: &mut Criterion) {
+    let payloads = fixture("claude/session_lifecycle.jsonl");
+    let lines: Vec<String> = payloads
+        .iter()
+        .flat_map(|payload| {
+            let hook: Value = serde_json::from_str(payload).unwrap();
+            claude_code::convert(&HookAgent::ClaudeCode, &hook).unwrap_or_default()
+        })
+        .enumerate()
+        .map(|(seq, event)| {
+            serde_json::to_string(&StampedEvent::new(event, 1_700_000_000_000, seq as u32)).unwrap()
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("hook");
+    group.throughput(Throughput::Elements(payloads.len() as u64));
+    group.bench_function("payload_to_events", |b| {
+        b.iter(|| {
+            for payload in &payloads {
+                let hook: Value = serde_json::from_str(black_box(payload)).unwrap();
+                black_box(claude_code::convert(&HookAgent::ClaudeCode, &hook));
+            }
+        })
+    });
+    group.throughput(Throughput::Elements(lines.len() as u64));
+    group.bench_function("event_line", |b| {
+        b.iter(|| {
+            for line in &lines {
+                black_box(serde_json::from_str::<StampedEvent>(black_box(line)).unwrap());
+            }
+        })
+    });
+    group.finish();
+}
+
+fn rollout_lines(c: &mut Criterion) {
+    let lines = fixture("codex/exec_and_patch.jsonl");
+    let values: Vec<Value> = lines
+        .iter()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    let mut group = c.benchmark_group("rollout");
+    group.throughput(Throughput::Elements(lines.len() as u64));
+    group.bench_function("parse_lines", |b| {
+        b.iter(|| black_box(parse_rollout("bench", black_box(&values))))
+    });
+    group.bench_function("json_and_parse_lines", |b| {
+        b.iter(|| {
+            let values: Vec<Value> = lines
+                .iter()
+                .map(|line| serde_json::from_str(black_box(line)).unwrap())
+                .collect();
+            black_box(parse_rollout("bench", &values))
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, hook_payloads, rollout_lines);
+criterion_main!(benches);
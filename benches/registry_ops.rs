@@ -0,0 +1,90 @@
+//! Registry operations with many sessions
+//!
+//! Fills a registry with up to 10k sessions, then measures applying a tool
+//! event to one of them, listing every session (`get_all`) and publishing a
+//! snapshot after a single change. Run with `cargo bench --bench registry_ops`.
+
+use aura::registry::SessionRegistry;
+use aura::{AgentEvent, AgentType};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+const SIZES: [usize; 3] = [100, 1_000, 10_000];
+
+fn session_id(i: usize) -> String {
+    format!("0199{i:08x}-0000-7000-8000-000000000000")
+}
+
+fn registry(sessions: usize) -> SessionRegistry {
+    let mut registry = SessionRegistry::new();
+    for i in 0..sessions {
+        registry.process_event(AgentEvent::SessionStarted {
+            session_id: session_id(i),
+            cwd: format!("/code/project-{}", i % 50),
+            agent: AgentType::ClaudeCode,
+            terminal: None,
+        });
+    }
+    registry.publish();
+    registry
+}
+
+fn process_event(c: &mut Criterion) {
+    let mut group = c.benchmark_group("registry/process_event");
+    for sessions in SIZES {
+        let mut registry = registry(sessions);
+        let mut call = 0usize;
+        group.bench_function(BenchmarkId::from_parameter(sessions), |b| {
+            b.iter(|| {
+                // A fresh tool ID each time, so no call is a repeated delivery
+                call += 1;
+                let session_id = session_id(call % sessions);
+                let tool_id = format!("toolu_{call}");
+                registry.process_event(AgentEvent::ToolStarted {
+                    session_id: session_id.clone(),
+                    cwd: "/code/aura".into(),
+                    tool_id: tool_id.clone(),
+                    tool_name: "Read".into(),
+                    tool_label: Some("src/registry.rs".into()),
+                    tool_full_label: None,
+                });
+                registry.process_event(AgentEvent::ToolCompleted {
+                    session_id,
+                    cwd: "/code/aura".into(),
+                    tool_id,
+                });
+            })
+        });
+    }
+    group.finish();
+}
+
+fn get_all(c: &mut Criterion) {
+    let mut group = c.benchmark_group("registry/get_all");
+    for sessions in SIZES {
+        let registry = registry(sessions);
+        group.bench_function(BenchmarkId::from_parameter(sessions), |b| {
+            b.iter(|| black_box(registry.get_all()))
+        });
+    }
+    group.finish();
+}
+
+fn publish(c: &mut Criterion) {
+    let mut group = c.benchmark_group("registry/publish");
+    for sessions in SIZES {
+        let mut registry = registry(sessions);
+        let mut call = 0usize;
+        group.bench_function(BenchmarkId::from_parameter(sessions), |b| {
+            b.iter(|| {
+                call += 1;
+                registry.rename_session(&session_id(call % sessions), &format!("task {call}"));
+                black_box(registry.publish())
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, process_event, get_all, publish);
+criterion_main!(benches);
@@ -0,0 +1,71 @@
+//! Transcript metadata: full rescan vs incremental tracking
+//!
+//! Builds a large Claude Code style transcript, then measures refreshing its
+//! totals after a small append both ways. Run with `cargo bench --bench transcript_meta`.
+
+use aura::transcript::{MetaTracker, TranscriptMeta};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use std::io::{BufReader, Write};
+use std::path::Path;
+
+const PROMPT: &str = r#"{"type":"user","message":{"content":"refactor the config loader and keep the old keys working"}}"#;
+const TOOL: &str = r#"{"type":"assistant","message":{"content":[{"type":"text","text":"Reading the loader."},{"type":"tool_use","name":"Read","input":{"file_path":"/code/aura/src/config.rs"}}]}}"#;
+const RESULT: &str = r#"{"type":"user","message":{"content":[{"type":"tool_result","tool_use_id":"t1","content":"pub struct Config { .. }"}]}}"#;
+
+/// Write a fixture of roughly `megabytes` MB
+fn write_fixture(path: &Path, megabytes: usize) {
+    let mut file = std::io::BufWriter::new(std::fs::File::create(path).unwrap());
+    let mut written = 0;
+    while written < megabytes * 1024 * 1024 {
+        for line in [PROMPT, TOOL, RESULT, TOOL, RESULT] {
+            writeln!(file, "{line}").unwrap();
+            written += line.len() + 1;
+        }
+    }
+    file.flush().unwrap();
+}
+
+fn append_turn(path: &Path) {
+    let mut file = std::fs::OpenOptions::new().append(true).open(path).unwrap();
+    writeln!(file, "{PROMPT}\n{TOOL}\n{RESULT}").unwrap();
+}
+
+fn refresh_after_append(c: &mut Criterion) {
+    let dir = tempfile::tempdir().unwrap();
+    let mut group = c.benchmark_group("transcript_meta");
+    group.sample_size(10);
+
+    for megabytes in [5, 50] {
+        let path = dir.path().join(format!("transcript-{megabytes}mb.jsonl"));
+        write_fixture(&path, megabytes);
+
+        group.bench_with_input(
+            BenchmarkId::new("full_scan", megabytes),
+            &path,
+            |b, path| {
+                b.iter(|| {
+                    append_turn(path);
+                    let file = std::fs::File::open(path).unwrap();
+                    TranscriptMeta::scan(BufReader::new(file))
+                })
+            },
+        );
+
+        let mut tracker = MetaTracker::new();
+        tracker.update(&path).unwrap();
+        group.bench_with_input(
+            BenchmarkId::new("incremental", megabytes),
+            &path,
+            |b, path| {
+                b.iter(|| {
+                    append_turn(path);
+                    tracker.update(path).unwrap().prompts
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, refresh_after_append);
+criterion_main!(benches);
@@ -39,6 +39,29 @@ Feature: CLI
     # Note: The actual name update happens via hook parsing when
     # Claude Code's PreToolUse hook intercepts this Bash command.
 
+  Rule: Sessions can be tagged and filtered by tag
+
+    Scenario: set-tag is a stub like set-name
+      When the user runs "aura set-tag billing"
+      Then the command prints "Session tagged: #billing"
+      And the tag is applied when the agent's Bash hook reports the command
+
+    Scenario: Tag and untag from the CLI
+      When the user runs "aura sessions tag abc billing Q3"
+      Then session "abc" has tags "billing" and "q3"
+      When the user runs "aura sessions untag abc q3"
+      Then session "abc" has only the tag "billing"
+
+    Scenario: List sessions with a tag
+      Given session "abc" is tagged "billing" and session "def" is not
+      When the user runs "aura sessions list --tag billing"
+      Then only session "abc" is listed, with "#billing" after its name
+
+    Scenario: Tags appear in exports
+      Given session "abc" is tagged "billing"
+      When the user runs "aura export abc"
+      Then the summary lists "Tags: #billing"
+
   Rule: hook subcommand forwards events to daemon
 
     Scenario: Hook reads stdin and sends to socket
@@ -56,3 +79,175 @@ Feature: CLI
         | claude-code |
 
     # Note: gemini-cli and open-code are future features
+
+  Rule: sessions subcommands control the running daemon
+
+    Scenario: Snooze a session by id prefix
+      Given the daemon is running
+      And a session with id "abc123..." exists
+      When the user runs "aura sessions snooze abc 1h30m"
+      Then the session is snoozed for 90 minutes
+      And the command prints the snooze end time
+
+    Scenario: Unsnooze a session
+      Given a snoozed session with id "abc123..."
+      When the user runs "aura sessions unsnooze abc"
+      Then the session is no longer snoozed
+
+    Scenario: Acknowledge a session
+      Given a session with id "abc123..." is in Attention state
+      When the user runs "aura ack abc"
+      Then the session is marked seen without changing its state
+      And the aggregate indicator stops pulsing once every session is seen
+
+    Scenario: List archived sessions
+      Given a session was archived from the HUD
+      When the user runs "aura sessions list --archived"
+      Then the archived session is listed
+
+    Scenario: Restore an archived session
+      Given an archived session with id "abc123..."
+      When the user runs "aura sessions restore abc"
+      Then the session reappears in the HUD
+
+    Scenario: Rename a session
+      Given a session with id "abc123..."
+      When the user runs "aura sessions rename abc 'fix login'"
+      Then the session list shows "fix login" for that session
+
+    Scenario: Blank names are rejected
+      When the user runs "aura sessions rename abc '  '"
+      Then the command prints an error
+      And exits with code 1
+
+    Scenario Outline: Bulk actions match the header menu
+      When the user runs "aura sessions bulk <action>"
+      Then the daemon applies the same action as the header menu item
+      And the command prints how many sessions changed
+
+      Examples:
+        | action        |
+        | clear-idle    |
+        | clear-stale   |
+        | mark-all-seen |
+
+    Scenario: Sessions command fails without a daemon
+      Given the daemon is not running
+      When the user runs "aura sessions snooze abc 30m"
+      Then the command prints an error
+      And exits with code 1
+
+  Rule: aura export summarizes a session's transcript
+
+    Scenario: Export to Markdown
+      Given a session with id "abc123..." and a known transcript
+      When the user runs "aura export abc"
+      Then stdout contains a Markdown summary with the session's prompts, tools used, files touched and duration
+
+    Scenario: Export to a JSON file
+      When the user runs "aura export abc --format json -o summary.json"
+      Then summary.json contains the same summary as JSON
+
+    Scenario: Sessions without a transcript can't be exported
+      Given the agent never reported a transcript path for the session
+      When the user runs "aura export abc"
+      Then the command prints an error
+      And exits with code 1
+
+  Rule: --json gives launchers stable output
+
+    Scenario: Status as JSON
+      Given one session needs attention and one is running
+      When the user runs "aura status --json"
+      Then stdout has "schema_version": 1, "state": "attention" and per-state counts
+
+    Scenario: Status as text
+      When the user runs "aura status"
+      Then stdout is "attention - 2 sessions (1 attention)"
+
+    Scenario: Following the status
+      When the user runs "aura status --follow"
+      Then stdout gets "14:02  attention - 2 sessions (1 attention)" right away
+      And a new timestamped line each time the daemon pushes a changed summary
+      And changes that leave the summary as it was print nothing
+      And with --json each update is one versioned JSON object per line
+      And the command exits with code 1 once the daemon stops
+
+    Scenario: Session list as JSON
+      When the user runs "aura sessions list --json"
+      Then stdout has "schema_version": 1 and a "sessions" array in the aura shortcut shape
+      And an empty list is still a JSON object
+
+  Rule: aura shortcut speaks JSON for Shortcuts and AppleScript
+
+    Scenario: List sessions as JSON
+      When the user runs "aura shortcut list"
+      Then stdout is a JSON object with a "sessions" array of id, name, cwd, state and snoozed
+
+    Scenario: Aggregate state
+      Given one session needs attention
+      When the user runs "aura shortcut state"
+      Then stdout is {"state":"attention", ...} with per-state counts
+
+    Scenario: Snooze everything for a Focus mode
+      When the user runs "aura shortcut snooze all 2h"
+      Then every visible session is snoozed for 2 hours
+      And stdout lists the snoozed sessions
+
+    Scenario: Errors are JSON too
+      Given the daemon is not running
+      When the user runs "aura shortcut state"
+      Then stdout is {"error": "..."}
+      And exits with code 1
+
+  Rule: aura prompt-segment shows the session for a directory
+
+    Scenario: Prompt shows a waiting session
+      Given session "fix login" in "/code/aura" is Waiting
+      When the user runs "aura prompt-segment" in "/code/aura/src"
+      Then stdout is "? fix login"
+
+    Scenario: Prompt segment doesn't contact the daemon
+      When the user runs "aura prompt-segment"
+      Then it reads the prompt cache file instead of the socket
+
+    Scenario: Nothing printed without a match
+      Given the daemon is not running
+      When the user runs "aura prompt-segment"
+      Then stdout is empty
+      And exits with code 0
+
+  Rule: aura sessions show prints one session in detail
+
+    Scenario: Changed files with line estimates
+      Given session "fix login" edited "src/a.rs" (+1/−0) and patched "src/b.rs" (+15/−7)
+      When the user runs "aura sessions show fix"
+      Then stdout includes "changes:    2 files, +16/−7"
+      And each changed file is listed with its own counts
+
+    Scenario: Branch, running tools, transcript totals and state history
+      Given session "fix login" runs "Read login.rs" on branch "fix/login"
+      And its transcript holds 3 prompts, 12 tool calls and 1.2M input tokens
+      When the user runs "aura sessions show fix"
+      Then stdout includes "branch:     fix/login" and "tools:      Read login.rs"
+      And "activity:", "prompt:" (the last prompt on one line, cut at 80 characters) and "tokens:     1.2M in, … out"
+      And a "history:" block lists each state entered with its clock time and how long it lasted
+
+    Scenario: JSON detail
+      When the user runs "aura sessions show abc --json"
+      Then stdout is {"schema_version": 1, "session": {...}, "branch": …, "transcript": {...}}
+      And "session" includes "changed_files" and "state_history"
+      And "transcript" holds "prompts", "tool_calls", "last_prompt", "input_tokens" and "output_tokens", or is null without a readable transcript
+
+  Rule: aura pause stops following one event source
+
+    Scenario: Pausing the Codex watcher
+      When the user runs "aura pause codex"
+      Then the daemon ignores Codex rollout events
+      And Diagnostics shows "Codex rollouts" as "paused, last event …"
+      And Claude Code hooks are still applied
+
+    Scenario: Resuming
+      Given Codex rollouts are paused
+      When the user runs "aura resume codex"
+      Then the next rollout event updates its session again
@@ -14,6 +14,12 @@ Feature: Claude Code Integration
       When a "SessionStart" hook fires with session "abc" and cwd "/projects/myapp"
       Then session "abc" is created with agent type "ClaudeCode"
 
+    Scenario: SessionStart records the terminal
+      Given the hook runs with TERM_PROGRAM "iTerm.app" and TMUX_PANE "%3"
+      When a "SessionStart" hook fires with session "abc"
+      Then session "abc" records terminal program "iTerm.app" and tmux pane "%3"
+      And it records the controlling tty and PID of the hook's parent process
+
     Scenario: PreToolUse starts a tool
       Given session "abc" exists
       When a "PreToolUse" hook fires with tool "Read" and tool_use_id "t1"
@@ -110,6 +116,13 @@ Feature: Claude Code Integration
       And passes hook JSON via stdin
       Then the hook CLI parses the JSON and sends AgentEvents to the Unix socket
 
+    Scenario: Events from slow hook processes are applied in firing order
+      Given a PreToolUse hook for "t1" fires before its PostToolUse hook
+      And the PostToolUse hook process connects to the daemon first
+      When both arrive within 100ms
+      Then the registry applies ToolStarted "t1" before ToolCompleted "t1"
+      And session "abc" shows 0 running tools
+
   Rule: Session naming is parsed from Bash tool hook events
 
     Scenario: Extracting session name from aura set-name command
@@ -19,6 +19,13 @@ Feature: Codex Integration
       When the daemon starts
       Then it watches "$CODEX_HOME/sessions/**.jsonl"
 
+    Scenario: Fallback scans back off while Codex is idle
+      Given no Codex session is in a turn
+      When fallback scans keep finding no changes
+      Then the scan interval doubles from 2 seconds up to 16 seconds
+      And a rollout change reported by the file watcher is still read immediately
+      And the interval returns to 2 seconds once a turn starts
+
   Rule: Rollout bootstrap is bounded
 
     Scenario: Recent rollout creates a session and replays a small tail
@@ -0,0 +1,51 @@
+@integration @editor-api
+Feature: Editor Status Endpoint
+  As a developer using VS Code or Zed
+  I want my editor to show the agent state of the open project
+  So that I notice a waiting session without looking at the HUD
+
+  Background:
+    Given "editor_api.enabled" is true in config.json
+    And the Aura daemon is running
+
+  Rule: GET /session reports the sessions of a workspace folder
+
+    Scenario: Sessions in and above the folder are included
+      Given a session in "/code/aura/web" that is Waiting
+      And a session in "/code/other" that is Attention
+      When an editor requests "/session?cwd=/code/aura"
+      Then the response state is "waiting"
+      And only the "/code/aura/web" session is listed
+
+    Scenario: No sessions
+      When an editor requests "/session?cwd=/tmp/empty"
+      Then the response state is null
+      And the sessions list is empty
+
+    Scenario: Snoozed sessions don't set the state
+      Given the only session in "/code/aura" is Attention and snoozed
+      When an editor requests "/session?cwd=/code/aura"
+      Then the response state is null
+
+  Rule: Long-polling wakes only on changes
+
+    Scenario: Held until the state changes
+      Given an editor has version "v1" for "/code/aura"
+      When it requests "/session?cwd=/code/aura&wait=v1"
+      And the session there moves from Running to Idle
+      Then the response arrives with state "idle" and a new version
+
+    Scenario: Unchanged state times out with the same version
+      When an editor waits on the current version and nothing changes for 25 seconds
+      Then the response repeats the current version
+
+  Rule: Only local clients can read it
+
+    Scenario: Foreign Host header is rejected
+      When a request arrives with "Host: evil.test"
+      Then the response status is 403
+
+    Scenario: Disabled by default
+      Given config.json has no "editor_api" section
+      When the daemon starts
+      Then nothing listens on port 47355
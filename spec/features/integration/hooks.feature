@@ -0,0 +1,56 @@
+@integration @hooks
+Feature: Event Hooks
+  As a developer who scripts their environment
+  I want Aura to run my commands when sessions change state
+  So that I can wire session activity into anything (sounds, lights, scripts)
+
+  Background:
+    Given the Aura daemon is running
+
+  Rule: config.json "hooks" maps triggers to shell commands
+
+    Scenario: Run a command when a session needs attention
+      Given "hooks.attention" is "say needs you"
+      When a session enters Attention
+      Then "say needs you" runs under sh -c
+
+    Scenario: Session lifecycle triggers
+      Given "hooks.session_started" and "hooks.session_ended" are set
+      When a session starts and later ends
+      Then each command runs once
+
+    Scenario: Warn when two sessions share a working directory
+      Given "hooks.cwd_conflict" is "osascript -e 'display notification \"$AURA_CWD is shared\"'"
+      When a second live session starts in a directory another session is using
+      Then the command runs once for each of the two sessions
+      And it doesn't run again while they keep sharing the directory
+
+    Scenario: Warn when two sessions modify the same file
+      Given "hooks.file_conflict" is set
+      When another live session modifies a file this session modified
+      Then the command runs with AURA_CONFLICT_FILES listing the shared paths, one per line
+      And it runs again only when a new shared path appears
+
+    Scenario: The session is described in the environment
+      When a hook runs for session "abc" named "fix login" in "/code/aura" entering Idle from Running
+      Then the command sees AURA_SESSION_ID=abc, AURA_NAME="fix login", AURA_CWD=/code/aura, AURA_STATE=idle, AURA_PREVIOUS_STATE=running and AURA_TRIGGER=idle
+
+    Scenario: Unknown triggers are reported
+      Given "hooks.atention" is set
+      When the daemon starts
+      Then a warning lists the valid triggers
+
+  Rule: Hooks are debounced and failures are logged
+
+    Scenario: Flapping sessions don't repeat a hook
+      Given "hooks.debounce_secs" is 5
+      When a session enters Attention twice within 5 seconds
+      Then the attention hook runs once
+
+    Scenario: Failing commands are logged
+      Given a hook command exits with status 1
+      Then the daemon logs a warning with the trigger, command, status and stderr
+
+    Scenario: Hung commands are killed
+      Given a hook command runs longer than 60 seconds
+      Then it is killed and a timeout warning is logged
@@ -0,0 +1,61 @@
+@integration @notifications
+Feature: Away Notifications
+  As a developer who steps away while agents work
+  I want Slack or Discord messages when a session needs me
+  So that I don't come back to a session that stalled an hour ago
+
+  Background:
+    Given the Aura daemon is running
+
+  Rule: Notifications are opt-in per destination
+
+    Scenario: Nothing is sent without a destination
+      Given config.json has no "notifications.slack" or "notifications.discord"
+      When a session enters Attention
+      Then no message is posted
+
+    Scenario: Discord webhook
+      Given "notifications.discord.webhook_url" is set
+      When session "fix login" in "/code/aura" enters Attention
+      Then a message "fix login (aura) is waiting for permission after 12m" is posted to the webhook
+
+    Scenario: Slack bot token
+      Given "notifications.slack.bot_token" and "notifications.slack.channel" are set
+      When a session enters Waiting
+      Then a message is posted to that channel via chat.postMessage
+
+    Scenario: Custom template
+      Given "notifications.template" is "{project}: {state}"
+      When a session in "/code/aura" finishes
+      Then the message is "aura: done"
+
+  Rule: Only sessions entering a configured state notify
+
+    Scenario: Default states
+      Then entering Attention, Waiting, or Idle notifies
+      And entering Running, Compacting, or Stale does not
+
+    Scenario: Staying in a state doesn't repeat
+      Given a notification was sent for a session in Attention
+      When the session stays in Attention
+      Then no further message is posted
+
+    Scenario: Snoozed sessions stay quiet
+      Given a session is snoozed
+      When it enters Attention
+      Then no message is posted
+
+  Rule: only_when_idle_minutes holds messages while the user is at the desk
+
+    Scenario: Held until the user is away
+      Given "notifications.only_when_idle_minutes" is 5
+      And the user typed 10 seconds ago
+      When a session enters Waiting
+      Then no message is posted yet
+      When the user has been idle for 5 minutes and the session is still Waiting
+      Then the message is posted
+
+    Scenario: Dropped when the session moves on
+      Given a held notification for a session in Attention
+      When the user grants permission and the session is Running again
+      Then the held message is discarded
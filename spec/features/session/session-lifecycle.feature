@@ -68,6 +68,18 @@ Feature: Session Lifecycle
       When the session receives a session_ended event
       Then the session is removed from the registry
 
+  Rule: Config filters scope which sessions are tracked
+
+    Scenario: An allow rule limits tracking to matching directories
+      Given config "filters" is [{ "action": "allow", "cwd": "/code/*" }]
+      When sessions start in "/code/aura" and "/home/bob/app"
+      Then only the session in "/code/aura" is in the registry
+
+    Scenario: A tool rule hides matching tool calls
+      Given config "filters" denies the tool "mcp__*"
+      When a session starts the tool "mcp__memory__search"
+      Then the session is Running without that tool in its running tools
+
   Rule: Stale detection uses per-session timers
 
     Scenario: Timer resets on new events
@@ -25,6 +25,11 @@ Feature: Tool Tracking
       When tool "t1" completes
       Then session "abc" shows 0 running tools
 
+    Scenario: A retried hook delivery is applied once
+      Given tool "Read" started with id "t1" and completed
+      When the PreToolUse hook for "t1" is delivered again within 5 seconds
+      Then session "abc" shows 0 running tools
+
   Rule: Recent tools persist for minimum display duration
 
     Scenario: Completed tool stays visible briefly
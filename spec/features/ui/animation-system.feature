@@ -59,3 +59,20 @@ Feature: Animation System
       When the user hovers over a session row
       Then the state icon transitions to the Bomb icon
       And the transition uses a 300ms slide and fade
+
+  Rule: Animation level is configurable and follows Reduce Motion
+
+    Scenario: Reduced motion
+      Given config "ui.animations" is "reduced"
+      Then nothing shakes, spins, breathes or slides
+      And running tool names switch without cross-fading
+
+    Scenario: Animations off
+      Given config "ui.animations" is "off"
+      Then the HUD is static
+      And each row shows its first running tool
+
+    Scenario: macOS Reduce Motion
+      Given config has no "ui.animations"
+      And macOS "Reduce Motion" is on
+      Then the HUD uses the reduced animation level
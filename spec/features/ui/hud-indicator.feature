@@ -38,7 +38,23 @@ Feature: HUD Indicator
       Then the indicator shows the Ghost icon
       And the indicator opacity oscillates between 0.5 and 0.3
 
-  Rule: Click toggles the session list
+  Rule: State priority is configurable
+
+    Scenario: Default priority
+      Given config has no "ui.indicator_priority"
+      Then Attention outranks Waiting, which outranks every other state
+
+    Scenario: Ignoring Idle sessions
+      Given config "ui.indicator_priority" lists only attention, waiting and running
+      And every session is Idle
+      Then the indicator shows the panda icon
+
+    Scenario: Treating Waiting as Attention
+      Given config "ui.indicator_priority" contains { "state": "waiting", "as": "attention" }
+      And a session is in "Waiting" state
+      Then the indicator shows the bell icon with shake
+      And "aura status" reports "attention"
+
 
     Scenario: Click opens session list
       Given the session list is collapsed
@@ -56,6 +72,19 @@ Feature: HUD Indicator
       When the user clicks the indicator
       Then nothing happens
 
+  Rule: Sessions can be shown as segments
+
+    Scenario: One arc per session
+      Given config "ui.indicator_style" is "segments"
+      And 3 sessions are "Running" and 1 is in "Attention" state
+      Then the indicator icon is ringed by 4 arcs
+      And the Attention arc comes first and is colored differently from the Running arcs
+
+    Scenario: Stale and snoozed sessions get no arc
+      Given config "ui.indicator_style" is "segments"
+      And a session is "Stale" and another is snoozed
+      Then neither session has an arc
+
   Rule: Right-click cycles the theme
 
     Scenario: Right-click cycles to next theme
@@ -95,3 +124,38 @@ Feature: HUD Indicator
     Scenario: Hover effect
       When the user hovers over the indicator
       Then the indicator scales to 1.08x
+
+  Rule: Window layering is configurable
+
+    Scenario: Default level floats above full-screen apps
+      Given no window_level is configured
+      When the daemon starts
+      Then the HUD windows stay visible over full-screen apps
+
+    Scenario: Desktop level sits below normal windows
+      Given ui.window_level is "desktop"
+      When the daemon starts
+      Then the HUD windows sit on the desktop beneath all app windows
+
+    Scenario: Single-Space mode
+      Given ui.all_spaces is false
+      When the user switches to another Space
+      Then the HUD windows are not shown on that Space
+
+  Rule: Auto-hide when nothing is running
+
+    Scenario: Indicator hides with no live sessions
+      Given ui.auto_hide is true
+      And every session is stale or there are no sessions
+      Then the indicator window is hidden and ignores clicks
+
+    Scenario: Indicator fades back in on the first event
+      Given ui.auto_hide is true
+      And the indicator is hidden
+      When a session emits an event
+      Then the indicator fades in over 300ms
+
+    Scenario: Auto-hide is off by default
+      Given no auto_hide is configured
+      And there are no sessions
+      Then the indicator shows the no-sessions icon
@@ -40,10 +40,33 @@ Feature: Session List
       Given a session has a running tool "Read" with label "main.rs"
       Then the session row second line shows the Read tool icon and "main.rs"
 
+    Scenario: Row header shows time since the last event
+      Given a session's last event arrived 2 minutes ago
+      Then the row header shows a dimmed "2m ago" after the name
+      And the label updates while the session list stays open
+
+    Scenario: Row header shows time in the current state
+      Given a session has been in "Waiting" state for 4 minutes
+      Then the row header shows "waiting 4m"
+
+    Scenario: Time in state escalates while the user keeps a session waiting
+      Given "ui.state_age_warn_minutes" is 5 and "ui.state_age_alert_minutes" is 15
+      When a session has been in "Attention" state for 6 minutes
+      Then its time in state turns amber
+      When it has been in "Attention" state for 15 minutes
+      Then its time in state turns red
+      And a seen or snoozed session, or one in another state, keeps the dimmed color
+
     Scenario: Session without custom name shows directory name
       Given a session exists with cwd "/home/user/my-project" and no custom name
       Then the session row shows "my-project" as the name
 
+    Scenario: Hovering a row shows what it truncates
+      Given a Claude Code session in "/Users/me/code/clients/acme/billing-service"
+      When the user hovers its row
+      Then a tooltip shows the full cwd, the session id, "agent Claude Code", the model and the start time
+      And in privacy mode it leaves out the cwd and shows the redacted name
+
   Rule: State placeholders appear when no tools are running
 
     Scenario: Running session with no tools shows thinking placeholder
@@ -77,22 +100,131 @@ Feature: Session List
       When the user hovers over a session row
       Then the state icon swaps to a Bomb icon with a 300ms slide transition
 
-    Scenario: Click bomb removes session
+    Scenario: Click bomb archives session
       Given the user is hovering over a session row
       When the user clicks the Bomb icon
-      Then the session is removed from the HUD
+      Then the session is archived and hidden from the HUD
+      And it is listed by "aura sessions list --archived"
+
+    Scenario: Click bell-off snoozes session
+      Given the user is hovering over a session row
+      When the user clicks the BellOff icon at the end of the header
+      Then the session is snoozed for 30 minutes
+      And the session no longer contributes to the aggregate indicator
+      And the BellOff icon stays visible while the snooze lasts
+
+    Scenario: Click bell-off on a snoozed session unsnoozes it
+      Given a session is snoozed
+      When the user clicks its BellOff icon
+      Then the session contributes to the aggregate indicator again
+
+  Rule: Header menu applies bulk actions
+
+    Scenario Outline: Bulk action from the header menu
+      Given the session list is open
+      When the user clicks the Ellipsis icon in the header
+      And chooses "<item>"
+      Then <effect>
+
+      Examples:
+        | item          | effect                                                   |
+        | Clear idle    | all Idle sessions are archived                           |
+        | Clear stale   | all Stale sessions are archived                          |
+        | Mark all seen | Attention and Waiting sessions stop driving the indicator, Idle sessions count as reviewed |
 
   Rule: Session list has size constraints
 
-    Scenario: Maximum 5 sessions visible without scrolling
+    Scenario: Maximum 5 sessions visible by default
       Given 7 sessions exist
       Then the session list shows 5 sessions
-      And the remaining sessions are accessible by scrolling
+      And a "+2 more…" footer row is shown below them
+
+    Scenario: Overflow footer reveals the remaining sessions
+      Given 7 sessions exist
+      When the user clicks the "+2 more…" footer
+      Then all 7 sessions are accessible by scrolling
+      And the footer reads "Show less"
+
+    Scenario: Visible session cap is configurable
+      Given config.json sets "ui.max_visible_sessions" to 3
+      And 4 sessions exist
+      Then the session list shows 3 sessions
+      And a "+1 more…" footer row is shown below them
+
+    Scenario: Ended sessions linger under a collapsed row
+      Given 3 sessions exist
+      When 2 of them end
+      Then the session list shows 1 session
+      And an "Ended recently (2)" row is shown below it
+      When the user clicks "Ended recently (2)"
+      Then the ended sessions are listed dimmed, with how long ago they ended
+      And the row reads "Hide ended (2)"
+
+    Scenario: Ended sessions leave after the configured window
+      Given config.json sets "ui.ended_visible_minutes" to 5
+      And a session ended 5 minutes ago
+      Then the "Ended recently" row no longer counts it
+      And archived sessions never appear under it
 
     Scenario: Session list height adapts to session count
       Given 3 sessions exist
       Then the session list height fits exactly 3 session rows
 
+    Scenario: Window height animates when rows are added or removed
+      Given 3 sessions exist
+      When a fourth session appears
+      Then the session list height eases to fit 4 rows over 200ms
+
+  Rule: Wide mode lays sessions out in a grid
+
+    Scenario: Grid adds columns as sessions accumulate
+      Given config.json sets "ui.layout" to "grid"
+      And 4 sessions exist
+      Then the session list shows 2 columns of 2 sessions
+      And the window widens to fit both columns
+
+    Scenario: Grid never exceeds three columns
+      Given config.json sets "ui.layout" to "grid"
+      And 12 sessions exist
+      Then the session list shows 3 columns
+      And "ui.max_visible_sessions" still caps the sessions shown
+
+  Rule: Sessions are ordered by project, manually or by activity
+
+    Scenario: Sessions default to most recent activity first
+      Given sessions in "/work/api" and "/work/web" exist
+      And "/work/web" had the most recent activity
+      Then the "/work/web" session is listed first
+
+    Scenario: Dragging a row sets a manual project order
+      When the user drags the "/work/api" row onto the "/work/web" row
+      Then "/work/api" sessions are listed in the position of "/work/web"
+      And the project order is saved to state.json
+
+    Scenario: Manual order survives restarts
+      Given state.json has a saved project order
+      When the daemon restarts
+      Then sessions in ordered projects are listed in that order
+      And sessions in other projects follow by most recent activity
+
+  Rule: Scrolling is stable across refreshes
+
+    Scenario: Scroll position is anchored on the top visible session
+      Given the user has scrolled so that session "b" is the top row
+      When a new session is inserted above "b"
+      Then session "b" stays the top visible row
+
+    Scenario: New sessions push in when scrolled to the top
+      Given the list is scrolled to the top
+      When a new session is inserted at the top
+      Then the new session is visible as the top row
+
+    Scenario: Scrollbar appears on hover
+      Given more sessions exist than fit in the list
+      When the user hovers over the session rows
+      Then a thin scrollbar shows the scroll position
+      And the scrollbar hides when the pointer leaves
+
   Rule: Session rows animate on appearance and removal
 
     Scenario: New session slides in from left
@@ -102,3 +234,272 @@ Feature: Session List
     Scenario: Removed session slides out to right
       When a session is removed
       Then the session row slides out to the right over 300ms
+
+  Rule: The frontmost terminal's session is highlighted when ui.follow_front_terminal is on
+
+    Scenario: Nothing is probed by default
+      Given ui.follow_front_terminal is not set
+      When a terminal whose shell is in "/code/aura" becomes frontmost
+      Then no row is highlighted
+      And no probe runs
+
+    Scenario: Probing stops without live sessions
+      Given ui.follow_front_terminal is on
+      When every session is stale or gone
+      Then the probe stops until a session is live again
+
+    Scenario: Row highlights when its terminal is in front
+      Given sessions in "/code/aura" and "/code/other"
+      When a terminal whose shell is in "/code/aura/src" becomes frontmost
+      Then the "/code/aura" row is highlighted
+
+    Scenario: Nested projects highlight the innermost match
+      Given sessions in "/code" and "/code/aura"
+      When a terminal whose shell is in "/code/aura" becomes frontmost
+      Then only the "/code/aura" row is highlighted
+
+    Scenario: Clicking the HUD keeps the highlight
+      Given the "/code/aura" row is highlighted
+      When the user clicks the indicator
+      Then the "/code/aura" row stays highlighted
+
+  Rule: Right-click a row for quick actions
+
+    Scenario: Context menu lists row actions
+      When the user right-clicks a session row
+      Then a menu opens at the pointer with "Rename", "Copy session id", "Copy link", "Open in Finder", "Open in terminal", "Open in editor", "View transcript", "Reveal transcript" and "Archive"
+
+    Scenario: Rename inline from the menu
+      When the user chooses "Rename" and types "fix login" then presses Enter
+      Then the row shows "fix login"
+
+    Scenario: Double-click the name to rename
+      When the user double-clicks a session's name
+      Then the name turns into an editable field with the current name
+      When the user types a new name and presses Enter
+      Then the registry records a SessionNameUpdated for that session
+
+    Scenario: Clicking elsewhere saves a rename
+      Given the user is renaming a session
+      When the user clicks outside the name
+      Then the edited name is saved
+
+    Scenario: Blank names are discarded
+      Given the user cleared the name with Cmd-Backspace
+      When the user presses Enter
+      Then the session keeps its previous name
+
+    Scenario: Escape cancels a rename
+      Given the user is renaming a session
+      When the user presses Escape
+      Then the original name is kept
+
+    Scenario: Transcript actions need a known transcript
+      Given the agent never reported a transcript path for the session
+      Then "View transcript" and "Reveal transcript" are disabled
+
+    Scenario: Open apps are configurable
+      Given ui.terminal_app is "Ghostty"
+      When the user chooses "Open in terminal"
+      Then the session's cwd opens in Ghostty
+
+    Scenario: Clicking outside closes the menu
+      Given the row menu is open
+      When the user clicks outside the menu
+      Then the menu closes
+
+  Rule: View a session's transcript without leaving the HUD
+
+    Scenario: Transcript window shows the conversation
+      When the user chooses "View transcript" for a session
+      Then a window opens listing the session's user and assistant turns in order
+      And tool results and thinking blocks are not shown
+
+    Scenario: Tool calls are collapsed
+      Given the transcript contains a Bash call running "cargo test"
+      Then it shows as one line "▸ Bash  cargo test"
+      When the user clicks the line
+      Then the tool input is shown below it
+
+    Scenario: New turns stream in
+      Given the transcript window is scrolled to the bottom
+      When the agent appends a reply to the transcript
+      Then the reply appears within a second and the view stays at the bottom
+
+    Scenario: Scrolling up stops following
+      Given the user scrolled up in the transcript window
+      When the agent appends a reply
+      Then the scroll position is kept
+
+  Rule: aura:// links jump back to a session
+
+    Scenario: Copy a session link
+      When the user chooses "Copy link" for session "abc-123"
+      Then the clipboard contains "aura://session/abc-123"
+
+    Scenario: Opening a link reveals the session
+      Given the session list is closed
+      When the user opens "aura://session/abc-123"
+      Then the session list opens
+      And the "abc-123" row is scrolled into view and highlighted for 3 seconds
+
+    Scenario: Links reveal rows behind the overflow footer
+      Given "abc-123" is hidden behind "+N more…"
+      When the user opens "aura://session/abc-123"
+      Then every session is shown and the "abc-123" row is scrolled into view
+
+    Scenario: Links to unknown sessions just open the list
+      When the user opens "aura://session/gone"
+      Then the session list opens without a highlight
+
+  Rule: Tags label rows and filter the list
+
+    Scenario: Tags show after the session name
+      Given session "fix login" is tagged "billing"
+      Then its row shows "#billing" after the name
+
+    Scenario: Edit tags from the row menu
+      When the user chooses "Edit tags" for a session
+      Then the name becomes an edit field holding the session's tags
+      When the user types "billing hotfix" and presses Enter
+      Then the session's tags are "billing" and "hotfix"
+
+    Scenario: Clearing the field removes every tag
+      When the user edits a session's tags to an empty field and presses Enter
+      Then the session has no tags
+
+    Scenario: Filter by tag from the header menu
+      Given sessions tagged "billing" and untagged sessions
+      When the user chooses "#billing" in the header menu
+      Then only sessions tagged "billing" are listed
+      And the header reads e.g. "2 running · 1 idle · #billing"
+      When the user chooses "#billing" again
+      Then every session is listed
+
+    Scenario: The indicator ignores the filter
+      Given the list is filtered to "#billing"
+      And an untagged session needs attention
+      Then the indicator still shows attention
+
+    Scenario: Tags from config rules
+      Given config.json has the tag rule {"tag": "hotfix", "branch": "hotfix/*"}
+      When a session starts in a repository on branch "hotfix/login"
+      Then the session is tagged "hotfix"
+
+  Rule: Sessions sharing a working directory are flagged
+
+    Scenario: Two live sessions in the same checkout
+      Given sessions "a" and "b" are both running in "/code/aura"
+      Then both rows show a warning badge before the snooze icon
+      And "aura sessions list" marks both "(shared cwd)"
+
+    Scenario: The badge clears when the collision ends
+      Given sessions "a" and "b" share "/code/aura"
+      When "b" is archived or goes stale
+      Then "a" no longer shows the warning badge
+
+    Scenario: Separate worktrees don't collide
+      Given session "a" runs in "/code/aura" and session "b" in "/code/aura-wt"
+      Then neither row shows the warning badge
+
+  Rule: Sessions modifying the same file are flagged
+
+    Scenario: Two sessions edit the same file
+      Given session "a" edited "/code/shared/api.rs" with Edit
+      And Codex session "b" patched "/code/shared/api.rs" with apply_patch
+      Then both rows show the warning badge
+      And each row's menu starts with "Shared with others:" and "api.rs"
+
+    Scenario: Long overlap lists are cut short
+      Given two sessions share 6 modified files
+      Then the row menu lists 4 file names and "+2 more"
+
+    Scenario: Reading a file is not a conflict
+      Given session "a" edited "/code/shared/api.rs" and session "b" only read it
+      Then neither row shows the warning badge
+
+  Rule: The row menu summarizes changed files
+
+    Scenario: Change summary heads the row menu
+      Given session "a" changed 7 files adding 412 and removing 88 lines
+      When the user opens the row menu for "a"
+      Then the menu starts with "7 files, +412/−88"
+
+  Rule: Acknowledging a session downgrades its urgency
+
+    Scenario: Clicking a row marks it seen
+      Given session "fix login" is in Attention state
+      When the user clicks its row
+      Then the session stays in Attention state
+      And its state icon stops shaking and the row dims slightly
+      And it no longer drives the aggregate indicator
+
+    Scenario: The next prompt needs a new acknowledgment
+      Given session "fix login" was marked seen in Waiting state
+      When it asks for permission again
+      Then its row animates again
+
+  Rule: Finished sessions nobody reviewed get a reminder
+
+    Scenario: Clock badge after the reminder delay
+      Given config "ui.review_reminder_minutes" is 20
+      And session "fix login" has been Idle for 20 minutes without being marked seen
+      Then its row shows a clock badge before the snooze icon
+      And a macOS notification "fix login finished 20m ago" is shown once
+
+    Scenario: Reviewing clears the reminder
+      Given session "fix login" shows the clock badge
+      When the user clicks the row or chooses "Mark all seen" in the header menu
+      Then the clock badge disappears
+      And no further reminder is shown until the session finishes another turn
+
+    Scenario: Snoozed and archived sessions are not reminded
+      Given session "fix login" has been Idle for an hour and is snoozed
+      Then no clock badge is shown and no notification is sent
+
+    Scenario: Reminders are off by default
+      Given config has no "ui.review_reminder_minutes"
+      Then Idle sessions never show the clock badge
+
+  Rule: The session list works without a mouse
+
+    Scenario: Focusing the list from the keyboard
+      When the user presses Cmd-Shift-L or chooses "Aura → Focus Session List"
+      Then the session list opens with key focus
+      And the first row has a focus ring
+
+    Scenario: Moving between rows
+      Given the session list has key focus
+      When the user presses Down or Up
+      Then the focus ring moves to the next or previous row, scrolling it into view
+      And in the grid layout Left and Right move between columns
+
+    Scenario: Acting on the focused row
+      Given row "fix login" has the focus ring
+      When the user presses Return
+      Then the session is marked seen, as if its row was clicked
+      When the user presses Delete
+      Then the session is archived and the next row takes the focus ring
+
+    Scenario: VoiceOver reads the focused row
+      Given row "fix login" has the focus ring and runs Edit on "login.rs"
+      Then VoiceOver reads "fix login, running, login.rs"
+      And with nothing focused it reads the header, e.g. "2 running · 1 idle"
+
+  Rule: The header summarizes sessions by state
+
+    Scenario: Counts per state
+      Given 2 sessions are Running, 1 is Waiting and 1 is Idle
+      Then the header reads "1 waiting · 2 running · 1 idle"
+      And states without sessions are left out
+
+    Scenario: Filtering by state from the header
+      When the user clicks "2 running" in the header
+      Then only the Running sessions are listed and "2 running" is highlighted
+      When the user clicks "2 running" again
+      Then every session is listed
+
+    Scenario: A state filter with no sessions left is dropped
+      Given the list is filtered to Running sessions
+      When the last Running session finishes
+      Then every session is listed
@@ -1,20 +1,29 @@
 //! Agent hook handler
 //!
-//! Reads hook JSON from stdin, converts to `AgentEvent`s, sends to daemon via Unix socket.
+//! Reads hook JSON from stdin, converts to `AgentEvent`s, sends to daemon via its socket
+//! (a named pipe on Windows).
 //! Invoked as `aura hook --agent <name>` subcommand.
 //!
 //! Each agent has its own stdin JSON format. The `--agent` flag selects the parser.
+//! Events go out as [`ipc::StampedEvent`]s stamped with the time the hook
+//! started, so the daemon applies them in firing order.
 //!
 //! # Claude Code hooks config:
 //! ```json
 //! { "type": "command", "command": "aura hook --agent claude-code" }
 //! ```
 
+use crate::ignore::IgnoreFilter;
 use crate::ipc;
-use crate::{AgentEvent, AgentType};
+use crate::{AgentEvent, AgentType, FileChange, PlanProgress, TerminalInfo};
 use serde_json::Value;
 use std::io::{Read, Write};
-use std::os::unix::net::UnixStream;
+use std::time::Duration;
+
+/// Text of Claude Code's idle prompt notification
+const IDLE_PROMPT_MESSAGE: &str = "waiting for your input";
+/// How long `SessionStart` waits for the daemon to answer the version ping
+const SKEW_CHECK_TIMEOUT: Duration = Duration::from_millis(200);
 
 /// Agent identifier for the `--agent` CLI flag.
 #[derive(Debug, Clone, PartialEq, clap::ValueEnum)]
@@ -27,13 +36,14 @@ pub enum HookAgent {
 
 /// Entry point for `aura hook` subcommand.
 pub fn run(agent: &HookAgent) {
-    let converter: fn(&Value) -> Option<Vec<AgentEvent>> = match agent {
-        HookAgent::ClaudeCode => convert_claude_code,
-        other => {
-            eprintln!("hook handler for {other:?} is not yet implemented");
-            return;
-        }
+    let Some(converter) = converter(agent) else {
+        eprintln!("hook handler for {agent:?} is not yet implemented");
+        return;
     };
+    // Taken before anything slow, so it reflects when the hook fired
+    let sent_at_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |since| since.as_millis() as u64);
 
     let mut input = String::new();
     if std::io::stdin().read_to_string(&mut input).is_err() {
@@ -45,24 +55,107 @@ pub fn run(agent: &HookAgent) {
         Err(_) => return,
     };
 
-    let Some(messages) = converter(&hook) else {
+    // Opted-out projects aren't captured or sent at all
+    let config = crate::config::load_config();
+    let ignore = config.ignore;
+    if ignore.opts_out(hook.get("cwd").and_then(|v| v.as_str()).unwrap_or("")) {
+        return;
+    }
+
+    crate::capture::Recorder::default().hook(agent, &hook);
+
+    let Some(mut messages) = converter(&hook) else {
         return;
     };
+    messages.extend(transcript_model_event(&hook));
+    messages.extend(profile_event(&hook, &config.agents.claude_config_dirs));
+    // Every hook runs in its own process, so the `ToolCompleted` of an ignored
+    // call still goes out; the registry doesn't know the tool and only notes
+    // the activity.
+    let mut messages = IgnoreFilter::new(ignore).apply(messages);
+
+    let session_start =
+        hook.get("hook_event_name").and_then(|v| v.as_str()) == Some("SessionStart");
+    if session_start {
+        attach_terminal(&mut messages, terminal_info());
+    }
 
-    let path = ipc::socket_path();
-    let mut stream = match UnixStream::connect(&path) {
+    let mut stream = match ipc::connect() {
         Ok(s) => s,
         Err(e) => {
-            eprintln!("daemon not running ({:?}): {}", path.display(), e);
+            eprintln!(
+                "daemon not running ({:?}): {}",
+                ipc::socket_path().display(),
+                e
+            );
             return;
         }
     };
 
-    for msg in messages {
-        if let Ok(json) = serde_json::to_string(&msg) {
+    for (seq, msg) in messages.into_iter().enumerate() {
+        let stamped = ipc::StampedEvent::new(msg, sent_at_ms, seq as u32);
+        if let Ok(json) = serde_json::to_string(&stamped) {
             let _ = writeln!(stream, "{json}");
         }
     }
+    drop(stream);
+
+    // Once the events are out, so a daemon slow to answer doesn't hold them
+    if session_start {
+        check_daemon();
+    }
+}
+
+/// Warn on stderr when the daemon speaks another protocol (a skewed
+/// install); once per daemon version, remembered by a marker file next to
+/// the socket. Gives up after [`SKEW_CHECK_TIMEOUT`], since the agent waits
+/// on the hook.
+fn check_daemon() {
+    let Ok(response) = ipc::send_request_within(&ipc::IpcMessage::Ping, SKEW_CHECK_TIMEOUT) else {
+        return;
+    };
+    let Some(problem) = ipc::incompatibility(&response) else {
+        return;
+    };
+    let daemon_version = match &response {
+        ipc::IpcResponse::Pong { version, .. } => version.as_str(),
+        _ => "",
+    };
+    let daemon_version: String = daemon_version
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-'))
+        .collect();
+    let marker = ipc::socket_path().with_file_name(format!("hook-skew-{daemon_version}"));
+    if marker.exists() {
+        return;
+    }
+    let _ = std::fs::write(&marker, &problem);
+    eprintln!("aura hook: {problem}");
+}
+
+/// Hook JSON parser for `agent`, if one exists yet
+fn converter(agent: &HookAgent) -> Option<fn(&Value) -> Option<Vec<AgentEvent>>> {
+    match agent {
+        HookAgent::ClaudeCode => Some(convert_claude_code),
+        _ => None,
+    }
+}
+
+/// Convert hook JSON from `agent` to agent events (used by `aura debug replay`
+/// and the golden tests).
+pub fn convert(agent: &HookAgent, hook: &Value) -> Option<Vec<AgentEvent>> {
+    converter(agent)?(hook)
+}
+
+/// Subagent (teammate) ID and type from newer hook payloads: `agent_id` plus
+/// `agent_type`, or `teammate_name` for named teammates
+fn subagent_fields(hook: &Value) -> Option<(String, Option<String>)> {
+    let agent_id = hook.get("agent_id")?.as_str().filter(|id| !id.is_empty())?;
+    let agent_type = ["agent_type", "teammate_name"]
+        .iter()
+        .find_map(|key| hook.get(*key)?.as_str())
+        .map(String::from);
+    Some((agent_id.to_string(), agent_type))
 }
 
 fn common_fields(hook: &Value) -> Option<(String, String)> {
@@ -82,14 +175,35 @@ fn common_fields(hook: &Value) -> Option<(String, String)> {
 fn convert_claude_code(hook: &Value) -> Option<Vec<AgentEvent>> {
     let event_name = hook.get("hook_event_name")?.as_str()?;
     let (session_id, cwd) = common_fields(hook)?;
+    // Hooks carry the current permission mode; "plan" means Claude Code is
+    // drafting a plan and will ask for approval via ExitPlanMode
+    let plan_mode = hook
+        .get("permission_mode")
+        .and_then(Value::as_str)
+        .map(|mode| AgentEvent::PlanModeChanged {
+            session_id: session_id.clone(),
+            active: mode == "plan",
+        });
+    // Some hooks report the model, as a string or `{"id", "display_name"}`
+    let model = hook
+        .get("model")
+        .and_then(|model| model.as_str().or_else(|| model.get("id")?.as_str()))
+        .filter(|model| !model.is_empty())
+        .map(|model| AgentEvent::ModelUpdated {
+            session_id: session_id.clone(),
+            model: model.to_string(),
+        });
 
-    let messages = match event_name {
+    let mut messages = match event_name {
         "SessionStart" => {
-            vec![AgentEvent::SessionStarted {
-                session_id,
+            let mut events = vec![AgentEvent::SessionStarted {
+                session_id: session_id.clone(),
                 cwd,
                 agent: AgentType::ClaudeCode,
-            }]
+                terminal: None,
+            }];
+            events.extend(transcript_path_event(hook, session_id));
+            events
         }
 
         "PreToolUse" => {
@@ -101,23 +215,66 @@ fn convert_claude_code(hook: &Value) -> Option<Vec<AgentEvent>> {
             let tool_id = hook
                 .get("tool_use_id")
                 .and_then(|v| v.as_str())
-                .unwrap_or("unknown")
+                .unwrap_or(crate::UNKNOWN_TOOL_ID)
                 .to_string();
             let tool_label = extract_tool_label(hook);
+            let tool_full_label = extract_full_tool_label(hook, &cwd)
+                .and_then(|value| crate::agents::full_label(&value, tool_label.as_deref()));
+            let change = hook
+                .get("tool_input")
+                .and_then(|input| file_change(&tool_name, input))
+                .map(|change| FileChange {
+                    path: crate::agents::resolve_path(&cwd, &change.path),
+                    ..change
+                });
 
             let mut events = vec![AgentEvent::ToolStarted {
                 session_id: session_id.clone(),
                 cwd,
-                tool_id,
+                tool_id: tool_id.clone(),
                 tool_name: tool_name.clone(),
                 tool_label,
+                tool_full_label,
             }];
+            // Tool calls made by a parallel subagent carry its ID
+            if let Some((agent_id, _)) = subagent_fields(hook) {
+                events.push(AgentEvent::SubagentTool {
+                    session_id: session_id.clone(),
+                    agent_id,
+                    tool_id,
+                });
+            }
+            if let Some(change) = change {
+                events.push(AgentEvent::FilesTouched {
+                    session_id: session_id.clone(),
+                    changes: vec![change],
+                });
+            }
+            if tool_name == "TodoWrite"
+                && let Some(progress) = hook.get("tool_input").and_then(todo_progress)
+            {
+                events.push(AgentEvent::PlanUpdated {
+                    session_id: session_id.clone(),
+                    progress,
+                });
+            }
 
             if tool_name == "Bash"
                 && let Some(tool_input) = hook.get("tool_input")
-                && let Some(name) = parse_set_name_command(tool_input)
             {
-                events.push(AgentEvent::SessionNameUpdated { session_id, name });
+                if let Some(name) = parse_set_name_command(tool_input) {
+                    events.push(AgentEvent::SessionNameUpdated {
+                        session_id: session_id.clone(),
+                        name,
+                    });
+                }
+                if let Some(tags) = tool_input
+                    .get("command")
+                    .and_then(Value::as_str)
+                    .and_then(crate::agents::parse_aura_set_tag_command)
+                {
+                    events.push(AgentEvent::SessionTagged { session_id, tags });
+                }
             }
 
             events
@@ -127,7 +284,7 @@ fn convert_claude_code(hook: &Value) -> Option<Vec<AgentEvent>> {
             let tool_id = hook
                 .get("tool_use_id")
                 .and_then(|v| v.as_str())
-                .unwrap_or("unknown")
+                .unwrap_or(crate::UNKNOWN_TOOL_ID)
                 .to_string();
 
             vec![AgentEvent::ToolCompleted {
@@ -171,11 +328,25 @@ fn convert_claude_code(hook: &Value) -> Option<Vec<AgentEvent>> {
                         .get("message")
                         .and_then(|v| v.as_str())
                         .map(String::from);
-                    vec![AgentEvent::NeedsAttention {
-                        session_id,
-                        cwd,
-                        message,
-                    }]
+                    // Claude Code before `notification_type` only sent the
+                    // text; its idle prompt isn't a request for attention
+                    let idle = notification_type.is_empty()
+                        && message
+                            .as_deref()
+                            .is_some_and(|m| m.contains(IDLE_PROMPT_MESSAGE));
+                    if idle {
+                        vec![AgentEvent::WaitingForInput {
+                            session_id,
+                            cwd,
+                            message,
+                        }]
+                    } else {
+                        vec![AgentEvent::NeedsAttention {
+                            session_id,
+                            cwd,
+                            message,
+                        }]
+                    }
                 }
             }
         }
@@ -205,19 +376,134 @@ fn convert_claude_code(hook: &Value) -> Option<Vec<AgentEvent>> {
         }
 
         "UserPromptSubmit" => {
-            vec![AgentEvent::Activity { session_id, cwd }]
+            // Re-announce the transcript so sessions registered late
+            // (daemon restarted mid-session) learn it too
+            let mut events = vec![AgentEvent::Activity {
+                session_id: session_id.clone(),
+                cwd,
+            }];
+            events.extend(transcript_path_event(hook, session_id));
+            events
         }
 
         "SubagentStart" | "SubagentStop" => {
-            vec![AgentEvent::Activity { session_id, cwd }]
+            let mut events = vec![AgentEvent::Activity {
+                session_id: session_id.clone(),
+                cwd,
+            }];
+            if let Some((agent_id, agent_type)) = subagent_fields(hook) {
+                events.push(if event_name == "SubagentStart" {
+                    AgentEvent::SubagentStarted {
+                        session_id,
+                        agent_id,
+                        agent_type,
+                    }
+                } else {
+                    AgentEvent::SubagentStopped {
+                        session_id,
+                        agent_id,
+                    }
+                });
+            }
+            events
         }
 
         _ => return None,
     };
 
+    messages.extend(plan_mode);
+    messages.extend(model);
     Some(messages)
 }
 
+/// `TranscriptPath` event from the hook's `transcript_path` field, if present
+fn transcript_path_event(hook: &Value, session_id: String) -> Option<AgentEvent> {
+    let path = hook.get("transcript_path")?.as_str()?;
+    (!path.is_empty()).then(|| AgentEvent::TranscriptPath {
+        session_id,
+        path: path.to_string(),
+    })
+}
+
+/// On `SessionStart`, the profile of the config dir (`agents.claude_config_dirs`)
+/// holding the session's transcript, as a tag
+fn profile_event(hook: &Value, dirs: &[crate::config::AgentHome]) -> Option<AgentEvent> {
+    if hook.get("hook_event_name")?.as_str()? != "SessionStart" {
+        return None;
+    }
+    let transcript = std::path::Path::new(hook.get("transcript_path")?.as_str()?);
+    let profile = dirs
+        .iter()
+        .find(|dir| transcript.starts_with(dir.dir()))?
+        .profile
+        .clone()?;
+    Some(AgentEvent::SessionTagged {
+        session_id: hook.get("session_id")?.as_str()?.to_string(),
+        tags: vec![profile],
+    })
+}
+
+/// Put `terminal` on the `SessionStarted` in `messages`, unless it's empty
+fn attach_terminal(messages: &mut [AgentEvent], info: TerminalInfo) {
+    if info.is_empty() {
+        return;
+    }
+    for message in messages {
+        if let AgentEvent::SessionStarted { terminal, .. } = message {
+            *terminal = Some(info.clone());
+        }
+    }
+}
+
+/// Terminal of the agent running this hook, from the environment it passed
+/// down and its controlling tty
+fn terminal_info() -> TerminalInfo {
+    let var = |name| std::env::var(name).ok().filter(|value| !value.is_empty());
+    let pid = parent_pid();
+    TerminalInfo {
+        program: var("TERM_PROGRAM"),
+        tmux_pane: var("TMUX_PANE"),
+        tty: pid.and_then(controlling_tty),
+        pid,
+    }
+}
+
+#[cfg(unix)]
+fn parent_pid() -> Option<u32> {
+    Some(std::os::unix::process::parent_id())
+}
+
+#[cfg(not(unix))]
+fn parent_pid() -> Option<u32> {
+    None
+}
+
+/// `/dev/<tty>` of process `pid`; hooks get piped stdio, so ask `ps`
+fn controlling_tty(pid: u32) -> Option<String> {
+    let output = std::process::Command::new("ps")
+        .args(["-o", "tty=", "-p", &pid.to_string()])
+        .output()
+        .ok()?;
+    let tty = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    // "??" (macOS) or "?" (Linux) without a controlling terminal
+    (!tty.is_empty() && !tty.starts_with('?')).then(|| format!("/dev/{tty}"))
+}
+
+/// At the end of a turn, the model the transcript's latest reply came from.
+///
+/// Not part of [`convert`]: it reads the transcript file, and the model can
+/// change mid-session (`/model`) without any hook reporting it.
+fn transcript_model_event(hook: &Value) -> Option<AgentEvent> {
+    if hook.get("hook_event_name")?.as_str()? != "Stop" {
+        return None;
+    }
+    let path = hook.get("transcript_path")?.as_str()?;
+    Some(AgentEvent::ModelUpdated {
+        session_id: hook.get("session_id")?.as_str()?.to_string(),
+        model: crate::transcript::latest_model(std::path::Path::new(path))?,
+    })
+}
+
 /// Parse `aura set-name "..."` from a Bash tool_input's `command` field.
 ///
 /// Supports double quotes, single quotes, and unquoted single-token names.
@@ -227,6 +513,61 @@ fn parse_set_name_command(tool_input: &Value) -> Option<String> {
     crate::agents::parse_aura_set_name_command(command)
 }
 
+/// File a modifying tool (Edit, Write, MultiEdit, NotebookEdit) is about to
+/// change, with line counts estimated from the input (path as given)
+fn file_change(tool_name: &str, input: &Value) -> Option<FileChange> {
+    let text = |value: &Value, key: &str| {
+        value
+            .get(key)
+            .and_then(Value::as_str)
+            .map_or(0, crate::agents::line_count)
+    };
+    let (key, added, removed) = match tool_name {
+        "Edit" => (
+            "file_path",
+            text(input, "new_string"),
+            text(input, "old_string"),
+        ),
+        "MultiEdit" => {
+            let edits = input.get("edits").and_then(Value::as_array);
+            let sum = |key| {
+                edits
+                    .into_iter()
+                    .flatten()
+                    .map(|edit| text(edit, key))
+                    .sum::<u32>()
+            };
+            ("file_path", sum("new_string"), sum("old_string"))
+        }
+        "Write" => ("file_path", text(input, "content"), 0),
+        "NotebookEdit" => ("notebook_path", text(input, "new_source"), 0),
+        _ => return None,
+    };
+    let path = input.get(key)?.as_str().filter(|path| !path.is_empty())?;
+    Some(FileChange {
+        path: path.to_string(),
+        added,
+        removed,
+    })
+}
+
+/// Progress of a TodoWrite list: `{"todos": [{"content", "status",
+/// "activeForm"}, ...]}`. The item in progress is named by its
+/// `activeForm` ("Running tests") when given.
+fn todo_progress(input: &Value) -> Option<PlanProgress> {
+    let todos = input.get("todos")?.as_array()?;
+    Some(PlanProgress::from_steps(todos.iter().map(|todo| {
+        let status = todo.get("status").and_then(Value::as_str).unwrap_or("");
+        let content = todo.get("content").and_then(Value::as_str).unwrap_or("");
+        let active = todo.get("activeForm").and_then(Value::as_str);
+        let step = match active {
+            Some(active) if status == "in_progress" && !active.trim().is_empty() => active,
+            _ => content,
+        };
+        (status, step)
+    })))
+}
+
 /// Extract a human-readable label for a tool invocation
 fn extract_tool_label(hook: &Value) -> Option<String> {
     let tool_name = hook.get("tool_name")?.as_str()?;
@@ -280,6 +621,23 @@ fn extract_tool_label(hook: &Value) -> Option<String> {
     }
 }
 
+/// The value a tool's label shortens: the Bash command (the label may be
+/// its description), the absolute file path, the URL or the search pattern
+fn extract_full_tool_label(hook: &Value, cwd: &str) -> Option<String> {
+    let tool_name = hook.get("tool_name")?.as_str()?;
+    let input = hook.get("tool_input")?;
+    let field = |name: &str| input.get(name).and_then(|v| v.as_str());
+    match tool_name {
+        "Bash" => field("command").map(String::from),
+        "Read" | "Write" | "Edit" => field("file_path").map(|path| super::resolve_path(cwd, path)),
+        "NotebookEdit" => field("notebook_path").map(|path| super::resolve_path(cwd, path)),
+        "Grep" => field("pattern").map(String::from),
+        "WebFetch" => field("url").map(String::from),
+        "WebSearch" => field("query").map(String::from),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -298,6 +656,85 @@ mod tests {
         assert!(json.contains("session_started"));
     }
 
+    #[test]
+    fn terminal_is_attached_to_session_started_only() {
+        let hook = serde_json::json!({
+            "session_id": "abc123",
+            "cwd": "/home/user/project",
+            "hook_event_name": "SessionStart",
+            "transcript_path": "/home/user/.claude/projects/p/abc123.jsonl"
+        });
+        let mut msgs = convert_claude_code(&hook).unwrap();
+        let info = TerminalInfo {
+            program: Some("iTerm.app".into()),
+            tmux_pane: Some("%3".into()),
+            tty: Some("/dev/ttys004".into()),
+            pid: Some(4242),
+        };
+        attach_terminal(&mut msgs, info.clone());
+        match &msgs[0] {
+            AgentEvent::SessionStarted { terminal, .. } => assert_eq!(terminal, &Some(info)),
+            other => panic!("expected SessionStarted, got {other:?}"),
+        }
+        assert!(matches!(msgs[1], AgentEvent::TranscriptPath { .. }));
+
+        let mut msgs = convert_claude_code(&hook).unwrap();
+        attach_terminal(&mut msgs, TerminalInfo::default());
+        let json = serde_json::to_value(&msgs[0]).unwrap();
+        assert!(json.get("terminal").is_none());
+    }
+
+    #[test]
+    fn session_start_is_tagged_with_the_config_dir_profile() {
+        let dirs = [
+            crate::config::AgentHome {
+                path: "/home/user/.claude".into(),
+                profile: None,
+            },
+            crate::config::AgentHome {
+                path: "/home/user/.claude-work".into(),
+                profile: Some("work".into()),
+            },
+        ];
+        let hook = |event: &str, transcript: &str| {
+            serde_json::json!({
+                "session_id": "abc123",
+                "hook_event_name": event,
+                "transcript_path": transcript
+            })
+        };
+        let work = "/home/user/.claude-work/projects/p/abc123.jsonl";
+        match profile_event(&hook("SessionStart", work), &dirs) {
+            Some(AgentEvent::SessionTagged { session_id, tags }) => {
+                assert_eq!(session_id, "abc123");
+                assert_eq!(tags, ["work"]);
+            }
+            other => panic!("expected SessionTagged, got {other:?}"),
+        }
+        assert!(profile_event(&hook("Stop", work), &dirs).is_none());
+        let personal = "/home/user/.claude/projects/p/abc123.jsonl";
+        assert!(profile_event(&hook("SessionStart", personal), &dirs).is_none());
+    }
+
+    #[test]
+    fn convert_session_start_with_transcript_path() {
+        let hook = serde_json::json!({
+            "session_id": "abc123",
+            "cwd": "/home/user/project",
+            "hook_event_name": "SessionStart",
+            "transcript_path": "/home/user/.claude/projects/p/abc123.jsonl"
+        });
+        let msgs = convert_claude_code(&hook).unwrap();
+        assert_eq!(msgs.len(), 2);
+        match &msgs[1] {
+            AgentEvent::TranscriptPath { session_id, path } => {
+                assert_eq!(session_id, "abc123");
+                assert_eq!(path, "/home/user/.claude/projects/p/abc123.jsonl");
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+
     #[test]
     fn convert_pre_tool_use() {
         let hook = serde_json::json!({
@@ -318,6 +755,33 @@ mod tests {
         assert!(json.contains("Run test suite"));
     }
 
+    #[test]
+    fn convert_todo_write_reports_progress() {
+        let hook = serde_json::json!({
+            "session_id": "abc123",
+            "cwd": "/home/user/project",
+            "hook_event_name": "PreToolUse",
+            "tool_name": "TodoWrite",
+            "tool_use_id": "toolu_02",
+            "tool_input": {
+                "todos": [
+                    {"content": "Add parser", "status": "completed", "activeForm": "Adding parser"},
+                    {"content": "Run tests", "status": "in_progress", "activeForm": "Running tests"},
+                    {"content": "Update docs", "status": "pending", "activeForm": "Updating docs"}
+                ]
+            }
+        });
+        let msgs = convert_claude_code(&hook).unwrap();
+        assert_eq!(msgs.len(), 2);
+        match &msgs[1] {
+            AgentEvent::PlanUpdated { progress, .. } => {
+                assert_eq!((progress.completed, progress.total), (1, 3));
+                assert_eq!(progress.current.as_deref(), Some("Running tests"));
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+
     #[test]
     fn convert_post_tool_use() {
         let hook = serde_json::json!({
@@ -365,6 +829,27 @@ mod tests {
         assert!(json.contains("waiting_for_input"));
     }
 
+    #[test]
+    fn untyped_notifications_tell_idle_prompts_from_attention() {
+        let notification = |message: &str| {
+            let hook = serde_json::json!({
+                "session_id": "abc123",
+                "cwd": "/home/user/project",
+                "hook_event_name": "Notification",
+                "message": message
+            });
+            convert_claude_code(&hook).unwrap().remove(0)
+        };
+        assert!(matches!(
+            notification("Claude is waiting for your input"),
+            AgentEvent::WaitingForInput { .. }
+        ));
+        assert!(matches!(
+            notification("Claude needs your permission to use Bash"),
+            AgentEvent::NeedsAttention { .. }
+        ));
+    }
+
     #[test]
     fn convert_stop() {
         let hook = serde_json::json!({
@@ -429,6 +914,55 @@ mod tests {
         assert!(json.contains("Write"));
     }
 
+    #[test]
+    fn convert_reports_model() {
+        for model in [
+            serde_json::json!("claude-sonnet-4-5"),
+            serde_json::json!({"id": "claude-sonnet-4-5", "display_name": "Sonnet 4.5"}),
+        ] {
+            let hook = serde_json::json!({
+                "session_id": "abc123",
+                "cwd": "/home/user/project",
+                "hook_event_name": "SessionStart",
+                "model": model
+            });
+            let msgs = convert_claude_code(&hook).unwrap();
+            assert!(matches!(
+                msgs.last(),
+                Some(AgentEvent::ModelUpdated { model, .. }) if model == "claude-sonnet-4-5"
+            ));
+        }
+    }
+
+    #[test]
+    fn convert_reports_plan_mode() {
+        let hook = serde_json::json!({
+            "session_id": "abc123",
+            "cwd": "/home/user/project",
+            "hook_event_name": "PermissionRequest",
+            "tool_name": "ExitPlanMode",
+            "permission_mode": "plan"
+        });
+        let msgs = convert_claude_code(&hook).unwrap();
+        assert_eq!(msgs.len(), 2);
+        assert!(matches!(
+            &msgs[1],
+            AgentEvent::PlanModeChanged { session_id, active: true } if session_id == "abc123"
+        ));
+
+        let hook = serde_json::json!({
+            "session_id": "abc123",
+            "cwd": "/home/user/project",
+            "hook_event_name": "UserPromptSubmit",
+            "permission_mode": "acceptEdits"
+        });
+        let msgs = convert_claude_code(&hook).unwrap();
+        assert!(matches!(
+            msgs.last(),
+            Some(AgentEvent::PlanModeChanged { active: false, .. })
+        ));
+    }
+
     #[test]
     fn subagent_start_emits_activity() {
         let hook = serde_json::json!({
@@ -443,6 +977,39 @@ mod tests {
         assert!(json.contains("\"type\":\"activity\""));
     }
 
+    #[test]
+    fn subagent_ids_group_tools_by_agent() {
+        let start = serde_json::json!({
+            "session_id": "abc123",
+            "cwd": "/home/user/project",
+            "hook_event_name": "SubagentStart",
+            "agent_id": "agent_1",
+            "agent_type": "Explore"
+        });
+        let msgs = convert_claude_code(&start).unwrap();
+        assert!(matches!(
+            &msgs[1],
+            AgentEvent::SubagentStarted { agent_id, agent_type: Some(kind), .. }
+                if agent_id == "agent_1" && kind == "Explore"
+        ));
+
+        let tool = serde_json::json!({
+            "session_id": "abc123",
+            "cwd": "/home/user/project",
+            "hook_event_name": "PreToolUse",
+            "tool_name": "Grep",
+            "tool_use_id": "toolu_1",
+            "tool_input": {"pattern": "login"},
+            "agent_id": "agent_1"
+        });
+        let msgs = convert_claude_code(&tool).unwrap();
+        assert!(matches!(
+            msgs.last(),
+            Some(AgentEvent::SubagentTool { agent_id, tool_id, .. })
+                if agent_id == "agent_1" && tool_id == "toolu_1"
+        ));
+    }
+
     #[test]
     fn subagent_stop_emits_activity() {
         let hook = serde_json::json!({
@@ -656,6 +1223,30 @@ mod tests {
         assert_eq!(extract_tool_label(&hook), Some("memory_search".to_string()));
     }
 
+    #[test]
+    fn full_tool_label_keeps_command_and_absolute_path() {
+        let hook = serde_json::json!({
+            "tool_name": "Bash",
+            "tool_input": { "command": "cargo test --workspace", "description": "Run tests" }
+        });
+        assert_eq!(
+            extract_full_tool_label(&hook, "/code/aura"),
+            Some("cargo test --workspace".to_string())
+        );
+        let hook = serde_json::json!({
+            "tool_name": "Read",
+            "tool_input": { "file_path": "src/main.rs" }
+        });
+        assert_eq!(
+            extract_full_tool_label(&hook, "/code/aura"),
+            Some("/code/aura/src/main.rs".to_string())
+        );
+        assert_eq!(
+            crate::agents::full_label("npm test", Some("npm test")),
+            None
+        );
+    }
+
     // --- parse_set_name_command tests ---
 
     #[test]
@@ -736,4 +1327,88 @@ mod tests {
         assert!(second.contains("test session"));
         assert!(second.contains("abc123"));
     }
+
+    #[test]
+    fn convert_pre_tool_use_with_set_tag() {
+        let hook = serde_json::json!({
+            "session_id": "abc123",
+            "cwd": "/home/user/project",
+            "hook_event_name": "PreToolUse",
+            "tool_name": "Bash",
+            "tool_use_id": "toolu_03",
+            "tool_input": { "command": "aura set-tag billing Q3" }
+        });
+        let msgs = convert_claude_code(&hook).unwrap();
+        assert_eq!(msgs.len(), 2, "expected ToolStarted + SessionTagged");
+        match &msgs[1] {
+            AgentEvent::SessionTagged { session_id, tags } => {
+                assert_eq!(session_id, "abc123");
+                assert_eq!(tags, &vec!["billing".to_string(), "q3".to_string()]);
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn convert_pre_tool_use_edit_touches_file() {
+        let hook = serde_json::json!({
+            "session_id": "abc123",
+            "cwd": "/home/user/project",
+            "hook_event_name": "PreToolUse",
+            "tool_name": "Edit",
+            "tool_use_id": "toolu_04",
+            "tool_input": { "file_path": "src/main.rs", "old_string": "a", "new_string": "b" }
+        });
+        let msgs = convert_claude_code(&hook).unwrap();
+        assert_eq!(msgs.len(), 2, "expected ToolStarted + FilesTouched");
+        match &msgs[1] {
+            AgentEvent::FilesTouched {
+                session_id,
+                changes,
+            } => {
+                assert_eq!(session_id, "abc123");
+                assert_eq!(
+                    changes,
+                    &vec![FileChange {
+                        path: "/home/user/project/src/main.rs".into(),
+                        added: 1,
+                        removed: 1,
+                    }]
+                );
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+
+        let read = serde_json::json!({
+            "session_id": "abc123",
+            "cwd": "/home/user/project",
+            "hook_event_name": "PreToolUse",
+            "tool_name": "Read",
+            "tool_use_id": "toolu_05",
+            "tool_input": { "file_path": "/home/user/project/src/main.rs" }
+        });
+        assert_eq!(convert_claude_code(&read).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn file_change_estimates_lines() {
+        let multi = serde_json::json!({
+            "file_path": "/p/a.rs",
+            "edits": [
+                { "old_string": "a\nb", "new_string": "c" },
+                { "old_string": "d", "new_string": "e\nf\ng" }
+            ]
+        });
+        let change = file_change("MultiEdit", &multi).unwrap();
+        assert_eq!((change.added, change.removed), (4, 3));
+
+        let write = serde_json::json!({ "file_path": "/p/b.rs", "content": "x\ny\n" });
+        let change = file_change("Write", &write).unwrap();
+        assert_eq!(
+            (change.path.as_str(), change.added, change.removed),
+            ("/p/b.rs", 2, 0)
+        );
+
+        assert!(file_change("Read", &write).is_none());
+    }
 }
@@ -4,8 +4,11 @@
 //! (or `$CODEX_HOME/sessions`).
 
 use crate::AgentEvent;
+use crate::config::AgentHome;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, OnceLock};
-use tokio::sync::broadcast;
+use tokio::sync::{Notify, broadcast};
+use tracing::warn;
 
 pub mod sessions;
 
@@ -15,26 +18,88 @@ const EVENT_BUFFER: usize = 4096;
 pub struct CodexEventStream {
     tx: broadcast::Sender<AgentEvent>,
     started: Arc<OnceLock<()>>,
+    lag: Arc<StreamLag>,
+    /// Codex homes to watch (empty watches `CODEX_HOME` or `~/.codex`)
+    homes: Arc<Vec<AgentHome>>,
 }
 
 #[derive(Debug)]
 pub struct CodexEventRx {
     rx: broadcast::Receiver<AgentEvent>,
+    lag: Arc<StreamLag>,
+}
+
+/// Events dropped because a receiver fell behind the broadcast buffer.
+///
+/// Lagging asks the rollout watcher to resync: recent rollouts are re-read and
+/// their latest state replayed, so a missed `Idle` doesn't leave a session
+/// stuck in Running.
+#[derive(Debug, Default)]
+pub struct StreamLag {
+    dropped: AtomicU64,
+    resyncs: AtomicU64,
+    requested: AtomicBool,
+    notify: Notify,
+}
+
+impl StreamLag {
+    /// Events dropped so far, across receivers
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Resyncs the watcher has run so far
+    pub fn resyncs(&self) -> u64 {
+        self.resyncs.load(Ordering::Relaxed)
+    }
+
+    fn record(&self, missed: u64) {
+        let total = self.dropped.fetch_add(missed, Ordering::Relaxed) + missed;
+        warn!(
+            target: crate::logging::CODEX_WATCHER,
+            missed,
+            total,
+            "codex receiver lagged; resyncing"
+        );
+        self.requested.store(true, Ordering::Relaxed);
+        self.notify.notify_one();
+    }
+
+    /// Wait until a receiver lags
+    async fn resync_requested(&self) {
+        self.notify.notified().await;
+    }
+
+    /// Take a pending resync request, counting it
+    fn take_resync(&self) -> bool {
+        let requested = self.requested.swap(false, Ordering::Relaxed);
+        if requested {
+            self.resyncs.fetch_add(1, Ordering::Relaxed);
+        }
+        requested
+    }
 }
 
 impl CodexEventStream {
     /// Subscribe to Codex agent events.
     ///
-    /// This receiver intentionally swallows `broadcast::RecvError::Lagged` since
-    /// the Codex integration is best-effort and Aura does not attempt to recover
-    /// missed events.
+    /// Events a slow receiver misses are counted in [`lag`](Self::lag) and
+    /// trigger a resync of recent rollouts instead of being recovered one by one.
     pub fn subscribe(&self) -> CodexEventRx {
         // Create the receiver first, then start the producer so bootstrap events
         // can't race ahead of the first subscriber.
         let rx = self.tx.subscribe();
         self.started
-            .get_or_init(|| sessions::spawn(self.tx.clone()));
-        CodexEventRx { rx }
+            .get_or_init(|| sessions::spawn(&self.homes, self.tx.clone(), Arc::clone(&self.lag)));
+        CodexEventRx {
+            rx,
+            lag: Arc::clone(&self.lag),
+        }
+    }
+
+    /// Lag accounting shared by every receiver
+    pub fn lag(&self) -> &StreamLag {
+        &self.lag
     }
 }
 
@@ -43,18 +108,60 @@ impl CodexEventRx {
         loop {
             match self.rx.recv().await {
                 Ok(ev) => return Some(ev),
-                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Lagged(missed)) => self.lag.record(missed),
                 Err(broadcast::error::RecvError::Closed) => return None,
             }
         }
     }
 }
 
-/// Spawn the Codex integration and return an event stream handle.
+/// Spawn the Codex integration and return an event stream handle, watching
+/// the homes in config `agents.codex_homes`.
 pub fn spawn() -> CodexEventStream {
+    spawn_with_homes(crate::config::load_config().agents.codex_homes)
+}
+
+/// Like [`spawn`], watching `homes` instead of the configured ones (empty
+/// watches `CODEX_HOME` or `~/.codex`).
+pub fn spawn_with_homes(homes: Vec<AgentHome>) -> CodexEventStream {
     let (tx, _rx) = broadcast::channel(EVENT_BUFFER);
     CodexEventStream {
         tx,
         started: Arc::new(OnceLock::new()),
+        lag: Arc::new(StreamLag::default()),
+        homes: Arc::new(homes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn activity(i: usize) -> AgentEvent {
+        AgentEvent::Activity {
+            session_id: format!("s{i}"),
+            cwd: "/tmp".into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn lagged_receiver_counts_drops_and_requests_resync() {
+        let (tx, rx) = broadcast::channel(2);
+        let lag = Arc::new(StreamLag::default());
+        let mut rx = CodexEventRx {
+            rx,
+            lag: Arc::clone(&lag),
+        };
+        for i in 0..5 {
+            tx.send(activity(i)).unwrap();
+        }
+
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.session_id(), "s3");
+        assert_eq!(lag.dropped(), 3);
+
+        assert!(lag.take_resync());
+        assert!(!lag.take_resync());
+        assert_eq!(lag.resyncs(), 1);
     }
 }
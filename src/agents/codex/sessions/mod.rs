@@ -10,80 +10,152 @@ mod parser;
 mod paths;
 
 use self::parser::RolloutState;
-use crate::{AgentEvent, AgentType};
+use super::StreamLag;
+use crate::capture::Recorder;
+use crate::config::AgentHome;
+use crate::ignore::{IgnoreConfig, IgnoreFilter};
+use crate::logging;
+use crate::tailer::{JsonlTail, Progress, Replay};
+use crate::{AgentEvent, AgentType, FileChange};
 use notify::{RecursiveMode, Watcher};
 use serde_json::Value;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
-use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncSeekExt, BufReader};
-use tokio::sync::{Notify, broadcast};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::{Notify, Semaphore, broadcast};
+use tokio::task::JoinSet;
 use tracing::{debug, info, trace, warn};
 
 const BOOTSTRAP_REPLAY_MAX_EVENTS: usize = 4;
+/// Bootstrap scans at most this much of the end of a rollout
+const BOOTSTRAP_WINDOW: u64 = 16 * 1024 * 1024;
+/// Rollouts read at once while bootstrapping
+const BOOTSTRAP_CONCURRENCY: usize = 8;
+/// Most rollouts tracked at once; the least recently active go first
+const MAX_WATCHED: usize = 1024;
+/// How often tracked rollouts are checked for eviction
+const EVICT_INTERVAL: Duration = Duration::from_secs(60);
 const VISIBILITY_WINDOW: Duration = Duration::from_secs(10 * 60);
+/// Fallback scan interval while a Codex turn runs
 const FALLBACK_SCAN_INTERVAL: Duration = Duration::from_secs(2);
-
-fn parse_json_line(path: &Path, line: &str, context: &'static str) -> Option<Value> {
-    match serde_json::from_str(line) {
-        Ok(v) => Some(v),
-        Err(e) => {
-            // Avoid logging the full line (may contain user content).
-            warn!(path = %path.display(), error = %e, "{context}");
-            None
-        }
-    }
-}
-
-fn drain_jsonl_lines(buffer: &mut String, mut on_line: impl FnMut(&str)) {
-    let mut start = 0usize;
-    let bytes = buffer.as_bytes();
-
-    for (idx, b) in bytes.iter().enumerate() {
-        if *b != b'\n' {
-            continue;
-        }
-
-        let line = buffer[start..idx].trim();
-        start = idx + 1;
-        if line.is_empty() {
-            continue;
-        }
-        on_line(line);
-    }
-
-    if start > 0 {
-        *buffer = buffer[start..].to_string();
-    }
-}
+/// Longest fallback scan interval, reached by doubling while every tracked
+/// session is idle
+const IDLE_SCAN_INTERVAL_MAX: Duration = Duration::from_secs(16);
+/// Fallback scan interval once notify is known to miss events
+const FAST_SCAN_INTERVAL: Duration = Duration::from_millis(500);
+/// Fallback-scan ticks in a row that found changes notify never reported
+/// before [`FAST_SCAN_INTERVAL`] polling takes over
+const MISSED_TICKS_BEFORE_FAST_SCAN: u32 = 3;
+/// How often a missing Codex home is checked for
+const CODEX_HOME_POLL: Duration = Duration::from_secs(30);
 
 #[derive(Debug)]
 struct WatchedRollout {
-    path: PathBuf,
-    offset: u64,
-    buffer: String,
+    tail: JsonlTail,
     state: RolloutState,
+    /// `TranscriptPath` already sent for this rollout
+    path_announced: bool,
+    /// Tailed lines go to `aura debug record` captures
+    recorder: Recorder,
+    /// Drops the config's ignored tools and paths
+    ignore: IgnoreFilter,
+    /// Tag for the Codex home's profile, added when the session is emitted
+    profile: Option<String>,
+    /// Last time the file changed or was found recent; rollouts idle for
+    /// longer than [`VISIBILITY_WINDOW`] are evicted
+    last_active: Instant,
 }
 
 impl WatchedRollout {
     fn new_existing(path: PathBuf, session_id: String, cwd: String, offset: u64) -> Self {
         Self {
-            path,
-            offset,
-            buffer: String::new(),
+            tail: JsonlTail::at(path, offset),
             state: RolloutState::new(session_id, cwd),
+            path_announced: false,
+            recorder: Recorder::default(),
+            ignore: IgnoreFilter::default(),
+            profile: None,
+            last_active: Instant::now(),
         }
     }
 
     fn new_fresh(path: PathBuf, session_id: String, cwd: String) -> Self {
         Self {
-            path,
-            offset: 0,
-            buffer: String::new(),
+            tail: JsonlTail::new(path),
             state: RolloutState::new(session_id, cwd),
+            path_announced: false,
+            recorder: Recorder::default(),
+            ignore: IgnoreFilter::default(),
+            profile: None,
+            last_active: Instant::now(),
+        }
+    }
+
+    /// Take the session ID and working directory from the `session_meta` line
+    async fn load_meta(&mut self) {
+        if let Some((session_id, cwd)) = read_first_session_meta(self.tail.path()).await {
+            self.state.session_id = session_id;
+            self.state.cwd = cwd;
         }
     }
+
+    /// Filter this rollout's events through the `ignore` config and label
+    /// its session with the home's profile
+    fn with_options(mut self, options: &RolloutOptions) -> Self {
+        self.ignore = IgnoreFilter::new(options.ignore.clone());
+        self.profile = options.profile.clone();
+        self
+    }
+}
+
+/// How the rollouts under one Codex home are read
+#[derive(Debug, Clone, Default)]
+struct RolloutOptions {
+    ignore: IgnoreConfig,
+    /// `agents.codex_homes[].profile`
+    profile: Option<String>,
+}
+
+/// Events for a whole rollout read from the start, as the watcher emits them
+/// while tailing (used by the golden tests). `session_id` is the fallback
+/// until a `session_meta` line names the session.
+pub fn parse_rollout<'a>(
+    session_id: &str,
+    lines: impl IntoIterator<Item = &'a Value>,
+) -> Vec<AgentEvent> {
+    let mut state = RolloutState::new(session_id.to_string(), String::new());
+    lines
+        .into_iter()
+        .flat_map(|line| state.apply_line(line))
+        .collect()
+}
+
+/// Parses captured rollout lines for `aura debug replay`, one state per rollout
+#[derive(Debug, Default)]
+pub(crate) struct ReplayRollouts {
+    states: HashMap<String, RolloutState>,
+}
+
+impl ReplayRollouts {
+    /// Events for one captured line; the first line of a rollout also
+    /// registers its session
+    pub(crate) fn apply(
+        &mut self,
+        path: &str,
+        session_id: &str,
+        cwd: &str,
+        line: &Value,
+    ) -> Vec<AgentEvent> {
+        let state = self
+            .states
+            .entry(path.to_string())
+            .or_insert_with(|| RolloutState::new(session_id.to_string(), cwd.to_string()));
+        let mut events: Vec<AgentEvent> = state.ensure_session_event().into_iter().collect();
+        events.extend(state.apply_line(line));
+        events
+    }
 }
 
 fn emit_events(tx: &broadcast::Sender<AgentEvent>, events: Vec<AgentEvent>) {
@@ -91,7 +163,7 @@ fn emit_events(tx: &broadcast::Sender<AgentEvent>, events: Vec<AgentEvent>) {
         return;
     }
     for event in events {
-        trace!(?event, "codex rollout event");
+        trace!(target: logging::CODEX_WATCHER, ?event, "codex rollout event");
         let _ = tx.send(event);
     }
 }
@@ -134,64 +206,67 @@ async fn file_len(path: &Path) -> Option<u64> {
 }
 
 async fn bootstrap_rollout(watched: &mut WatchedRollout, tx: &broadcast::Sender<AgentEvent>) {
-    // Ignore stale sessions to avoid flooding the HUD with historical rollouts.
-    if !paths::modified_within(&watched.path, VISIBILITY_WINDOW).await {
-        watched.offset = file_len(&watched.path).await.unwrap_or(watched.offset);
-        watched.buffer.clear();
-        return;
-    }
+    let events = scan_rollout(watched).await;
+    emit_events(tx, events);
+}
 
-    let mut file = match tokio::fs::File::open(&watched.path).await {
-        Ok(f) => f,
-        Err(e) => {
-            debug!(path = %watched.path.display(), error = %e, "failed to open codex rollout for bootstrap");
-            return;
-        }
+/// Re-read a rollout from the start, returning the bootstrap events
+/// (empty if the rollout is stale or unreadable).
+async fn scan_rollout(watched: &mut WatchedRollout) -> Vec<AgentEvent> {
+    // Ignore stale sessions to avoid flooding the HUD with historical rollouts.
+    let path = watched.tail.path().to_path_buf();
+    let Some(len) = file_len(&path).await else {
+        return Vec::new();
     };
-
-    let mut buf = Vec::new();
-    if let Err(e) = file.read_to_end(&mut buf).await {
-        debug!(path = %watched.path.display(), error = %e, "failed to read codex rollout for bootstrap");
-        return;
+    if !paths::modified_within(&path, VISIBILITY_WINDOW).await {
+        watched.tail.seek_to(len);
+        return Vec::new();
     }
-    watched.offset = buf.len() as u64;
 
     let mut scan_state =
         RolloutState::new(watched.state.session_id.clone(), watched.state.cwd.clone());
-    let mut replay: std::collections::VecDeque<AgentEvent> =
-        std::collections::VecDeque::with_capacity(BOOTSTRAP_REPLAY_MAX_EVENTS);
+    let mut replay = Replay::new(BOOTSTRAP_REPLAY_MAX_EVENTS);
     let mut latest_name: Option<String> = None;
-
-    let path = watched.path.clone();
-    watched.buffer = String::from_utf8_lossy(&buf).to_string();
-    drain_jsonl_lines(&mut watched.buffer, |line| {
-        let Some(value) = parse_json_line(
-            path.as_path(),
-            line,
-            "malformed JSON in codex rollout bootstrap",
-        ) else {
-            return;
-        };
-        for event in scan_state.apply_line(&value) {
-            match event {
-                AgentEvent::SessionStarted { .. } => {}
-                AgentEvent::SessionNameUpdated { name, .. } => latest_name = Some(name),
-                other => {
-                    if replay.len() == BOOTSTRAP_REPLAY_MAX_EVENTS {
-                        replay.pop_front();
-                    }
-                    replay.push_back(other);
+    let mut tags: Vec<String> = watched.profile.iter().cloned().collect();
+    let mut changes: Vec<FileChange> = Vec::new();
+
+    // Huge rollouts are scanned from a window at the end; a name or tags set
+    // before it are missed.
+    let resume_at = watched.tail.offset();
+    watched.tail.seek_near_end(len, BOOTSTRAP_WINDOW);
+    let scanned = watched
+        .tail
+        .read_new_async(|value| {
+            for event in scan_state.apply_line(value) {
+                match event {
+                    AgentEvent::SessionStarted { .. } => {}
+                    AgentEvent::SessionNameUpdated { name, .. } => latest_name = Some(name),
+                    AgentEvent::SessionTagged { tags: added, .. } => tags.extend(added),
+                    AgentEvent::FilesTouched { changes: more, .. } => changes.extend(more),
+                    other => replay.push(other),
                 }
             }
-        }
-    });
+        })
+        .await;
+    if let Err(e) = scanned {
+        debug!(
+            target: logging::CODEX_WATCHER,
+            path = %path.display(),
+            error = %e,
+            "failed to read codex rollout for bootstrap"
+        );
+        watched.tail.seek_to(resume_at);
+        return Vec::new();
+    }
 
-    // Emit bootstrap events (SessionStarted + latest SessionNameUpdated + last N events).
-    let mut out = Vec::with_capacity(2 + replay.len());
+    // Emit bootstrap events (SessionStarted + latest SessionNameUpdated + every
+    // SessionTagged tag + every file change + last N events).
+    let mut out = Vec::with_capacity(4 + replay.len());
     out.push(AgentEvent::SessionStarted {
         session_id: scan_state.session_id.clone(),
         cwd: scan_state.cwd.clone(),
         agent: AgentType::Codex,
+        terminal: None,
     });
     if let Some(name) = latest_name {
         out.push(AgentEvent::SessionNameUpdated {
@@ -199,75 +274,246 @@ async fn bootstrap_rollout(watched: &mut WatchedRollout, tx: &broadcast::Sender<
             name,
         });
     }
+    if !tags.is_empty() {
+        out.push(AgentEvent::SessionTagged {
+            session_id: scan_state.session_id.clone(),
+            tags,
+        });
+    }
+    if !changes.is_empty() {
+        out.push(AgentEvent::FilesTouched {
+            session_id: scan_state.session_id.clone(),
+            changes,
+        });
+    }
     out.extend(replay.into_iter());
-    emit_events(tx, out);
 
     watched.state = scan_state;
     watched.state.session_emitted = true;
+    watched.ignore.apply(out)
 }
 
-async fn tail_rollout(watched: &mut WatchedRollout, tx: &broadcast::Sender<AgentEvent>) {
-    loop {
-        let Some(len) = file_len(&watched.path).await else {
-            return;
-        };
+/// Register the recent rollouts under `root` (mtime within
+/// [`VISIBILITY_WINDOW`]), several at a time, bootstrapping them to seed the
+/// HUD. Stale rollouts are only stat'ed; they're discovered like new ones
+/// once they change.
+async fn bootstrap_all(
+    root: &Path,
+    options: &RolloutOptions,
+    tx: &broadcast::Sender<AgentEvent>,
+) -> HashMap<PathBuf, WatchedRollout> {
+    let started = Instant::now();
+    let dir = root.to_path_buf();
+    let found = tokio::task::spawn_blocking(move || paths::read_dir_recursive(&dir))
+        .await
+        .unwrap_or_default();
+
+    let limit = Arc::new(Semaphore::new(BOOTSTRAP_CONCURRENCY));
+    let mut tasks = JoinSet::new();
+    for path in found {
+        let (limit, options, tx) = (Arc::clone(&limit), options.clone(), tx.clone());
+        tasks.spawn(async move {
+            let _permit = limit.acquire_owned().await;
+            register_existing(path, &options, &tx).await
+        });
+    }
+
+    let scanned = tasks.len();
+    let mut watched = HashMap::new();
+    while let Some(result) = tasks.join_next().await {
+        match result {
+            Ok(Some(rollout)) => {
+                watched.insert(rollout.tail.path().to_path_buf(), rollout);
+            }
+            Ok(None) => {}
+            Err(e) => warn!(
+                target: logging::CODEX_WATCHER,
+                error = %e,
+                "codex rollout bootstrap task failed"
+            ),
+        }
+    }
 
-        if len < watched.offset {
-            debug!(path = %watched.path.display(), "codex rollout truncated; resetting cursor");
-            watched.offset = 0;
-            watched.buffer.clear();
-            watched.state.session_emitted = false;
+    let elapsed = started.elapsed();
+    crate::health::record_codex_bootstrap(scanned, elapsed);
+    info!(
+        target: logging::CODEX_WATCHER,
+        rollouts = scanned,
+        recent = watched.len(),
+        elapsed_ms = elapsed.as_millis() as u64,
+        "codex rollouts bootstrapped"
+    );
+    watched
+}
 
-            // A truncation is effectively a new rollout stream. Re-bootstrap immediately
-            // so we don't replay the full history and flood the HUD.
-            bootstrap_rollout(watched, tx).await;
+/// A rollout found at startup, bootstrapped if recent; `None` if stale
+async fn register_existing(
+    path: PathBuf,
+    options: &RolloutOptions,
+    tx: &broadcast::Sender<AgentEvent>,
+) -> Option<WatchedRollout> {
+    let metadata = tokio::fs::metadata(&path).await.ok()?;
+    if !paths::is_recent(&metadata, VISIBILITY_WINDOW) {
+        return None;
+    }
 
-            // Catch any bytes appended during the bootstrap scan.
-            continue;
+    let session_id = paths::session_id_from_path(&path);
+    let mut rollout =
+        WatchedRollout::new_existing(path, session_id, String::new(), 0).with_options(options);
+    rollout.load_meta().await;
+    bootstrap_rollout(&mut rollout, tx).await;
+    // Catch any bytes appended during bootstrap scan.
+    tail_rollout(&mut rollout, tx).await;
+    Some(rollout)
+}
+
+/// Stop tracking rollouts idle for longer than [`VISIBILITY_WINDOW`], then
+/// the least recently active ones beyond [`MAX_WATCHED`]; returns how many
+/// went. An evicted rollout that changes again is rediscovered (and
+/// bootstrapped) like a new one.
+fn evict_rollouts(watched: &mut HashMap<PathBuf, WatchedRollout>, now: Instant) -> usize {
+    let before = watched.len();
+    watched.retain(|_, rollout| now.duration_since(rollout.last_active) <= VISIBILITY_WINDOW);
+    if watched.len() > MAX_WATCHED {
+        let mut by_activity: Vec<(Instant, PathBuf)> = watched
+            .iter()
+            .map(|(path, rollout)| (rollout.last_active, path.clone()))
+            .collect();
+        by_activity.sort();
+        let excess = watched.len() - MAX_WATCHED;
+        warn!(
+            target: logging::CODEX_WATCHER,
+            excess,
+            "too many active codex rollouts; evicting the least recent"
+        );
+        for (_, path) in by_activity.into_iter().take(excess) {
+            watched.remove(&path);
         }
+    }
+    before - watched.len()
+}
 
-        if len == watched.offset {
+/// Bring a changed (or recently modified) rollout up to date: discover it if
+/// it isn't tracked, bootstrap its session once, then tail it
+async fn process_rollout(
+    watched: &mut HashMap<PathBuf, WatchedRollout>,
+    path: PathBuf,
+    options: &RolloutOptions,
+    tx: &broadcast::Sender<AgentEvent>,
+) {
+    if !watched.contains_key(&path) {
+        // Full rescans list every historical rollout; only recent ones count
+        if !paths::modified_within(&path, VISIBILITY_WINDOW).await {
             return;
         }
+        let mut session_id = paths::session_id_from_path(&path);
+        let mut cwd = String::new();
+        if let Some((meta_id, meta_cwd)) = read_first_session_meta(&path).await {
+            session_id = meta_id;
+            cwd = meta_cwd;
+        }
 
-        let start_offset = watched.offset;
-        let mut file = match tokio::fs::File::open(&watched.path).await {
-            Ok(f) => f,
-            Err(e) => {
-                debug!(path = %watched.path.display(), error = %e, "failed to open codex rollout");
-                return;
-            }
-        };
+        info!(
+            target: logging::CODEX_WATCHER,
+            path = %path.display(),
+            %session_id,
+            "discovered codex rollout"
+        );
+        watched.insert(
+            path.clone(),
+            WatchedRollout::new_fresh(path.clone(), session_id, cwd).with_options(options),
+        );
+    }
 
-        if let Err(e) = file.seek(std::io::SeekFrom::Start(watched.offset)).await {
-            debug!(path = %watched.path.display(), error = %e, "failed to seek codex rollout");
-            return;
-        }
+    let Some(w) = watched.get_mut(&path) else {
+        return;
+    };
+    w.last_active = Instant::now();
+    if !w.state.session_emitted {
+        bootstrap_rollout(w, tx).await;
+    }
+    if w.state.session_emitted {
+        tail_rollout(w, tx).await;
+        announce_path(w, tx);
+    } else {
+        // Keep the cursor pinned to EOF so we don't accidentally replay old rollouts.
+        let len = file_len(&path).await.unwrap_or(w.tail.offset());
+        w.tail.seek_to(len);
+    }
+}
 
-        let mut buf = Vec::new();
-        if let Err(e) = file.read_to_end(&mut buf).await {
-            debug!(path = %watched.path.display(), error = %e, "failed to read codex rollout");
-            return;
+/// Replay the latest state of every recent rollout after a receiver lagged.
+///
+/// `SessionStarted` is left out so sessions the user archived stay archived;
+/// the replayed events re-register any session that was missed entirely.
+async fn resync_rollouts(
+    watched: &mut HashMap<PathBuf, WatchedRollout>,
+    tx: &broadcast::Sender<AgentEvent>,
+) {
+    let mut resynced = 0usize;
+    for rollout in watched.values_mut() {
+        if !rollout.state.session_emitted
+            || !paths::modified_within(rollout.tail.path(), VISIBILITY_WINDOW).await
+        {
+            continue;
         }
-        watched.offset = start_offset + buf.len() as u64;
-
-        watched.buffer.push_str(&String::from_utf8_lossy(&buf));
+        let events: Vec<AgentEvent> = scan_rollout(rollout)
+            .await
+            .into_iter()
+            .filter(|event| !matches!(event, AgentEvent::SessionStarted { .. }))
+            .collect();
+        emit_events(tx, events);
+        // Catch any bytes appended during the scan.
+        tail_rollout(rollout, tx).await;
+        rollout.path_announced = false;
+        announce_path(rollout, tx);
+        resynced += 1;
+    }
+    info!(target: logging::CODEX_WATCHER, resynced, "codex rollouts resynced");
+}
 
-        // Process complete JSONL lines, leaving any partial line in `buffer`.
-        let path = watched.path.clone();
+async fn tail_rollout(watched: &mut WatchedRollout, tx: &broadcast::Sender<AgentEvent>) {
+    loop {
+        let path = watched.tail.path().to_path_buf();
         let state = &mut watched.state;
-        let buffer = &mut watched.buffer;
-        drain_jsonl_lines(buffer, |line| {
-            let Some(value) =
-                parse_json_line(path.as_path(), line, "malformed JSON in codex rollout")
-            else {
-                return;
-            };
-            let events = state.apply_line(&value);
-            emit_events(tx, events);
-        });
+        let recorder = &mut watched.recorder;
+        let ignore = &mut watched.ignore;
+        let progress = watched
+            .tail
+            .read_new_async(|value| {
+                let events = ignore.apply(state.apply_line(value));
+                if !ignore.is_opted_out(&state.session_id) {
+                    recorder.rollout(&path, &state.session_id, &state.cwd, value);
+                }
+                emit_events(tx, events);
+            })
+            .await;
+        match progress {
+            Ok(Progress::Truncated) => {
+                debug!(
+                    target: logging::CODEX_WATCHER,
+                    path = %watched.tail.path().display(),
+                    "codex rollout truncated; resetting cursor"
+                );
+                watched.state.session_emitted = false;
 
-        return;
+                // A truncation is effectively a new rollout stream. Re-bootstrap immediately
+                // so we don't replay the full history and flood the HUD.
+                bootstrap_rollout(watched, tx).await;
+
+                // Catch any bytes appended during the bootstrap scan.
+            }
+            Ok(Progress::Unchanged | Progress::Appended) => return,
+            Err(e) => {
+                debug!(
+                    target: logging::CODEX_WATCHER,
+                    path = %watched.tail.path().display(),
+                    error = %e,
+                    "failed to read codex rollout"
+                );
+                return;
+            }
+        }
     }
 }
 
@@ -309,25 +555,75 @@ impl DirtyRollouts {
     }
 }
 
-async fn run(tx: broadcast::Sender<AgentEvent>) {
-    let codex_paths = paths::CodexPaths::detect();
-    let home = codex_paths.home;
-    let root = codex_paths.sessions_root;
-    let root_alt = codex_paths.sessions_root_alt;
+async fn run(
+    home: PathBuf,
+    profile: Option<String>,
+    tx: broadcast::Sender<AgentEvent>,
+    lag: Arc<StreamLag>,
+) {
+    let options = RolloutOptions {
+        ignore: crate::config::load_config().ignore,
+        profile,
+    };
+    let health = crate::health::watcher_started();
+
+    // Codex may be installed, or its home removed and restored, while Aura runs
+    loop {
+        let codex_paths = wait_for_codex_home(home.clone()).await;
+        if !watch_codex_home(codex_paths, &options, &tx, &lag, &health).await {
+            return;
+        }
+        info!(target: logging::CODEX_WATCHER, "codex home removed; waiting for it to return");
+        health.set(crate::health::WatcherState::WaitingForCodex);
+    }
+}
+
+/// Codex's paths once its home exists, polling every [`CODEX_HOME_POLL`]
+async fn wait_for_codex_home(home: PathBuf) -> paths::CodexPaths {
+    let mut codex_paths = paths::CodexPaths::resolve(home);
+    if !codex_paths.home.exists() {
+        let home = &codex_paths.home;
+        info!(target: logging::CODEX_WATCHER, path = %home.display(), "waiting for codex home");
+        while !home.exists() {
+            tokio::time::sleep(CODEX_HOME_POLL).await;
+        }
+        // It may be a symlink, only resolvable now
+        codex_paths.refresh();
+    }
+    codex_paths
+}
 
-    info!(path = %root.display(), "watching codex sessions");
+/// Watch and tail the rollouts under an existing Codex home, attaching to
+/// `sessions/` whenever it (re)appears. Returns true once the home itself is
+/// removed, false if watching failed.
+async fn watch_codex_home(
+    codex_paths: paths::CodexPaths,
+    options: &RolloutOptions,
+    tx: &broadcast::Sender<AgentEvent>,
+    lag: &StreamLag,
+    health: &crate::health::WatcherGuard,
+) -> bool {
+    let home = codex_paths.home.clone();
+    let mut root = codex_paths.sessions_root.clone();
+    // Shared with the notify callback, which maps event paths onto `root`
+    let codex_paths = Arc::new(Mutex::new(codex_paths));
+
+    info!(target: logging::CODEX_WATCHER, path = %root.display(), "watching codex sessions");
 
     let dirty = Arc::new(DirtyRollouts::default());
 
     let dirty_cb = Arc::clone(&dirty);
-    let sessions_root = root.clone();
-    let sessions_root_alt = root_alt.clone();
+    let event_paths = Arc::clone(&codex_paths);
     let mut watcher =
         match notify::recommended_watcher(move |res: Result<notify::Event, notify::Error>| {
             let event = match res {
                 Ok(e) => e,
                 Err(err) => {
-                    debug!(error = %err, "codex file watcher error");
+                    debug!(
+                        target: logging::CODEX_WATCHER,
+                        error = %err,
+                        "codex file watcher error"
+                    );
                     dirty_cb.mark_rescan();
                     return;
                 }
@@ -347,9 +643,14 @@ async fn run(tx: broadcast::Sender<AgentEvent>) {
             }
 
             for path in event.paths {
-                if !path.starts_with(&sessions_root) && !path.starts_with(&sessions_root_alt) {
+                // Backends report symlinks resolved (FSEvents) or as watched
+                let normalized = event_paths
+                    .lock()
+                    .ok()
+                    .and_then(|paths| paths.normalize(&path));
+                let Some(path) = normalized else {
                     continue;
-                }
+                };
                 if paths::is_jsonl(&path) {
                     dirty_cb.mark(path);
                 } else {
@@ -360,8 +661,12 @@ async fn run(tx: broadcast::Sender<AgentEvent>) {
         }) {
             Ok(w) => w,
             Err(e) => {
-                warn!(error = %e, "failed to initialize codex file watcher");
-                return;
+                warn!(
+                    target: logging::CODEX_WATCHER,
+                    error = %e,
+                    "failed to initialize codex file watcher"
+                );
+                return false;
             }
         };
 
@@ -375,13 +680,19 @@ async fn run(tx: broadcast::Sender<AgentEvent>) {
             Ok(()) => sessions_watched = true,
             Err(e) => {
                 warn!(
+                    target: logging::CODEX_WATCHER,
                     path = %root.display(),
                     error = %e,
                     "failed to watch codex sessions; falling back to codex home"
                 );
                 if let Err(e) = watcher.watch(&home, RecursiveMode::Recursive) {
-                    warn!(path = %home.display(), error = %e, "failed to watch codex home");
-                    return;
+                    warn!(
+                        target: logging::CODEX_WATCHER,
+                        path = %home.display(),
+                        error = %e,
+                        "failed to watch codex home"
+                    );
+                    return false;
                 }
                 home_watched = true;
             }
@@ -389,62 +700,110 @@ async fn run(tx: broadcast::Sender<AgentEvent>) {
     } else {
         // Watch Codex home (non-recursive) to detect `sessions/` creation.
         if let Err(e) = watcher.watch(&home, RecursiveMode::NonRecursive) {
-            warn!(path = %home.display(), error = %e, "failed to watch codex home");
-            return;
+            warn!(
+                target: logging::CODEX_WATCHER,
+                path = %home.display(),
+                error = %e,
+                "failed to watch codex home"
+            );
+            return false;
         }
         home_watched = true;
     }
+    health.set(crate::health::WatcherState::Watching);
 
     // Bootstrap: register all existing rollouts. For "recent" rollouts (mtime <= 10m),
     // emit a bounded replay to seed the HUD.
-    let mut watched: HashMap<PathBuf, WatchedRollout> = HashMap::new();
-    if root.exists() {
-        for path in paths::read_dir_recursive(&root) {
-            let mut session_id = paths::session_id_from_path(&path);
-            let mut cwd = String::new();
-            if let Some((meta_id, meta_cwd)) = read_first_session_meta(&path).await {
-                session_id = meta_id;
-                cwd = meta_cwd;
-            }
-
-            let mut rollout = WatchedRollout::new_existing(path.clone(), session_id, cwd, 0);
-            if paths::modified_within(&path, VISIBILITY_WINDOW).await {
-                bootstrap_rollout(&mut rollout, &tx).await;
-                // Catch any bytes appended during bootstrap scan.
-                tail_rollout(&mut rollout, &tx).await;
-            } else {
-                rollout.offset = file_len(&path).await.unwrap_or(0);
-            }
-
-            watched.insert(path.clone(), rollout);
-        }
-        debug!("codex rollouts registered: {}", watched.len());
-    }
+    let mut watched = if root.exists() {
+        bootstrap_all(&root, options, tx).await
+    } else {
+        HashMap::new()
+    };
 
-    let mut scan_tick = tokio::time::interval(FALLBACK_SCAN_INTERVAL);
-    scan_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    let mut pacing = ScanPacing::new(FALLBACK_SCAN_INTERVAL);
+    let mut next_scan = tokio::time::Instant::now() + pacing.interval();
+    let mut evicted_at = Instant::now();
+    let mut missed = MissedEvents::default();
 
     loop {
         let mut ticked = false;
         tokio::select! {
             biased;
-            _ = scan_tick.tick() => ticked = true,
+            _ = tokio::time::sleep_until(next_scan) => ticked = true,
             _ = dirty.notify.notified() => {},
+            _ = lag.resync_requested() => {},
+        }
+
+        if lag.take_resync() {
+            resync_rollouts(&mut watched, tx).await;
+        }
+
+        if ticked && evicted_at.elapsed() >= EVICT_INTERVAL {
+            evicted_at = Instant::now();
+            let evicted = evict_rollouts(&mut watched, evicted_at);
+            if evicted > 0 {
+                debug!(
+                    target: logging::CODEX_WATCHER,
+                    evicted,
+                    tracked = watched.len(),
+                    "codex rollouts evicted"
+                );
+            }
+        }
+
+        if ticked && !home.exists() {
+            return true;
+        }
+        if ticked && sessions_watched && !root.exists() {
+            // Deleted (or unmounted): watch the home for it to come back
+            info!(target: logging::CODEX_WATCHER, path = %root.display(), "codex sessions removed");
+            let _ = watcher.unwatch(&root);
+            sessions_watched = false;
+            watched.clear();
+            if !home_watched {
+                match watcher.watch(&home, RecursiveMode::NonRecursive) {
+                    Ok(()) => home_watched = true,
+                    Err(e) => warn!(
+                        target: logging::CODEX_WATCHER,
+                        path = %home.display(),
+                        error = %e,
+                        "failed to watch codex home"
+                    ),
+                }
+            }
         }
 
         let (rescan, mut paths) = dirty.drain();
+        let mut unreported = 0;
         if ticked && root.exists() {
-            paths.extend(paths::scan_recent_rollouts(&root, VISIBILITY_WINDOW).await);
+            let scanned = paths::scan_recent_rollouts(&root, VISIBILITY_WINDOW).await;
+            unreported = unreported_changes(&watched, &paths, &scanned).await;
+            if missed.record(unreported) {
+                warn!(
+                    target: logging::CODEX_WATCHER,
+                    path = %root.display(),
+                    interval = ?FAST_SCAN_INTERVAL,
+                    "codex file watcher is missing events; polling faster"
+                );
+                pacing.set_base(FAST_SCAN_INTERVAL);
+            }
+            paths.extend(scanned);
         }
 
         if rescan || ticked {
             if !sessions_watched && root.exists() {
+                // `sessions/` may have appeared as a symlink
+                if let Ok(mut paths) = codex_paths.lock() {
+                    paths.refresh();
+                    root = paths.sessions_root.clone();
+                }
                 match watcher.watch(&root, RecursiveMode::Recursive) {
                     Ok(()) => {
                         sessions_watched = true;
                         if home_watched {
                             if let Err(e) = watcher.unwatch(&home) {
                                 warn!(
+                                    target: logging::CODEX_WATCHER,
                                     path = %home.display(),
                                     error = %e,
                                     "failed to unwatch codex home"
@@ -455,6 +814,7 @@ async fn run(tx: broadcast::Sender<AgentEvent>) {
                         }
                     }
                     Err(e) => warn!(
+                        target: logging::CODEX_WATCHER,
                         path = %root.display(),
                         error = %e,
                         "failed to watch codex sessions"
@@ -471,46 +831,134 @@ async fn run(tx: broadcast::Sender<AgentEvent>) {
         paths.sort();
         paths.dedup();
 
-        if paths.is_empty() {
-            continue;
+        for path in paths {
+            process_rollout(&mut watched, path, options, tx).await;
         }
 
-        for path in paths {
-            if !watched.contains_key(&path) {
-                let mut session_id = paths::session_id_from_path(&path);
-                let mut cwd = String::new();
-                if let Some((meta_id, meta_cwd)) = read_first_session_meta(&path).await {
-                    session_id = meta_id;
-                    cwd = meta_cwd;
-                }
+        let busy = watched.values().any(|rollout| rollout.state.in_turn());
+        if ticked {
+            next_scan = tokio::time::Instant::now() + pacing.after_scan(busy || unreported > 0);
+        } else if busy {
+            // A turn started since the last scan: stop backing off
+            next_scan = next_scan.min(tokio::time::Instant::now() + pacing.after_scan(true));
+        }
+    }
+}
 
-                info!(path = %path.display(), %session_id, "discovered new codex rollout");
-                watched.insert(
-                    path.clone(),
-                    WatchedRollout::new_fresh(path.clone(), session_id, cwd),
-                );
-            }
+/// Fallback scan interval: the base one ([`FALLBACK_SCAN_INTERVAL`], or
+/// [`FAST_SCAN_INTERVAL`] once notify is known to miss events) while a Codex
+/// turn runs, doubling with each quiet scan up to [`IDLE_SCAN_INTERVAL_MAX`]
+/// while everything is idle. File events wake the watcher regardless.
+#[derive(Debug)]
+struct ScanPacing {
+    base: Duration,
+    interval: Duration,
+}
 
-            if let Some(w) = watched.get_mut(&path) {
-                if !w.state.session_emitted {
-                    bootstrap_rollout(w, &tx).await;
-                }
-                if w.state.session_emitted {
-                    tail_rollout(w, &tx).await;
-                } else {
-                    // Keep the cursor pinned to EOF so we don't accidentally replay old rollouts.
-                    w.offset = file_len(&path).await.unwrap_or(w.offset);
-                }
-            }
+impl ScanPacing {
+    fn new(base: Duration) -> Self {
+        Self {
+            base,
+            interval: base,
+        }
+    }
+
+    fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Poll at `base` from now on
+    fn set_base(&mut self, base: Duration) {
+        self.base = base;
+        self.interval = base;
+    }
+
+    /// Interval until the next scan; `busy` when a turn runs or the last
+    /// scan found changes
+    fn after_scan(&mut self, busy: bool) -> Duration {
+        self.interval = if busy {
+            self.base
+        } else {
+            (self.interval * 2).min(IDLE_SCAN_INTERVAL_MAX.max(self.base))
+        };
+        self.interval
+    }
+}
+
+/// Whether the notify backend keeps missing rollout changes, as happens on
+/// network volumes and with some symlinked homes
+#[derive(Debug, Default)]
+struct MissedEvents {
+    /// Consecutive fallback scans with unreported changes
+    ticks: u32,
+    escalated: bool,
+}
+
+impl MissedEvents {
+    /// Note one fallback scan; true when polling should speed up (once)
+    fn record(&mut self, unreported: usize) -> bool {
+        self.ticks = if unreported > 0 { self.ticks + 1 } else { 0 };
+        if self.escalated || self.ticks < MISSED_TICKS_BEFORE_FAST_SCAN {
+            return false;
         }
+        self.escalated = true;
+        true
     }
 }
 
-/// Spawn the Codex session rollout watcher.
-pub fn spawn(tx: broadcast::Sender<AgentEvent>) {
-    tokio::spawn(async move {
-        run(tx).await;
-    });
+/// How many of the fallback scan's rollouts grew (or appeared) without
+/// notify marking them dirty
+async fn unreported_changes(
+    watched: &HashMap<PathBuf, WatchedRollout>,
+    notified: &[PathBuf],
+    scanned: &[PathBuf],
+) -> usize {
+    let mut unreported = 0;
+    for path in scanned.iter().filter(|path| !notified.contains(path)) {
+        let changed = match watched.get(path) {
+            Some(rollout) => file_len(path)
+                .await
+                .is_some_and(|len| len > rollout.tail.offset()),
+            None => true,
+        };
+        unreported += usize::from(changed);
+    }
+    unreported
+}
+
+/// Tell the registry where the rollout lives, once its session is registered.
+fn announce_path(watched: &mut WatchedRollout, tx: &broadcast::Sender<AgentEvent>) {
+    if watched.path_announced || !watched.state.session_emitted {
+        return;
+    }
+    watched.path_announced = true;
+    emit_events(
+        tx,
+        vec![AgentEvent::TranscriptPath {
+            session_id: watched.state.session_id.clone(),
+            path: watched.tail.path().display().to_string(),
+        }],
+    );
+}
+
+/// Spawn a Codex session rollout watcher per home in `homes` (else
+/// `CODEX_HOME` or `~/.codex`); lagging receivers in `lag` make them resync.
+pub fn spawn(homes: &[AgentHome], tx: broadcast::Sender<AgentEvent>, lag: Arc<StreamLag>) {
+    let homes: Vec<(PathBuf, Option<String>)> = if homes.is_empty() {
+        vec![(paths::default_home(), None)]
+    } else {
+        homes
+            .iter()
+            .map(|home| (home.dir(), home.profile.clone()))
+            .collect()
+    };
+    for (home, profile) in homes {
+        let (tx, lag) = (tx.clone(), Arc::clone(&lag));
+        // A restart bootstraps again, replaying the latest state of recent rollouts
+        crate::supervisor::spawn("codex watcher", move || {
+            run(home.clone(), profile.clone(), tx.clone(), Arc::clone(&lag))
+        });
+    }
 }
 
 #[cfg(test)]
@@ -603,6 +1051,7 @@ mod tests {
                 session_id,
                 cwd,
                 agent,
+                ..
             } => {
                 assert_eq!(session_id, "sess_1");
                 assert_eq!(cwd, "/tmp/project");
@@ -659,7 +1108,7 @@ mod tests {
         assert_eq!(watched.state.cwd, "/tmp/project");
 
         let len = std::fs::metadata(&path).unwrap().len();
-        assert_eq!(watched.offset, len);
+        assert_eq!(watched.tail.offset(), len);
     }
 
     #[tokio::test]
@@ -688,8 +1137,115 @@ mod tests {
         assert!(!watched.state.session_emitted);
 
         let len = std::fs::metadata(&path).unwrap().len();
-        assert_eq!(watched.offset, len);
-        assert!(watched.buffer.is_empty());
+        assert_eq!(watched.tail.offset(), len);
+        assert_eq!(watched.tail.pending(), 0);
+    }
+
+    #[tokio::test]
+    async fn bootstrap_all_replays_recent_rollouts_and_skips_stale_ones() {
+        let tmp = TempDir::new().unwrap();
+        let old = FileTime::from_system_time(
+            SystemTime::now() - (VISIBILITY_WINDOW + Duration::from_secs(60)),
+        );
+        let rollout = |day: usize, i: usize| {
+            let dir = tmp.path().join(format!("2026/09/{:02}", day % 28 + 1));
+            std::fs::create_dir_all(&dir).unwrap();
+            let path = dir.join(format!(
+                "rollout-2026-09-01-{i:08}-0000-0000-0000-000000000000.jsonl"
+            ));
+            write_jsonl(
+                &path,
+                &[
+                    json!({
+                        "type": "session_meta",
+                        "payload": { "id": format!("sess_{i}"), "cwd": format!("/code/p{i}") }
+                    }),
+                    json!({ "type": "event_msg", "payload": { "type": "task_started" } }),
+                ],
+            );
+            path
+        };
+        let stale: Vec<PathBuf> = (0..2000).map(|i| rollout(i, i)).collect();
+        for path in &stale {
+            set_file_mtime(path, old).unwrap();
+        }
+        let recent: Vec<PathBuf> = (2000..2003).map(|i| rollout(0, i)).collect();
+
+        let (tx, mut rx) = broadcast::channel(64);
+        let mut watched = bootstrap_all(tmp.path(), &RolloutOptions::default(), &tx).await;
+        assert_eq!(watched.len(), 3);
+
+        let mut started: Vec<String> = drain_rx(&mut rx)
+            .into_iter()
+            .filter_map(|event| match event {
+                AgentEvent::SessionStarted { session_id, .. } => Some(session_id),
+                _ => None,
+            })
+            .collect();
+        started.sort();
+        assert_eq!(started, vec!["sess_2000", "sess_2001", "sess_2002"]);
+        for path in &recent {
+            assert!(watched[path].state.session_emitted);
+        }
+
+        // A stale rollout that changes is discovered like a new one
+        write_jsonl(
+            &stale[7],
+            &[
+                json!({
+                    "type": "session_meta",
+                    "payload": { "id": "sess_7", "cwd": "/code/p7" }
+                }),
+                json!({ "type": "event_msg", "payload": { "type": "task_started" } }),
+                json!({ "type": "event_msg", "payload": { "type": "task_complete" } }),
+            ],
+        );
+        process_rollout(
+            &mut watched,
+            stale[7].clone(),
+            &RolloutOptions::default(),
+            &tx,
+        )
+        .await;
+        let rediscovered = &watched[&stale[7]];
+        assert_eq!(rediscovered.state.session_id, "sess_7");
+        assert_eq!(rediscovered.state.cwd, "/code/p7");
+        assert!(rediscovered.state.session_emitted);
+
+        // An untouched one is still skipped, e.g. by a full rescan
+        process_rollout(
+            &mut watched,
+            stale[8].clone(),
+            &RolloutOptions::default(),
+            &tx,
+        )
+        .await;
+        assert_eq!(watched.len(), 4);
+    }
+
+    #[test]
+    fn eviction_drops_idle_rollouts_then_the_least_recent_over_the_cap() {
+        let now = Instant::now();
+        let rollout = |i: usize, idle: Duration| {
+            let path = PathBuf::from(format!("/tmp/rollout-{i}.jsonl"));
+            let mut rollout =
+                WatchedRollout::new_fresh(path.clone(), format!("sess_{i}"), String::new());
+            rollout.last_active = now - idle;
+            (path, rollout)
+        };
+        let mut watched: HashMap<PathBuf, WatchedRollout> = HashMap::new();
+        watched.extend([rollout(0, VISIBILITY_WINDOW + Duration::from_secs(1))]);
+        watched.extend((1..=MAX_WATCHED + 2).map(|i| rollout(i, Duration::from_secs(i as u64))));
+
+        assert_eq!(evict_rollouts(&mut watched, now), 3);
+        assert_eq!(watched.len(), MAX_WATCHED);
+        // The idle one and the two least recently active went
+        for i in [0, MAX_WATCHED + 1, MAX_WATCHED + 2] {
+            assert!(!watched.contains_key(Path::new(&format!("/tmp/rollout-{i}.jsonl"))));
+        }
+        assert!(watched.contains_key(Path::new("/tmp/rollout-1.jsonl")));
+
+        assert_eq!(evict_rollouts(&mut watched, now), 0);
     }
 
     #[tokio::test]
@@ -744,6 +1300,7 @@ mod tests {
                 session_id,
                 cwd,
                 agent,
+                ..
             } => {
                 assert_eq!(session_id, "sess_1");
                 assert_eq!(cwd, "/tmp/project");
@@ -832,6 +1389,7 @@ mod tests {
                     session_id,
                     cwd,
                     agent,
+                    ..
                 },
                 AgentEvent::SessionNameUpdated {
                     session_id: s2,
@@ -848,7 +1406,225 @@ mod tests {
         }
 
         // Bounded replay tail stays small (we should not replay the entire file).
-        assert_eq!(watched.offset, file_len);
+        assert_eq!(watched.tail.offset(), file_len);
         assert!(watched.state.session_emitted);
     }
+
+    #[tokio::test]
+    async fn resync_replays_latest_state_without_session_started() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("rollout-2026-02-14-sess_1.jsonl");
+        write_jsonl(
+            &path,
+            &[
+                json!({
+                    "type": "session_meta",
+                    "payload": { "id": "sess_1", "cwd": "/tmp/project" }
+                }),
+                json!({ "type": "event_msg", "payload": { "type": "task_started" } }),
+                json!({ "type": "event_msg", "payload": { "type": "task_complete" } }),
+            ],
+        );
+
+        let (tx, mut rx) = broadcast::channel(32);
+        let mut rollout =
+            WatchedRollout::new_existing(path.clone(), "fallback".to_string(), "".to_string(), 0);
+        bootstrap_rollout(&mut rollout, &tx).await;
+        drain_rx(&mut rx);
+
+        let mut watched = HashMap::from([(path.clone(), rollout)]);
+        resync_rollouts(&mut watched, &tx).await;
+
+        let events = drain_rx(&mut rx);
+        assert!(
+            !events
+                .iter()
+                .any(|e| matches!(e, AgentEvent::SessionStarted { .. }))
+        );
+        assert!(events.iter().any(|e| matches!(e, AgentEvent::Idle { .. })));
+        assert!(matches!(
+            events.last(),
+            Some(AgentEvent::TranscriptPath { .. })
+        ));
+        assert_eq!(
+            watched[&path].tail.offset(),
+            std::fs::metadata(&path).unwrap().len()
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_home_and_sessions_map_onto_one_root() {
+        use std::os::unix::fs::symlink;
+
+        let tmp = TempDir::new().unwrap();
+        let volume = tmp.path().join("Volume/sessions");
+        std::fs::create_dir_all(&volume).unwrap();
+        let real_home = tmp.path().join("codex-real");
+        std::fs::create_dir_all(&real_home).unwrap();
+        symlink(&volume, real_home.join("sessions")).unwrap();
+        let home = tmp.path().join(".codex");
+        symlink(&real_home, &home).unwrap();
+
+        let codex_paths = paths::CodexPaths::resolve(home.clone());
+        let root = std::fs::canonicalize(&volume).unwrap();
+        assert_eq!(codex_paths.sessions_root, root);
+
+        let rollout = root.join("2026/09/01/rollout.jsonl");
+        for reported in [
+            home.join("sessions/2026/09/01/rollout.jsonl"),
+            real_home.join("sessions/2026/09/01/rollout.jsonl"),
+            rollout.clone(),
+        ] {
+            assert_eq!(codex_paths.normalize(&reported).as_ref(), Some(&rollout));
+        }
+        // Case-insensitive volumes may report another casing
+        let upper = PathBuf::from(root.display().to_string().replace("Volume", "VOLUME"));
+        assert_eq!(
+            codex_paths.normalize(&upper.join("2026/09/01/rollout.jsonl")),
+            Some(rollout)
+        );
+        assert_eq!(codex_paths.normalize(&real_home.join("config.toml")), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn read_dir_recursive_follows_symlinked_dirs_once() {
+        use std::os::unix::fs::symlink;
+
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().join("sessions");
+        let day = root.join("2026/09/01");
+        std::fs::create_dir_all(&day).unwrap();
+        std::fs::write(day.join("a.jsonl"), "").unwrap();
+        let elsewhere = tmp.path().join("archive");
+        std::fs::create_dir_all(&elsewhere).unwrap();
+        std::fs::write(elsewhere.join("b.jsonl"), "").unwrap();
+        symlink(&elsewhere, root.join("2026/10")).unwrap();
+        symlink(elsewhere.join("b.jsonl"), day.join("c.jsonl")).unwrap();
+        // A cycle back to the root is read once
+        symlink(&root, root.join("2026/loop")).unwrap();
+
+        let mut found = paths::read_dir_recursive(&root);
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                day.join("a.jsonl"),
+                day.join("c.jsonl"),
+                root.join("2026/10/b.jsonl"),
+            ]
+        );
+    }
+
+    #[test]
+    fn polling_speeds_up_after_consecutive_missed_events() {
+        let mut missed = MissedEvents::default();
+        assert!(!missed.record(1));
+        assert!(!missed.record(2));
+        // A scan notify fully covered starts over
+        assert!(!missed.record(0));
+        assert!(!missed.record(1));
+        assert!(!missed.record(1));
+        assert!(missed.record(1));
+        // Only once
+        assert!(!missed.record(1));
+    }
+
+    #[test]
+    fn scans_back_off_while_idle() {
+        let mut pacing = ScanPacing::new(FALLBACK_SCAN_INTERVAL);
+        let quiet: Vec<_> = (0..5).map(|_| pacing.after_scan(false)).collect();
+        let secs = |s| Duration::from_secs(s);
+        assert_eq!(quiet, vec![secs(4), secs(8), secs(16), secs(16), secs(16)]);
+        // A running turn (or changes found) goes back to the base interval
+        assert_eq!(pacing.after_scan(true), FALLBACK_SCAN_INTERVAL);
+
+        pacing.set_base(FAST_SCAN_INTERVAL);
+        assert_eq!(pacing.interval(), FAST_SCAN_INTERVAL);
+        assert_eq!(pacing.after_scan(false), Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn watcher_attaches_to_sessions_created_later_and_returns_when_home_goes() {
+        let tmp = TempDir::new().unwrap();
+        let home = tmp.path().join("codex");
+        std::fs::create_dir_all(&home).unwrap();
+        let (tx, mut rx) = broadcast::channel(64);
+        let lag = StreamLag::default();
+        let health = crate::health::watcher_started();
+        let watching = watch_codex_home(
+            paths::CodexPaths::resolve(home.clone()),
+            &RolloutOptions::default(),
+            &tx,
+            &lag,
+            &health,
+        );
+        tokio::pin!(watching);
+
+        // Codex creates `sessions/` after the watcher started
+        let day = home.join("sessions/2026/10/14");
+        let created = async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            std::fs::create_dir_all(&day).unwrap();
+            write_jsonl(
+                &day.join("rollout-2026-10-14-sess_late.jsonl"),
+                &[
+                    json!({
+                        "type": "session_meta",
+                        "payload": { "id": "sess_late", "cwd": "/code/late" }
+                    }),
+                    json!({ "type": "event_msg", "payload": { "type": "task_started" } }),
+                ],
+            );
+            loop {
+                match rx.recv().await {
+                    Ok(AgentEvent::SessionStarted { session_id, .. }) => break session_id,
+                    Ok(_) => {}
+                    Err(e) => panic!("{e}"),
+                }
+            }
+        };
+        let started = tokio::select! {
+            _ = &mut watching => panic!("watcher returned early"),
+            started = tokio::time::timeout(Duration::from_secs(10), created) => started,
+        };
+        assert_eq!(started.unwrap(), "sess_late");
+
+        std::fs::remove_dir_all(&home).unwrap();
+        let removed = tokio::time::timeout(Duration::from_secs(10), watching).await;
+        assert_eq!(removed, Ok(true));
+    }
+
+    #[tokio::test]
+    async fn sessions_are_tagged_with_the_home_profile() {
+        let tmp = TempDir::new().unwrap();
+        let day = tmp.path().join("2026/10/14");
+        std::fs::create_dir_all(&day).unwrap();
+        write_jsonl(
+            &day.join("rollout-2026-10-14-sess_work.jsonl"),
+            &[json!({
+                "type": "session_meta",
+                "payload": { "id": "sess_work", "cwd": "/code/api" }
+            })],
+        );
+
+        let (tx, mut rx) = broadcast::channel(64);
+        let options = RolloutOptions {
+            profile: Some("work".into()),
+            ..RolloutOptions::default()
+        };
+        bootstrap_all(tmp.path(), &options, &tx).await;
+        let tags: Vec<Vec<String>> = drain_rx(&mut rx)
+            .into_iter()
+            .filter_map(|event| match event {
+                AgentEvent::SessionTagged { session_id, tags } => {
+                    assert_eq!(session_id, "sess_work");
+                    Some(tags)
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(tags, vec![vec!["work".to_string()]]);
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{AgentEvent, AgentType};
+use crate::{AgentEvent, AgentType, FileChange, PlanProgress};
 use serde_json::Value;
 use tracing::warn;
 
@@ -40,17 +40,70 @@ fn tool_label_from_args(tool_name: &str, args_json: &Value) -> Option<String> {
     }
 }
 
+/// The path or command [`tool_label_from_args`] shortens
+fn full_value_from_args(args_json: &Value) -> Option<&str> {
+    json_string_field(args_json, &["path", "file_path", "filePath"])
+        .or_else(|| json_string_field(args_json, &["cmd", "command"]))
+}
+
+/// The command an `exec_approval_request` / `exec_command_begin` runs: the
+/// script for `bash -lc <script>`, else the argv joined
+fn approval_command(payload: &Value) -> Option<String> {
+    let command = payload.get("command")?;
+    if let Some(command) = command.as_str() {
+        return Some(command.trim().to_string()).filter(|c| !c.is_empty());
+    }
+    let argv: Vec<&str> = command
+        .as_array()?
+        .iter()
+        .filter_map(Value::as_str)
+        .collect();
+    let command = match argv.as_slice() {
+        [shell, "-c" | "-lc", script] if matches!(*shell, "bash" | "sh" | "zsh") => {
+            script.trim().to_string()
+        }
+        _ => argv.join(" "),
+    };
+    Some(command).filter(|c| !c.is_empty())
+}
+
+/// Progress of an `update_plan` call's arguments or a `plan_update` event:
+/// `{"plan": [{"step": "...", "status": "in_progress"}, ...]}`
+fn plan_progress(value: &Value) -> Option<PlanProgress> {
+    let steps = value.get("plan")?.as_array()?;
+    Some(PlanProgress::from_steps(steps.iter().map(|item| {
+        (
+            json_string_field(item, &["status"]).unwrap_or("pending"),
+            json_string_field(item, &["step"]).unwrap_or(""),
+        )
+    })))
+}
+
 fn parse_json_string(value: &Value) -> Option<Value> {
     let s = value.as_str()?;
     serde_json::from_str(s).ok()
 }
 
+/// A tool call waiting on the user's approval
+#[derive(Debug, Clone)]
+struct Approval {
+    call_id: String,
+    tool_name: String,
+    tool_label: Option<String>,
+    tool_full_label: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub(super) struct RolloutState {
     pub(super) session_id: String,
     pub(super) cwd: String,
     pub(super) session_emitted: bool,
     web_search_seq: u64,
+    approval: Option<Approval>,
+    /// Model from the latest `turn_context`
+    model: Option<String>,
+    /// A turn is under way: the latest state event was activity or a tool
+    in_turn: bool,
 }
 
 impl RolloutState {
@@ -60,9 +113,18 @@ impl RolloutState {
             cwd,
             session_emitted: false,
             web_search_seq: 0,
+            approval: None,
+            model: None,
+            in_turn: false,
         }
     }
 
+    /// Whether the agent is working on a turn (rather than idle, waiting or
+    /// asking for approval), as of the last line applied
+    pub(super) fn in_turn(&self) -> bool {
+        self.in_turn
+    }
+
     pub(super) fn ensure_session_event(&mut self) -> Option<AgentEvent> {
         if self.session_emitted {
             return None;
@@ -72,6 +134,7 @@ impl RolloutState {
             session_id: self.session_id.clone(),
             cwd: self.cwd.clone(),
             agent: AgentType::Codex,
+            terminal: None,
         })
     }
 
@@ -85,6 +148,7 @@ impl RolloutState {
             session_id: self.session_id.clone(),
             cwd: self.cwd.clone(),
             agent: AgentType::Codex,
+            terminal: None,
         })
     }
 
@@ -107,6 +171,7 @@ impl RolloutState {
                             self.session_id = id.to_string();
                         } else if self.session_id != id {
                             warn!(
+                                target: crate::logging::CODEX_WATCHER,
                                 current = %self.session_id,
                                 meta = %id,
                                 "codex rollout session id changed after emission; ignoring"
@@ -137,6 +202,17 @@ impl RolloutState {
                         self.cwd = cwd.to_string();
                     }
                 }
+                if let Some(model) = value
+                    .pointer("/payload/model")
+                    .and_then(|v| v.as_str())
+                    .filter(|model| self.model.as_deref() != Some(*model))
+                {
+                    self.model = Some(model.to_string());
+                    events.push(AgentEvent::ModelUpdated {
+                        session_id: self.session_id.clone(),
+                        model: model.to_string(),
+                    });
+                }
                 // No user-facing state change; treat as activity at most.
                 return events;
             }
@@ -169,6 +245,7 @@ impl RolloutState {
                         });
                     }
                     "task_complete" | "turn_aborted" => {
+                        self.approval = None;
                         events.push(AgentEvent::Idle {
                             session_id: self.session_id.clone(),
                             cwd: self.cwd.clone(),
@@ -181,6 +258,96 @@ impl RolloutState {
                             message: None,
                         });
                     }
+                    "exec_approval_request" => {
+                        let command = approval_command(payload);
+                        let tool_name = command
+                            .as_deref()
+                            .and_then(first_shell_token)
+                            .unwrap_or_else(|| "exec".to_string());
+                        let tool_label = command.as_deref().map(|cmd| truncate_owned(cmd, 60));
+                        let tool_full_label = command
+                            .and_then(|cmd| crate::agents::full_label(&cmd, tool_label.as_deref()));
+                        self.request_approval(
+                            payload,
+                            tool_name,
+                            (tool_label, tool_full_label),
+                            &mut events,
+                        );
+                    }
+                    "apply_patch_approval_request" => {
+                        let path = payload
+                            .get("changes")
+                            .and_then(Value::as_object)
+                            .and_then(|changes| changes.keys().min());
+                        let tool_label = path.map(|path| crate::agents::short_path(path));
+                        let tool_full_label = path.and_then(|path| {
+                            crate::agents::full_label(path, tool_label.as_deref())
+                        });
+                        self.request_approval(
+                            payload,
+                            "apply_patch".to_string(),
+                            (tool_label, tool_full_label),
+                            &mut events,
+                        );
+                    }
+                    "plan_update" => {
+                        if let Some(progress) = plan_progress(payload) {
+                            events.push(AgentEvent::PlanUpdated {
+                                session_id: self.session_id.clone(),
+                                progress,
+                            });
+                        }
+                    }
+                    "mcp_tool_call_begin" => {
+                        let invocation = payload.get("invocation").unwrap_or(&Value::Null);
+                        let server = json_string_field(invocation, &["server"]).unwrap_or("mcp");
+                        let tool = json_string_field(invocation, &["tool"]).unwrap_or("tool");
+                        // Same naming as Claude Code, so the HUD shows "server: label"
+                        let tool_name = format!("mcp__{server}__{tool}");
+                        let args = invocation.get("arguments");
+                        let tool_label =
+                            args.and_then(|args| tool_label_from_args(&tool_name, args));
+                        let tool_full_label =
+                            args.and_then(full_value_from_args).and_then(|value| {
+                                crate::agents::full_label(value, tool_label.as_deref())
+                            });
+                        events.push(AgentEvent::ToolStarted {
+                            session_id: self.session_id.clone(),
+                            cwd: self.cwd.clone(),
+                            tool_id: json_string_field(payload, &["call_id", "callId"])
+                                .unwrap_or("unknown")
+                                .to_string(),
+                            tool_name,
+                            tool_label,
+                            tool_full_label,
+                        });
+                    }
+                    "mcp_tool_call_end" => {
+                        events.push(AgentEvent::ToolCompleted {
+                            session_id: self.session_id.clone(),
+                            cwd: self.cwd.clone(),
+                            tool_id: json_string_field(payload, &["call_id", "callId"])
+                                .unwrap_or("unknown")
+                                .to_string(),
+                        });
+                    }
+                    // Approved: the call runs now
+                    "exec_command_begin" | "patch_apply_begin" => {
+                        let call_id = json_string_field(payload, &["call_id", "callId"]);
+                        if let Some(approval) = self
+                            .approval
+                            .take_if(|approval| call_id == Some(approval.call_id.as_str()))
+                        {
+                            events.push(AgentEvent::ToolStarted {
+                                session_id: self.session_id.clone(),
+                                cwd: self.cwd.clone(),
+                                tool_id: approval.call_id,
+                                tool_name: approval.tool_name,
+                                tool_label: approval.tool_label,
+                                tool_full_label: approval.tool_full_label,
+                            });
+                        }
+                    }
                     // High-frequency / non-UX events.
                     "token_count" | "agent_reasoning" => {}
                     _ => {}
@@ -210,9 +377,56 @@ impl RolloutState {
             _ => {}
         }
 
+        for event in &events {
+            match event {
+                AgentEvent::Activity { .. }
+                | AgentEvent::ToolStarted { .. }
+                | AgentEvent::ToolCompleted { .. }
+                | AgentEvent::Compacting { .. } => self.in_turn = true,
+                AgentEvent::Idle { .. }
+                | AgentEvent::WaitingForInput { .. }
+                | AgentEvent::NeedsAttention { .. }
+                | AgentEvent::SessionEnded { .. } => self.in_turn = false,
+                _ => {}
+            }
+        }
         events
     }
 
+    /// Remember the call awaiting approval and flag the session
+    fn request_approval(
+        &mut self,
+        payload: &Value,
+        tool_name: String,
+        (tool_label, tool_full_label): (Option<String>, Option<String>),
+        events: &mut Vec<AgentEvent>,
+    ) {
+        let call_id = json_string_field(payload, &["call_id", "callId"]).unwrap_or("unknown");
+        events.push(AgentEvent::NeedsAttention {
+            session_id: self.session_id.clone(),
+            cwd: self.cwd.clone(),
+            message: Some(tool_label.clone().unwrap_or_else(|| tool_name.clone())),
+        });
+        self.approval = Some(Approval {
+            call_id: call_id.to_string(),
+            tool_name,
+            tool_label,
+            tool_full_label,
+        });
+    }
+
+    /// A call finished without a begin event: it was denied (Codex returns
+    /// the rejection as the call's output), so nothing awaits approval
+    fn resolve_approval(&mut self, tool_id: &str) {
+        if self
+            .approval
+            .as_ref()
+            .is_some_and(|approval| approval.call_id == tool_id)
+        {
+            self.approval = None;
+        }
+    }
+
     fn apply_response_item(
         &mut self,
         payload: &Value,
@@ -228,21 +442,26 @@ impl RolloutState {
                 let args = payload.get("arguments");
                 let args_json = args.and_then(parse_json_string);
 
-                let (tool_name, tool_label, session_name) = if tool_name_raw == "exec_command" {
-                    let cmd = args_json
-                        .as_ref()
-                        .and_then(|v| json_string_field(v, &["cmd"]))
-                        .unwrap_or("");
-                    let name = first_shell_token(cmd).unwrap_or_else(|| "exec".to_string());
-                    let label = (!cmd.is_empty()).then(|| truncate_owned(cmd, 60));
-                    let session_name = crate::agents::parse_aura_set_name_command(cmd);
-                    (name, label, session_name)
-                } else {
-                    let label = args_json
-                        .as_ref()
-                        .and_then(|v| tool_label_from_args(tool_name_raw, v));
-                    (tool_name_raw.to_string(), label, None)
-                };
+                let full_value = args_json.as_ref().and_then(full_value_from_args);
+                let (tool_name, tool_label, session_name, session_tags) =
+                    if tool_name_raw == "exec_command" {
+                        let cmd = args_json
+                            .as_ref()
+                            .and_then(|v| json_string_field(v, &["cmd"]))
+                            .unwrap_or("");
+                        let name = first_shell_token(cmd).unwrap_or_else(|| "exec".to_string());
+                        let label = (!cmd.is_empty()).then(|| truncate_owned(cmd, 60));
+                        let session_name = crate::agents::parse_aura_set_name_command(cmd);
+                        let session_tags = crate::agents::parse_aura_set_tag_command(cmd);
+                        (name, label, session_name, session_tags)
+                    } else {
+                        let label = args_json
+                            .as_ref()
+                            .and_then(|v| tool_label_from_args(tool_name_raw, v));
+                        (tool_name_raw.to_string(), label, None, None)
+                    };
+                let tool_full_label = full_value
+                    .and_then(|value| crate::agents::full_label(value, tool_label.as_deref()));
 
                 events.push(AgentEvent::ToolStarted {
                     session_id: self.session_id.clone(),
@@ -250,6 +469,7 @@ impl RolloutState {
                     tool_id: tool_id.to_string(),
                     tool_name,
                     tool_label,
+                    tool_full_label,
                 });
                 if let Some(name) = session_name {
                     events.push(AgentEvent::SessionNameUpdated {
@@ -257,10 +477,25 @@ impl RolloutState {
                         name,
                     });
                 }
+                if let Some(tags) = session_tags {
+                    events.push(AgentEvent::SessionTagged {
+                        session_id: self.session_id.clone(),
+                        tags,
+                    });
+                }
+                if tool_name_raw == "update_plan"
+                    && let Some(progress) = args_json.as_ref().and_then(plan_progress)
+                {
+                    events.push(AgentEvent::PlanUpdated {
+                        session_id: self.session_id.clone(),
+                        progress,
+                    });
+                }
             }
             "function_call_output" => {
                 let tool_id =
                     json_string_field(payload, &["call_id", "callId"]).unwrap_or("unknown");
+                self.resolve_approval(tool_id);
                 events.push(AgentEvent::ToolCompleted {
                     session_id: self.session_id.clone(),
                     cwd: self.cwd.clone(),
@@ -271,17 +506,40 @@ impl RolloutState {
                 let tool_id =
                     json_string_field(payload, &["call_id", "callId"]).unwrap_or("unknown");
                 let tool_name = json_string_field(payload, &["name"]).unwrap_or("custom_tool");
+                let patched: Vec<FileChange> = if tool_name == "apply_patch" {
+                    json_string_field(payload, &["input"])
+                        .map(crate::agents::apply_patch_changes)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|change| FileChange {
+                            path: crate::agents::resolve_path(&self.cwd, &change.path),
+                            ..change
+                        })
+                        .collect()
+                } else {
+                    Vec::new()
+                };
                 events.push(AgentEvent::ToolStarted {
                     session_id: self.session_id.clone(),
                     cwd: self.cwd.clone(),
                     tool_id: tool_id.to_string(),
                     tool_name: tool_name.to_string(),
-                    tool_label: None,
+                    tool_label: patched
+                        .first()
+                        .map(|change| crate::agents::short_path(&change.path)),
+                    tool_full_label: None,
                 });
+                if !patched.is_empty() {
+                    events.push(AgentEvent::FilesTouched {
+                        session_id: self.session_id.clone(),
+                        changes: patched,
+                    });
+                }
             }
             "custom_tool_call_output" => {
                 let tool_id =
                     json_string_field(payload, &["call_id", "callId"]).unwrap_or("unknown");
+                self.resolve_approval(tool_id);
                 events.push(AgentEvent::ToolCompleted {
                     session_id: self.session_id.clone(),
                     cwd: self.cwd.clone(),
@@ -310,6 +568,7 @@ impl RolloutState {
                     tool_id: tool_id.clone(),
                     tool_name: "WebSearch".to_string(),
                     tool_label: query,
+                    tool_full_label: None,
                 });
                 events.push(AgentEvent::ToolCompleted {
                     session_id: self.session_id.clone(),
@@ -349,6 +608,7 @@ mod tests {
                 session_id,
                 cwd,
                 agent,
+                ..
             } => {
                 assert_eq!(session_id, "sess_1");
                 assert_eq!(cwd, "/tmp/project");
@@ -358,6 +618,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn turn_context_reports_model_changes() {
+        let mut state = RolloutState::new("sess_1".to_string(), "/tmp".to_string());
+        let _ = state.ensure_session_event();
+        let turn = |model: &str| {
+            json!({
+                "type": "turn_context",
+                "payload": { "cwd": "/tmp", "model": model }
+            })
+        };
+
+        let events = state.apply_line(&turn("gpt-5-codex"));
+        assert!(matches!(
+            events.as_slice(),
+            [AgentEvent::ModelUpdated { model, .. }] if model == "gpt-5-codex"
+        ));
+        // Unchanged: nothing to report
+        assert!(state.apply_line(&turn("gpt-5-codex")).is_empty());
+        assert_eq!(state.apply_line(&turn("gpt-5")).len(), 1);
+    }
+
     #[test]
     fn exec_command_function_call_maps_to_tool_with_cmd_label() {
         let mut state = RolloutState::new("sess_1".to_string(), "/tmp".to_string());
@@ -493,4 +774,267 @@ mod tests {
             other => panic!("unexpected events: {other:?}"),
         }
     }
+
+    #[test]
+    fn apply_patch_emits_files_touched() {
+        let mut state = RolloutState::new("sess_1".to_string(), "/code/aura".to_string());
+        let _ = state.ensure_session_event();
+
+        let events = state.apply_line(&json!({
+            "type": "response_item",
+            "payload": {
+                "type": "custom_tool_call",
+                "call_id": "call_patch",
+                "name": "apply_patch",
+                "input": "*** Begin Patch\n*** Update File: src/main.rs\n@@\n-a\n+b\n*** Add File: /tmp/new.txt\n+hi\n*** Update File: src/main.rs\n*** End Patch"
+            }
+        }));
+
+        assert_eq!(events.len(), 2);
+        match &events[0] {
+            AgentEvent::ToolStarted {
+                tool_name,
+                tool_label,
+                ..
+            } => {
+                assert_eq!(tool_name, "apply_patch");
+                assert_eq!(tool_label.as_deref(), Some("main.rs"));
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+        match &events[1] {
+            AgentEvent::FilesTouched {
+                session_id,
+                changes,
+            } => {
+                assert_eq!(session_id, "sess_1");
+                let paths: Vec<_> = changes.iter().map(|c| c.path.as_str()).collect();
+                assert_eq!(paths, vec!["/code/aura/src/main.rs", "/tmp/new.txt"]);
+                assert_eq!((changes[0].added, changes[0].removed), (1, 1));
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn exec_approval_request_needs_attention_until_approved() {
+        let mut state = RolloutState::new("sess_1".to_string(), "/tmp".to_string());
+        let _ = state.ensure_session_event();
+
+        let events = state.apply_line(&json!({
+            "type": "event_msg",
+            "payload": {
+                "type": "exec_approval_request",
+                "call_id": "call_1",
+                "command": ["bash", "-lc", "cargo test --workspace"],
+                "cwd": "/tmp",
+            }
+        }));
+        match events.as_slice() {
+            [AgentEvent::NeedsAttention { message, .. }] => {
+                assert_eq!(message.as_deref(), Some("cargo test --workspace"));
+            }
+            other => panic!("unexpected events: {other:?}"),
+        }
+        assert!(!state.in_turn());
+
+        // Another call's begin doesn't resolve it
+        let other = state.apply_line(&json!({
+            "type": "event_msg",
+            "payload": { "type": "exec_command_begin", "call_id": "call_0" }
+        }));
+        assert!(other.is_empty());
+
+        let events = state.apply_line(&json!({
+            "type": "event_msg",
+            "payload": { "type": "exec_command_begin", "call_id": "call_1" }
+        }));
+        match events.as_slice() {
+            [
+                AgentEvent::ToolStarted {
+                    tool_id,
+                    tool_name,
+                    tool_label,
+                    ..
+                },
+            ] => {
+                assert_eq!(tool_id, "call_1");
+                assert_eq!(tool_name, "cargo");
+                assert_eq!(tool_label.as_deref(), Some("cargo test --workspace"));
+            }
+            other => panic!("unexpected events: {other:?}"),
+        }
+        assert!(state.approval.is_none());
+        assert!(state.in_turn());
+    }
+
+    #[test]
+    fn denied_approval_completes_the_call() {
+        let mut state = RolloutState::new("sess_1".to_string(), "/code/aura".to_string());
+        let _ = state.ensure_session_event();
+
+        let events = state.apply_line(&json!({
+            "type": "event_msg",
+            "payload": {
+                "type": "apply_patch_approval_request",
+                "call_id": "call_patch",
+                "changes": {
+                    "/code/aura/src/main.rs": { "update": { "unified_diff": "" } },
+                    "/code/aura/src/lib.rs": { "update": { "unified_diff": "" } },
+                },
+            }
+        }));
+        match events.as_slice() {
+            [AgentEvent::NeedsAttention { message, .. }] => {
+                assert_eq!(message.as_deref(), Some("lib.rs"));
+            }
+            other => panic!("unexpected events: {other:?}"),
+        }
+
+        let events = state.apply_line(&json!({
+            "type": "response_item",
+            "payload": {
+                "type": "custom_tool_call_output",
+                "call_id": "call_patch",
+                "output": "patch rejected by user",
+            }
+        }));
+        assert!(matches!(
+            events.as_slice(),
+            [AgentEvent::ToolCompleted { tool_id, .. }] if tool_id == "call_patch"
+        ));
+        assert!(state.approval.is_none());
+    }
+
+    #[test]
+    fn mcp_tool_call_events_map_to_tool_lifecycle() {
+        let mut state = RolloutState::new("sess_1".to_string(), "/tmp".to_string());
+        let _ = state.ensure_session_event();
+
+        let events = state.apply_line(&json!({
+            "timestamp": "2026-03-02T09:14:07.512Z",
+            "type": "event_msg",
+            "payload": {
+                "type": "mcp_tool_call_begin",
+                "call_id": "call_mcp_1",
+                "invocation": {
+                    "server": "github",
+                    "tool": "search_issues",
+                    "arguments": { "query": "is:open label:bug", "per_page": 20 }
+                }
+            }
+        }));
+        match events.as_slice() {
+            [
+                AgentEvent::ToolStarted {
+                    tool_id,
+                    tool_name,
+                    tool_label,
+                    ..
+                },
+            ] => {
+                assert_eq!(tool_id, "call_mcp_1");
+                assert_eq!(tool_name, "mcp__github__search_issues");
+                assert_eq!(tool_label.as_deref(), Some("is:open label:bug"));
+            }
+            other => panic!("unexpected events: {other:?}"),
+        }
+
+        let events = state.apply_line(&json!({
+            "timestamp": "2026-03-02T09:14:08.130Z",
+            "type": "event_msg",
+            "payload": {
+                "type": "mcp_tool_call_end",
+                "call_id": "call_mcp_1",
+                "invocation": {
+                    "server": "github",
+                    "tool": "search_issues",
+                    "arguments": { "query": "is:open label:bug", "per_page": 20 }
+                },
+                "duration": { "secs": 0, "nanos": 618000000 },
+                "result": { "Ok": { "content": [{ "type": "text", "text": "[]" }], "isError": false } }
+            }
+        }));
+        assert!(matches!(
+            events.as_slice(),
+            [AgentEvent::ToolCompleted { tool_id, .. }] if tool_id == "call_mcp_1"
+        ));
+    }
+
+    #[test]
+    fn mcp_tool_call_without_arguments_has_no_label() {
+        let mut state = RolloutState::new("sess_1".to_string(), "/tmp".to_string());
+        let _ = state.ensure_session_event();
+
+        let events = state.apply_line(&json!({
+            "type": "event_msg",
+            "payload": {
+                "type": "mcp_tool_call_begin",
+                "call_id": "call_mcp_2",
+                "invocation": { "server": "memory", "tool": "read_graph", "arguments": null }
+            }
+        }));
+        match events.as_slice() {
+            [
+                AgentEvent::ToolStarted {
+                    tool_name,
+                    tool_label,
+                    ..
+                },
+            ] => {
+                assert_eq!(tool_name, "mcp__memory__read_graph");
+                assert_eq!(tool_label, &None);
+            }
+            other => panic!("unexpected events: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn update_plan_call_emits_plan_progress() {
+        let mut state = RolloutState::new("sess_1".to_string(), "/tmp".to_string());
+        let _ = state.ensure_session_event();
+
+        let events = state.apply_line(&json!({
+            "type": "response_item",
+            "payload": {
+                "type": "function_call",
+                "call_id": "call_plan",
+                "name": "update_plan",
+                "arguments": "{\"explanation\":\"Starting\",\"plan\":[{\"step\":\"Read config\",\"status\":\"completed\"},{\"step\":\"Migrate config loader\",\"status\":\"in_progress\"},{\"step\":\"Add tests\",\"status\":\"pending\"}]}"
+            }
+        }));
+        assert_eq!(events.len(), 2);
+        match &events[1] {
+            AgentEvent::PlanUpdated { progress, .. } => {
+                assert_eq!(
+                    progress.subtitle().as_deref(),
+                    Some("1/3 · Migrate config loader")
+                );
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn plan_update_event_emits_plan_progress() {
+        let mut state = RolloutState::new("sess_1".to_string(), "/tmp".to_string());
+        let _ = state.ensure_session_event();
+
+        let events = state.apply_line(&json!({
+            "type": "event_msg",
+            "payload": {
+                "type": "plan_update",
+                "plan": [
+                    { "step": "Read config", "status": "completed" },
+                    { "step": "Add tests", "status": "completed" }
+                ]
+            }
+        }));
+        match events.as_slice() {
+            [AgentEvent::PlanUpdated { progress, .. }] => {
+                assert_eq!(progress.subtitle().as_deref(), Some("2/2"));
+            }
+            other => panic!("unexpected events: {other:?}"),
+        }
+    }
 }
@@ -1,30 +1,77 @@
 use chrono::{Datelike, Local, NaiveDate};
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime};
 
 #[derive(Debug, Clone)]
 pub(super) struct CodexPaths {
+    /// As configured
+    home_raw: PathBuf,
+    /// Symlinks resolved
     pub(super) home: PathBuf,
+    /// `sessions/` with symlinks resolved, once it exists; every tracked
+    /// rollout path starts with it
     pub(super) sessions_root: PathBuf,
-    pub(super) sessions_root_alt: PathBuf,
+    /// Other spellings of the sessions root (through the configured, possibly
+    /// symlinked home) that watcher events may use
+    sessions_aliases: Vec<PathBuf>,
 }
 
 impl CodexPaths {
-    pub(super) fn detect() -> Self {
-        let home_raw = codex_home();
+    /// Resolve `home_raw` (`~/.codex` or `CODEX_HOME`), which may be a
+    /// symlink, hold a symlinked `sessions/` or not exist yet
+    pub(super) fn resolve(home_raw: PathBuf) -> Self {
         let home = std::fs::canonicalize(&home_raw).unwrap_or_else(|_| home_raw.clone());
-        let sessions_root = home.join("sessions");
-        let sessions_root_alt = home_raw.join("sessions");
+        let sessions = home.join("sessions");
+        let sessions_root = std::fs::canonicalize(&sessions).unwrap_or_else(|_| sessions.clone());
+        let mut sessions_aliases = vec![sessions, home_raw.join("sessions")];
+        sessions_aliases.retain(|alias| *alias != sessions_root);
+        sessions_aliases.dedup();
 
         Self {
+            home_raw,
             home,
             sessions_root,
-            sessions_root_alt,
+            sessions_aliases,
         }
     }
+
+    /// Resolve again, e.g. once `sessions/` appeared (maybe as a symlink)
+    pub(super) fn refresh(&mut self) {
+        *self = Self::resolve(self.home_raw.clone());
+    }
+
+    /// `path` rewritten under [`Self::sessions_root`], if it's inside the
+    /// sessions directory under any spelling. Prefixes match exactly first,
+    /// then ignoring ASCII case, since case-insensitive volumes (APFS, SMB)
+    /// may report a differently-cased path than the one configured.
+    pub(super) fn normalize(&self, path: &Path) -> Option<PathBuf> {
+        let roots = || std::iter::once(&self.sessions_root).chain(&self.sessions_aliases);
+        roots()
+            .find_map(|root| path.strip_prefix(root).ok().map(Path::to_path_buf))
+            .or_else(|| roots().find_map(|root| strip_prefix_ignore_case(path, root)))
+            .map(|rest| self.sessions_root.join(rest))
+    }
+}
+
+fn strip_prefix_ignore_case(path: &Path, prefix: &Path) -> Option<PathBuf> {
+    let mut components = path.components();
+    for expected in prefix.components() {
+        let actual = components.next()?;
+        let same = actual
+            .as_os_str()
+            .to_str()
+            .zip(expected.as_os_str().to_str())
+            .is_some_and(|(a, b)| a.eq_ignore_ascii_case(b));
+        if !same {
+            return None;
+        }
+    }
+    Some(components.as_path().to_path_buf())
 }
 
-fn codex_home() -> PathBuf {
+/// `CODEX_HOME`, else `~/.codex`
+pub(super) fn default_home() -> PathBuf {
     if let Some(home) = std::env::var_os("CODEX_HOME") {
         return PathBuf::from(home);
     }
@@ -54,11 +101,19 @@ pub(super) fn session_id_from_path(path: &Path) -> String {
     stem.to_string()
 }
 
+/// Every rollout under `root`, following symlinked directories and files
+/// (each directory is read once, so symlink cycles are harmless). Paths keep
+/// `root` as their prefix.
 pub(super) fn read_dir_recursive(root: &Path) -> Vec<PathBuf> {
     let mut out = Vec::new();
+    let mut visited = HashSet::new();
     let mut stack = vec![root.to_path_buf()];
 
     while let Some(path) = stack.pop() {
+        let real = std::fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+        if !visited.insert(real) {
+            continue;
+        }
         let entries = match std::fs::read_dir(&path) {
             Ok(e) => e,
             Err(_) => continue,
@@ -66,7 +121,11 @@ pub(super) fn read_dir_recursive(root: &Path) -> Vec<PathBuf> {
 
         for entry in entries.flatten() {
             let p = entry.path();
-            match entry.file_type() {
+            let file_type = match entry.file_type() {
+                Ok(t) if t.is_symlink() => std::fs::metadata(&p).map(|m| m.file_type()),
+                other => other,
+            };
+            match file_type {
                 Ok(t) if t.is_dir() => stack.push(p),
                 Ok(t) if t.is_file() && is_jsonl(&p) => out.push(p),
                 _ => {}
@@ -88,10 +147,7 @@ fn max_numeric_child_dir(parent: &Path, len: usize) -> Option<PathBuf> {
     let mut best: Option<(String, PathBuf)> = None;
 
     for entry in entries.flatten() {
-        let Ok(file_type) = entry.file_type() else {
-            continue;
-        };
-        if !file_type.is_dir() {
+        if !entry.path().is_dir() {
             continue;
         }
 
@@ -144,10 +200,11 @@ async fn read_dir_jsonl(dir: &Path) -> Vec<PathBuf> {
     let mut out = Vec::new();
     while let Ok(Some(entry)) = rd.next_entry().await {
         let path = entry.path();
-        let Ok(file_type) = entry.file_type().await else {
+        // Follows symlinks, unlike `DirEntry::file_type`
+        let Ok(meta) = tokio::fs::metadata(&path).await else {
             continue;
         };
-        if file_type.is_file() && is_jsonl(&path) {
+        if meta.is_file() && is_jsonl(&path) {
             out.push(path);
         }
     }
@@ -156,9 +213,13 @@ async fn read_dir_jsonl(dir: &Path) -> Vec<PathBuf> {
 }
 
 pub(super) async fn modified_within(path: &Path, window: Duration) -> bool {
-    let Ok(meta) = tokio::fs::metadata(path).await else {
-        return false;
-    };
+    tokio::fs::metadata(path)
+        .await
+        .is_ok_and(|meta| is_recent(&meta, window))
+}
+
+/// Whether a file with `meta` was modified within `window`
+pub(super) fn is_recent(meta: &std::fs::Metadata, window: Duration) -> bool {
     let Ok(modified) = meta.modified() else {
         return false;
     };
@@ -5,6 +5,17 @@
 pub mod claude_code;
 pub mod codex;
 
+use crate::FileChange;
+
+/// Longest full tool label kept for copying (scripts can be much longer)
+const FULL_LABEL_MAX: usize = 4096;
+
+/// `value` as a tool call's full label: `None` when `label` already shows
+/// all of it
+pub(crate) fn full_label(value: &str, label: Option<&str>) -> Option<String> {
+    (!value.is_empty() && label != Some(value)).then(|| truncate(value, FULL_LABEL_MAX).to_string())
+}
+
 /// Truncate a string to at most `max` characters (by Unicode char boundary).
 pub(crate) fn truncate(s: &str, max: usize) -> &str {
     match s.char_indices().nth(max) {
@@ -18,18 +29,109 @@ pub(crate) fn short_path(path: &str) -> String {
     path.rsplit('/').next().unwrap_or(path).to_string()
 }
 
+/// Absolute form of a file path an agent reported relative to its cwd
+pub(crate) fn resolve_path(cwd: &str, path: &str) -> String {
+    if path.starts_with('/') || cwd.is_empty() {
+        return path.to_string();
+    }
+    format!(
+        "{}/{}",
+        cwd.trim_end_matches('/'),
+        path.trim_start_matches("./")
+    )
+}
+
+/// Number of lines in a string (0 for an empty string)
+pub(crate) fn line_count(text: &str) -> u32 {
+    text.lines().count().try_into().unwrap_or(u32::MAX)
+}
+
+/// Files changed by an `apply_patch` body, in order, with paths as written
+/// (usually relative).
+///
+/// Files come from `*** Add File:`, `*** Update File:`, `*** Delete File:`
+/// and `*** Move to:` headers; `+`/`-` lines below a header count toward it.
+pub(crate) fn apply_patch_changes(patch: &str) -> Vec<FileChange> {
+    const HEADERS: &[&str] = &[
+        "*** Add File: ",
+        "*** Update File: ",
+        "*** Delete File: ",
+        "*** Move to: ",
+    ];
+    let mut changes: Vec<FileChange> = Vec::new();
+    let mut current: Option<usize> = None;
+    for line in patch.lines() {
+        let header = HEADERS
+            .iter()
+            .find_map(|header| line.strip_prefix(header))
+            .map(str::trim);
+        if let Some(path) = header {
+            current = (!path.is_empty()).then(|| {
+                changes
+                    .iter()
+                    .position(|change| change.path == path)
+                    .unwrap_or_else(|| {
+                        changes.push(FileChange {
+                            path: path.to_string(),
+                            ..Default::default()
+                        });
+                        changes.len() - 1
+                    })
+            });
+        } else if line.starts_with("*** End Patch") {
+            current = None;
+        } else if let Some(change) = current.and_then(|i| changes.get_mut(i)) {
+            if line.starts_with('+') {
+                change.added += 1;
+            } else if line.starts_with('-') {
+                change.removed += 1;
+            }
+        }
+    }
+    changes
+}
+
 /// Parse `aura set-name "..."` from a shell command string.
 ///
 /// Supports double quotes, single quotes, and unquoted names (including multi-word).
 /// Returns the extracted name, or `None` if the command is not an `aura set-name` invocation.
 pub(crate) fn parse_aura_set_name_command(command: &str) -> Option<String> {
+    let rest = aura_subcommand_args(command, "set-name")?;
+
+    // Strip matching quotes if present
+    if (rest.starts_with('"') && rest.ends_with('"'))
+        || (rest.starts_with('\'') && rest.ends_with('\''))
+    {
+        let inner = &rest[1..rest.len() - 1];
+        if inner.is_empty() {
+            return None;
+        }
+        return Some(inner.to_string());
+    }
+
+    Some(rest.to_string())
+}
+
+/// Parse `aura set-tag billing [more tags…]` from a shell command string.
+///
+/// Returns the normalized tags, or `None` if the command is not an
+/// `aura set-tag` invocation with at least one valid tag.
+pub(crate) fn parse_aura_set_tag_command(command: &str) -> Option<Vec<String>> {
+    let rest = aura_subcommand_args(command, "set-tag")?;
+    let tags = crate::tags::parse_tags(&rest.replace(['"', '\''], " "));
+    (!tags.is_empty()).then_some(tags)
+}
+
+/// Arguments of `aura <subcommand> ...` in a shell command string (trimmed,
+/// non-empty), or `None` for any other command.
+fn aura_subcommand_args<'a>(command: &'a str, subcommand: &str) -> Option<&'a str> {
     let trimmed = command.trim();
     if trimmed.is_empty() {
         return None;
     }
 
     // Use split_whitespace to skip arbitrary interior whitespace, then verify
-    // the first two tokens are "<something>/aura" (or just "aura") and "set-name".
+    // the first two tokens are "<something>/aura" (or just "aura") and the subcommand.
     // Accepts: "aura", "./aura", "/usr/local/bin/aura", "../aura", etc.
     let mut tokens = trimmed.split_whitespace();
     let binary = tokens.next()?;
@@ -37,36 +139,52 @@ pub(crate) fn parse_aura_set_name_command(command: &str) -> Option<String> {
     if basename != "aura" {
         return None;
     }
-    if tokens.next() != Some("set-name") {
-        return None;
-    }
-
-    // Find where the name argument starts in the original string (after "set-name" + whitespace)
-    let set_name_pos = trimmed.find("set-name")?;
-    let after_keyword = &trimmed[set_name_pos + "set-name".len()..];
-    let rest = after_keyword.trim();
-    if rest.is_empty() {
+    if tokens.next() != Some(subcommand) {
         return None;
     }
 
-    // Strip matching quotes if present
-    if (rest.starts_with('"') && rest.ends_with('"'))
-        || (rest.starts_with('\'') && rest.ends_with('\''))
-    {
-        let inner = &rest[1..rest.len() - 1];
-        if inner.is_empty() {
-            return None;
-        }
-        return Some(inner.to_string());
-    }
-
-    Some(rest.to_string())
+    // Find where the argument starts in the original string (after the subcommand + whitespace)
+    let keyword_pos = trimmed.find(subcommand)?;
+    let rest = trimmed[keyword_pos + subcommand.len()..].trim();
+    (!rest.is_empty()).then_some(rest)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn resolve_path_joins_relative_paths() {
+        assert_eq!(
+            resolve_path("/code/aura", "src/main.rs"),
+            "/code/aura/src/main.rs"
+        );
+        assert_eq!(
+            resolve_path("/code/aura/", "./README.md"),
+            "/code/aura/README.md"
+        );
+        assert_eq!(resolve_path("/code/aura", "/etc/hosts"), "/etc/hosts");
+        assert_eq!(resolve_path("", "src/main.rs"), "src/main.rs");
+    }
+
+    #[test]
+    fn apply_patch_changes_count_lines_per_file() {
+        let patch = "*** Begin Patch\n*** Update File: src/main.rs\n@@\n-a\n+b\n+c\n*** Add File: /tmp/new.txt\n+hi\n*** Update File: src/main.rs\n@@\n-d\n*** End Patch";
+        let changes: Vec<_> = apply_patch_changes(patch)
+            .into_iter()
+            .map(|c| (c.path, c.added, c.removed))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("src/main.rs".to_string(), 2, 2),
+                ("/tmp/new.txt".to_string(), 1, 0)
+            ]
+        );
+        assert_eq!(line_count(""), 0);
+        assert_eq!(line_count("a\nb\n"), 2);
+    }
+
     #[test]
     fn parse_set_name_double_quotes() {
         assert_eq!(
@@ -129,4 +247,27 @@ mod tests {
     fn parse_set_name_missing_arg_rejected() {
         assert_eq!(parse_aura_set_name_command("aura set-name"), None);
     }
+
+    #[test]
+    fn parse_set_tag_single_and_multiple() {
+        assert_eq!(
+            parse_aura_set_tag_command("aura set-tag billing"),
+            Some(vec!["billing".to_string()])
+        );
+        assert_eq!(
+            parse_aura_set_tag_command("aura set-tag \"billing, Hotfix\" #q3"),
+            Some(vec![
+                "billing".to_string(),
+                "hotfix".to_string(),
+                "q3".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_set_tag_rejects_other_commands() {
+        assert_eq!(parse_aura_set_tag_command("aura set-tag"), None);
+        assert_eq!(parse_aura_set_tag_command("aura set-tag \"\""), None);
+        assert_eq!(parse_aura_set_tag_command("aura set-name billing"), None);
+    }
 }
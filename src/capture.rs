@@ -0,0 +1,299 @@
+//! Event capture - record raw agent input for bug reports, replay it later
+//!
+//! `aura debug record <file>` drops a marker in the runtime dir. While it
+//! exists, `aura hook` appends each hook payload it reads, and the Codex
+//! watcher each rollout line it tails, to the capture file as JSONL
+//! ([`Entry`]), optionally only for one session. `aura debug record --stop`
+//! removes the marker.
+//!
+//! `aura debug replay <file>` feeds a capture back through the hook and
+//! rollout parsers into a fresh registry, so the same capture always yields
+//! the same events and sessions.
+
+use crate::agents::claude_code::{self, HookAgent};
+use crate::agents::codex::sessions::ReplayRollouts;
+use crate::registry::SessionRegistry;
+use crate::{AgentEvent, AgentType, SessionInfo};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// How often a [`Recorder`] rechecks the marker
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Marker file telling writers where to record
+fn marker_path() -> PathBuf {
+    crate::paths::runtime_dir().join("aura-capture.json")
+}
+
+/// An active recording
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Capture {
+    /// Capture file (absolute)
+    pub path: PathBuf,
+    /// Only record this session (ID or prefix)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+impl Capture {
+    fn includes(&self, session_id: &str) -> bool {
+        self.session_id
+            .as_deref()
+            .is_none_or(|wanted| session_id.starts_with(wanted))
+    }
+}
+
+/// One captured line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum Entry {
+    /// Hook JSON as read from `aura hook` stdin
+    Hook {
+        /// `--agent` value, e.g. `claude-code`
+        agent: String,
+        payload: Value,
+    },
+    /// A line appended to a Codex rollout
+    Rollout {
+        path: String,
+        session_id: String,
+        cwd: String,
+        payload: Value,
+    },
+}
+
+/// Start recording to `output`, replacing any recording in progress.
+pub fn start(output: &Path, session_id: Option<String>) -> std::io::Result<Capture> {
+    let path = std::path::absolute(output)?;
+    // Fail now rather than silently in every writer
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?;
+    let capture = Capture { path, session_id };
+    let marker = marker_path();
+    if let Some(dir) = marker.parent() {
+        crate::paths::ensure_private_dir(dir)?;
+    }
+    let json = serde_json::to_string(&capture).map_err(std::io::Error::other)?;
+    std::fs::write(marker, json)?;
+    Ok(capture)
+}
+
+/// Stop recording, returning the recording that was active
+pub fn stop() -> std::io::Result<Option<Capture>> {
+    let capture = read_marker();
+    match std::fs::remove_file(marker_path()) {
+        Ok(()) => Ok(capture),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+fn read_marker() -> Option<Capture> {
+    let json = std::fs::read_to_string(marker_path()).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// Appends entries to the active capture, if any
+#[derive(Debug, Default)]
+pub struct Recorder {
+    checked_at: Option<Instant>,
+    capture: Option<Capture>,
+}
+
+impl Recorder {
+    /// Record a hook payload
+    pub fn hook(&mut self, agent: &HookAgent, payload: &Value) {
+        let session_id = payload
+            .get("session_id")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let agent = agent
+            .to_possible_value()
+            .map(|value| value.get_name().to_string())
+            .unwrap_or_default();
+        self.record(session_id, || Entry::Hook {
+            agent,
+            payload: payload.clone(),
+        });
+    }
+
+    /// Record a rollout line
+    pub fn rollout(&mut self, path: &Path, session_id: &str, cwd: &str, payload: &Value) {
+        self.record(session_id, || Entry::Rollout {
+            path: path.display().to_string(),
+            session_id: session_id.to_string(),
+            cwd: cwd.to_string(),
+            payload: payload.clone(),
+        });
+    }
+
+    fn record(&mut self, session_id: &str, entry: impl FnOnce() -> Entry) {
+        if self
+            .checked_at
+            .is_none_or(|checked| checked.elapsed() >= CHECK_INTERVAL)
+        {
+            self.checked_at = Some(Instant::now());
+            self.capture = read_marker();
+        }
+        let Some(capture) = self.capture.as_ref().filter(|c| c.includes(session_id)) else {
+            return;
+        };
+        if let Err(e) = append(&capture.path, &entry()) {
+            tracing::debug!("Failed to record to {}: {}", capture.path.display(), e);
+        }
+    }
+}
+
+fn append(path: &Path, entry: &Entry) -> std::io::Result<()> {
+    let mut line = serde_json::to_string(entry).map_err(std::io::Error::other)?;
+    line.push('\n');
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(line.as_bytes())
+}
+
+/// Result of replaying a capture
+#[derive(Debug, Default)]
+pub struct Replayed {
+    /// Events the parsers produced, in order
+    pub events: Vec<AgentEvent>,
+    /// Sessions in the registry afterwards
+    pub sessions: Vec<SessionInfo>,
+    /// Lines that weren't capture entries
+    pub skipped: usize,
+}
+
+/// Replay a capture file.
+pub fn replay(path: &Path) -> std::io::Result<Replayed> {
+    replay_from(BufReader::new(std::fs::File::open(path)?))
+}
+
+fn replay_from(reader: impl BufRead) -> std::io::Result<Replayed> {
+    let mut registry = SessionRegistry::new();
+    let mut rollouts = ReplayRollouts::default();
+    let mut replayed = Replayed::default();
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (events, agent) = match serde_json::from_str::<Entry>(&line) {
+            Ok(Entry::Hook { agent, payload }) => {
+                let events = HookAgent::from_str(&agent, true)
+                    .ok()
+                    .and_then(|agent| claude_code::convert(&agent, &payload))
+                    .unwrap_or_default();
+                (events, AgentType::ClaudeCode)
+            }
+            Ok(Entry::Rollout {
+                path,
+                session_id,
+                cwd,
+                payload,
+            }) => (
+                rollouts.apply(&path, &session_id, &cwd, &payload),
+                AgentType::Codex,
+            ),
+            Err(_) => {
+                replayed.skipped += 1;
+                continue;
+            }
+        };
+        for event in events {
+            replayed.events.push(event.clone());
+            registry.process_event_from(event, agent.clone());
+        }
+    }
+    replayed.sessions = registry.get_all();
+    Ok(replayed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SessionState;
+    use serde_json::json;
+
+    #[test]
+    fn capture_filters_by_session_prefix() {
+        let capture = Capture {
+            path: "/tmp/capture.jsonl".into(),
+            session_id: Some("abc".into()),
+        };
+        assert!(capture.includes("abc123"));
+        assert!(!capture.includes("def456"));
+        let all = Capture {
+            session_id: None,
+            ..capture
+        };
+        assert!(all.includes("def456"));
+    }
+
+    #[test]
+    fn replay_runs_hooks_and_rollouts_through_the_registry() {
+        let entries = [
+            Entry::Hook {
+                agent: "claude-code".into(),
+                payload: json!({
+                    "hook_event_name": "SessionStart",
+                    "session_id": "cc1",
+                    "cwd": "/code/aura",
+                }),
+            },
+            Entry::Hook {
+                agent: "claude-code".into(),
+                payload: json!({
+                    "hook_event_name": "Stop",
+                    "session_id": "cc1",
+                    "cwd": "/code/aura",
+                }),
+            },
+            Entry::Rollout {
+                path: "/codex/rollout-cx1.jsonl".into(),
+                session_id: "cx1".into(),
+                cwd: "/code/api".into(),
+                payload: json!({
+                    "type": "response_item",
+                    "payload": {
+                        "type": "function_call",
+                        "name": "shell",
+                        "call_id": "c1",
+                        "arguments": "{\"command\":[\"ls\"]}",
+                    },
+                }),
+            },
+        ];
+        let mut capture: String = entries
+            .iter()
+            .map(|entry| serde_json::to_string(entry).unwrap() + "\n")
+            .collect();
+        capture.push_str("not json\n");
+
+        let first = replay_from(capture.as_bytes()).unwrap();
+        assert_eq!(first.skipped, 1);
+        let state = |id: &str| {
+            first
+                .sessions
+                .iter()
+                .find(|s| s.session_id == id)
+                .map(|s| s.state)
+        };
+        assert_eq!(state("cc1"), Some(SessionState::Idle));
+        assert_eq!(state("cx1"), Some(SessionState::Running));
+
+        // Deterministic: the same capture yields the same events
+        let second = replay_from(capture.as_bytes()).unwrap();
+        assert_eq!(
+            serde_json::to_string(&first.events).unwrap(),
+            serde_json::to_string(&second.events).unwrap()
+        );
+    }
+}
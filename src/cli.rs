@@ -0,0 +1,850 @@
+//! Handlers for CLI subcommands that talk to a running daemon
+//!
+//! Each handler sends an [`IpcMessage`] over the daemon socket and prints a
+//! human-readable result. Errors are returned as strings for `main` to report.
+//!
+//! Read-only commands also take `--json` for launchers (Raycast, Alfred).
+//! Their output is an object tagged with `schema_version`
+//! ([`JSON_SCHEMA_VERSION`]); fields may be added within a version, but
+//! renaming or removing one bumps it. Sessions use the same shape as
+//! `aura shortcut` ([`ShortcutSession`]).
+
+use crate::SessionInfo;
+use crate::export::{self, ExportFormat};
+use crate::health;
+use crate::ipc::{self, IpcMessage, IpcResponse};
+use crate::pause::Source;
+use crate::registry::BulkAction;
+use crate::shortcut::{self, ShortcutSession};
+use crate::transcript::TranscriptMeta;
+use crate::ui::session_list::extract_session_name;
+use chrono::{DateTime, Local, Utc};
+use serde_json::{Value, json};
+use std::path::Path;
+use std::time::Duration;
+
+/// Version of the `--json` output schemas
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+/// Characters of the last prompt `sessions show` prints
+const PROMPT_PREVIEW_MAX: usize = 80;
+
+/// Parse a human duration like `30m`, `2h`, `1h30m`, or `45s`.
+///
+/// Used as a clap `value_parser`. Units: `s`, `m`, `h`, `d`.
+pub fn parse_duration(input: &str) -> Result<Duration, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("empty duration".to_string());
+    }
+
+    let mut total = 0u64;
+    let mut digits = String::new();
+    for c in input.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let unit_secs = match c {
+            's' => 1,
+            'm' => 60,
+            'h' => 60 * 60,
+            'd' => 24 * 60 * 60,
+            other => return Err(format!("unknown duration unit '{other}' in '{input}'")),
+        };
+        let value: u64 = digits
+            .parse()
+            .map_err(|_| format!("missing number before '{c}' in '{input}'"))?;
+        total = total.saturating_add(value.saturating_mul(unit_secs));
+        digits.clear();
+    }
+
+    if !digits.is_empty() {
+        return Err(format!("missing unit after '{digits}' in '{input}'"));
+    }
+    if total == 0 {
+        return Err(format!("duration '{input}' must be greater than zero"));
+    }
+    Ok(Duration::from_secs(total))
+}
+
+/// `aura sessions snooze <id> <duration>`
+pub fn snooze(session_id: &str, duration: Duration) -> Result<(), String> {
+    let session = request_session(IpcMessage::Snooze {
+        session_id: session_id.to_string(),
+        duration_secs: duration.as_secs(),
+    })?;
+    match session.snoozed_until {
+        Some(until) => println!(
+            "Snoozed {} until {}",
+            describe(&session),
+            format_clock(until)
+        ),
+        None => println!("Snoozed {}", describe(&session)),
+    }
+    Ok(())
+}
+
+/// `aura sessions unsnooze <id>`
+pub fn unsnooze(session_id: &str) -> Result<(), String> {
+    let session = request_session(IpcMessage::Unsnooze {
+        session_id: session_id.to_string(),
+    })?;
+    println!("Unsnoozed {}", describe(&session));
+    Ok(())
+}
+
+/// `aura ack <id>`
+pub fn ack(session_id: &str) -> Result<(), String> {
+    let session = request_session(IpcMessage::Ack {
+        session_id: session_id.to_string(),
+    })?;
+    println!("Marked {} seen", describe(&session));
+    Ok(())
+}
+
+/// `aura pause <source>` / `aura resume <source>`
+pub fn set_paused(source: Source, paused: bool) -> Result<(), String> {
+    let message = if paused {
+        IpcMessage::Pause { source }
+    } else {
+        IpcMessage::Resume { source }
+    };
+    match request(message)? {
+        IpcResponse::Pong { .. } => {}
+        other => return Err(format!("unexpected daemon response: {other:?}")),
+    }
+    if paused {
+        println!(
+            "Paused {} (aura resume to apply them again)",
+            source.label()
+        );
+    } else {
+        println!("Resumed {}", source.label());
+    }
+    Ok(())
+}
+
+/// `aura status [--json] [--follow]`
+///
+/// Following prints the state again (text lines prefixed with the time, or
+/// one JSON object per line) whenever the daemon pushes a changed summary,
+/// until the daemon stops.
+pub fn status(json: bool, follow: bool) -> Result<(), String> {
+    let priority = crate::config::load_config().ui.indicator_priority;
+    if !follow {
+        let summary = shortcut::state_summary(&list(false)?, &priority);
+        if json {
+            println!("{}", versioned(summary));
+        } else {
+            println!("{}", format_status(&summary));
+        }
+        return Ok(());
+    }
+
+    let mut last = None;
+    let watched = ipc::watch(|sessions| {
+        let summary = shortcut::state_summary(&sessions, &priority);
+        // Pushed for any change to the list; most leave the summary as it was
+        if last.as_ref() == Some(&summary) {
+            return;
+        }
+        if json {
+            println!("{}", versioned(summary.clone()));
+        } else {
+            let now = crate::reminder::unix_now();
+            println!("{}  {}", format_clock(now), format_status(&summary));
+        }
+        last = Some(summary);
+    });
+    match watched {
+        Ok(()) => Err("the daemon stopped".to_string()),
+        // The daemon's own error, e.g. from one that predates `Watch`
+        Err(e) if e.kind() == std::io::ErrorKind::Other => Err(e.to_string()),
+        Err(e) => Err(format!(
+            "daemon not running ({}): {}",
+            ipc::socket_path().display(),
+            e
+        )),
+    }
+}
+
+/// `aura sessions list [--archived] [--tag <tag>] [--json]`
+pub fn list_sessions(archived: bool, tag: Option<&str>, json: bool) -> Result<(), String> {
+    let mut sessions = list(archived)?;
+    if let Some(tag) = tag {
+        let tag = crate::tags::normalize_tag(tag).ok_or("invalid tag")?;
+        sessions.retain(|s| s.tags.contains(&tag));
+    }
+    sessions.sort_by(|a, b| a.cwd.cmp(&b.cwd).then(a.session_id.cmp(&b.session_id)));
+    if json {
+        println!("{}", sessions_json(&sessions));
+        return Ok(());
+    }
+    if sessions.is_empty() {
+        println!("No {}sessions", if archived { "archived " } else { "" });
+        return Ok(());
+    }
+    let now = crate::reminder::unix_now();
+    for session in &sessions {
+        println!("{}", format_session_line(session, Some(now)));
+    }
+    Ok(())
+}
+
+/// `aura sessions show <id> [--json]`
+pub fn show(session_id: &str, json: bool) -> Result<(), String> {
+    let session = request_session(IpcMessage::GetSession {
+        session_id: session_id.to_string(),
+    })?;
+    let local = LocalDetail::read(&session);
+    if json {
+        println!(
+            "{}",
+            versioned(json!({
+                "session": session,
+                "branch": local.branch,
+                "transcript": local.transcript,
+            }))
+        );
+    } else {
+        print!(
+            "{}",
+            format_session_detail(&session, &local, crate::reminder::unix_now())
+        );
+    }
+    Ok(())
+}
+
+/// `aura sessions tag <id> <tags>...`
+pub fn tag(session_id: &str, tags: &[String]) -> Result<(), String> {
+    let session = request_session(IpcMessage::Tag {
+        session_id: session_id.to_string(),
+        tags: tags.to_vec(),
+    })?;
+    println!(
+        "Tagged {} {}",
+        describe(&session),
+        crate::tags::format_tags(&session.tags)
+    );
+    Ok(())
+}
+
+/// `aura sessions untag <id> <tags>...`
+pub fn untag(session_id: &str, tags: &[String]) -> Result<(), String> {
+    let session = request_session(IpcMessage::Untag {
+        session_id: session_id.to_string(),
+        tags: tags.to_vec(),
+    })?;
+    println!("Untagged {}", describe(&session));
+    Ok(())
+}
+
+/// `aura sessions archive <id>`
+pub fn archive(session_id: &str) -> Result<(), String> {
+    let session = request_session(IpcMessage::Archive {
+        session_id: session_id.to_string(),
+    })?;
+    println!("Archived {}", describe(&session));
+    Ok(())
+}
+
+/// `aura sessions rename <id> <name>`
+pub fn rename(session_id: &str, name: &str) -> Result<(), String> {
+    let session = request_session(IpcMessage::Rename {
+        session_id: session_id.to_string(),
+        name: name.to_string(),
+    })?;
+    println!("Renamed {}", describe(&session));
+    Ok(())
+}
+
+/// `aura sessions restore <id>`
+pub fn restore(session_id: &str) -> Result<(), String> {
+    let session = request_session(IpcMessage::Restore {
+        session_id: session_id.to_string(),
+    })?;
+    println!("Restored {}", describe(&session));
+    Ok(())
+}
+
+/// `aura sessions bulk <action>`
+pub fn bulk(action: BulkAction) -> Result<(), String> {
+    let count = match request(IpcMessage::Bulk { action })? {
+        IpcResponse::Affected { count } => count,
+        other => return Err(format!("unexpected daemon response: {other:?}")),
+    };
+    let verb = match action {
+        BulkAction::ClearIdle | BulkAction::ClearStale => "Archived",
+        BulkAction::MarkAllSeen => "Marked seen",
+    };
+    println!(
+        "{verb} {count} session{}",
+        if count == 1 { "" } else { "s" }
+    );
+    Ok(())
+}
+
+/// `aura export <id> [--format md|json] [--output <file>]`
+pub fn export(session_id: &str, format: ExportFormat, output: Option<&Path>) -> Result<(), String> {
+    let session = request_session(IpcMessage::GetSession {
+        session_id: session_id.to_string(),
+    })?;
+    let Some(transcript_path) = session.transcript_path.as_deref() else {
+        return Err(format!("no transcript known for {}", describe(&session)));
+    };
+    let file = std::fs::File::open(transcript_path)
+        .map_err(|e| format!("can't read transcript {transcript_path}: {e}"))?;
+    let config = crate::config::load_config();
+    if config.privacy {
+        return Err("privacy mode is on; turn it off to export transcripts".to_string());
+    }
+    let summary = export::summarize(
+        &session,
+        std::io::BufReader::new(file),
+        &config.tool_categories(),
+        &config.ignore,
+    );
+    let rendered = export::render(&summary, format);
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, rendered)
+                .map_err(|e| format!("can't write {}: {e}", path.display()))?;
+            eprintln!("Exported {} to {}", describe(&session), path.display());
+        }
+        None => print!("{rendered}"),
+    }
+    Ok(())
+}
+
+/// `aura debug record <file> [--session <id>]` / `aura debug record --stop`
+pub fn record(output: Option<&Path>, session_id: Option<String>, stop: bool) -> Result<(), String> {
+    if stop {
+        match crate::capture::stop().map_err(|e| format!("can't stop recording: {e}"))? {
+            Some(capture) => println!("Stopped recording to {}", capture.path.display()),
+            None => println!("Not recording"),
+        }
+        return Ok(());
+    }
+    let output = output.ok_or("missing capture file")?;
+    let capture = crate::capture::start(output, session_id)
+        .map_err(|e| format!("can't record to {}: {e}", output.display()))?;
+    let scope = match &capture.session_id {
+        Some(id) => format!("session {id}"),
+        None => "all sessions".to_string(),
+    };
+    println!(
+        "Recording {scope} to {} (stop with `aura debug record --stop`)",
+        capture.path.display()
+    );
+    Ok(())
+}
+
+/// `aura debug replay <file> [--events]`
+pub fn replay(capture: &Path, events: bool) -> Result<(), String> {
+    let replayed = crate::capture::replay(capture)
+        .map_err(|e| format!("can't replay {}: {e}", capture.display()))?;
+    if events {
+        for event in &replayed.events {
+            let json = serde_json::to_string(event).map_err(|e| e.to_string())?;
+            println!("{json}");
+        }
+    }
+    println!(
+        "{} events, {} sessions{}",
+        replayed.events.len(),
+        replayed.sessions.len(),
+        match replayed.skipped {
+            0 => String::new(),
+            n => format!(" ({n} unreadable lines skipped)"),
+        }
+    );
+    for session in &replayed.sessions {
+        println!("{}", format_session_line(session, None));
+    }
+    Ok(())
+}
+
+/// Send a request to the daemon, turning transport failures and
+/// `IpcResponse::Error` into an error message.
+pub(crate) fn request(message: IpcMessage) -> Result<IpcResponse, String> {
+    match ipc::send_request(&message) {
+        Ok(IpcResponse::Error { message }) => Err(message),
+        Ok(response) => Ok(response),
+        Err(e) => Err(format!(
+            "daemon not running ({}): {}",
+            ipc::socket_path().display(),
+            e
+        )),
+    }
+}
+
+/// Visible (or archived) sessions
+fn list(archived: bool) -> Result<Vec<SessionInfo>, String> {
+    match request(IpcMessage::ListSessions { archived })? {
+        IpcResponse::Sessions { sessions } => Ok(sessions),
+        other => Err(format!("unexpected daemon response: {other:?}")),
+    }
+}
+
+/// Send a request that is expected to answer with a single session.
+fn request_session(message: IpcMessage) -> Result<SessionInfo, String> {
+    match request(message)? {
+        IpcResponse::Session { session } => Ok(session),
+        other => Err(format!("unexpected daemon response: {other:?}")),
+    }
+}
+
+/// "name (id-prefix)" for confirmation messages
+fn describe(session: &SessionInfo) -> String {
+    let name = session
+        .name
+        .clone()
+        .unwrap_or_else(|| extract_session_name(&session.cwd));
+    let short_id: String = session.session_id.chars().take(8).collect();
+    format!("{name} ({short_id})")
+}
+
+/// One `sessions list` row: short id, state, time in state (given the
+/// current Unix time), name, tags, cwd
+pub(crate) fn format_session_line(session: &SessionInfo, now_unix: Option<u64>) -> String {
+    let short_id: String = session.session_id.chars().take(8).collect();
+    let state = format!("{:?}", session.state).to_lowercase();
+    let name = session
+        .name
+        .clone()
+        .unwrap_or_else(|| extract_session_name(&session.cwd));
+    let snoozed = if session.is_snoozed() {
+        " (snoozed)"
+    } else {
+        ""
+    };
+    let conflict = match session.file_conflicts.len() {
+        _ if session.cwd_conflict => " (shared cwd)".to_string(),
+        0 => String::new(),
+        1 => " (1 shared file)".to_string(),
+        n => format!(" ({n} shared files)"),
+    };
+    let tags = if session.tags.is_empty() {
+        String::new()
+    } else {
+        format!(" {}", crate::tags::format_tags(&session.tags))
+    };
+    let age = now_unix
+        .and_then(|now| session.time_in_state(now))
+        .map(|elapsed| format!("{:<6}  ", health::format_uptime(elapsed.as_secs())))
+        .unwrap_or_default();
+    format!(
+        "{short_id:<8}  {state:<10}  {age}{name}{snoozed}{conflict}{tags}  {}",
+        session.cwd
+    )
+}
+
+/// What `sessions show` reads on this machine besides the daemon's reply.
+/// Empty in privacy mode, which withholds the cwd and transcript.
+#[derive(Debug, Default)]
+struct LocalDetail {
+    /// Git branch checked out in the cwd
+    branch: Option<String>,
+    /// Prompt, tool call and token totals of the transcript
+    transcript: Option<TranscriptMeta>,
+}
+
+impl LocalDetail {
+    fn read(session: &SessionInfo) -> Self {
+        let branch = if session.cwd.is_empty() {
+            None
+        } else {
+            crate::tags::git_branch(Path::new(&session.cwd))
+        };
+        let transcript = session
+            .transcript_path
+            .as_ref()
+            .and_then(|path| std::fs::File::open(path).ok())
+            .map(|file| TranscriptMeta::scan(std::io::BufReader::new(file)));
+        Self { branch, transcript }
+    }
+}
+
+/// `sessions show` text: a heading, then one "key: value" line per known
+/// field, the changed files with their line counts and the state history
+fn format_session_detail(session: &SessionInfo, local: &LocalDetail, now_unix: u64) -> String {
+    let state = session
+        .state_age_label(now_unix)
+        .unwrap_or_else(|| format!("{:?}", session.state).to_lowercase());
+    let mut fields: Vec<(&str, String)> = vec![("state", state), ("cwd", session.cwd.clone())];
+    if let Some(branch) = &local.branch {
+        fields.push(("branch", branch.clone()));
+    }
+    if let Some(model) = &session.model {
+        fields.push(("model", model.clone()));
+    }
+    if !session.tags.is_empty() {
+        fields.push(("tags", crate::tags::format_tags(&session.tags)));
+    }
+    if !session.running_tools.is_empty() {
+        let tools: Vec<String> = session
+            .running_tools
+            .iter()
+            .map(|tool| match &tool.tool_label {
+                Some(label) => format!("{} {label}", tool.tool_name),
+                None => tool.tool_name.clone(),
+            })
+            .collect();
+        fields.push(("tools", tools.join(", ")));
+    }
+    if let Some(meta) = &local.transcript {
+        fields.push((
+            "activity",
+            format!("{} prompts, {} tool calls", meta.prompts, meta.tool_calls),
+        ));
+        if let Some(prompt) = &meta.last_prompt {
+            let prompt = prompt.split_whitespace().collect::<Vec<_>>().join(" ");
+            let preview = crate::agents::truncate(&prompt, PROMPT_PREVIEW_MAX);
+            let ellipsis = if preview.len() < prompt.len() {
+                "…"
+            } else {
+                ""
+            };
+            fields.push(("prompt", format!("{preview}{ellipsis}")));
+        }
+        if meta.input_tokens + meta.output_tokens > 0 {
+            fields.push((
+                "tokens",
+                format!(
+                    "{} in, {} out",
+                    format_tokens(meta.input_tokens),
+                    format_tokens(meta.output_tokens)
+                ),
+            ));
+        }
+    }
+    if let Some(path) = &session.transcript_path {
+        fields.push(("transcript", path.clone()));
+    }
+    if session.cwd_conflict {
+        fields.push(("conflict", "another session shares this cwd".into()));
+    }
+    if let Some(summary) = session.change_summary() {
+        fields.push(("changes", summary));
+    }
+
+    let mut out = format!("{}\n", describe(session));
+    for (key, value) in fields {
+        out.push_str(&format!("  {:<11} {value}\n", format!("{key}:")));
+    }
+    for change in &session.changed_files {
+        let counts = format!("+{}/−{}", change.added, change.removed);
+        let shared = if session.file_conflicts.contains(&change.path) {
+            "  (also edited by another session)"
+        } else {
+            ""
+        };
+        out.push_str(&format!("    {counts:<12} {}{shared}\n", change.path));
+    }
+    if !session.state_history.is_empty() {
+        out.push_str("  history:\n");
+    }
+    let history = &session.state_history;
+    for (i, change) in history.iter().enumerate() {
+        let until = history.get(i + 1).map_or(now_unix, |next| next.at);
+        out.push_str(&format!(
+            "    {}  {:<10}  {}\n",
+            format_clock(change.at),
+            format!("{:?}", change.state).to_lowercase(),
+            health::format_uptime(until.saturating_sub(change.at))
+        ));
+    }
+    out
+}
+
+/// Token count as "950", "12.3k" or "1.2M"
+fn format_tokens(count: u64) -> String {
+    match count {
+        0..1_000 => count.to_string(),
+        1_000..1_000_000 => format!("{:.1}k", count as f64 / 1e3),
+        _ => format!("{:.1}M", count as f64 / 1e6),
+    }
+}
+
+/// Tag a `--json` object with [`JSON_SCHEMA_VERSION`]
+fn versioned(mut value: Value) -> Value {
+    if let Value::Object(map) = &mut value {
+        map.insert("schema_version".into(), JSON_SCHEMA_VERSION.into());
+    }
+    value
+}
+
+/// `sessions list --json` body
+fn sessions_json(sessions: &[SessionInfo]) -> Value {
+    let sessions: Vec<ShortcutSession> = sessions.iter().map(ShortcutSession::from).collect();
+    versioned(json!({ "sessions": sessions }))
+}
+
+/// `aura status` line, e.g. "attention - 3 sessions (1 attention, 1 waiting)"
+fn format_status(summary: &Value) -> String {
+    let count = |key: &str| summary[key].as_u64().unwrap_or_default();
+    let sessions = count("sessions");
+    if sessions == 0 {
+        return "No sessions".to_string();
+    }
+    let details: Vec<String> = ["attention", "waiting", "snoozed"]
+        .iter()
+        .filter(|key| count(key) > 0)
+        .map(|key| format!("{} {key}", count(key)))
+        .collect();
+    let details = if details.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", details.join(", "))
+    };
+    format!(
+        "{} - {sessions} session{}{details}",
+        summary["state"].as_str().unwrap_or("none"),
+        if sessions == 1 { "" } else { "s" }
+    )
+}
+
+/// Format a Unix timestamp as local "HH:MM"
+fn format_clock(unix_ts: u64) -> String {
+    let datetime = DateTime::<Utc>::from_timestamp(unix_ts as i64, 0).unwrap_or_else(Utc::now);
+    let local: DateTime<Local> = datetime.into();
+    local.format("%H:%M").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SessionState;
+
+    #[test]
+    fn parse_duration_single_unit() {
+        assert_eq!(parse_duration("45s"), Ok(Duration::from_secs(45)));
+        assert_eq!(parse_duration("30m"), Ok(Duration::from_secs(30 * 60)));
+        assert_eq!(parse_duration("2h"), Ok(Duration::from_secs(2 * 60 * 60)));
+        assert_eq!(parse_duration("1d"), Ok(Duration::from_secs(24 * 60 * 60)));
+    }
+
+    #[test]
+    fn parse_duration_compound() {
+        assert_eq!(parse_duration("1h30m"), Ok(Duration::from_secs(90 * 60)));
+    }
+
+    #[test]
+    fn parse_duration_rejects_invalid() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("30").is_err());
+        assert!(parse_duration("m").is_err());
+        assert!(parse_duration("5x").is_err());
+        assert!(parse_duration("0m").is_err());
+    }
+
+    #[test]
+    fn describe_uses_name_or_cwd() {
+        let mut session = SessionInfo {
+            session_id: "0123456789abcdef".into(),
+            cwd: "/home/user/project".into(),
+            ..Default::default()
+        };
+        assert_eq!(describe(&session), "project (01234567)");
+
+        session.name = Some("fix login".into());
+        assert_eq!(describe(&session), "fix login (01234567)");
+    }
+
+    #[test]
+    fn format_session_line_columns() {
+        let session = SessionInfo {
+            session_id: "0123456789abcdef".into(),
+            cwd: "/home/user/project".into(),
+            state: SessionState::Idle,
+            ..Default::default()
+        };
+        assert_eq!(
+            format_session_line(&session, None),
+            "01234567  idle        project  /home/user/project"
+        );
+    }
+
+    #[test]
+    fn format_session_line_shows_tags() {
+        let session = SessionInfo {
+            session_id: "0123456789abcdef".into(),
+            cwd: "/home/user/project".into(),
+            state: SessionState::Idle,
+            tags: vec!["billing".into(), "q3".into()],
+            ..Default::default()
+        };
+        assert_eq!(
+            format_session_line(&session, None),
+            "01234567  idle        project #billing #q3  /home/user/project"
+        );
+    }
+
+    #[test]
+    fn format_session_line_flags_conflicts() {
+        let session = SessionInfo {
+            session_id: "0123456789abcdef".into(),
+            cwd: "/home/user/project".into(),
+            state: SessionState::Running,
+            file_conflicts: vec!["/home/user/project/a.rs".into(), "/tmp/b.rs".into()],
+            ..Default::default()
+        };
+        assert_eq!(
+            format_session_line(&session, None),
+            "01234567  running     project (2 shared files)  /home/user/project"
+        );
+    }
+
+    #[test]
+    fn format_session_line_shows_time_in_state() {
+        let session = SessionInfo {
+            session_id: "0123456789abcdef".into(),
+            cwd: "/home/user/project".into(),
+            state: SessionState::Waiting,
+            state_changed_at: Some(1_000),
+            ..Default::default()
+        };
+        assert_eq!(
+            format_session_line(&session, Some(1_000 + 4 * 60)),
+            "01234567  waiting     4m      project  /home/user/project"
+        );
+        assert!(
+            format_session_detail(&session, &LocalDetail::default(), 1_000 + 3 * 3600 + 120)
+                .contains("  state:      waiting 3h 2m\n")
+        );
+    }
+
+    #[test]
+    fn format_session_detail_lists_changes() {
+        let session = SessionInfo {
+            session_id: "0123456789abcdef".into(),
+            cwd: "/code/aura".into(),
+            state: SessionState::Idle,
+            name: Some("fix login".into()),
+            changed_files: vec![
+                crate::FileChange {
+                    path: "/code/aura/a.rs".into(),
+                    added: 1,
+                    removed: 0,
+                },
+                crate::FileChange {
+                    path: "/code/aura/b.rs".into(),
+                    added: 15,
+                    removed: 7,
+                },
+            ],
+            file_conflicts: vec!["/code/aura/b.rs".into()],
+            ..Default::default()
+        };
+        let expected = [
+            "fix login (01234567)",
+            "  state:      idle",
+            "  cwd:        /code/aura",
+            "  changes:    2 files, +16/−7",
+            "    +1/−0        /code/aura/a.rs",
+            "    +15/−7       /code/aura/b.rs  (also edited by another session)",
+        ];
+        assert_eq!(
+            format_session_detail(&session, &LocalDetail::default(), 0),
+            format!("{}\n", expected.join("\n"))
+        );
+    }
+
+    #[test]
+    fn format_session_detail_adds_tools_transcript_and_history() {
+        let session = SessionInfo {
+            session_id: "0123456789abcdef".into(),
+            cwd: "/code/aura".into(),
+            state: SessionState::Running,
+            running_tools: vec![crate::RunningTool {
+                tool_id: "t1".into(),
+                tool_name: "Read".into(),
+                tool_label: Some("login.rs".into()),
+                tool_full_label: None,
+                category: Default::default(),
+            }],
+            state_history: vec![
+                crate::StateChange {
+                    state: SessionState::Running,
+                    at: 1_000,
+                },
+                crate::StateChange {
+                    state: SessionState::Attention,
+                    at: 1_000 + 90,
+                },
+                crate::StateChange {
+                    state: SessionState::Running,
+                    at: 1_000 + 150,
+                },
+            ],
+            ..Default::default()
+        };
+        let mut meta = TranscriptMeta::default();
+        meta.prompts = 3;
+        meta.tool_calls = 12;
+        meta.last_prompt = Some(format!("fix the\nlogin {}", "bug ".repeat(30)));
+        meta.input_tokens = 1_234_567;
+        meta.output_tokens = 950;
+        let local = LocalDetail {
+            branch: Some("fix/login".into()),
+            transcript: Some(meta),
+        };
+
+        let detail = format_session_detail(&session, &local, 1_000 + 450);
+        let lines: Vec<&str> = detail.lines().collect();
+        assert_eq!(lines[2], "  cwd:        /code/aura");
+        assert_eq!(lines[3], "  branch:     fix/login");
+        assert_eq!(lines[4], "  tools:      Read login.rs");
+        assert_eq!(lines[5], "  activity:   3 prompts, 12 tool calls");
+        assert!(lines[6].starts_with("  prompt:     fix the login bug bug"));
+        assert!(lines[6].ends_with('…'));
+        assert_eq!(lines[7], "  tokens:     1.2M in, 950 out");
+        assert_eq!(lines[8], "  history:");
+        assert_eq!(
+            lines[9..],
+            [
+                format!("    {}  running     1m", format_clock(1_000)),
+                format!("    {}  attention   1m", format_clock(1_090)),
+                format!("    {}  running     5m", format_clock(1_150)),
+            ]
+        );
+    }
+
+    #[test]
+    fn sessions_json_is_versioned() {
+        let session = SessionInfo {
+            session_id: "abc".into(),
+            cwd: "/code/aura".into(),
+            state: SessionState::Waiting,
+            ..Default::default()
+        };
+        let value = sessions_json(&[session]);
+        assert_eq!(value["schema_version"], JSON_SCHEMA_VERSION);
+        assert_eq!(value["sessions"][0]["id"], "abc");
+        assert_eq!(value["sessions"][0]["state"], "waiting");
+    }
+
+    #[test]
+    fn format_status_summarizes_counts() {
+        let priority = crate::config::UiConfig::default().indicator_priority;
+        assert_eq!(
+            format_status(&shortcut::state_summary(&[], &priority)),
+            "No sessions"
+        );
+        let sessions = vec![
+            SessionInfo {
+                state: SessionState::Attention,
+                ..Default::default()
+            },
+            SessionInfo {
+                state: SessionState::Running,
+                ..Default::default()
+            },
+        ];
+        assert_eq!(
+            format_status(&shortcut::state_summary(&sessions, &priority)),
+            "attention - 2 sessions (1 attention)"
+        );
+    }
+}
@@ -0,0 +1,145 @@
+//! Clock - the daemon's source of "now"
+//!
+//! Time-based registry behavior (stale promotion, tool display expiry,
+//! snoozes, the timestamps in [`crate::SessionInfo`]) reads the time from a
+//! [`SharedClock`] instead of calling `Instant::now()` directly. The daemon
+//! uses [`SystemClock`]; tests use a [`ManualClock`] and move it forward
+//! explicitly.
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Source of the current time
+pub trait Clock: Send + Sync {
+    /// Monotonic time, for deadlines and ages
+    fn now(&self) -> Instant;
+    /// Wall-clock time, for timestamps shown to users
+    fn system_now(&self) -> SystemTime;
+}
+
+/// The real clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn system_now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A clock that only moves when told to
+#[derive(Debug)]
+pub struct ManualClock {
+    instant: Instant,
+    system: SystemTime,
+    elapsed: Mutex<Duration>,
+}
+
+impl ManualClock {
+    /// Start at the current time
+    pub fn new() -> Self {
+        Self {
+            instant: Instant::now(),
+            system: SystemTime::now(),
+            elapsed: Mutex::new(Duration::ZERO),
+        }
+    }
+
+    /// Move the clock forward
+    pub fn advance(&self, by: Duration) {
+        *self.elapsed.lock().unwrap_or_else(|e| e.into_inner()) += by;
+    }
+
+    fn elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.instant + self.elapsed()
+    }
+
+    fn system_now(&self) -> SystemTime {
+        self.system + self.elapsed()
+    }
+}
+
+/// Cheaply cloneable handle to a [`Clock`]; the system clock by default
+#[derive(Clone)]
+pub struct SharedClock(Arc<dyn Clock>);
+
+impl SharedClock {
+    pub fn new(clock: Arc<dyn Clock>) -> Self {
+        Self(clock)
+    }
+
+    pub fn now(&self) -> Instant {
+        self.0.now()
+    }
+
+    pub fn system_now(&self) -> SystemTime {
+        self.0.system_now()
+    }
+
+    /// Unix timestamp (seconds) of an instant, past or future
+    pub fn unix_timestamp(&self, instant: Instant) -> u64 {
+        let now = self.now();
+        let system_time = match instant.checked_duration_since(now) {
+            Some(ahead) => self.system_now() + ahead,
+            None => self.system_now() - now.saturating_duration_since(instant),
+        };
+        system_time
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    }
+}
+
+impl Default for SharedClock {
+    fn default() -> Self {
+        Self(Arc::new(SystemClock))
+    }
+}
+
+impl fmt::Debug for SharedClock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedClock")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manual_clock_moves_both_times_together() {
+        let manual = Arc::new(ManualClock::new());
+        let clock = SharedClock::new(manual.clone());
+        let (start, start_system) = (clock.now(), clock.system_now());
+        assert_eq!(clock.now(), start);
+
+        manual.advance(Duration::from_secs(90));
+        assert_eq!(clock.now() - start, Duration::from_secs(90));
+        assert_eq!(
+            clock.system_now().duration_since(start_system).unwrap(),
+            Duration::from_secs(90)
+        );
+        // Instants convert relative to the clock's own "now"
+        assert_eq!(
+            clock.unix_timestamp(start),
+            clock.unix_timestamp(clock.now()) - 90
+        );
+    }
+}
@@ -0,0 +1,250 @@
+//! Event coalescing - apply agent events to the registry in small batches
+//!
+//! Busy sessions send hundreds of tool events a minute. Instead of locking the
+//! registry and marking it dirty once per event, the IPC server and the Codex
+//! watcher hand events to a [`Coalescer`], which collects everything that
+//! arrives within [`BATCH_WINDOW`] and applies it under one lock (publishing
+//! one snapshot), in arrival order. A run of `Activity` events for one session
+//! collapses into the latest, since only the last one changes anything.
+//! Stamped hook events keep their [`EventStamp`] for the registry to reorder
+//! (see [`process_stamped`](crate::registry::SessionRegistry::process_stamped)),
+//! and the task applies them once their reorder window passes.
+
+use crate::ipc::EventStamp;
+use crate::logging;
+use crate::registry::SharedRegistry;
+use crate::{AgentEvent, AgentType};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{debug, trace, warn};
+
+/// How long events are collected before being applied
+pub const BATCH_WINDOW: Duration = Duration::from_millis(50);
+
+/// An event with the default agent for sessions it creates and its stamp
+pub type Queued = (AgentEvent, AgentType, Option<EventStamp>);
+
+/// Events received within one batch window, oldest first
+#[derive(Debug, Default)]
+pub struct Batch {
+    events: Vec<Queued>,
+    /// Index of each session's latest event in `events`
+    latest: HashMap<String, usize>,
+    merged: usize,
+}
+
+impl Batch {
+    /// Queue an event, folding it into the session's previous `Activity`
+    pub fn push(&mut self, event: AgentEvent, agent: AgentType) {
+        self.push_stamped(event, agent, None);
+    }
+
+    /// Queue an event stamped by its sender
+    pub fn push_stamped(&mut self, event: AgentEvent, agent: AgentType, stamp: Option<EventStamp>) {
+        let session_id = event.session_id().to_string();
+        if let AgentEvent::Activity { .. } = event
+            && let Some(&index) = self.latest.get(&session_id)
+            && matches!(self.events[index].0, AgentEvent::Activity { .. })
+        {
+            self.events[index] = (event, agent, stamp);
+            self.merged += 1;
+            return;
+        }
+        self.latest.insert(session_id, self.events.len());
+        self.events.push((event, agent, stamp));
+    }
+
+    /// Events folded into an earlier one
+    pub fn merged(&self) -> usize {
+        self.merged
+    }
+
+    /// Events to apply, in arrival order
+    pub fn into_events(self) -> Vec<Queued> {
+        self.events
+    }
+}
+
+/// Running totals, logged with each batch
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub received: u64,
+    pub merged: u64,
+    pub batches: u64,
+}
+
+/// Handle for queueing events; cheap to clone
+#[derive(Debug, Clone)]
+pub struct Coalescer {
+    tx: mpsc::UnboundedSender<Queued>,
+}
+
+impl Coalescer {
+    /// Start the batching task on the current tokio runtime.
+    pub fn spawn(registry: SharedRegistry) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(run(rx, registry));
+        Self { tx }
+    }
+
+    /// Queue an event; `agent` is the default for sessions it creates.
+    /// Events from a paused source ([`crate::pause`]) are dropped.
+    pub fn send(&self, event: AgentEvent, agent: AgentType) {
+        self.send_stamped(event, agent, None);
+    }
+
+    /// Queue an event with the stamp its sender gave it
+    pub fn send_stamped(&self, event: AgentEvent, agent: AgentType, stamp: Option<EventStamp>) {
+        if crate::pause::is_paused(crate::pause::Source::of(&agent)) {
+            trace!(target: logging::REGISTRY, ?event, "source paused; dropping event");
+            return;
+        }
+        crate::health::record_event(&agent);
+        if self.tx.send((event, agent, stamp)).is_err() {
+            warn!(target: logging::REGISTRY, "Event coalescer stopped; dropping event");
+        }
+    }
+}
+
+/// Next event, or `None` once every sender is gone; meanwhile applies the
+/// stamped events the registry holds as their reorder windows pass
+async fn next_event(
+    rx: &mut mpsc::UnboundedReceiver<Queued>,
+    registry: &SharedRegistry,
+) -> Option<Queued> {
+    loop {
+        let release = registry.read(|registry| registry.next_reorder_release());
+        let Some(at) = release.flatten() else {
+            return rx.recv().await;
+        };
+        tokio::select! {
+            queued = rx.recv() => return queued,
+            _ = tokio::time::sleep_until(at.into()) => {
+                registry.update(|registry| registry.release_reordered());
+            }
+        }
+    }
+}
+
+async fn run(mut rx: mpsc::UnboundedReceiver<Queued>, registry: SharedRegistry) {
+    let mut stats = Stats::default();
+    while let Some((event, agent, stamp)) = next_event(&mut rx, &registry).await {
+        let mut batch = Batch::default();
+        batch.push_stamped(event, agent, stamp);
+        tokio::time::sleep(BATCH_WINDOW).await;
+        let mut received = 1;
+        while let Ok((event, agent, stamp)) = rx.try_recv() {
+            batch.push_stamped(event, agent, stamp);
+            received += 1;
+        }
+
+        stats.received += received;
+        stats.merged += batch.merged() as u64;
+        stats.batches += 1;
+        debug!(
+            target: logging::REGISTRY,
+            received,
+            merged = batch.merged(),
+            total_received = stats.received,
+            total_merged = stats.merged,
+            batches = stats.batches,
+            "applying event batch"
+        );
+
+        registry.update(|registry| {
+            for (event, agent, stamp) in batch.into_events() {
+                registry.process_stamped(event, agent, stamp);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::SessionRegistry;
+
+    fn activity(session_id: &str, cwd: &str) -> AgentEvent {
+        AgentEvent::Activity {
+            session_id: session_id.into(),
+            cwd: cwd.into(),
+        }
+    }
+
+    fn tool(session_id: &str, tool_id: &str) -> AgentEvent {
+        AgentEvent::ToolStarted {
+            session_id: session_id.into(),
+            cwd: "/tmp".into(),
+            tool_id: tool_id.into(),
+            tool_name: "Read".into(),
+            tool_label: None,
+            tool_full_label: None,
+        }
+    }
+
+    fn describe(batch: Batch) -> Vec<String> {
+        batch
+            .into_events()
+            .into_iter()
+            .map(|(event, ..)| match event {
+                AgentEvent::Activity { session_id, cwd } => format!("{session_id}:activity:{cwd}"),
+                AgentEvent::ToolStarted {
+                    session_id,
+                    tool_id,
+                    ..
+                } => format!("{session_id}:tool:{tool_id}"),
+                other => format!("{other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn keeps_arrival_order() {
+        let mut batch = Batch::default();
+        batch.push(tool("s1", "t1"), AgentType::ClaudeCode);
+        batch.push(tool("s2", "t2"), AgentType::Codex);
+        batch.push(tool("s1", "t3"), AgentType::ClaudeCode);
+        assert_eq!(batch.merged(), 0);
+        assert_eq!(
+            describe(batch),
+            vec!["s1:tool:t1", "s2:tool:t2", "s1:tool:t3"]
+        );
+    }
+
+    #[test]
+    fn merges_repeated_activity_per_session() {
+        let mut batch = Batch::default();
+        batch.push(activity("s1", "/a"), AgentType::ClaudeCode);
+        batch.push(activity("s2", "/b"), AgentType::ClaudeCode);
+        batch.push(activity("s1", "/a2"), AgentType::ClaudeCode);
+        assert_eq!(batch.merged(), 1);
+        assert_eq!(describe(batch), vec!["s1:activity:/a2", "s2:activity:/b"]);
+    }
+
+    #[test]
+    fn activity_after_other_events_is_kept() {
+        let mut batch = Batch::default();
+        batch.push(activity("s1", "/a"), AgentType::ClaudeCode);
+        batch.push(tool("s1", "t1"), AgentType::ClaudeCode);
+        batch.push(activity("s1", "/a"), AgentType::ClaudeCode);
+        assert_eq!(batch.merged(), 0);
+        assert_eq!(
+            describe(batch),
+            vec!["s1:activity:/a", "s1:tool:t1", "s1:activity:/a"]
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_batch_once() {
+        let registry = SharedRegistry::new(SessionRegistry::new());
+        let mut changes = registry.subscribe();
+        let coalescer = Coalescer::spawn(registry.clone());
+        coalescer.send(tool("s1", "t1"), AgentType::Codex);
+        coalescer.send(activity("s2", "/tmp"), AgentType::ClaudeCode);
+
+        changes.changed().await.unwrap();
+        assert_eq!(changes.borrow_and_update().len(), 2);
+        assert!(!changes.has_changed().unwrap());
+    }
+}
@@ -1,11 +1,14 @@
 //! Persistence for user preferences (config) and runtime state.
 //!
-//! - **Config** (`config.json`): theme preference, saved to the platform config directory.
-//! - **State** (`state.json`): indicator position, saved to the platform data directory.
+//! - **Config** (`config.json`): theme, UI, notification, hook, editor API, and tag
+//!   rule preferences, saved to the platform config directory.
+//! - **State** (`state.json`): indicator position and manual session order, saved to the
+//!   platform data directory.
 //!
 //! On macOS both resolve to `~/Library/Application Support/aura/`.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 // ---------------------------------------------------------------------------
@@ -27,6 +30,53 @@ pub struct Config {
     /// how to handle unknown values.
     #[serde(default = "default_theme")]
     pub theme: String,
+    /// Per-state icon colors for each theme.
+    #[serde(default, skip_serializing_if = "StateColorConfig::is_empty")]
+    pub state_colors: StateColorConfig,
+    /// Session list and window preferences.
+    #[serde(default)]
+    pub ui: UiConfig,
+    /// Slack / Discord notifications while away from the desk.
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+    /// Shell commands run on session transitions.
+    #[serde(default)]
+    pub hooks: HooksConfig,
+    /// Localhost status endpoint for editor extensions.
+    #[serde(default)]
+    pub editor_api: EditorApiConfig,
+    /// Rules tagging sessions by working directory or git branch.
+    #[serde(default)]
+    pub tags: Vec<TagRule>,
+    /// How long finished sessions stay in the registry.
+    #[serde(default)]
+    pub retention: RetentionConfig,
+    /// Extra tool name (or `prefix*`) to category mappings, see [`crate::tools`].
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tool_categories: BTreeMap<String, crate::tools::ToolCategory>,
+    /// Tools and paths left out of recent activity and stats, see [`crate::ignore`].
+    #[serde(default, skip_serializing_if = "crate::ignore::IgnoreConfig::is_empty")]
+    pub ignore: crate::ignore::IgnoreConfig,
+    /// Allow/deny rules scoping the daemon to some agents, projects and
+    /// tools, see [`crate::filter`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub filters: Vec<crate::filter::FilterRule>,
+    /// Hide names, paths and labels everywhere, see [`crate::privacy`].
+    #[serde(default)]
+    pub privacy: bool,
+    /// Let Aura.app offer new releases, see [`crate::update`].
+    #[serde(default = "default_true")]
+    pub check_for_updates: bool,
+    /// Agent homes for several profiles (Codex homes, Claude config dirs).
+    #[serde(default, skip_serializing_if = "AgentsConfig::is_empty")]
+    pub agents: AgentsConfig,
+}
+
+impl Config {
+    /// Tool category table with the `tool_categories` overrides applied
+    pub fn tool_categories(&self) -> crate::tools::ToolCategories {
+        crate::tools::ToolCategories::new(&self.tool_categories)
+    }
 }
 
 fn default_theme() -> String {
@@ -37,6 +87,614 @@ impl Default for Config {
     fn default() -> Self {
         Self {
             theme: default_theme(),
+            state_colors: StateColorConfig::default(),
+            ui: UiConfig::default(),
+            notifications: NotificationsConfig::default(),
+            hooks: HooksConfig::default(),
+            editor_api: EditorApiConfig::default(),
+            tags: Vec::new(),
+            retention: RetentionConfig::default(),
+            tool_categories: BTreeMap::new(),
+            ignore: crate::ignore::IgnoreConfig::default(),
+            filters: Vec::new(),
+            privacy: false,
+            check_for_updates: true,
+            agents: AgentsConfig::default(),
+        }
+    }
+}
+
+/// UI preferences (the `"ui"` object in config.json).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UiConfig {
+    /// Session rows shown before the list collapses the rest into a
+    /// "+N more…" footer.
+    #[serde(default = "default_max_visible_sessions")]
+    pub max_visible_sessions: usize,
+    /// One column of sessions, or a grid as wide as the sessions need.
+    #[serde(default)]
+    pub layout: Layout,
+    /// Stacking level of the HUD windows.
+    #[serde(default)]
+    pub window_level: WindowLevel,
+    /// Show the HUD on every Space rather than only the one it was opened on.
+    #[serde(default = "default_true")]
+    pub all_spaces: bool,
+    /// Hide the indicator window while no non-stale sessions exist.
+    #[serde(default)]
+    pub auto_hide: bool,
+    /// Flash a colored bar along the top of the screen when a session
+    /// needs attention.
+    #[serde(default)]
+    pub edge_pulse: bool,
+    /// States whose entry flashes the bar, in their own colors: `attention`
+    /// (permission prompts) and/or `waiting` (idle prompts).
+    #[serde(default = "default_edge_pulse_states")]
+    pub edge_pulse_states: Vec<crate::SessionState>,
+    /// App used by "Open in terminal" in the row context menu.
+    #[serde(default = "default_terminal_app")]
+    pub terminal_app: String,
+    /// App used by "Open in editor" in the row context menu.
+    #[serde(default = "default_editor_app")]
+    pub editor_app: String,
+    /// Remind (clock badge + notification) about Idle sessions nobody has
+    /// marked seen after this many minutes; off when unset.
+    #[serde(default)]
+    pub review_reminder_minutes: Option<u64>,
+    /// Minutes in Attention or Waiting after which a row's time in state
+    /// turns amber; 0 turns it off.
+    #[serde(default = "default_state_age_warn_minutes")]
+    pub state_age_warn_minutes: u64,
+    /// Minutes in Attention or Waiting after which it turns red; 0 turns
+    /// it off.
+    #[serde(default = "default_state_age_alert_minutes")]
+    pub state_age_alert_minutes: u64,
+    /// Session states that drive the aggregate indicator, highest priority
+    /// first; states left out are ignored.
+    #[serde(default = "default_indicator_priority")]
+    pub indicator_priority: Vec<IndicatorRule>,
+    /// How the collapsed indicator shows the sessions.
+    #[serde(default)]
+    pub indicator_style: IndicatorStyle,
+    /// Installed font family for the HUD text instead of the embedded Maple
+    /// Mono (which is used when the family isn't installed).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub font_family: Option<String>,
+    /// Size of the session list's body text in points (default 12, 9-18);
+    /// the other text and the row heights scale with it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub font_size: Option<f32>,
+    /// Minutes an ended session stays under the list's collapsed "Ended
+    /// recently" row; 0 turns the row off.
+    #[serde(default = "default_ended_visible_minutes")]
+    pub ended_visible_minutes: u64,
+    /// How much the HUD animates; macOS "Reduce Motion" lowers `full` to
+    /// `reduced`.
+    #[serde(default)]
+    pub animations: Animations,
+    /// How tool labels too long for their row are shown.
+    #[serde(default)]
+    pub marquee: MarqueeConfig,
+    /// Sample each agent's CPU use for a row badge, and stop animating on
+    /// battery while an agent is busy, see [`crate::sysinfo`].
+    #[serde(default)]
+    pub show_cpu_load: bool,
+    /// Highlight the session the frontmost terminal is in (macOS). Probes
+    /// every 1.5s while sessions are live, and reading the front tab over
+    /// AppleScript asks for macOS Automation access.
+    #[serde(default)]
+    pub follow_front_terminal: bool,
+}
+
+/// One `ui.indicator_priority` entry, e.g. `{ "state": "waiting", "as": "attention" }`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndicatorRule {
+    pub state: crate::SessionState,
+    /// Show sessions in `state` as this state instead (only Attention and
+    /// Waiting have their own indicator look; everything else shows Running)
+    #[serde(default, rename = "as", skip_serializing_if = "Option::is_none")]
+    pub show_as: Option<crate::SessionState>,
+}
+
+impl IndicatorRule {
+    /// State whose indicator look this rule shows
+    pub fn shown_state(&self) -> crate::SessionState {
+        self.show_as.unwrap_or(self.state)
+    }
+}
+
+fn default_max_visible_sessions() -> usize {
+    5
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_indicator_priority() -> Vec<IndicatorRule> {
+    use crate::SessionState;
+    [
+        SessionState::Attention,
+        SessionState::Waiting,
+        SessionState::Running,
+        SessionState::Compacting,
+        SessionState::Idle,
+        SessionState::Stale,
+    ]
+    .into_iter()
+    .map(|state| IndicatorRule {
+        state,
+        show_as: None,
+    })
+    .collect()
+}
+
+fn default_state_age_warn_minutes() -> u64 {
+    5
+}
+
+fn default_state_age_alert_minutes() -> u64 {
+    15
+}
+
+fn default_ended_visible_minutes() -> u64 {
+    5
+}
+
+fn default_terminal_app() -> String {
+    "Terminal".to_string()
+}
+
+fn default_editor_app() -> String {
+    "Visual Studio Code".to_string()
+}
+
+fn default_edge_pulse_states() -> Vec<crate::SessionState> {
+    vec![crate::SessionState::Attention]
+}
+
+impl UiConfig {
+    /// States that flash the screen edge: none unless `edge_pulse` is on,
+    /// and only Attention and Waiting (the others don't wait on the user)
+    pub fn edge_pulse_states(&self) -> Vec<crate::SessionState> {
+        use crate::SessionState;
+        if !self.edge_pulse {
+            return Vec::new();
+        }
+        self.edge_pulse_states
+            .iter()
+            .copied()
+            .filter(|state| matches!(state, SessionState::Attention | SessionState::Waiting))
+            .collect()
+    }
+
+    /// `review_reminder_minutes` as a duration (`None` when off)
+    pub fn review_reminder(&self) -> Option<std::time::Duration> {
+        self.review_reminder_minutes
+            .filter(|minutes| *minutes > 0)
+            .map(|minutes| std::time::Duration::from_secs(minutes * 60))
+    }
+
+    /// `ended_visible_minutes` as a duration (`None` when off)
+    pub fn ended_visible(&self) -> Option<std::time::Duration> {
+        (self.ended_visible_minutes > 0)
+            .then(|| std::time::Duration::from_secs(self.ended_visible_minutes * 60))
+    }
+
+    /// `state_age_warn_minutes` / `state_age_alert_minutes` as durations
+    pub fn state_age_thresholds(&self) -> StateAgeThresholds {
+        let minutes =
+            |minutes: u64| (minutes > 0).then(|| std::time::Duration::from_secs(minutes * 60));
+        StateAgeThresholds {
+            warn: minutes(self.state_age_warn_minutes),
+            alert: minutes(self.state_age_alert_minutes),
+        }
+    }
+}
+
+/// When a session left waiting on the user gets flagged, per level (`None`
+/// when off)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StateAgeThresholds {
+    pub warn: Option<std::time::Duration>,
+    pub alert: Option<std::time::Duration>,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            max_visible_sessions: default_max_visible_sessions(),
+            layout: Layout::default(),
+            window_level: WindowLevel::default(),
+            all_spaces: default_true(),
+            auto_hide: false,
+            edge_pulse: false,
+            edge_pulse_states: default_edge_pulse_states(),
+            terminal_app: default_terminal_app(),
+            editor_app: default_editor_app(),
+            review_reminder_minutes: None,
+            state_age_warn_minutes: default_state_age_warn_minutes(),
+            state_age_alert_minutes: default_state_age_alert_minutes(),
+            indicator_priority: default_indicator_priority(),
+            indicator_style: IndicatorStyle::default(),
+            font_family: None,
+            font_size: None,
+            ended_visible_minutes: default_ended_visible_minutes(),
+            animations: Animations::default(),
+            marquee: MarqueeConfig::default(),
+            show_cpu_load: false,
+            follow_front_terminal: false,
+        }
+    }
+}
+
+/// Session list arrangement (`ui.layout`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Layout {
+    /// A single 320px column
+    #[default]
+    List,
+    /// Up to three columns side by side, added as sessions accumulate
+    Grid,
+}
+
+/// Where the HUD windows sit in the window stack (`ui.window_level`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WindowLevel {
+    /// Floats above everything, including full-screen apps
+    #[default]
+    AboveFullscreen,
+    /// Floats above normal windows, hidden behind full-screen apps
+    Floating,
+    /// Sticks to the desktop, below all normal windows
+    Desktop,
+}
+
+/// Collapsed indicator rendering (`ui.indicator_style`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IndicatorStyle {
+    /// One icon for the aggregate state
+    #[default]
+    Icon,
+    /// The aggregate icon ringed by one arc per session, colored by its state
+    Segments,
+}
+
+/// HUD animation level (`ui.animations`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Animations {
+    /// Every animation
+    #[default]
+    Full,
+    /// No movement (shake, spin, breathing, slides); tool names switch
+    /// without cross-fading
+    Reduced,
+    /// Nothing animates; the first running tool is shown
+    Off,
+}
+
+/// Long tool labels (`ui.marquee`), e.g. `{ "speed": 20, "delay_ms": 1500 }`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MarqueeConfig {
+    /// Scroll a hovered row's label end to end; when off, labels keep both
+    /// ends around a middle "…" instead.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Scroll speed in pixels per second.
+    #[serde(default = "default_marquee_speed")]
+    pub speed: f32,
+    /// Milliseconds the label rests at each end before scrolling on.
+    #[serde(default = "default_marquee_delay_ms")]
+    pub delay_ms: u64,
+}
+
+fn default_marquee_speed() -> f32 {
+    24.0
+}
+
+fn default_marquee_delay_ms() -> u64 {
+    1200
+}
+
+impl Default for MarqueeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_true(),
+            speed: default_marquee_speed(),
+            delay_ms: default_marquee_delay_ms(),
+        }
+    }
+}
+
+/// State colors per theme (the `"state_colors"` object in config.json), e.g.
+/// `{ "liquid-dark": { "attention": "#ff9f0a", "waiting": "#0a84ff" } }`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateColorConfig {
+    #[serde(
+        rename = "liquid-dark",
+        default,
+        skip_serializing_if = "StatePalette::is_empty"
+    )]
+    pub dark: StatePalette,
+    #[serde(
+        rename = "liquid-light",
+        default,
+        skip_serializing_if = "StatePalette::is_empty"
+    )]
+    pub light: StatePalette,
+}
+
+impl StateColorConfig {
+    pub fn is_empty(&self) -> bool {
+        self.dark.is_empty() && self.light.is_empty()
+    }
+}
+
+/// `"#rrggbb"` color per session state; states left out keep the theme's
+/// colors.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatePalette {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub running: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attention: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub waiting: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compacting: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idle: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stale: Option<String>,
+}
+
+impl StatePalette {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Color configured for `state`
+    pub fn get(&self, state: crate::SessionState) -> Option<&str> {
+        use crate::SessionState;
+        match state {
+            SessionState::Running => &self.running,
+            SessionState::Attention => &self.attention,
+            SessionState::Waiting => &self.waiting,
+            SessionState::Compacting => &self.compacting,
+            SessionState::Idle => &self.idle,
+            SessionState::Stale => &self.stale,
+        }
+        .as_deref()
+    }
+}
+
+/// Away notifications (the `"notifications"` object in config.json).
+///
+/// Nothing is sent unless `slack` or `discord` is configured.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationsConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slack: Option<SlackConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub discord: Option<DiscordConfig>,
+    /// Only notify once the user has been idle (no keyboard or mouse input)
+    /// for this many minutes. Unset notifies immediately.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub only_when_idle_minutes: Option<u64>,
+    /// States that trigger a notification when a session enters them.
+    #[serde(default = "default_notify_states")]
+    pub states: Vec<crate::SessionState>,
+    /// Message template. Placeholders: `{name}`, `{project}`, `{state}`,
+    /// `{duration}`, `{cwd}`.
+    #[serde(default = "default_notify_template")]
+    pub template: String,
+}
+
+/// Slack destination: an incoming webhook, or a bot token plus channel.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlackConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bot_token: Option<String>,
+    /// Channel for `bot_token` (e.g. `#agents` or a channel id).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    /// Overrides the shared template for Slack.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+}
+
+/// Discord destination: a channel webhook.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiscordConfig {
+    pub webhook_url: String,
+    /// Overrides the shared template for Discord.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+}
+
+fn default_notify_states() -> Vec<crate::SessionState> {
+    use crate::SessionState;
+    vec![
+        SessionState::Attention,
+        SessionState::Waiting,
+        SessionState::Idle,
+    ]
+}
+
+fn default_notify_template() -> String {
+    "{name} ({project}) is {state} after {duration}".to_string()
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            slack: None,
+            discord: None,
+            only_when_idle_minutes: None,
+            states: default_notify_states(),
+            template: default_notify_template(),
+        }
+    }
+}
+
+impl NotificationsConfig {
+    /// Whether any destination is configured.
+    pub fn is_enabled(&self) -> bool {
+        self.slack.is_some() || self.discord.is_some()
+    }
+}
+
+/// Event hooks (the `"hooks"` object in config.json).
+///
+/// Every key other than `debounce_secs` is a trigger (see
+/// [`crate::hooks::TRIGGERS`]) mapped to a shell command.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HooksConfig {
+    /// Minimum seconds between runs of the same trigger for one session.
+    #[serde(default = "default_hook_debounce_secs")]
+    pub debounce_secs: u64,
+    #[serde(flatten)]
+    pub commands: std::collections::BTreeMap<String, String>,
+}
+
+fn default_hook_debounce_secs() -> u64 {
+    5
+}
+
+impl Default for HooksConfig {
+    fn default() -> Self {
+        Self {
+            debounce_secs: default_hook_debounce_secs(),
+            commands: Default::default(),
+        }
+    }
+}
+
+/// Editor status endpoint (the `"editor_api"` object in config.json).
+///
+/// See [`crate::editor_api`] for the HTTP contract.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EditorApiConfig {
+    /// Serve `GET /session` on localhost; off by default.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Port bound on 127.0.0.1.
+    #[serde(default = "default_editor_api_port")]
+    pub port: u16,
+}
+
+fn default_editor_api_port() -> u16 {
+    47355
+}
+
+impl Default for EditorApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: default_editor_api_port(),
+        }
+    }
+}
+
+/// Session retention (the `"retention"` object in config.json).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionConfig {
+    /// Archive sessions that have been Stale this long, e.g. `"2h"` (see
+    /// [`crate::cli::parse_duration`]). Unset keeps them until removed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remove_stale_after: Option<String>,
+    /// Sessions kept, archived ones included; the oldest archived or Stale
+    /// sessions are dropped past it.
+    #[serde(default = "default_max_sessions")]
+    pub max_sessions: usize,
+}
+
+fn default_max_sessions() -> usize {
+    200
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            remove_stale_after: None,
+            max_sessions: default_max_sessions(),
+        }
+    }
+}
+
+impl RetentionConfig {
+    /// The registry policy; an unparseable `remove_stale_after` is ignored
+    pub fn policy(&self) -> crate::registry::Retention {
+        let remove_stale_after = self.remove_stale_after.as_deref().and_then(|value| {
+            crate::cli::parse_duration(value)
+                .inspect_err(|e| tracing::warn!("Ignoring retention.remove_stale_after: {}", e))
+                .ok()
+        });
+        crate::registry::Retention {
+            remove_stale_after,
+            max_sessions: self.max_sessions,
+        }
+    }
+}
+
+/// Tag rule (an entry of the `"tags"` array in config.json).
+///
+/// Applied when a session starts; every pattern given must match. Patterns
+/// are globs (`*`, `?`), see [`crate::tags`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagRule {
+    pub tag: String,
+    /// Pattern for the session's working directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    /// Pattern for the git branch checked out in the working directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+}
+
+/// Agent profiles (the `"agents"` object in config.json).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentsConfig {
+    /// Codex homes, each watched on its own. Empty watches `CODEX_HOME` (or
+    /// `~/.codex`) unlabeled.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub codex_homes: Vec<AgentHome>,
+    /// Claude Code config dirs (`CLAUDE_CONFIG_DIR`), to label their
+    /// sessions; hooks report sessions whatever the dir.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub claude_config_dirs: Vec<AgentHome>,
+}
+
+impl AgentsConfig {
+    pub fn is_empty(&self) -> bool {
+        self.codex_homes.is_empty() && self.claude_config_dirs.is_empty()
+    }
+}
+
+/// One agent home, e.g. `{ "path": "~/.codex-work", "profile": "work" }`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentHome {
+    /// Directory; a leading `~` is the home directory.
+    pub path: String,
+    /// Tag added to sessions from this home.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+}
+
+impl AgentHome {
+    /// `path` with a leading `~` expanded
+    pub fn dir(&self) -> PathBuf {
+        match self.path.strip_prefix('~') {
+            Some(rest) if rest.is_empty() || rest.starts_with('/') => dirs::home_dir()
+                .unwrap_or_default()
+                .join(rest.trim_start_matches('/')),
+            _ => PathBuf::from(&self.path),
         }
     }
 }
@@ -54,20 +712,23 @@ pub struct State {
     /// Indicator Y position (logical pixels from top edge).
     #[serde(default)]
     pub indicator_y: Option<f64>,
+    /// Manual project order (cwds) from dragging rows in the session list.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub session_order: Vec<String>,
 }
 
 // ---------------------------------------------------------------------------
 // Directory helpers
 // ---------------------------------------------------------------------------
 
-/// Aura config directory (e.g. `~/Library/Application Support/aura/`).
+/// Aura config directory (see [`crate::paths`]).
 fn config_dir() -> Option<PathBuf> {
-    dirs::config_dir().map(|p| p.join("aura"))
+    crate::paths::config_dir()
 }
 
-/// Aura data directory (e.g. `~/Library/Application Support/aura/`).
-fn data_dir() -> Option<PathBuf> {
-    dirs::data_dir().map(|p| p.join("aura"))
+/// Aura state directory (see [`crate::paths`]).
+fn state_dir() -> Option<PathBuf> {
+    crate::paths::state_dir()
 }
 
 // ---------------------------------------------------------------------------
@@ -95,7 +756,7 @@ pub fn save_config(config: &Config) -> Result<(), std::io::Error> {
 
 /// Load state from disk, returning defaults if the file is missing or invalid.
 pub fn load_state() -> State {
-    let Some(path) = data_dir().map(|d| d.join("state.json")) else {
+    let Some(path) = state_dir().map(|d| d.join("state.json")) else {
         return State::default();
     };
     load_state_from(&path)
@@ -103,8 +764,8 @@ pub fn load_state() -> State {
 
 /// Save state to disk.
 pub fn save_state(state: &State) -> Result<(), std::io::Error> {
-    let dir = data_dir()
-        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "data dir not found"))?;
+    let dir = state_dir()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "state dir not found"))?;
     save_state_to(state, &dir.join("state.json"))
 }
 
@@ -169,6 +830,7 @@ mod tests {
     fn config_default_values() {
         let config = Config::default();
         assert_eq!(config.theme, "system");
+        assert_eq!(config.ui.max_visible_sessions, 5);
     }
 
     #[test]
@@ -176,6 +838,7 @@ mod tests {
         let state = State::default();
         assert!(state.indicator_x.is_none());
         assert!(state.indicator_y.is_none());
+        assert!(state.session_order.is_empty());
     }
 
     #[test]
@@ -187,6 +850,7 @@ mod tests {
 
         let config = Config {
             theme: "liquid-dark".to_string(),
+            ..Default::default()
         };
         save_config_to(&config, &path).unwrap();
         let loaded = load_config_from(&path);
@@ -205,11 +869,13 @@ mod tests {
         let state = State {
             indicator_x: Some(100.0),
             indicator_y: Some(200.0),
+            session_order: vec!["/work/api".into(), "/work/web".into()],
         };
         save_state_to(&state, &path).unwrap();
         let loaded = load_state_from(&path);
         assert_eq!(loaded.indicator_x, Some(100.0));
         assert_eq!(loaded.indicator_y, Some(200.0));
+        assert_eq!(loaded.session_order, ["/work/api", "/work/web"]);
 
         let _ = fs::remove_dir_all(&dir);
     }
@@ -248,4 +914,337 @@ mod tests {
 
         let _ = fs::remove_dir_all(&dir);
     }
+
+    #[test]
+    fn config_partial_ui_section_uses_defaults() {
+        let config: Config = serde_json::from_str(r#"{"theme":"system","ui":{}}"#).unwrap();
+        assert_eq!(config.ui.max_visible_sessions, 5);
+
+        let config: Config = serde_json::from_str(r#"{"ui":{"max_visible_sessions":8}}"#).unwrap();
+        assert_eq!(config.ui.max_visible_sessions, 8);
+        assert_eq!(config.theme, "system");
+    }
+
+    #[test]
+    fn config_window_level_values() {
+        let config = Config::default();
+        assert_eq!(config.ui.window_level, WindowLevel::AboveFullscreen);
+        assert!(config.ui.all_spaces);
+
+        let config: Config =
+            serde_json::from_str(r#"{"ui":{"window_level":"desktop","all_spaces":false}}"#)
+                .unwrap();
+        assert_eq!(config.ui.window_level, WindowLevel::Desktop);
+        assert!(!config.ui.all_spaces);
+
+        let config: Config = serde_json::from_str(r#"{"ui":{"window_level":"floating"}}"#).unwrap();
+        assert_eq!(config.ui.window_level, WindowLevel::Floating);
+    }
+
+    #[test]
+    fn config_open_apps() {
+        let config = Config::default();
+        assert_eq!(config.ui.terminal_app, "Terminal");
+        assert_eq!(config.ui.editor_app, "Visual Studio Code");
+
+        let config: Config =
+            serde_json::from_str(r#"{"ui":{"terminal_app":"Ghostty","editor_app":"Zed"}}"#)
+                .unwrap();
+        assert_eq!(config.ui.terminal_app, "Ghostty");
+        assert_eq!(config.ui.editor_app, "Zed");
+    }
+
+    #[test]
+    fn config_review_reminder_is_opt_in() {
+        assert_eq!(Config::default().ui.review_reminder(), None);
+        let config: Config =
+            serde_json::from_str(r#"{"ui":{"review_reminder_minutes":20}}"#).unwrap();
+        assert_eq!(
+            config.ui.review_reminder(),
+            Some(std::time::Duration::from_secs(20 * 60))
+        );
+    }
+
+    #[test]
+    fn config_state_age_thresholds() {
+        let minutes = |m: u64| Some(std::time::Duration::from_secs(m * 60));
+        let defaults = Config::default().ui.state_age_thresholds();
+        assert_eq!(defaults.warn, minutes(5));
+        assert_eq!(defaults.alert, minutes(15));
+        let config: Config = serde_json::from_str(
+            r#"{"ui":{"state_age_warn_minutes":0,"state_age_alert_minutes":30}}"#,
+        )
+        .unwrap();
+        let thresholds = config.ui.state_age_thresholds();
+        assert_eq!(thresholds.warn, None);
+        assert_eq!(thresholds.alert, minutes(30));
+    }
+
+    #[test]
+    fn config_ended_visible() {
+        assert_eq!(
+            Config::default().ui.ended_visible(),
+            Some(std::time::Duration::from_secs(5 * 60))
+        );
+        let config: Config = serde_json::from_str(r#"{"ui":{"ended_visible_minutes":0}}"#).unwrap();
+        assert_eq!(config.ui.ended_visible(), None);
+    }
+
+    #[test]
+    fn config_tool_categories() {
+        let config: Config =
+            serde_json::from_str(r#"{"tool_categories":{"mcp__linear__*":"vcs"}}"#).unwrap();
+        assert_eq!(
+            config
+                .tool_categories()
+                .categorize("mcp__linear__create_issue"),
+            crate::tools::ToolCategory::Vcs
+        );
+        assert!(Config::default().tool_categories.is_empty());
+    }
+
+    #[test]
+    fn config_ignore() {
+        let config: Config = serde_json::from_str(r#"{"ignore":{"paths":["*.lock"]}}"#).unwrap();
+        assert!(config.ignore.ignores_path("/code/app/Cargo.lock"));
+        assert!(config.ignore.tools.is_empty());
+        let json = serde_json::to_value(Config::default()).unwrap();
+        assert!(json.get("ignore").is_none());
+    }
+
+    #[test]
+    fn config_filters() {
+        let config: Config = serde_json::from_str(
+            r#"{"filters":[
+                {"action":"allow","cwd":"/code/*"},
+                {"action":"deny","agent":"codex","tool":"shell"}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(config.filters.len(), 2);
+        assert_eq!(config.filters[0].action, crate::filter::FilterAction::Allow);
+        assert_eq!(config.filters[1].agent, Some(crate::AgentType::Codex));
+        assert_eq!(config.filters[1].tool.as_deref(), Some("shell"));
+        let json = serde_json::to_value(Config::default()).unwrap();
+        assert!(json.get("filters").is_none());
+    }
+
+    #[test]
+    fn config_retention() {
+        let default = Config::default().retention.policy();
+        assert_eq!(default.remove_stale_after, None);
+        assert_eq!(default.max_sessions, 200);
+
+        let config: Config =
+            serde_json::from_str(r#"{"retention":{"remove_stale_after":"2h","max_sessions":50}}"#)
+                .unwrap();
+        let policy = config.retention.policy();
+        assert_eq!(
+            policy.remove_stale_after,
+            Some(std::time::Duration::from_secs(2 * 3600))
+        );
+        assert_eq!(policy.max_sessions, 50);
+
+        let config: Config =
+            serde_json::from_str(r#"{"retention":{"remove_stale_after":"soon"}}"#).unwrap();
+        assert_eq!(config.retention.policy().remove_stale_after, None);
+    }
+
+    #[test]
+    fn config_indicator_priority() {
+        use crate::SessionState;
+        let default = Config::default().ui.indicator_priority;
+        assert_eq!(default.len(), 6);
+        assert_eq!(default[0].state, SessionState::Attention);
+
+        let config: Config = serde_json::from_str(
+            r#"{"ui":{"indicator_priority":[
+                {"state":"attention"},
+                {"state":"waiting","as":"attention"},
+                {"state":"running"}
+            ]}}"#,
+        )
+        .unwrap();
+        let rules = config.ui.indicator_priority;
+        assert_eq!(rules.len(), 3);
+        assert_eq!(rules[1].shown_state(), SessionState::Attention);
+        assert_eq!(rules[2].shown_state(), SessionState::Running);
+    }
+
+    #[test]
+    fn config_layout() {
+        assert_eq!(Config::default().ui.layout, Layout::List);
+        let config: Config = serde_json::from_str(r#"{"ui":{"layout":"grid"}}"#).unwrap();
+        assert_eq!(config.ui.layout, Layout::Grid);
+    }
+
+    #[test]
+    fn config_indicator_style() {
+        assert_eq!(Config::default().ui.indicator_style, IndicatorStyle::Icon);
+        let config: Config =
+            serde_json::from_str(r#"{"ui":{"indicator_style":"segments"}}"#).unwrap();
+        assert_eq!(config.ui.indicator_style, IndicatorStyle::Segments);
+    }
+
+    #[test]
+    fn config_state_colors() {
+        assert!(Config::default().state_colors.is_empty());
+        let config: Config = serde_json::from_str(
+            r##"{"state_colors":{"liquid-dark":{"attention":"#ff9f0a"},"liquid-light":{}}}"##,
+        )
+        .unwrap();
+        let dark = &config.state_colors.dark;
+        assert_eq!(dark.get(crate::SessionState::Attention), Some("#ff9f0a"));
+        assert_eq!(dark.get(crate::SessionState::Running), None);
+        assert!(config.state_colors.light.is_empty());
+        let json = serde_json::to_string(&config.state_colors).unwrap();
+        assert_eq!(json, r##"{"liquid-dark":{"attention":"#ff9f0a"}}"##);
+    }
+
+    #[test]
+    fn config_font() {
+        let ui = Config::default().ui;
+        assert_eq!((ui.font_family, ui.font_size), (None, None));
+        let config: Config =
+            serde_json::from_str(r#"{"ui":{"font_family":"SF Mono","font_size":14}}"#).unwrap();
+        assert_eq!(config.ui.font_family.as_deref(), Some("SF Mono"));
+        assert_eq!(config.ui.font_size, Some(14.0));
+    }
+
+    #[test]
+    fn config_animations() {
+        assert_eq!(Config::default().ui.animations, Animations::Full);
+        let config: Config = serde_json::from_str(r#"{"ui":{"animations":"reduced"}}"#).unwrap();
+        assert_eq!(config.ui.animations, Animations::Reduced);
+        assert!(serde_json::from_str::<Config>(r#"{"ui":{"animations":"slow"}}"#).is_err());
+    }
+
+    #[test]
+    fn config_marquee() {
+        assert_eq!(Config::default().ui.marquee, MarqueeConfig::default());
+        let config: Config =
+            serde_json::from_str(r#"{"ui":{"marquee":{"speed":12.5,"enabled":false}}}"#).unwrap();
+        assert_eq!(
+            config.ui.marquee,
+            MarqueeConfig {
+                enabled: false,
+                speed: 12.5,
+                delay_ms: 1200,
+            }
+        );
+    }
+
+    #[test]
+    fn config_auto_hide_is_opt_in() {
+        assert!(!Config::default().ui.auto_hide);
+
+        let config: Config = serde_json::from_str(r#"{"ui":{"auto_hide":true}}"#).unwrap();
+        assert!(config.ui.auto_hide);
+        assert_eq!(config.ui.max_visible_sessions, 5);
+    }
+
+    #[test]
+    fn config_edge_pulse_is_opt_in() {
+        assert!(!Config::default().ui.edge_pulse);
+        assert!(Config::default().ui.edge_pulse_states().is_empty());
+        let config: Config = serde_json::from_str(r#"{"ui":{"edge_pulse":true}}"#).unwrap();
+        assert!(config.ui.edge_pulse);
+        assert_eq!(
+            config.ui.edge_pulse_states(),
+            [crate::SessionState::Attention]
+        );
+        let config: Config = serde_json::from_str(
+            r#"{"ui":{"edge_pulse":true,"edge_pulse_states":["waiting","idle"]}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.ui.edge_pulse_states(),
+            [crate::SessionState::Waiting]
+        );
+    }
+
+    #[test]
+    fn config_notifications_off_by_default() {
+        let config = Config::default();
+        assert!(!config.notifications.is_enabled());
+        assert_eq!(config.notifications.states.len(), 3);
+
+        let config: Config = serde_json::from_str(
+            r#"{"notifications":{
+                "discord":{"webhook_url":"https://discord.test/hook"},
+                "only_when_idle_minutes":5,
+                "states":["attention"]
+            }}"#,
+        )
+        .unwrap();
+        assert!(config.notifications.is_enabled());
+        assert_eq!(config.notifications.only_when_idle_minutes, Some(5));
+        assert_eq!(
+            config.notifications.states,
+            vec![crate::SessionState::Attention]
+        );
+        assert!(config.notifications.template.contains("{name}"));
+    }
+
+    #[test]
+    fn config_hooks_map_triggers_to_commands() {
+        assert!(Config::default().hooks.commands.is_empty());
+
+        let config: Config = serde_json::from_str(
+            r#"{"hooks":{"attention":"say hi","session_ended":"true","debounce_secs":30}}"#,
+        )
+        .unwrap();
+        assert_eq!(config.hooks.debounce_secs, 30);
+        assert_eq!(config.hooks.commands.len(), 2);
+        assert_eq!(config.hooks.commands["attention"], "say hi");
+
+        // Round-trips without nesting the commands
+        let json = serde_json::to_value(&config.hooks).unwrap();
+        assert_eq!(json["session_ended"], "true");
+    }
+
+    #[test]
+    fn config_tag_rules() {
+        assert!(Config::default().tags.is_empty());
+
+        let config: Config = serde_json::from_str(
+            r#"{"tags":[{"tag":"billing","cwd":"*/billing-*"},{"tag":"hotfix","branch":"hotfix/*"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(config.tags.len(), 2);
+        assert_eq!(config.tags[0].cwd.as_deref(), Some("*/billing-*"));
+        assert_eq!(config.tags[1].branch.as_deref(), Some("hotfix/*"));
+        assert_eq!(config.tags[1].cwd, None);
+    }
+
+    #[test]
+    fn config_editor_api_is_opt_in() {
+        let default = Config::default().editor_api;
+        assert!(!default.enabled);
+        assert_eq!(default.port, 47355);
+
+        let config: Config = serde_json::from_str(r#"{"editor_api":{"enabled":true}}"#).unwrap();
+        assert!(config.editor_api.enabled);
+        assert_eq!(config.editor_api.port, 47355);
+    }
+
+    #[test]
+    fn config_agent_homes() {
+        let config: Config = serde_json::from_str(
+            r#"{"agents":{"codex_homes":[
+                {"path":"~/.codex","profile":"personal"},
+                {"path":"/srv/codex-work"}
+            ]}}"#,
+        )
+        .unwrap();
+        let homes = &config.agents.codex_homes;
+        assert_eq!(homes[0].profile.as_deref(), Some("personal"));
+        assert_eq!(homes[0].dir(), dirs::home_dir().unwrap().join(".codex"));
+        assert_eq!(homes[1].dir(), PathBuf::from("/srv/codex-work"));
+        assert!(homes[1].profile.is_none());
+        assert!(config.agents.claude_config_dirs.is_empty());
+
+        let json = serde_json::to_value(Config::default()).unwrap();
+        assert!(json.get("agents").is_none());
+    }
 }
@@ -0,0 +1,279 @@
+//! Demo mode - the same attractive HUD on every run
+//!
+//! `aura --demo` (hidden) starts the HUD on a registry seeded with made-up
+//! sessions instead of listening for agents: one per state, with tools,
+//! plans, models and tags (fewer with `--demo <scenario>`). Its clock never
+//! moves and the animation seed is fixed, so documentation screenshots and
+//! release videos come out the same each time. `--demo-theme` overrides the
+//! configured theme, and `--screenshot <png>` opens the session list, turns
+//! animations off, makes the windows opaque, saves the HUD to the file and
+//! quits - which the visual regression tests (`tests/visual_test.rs`) build on.
+
+use crate::clock::{ManualClock, SharedClock};
+use crate::registry::SessionRegistry;
+use crate::{AgentEvent, AgentType, FileChange, PlanProgress};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+/// Animation seed used instead of the time of day
+pub const ANIMATION_SEED: u64 = 42;
+
+/// How long the HUD settles (windows open, rows slide in) before a screenshot
+pub const SCREENSHOT_DELAY: Duration = Duration::from_secs(2);
+
+/// Which demo sessions to show (`--demo <scenario>`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Scenario {
+    /// One session per live state
+    #[default]
+    All,
+    /// Two running sessions
+    Running,
+    /// A session asking for permission next to two running ones
+    Attention,
+    /// No sessions at all
+    Empty,
+}
+
+impl Scenario {
+    fn includes(self, session_id: &str) -> bool {
+        match self {
+            Self::All => true,
+            Self::Running => ["demo-billing", "demo-web"].contains(&session_id),
+            Self::Attention => ["demo-billing", "demo-web", "demo-api"].contains(&session_id),
+            Self::Empty => false,
+        }
+    }
+}
+
+/// How the demo runs, from the command line
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub scenario: Scenario,
+    /// Theme instead of config.json's (`liquid-dark` or `liquid-light`)
+    pub theme: Option<String>,
+    /// Save the HUD here and quit
+    pub screenshot: Option<PathBuf>,
+}
+
+static OPTIONS: OnceLock<Options> = OnceLock::new();
+
+/// Turn demo mode on
+pub fn set_enabled(options: Options) {
+    let _ = OPTIONS.set(options);
+}
+
+pub fn enabled() -> bool {
+    OPTIONS.get().is_some()
+}
+
+/// Theme forced by `--demo-theme`
+pub fn theme() -> Option<&'static str> {
+    OPTIONS.get()?.theme.as_deref()
+}
+
+/// Where `--screenshot` saves the HUD
+pub fn screenshot_path() -> Option<&'static Path> {
+    OPTIONS.get()?.screenshot.as_deref()
+}
+
+/// Events of the `scenario`'s demo sessions, in order
+pub fn events(scenario: Scenario) -> Vec<AgentEvent> {
+    let mut events = Vec::new();
+    let mut session = |id: &str, cwd: &str, name: &str, agent: AgentType, model: &str| {
+        events.push(AgentEvent::SessionStarted {
+            session_id: id.into(),
+            cwd: cwd.into(),
+            agent,
+            terminal: None,
+        });
+        events.push(AgentEvent::SessionNameUpdated {
+            session_id: id.into(),
+            name: name.into(),
+        });
+        events.push(AgentEvent::ModelUpdated {
+            session_id: id.into(),
+            model: model.into(),
+        });
+    };
+    session(
+        "demo-billing",
+        "/Users/demo/code/billing",
+        "invoice export",
+        AgentType::ClaudeCode,
+        "claude-sonnet-4-5",
+    );
+    session(
+        "demo-web",
+        "/Users/demo/code/web",
+        "checkout redesign",
+        AgentType::Codex,
+        "gpt-5-codex",
+    );
+    session(
+        "demo-api",
+        "/Users/demo/code/api",
+        "rate limiter",
+        AgentType::ClaudeCode,
+        "claude-opus-4-1",
+    );
+    session(
+        "demo-docs",
+        "/Users/demo/code/docs",
+        "release notes",
+        AgentType::ClaudeCode,
+        "claude-haiku-4-5",
+    );
+    session(
+        "demo-mobile",
+        "/Users/demo/code/mobile",
+        "offline sync",
+        AgentType::Codex,
+        "gpt-5-codex",
+    );
+
+    events.extend([
+        AgentEvent::SessionTagged {
+            session_id: "demo-billing".into(),
+            tags: vec!["work".into()],
+        },
+        AgentEvent::PlanUpdated {
+            session_id: "demo-billing".into(),
+            progress: PlanProgress {
+                completed: 3,
+                total: 7,
+                current: Some("stream rows to CSV".into()),
+            },
+        },
+        AgentEvent::FilesTouched {
+            session_id: "demo-billing".into(),
+            changes: vec![FileChange {
+                path: "/Users/demo/code/billing/src/export.rs".into(),
+                added: 48,
+                removed: 12,
+            }],
+        },
+        AgentEvent::ToolStarted {
+            session_id: "demo-billing".into(),
+            cwd: "/Users/demo/code/billing".into(),
+            tool_id: "demo-tool-1".into(),
+            tool_name: "Edit".into(),
+            tool_label: Some("src/export.rs".into()),
+            tool_full_label: None,
+        },
+        AgentEvent::ToolStarted {
+            session_id: "demo-web".into(),
+            cwd: "/Users/demo/code/web".into(),
+            tool_id: "demo-tool-2".into(),
+            tool_name: "Bash".into(),
+            tool_label: Some("pnpm test checkout".into()),
+            tool_full_label: None,
+        },
+        AgentEvent::NeedsAttention {
+            session_id: "demo-api".into(),
+            cwd: "/Users/demo/code/api".into(),
+            message: Some("Allow cargo bench?".into()),
+        },
+        AgentEvent::WaitingForInput {
+            session_id: "demo-docs".into(),
+            cwd: "/Users/demo/code/docs".into(),
+            message: None,
+        },
+        AgentEvent::Compacting {
+            session_id: "demo-mobile".into(),
+            cwd: "/Users/demo/code/mobile".into(),
+        },
+    ]);
+    events.retain(|event| scenario.includes(event.session_id()));
+    events
+}
+
+/// Registry holding the demo sessions, on a clock that never moves
+pub fn registry(scenario: Scenario) -> SessionRegistry {
+    let clock = SharedClock::new(Arc::new(ManualClock::new()));
+    let mut registry = SessionRegistry::with_clock(clock);
+    for event in events(scenario) {
+        registry.process_event(event);
+    }
+    registry
+}
+
+/// Save the screen area `(x, y, width, height)` (points) to `path` as PNG
+pub fn capture(area: (f32, f32, f32, f32), path: &Path) -> Result<(), String> {
+    if !cfg!(target_os = "macos") {
+        return Err("screenshots need macOS (screencapture)".to_string());
+    }
+    let (x, y, width, height) = area;
+    let status = std::process::Command::new("screencapture")
+        .arg("-x")
+        .arg(format!("-R{x},{y},{width},{height}"))
+        .arg(path)
+        .status()
+        .map_err(|e| format!("screencapture: {e}"))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("screencapture exited with {status}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SessionState;
+
+    #[test]
+    fn demo_sessions_cover_every_live_state_and_repeat_exactly() {
+        let sessions = registry(Scenario::All).get_all();
+        assert_eq!(sessions.len(), 5);
+        for state in [
+            SessionState::Running,
+            SessionState::Attention,
+            SessionState::Waiting,
+            SessionState::Compacting,
+        ] {
+            assert!(sessions.iter().any(|s| s.state == state), "{state:?}");
+        }
+        let billing = sessions
+            .iter()
+            .find(|s| s.session_id == "demo-billing")
+            .unwrap();
+        assert_eq!(billing.name.as_deref(), Some("invoice export"));
+        assert_eq!(billing.running_tools.len(), 1);
+
+        let ids = |sessions: Vec<crate::SessionInfo>| {
+            sessions
+                .into_iter()
+                .map(|s| (s.session_id, s.state))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(registry(Scenario::All).get_all()), ids(sessions));
+    }
+
+    #[test]
+    fn scenarios_pick_their_sessions() {
+        let states = |scenario| {
+            let mut states: Vec<SessionState> = registry(scenario)
+                .get_all()
+                .into_iter()
+                .map(|s| s.state)
+                .collect();
+            states.sort_by_key(|state| format!("{state:?}"));
+            states
+        };
+        assert_eq!(
+            states(Scenario::Running),
+            vec![SessionState::Running, SessionState::Running]
+        );
+        assert_eq!(
+            states(Scenario::Attention),
+            vec![
+                SessionState::Attention,
+                SessionState::Running,
+                SessionState::Running
+            ]
+        );
+        assert!(states(Scenario::Empty).is_empty());
+    }
+}
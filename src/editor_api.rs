@@ -0,0 +1,382 @@
+//! Editor status endpoint - a tiny localhost HTTP API for editor extensions
+//!
+//! Enabled with `"editor_api": { "enabled": true }` in config.json. Serves
+//! one long-poll endpoint on `127.0.0.1:<port>` (default 47355):
+//!
+//! ```text
+//! GET /session?cwd=<workspace folder>[&wait=<version>]
+//! ```
+//!
+//! The response describes every session working in (or above) that folder:
+//!
+//! ```json
+//! {
+//!   "cwd": "/code/aura",
+//!   "state": "waiting",
+//!   "version": "5f0c7d2a9e31b8c4",
+//!   "sessions": [
+//!     { "id": "0199…", "name": "fix login", "cwd": "/code/aura",
+//!       "state": "waiting", "snoozed": false }
+//!   ]
+//! }
+//! ```
+//!
+//! `state` is the most urgent session state, or `null` without sessions.
+//! `version` changes only when something in the response changes. Pass the
+//! last `version` as `wait` and the request is held until it changes (or
+//! ~25s pass, answering with the same version) - so a client loops on the
+//! endpoint and only wakes up on real state changes. See
+//! `docs/editor-api.md` for the full contract and a VS Code example.
+//!
+//! Only loopback `Host` headers are accepted (DNS rebinding) and no CORS
+//! headers are sent, so web pages can't read session names or paths.
+
+use crate::config::EditorApiConfig;
+use crate::registry::{SessionRegistry, SharedRegistry};
+use crate::ui::session_list::extract_session_name;
+use crate::{SessionInfo, SessionState};
+use serde::Serialize;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Long-poll requests are answered after this long without changes
+const LONG_POLL_TIMEOUT: Duration = Duration::from_secs(25);
+/// Requests larger than this are rejected
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+/// Connections that haven't sent a whole request head by then are closed
+const HEAD_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Session as seen by editors
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct EditorSession {
+    pub id: String,
+    pub name: String,
+    pub cwd: String,
+    pub state: SessionState,
+    pub snoozed: bool,
+}
+
+/// Body of `GET /session`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProjectStatus {
+    pub cwd: String,
+    pub state: Option<SessionState>,
+    pub version: String,
+    pub sessions: Vec<EditorSession>,
+}
+
+impl ProjectStatus {
+    /// Hide session names and paths (privacy mode); `version` still follows
+    /// the real sessions
+    fn redact(&mut self) {
+        for session in &mut self.sessions {
+            session.name = crate::privacy::redacted_name(&session.id);
+            session.cwd.clear();
+        }
+    }
+}
+
+/// Status of the sessions related to a workspace folder: sessions inside it
+/// (a subdirectory of the workspace) or containing it (a monorepo root).
+/// Paths match by component, and sessions without a cwd relate to nothing.
+pub fn project_status(sessions: &[SessionInfo], cwd: &str) -> ProjectStatus {
+    let workspace = Path::new(cwd);
+    let mut related: Vec<EditorSession> = sessions
+        .iter()
+        .filter(|s| !s.cwd.is_empty() && !cwd.is_empty())
+        .filter(|s| {
+            let session_cwd = Path::new(&s.cwd);
+            session_cwd.starts_with(workspace) || workspace.starts_with(session_cwd)
+        })
+        .map(|s| EditorSession {
+            id: s.session_id.clone(),
+            name: s
+                .name
+                .clone()
+                .unwrap_or_else(|| extract_session_name(&s.cwd)),
+            cwd: s.cwd.clone(),
+            state: s.state,
+            snoozed: s.is_snoozed(),
+        })
+        .collect();
+    related.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let state = related
+        .iter()
+        .filter(|s| !s.snoozed)
+        .map(|s| s.state)
+        .max_by_key(SessionState::urgency);
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    related.hash(&mut hasher);
+    ProjectStatus {
+        cwd: cwd.to_string(),
+        state,
+        version: format!("{:016x}", hasher.finish()),
+        sessions: related,
+    }
+}
+
+/// A parsed request line
+#[derive(Debug, PartialEq, Eq)]
+struct SessionRequest {
+    cwd: String,
+    wait: Option<String>,
+}
+
+/// Parse the request head; `Err` carries the HTTP status line to answer with
+fn parse_request(head: &str) -> Result<SessionRequest, &'static str> {
+    let mut lines = head.lines();
+    let request_line = lines.next().unwrap_or_default();
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+
+    let host_ok = lines
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("host"))
+        .is_some_and(|(_, value)| is_loopback_host(value.trim()));
+    if !host_ok {
+        return Err("403 Forbidden");
+    }
+    if method != "GET" {
+        return Err("405 Method Not Allowed");
+    }
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    if path != "/session" {
+        return Err("404 Not Found");
+    }
+
+    let mut cwd = None;
+    let mut wait = None;
+    for pair in query.split('&') {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        match key {
+            "cwd" => cwd = Some(percent_decode(value)),
+            "wait" => wait = Some(percent_decode(value)),
+            _ => {}
+        }
+    }
+    match cwd {
+        Some(cwd) if !cwd.is_empty() => Ok(SessionRequest { cwd, wait }),
+        _ => Err("400 Bad Request"),
+    }
+}
+
+/// `localhost`, `127.0.0.1` or `[::1]`, with an optional port
+fn is_loopback_host(host: &str) -> bool {
+    let name = match host.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or_default(),
+        None => host.split(':').next().unwrap_or_default(),
+    };
+    matches!(name, "localhost" | "127.0.0.1" | "::1")
+}
+
+/// Decode `%XX` escapes and `+` in a query value
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or("");
+                match u8::from_str_radix(hex, 16) {
+                    Ok(byte) => {
+                        out.push(byte);
+                        i += 3;
+                        continue;
+                    }
+                    Err(_) => out.push(b'%'),
+                }
+            }
+            b'+' => out.push(b' '),
+            byte => out.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Read the request head (up to the blank line)
+async fn read_head(stream: &mut TcpStream) -> Option<String> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+        if buf.len() > MAX_REQUEST_BYTES {
+            return None;
+        }
+        let n = stream.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+    Some(String::from_utf8_lossy(&buf).into_owned())
+}
+
+async fn handle(mut stream: TcpStream, registry: SharedRegistry) {
+    let Ok(Some(head)) = tokio::time::timeout(HEAD_TIMEOUT, read_head(&mut stream)).await else {
+        return;
+    };
+    let (status, body) = match parse_request(&head) {
+        Ok(request) => {
+            let deadline = tokio::time::Instant::now() + LONG_POLL_TIMEOUT;
+            let mut changes = registry.observe();
+            let mut current = project_status(&registry.snapshot(), &request.cwd);
+            while request.wait.as_deref() == Some(current.version.as_str()) {
+                let Some(changes) = changes.as_mut() else {
+                    break;
+                };
+                let Ok(Some(_)) = tokio::time::timeout_at(deadline, changes.recv()).await else {
+                    break;
+                };
+                while changes.try_recv().is_ok() {}
+                // Through the lock: the change is sent before the snapshot
+                let sessions = registry.read(SessionRegistry::get_all).unwrap_or_default();
+                current = project_status(&sessions, &request.cwd);
+            }
+            if crate::privacy::enabled() {
+                current.redact();
+            }
+            (
+                "200 OK",
+                serde_json::to_string(&current).unwrap_or_default(),
+            )
+        }
+        Err(status) => (status, format!(r#"{{"error":"{status}"}}"#)),
+    };
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    if let Err(e) = stream.write_all(response.as_bytes()).await {
+        tracing::debug!("Failed to write editor API response: {}", e);
+    }
+}
+
+/// Serve the editor API until the runtime shuts down.
+pub async fn serve(config: EditorApiConfig, registry: SharedRegistry) {
+    let listener = match TcpListener::bind(("127.0.0.1", config.port)).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            tracing::warn!("Failed to bind editor API on port {}: {}", config.port, e);
+            return;
+        }
+    };
+    tracing::info!("Editor API listening on http://127.0.0.1:{}", config.port);
+    loop {
+        match listener.accept().await {
+            Ok((stream, _addr)) => {
+                tokio::spawn(handle(stream, registry.clone()));
+            }
+            Err(e) => tracing::warn!("Failed to accept editor API connection: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(id: &str, cwd: &str, state: SessionState) -> SessionInfo {
+        SessionInfo {
+            session_id: id.into(),
+            cwd: cwd.into(),
+            state,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn project_status_collects_related_sessions() {
+        let sessions = vec![
+            session("a", "/code/aura", SessionState::Running),
+            session("b", "/code/aura/web", SessionState::Waiting),
+            session("c", "/code/other", SessionState::Attention),
+        ];
+        let status = project_status(&sessions, "/code/aura");
+        assert_eq!(status.state, Some(SessionState::Waiting));
+        let ids: Vec<_> = status.sessions.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b"]);
+
+        let empty = project_status(&sessions, "/tmp");
+        assert_eq!(empty.state, None);
+        assert!(empty.sessions.is_empty());
+    }
+
+    #[test]
+    fn project_status_skips_unrelated_prefixes_and_missing_cwds() {
+        let sessions = vec![
+            session("a", "/code/a", SessionState::Running),
+            session("b", "", SessionState::Attention),
+            session("c", "/code/ab", SessionState::Waiting),
+        ];
+        let status = project_status(&sessions, "/code/a");
+        let ids: Vec<_> = status.sessions.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["a"]);
+        assert_eq!(status.state, Some(SessionState::Running));
+
+        assert!(project_status(&sessions, "").sessions.is_empty());
+    }
+
+    #[test]
+    fn version_changes_only_with_state() {
+        let mut sessions = vec![session("a", "/code/aura", SessionState::Running)];
+        let before = project_status(&sessions, "/code/aura").version;
+        sessions.push(session("z", "/elsewhere", SessionState::Attention));
+        assert_eq!(project_status(&sessions, "/code/aura").version, before);
+        sessions[0].state = SessionState::Idle;
+        assert_ne!(project_status(&sessions, "/code/aura").version, before);
+    }
+
+    #[test]
+    fn parse_request_reads_query() {
+        let head = "GET /session?cwd=%2Fcode%2Fmy%20app&wait=abc HTTP/1.1\r\nHost: 127.0.0.1:47355\r\n\r\n";
+        assert_eq!(
+            parse_request(head),
+            Ok(SessionRequest {
+                cwd: "/code/my app".into(),
+                wait: Some("abc".into()),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_request_rejects_bad_requests() {
+        let host = "Host: localhost:47355\r\n\r\n";
+        assert_eq!(
+            parse_request(&format!("POST /session?cwd=/a HTTP/1.1\r\n{host}")),
+            Err("405 Method Not Allowed")
+        );
+        assert_eq!(
+            parse_request(&format!("GET /other HTTP/1.1\r\n{host}")),
+            Err("404 Not Found")
+        );
+        assert_eq!(
+            parse_request(&format!("GET /session HTTP/1.1\r\n{host}")),
+            Err("400 Bad Request")
+        );
+        // DNS rebinding: a page served from evil.test resolving to 127.0.0.1
+        assert_eq!(
+            parse_request("GET /session?cwd=/a HTTP/1.1\r\nHost: evil.test:47355\r\n\r\n"),
+            Err("403 Forbidden")
+        );
+    }
+
+    #[test]
+    fn loopback_hosts() {
+        assert!(is_loopback_host("localhost"));
+        assert!(is_loopback_host("127.0.0.1:47355"));
+        assert!(is_loopback_host("[::1]:47355"));
+        assert!(!is_loopback_host("localhost.evil.test"));
+    }
+
+    #[test]
+    fn percent_decode_handles_escapes() {
+        assert_eq!(percent_decode("a%20b+c"), "a b c");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%E2%9C%93"), "✓");
+    }
+}
@@ -5,6 +5,9 @@
 
 use serde::{Deserialize, Serialize};
 
+/// Tool ID of a call whose hook didn't carry one
+pub const UNKNOWN_TOOL_ID: &str = "unknown";
+
 /// Type of AI code agent
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -16,10 +19,107 @@ pub enum AgentType {
     Custom(String),
 }
 
+impl AgentType {
+    /// Name shown in the HUD, e.g. "Claude Code"
+    pub fn display_name(&self) -> &str {
+        match self {
+            Self::ClaudeCode => "Claude Code",
+            Self::GeminiCli => "Gemini CLI",
+            Self::Codex => "Codex",
+            Self::OpenCode => "OpenCode",
+            Self::Custom(name) => name,
+        }
+    }
+}
+
+/// One file modification with line counts estimated from the tool input
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileChange {
+    /// Absolute path (relative if the agent's cwd is unknown)
+    pub path: String,
+    pub added: u32,
+    pub removed: u32,
+}
+
+/// Terminal an agent runs in, from its hook's environment
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TerminalInfo {
+    /// `$TERM_PROGRAM`, e.g. "iTerm.app", "Apple_Terminal" or "vscode"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub program: Option<String>,
+    /// `$TMUX_PANE`, e.g. "%3"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tmux_pane: Option<String>,
+    /// Controlling terminal, e.g. "/dev/ttys004"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tty: Option<String>,
+    /// The hook's parent process: the agent, or the shell running the hook
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+}
+
+impl TerminalInfo {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Progress through an agent's plan (Codex `update_plan`) or todo list
+/// (Claude Code `TodoWrite`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanProgress {
+    pub completed: u32,
+    pub total: u32,
+    /// The step in progress, else the next pending one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current: Option<String>,
+}
+
+impl PlanProgress {
+    /// Summarize `(status, step)` pairs; statuses are `completed`,
+    /// `in_progress` and `pending`
+    pub fn from_steps<'a>(steps: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        let mut progress = Self::default();
+        let mut next_pending = None;
+        for (status, step) in steps {
+            progress.total += 1;
+            match status {
+                "completed" => progress.completed += 1,
+                "in_progress" if progress.current.is_none() => {
+                    progress.current = Some(step.trim().to_string());
+                }
+                _ if next_pending.is_none() => next_pending = Some(step.trim().to_string()),
+                _ => {}
+            }
+        }
+        progress.current = progress.current.or(next_pending);
+        progress
+    }
+
+    /// "3/7 · migrate config loader", or `None` for an empty plan
+    pub fn subtitle(&self) -> Option<String> {
+        if self.total == 0 {
+            return None;
+        }
+        let count = format!("{}/{}", self.completed, self.total);
+        Some(
+            match self.current.as_deref().filter(|step| !step.is_empty()) {
+                Some(step) => format!("{count} · {step}"),
+                None => count,
+            },
+        )
+    }
+}
+
 /// Event from an AI code agent
 ///
 /// Currently only Claude Code is supported. The architecture is designed
 /// to support additional agents in future versions.
+///
+/// New event types go at the end and new fields of existing ones need a
+/// serde default, so daemons and hooks of neighbouring versions still read
+/// each other's lines: an older daemon parses a type it doesn't know as
+/// [`Unknown`](Self::Unknown) and skips it, and ignores fields it doesn't know.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum AgentEvent {
@@ -28,6 +128,9 @@ pub enum AgentEvent {
         session_id: String,
         cwd: String,
         agent: AgentType,
+        /// Where the agent runs, when its hook could tell
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        terminal: Option<TerminalInfo>,
     },
     /// Activity detected (health check)
     Activity { session_id: String, cwd: String },
@@ -38,6 +141,9 @@ pub enum AgentEvent {
         tool_id: String,
         tool_name: String,
         tool_label: Option<String>,
+        /// The untruncated command or path `tool_label` shortens, for copying
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tool_full_label: Option<String>,
     },
     /// Tool execution completed
     ToolCompleted {
@@ -65,6 +171,51 @@ pub enum AgentEvent {
     SessionEnded { session_id: String },
     /// Session name updated (via `aura set-name`)
     SessionNameUpdated { session_id: String, name: String },
+    /// Tags added to a session (via `aura set-tag`)
+    SessionTagged {
+        session_id: String,
+        tags: Vec<String>,
+    },
+    /// Files the agent is about to modify (Edit/Write tool inputs, Codex
+    /// `apply_patch`)
+    FilesTouched {
+        session_id: String,
+        changes: Vec<FileChange>,
+    },
+    /// Location of the session's transcript file (Claude Code transcript or Codex rollout)
+    TranscriptPath { session_id: String, path: String },
+    /// The agent's plan changed (an empty plan clears it)
+    PlanUpdated {
+        session_id: String,
+        progress: PlanProgress,
+    },
+    /// The agent entered or left plan mode (Claude Code `permission_mode`)
+    PlanModeChanged { session_id: String, active: bool },
+    /// Model the agent is using (e.g. `claude-sonnet-4-5`, `gpt-5-codex`)
+    ModelUpdated { session_id: String, model: String },
+    /// Prompts queued behind the current turn (Claude Code transcript)
+    PromptsQueued { session_id: String, count: usize },
+    /// A parallel subagent started (Claude Code `SubagentStart` with an `agent_id`)
+    SubagentStarted {
+        session_id: String,
+        agent_id: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        agent_type: Option<String>,
+    },
+    /// A started tool belongs to a subagent rather than the main agent
+    SubagentTool {
+        session_id: String,
+        agent_id: String,
+        tool_id: String,
+    },
+    /// A parallel subagent finished
+    SubagentStopped {
+        session_id: String,
+        agent_id: String,
+    },
+    /// An event type from a newer hook; never applied
+    #[serde(other)]
+    Unknown,
 }
 
 impl AgentEvent {
@@ -80,11 +231,24 @@ impl AgentEvent {
             | Self::Compacting { session_id, .. }
             | Self::Idle { session_id, .. }
             | Self::SessionEnded { session_id }
-            | Self::SessionNameUpdated { session_id, .. } => session_id,
+            | Self::SessionNameUpdated { session_id, .. }
+            | Self::SessionTagged { session_id, .. }
+            | Self::FilesTouched { session_id, .. }
+            | Self::TranscriptPath { session_id, .. }
+            | Self::PlanUpdated { session_id, .. }
+            | Self::PlanModeChanged { session_id, .. }
+            | Self::ModelUpdated { session_id, .. }
+            | Self::PromptsQueued { session_id, .. }
+            | Self::SubagentStarted { session_id, .. }
+            | Self::SubagentTool { session_id, .. }
+            | Self::SubagentStopped { session_id, .. } => session_id,
+            Self::Unknown => "",
         }
     }
 
-    /// Get cwd from any event (empty for SessionEnded and SessionNameUpdated)
+    /// Get cwd from any event (empty for SessionEnded, SessionNameUpdated, SessionTagged,
+    /// FilesTouched, TranscriptPath, PlanUpdated, PlanModeChanged, ModelUpdated,
+    /// PromptsQueued and the subagent events)
     pub fn cwd(&self) -> &str {
         match self {
             Self::SessionStarted { cwd, .. }
@@ -95,7 +259,19 @@ impl AgentEvent {
             | Self::WaitingForInput { cwd, .. }
             | Self::Compacting { cwd, .. }
             | Self::Idle { cwd, .. } => cwd,
-            Self::SessionEnded { .. } | Self::SessionNameUpdated { .. } => "",
+            Self::SessionEnded { .. }
+            | Self::SessionNameUpdated { .. }
+            | Self::SessionTagged { .. }
+            | Self::FilesTouched { .. }
+            | Self::TranscriptPath { .. }
+            | Self::PlanUpdated { .. }
+            | Self::PlanModeChanged { .. }
+            | Self::ModelUpdated { .. }
+            | Self::PromptsQueued { .. }
+            | Self::SubagentStarted { .. }
+            | Self::SubagentTool { .. }
+            | Self::SubagentStopped { .. }
+            | Self::Unknown => "",
         }
     }
 }
@@ -111,6 +287,7 @@ mod tests {
                 session_id: "s1".into(),
                 cwd: "/tmp".into(),
                 agent: AgentType::ClaudeCode,
+                terminal: None,
             },
             AgentEvent::Activity {
                 session_id: "s2".into(),
@@ -122,6 +299,7 @@ mod tests {
                 tool_id: "t1".into(),
                 tool_name: "Read".into(),
                 tool_label: None,
+                tool_full_label: None,
             },
             AgentEvent::ToolCompleted {
                 session_id: "s4".into(),
@@ -149,6 +327,52 @@ mod tests {
             AgentEvent::SessionEnded {
                 session_id: "s9".into(),
             },
+            AgentEvent::TranscriptPath {
+                session_id: "s10".into(),
+                path: "/tmp/s10.jsonl".into(),
+            },
+            AgentEvent::SessionTagged {
+                session_id: "s11".into(),
+                tags: vec!["billing".into()],
+            },
+            AgentEvent::FilesTouched {
+                session_id: "s12".into(),
+                changes: vec![FileChange {
+                    path: "/tmp/main.rs".into(),
+                    added: 1,
+                    removed: 0,
+                }],
+            },
+            AgentEvent::PlanUpdated {
+                session_id: "s13".into(),
+                progress: PlanProgress::default(),
+            },
+            AgentEvent::PlanModeChanged {
+                session_id: "s14".into(),
+                active: true,
+            },
+            AgentEvent::ModelUpdated {
+                session_id: "s15".into(),
+                model: "gpt-5-codex".into(),
+            },
+            AgentEvent::PromptsQueued {
+                session_id: "s16".into(),
+                count: 2,
+            },
+            AgentEvent::SubagentStarted {
+                session_id: "s17".into(),
+                agent_id: "a1".into(),
+                agent_type: None,
+            },
+            AgentEvent::SubagentTool {
+                session_id: "s18".into(),
+                agent_id: "a1".into(),
+                tool_id: "t1".into(),
+            },
+            AgentEvent::SubagentStopped {
+                session_id: "s19".into(),
+                agent_id: "a1".into(),
+            },
         ];
 
         for (i, event) in events.iter().enumerate() {
@@ -156,6 +380,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn plan_progress_summarizes_steps() {
+        let progress = PlanProgress::from_steps([
+            ("completed", "read config"),
+            ("completed", "add loader"),
+            ("pending", "write tests"),
+            ("in_progress", " migrate config loader "),
+            ("pending", "docs"),
+        ]);
+        assert_eq!((progress.completed, progress.total), (2, 5));
+        assert_eq!(
+            progress.subtitle().as_deref(),
+            Some("2/5 · migrate config loader")
+        );
+
+        // Nothing in progress: the next pending step
+        let progress = PlanProgress::from_steps([("completed", "a"), ("pending", "b")]);
+        assert_eq!(progress.subtitle().as_deref(), Some("1/2 · b"));
+
+        let done = PlanProgress::from_steps([("completed", "a")]);
+        assert_eq!(done.subtitle().as_deref(), Some("1/1"));
+        assert_eq!(PlanProgress::from_steps([]).subtitle(), None);
+    }
+
     #[test]
     fn agent_event_serialization() {
         let event = AgentEvent::ToolStarted {
@@ -164,6 +412,7 @@ mod tests {
             tool_id: "toolu_01".into(),
             tool_name: "Read".into(),
             tool_label: Some("config.rs".into()),
+            tool_full_label: None,
         };
 
         let json = serde_json::to_string(&event).unwrap();
@@ -184,5 +433,10 @@ mod tests {
             serde_json::to_string(&AgentType::Custom("my-agent".into())).unwrap(),
             "{\"custom\":\"my-agent\"}"
         );
+        assert_eq!(AgentType::GeminiCli.display_name(), "Gemini CLI");
+        assert_eq!(
+            AgentType::Custom("my-agent".into()).display_name(),
+            "my-agent"
+        );
     }
 }
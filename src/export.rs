@@ -0,0 +1,447 @@
+//! Session export - summarize a transcript as Markdown or JSON
+//!
+//! Backs `aura export`. The summary is built from the same transcript parser
+//! as the viewer ([`crate::transcript`]): prompts in order, tool usage counts,
+//! files touched by edit/read tools, and the time between the first and last
+//! timestamped line. Tools and paths in the config's `ignore` list are left
+//! out, as they are on the HUD.
+
+use crate::SessionInfo;
+use crate::ignore::IgnoreConfig;
+use crate::tools::{ToolCategories, ToolCategory};
+use crate::transcript::{self, TranscriptEntry};
+use crate::ui::session_list::extract_session_name;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::BufRead;
+
+/// Output format of `aura export`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ExportFormat {
+    #[default]
+    Md,
+    Json,
+}
+
+/// Tool input keys that name a file
+const FILE_KEYS: &[&str] = &["file_path", "notebook_path", "path"];
+
+/// Exported summary of one session
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SessionSummary {
+    pub session_id: String,
+    pub name: String,
+    pub cwd: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// First timestamp in the transcript (RFC 3339)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<String>,
+    /// Last timestamp in the transcript (RFC 3339)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ended_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<u64>,
+    /// User prompts in order
+    pub prompts: Vec<String>,
+    /// Tool call counts, most used first
+    pub tools: Vec<ToolUsage>,
+    /// Tool call counts per category (see [`crate::tools`]), most used first
+    pub categories: Vec<CategoryUsage>,
+    /// Time spent per model, most used first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub models: Vec<ModelUsage>,
+    /// Files referenced by tool calls, sorted
+    pub files: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ToolUsage {
+    pub name: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CategoryUsage {
+    pub category: ToolCategory,
+    pub count: usize,
+}
+
+/// Time attributed to one model: the gaps leading up to timestamped lines
+/// while it was the latest model named in the transcript
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ModelUsage {
+    pub model: String,
+    pub secs: u64,
+}
+
+/// Build a summary from a session and its transcript lines, grouping tools
+/// with `categories` and skipping what `ignore` lists.
+pub fn summarize(
+    session: &SessionInfo,
+    transcript: impl BufRead,
+    categories: &ToolCategories,
+    ignore: &IgnoreConfig,
+) -> SessionSummary {
+    let mut prompts = Vec::new();
+    let mut tool_counts: BTreeMap<String, usize> = BTreeMap::new();
+    let mut files = BTreeSet::new();
+    let mut first: Option<DateTime<Utc>> = None;
+    let mut last: Option<DateTime<Utc>> = None;
+    let mut model_secs: BTreeMap<String, u64> = BTreeMap::new();
+    let mut model: Option<String> = None;
+    let mut previous: Option<DateTime<Utc>> = None;
+
+    for line in transcript.lines().map_while(Result::ok) {
+        let Ok(value) = serde_json::from_str::<Value>(line.trim()) else {
+            continue;
+        };
+        if let Some(named) = transcript::line_model(&value) {
+            model = Some(named.to_string());
+        }
+        if let Some(ts) = value
+            .get("timestamp")
+            .and_then(Value::as_str)
+            .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+        {
+            let ts = ts.with_timezone(&Utc);
+            first = Some(first.map_or(ts, |f| f.min(ts)));
+            last = Some(last.map_or(ts, |l| l.max(ts)));
+            if let (Some(model), Some(previous)) = (&model, previous) {
+                let gap = (ts - previous).num_seconds().max(0) as u64;
+                *model_secs.entry(model.clone()).or_default() += gap;
+            }
+            previous = Some(ts);
+        }
+        for entry in transcript::parse_line(&value) {
+            match entry {
+                TranscriptEntry::User { text } => prompts.push(text),
+                TranscriptEntry::Assistant { .. } => {}
+                TranscriptEntry::ToolCall { name, input, .. } => {
+                    let named = files_in_input(&input);
+                    let kept: Vec<String> = named
+                        .iter()
+                        .filter(|file| !ignore.ignores_path(file))
+                        .cloned()
+                        .collect();
+                    // A call on ignored files only is ignored as a whole
+                    if ignore.ignores_tool(&name) || (kept.is_empty() && !named.is_empty()) {
+                        continue;
+                    }
+                    files.extend(kept);
+                    *tool_counts.entry(name).or_default() += 1;
+                }
+            }
+        }
+    }
+
+    let mut category_counts: BTreeMap<ToolCategory, usize> = BTreeMap::new();
+    for (name, count) in &tool_counts {
+        *category_counts
+            .entry(categories.categorize(name))
+            .or_default() += count;
+    }
+    let mut categories: Vec<CategoryUsage> = category_counts
+        .into_iter()
+        .map(|(category, count)| CategoryUsage { category, count })
+        .collect();
+    categories.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.category.cmp(&b.category))
+    });
+    let mut tools: Vec<ToolUsage> = tool_counts
+        .into_iter()
+        .map(|(name, count)| ToolUsage { name, count })
+        .collect();
+    tools.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    let mut models: Vec<ModelUsage> = model_secs
+        .into_iter()
+        .map(|(model, secs)| ModelUsage { model, secs })
+        .collect();
+    models.sort_by(|a, b| b.secs.cmp(&a.secs).then_with(|| a.model.cmp(&b.model)));
+
+    SessionSummary {
+        session_id: session.session_id.clone(),
+        name: session
+            .name
+            .clone()
+            .unwrap_or_else(|| extract_session_name(&session.cwd)),
+        cwd: session.cwd.clone(),
+        tags: session.tags.clone(),
+        started_at: first.map(|t| t.to_rfc3339()),
+        ended_at: last.map(|t| t.to_rfc3339()),
+        duration_secs: first
+            .zip(last)
+            .map(|(f, l)| (l - f).num_seconds().max(0) as u64),
+        prompts,
+        tools,
+        categories,
+        models,
+        files: files.into_iter().collect(),
+    }
+}
+
+/// File paths named by a tool call's input (JSON arguments or a patch)
+fn files_in_input(input: &str) -> Vec<String> {
+    if let Ok(Value::Object(args)) = serde_json::from_str::<Value>(input) {
+        return FILE_KEYS
+            .iter()
+            .filter_map(|key| args.get(*key)?.as_str())
+            .map(str::to_string)
+            .collect();
+    }
+    crate::agents::apply_patch_changes(input)
+        .into_iter()
+        .map(|change| change.path)
+        .collect()
+}
+
+/// Render a summary in the requested format
+pub fn render(summary: &SessionSummary, format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Md => to_markdown(summary),
+        ExportFormat::Json => serde_json::to_string_pretty(summary).unwrap_or_default() + "\n",
+    }
+}
+
+fn to_markdown(summary: &SessionSummary) -> String {
+    let mut out = format!("# {}\n\n", summary.name);
+    out.push_str(&format!("- **Session:** `{}`\n", summary.session_id));
+    out.push_str(&format!("- **Directory:** `{}`\n", summary.cwd));
+    if !summary.tags.is_empty() {
+        out.push_str(&format!(
+            "- **Tags:** {}\n",
+            crate::tags::format_tags(&summary.tags)
+        ));
+    }
+    if let Some(started) = &summary.started_at {
+        out.push_str(&format!("- **Started:** {started}\n"));
+    }
+    if let Some(secs) = summary.duration_secs {
+        out.push_str(&format!("- **Duration:** {}\n", format_duration(secs)));
+    }
+
+    out.push_str("\n## Prompts\n\n");
+    if summary.prompts.is_empty() {
+        out.push_str("_None_\n");
+    }
+    for (i, prompt) in summary.prompts.iter().enumerate() {
+        // Continuation lines are indented to stay inside the list item
+        let prompt = prompt.lines().collect::<Vec<_>>().join("\n   ");
+        out.push_str(&format!("{}. {prompt}\n", i + 1));
+    }
+
+    out.push_str("\n## Tools\n\n");
+    if summary.tools.is_empty() {
+        out.push_str("_None_\n");
+    } else {
+        out.push_str("| Tool | Calls |\n|------|-------|\n");
+        for tool in &summary.tools {
+            out.push_str(&format!("| {} | {} |\n", tool.name, tool.count));
+        }
+    }
+
+    if !summary.categories.is_empty() {
+        let mix: Vec<String> = summary
+            .categories
+            .iter()
+            .map(|usage| format!("{} {}", usage.category.as_str(), usage.count))
+            .collect();
+        out.push_str(&format!("\nBy category: {}\n", mix.join(", ")));
+    }
+
+    if !summary.models.is_empty() {
+        out.push_str("\n## Models\n\n| Model | Time |\n|-------|------|\n");
+        for usage in &summary.models {
+            out.push_str(&format!(
+                "| {} | {} |\n",
+                usage.model,
+                format_duration(usage.secs)
+            ));
+        }
+    }
+
+    out.push_str("\n## Files\n\n");
+    if summary.files.is_empty() {
+        out.push_str("_None_\n");
+    }
+    for file in &summary.files {
+        out.push_str(&format!("- `{file}`\n"));
+    }
+    out
+}
+
+/// "1h 5m", "12m 30s", "45s"
+fn format_duration(secs: u64) -> String {
+    let (h, m, s) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    match (h, m) {
+        (0, 0) => format!("{s}s"),
+        (0, _) => format!("{m}m {s}s"),
+        _ => format!("{h}h {m}m"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session() -> SessionInfo {
+        SessionInfo {
+            session_id: "abc-123".into(),
+            cwd: "/code/aura".into(),
+            ..Default::default()
+        }
+    }
+
+    const CLAUDE_TRANSCRIPT: &str = r#"{"type":"user","timestamp":"2026-01-05T10:00:00Z","message":{"content":"fix the login bug"}}
+{"type":"assistant","timestamp":"2026-01-05T10:00:05Z","message":{"content":[{"type":"tool_use","name":"Read","input":{"file_path":"/code/aura/src/login.rs"}}]}}
+{"type":"assistant","timestamp":"2026-01-05T10:01:00Z","message":{"content":[{"type":"tool_use","name":"Edit","input":{"file_path":"/code/aura/src/login.rs"}}]}}
+{"type":"assistant","timestamp":"2026-01-05T10:01:30Z","message":{"content":[{"type":"tool_use","name":"Bash","input":{"command":"cargo test"}}]}}
+not json
+{"type":"user","timestamp":"2026-01-05T10:12:30Z","message":{"content":"thanks"}}
+"#;
+
+    #[test]
+    fn summarize_collects_prompts_tools_files_and_duration() {
+        let summary = summarize(
+            &session(),
+            CLAUDE_TRANSCRIPT.as_bytes(),
+            &ToolCategories::default(),
+            &IgnoreConfig::default(),
+        );
+        assert_eq!(summary.name, "aura");
+        assert_eq!(summary.prompts, vec!["fix the login bug", "thanks"]);
+        assert_eq!(summary.files, vec!["/code/aura/src/login.rs"]);
+        assert_eq!(summary.duration_secs, Some(750));
+        assert_eq!(
+            summary.tools,
+            vec![
+                ToolUsage {
+                    name: "Bash".into(),
+                    count: 1
+                },
+                ToolUsage {
+                    name: "Edit".into(),
+                    count: 1
+                },
+                ToolUsage {
+                    name: "Read".into(),
+                    count: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn model_time_follows_model_switches() {
+        let transcript = r#"{"type":"user","timestamp":"2026-01-05T10:00:00Z","message":{"content":"go"}}
+{"type":"assistant","timestamp":"2026-01-05T10:01:00Z","message":{"model":"claude-sonnet-4-5","content":"done"}}
+{"type":"user","timestamp":"2026-01-05T10:02:00Z","message":{"content":"again"}}
+{"type":"assistant","timestamp":"2026-01-05T10:10:00Z","message":{"model":"claude-opus-4-1","content":"done"}}
+"#;
+        let summary = summarize(
+            &session(),
+            transcript.as_bytes(),
+            &ToolCategories::default(),
+            &IgnoreConfig::default(),
+        );
+        assert_eq!(
+            summary.models,
+            vec![
+                ModelUsage {
+                    model: "claude-opus-4-1".into(),
+                    secs: 480
+                },
+                ModelUsage {
+                    model: "claude-sonnet-4-5".into(),
+                    secs: 120
+                },
+            ]
+        );
+        assert!(to_markdown(&summary).contains("| claude-opus-4-1 | 8m 0s |"));
+    }
+
+    #[test]
+    fn ignored_tools_and_paths_are_left_out() {
+        let ignore = IgnoreConfig {
+            tools: vec!["Bash".into()],
+            paths: vec!["login.rs".into()],
+            ..Default::default()
+        };
+        let summary = summarize(
+            &session(),
+            CLAUDE_TRANSCRIPT.as_bytes(),
+            &ToolCategories::default(),
+            &ignore,
+        );
+        assert!(summary.tools.is_empty());
+        assert!(summary.categories.is_empty());
+        assert!(summary.files.is_empty());
+        assert_eq!(summary.prompts.len(), 2);
+    }
+
+    #[test]
+    fn patch_inputs_name_files() {
+        let patch = "*** Begin Patch\n*** Update File: src/main.rs\n@@\n-a\n+b\n*** Add File: src/new.rs\n*** End Patch";
+        assert_eq!(files_in_input(patch), vec!["src/main.rs", "src/new.rs"]);
+        assert!(files_in_input(r#"{"command":"ls"}"#).is_empty());
+    }
+
+    #[test]
+    fn markdown_has_sections() {
+        let summary = summarize(
+            &session(),
+            CLAUDE_TRANSCRIPT.as_bytes(),
+            &ToolCategories::default(),
+            &IgnoreConfig::default(),
+        );
+        let md = render(&summary, ExportFormat::Md);
+        assert!(md.starts_with("# aura\n"));
+        assert!(md.contains("- **Duration:** 12m 30s\n"));
+        assert!(md.contains("1. fix the login bug\n"));
+        assert!(md.contains("| Edit | 1 |\n"));
+        assert!(md.contains("By category: shell 1, edit 1, read 1\n"));
+        assert!(md.contains("- `/code/aura/src/login.rs`\n"));
+    }
+
+    #[test]
+    fn tags_are_exported() {
+        let tagged = SessionInfo {
+            tags: vec!["billing".into()],
+            ..session()
+        };
+        let summary = summarize(
+            &tagged,
+            "".as_bytes(),
+            &ToolCategories::default(),
+            &IgnoreConfig::default(),
+        );
+        assert!(render(&summary, ExportFormat::Md).contains("- **Tags:** #billing\n"));
+        let json: Value = serde_json::from_str(&render(&summary, ExportFormat::Json)).unwrap();
+        assert_eq!(json["tags"], serde_json::json!(["billing"]));
+    }
+
+    #[test]
+    fn json_is_machine_readable() {
+        let summary = summarize(
+            &session(),
+            "".as_bytes(),
+            &ToolCategories::default(),
+            &IgnoreConfig::default(),
+        );
+        let json: Value = serde_json::from_str(&render(&summary, ExportFormat::Json)).unwrap();
+        assert_eq!(json["session_id"], "abc-123");
+        assert!(json.get("duration_secs").is_none());
+        assert_eq!(json["prompts"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn format_duration_units() {
+        assert_eq!(format_duration(45), "45s");
+        assert_eq!(format_duration(750), "12m 30s");
+        assert_eq!(format_duration(3900), "1h 5m");
+    }
+}
@@ -0,0 +1,350 @@
+//! Event filters - scope the daemon to some agents, projects and tools
+//!
+//! The config's `filters` list holds allow/deny rules matching an agent type,
+//! a working directory glob (covering its subdirectories) and a tool name
+//! glob ([`crate::tags::glob_match`] syntax); fields left out match anything.
+//! The registry runs every event through an [`EventFilter`] before applying
+//! it, whichever adapter sent it.
+//!
+//! Rules without `tool` scope sessions: the first one matching a session's
+//! agent and cwd decides, once its first event with a cwd arrives, and the
+//! events of a denied session are dropped. Rules with `tool` scope tool calls
+//! the same way; a denied call is reduced to `Activity`, like an ignored one
+//! (see [`crate::ignore`]). When no rule matches, sessions (or calls) are let
+//! through unless some rule of their kind allows, so a single `allow` rule
+//! turns the list into an allow list, e.g. for shared machines:
+//! `[{ "action": "allow", "cwd": "/Users/me/code/*" }]`.
+//!
+//! A session's verdict is kept until it ends, or is forgotten after
+//! [`SCOPE_TTL`] without events (a denied session that was closed without
+//! `SessionEnded` never reaches the registry's garbage collection).
+
+use crate::tags::glob_match;
+use crate::{AgentEvent, AgentType};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// How long a session's verdict is kept without events
+const SCOPE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterAction {
+    Allow,
+    Deny,
+}
+
+/// One `filters` entry in config.json
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FilterRule {
+    pub action: FilterAction,
+    /// Agent type, e.g. `"codex"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<AgentType>,
+    /// Working directory glob; subdirectories of a match are covered too
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    /// Tool name glob, e.g. `"mcp__*"`; makes the rule scope tool calls
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+}
+
+impl FilterRule {
+    fn matches(&self, agent: &AgentType, cwd: &str, tool: Option<&str>) -> bool {
+        self.agent.as_ref().is_none_or(|a| a == agent)
+            && self.cwd.as_deref().is_none_or(|pattern| {
+                !cwd.is_empty()
+                    && Path::new(cwd)
+                        .ancestors()
+                        .any(|dir| glob_match(pattern, &dir.to_string_lossy()))
+            })
+            && match (&self.tool, tool) {
+                (Some(pattern), Some(tool)) => glob_match(pattern, tool),
+                (None, None) => true,
+                _ => false,
+            }
+    }
+}
+
+/// Whether the first rule matching decides to allow; `true` without a match
+/// unless one of `rules` allows
+fn allowed<'a>(
+    mut rules: impl Iterator<Item = &'a FilterRule> + Clone,
+    agent: &AgentType,
+    cwd: &str,
+    tool: Option<&str>,
+) -> bool {
+    match rules.clone().find(|rule| rule.matches(agent, cwd, tool)) {
+        Some(rule) => rule.action == FilterAction::Allow,
+        None => !rules.any(|rule| rule.action == FilterAction::Allow),
+    }
+}
+
+/// What the filter knows about one session
+#[derive(Debug)]
+struct Scope {
+    agent: AgentType,
+    cwd: String,
+    /// Decided by the first event with a cwd
+    allowed: Option<bool>,
+    last_seen: Instant,
+}
+
+/// Applies the `filters` rules to an event stream, remembering each
+/// session's agent, cwd and verdict
+#[derive(Debug, Default)]
+pub struct EventFilter {
+    rules: Vec<FilterRule>,
+    sessions: HashMap<String, Scope>,
+    /// (session ID, tool ID) of denied `ToolStarted` events
+    dropped: HashSet<(String, String)>,
+}
+
+impl EventFilter {
+    pub fn new(rules: Vec<FilterRule>) -> Self {
+        Self {
+            rules,
+            ..Self::default()
+        }
+    }
+
+    /// `event` as it should reach the registry, `None` to drop it;
+    /// `default_agent` is the sending adapter's agent
+    pub fn apply(
+        &mut self,
+        event: AgentEvent,
+        default_agent: &AgentType,
+        now: Instant,
+    ) -> Option<AgentEvent> {
+        if self.rules.is_empty() {
+            return Some(event);
+        }
+        let session_id = event.session_id().to_string();
+        if let AgentEvent::SessionEnded { .. } = event {
+            self.dropped.retain(|(id, _)| *id != session_id);
+            let scope = self.sessions.remove(&session_id);
+            return (scope.and_then(|s| s.allowed) != Some(false)).then_some(event);
+        }
+
+        let agent = match &event {
+            AgentEvent::SessionStarted { agent, .. } => agent.clone(),
+            _ => default_agent.clone(),
+        };
+        let scope = self.sessions.entry(session_id.clone()).or_insert(Scope {
+            agent,
+            cwd: String::new(),
+            allowed: None,
+            last_seen: now,
+        });
+        scope.last_seen = now;
+        if scope.allowed.is_none() && !event.cwd().is_empty() {
+            scope.cwd = event.cwd().to_string();
+            let session_rules = self.rules.iter().filter(|rule| rule.tool.is_none());
+            let allowed = allowed(session_rules, &scope.agent, &scope.cwd, None);
+            if !allowed {
+                tracing::info!(%session_id, cwd = %scope.cwd, "session filtered out");
+            }
+            scope.allowed = Some(allowed);
+        }
+        if scope.allowed == Some(false) {
+            return None;
+        }
+
+        match event {
+            AgentEvent::ToolStarted {
+                session_id,
+                cwd,
+                tool_id,
+                tool_name,
+                ..
+            } if !self.tool_allowed(&session_id, &tool_name) => {
+                self.dropped.insert((session_id.clone(), tool_id));
+                Some(AgentEvent::Activity { session_id, cwd })
+            }
+            AgentEvent::ToolCompleted {
+                ref session_id,
+                ref tool_id,
+                ..
+            } if self.dropped.remove(&(session_id.clone(), tool_id.clone())) => None,
+            AgentEvent::SubagentTool {
+                ref session_id,
+                ref tool_id,
+                ..
+            } if self
+                .dropped
+                .contains(&(session_id.clone(), tool_id.clone())) =>
+            {
+                None
+            }
+            event => Some(event),
+        }
+    }
+
+    /// Drop what's known about a session that went away without ending
+    /// (evicted by retention)
+    pub fn forget(&mut self, session_id: &str) {
+        self.sessions.remove(session_id);
+        self.dropped.retain(|(id, _)| id != session_id);
+    }
+
+    /// Forget sessions without events for [`SCOPE_TTL`]
+    pub fn forget_idle(&mut self, now: Instant) {
+        self.sessions
+            .retain(|_, scope| now.saturating_duration_since(scope.last_seen) < SCOPE_TTL);
+        let sessions = &self.sessions;
+        self.dropped.retain(|(id, _)| sessions.contains_key(id));
+    }
+
+    /// Sessions currently tracked
+    pub fn tracked(&self) -> usize {
+        self.sessions.len()
+    }
+
+    fn tool_allowed(&self, session_id: &str, tool_name: &str) -> bool {
+        let Some(scope) = self.sessions.get(session_id) else {
+            return true;
+        };
+        let tool_rules = self.rules.iter().filter(|rule| rule.tool.is_some());
+        allowed(tool_rules, &scope.agent, &scope.cwd, Some(tool_name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(json: &str) -> Vec<FilterRule> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn started(session_id: &str, cwd: &str, agent: AgentType) -> AgentEvent {
+        AgentEvent::SessionStarted {
+            session_id: session_id.into(),
+            cwd: cwd.into(),
+            agent,
+            terminal: None,
+        }
+    }
+
+    fn tool(session_id: &str, tool_id: &str, tool_name: &str) -> AgentEvent {
+        AgentEvent::ToolStarted {
+            session_id: session_id.into(),
+            cwd: "/code/aura".into(),
+            tool_id: tool_id.into(),
+            tool_name: tool_name.into(),
+            tool_label: None,
+            tool_full_label: None,
+        }
+    }
+
+    #[test]
+    fn one_allow_rule_makes_an_allow_list() {
+        let mut filter = EventFilter::new(rules(
+            r#"[{ "action": "allow", "cwd": "/code/*" }, { "action": "deny", "agent": "codex" }]"#,
+        ));
+        let now = Instant::now();
+        let claude = AgentType::ClaudeCode;
+        assert!(
+            filter
+                .apply(
+                    started("mine", "/code/aura/src", claude.clone()),
+                    &claude,
+                    now
+                )
+                .is_some()
+        );
+        assert!(
+            filter
+                .apply(
+                    started("theirs", "/home/bob/app", claude.clone()),
+                    &claude,
+                    now
+                )
+                .is_none()
+        );
+        // The first matching rule wins: Codex under /code is allowed
+        let codex = AgentType::Codex;
+        assert!(
+            filter
+                .apply(started("codex", "/code/web", codex.clone()), &codex, now)
+                .is_some()
+        );
+
+        // Later events of a denied session go, even without a cwd
+        let renamed = AgentEvent::SessionNameUpdated {
+            session_id: "theirs".into(),
+            name: "x".into(),
+        };
+        assert!(filter.apply(renamed, &claude, now).is_none());
+        let ended = AgentEvent::SessionEnded {
+            session_id: "theirs".into(),
+        };
+        assert!(filter.apply(ended, &claude, now).is_none());
+        assert!(!filter.sessions.contains_key("theirs"));
+    }
+
+    #[test]
+    fn deny_rules_by_agent() {
+        let mut filter = EventFilter::new(rules(r#"[{ "action": "deny", "agent": "codex" }]"#));
+        let now = Instant::now();
+        let codex = AgentType::Codex;
+        // Rollout events carry the watcher's agent, not a SessionStarted
+        let activity = AgentEvent::Activity {
+            session_id: "c1".into(),
+            cwd: "/code/aura".into(),
+        };
+        assert!(filter.apply(activity, &codex, now).is_none());
+        assert_eq!(filter.tracked(), 1);
+        // A denied session closed without `SessionEnded` is forgotten in time
+        filter.forget_idle(now + SCOPE_TTL / 2);
+        assert_eq!(filter.tracked(), 1);
+        filter.forget_idle(now + SCOPE_TTL);
+        assert_eq!(filter.tracked(), 0);
+        let claude = AgentType::ClaudeCode;
+        assert!(
+            filter
+                .apply(started("s1", "/code/aura", claude.clone()), &claude, now)
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn denied_tool_calls_become_activity() {
+        let mut filter = EventFilter::new(rules(
+            r#"[{ "action": "deny", "tool": "mcp__*", "cwd": "/code/aura" }]"#,
+        ));
+        let now = Instant::now();
+        let claude = AgentType::ClaudeCode;
+        filter.apply(started("s1", "/code/aura", claude.clone()), &claude, now);
+        assert!(matches!(
+            filter.apply(tool("s1", "t1", "mcp__memory__search"), &claude, now),
+            Some(AgentEvent::Activity { .. })
+        ));
+        assert!(matches!(
+            filter.apply(tool("s1", "t2", "Read"), &claude, now),
+            Some(AgentEvent::ToolStarted { .. })
+        ));
+        let completed = |tool_id: &str| AgentEvent::ToolCompleted {
+            session_id: "s1".into(),
+            cwd: "/code/aura".into(),
+            tool_id: tool_id.into(),
+        };
+        assert!(filter.apply(completed("t1"), &claude, now).is_none());
+        assert!(filter.apply(completed("t2"), &claude, now).is_some());
+    }
+
+    #[test]
+    fn no_rules_let_everything_through() {
+        let mut filter = EventFilter::default();
+        let now = Instant::now();
+        let claude = AgentType::ClaudeCode;
+        assert!(
+            filter
+                .apply(started("s1", "/anywhere", claude.clone()), &claude, now)
+                .is_some()
+        );
+        assert!(filter.sessions.is_empty());
+    }
+}
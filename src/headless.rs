@@ -0,0 +1,118 @@
+//! Headless mode - track sessions without the HUD
+//!
+//! `aura --headless` (the only mode on Windows, where there is no HUD) runs
+//! the daemon alone and prints a `sessions list` row to stdout whenever a
+//! session appears or changes state, and a line when it leaves the list.
+
+use crate::SessionState;
+use crate::registry::{RegistryChange, SharedRegistry};
+use std::collections::HashMap;
+
+/// Print session changes until the registry goes away.
+pub async fn run(registry: SharedRegistry) {
+    let Some(mut changes) = registry.observe() else {
+        return;
+    };
+    let mut printer = Printer::default();
+    for session in registry.snapshot().iter() {
+        if let Some(line) = printer.update(&RegistryChange::Added(session.clone())) {
+            println!("{line}");
+        }
+    }
+    while let Some(change) = changes.recv().await {
+        if let Some(line) = printer.update(&change) {
+            println!("{line}");
+        }
+    }
+}
+
+/// Turns registry changes into output lines, skipping updates that don't
+/// change a session's state
+#[derive(Debug, Default)]
+struct Printer {
+    states: HashMap<String, SessionState>,
+}
+
+impl Printer {
+    fn update(&mut self, change: &RegistryChange) -> Option<String> {
+        let id = change.session_id();
+        match change.current() {
+            Some(session) => {
+                if self.states.insert(id.to_string(), session.state) == Some(session.state) {
+                    return None;
+                }
+                let shown = crate::privacy::apply(session.clone());
+                Some(crate::cli::format_session_line(&shown, None))
+            }
+            None => {
+                self.states.remove(id)?;
+                let short_id: String = id.chars().take(8).collect();
+                Some(format!("{short_id:<8}  removed"))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::SessionRegistry;
+    use crate::{AgentEvent, AgentType, SessionInfo};
+
+    #[test]
+    fn prints_state_changes_only() {
+        let mut registry = SessionRegistry::new();
+        let mut changes = registry.observe();
+        let mut printer = Printer::default();
+        let mut lines = |registry: &mut SessionRegistry| {
+            registry.publish();
+            std::iter::from_fn(|| changes.try_recv().ok())
+                .filter_map(|change| printer.update(&change))
+                .collect::<Vec<_>>()
+        };
+        registry.process_event(AgentEvent::SessionStarted {
+            session_id: "abc12345".into(),
+            cwd: "/code/aura".into(),
+            agent: AgentType::ClaudeCode,
+            terminal: None,
+        });
+        let printed = lines(&mut registry);
+        assert_eq!(printed.len(), 1);
+        assert!(
+            printed[0].starts_with("abc12345  running"),
+            "{}",
+            printed[0]
+        );
+
+        registry.process_event(AgentEvent::Activity {
+            session_id: "abc12345".into(),
+            cwd: "/code/aura".into(),
+        });
+        assert!(lines(&mut registry).is_empty());
+
+        registry.process_event(AgentEvent::SessionEnded {
+            session_id: "abc12345".into(),
+        });
+        assert_eq!(lines(&mut registry), vec!["abc12345  removed"]);
+    }
+
+    #[test]
+    fn removal_shortens_multibyte_ids() {
+        let mut printer = Printer::default();
+        let session = SessionInfo {
+            session_id: "セッション-12345".into(),
+            ..Default::default()
+        };
+        printer
+            .states
+            .insert(session.session_id.clone(), session.state);
+        let change = RegistryChange::Removed {
+            session,
+            archived: false,
+        };
+        assert_eq!(
+            printer.update(&change).as_deref(),
+            Some("セッション-12  removed")
+        );
+    }
+}
@@ -0,0 +1,278 @@
+//! Health - whether Aura is still hearing from its event sources
+//!
+//! The daemon records when each agent last sent an event (hooks for Claude
+//! Code, rollouts for Codex), whether the IPC socket is bound and what the
+//! Codex watcher is doing. [`report`] gathers that with the tasks the
+//! [`crate::supervisor`] sees failing; any [`HealthReport::warnings`] put a
+//! warning badge on the indicator, and the Diagnostics window lists the whole
+//! report, so "why is nothing showing up" can be answered in-app.
+
+use crate::AgentType;
+use crate::pause::Source;
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Whether the IPC server listens for hook events and requests
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SocketState {
+    /// Not bound yet
+    Starting,
+    Listening,
+    /// Binding failed, or the server stopped
+    Failed,
+}
+
+/// What the Codex rollout watcher is doing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatcherState {
+    /// Not started yet
+    Starting,
+    /// Exited (or panicked); the supervisor restarts it
+    Stopped,
+    /// Waiting for the Codex home directory to appear
+    WaitingForCodex,
+    /// Watching the sessions directory
+    Watching,
+}
+
+#[derive(Debug)]
+struct Health {
+    started: Option<Instant>,
+    claude_code_event: Option<Instant>,
+    codex_event: Option<Instant>,
+    socket: SocketState,
+    watcher: WatcherState,
+    codex_bootstrap: Option<BootstrapStats>,
+}
+
+static HEALTH: Mutex<Health> = Mutex::new(Health {
+    started: None,
+    claude_code_event: None,
+    codex_event: None,
+    socket: SocketState::Starting,
+    watcher: WatcherState::Starting,
+    codex_bootstrap: None,
+});
+
+fn update(f: impl FnOnce(&mut Health)) {
+    f(&mut HEALTH.lock().unwrap_or_else(PoisonError::into_inner));
+}
+
+/// Start the uptime clock (once the daemon runs its services)
+pub fn daemon_started() {
+    let now = Instant::now();
+    update(|health| {
+        health.started.get_or_insert(now);
+    });
+}
+
+/// Note an event from `agent` (as reported by the adapter that sent it)
+pub fn record_event(agent: &AgentType) {
+    let now = Instant::now();
+    update(|health| match Source::of(agent) {
+        Source::Codex => health.codex_event = Some(now),
+        Source::ClaudeCode => health.claude_code_event = Some(now),
+    });
+}
+
+pub fn set_socket(state: SocketState) {
+    update(|health| health.socket = state);
+}
+
+/// Note how long the Codex watcher took to register the existing rollouts
+pub fn record_codex_bootstrap(rollouts: usize, elapsed: Duration) {
+    let stats = BootstrapStats {
+        rollouts,
+        millis: elapsed.as_millis() as u64,
+    };
+    update(|health| health.codex_bootstrap = Some(stats));
+}
+
+/// Marks the Codex watcher as running until dropped, even when it panics
+#[derive(Debug)]
+pub struct WatcherGuard(());
+
+/// The Codex watcher started; it waits for Codex until told otherwise
+pub fn watcher_started() -> WatcherGuard {
+    update(|health| health.watcher = WatcherState::WaitingForCodex);
+    WatcherGuard(())
+}
+
+impl WatcherGuard {
+    pub fn set(&self, state: WatcherState) {
+        update(|health| health.watcher = state);
+    }
+}
+
+impl Drop for WatcherGuard {
+    fn drop(&mut self) {
+        update(|health| health.watcher = WatcherState::Stopped);
+    }
+}
+
+/// Last event seen from one source
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceHealth {
+    pub name: String,
+    /// Seconds since its last event; `None` before the first one
+    pub last_event_secs: Option<u64>,
+    /// Its events are being dropped, see [`crate::pause`]
+    #[serde(default)]
+    pub paused: bool,
+}
+
+/// Startup cost of the Codex watcher's bootstrap
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BootstrapStats {
+    /// Rollouts found on disk (only recent ones are tracked)
+    pub rollouts: usize,
+    pub millis: u64,
+}
+
+/// Snapshot of the daemon's health (also sent in `Pong`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub uptime_secs: u64,
+    pub sources: Vec<SourceHealth>,
+    pub socket: SocketState,
+    pub watcher: WatcherState,
+    /// Latest Codex bootstrap, once one finished
+    #[serde(default)]
+    pub codex_bootstrap: Option<BootstrapStats>,
+    /// Tasks restarting over and over, see [`crate::supervisor::failing`]
+    #[serde(default)]
+    pub failing_tasks: Vec<String>,
+}
+
+impl HealthReport {
+    /// Problems worth a warning on the indicator
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.socket == SocketState::Failed {
+            warnings.push("Hook socket isn't listening".to_string());
+        }
+        if self.watcher == WatcherState::Stopped {
+            warnings.push("Codex watcher isn't running".to_string());
+        }
+        warnings.extend(
+            self.failing_tasks
+                .iter()
+                .map(|task| format!("Task \"{task}\" keeps failing")),
+        );
+        warnings
+    }
+
+    pub fn is_degraded(&self) -> bool {
+        !self.warnings().is_empty()
+    }
+}
+
+/// Current health of the daemon
+pub fn report() -> HealthReport {
+    let now = Instant::now();
+    let since = |at: Option<Instant>| at.map(|at| now.saturating_duration_since(at).as_secs());
+    let health = HEALTH.lock().unwrap_or_else(PoisonError::into_inner);
+    HealthReport {
+        uptime_secs: since(health.started).unwrap_or(0),
+        sources: Source::ALL
+            .into_iter()
+            .map(|source| SourceHealth {
+                name: source.label().to_string(),
+                last_event_secs: since(match source {
+                    Source::ClaudeCode => health.claude_code_event,
+                    Source::Codex => health.codex_event,
+                }),
+                paused: crate::pause::is_paused(source),
+            })
+            .collect(),
+        socket: health.socket,
+        watcher: health.watcher,
+        codex_bootstrap: health.codex_bootstrap,
+        failing_tasks: crate::supervisor::failing()
+            .into_iter()
+            .map(String::from)
+            .collect(),
+    }
+}
+
+/// "just now", "42s ago", "5m ago", "3h ago" or "2d ago"
+pub fn format_ago(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    match secs {
+        0..5 => "just now".to_string(),
+        5..60 => format!("{secs}s ago"),
+        60..3600 => format!("{}m ago", secs / 60),
+        3600..86400 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86400),
+    }
+}
+
+/// "45s", "12m" or "3h 2m"
+pub fn format_uptime(secs: u64) -> String {
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m", secs / 60),
+        _ => format!("{}h {}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn healthy() -> HealthReport {
+        HealthReport {
+            uptime_secs: 60,
+            sources: Vec::new(),
+            socket: SocketState::Listening,
+            watcher: WatcherState::Watching,
+            codex_bootstrap: None,
+            failing_tasks: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn warnings_cover_socket_watcher_and_failing_tasks() {
+        assert!(!healthy().is_degraded());
+        let starting = HealthReport {
+            socket: SocketState::Starting,
+            watcher: WatcherState::Starting,
+            ..healthy()
+        };
+        assert!(!starting.is_degraded());
+        let waiting = HealthReport {
+            watcher: WatcherState::WaitingForCodex,
+            ..healthy()
+        };
+        assert!(!waiting.is_degraded());
+
+        let broken = HealthReport {
+            socket: SocketState::Failed,
+            watcher: WatcherState::Stopped,
+            failing_tasks: vec!["queue".into()],
+            ..healthy()
+        };
+        assert_eq!(
+            broken.warnings(),
+            vec![
+                "Hook socket isn't listening",
+                "Codex watcher isn't running",
+                "Task \"queue\" keeps failing",
+            ]
+        );
+    }
+
+    #[test]
+    fn ago_labels() {
+        assert_eq!(format_ago(Duration::from_secs(2)), "just now");
+        assert_eq!(format_ago(Duration::from_secs(42)), "42s ago");
+        assert_eq!(format_ago(Duration::from_secs(5 * 60 + 10)), "5m ago");
+        assert_eq!(format_ago(Duration::from_secs(3 * 3600)), "3h ago");
+        assert_eq!(format_ago(Duration::from_secs(2 * 86400)), "2d ago");
+        assert_eq!(format_uptime(45), "45s");
+        assert_eq!(format_uptime(3 * 3600 + 125), "3h 2m");
+    }
+}
@@ -0,0 +1,474 @@
+//! Event hooks - run user commands on session transitions
+//!
+//! The `hooks` section of config.json maps a trigger to a shell command:
+//!
+//! ```json
+//! "hooks": {
+//!   "attention": "say 'Claude needs you'",
+//!   "session_ended": "osascript -e 'display notification \"$AURA_NAME finished\"'",
+//!   "debounce_secs": 10
+//! }
+//! ```
+//!
+//! Triggers are a session state a session enters (`running`, `idle`,
+//! `attention`, `waiting`, `compacting`, `stale`) or `session_started` /
+//! `session_ended`, or `cwd_conflict` / `file_conflict` when a session starts
+//! sharing its working directory or a modified file with another live
+//! session. Commands run under `sh -c` with the session described in
+//! `AURA_*` environment variables. A trigger fires at most once per session
+//! per `debounce_secs`, so a session flapping between states doesn't spawn a
+//! burst of commands.
+
+use crate::config::HooksConfig;
+use crate::registry::SharedRegistry;
+use crate::ui::session_list::extract_session_name;
+use crate::{SessionInfo, SessionState};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+/// How often archived sessions, which aren't observed, are checked for
+/// transitions when nothing listed changes
+const ARCHIVED_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Hook commands are killed after this long
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub const SESSION_STARTED: &str = "session_started";
+pub const SESSION_ENDED: &str = "session_ended";
+pub const CWD_CONFLICT: &str = "cwd_conflict";
+pub const FILE_CONFLICT: &str = "file_conflict";
+
+/// Every trigger name a hook can be keyed on
+pub const TRIGGERS: &[&str] = &[
+    SESSION_STARTED,
+    SESSION_ENDED,
+    CWD_CONFLICT,
+    FILE_CONFLICT,
+    "running",
+    "idle",
+    "attention",
+    "waiting",
+    "compacting",
+    "stale",
+];
+
+/// A hook command due to run
+#[derive(Debug, Clone)]
+pub struct HookRun {
+    pub trigger: &'static str,
+    pub command: String,
+    /// Session as last seen (for `session_ended`, its final state)
+    pub session: SessionInfo,
+    pub previous_state: Option<SessionState>,
+}
+
+impl HookRun {
+    /// `AURA_*` variables passed to the command
+    pub fn env(&self) -> Vec<(&'static str, String)> {
+        let session = &self.session;
+        vec![
+            ("AURA_TRIGGER", self.trigger.to_string()),
+            ("AURA_SESSION_ID", session.session_id.clone()),
+            (
+                "AURA_NAME",
+                session
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| extract_session_name(&session.cwd)),
+            ),
+            ("AURA_CWD", session.cwd.clone()),
+            ("AURA_STATE", state_name(session.state).to_string()),
+            (
+                "AURA_PREVIOUS_STATE",
+                self.previous_state
+                    .map(state_name)
+                    .unwrap_or_default()
+                    .to_string(),
+            ),
+            ("AURA_CONFLICT_FILES", session.file_conflicts.join("\n")),
+        ]
+    }
+}
+
+/// Trigger name of a state (matches its serde name)
+fn state_name(state: SessionState) -> &'static str {
+    match state {
+        SessionState::Running => "running",
+        SessionState::Idle => "idle",
+        SessionState::Attention => "attention",
+        SessionState::Waiting => "waiting",
+        SessionState::Compacting => "compacting",
+        SessionState::Stale => "stale",
+    }
+}
+
+/// Diffs registry snapshots into hook runs
+#[derive(Debug)]
+pub struct HookRunner {
+    config: HooksConfig,
+    /// Sessions from the previous snapshot; `None` until the first one
+    seen: Option<HashMap<String, SessionInfo>>,
+    /// Last time each (session, trigger) fired
+    last_fired: HashMap<(String, &'static str), Instant>,
+}
+
+impl HookRunner {
+    pub fn new(config: HooksConfig) -> Self {
+        for trigger in config.commands.keys() {
+            if !TRIGGERS.contains(&trigger.as_str()) {
+                tracing::warn!(
+                    "Unknown hook trigger '{}' (expected one of {})",
+                    trigger,
+                    TRIGGERS.join(", ")
+                );
+            }
+        }
+        Self {
+            config,
+            seen: None,
+            last_fired: HashMap::new(),
+        }
+    }
+
+    /// Compare a snapshot (visible and archived sessions) with the previous
+    /// one and return the commands to run. The first snapshot only records
+    /// what already exists.
+    pub fn update(&mut self, sessions: &[SessionInfo], now: Instant) -> Vec<HookRun> {
+        let current: HashMap<String, SessionInfo> = sessions
+            .iter()
+            .map(|s| (s.session_id.clone(), s.clone()))
+            .collect();
+        let Some(previous) = self.seen.replace(current) else {
+            return Vec::new();
+        };
+        let current_ids: HashSet<&str> = sessions.iter().map(|s| s.session_id.as_str()).collect();
+
+        let mut transitions = Vec::new();
+        for session in sessions {
+            let was_conflicted = previous
+                .get(&session.session_id)
+                .is_some_and(|before| before.cwd_conflict);
+            if session.cwd_conflict && !was_conflicted {
+                transitions.push((CWD_CONFLICT, session.clone(), None));
+            }
+            let known_files = previous
+                .get(&session.session_id)
+                .map(|before| before.file_conflicts.as_slice())
+                .unwrap_or_default();
+            if session
+                .file_conflicts
+                .iter()
+                .any(|path| !known_files.contains(path))
+            {
+                transitions.push((FILE_CONFLICT, session.clone(), None));
+            }
+            match previous.get(&session.session_id) {
+                None => {
+                    transitions.push((SESSION_STARTED, session.clone(), None));
+                    transitions.push((state_name(session.state), session.clone(), None));
+                }
+                Some(before) if before.state != session.state => {
+                    transitions.push((
+                        state_name(session.state),
+                        session.clone(),
+                        Some(before.state),
+                    ));
+                }
+                Some(_) => {}
+            }
+        }
+        for (id, session) in &previous {
+            if !current_ids.contains(id.as_str()) {
+                transitions.push((SESSION_ENDED, session.clone(), Some(session.state)));
+            }
+        }
+
+        let debounce = Duration::from_secs(self.config.debounce_secs);
+        let mut runs = Vec::new();
+        for (trigger, session, previous_state) in transitions {
+            let Some(command) = self.config.commands.get(trigger) else {
+                continue;
+            };
+            let key = (session.session_id.clone(), trigger);
+            if self
+                .last_fired
+                .get(&key)
+                .is_some_and(|last| now.saturating_duration_since(*last) < debounce)
+            {
+                tracing::debug!(trigger, session_id = %session.session_id, "hook debounced");
+                continue;
+            }
+            self.last_fired.insert(key, now);
+            runs.push(HookRun {
+                trigger,
+                command: command.clone(),
+                session,
+                previous_state,
+            });
+        }
+
+        // Forget debounce entries that can no longer matter
+        self.last_fired
+            .retain(|_, last| now.saturating_duration_since(*last) < debounce);
+        runs
+    }
+}
+
+/// Shell and flag that run a command string
+const SHELL: (&str, &str) = if cfg!(windows) {
+    ("cmd", "/C")
+} else {
+    ("sh", "-c")
+};
+
+/// Run one hook command, logging failures
+async fn execute(run: HookRun) {
+    let spawned = tokio::process::Command::new(SHELL.0)
+        .arg(SHELL.1)
+        .arg(&run.command)
+        .envs(run.env())
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn();
+    let child = match spawned {
+        Ok(child) => child,
+        Err(e) => {
+            tracing::warn!("Failed to start {} hook: {}", run.trigger, e);
+            return;
+        }
+    };
+
+    match tokio::time::timeout(COMMAND_TIMEOUT, child.wait_with_output()).await {
+        Ok(Ok(output)) if output.status.success() => {
+            tracing::debug!(trigger = run.trigger, "hook finished");
+        }
+        Ok(Ok(output)) => tracing::warn!(
+            "{} hook `{}` failed ({}): {}",
+            run.trigger,
+            run.command,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+        Ok(Err(e)) => tracing::warn!("{} hook `{}` failed: {}", run.trigger, run.command, e),
+        Err(_) => tracing::warn!(
+            "{} hook `{}` timed out after {}s and was killed",
+            run.trigger,
+            run.command,
+            COMMAND_TIMEOUT.as_secs()
+        ),
+    }
+}
+
+/// Watch the registry and run hooks until the runtime shuts down.
+pub async fn run(config: HooksConfig, registry: SharedRegistry) {
+    let mut runner = HookRunner::new(config);
+    let Some(mut changes) = registry.observe() else {
+        return;
+    };
+    loop {
+        // Through the lock: a change is sent before its snapshot
+        let sessions = registry
+            .read(|registry| {
+                let mut sessions = registry.get_all();
+                sessions.extend(registry.get_archived());
+                sessions
+            })
+            .unwrap_or_default();
+        for hook in runner.update(&sessions, Instant::now()) {
+            tracing::info!(
+                trigger = hook.trigger,
+                session_id = %hook.session.session_id,
+                "running hook"
+            );
+            tokio::spawn(execute(hook));
+        }
+        match tokio::time::timeout(ARCHIVED_POLL_INTERVAL, changes.recv()).await {
+            Ok(None) => break,
+            Ok(Some(_)) => while changes.try_recv().is_ok() {},
+            Err(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn config(commands: &[(&str, &str)]) -> HooksConfig {
+        HooksConfig {
+            commands: commands
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<BTreeMap<_, _>>(),
+            debounce_secs: 5,
+        }
+    }
+
+    fn session(id: &str, state: SessionState) -> SessionInfo {
+        SessionInfo {
+            session_id: id.into(),
+            cwd: "/code/aura".into(),
+            state,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn first_snapshot_only_primes() {
+        let mut runner = HookRunner::new(config(&[(SESSION_STARTED, "echo hi")]));
+        let now = Instant::now();
+        assert!(
+            runner
+                .update(&[session("s1", SessionState::Running)], now)
+                .is_empty()
+        );
+        let runs = runner.update(
+            &[
+                session("s1", SessionState::Running),
+                session("s2", SessionState::Running),
+            ],
+            now,
+        );
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].session.session_id, "s2");
+    }
+
+    #[test]
+    fn state_transitions_and_session_end() {
+        let mut runner = HookRunner::new(config(&[
+            ("attention", "say attention"),
+            (SESSION_ENDED, "say bye"),
+        ]));
+        let now = Instant::now();
+        runner.update(&[session("s1", SessionState::Running)], now);
+
+        let runs = runner.update(&[session("s1", SessionState::Attention)], now);
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].trigger, "attention");
+        assert_eq!(runs[0].previous_state, Some(SessionState::Running));
+
+        let runs = runner.update(&[], now + Duration::from_secs(1));
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].trigger, SESSION_ENDED);
+        assert_eq!(runs[0].session.state, SessionState::Attention);
+    }
+
+    #[test]
+    fn cwd_conflict_fires_once_per_collision() {
+        let mut runner = HookRunner::new(config(&[(CWD_CONFLICT, "say conflict")]));
+        let now = Instant::now();
+        let conflicted = |id| SessionInfo {
+            cwd_conflict: true,
+            ..session(id, SessionState::Running)
+        };
+        runner.update(&[session("s1", SessionState::Running)], now);
+
+        let runs = runner.update(&[conflicted("s1"), conflicted("s2")], now);
+        let mut ids: Vec<_> = runs.iter().map(|r| r.session.session_id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["s1", "s2"]);
+        assert!(runs.iter().all(|r| r.trigger == CWD_CONFLICT));
+
+        // Still sharing the directory: nothing new
+        assert!(
+            runner
+                .update(&[conflicted("s1"), conflicted("s2")], now)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn debounces_per_session_and_trigger() {
+        let mut runner = HookRunner::new(config(&[("attention", "say attention")]));
+        let start = Instant::now();
+        runner.update(&[session("s1", SessionState::Running)], start);
+        assert_eq!(
+            runner
+                .update(&[session("s1", SessionState::Attention)], start)
+                .len(),
+            1
+        );
+        runner.update(&[session("s1", SessionState::Running)], start);
+        // Flapping back within the window is suppressed
+        assert!(
+            runner
+                .update(
+                    &[session("s1", SessionState::Attention)],
+                    start + Duration::from_secs(2)
+                )
+                .is_empty()
+        );
+        runner.update(&[session("s1", SessionState::Running)], start);
+        assert_eq!(
+            runner
+                .update(
+                    &[session("s1", SessionState::Attention)],
+                    start + Duration::from_secs(6)
+                )
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn env_describes_the_session() {
+        let run = HookRun {
+            trigger: "idle",
+            command: "true".into(),
+            session: SessionInfo {
+                name: Some("fix login".into()),
+                ..session("s1", SessionState::Idle)
+            },
+            previous_state: Some(SessionState::Running),
+        };
+        let env: HashMap<_, _> = run.env().into_iter().collect();
+        assert_eq!(env["AURA_SESSION_ID"], "s1");
+        assert_eq!(env["AURA_NAME"], "fix login");
+        assert_eq!(env["AURA_CWD"], "/code/aura");
+        assert_eq!(env["AURA_STATE"], "idle");
+        assert_eq!(env["AURA_PREVIOUS_STATE"], "running");
+        assert_eq!(env["AURA_TRIGGER"], "idle");
+        assert_eq!(env["AURA_CONFLICT_FILES"], "");
+    }
+
+    #[test]
+    fn file_conflict_fires_for_new_overlaps() {
+        let mut runner = HookRunner::new(config(&[(FILE_CONFLICT, "say conflict")]));
+        let now = Instant::now();
+        let editing = |files: &[&str]| SessionInfo {
+            file_conflicts: files.iter().map(|f| f.to_string()).collect(),
+            ..session("s1", SessionState::Running)
+        };
+        runner.update(&[editing(&[])], now);
+
+        let runs = runner.update(&[editing(&["/code/a.rs"])], now);
+        assert_eq!(runs.len(), 1);
+        let env: HashMap<_, _> = runs[0].env().into_iter().collect();
+        assert_eq!(env["AURA_CONFLICT_FILES"], "/code/a.rs");
+        assert!(runner.update(&[editing(&["/code/a.rs"])], now).is_empty());
+
+        let later = now + Duration::from_secs(10);
+        let runs = runner.update(&[editing(&["/code/a.rs", "/code/b.rs"])], later);
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].session.file_conflicts.len(), 2);
+    }
+
+    #[test]
+    fn state_names_match_serde() {
+        for state in [
+            SessionState::Running,
+            SessionState::Idle,
+            SessionState::Attention,
+            SessionState::Waiting,
+            SessionState::Compacting,
+            SessionState::Stale,
+        ] {
+            let serde_name = serde_json::to_value(state).unwrap();
+            assert_eq!(serde_name, state_name(state));
+            assert!(TRIGGERS.contains(&state_name(state)));
+        }
+    }
+}
@@ -0,0 +1,327 @@
+//! Ignore list - keep noisy tools, paths and whole projects out of the HUD
+//!
+//! The config's `ignore` object lists tool name globs and path globs (the
+//! [`crate::tags::glob_match`] syntax). A tool call whose name matches, or
+//! whose label or file path matches, is reduced to plain `Activity` by the
+//! adapters before it reaches the registry: the session still counts as
+//! working, but the call never shows as recent activity, and `aura export`
+//! leaves it out of the tool, category and file stats. A path pattern also
+//! matches when it matches just the file name, so `*.lock` and `Cargo.lock`
+//! both cover `/code/app/Cargo.lock`.
+//!
+//! Projects opt out of monitoring entirely with an [`OPT_OUT_MARKER`] file in
+//! their directory (or any parent), or a `directories` glob in the config:
+//! every event of a session working there is dropped, so it's never
+//! registered, shown, exported or captured.
+
+use crate::AgentEvent;
+use crate::tags::glob_match;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// File whose presence in a session's working directory, or a parent of it,
+/// opts the session out of monitoring
+pub const OPT_OUT_MARKER: &str = ".aura-ignore";
+
+/// The `"ignore"` object in config.json
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IgnoreConfig {
+    /// Tool name globs, e.g. `"TodoWrite"` or `"mcp__memory__*"`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<String>,
+    /// Path globs, e.g. `"*.lock"` or `"*/node_modules/*"`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
+    /// Directory globs whose sessions are never tracked, e.g.
+    /// `"/code/clients/*"`; subdirectories of a match are covered too
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub directories: Vec<String>,
+}
+
+impl IgnoreConfig {
+    pub fn is_empty(&self) -> bool {
+        self.tools.is_empty() && self.paths.is_empty() && self.directories.is_empty()
+    }
+
+    /// Whether sessions working in `cwd` must not be tracked: `cwd` or a
+    /// parent matches `directories` or holds the [`OPT_OUT_MARKER`]
+    pub fn opts_out(&self, cwd: &str) -> bool {
+        if cwd.is_empty() {
+            return false;
+        }
+        Path::new(cwd).ancestors().any(|dir| {
+            let dir_str = dir.to_string_lossy();
+            self.directories.iter().any(|p| glob_match(p, &dir_str))
+                || dir.join(OPT_OUT_MARKER).is_file()
+        })
+    }
+
+    pub fn ignores_tool(&self, tool_name: &str) -> bool {
+        self.tools.iter().any(|p| glob_match(p, tool_name))
+    }
+
+    pub fn ignores_path(&self, path: &str) -> bool {
+        let file_name = path.rsplit('/').next().unwrap_or(path);
+        self.paths
+            .iter()
+            .any(|p| glob_match(p, path) || glob_match(p, file_name))
+    }
+
+    /// Whether a tool call with this name and label is ignored
+    pub fn ignores_call(&self, tool_name: &str, tool_label: Option<&str>) -> bool {
+        self.ignores_tool(tool_name) || tool_label.is_some_and(|label| self.ignores_path(label))
+    }
+}
+
+/// Drops the events of opted-out sessions from an adapter's event stream,
+/// replaces ignored tool calls with `Activity` and drops the events that
+/// refer back to them by tool ID
+#[derive(Debug, Default)]
+pub struct IgnoreFilter {
+    config: IgnoreConfig,
+    /// (session ID, tool ID) of dropped `ToolStarted` events
+    dropped: HashSet<(String, String)>,
+    /// Sessions working in an opted-out directory
+    opted_out: HashSet<String>,
+    /// [`IgnoreConfig::opts_out`] per directory, checked once
+    directories: HashMap<String, bool>,
+}
+
+impl IgnoreFilter {
+    pub fn new(config: IgnoreConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// Whether a session was seen working in an opted-out directory
+    pub fn is_opted_out(&self, session_id: &str) -> bool {
+        self.opted_out.contains(session_id)
+    }
+
+    /// `events` without opted-out sessions, ignored tool calls and file paths
+    pub fn apply(&mut self, events: Vec<AgentEvent>) -> Vec<AgentEvent> {
+        events
+            .into_iter()
+            .filter(|event| !self.opts_out(event))
+            .filter_map(|event| self.filter(event))
+            .collect()
+    }
+
+    /// Whether `event` belongs to an opted-out session; the first event with
+    /// a working directory decides
+    fn opts_out(&mut self, event: &AgentEvent) -> bool {
+        let session_id = event.session_id();
+        if let AgentEvent::SessionEnded { .. } = event {
+            return self.opted_out.remove(session_id);
+        }
+        if self.opted_out.contains(session_id) {
+            return true;
+        }
+        let cwd = event.cwd();
+        if cwd.is_empty() {
+            return false;
+        }
+        let config = &self.config;
+        let opted_out = *self
+            .directories
+            .entry(cwd.to_string())
+            .or_insert_with(|| config.opts_out(cwd));
+        if opted_out {
+            tracing::info!(%session_id, cwd, "session opted out of monitoring");
+            self.opted_out.insert(session_id.to_string());
+        }
+        opted_out
+    }
+
+    fn filter(&mut self, event: AgentEvent) -> Option<AgentEvent> {
+        if self.config.is_empty() {
+            return Some(event);
+        }
+        match event {
+            AgentEvent::ToolStarted {
+                session_id,
+                cwd,
+                tool_id,
+                tool_name,
+                tool_label,
+                ..
+            } if self.config.ignores_call(&tool_name, tool_label.as_deref()) => {
+                self.dropped.insert((session_id.clone(), tool_id));
+                Some(AgentEvent::Activity { session_id, cwd })
+            }
+            AgentEvent::ToolCompleted {
+                ref session_id,
+                ref tool_id,
+                ..
+            } if self.dropped.remove(&(session_id.clone(), tool_id.clone())) => None,
+            AgentEvent::SubagentTool {
+                ref session_id,
+                ref tool_id,
+                ..
+            } if self
+                .dropped
+                .contains(&(session_id.clone(), tool_id.clone())) =>
+            {
+                None
+            }
+            AgentEvent::FilesTouched {
+                session_id,
+                mut changes,
+            } => {
+                changes.retain(|change| !self.config.ignores_path(&change.path));
+                (!changes.is_empty()).then_some(AgentEvent::FilesTouched {
+                    session_id,
+                    changes,
+                })
+            }
+            AgentEvent::SessionEnded { ref session_id } => {
+                self.dropped.retain(|(id, _)| id != session_id);
+                Some(event)
+            }
+            event => Some(event),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FileChange;
+
+    fn config() -> IgnoreConfig {
+        IgnoreConfig {
+            tools: vec!["TodoWrite".into(), "mcp__memory__*".into()],
+            paths: vec!["*.lock".into(), "*/node_modules/*".into()],
+            directories: vec!["/code/clients/*".into()],
+        }
+    }
+
+    fn started(tool_id: &str, tool_name: &str, tool_label: Option<&str>) -> AgentEvent {
+        AgentEvent::ToolStarted {
+            session_id: "s1".into(),
+            cwd: "/code/app".into(),
+            tool_id: tool_id.into(),
+            tool_name: tool_name.into(),
+            tool_label: tool_label.map(String::from),
+            tool_full_label: None,
+        }
+    }
+
+    fn completed(tool_id: &str) -> AgentEvent {
+        AgentEvent::ToolCompleted {
+            session_id: "s1".into(),
+            cwd: "/code/app".into(),
+            tool_id: tool_id.into(),
+        }
+    }
+
+    #[test]
+    fn tool_and_path_globs() {
+        let config = config();
+        assert!(config.ignores_tool("mcp__memory__search"));
+        assert!(!config.ignores_tool("mcp__github__create_pr"));
+        assert!(config.ignores_path("/code/app/Cargo.lock"));
+        assert!(config.ignores_path("Cargo.lock"));
+        assert!(config.ignores_path("/code/app/node_modules/x/index.js"));
+        assert!(!config.ignores_path("/code/app/src/lock.rs"));
+        assert!(config.ignores_call("Read", Some("Cargo.lock")));
+        assert!(!config.ignores_call("Read", Some("main.rs")));
+        assert!(!IgnoreConfig::default().ignores_call("Read", Some("Cargo.lock")));
+    }
+
+    #[test]
+    fn filter_drops_ignored_calls_and_their_completions() {
+        let mut filter = IgnoreFilter::new(config());
+        let events = filter.apply(vec![
+            started("t1", "Read", Some("Cargo.lock")),
+            AgentEvent::SubagentTool {
+                session_id: "s1".into(),
+                agent_id: "a1".into(),
+                tool_id: "t1".into(),
+            },
+            started("t2", "TodoWrite", None),
+            started("t3", "Read", Some("main.rs")),
+            AgentEvent::FilesTouched {
+                session_id: "s1".into(),
+                changes: vec![
+                    FileChange {
+                        path: "/code/app/Cargo.lock".into(),
+                        added: 3,
+                        removed: 1,
+                    },
+                    FileChange {
+                        path: "/code/app/Cargo.toml".into(),
+                        added: 1,
+                        removed: 0,
+                    },
+                ],
+            },
+        ]);
+        assert!(matches!(
+            events.as_slice(),
+            [
+                AgentEvent::Activity { .. },
+                AgentEvent::Activity { .. },
+                AgentEvent::ToolStarted { tool_id, .. },
+                AgentEvent::FilesTouched { changes, .. },
+            ] if tool_id == "t3" && changes.len() == 1 && changes[0].path.ends_with("Cargo.toml")
+        ));
+
+        // Completions of dropped calls go too, later ones pass through
+        let events = filter.apply(vec![completed("t1"), completed("t2"), completed("t3")]);
+        assert!(matches!(
+            events.as_slice(),
+            [AgentEvent::ToolCompleted { tool_id, .. }] if tool_id == "t3"
+        ));
+        assert!(filter.dropped.is_empty());
+    }
+
+    #[test]
+    fn opted_out_directories_by_config_and_marker() {
+        let config = config();
+        assert!(config.opts_out("/code/clients/acme"));
+        assert!(config.opts_out("/code/clients/acme/api"));
+        assert!(!config.opts_out("/code/aura"));
+        assert!(!config.opts_out(""));
+
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("secret");
+        std::fs::create_dir_all(project.join("src")).unwrap();
+        let nested = project.join("src").display().to_string();
+        assert!(!IgnoreConfig::default().opts_out(&nested));
+        std::fs::write(project.join(OPT_OUT_MARKER), "").unwrap();
+        assert!(IgnoreConfig::default().opts_out(&nested));
+    }
+
+    #[test]
+    fn filter_drops_every_event_of_opted_out_sessions() {
+        let mut filter = IgnoreFilter::new(config());
+        let events = filter.apply(vec![
+            AgentEvent::SessionStarted {
+                session_id: "s2".into(),
+                cwd: "/code/clients/acme".into(),
+                agent: crate::AgentType::Codex,
+                terminal: None,
+            },
+            AgentEvent::TranscriptPath {
+                session_id: "s2".into(),
+                path: "/tmp/rollout.jsonl".into(),
+            },
+            started("t1", "Read", Some("main.rs")),
+        ]);
+        assert!(matches!(
+            events.as_slice(),
+            [AgentEvent::ToolStarted { session_id, .. }] if session_id == "s1"
+        ));
+        assert!(filter.is_opted_out("s2"));
+
+        let ended = filter.apply(vec![AgentEvent::SessionEnded {
+            session_id: "s2".into(),
+        }]);
+        assert!(ended.is_empty());
+        assert!(!filter.is_opted_out("s2"));
+    }
+}
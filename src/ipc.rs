@@ -1,17 +1,346 @@
-//! IPC utilities for Unix socket communication
+//! IPC utilities for daemon communication
+//!
+//! The daemon listens on a Unix socket, or a named pipe on Windows
+//! ([`socket_path`] gives the address either way, [`connect`] opens it).
 //!
 //! The hook subprocess and daemon server both live in the same binary,
 //! so `AgentEvent` (from `event.rs`) is sent directly on the wire as
-//! newline-delimited JSON. This module only provides the shared socket path.
+//! newline-delimited JSON.
+//!
+//! CLI commands that query or control the daemon send an [`IpcMessage`]
+//! line (tagged by `command` rather than `type`, so it can't be confused
+//! with an `AgentEvent`) and read back a single [`IpcResponse`] line.
+//!
+//! Hooks send each event as a [`StampedEvent`]: the event's own fields plus
+//! when the hook process started and the event's place among those it sent,
+//! so the daemon can undo the reordering of hook processes that connect in a
+//! different order than they were fired (see
+//! [`SessionRegistry::process_stamped`](crate::registry::SessionRegistry::process_stamped)).
+//! Both fields are optional; unstamped events apply in arrival order. The
+//! envelope also carries the sender's [`PROTOCOL_VERSION`] as `version`.
+//!
+//! Neighbouring versions degrade instead of failing to parse: event types,
+//! commands and responses a side doesn't know read as their enum's `Unknown`
+//! variant (an event is skipped, a command answered with an `Error`), and
+//! unknown fields are ignored.
+//!
+//! `Ping` doubles as a handshake: the `Pong` carries the daemon's version,
+//! [`PROTOCOL_VERSION`], uptime and health, so a hook or CLI from another
+//! install can tell it's talking to an incompatible daemon.
+
+use crate::registry::BulkAction;
+use crate::{AgentEvent, SessionInfo};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Version of the wire format (events, requests and responses); bumped on
+/// incompatible changes. Daemons from before the handshake report 0.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// How long CLI requests wait for the daemon to respond
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Client end of a daemon connection
+#[cfg(unix)]
+pub type Connection = std::os::unix::net::UnixStream;
+
+/// Client end of a daemon connection (a named pipe opened as a file)
+#[cfg(windows)]
+pub type Connection = std::fs::File;
+
+/// Default socket path for the daemon (in [`crate::paths::runtime_dir`]).
+#[cfg(unix)]
+pub fn socket_path() -> PathBuf {
+    crate::paths::runtime_dir().join("aura.sock")
+}
+
+/// Named pipe of the daemon, one per user:
+/// `\\.\pipe\aura-<user>`, or `aura-<name>` with
+/// [`AURA_RUNTIME_DIR`](crate::paths::RUNTIME_DIR_ENV) set to `<name>`.
+#[cfg(windows)]
+pub fn socket_path() -> PathBuf {
+    let name = std::env::var(crate::paths::RUNTIME_DIR_ENV)
+        .ok()
+        .filter(|name| !name.is_empty())
+        .or_else(|| std::env::var("USERNAME").ok())
+        .unwrap_or_default();
+    PathBuf::from(pipe_name(&name))
+}
+
+/// Pipe path for `name`; pipe names can't contain backslashes
+#[cfg_attr(not(windows), allow(dead_code))]
+fn pipe_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c == '\\' || c == '/' || c == ':' {
+                '-'
+            } else {
+                c
+            }
+        })
+        .collect();
+    if name.is_empty() {
+        r"\\.\pipe\aura".to_string()
+    } else {
+        format!(r"\\.\pipe\aura-{name}")
+    }
+}
+
+/// Connect to the daemon.
+#[cfg(unix)]
+pub fn connect() -> std::io::Result<Connection> {
+    Connection::connect(socket_path())
+}
+
+/// Connect to the daemon.
+#[cfg(windows)]
+pub fn connect() -> std::io::Result<Connection> {
+    std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(socket_path())
+}
 
-/// Default socket path for the daemon.
-pub fn socket_path() -> std::path::PathBuf {
-    std::env::temp_dir().join("aura.sock")
+/// When a hook sent an event: its client timestamp, then its position among
+/// the events of that hook, for events sharing a timestamp
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EventStamp {
+    /// Unix time in milliseconds when the hook process started
+    pub sent_at_ms: u64,
+    pub seq: u32,
+}
+
+/// An `AgentEvent` line, optionally stamped by the sender
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StampedEvent {
+    #[serde(flatten)]
+    pub event: AgentEvent,
+    /// Sender's [`PROTOCOL_VERSION`]; `None` from hooks older than the field
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sent_at_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seq: Option<u32>,
+}
+
+impl StampedEvent {
+    /// `event` as the `seq`th event sent at `sent_at_ms`
+    pub fn new(event: AgentEvent, sent_at_ms: u64, seq: u32) -> Self {
+        Self {
+            event,
+            version: Some(PROTOCOL_VERSION),
+            sent_at_ms: Some(sent_at_ms),
+            seq: Some(seq),
+        }
+    }
+
+    /// The sender's stamp; `None` for unstamped events
+    pub fn stamp(&self) -> Option<EventStamp> {
+        Some(EventStamp {
+            sent_at_ms: self.sent_at_ms?,
+            seq: self.seq.unwrap_or(0),
+        })
+    }
+}
+
+/// Control request from a CLI command to the daemon
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum IpcMessage {
+    /// Liveness check and version handshake
+    Ping,
+    /// Snooze a session (by ID or unique ID prefix) for `duration_secs`
+    Snooze {
+        session_id: String,
+        duration_secs: u64,
+    },
+    /// Clear a session's snooze
+    Unsnooze { session_id: String },
+    /// Acknowledge a session's Attention/Waiting/Idle state (see
+    /// [`SessionRegistry::mark_seen`](crate::registry::SessionRegistry::mark_seen))
+    Ack { session_id: String },
+    /// List tracked sessions (archived ones only when `archived` is set)
+    ListSessions {
+        #[serde(default)]
+        archived: bool,
+    },
+    /// Hide a session from the list without forgetting it
+    Archive { session_id: String },
+    /// Bring an archived session back into the list
+    Restore { session_id: String },
+    /// Apply a bulk action to all visible sessions
+    Bulk { action: BulkAction },
+    /// Set a session's display name
+    Rename { session_id: String, name: String },
+    /// Look up one session (visible or archived) without changing it
+    GetSession { session_id: String },
+    /// Add tags to a session
+    Tag {
+        session_id: String,
+        tags: Vec<String>,
+    },
+    /// Remove tags from a session
+    Untag {
+        session_id: String,
+        tags: Vec<String>,
+    },
+    /// Restart the daemon onto the binary now at its path (after
+    /// `aura self-update`); answered with `Pong` first
+    Restart,
+    /// Drop a source's events until resumed (see [`crate::pause`]);
+    /// answered with `Pong`
+    Pause { source: crate::pause::Source },
+    /// Apply a paused source's events again; answered with `Pong`
+    Resume { source: crate::pause::Source },
+    /// Answered with `Sessions` (the visible ones), then again whenever they
+    /// change, until the client disconnects (`aura status --follow`)
+    Watch,
+    /// A command from a newer CLI; answered with `Error`
+    #[serde(other)]
+    Unknown,
+}
+
+/// Daemon reply to an [`IpcMessage`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum IpcResponse {
+    /// Reply to `Ping`; fields are empty from daemons older than the handshake
+    Pong {
+        #[serde(default)]
+        version: String,
+        #[serde(default)]
+        protocol: u32,
+        #[serde(default)]
+        health: Option<crate::health::HealthReport>,
+    },
+    /// Request succeeded; carries the affected session
+    Session { session: SessionInfo },
+    /// Reply to `ListSessions`
+    Sessions { sessions: Vec<SessionInfo> },
+    /// Number of sessions changed by a bulk action
+    Affected { count: usize },
+    /// Request failed (e.g. no matching session)
+    Error { message: String },
+    /// A reply type from a newer daemon
+    #[serde(other)]
+    Unknown,
+}
+
+/// Why a daemon that answered `response` to a `Ping` can't be relied on by
+/// this binary, if it can't
+pub fn incompatibility(response: &IpcResponse) -> Option<String> {
+    match response {
+        IpcResponse::Pong { protocol, .. } if *protocol == PROTOCOL_VERSION => None,
+        IpcResponse::Pong {
+            version, protocol, ..
+        } => {
+            let daemon = if version.is_empty() {
+                "an older aura"
+            } else {
+                version
+            };
+            let ours = format!(
+                "{} (protocol {PROTOCOL_VERSION})",
+                env!("CARGO_PKG_VERSION")
+            );
+            Some(format!(
+                "daemon ({daemon}, protocol {protocol}) doesn't match aura {ours}; restart it"
+            ))
+        }
+        _ => Some("daemon didn't answer the ping".to_string()),
+    }
+}
+
+/// Send a control request to the daemon and wait for its response.
+pub fn send_request(message: &IpcMessage) -> std::io::Result<IpcResponse> {
+    send_request_within(message, REQUEST_TIMEOUT)
+}
+
+/// [`send_request`], giving up after `timeout` instead of [`REQUEST_TIMEOUT`]
+pub fn send_request_within(
+    message: &IpcMessage,
+    timeout: Duration,
+) -> std::io::Result<IpcResponse> {
+    let stream = connect()?;
+    let json = serde_json::to_string(message).map_err(std::io::Error::other)?;
+    #[cfg(unix)]
+    {
+        stream.set_read_timeout(Some(timeout))?;
+        stream.set_write_timeout(Some(timeout))?;
+        exchange(stream, json)
+    }
+    #[cfg(windows)]
+    with_deadline(timeout, move || exchange(stream, json))
+}
+
+/// Write the request line `json` and read back one response
+fn exchange(stream: Connection, json: String) -> std::io::Result<IpcResponse> {
+    let stream = send_line(stream, json)?;
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line)?;
+    serde_json::from_str(line.trim()).map_err(std::io::Error::other)
+}
+
+/// Write `json` as one line, handing the stream back
+fn send_line(mut stream: Connection, json: String) -> std::io::Result<Connection> {
+    writeln!(stream, "{json}")?;
+    Ok(stream)
+}
+
+/// Run `io` on a helper thread, giving up after `timeout`: named pipes
+/// opened as files have no timeouts of their own. A pipe that never answers
+/// keeps its thread blocked until the CLI exits.
+#[cfg(windows)]
+fn with_deadline<T: Send + 'static>(
+    timeout: Duration,
+    io: impl FnOnce() -> std::io::Result<T> + Send + 'static,
+) -> std::io::Result<T> {
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = tx.send(io());
+    });
+    rx.recv_timeout(timeout).map_err(|_| {
+        std::io::Error::new(
+            std::io::ErrorKind::TimedOut,
+            "daemon didn't respond in time",
+        )
+    })?
+}
+
+/// Send `Watch` and hand each list of sessions the daemon pushes to
+/// `on_update`; returns once the daemon closes the connection.
+pub fn watch(mut on_update: impl FnMut(Vec<SessionInfo>)) -> std::io::Result<()> {
+    let stream = connect()?;
+    let json = serde_json::to_string(&IpcMessage::Watch).map_err(std::io::Error::other)?;
+    #[cfg(unix)]
+    let stream = {
+        stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+        send_line(stream, json)?
+    };
+    #[cfg(windows)]
+    let stream = with_deadline(REQUEST_TIMEOUT, move || send_line(stream, json))?;
+
+    for line in BufReader::new(stream).lines() {
+        match serde_json::from_str(line?.trim()).map_err(std::io::Error::other)? {
+            IpcResponse::Sessions { sessions } => on_update(sessions),
+            IpcResponse::Error { message } => return Err(std::io::Error::other(message)),
+            other => {
+                return Err(std::io::Error::other(format!(
+                    "unexpected daemon response: {other:?}"
+                )));
+            }
+        }
+    }
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::AgentEvent;
+    use super::*;
 
     #[test]
     fn agent_event_ipc_roundtrip() {
@@ -21,6 +350,7 @@ mod tests {
             tool_id: "t1".into(),
             tool_name: "Bash".into(),
             tool_label: Some("npm test".into()),
+            tool_full_label: None,
         };
 
         let json = serde_json::to_string(&event).unwrap();
@@ -53,6 +383,83 @@ mod tests {
         assert_eq!(event.session_id(), "abc");
     }
 
+    #[test]
+    fn stamped_events_extend_the_event_line() {
+        let event = AgentEvent::Idle {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+        };
+        let json = serde_json::to_string(&StampedEvent::new(event, 1_700_000_000_123, 2)).unwrap();
+        assert!(json.contains("\"type\":\"idle\""));
+        assert!(json.contains("\"sent_at_ms\":1700000000123"));
+        // Daemons from before stamps read the line as a plain event
+        assert_eq!(
+            serde_json::from_str::<AgentEvent>(&json)
+                .unwrap()
+                .session_id(),
+            "s1"
+        );
+
+        let parsed: StampedEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            parsed.stamp(),
+            Some(EventStamp {
+                sent_at_ms: 1_700_000_000_123,
+                seq: 2
+            })
+        );
+        let plain: StampedEvent =
+            serde_json::from_str(r#"{"type":"session_ended","session_id":"s1"}"#).unwrap();
+        assert_eq!(plain.stamp(), None);
+        assert!(serde_json::from_str::<StampedEvent>(r#"{"command":"ping"}"#).is_err());
+    }
+
+    #[test]
+    fn unknown_types_from_other_versions_still_parse() {
+        // A newer hook: a type this daemon lacks, and an extra field
+        let newer: StampedEvent = serde_json::from_str(
+            r#"{"type":"context_window","session_id":"s1","percent":80,"version":9}"#,
+        )
+        .unwrap();
+        assert!(matches!(newer.event, AgentEvent::Unknown));
+        assert_eq!(newer.version, Some(9));
+        let extra = r#"{"type":"idle","session_id":"s1","cwd":"/tmp","effort":"high"}"#;
+        let known: StampedEvent = serde_json::from_str(extra).unwrap();
+        assert!(matches!(known.event, AgentEvent::Idle { .. }));
+        assert_eq!(known.version, None);
+
+        let command: IpcMessage =
+            serde_json::from_str(r#"{"command":"summarize","session_id":"s1"}"#).unwrap();
+        assert!(matches!(command, IpcMessage::Unknown));
+        let response: IpcResponse =
+            serde_json::from_str(r#"{"type":"summary","text":"x"}"#).unwrap();
+        assert!(matches!(response, IpcResponse::Unknown));
+        assert!(incompatibility(&response).is_some());
+    }
+
+    #[test]
+    fn pong_handshake_detects_skew() {
+        // Daemons from before the handshake answer a bare pong
+        let old: IpcResponse = serde_json::from_str(r#"{"type":"pong"}"#).unwrap();
+        let problem = incompatibility(&old).unwrap();
+        assert!(problem.contains("an older aura, protocol 0"));
+
+        let current = IpcResponse::Pong {
+            version: "0.2.0".into(),
+            protocol: PROTOCOL_VERSION,
+            health: None,
+        };
+        let json = serde_json::to_string(&current).unwrap();
+        let parsed: IpcResponse = serde_json::from_str(&json).unwrap();
+        assert!(incompatibility(&parsed).is_none());
+        assert!(
+            incompatibility(&IpcResponse::Error {
+                message: "unknown".into()
+            })
+            .is_some()
+        );
+    }
+
     #[test]
     fn agent_event_ipc_all_variants() {
         let messages = vec![
@@ -72,4 +479,58 @@ mod tests {
             let _event: AgentEvent = serde_json::from_str(json).unwrap();
         }
     }
+
+    #[test]
+    fn ipc_message_is_not_an_agent_event() {
+        let json = serde_json::to_string(&IpcMessage::Snooze {
+            session_id: "s1".into(),
+            duration_secs: 1800,
+        })
+        .unwrap();
+        assert!(json.contains("\"command\":\"snooze\""));
+        assert!(serde_json::from_str::<AgentEvent>(&json).is_err());
+
+        let parsed: IpcMessage = serde_json::from_str(&json).unwrap();
+        assert!(matches!(
+            parsed,
+            IpcMessage::Snooze {
+                duration_secs: 1800,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn pause_names_its_source() {
+        let parsed: IpcMessage =
+            serde_json::from_str(r#"{"command":"pause","source":"codex"}"#).unwrap();
+        assert!(matches!(
+            parsed,
+            IpcMessage::Pause {
+                source: crate::pause::Source::Codex
+            }
+        ));
+        let json = serde_json::to_string(&IpcMessage::Resume {
+            source: crate::pause::Source::ClaudeCode,
+        })
+        .unwrap();
+        assert_eq!(json, r#"{"command":"resume","source":"claude_code"}"#);
+    }
+
+    #[test]
+    fn pipe_name_is_per_user() {
+        assert_eq!(pipe_name("alice"), r"\\.\pipe\aura-alice");
+        assert_eq!(pipe_name(r"CORP\alice"), r"\\.\pipe\aura-CORP-alice");
+        assert_eq!(pipe_name(""), r"\\.\pipe\aura");
+    }
+
+    #[test]
+    fn ipc_response_roundtrip() {
+        let json = serde_json::to_string(&IpcResponse::Error {
+            message: "no session".into(),
+        })
+        .unwrap();
+        let parsed: IpcResponse = serde_json::from_str(&json).unwrap();
+        assert!(matches!(parsed, IpcResponse::Error { message } if message == "no session"));
+    }
 }
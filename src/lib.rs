@@ -1,14 +1,44 @@
 //! Aura — HUD for AI coding agents
 
+pub mod capture;
+pub mod cli;
+pub mod clock;
+pub mod coalesce;
 pub mod config;
+pub mod demo;
+pub mod editor_api;
 mod event;
+pub mod export;
+pub mod filter;
+pub mod headless;
+pub mod health;
+pub mod hooks;
+pub mod ignore;
 pub mod ipc;
+pub mod logging;
 mod session;
 
 pub use event::*;
 pub use session::*;
 
 pub mod agents;
+pub mod notifier;
+pub mod paths;
+pub mod pause;
+pub mod privacy;
+pub mod prompt;
+pub mod queue;
 pub mod registry;
+pub mod reminder;
 pub mod server;
+pub mod shortcut;
+#[cfg(feature = "simulator")]
+pub mod simulator;
+pub mod supervisor;
+pub mod sysinfo;
+pub mod tags;
+pub mod tailer;
+pub mod tools;
+pub mod transcript;
 pub mod ui;
+pub mod update;
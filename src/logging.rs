@@ -0,0 +1,135 @@
+//! Logging - subsystem targets, console output and the daemon log file
+//!
+//! Log lines carry a short subsystem target instead of the module path, so
+//! `AURA_LOG=codex.watcher=debug` narrows output to one part of the daemon:
+//!
+//! | Target          | Emitted by                                   |
+//! |-----------------|----------------------------------------------|
+//! | `ipc`           | socket / named pipe server                   |
+//! | `codex.watcher` | Codex rollout watcher and parser             |
+//! | `registry`      | session registry and event coalescer         |
+//! | `ui`            | HUD windows                                  |
+//!
+//! The daemon also writes a daily log file to [`crate::paths::log_dir`]
+//! (keeping a week of them), which the HUD's "Show Log" window tails.
+
+use std::path::PathBuf;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::{EnvFilter, Layer, fmt, layer::SubscriberExt, util::SubscriberInitExt};
+
+/// Socket / named pipe server
+pub const IPC: &str = "ipc";
+/// Codex rollout watcher
+pub const CODEX_WATCHER: &str = "codex.watcher";
+/// Session registry and event coalescing
+pub const REGISTRY: &str = "registry";
+/// HUD windows
+pub const UI: &str = "ui";
+
+/// Log files are `aura.<date>.log`
+const FILE_PREFIX: &str = "aura";
+const FILE_SUFFIX: &str = "log";
+/// Daily files kept before the oldest is deleted
+const MAX_FILES: usize = 7;
+
+/// Filter from `AURA_LOG`, else everything at `default_level`
+fn filter(default_level: &str) -> EnvFilter {
+    EnvFilter::try_from_env("AURA_LOG").unwrap_or_else(|_| EnvFilter::new(default_level))
+}
+
+/// Console level for `-v` flags
+fn console_level(verbose: u8) -> &'static str {
+    match verbose {
+        0 => "warn",
+        1 => "info",
+        2 => "debug",
+        _ => "trace",
+    }
+}
+
+/// Install the global subscriber: console output per `verbose`, and with
+/// `to_file` an info-level (or more verbose) log file.
+///
+/// Keep the returned guard alive; dropping it flushes and closes the file.
+pub fn init(verbose: u8, to_file: bool) -> Option<WorkerGuard> {
+    let level = console_level(verbose);
+    let console = fmt::layer().with_filter(filter(level));
+
+    let (file, guard) = match to_file.then(open_appender).flatten() {
+        Some(appender) => {
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            let file_level = if verbose == 0 { "info" } else { level };
+            let layer = fmt::layer()
+                .with_ansi(false)
+                .with_writer(writer)
+                .with_filter(filter(file_level));
+            (Some(layer), Some(guard))
+        }
+        None => (None, None),
+    };
+
+    tracing_subscriber::registry()
+        .with(console)
+        .with(file)
+        .init();
+    guard
+}
+
+fn open_appender() -> Option<RollingFileAppender> {
+    let dir = crate::paths::log_dir()?;
+    if let Err(e) = crate::paths::ensure_private_dir(&dir) {
+        eprintln!("aura: can't create log dir {}: {e}", dir.display());
+        return None;
+    }
+    RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(FILE_PREFIX)
+        .filename_suffix(FILE_SUFFIX)
+        .max_log_files(MAX_FILES)
+        .build(&dir)
+        .inspect_err(|e| eprintln!("aura: can't open log file in {}: {e}", dir.display()))
+        .ok()
+}
+
+/// Most recent log file, if the daemon has written one
+pub fn latest_file() -> Option<PathBuf> {
+    latest_file_in(&crate::paths::log_dir()?)
+}
+
+fn latest_file_in(dir: &std::path::Path) -> Option<PathBuf> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| is_log_file(path))
+        // Dates sort lexically
+        .max()
+}
+
+fn is_log_file(path: &std::path::Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| {
+            name.starts_with(&format!("{FILE_PREFIX}."))
+                && name.ends_with(&format!(".{FILE_SUFFIX}"))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latest_file_picks_newest_log() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(latest_file_in(dir.path()), None);
+        for name in ["aura.2026-10-12.log", "aura.2026-10-14.log", "notes.txt"] {
+            std::fs::write(dir.path().join(name), "").unwrap();
+        }
+        assert_eq!(
+            latest_file_in(dir.path()),
+            Some(dir.path().join("aura.2026-10-14.log"))
+        );
+    }
+}
@@ -4,20 +4,17 @@
 //! and renders the notch-flanking HUD icons.
 
 use aura::agents::claude_code::HookAgent;
-use aura::{registry::SessionRegistry, ui};
+use aura::registry::BulkAction;
+use aura::registry::{SessionRegistry, SharedRegistry};
+use aura::ui;
 use clap::Parser;
 #[cfg(target_os = "macos")]
 use std::path::PathBuf;
 #[cfg(target_os = "macos")]
 use std::process::Command as ProcessCommand;
-use std::sync::{
-    Arc, Mutex,
-    atomic::{AtomicBool, Ordering},
-};
-use std::time::{Duration, Instant};
+use std::time::Duration;
 #[cfg(target_os = "macos")]
 use tracing::{debug, info};
-use tracing_subscriber::{EnvFilter, fmt};
 
 /// Stale timeout - mark session stale after 10min of no activity
 const STALE_TIMEOUT: Duration = Duration::from_secs(600);
@@ -29,6 +26,37 @@ struct Cli {
     #[arg(short, long, action = clap::ArgAction::Count)]
     verbose: u8,
 
+    /// Run the daemon without the HUD, printing session changes to stdout
+    /// (always on for Windows)
+    #[arg(long)]
+    headless: bool,
+
+    /// Show made-up sessions with fixed animation seeds instead of real
+    /// agents (for screenshots and videos)
+    #[arg(
+        long,
+        hide = true,
+        value_enum,
+        num_args = 0..=1,
+        default_missing_value = "all",
+        value_name = "SCENARIO",
+        conflicts_with = "headless"
+    )]
+    demo: Option<aura::demo::Scenario>,
+
+    /// With --demo: theme to use instead of the configured one
+    #[arg(
+        long,
+        hide = true,
+        requires = "demo",
+        value_parser = ["liquid-dark", "liquid-light"]
+    )]
+    demo_theme: Option<String>,
+
+    /// With --demo: save the HUD to this PNG once it settled, then quit
+    #[arg(long, hide = true, requires = "demo", value_name = "PNG")]
+    screenshot: Option<PathBuf>,
+
     #[command(subcommand)]
     command: Option<Command>,
 }
@@ -40,24 +68,229 @@ enum Command {
         /// The name to display for the current session
         name: String,
     },
+    /// Tag the current session (picked up from the agent's Bash tool call)
+    SetTag {
+        /// Tags to add, e.g. `billing hotfix`
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
     /// Handle agent hook events (reads JSON from stdin, forwards to daemon)
     Hook {
         /// Agent type whose hook format to parse
         #[arg(long, value_enum)]
         agent: HookAgent,
     },
+    /// Show the aggregate indicator state of the running daemon
+    Status {
+        /// Print versioned JSON instead of text
+        #[arg(long)]
+        json: bool,
+        /// Keep running and print a line whenever the state changes
+        #[arg(long, short)]
+        follow: bool,
+    },
+    /// Acknowledge an Attention/Waiting/Idle session so it stops pulsing
+    /// (the agent state is unchanged)
+    Ack {
+        /// Session ID (or a unique prefix)
+        session_id: String,
+    },
+    /// Stop applying one source's events without quitting the daemon
+    /// (e.g. while cleaning up Codex rollouts)
+    Pause {
+        #[arg(value_enum)]
+        source: aura::pause::Source,
+    },
+    /// Apply a paused source's events again
+    Resume {
+        #[arg(value_enum)]
+        source: aura::pause::Source,
+    },
+    /// Inspect and control sessions tracked by the running daemon
+    Sessions {
+        #[command(subcommand)]
+        command: SessionsCommand,
+    },
+    /// JSON interface for macOS Shortcuts and AppleScript automations
+    Shortcut {
+        #[command(subcommand)]
+        command: ShortcutCommand,
+    },
+    /// Print a short state glyph and name for the session in a directory
+    /// (for starship and other prompt tools)
+    PromptSegment {
+        /// Directory to match (defaults to the current directory)
+        #[arg(long)]
+        cwd: Option<std::path::PathBuf>,
+    },
+    /// Record raw agent input to a capture file, or replay one
+    Debug {
+        #[command(subcommand)]
+        command: DebugCommand,
+    },
+    /// Send fake agent sessions to the running daemon (for demos)
+    #[cfg(feature = "simulator")]
+    Simulate {
+        /// Number of fake sessions
+        #[arg(long, default_value_t = 5)]
+        sessions: usize,
+        /// Simulation script (TOML); built-in defaults otherwise
+        #[arg(long)]
+        script: Option<std::path::PathBuf>,
+    },
+    /// Summarize a session's transcript (prompts, tools, files, duration)
+    Export {
+        /// Session ID (or a unique prefix)
+        session_id: String,
+        /// Output format
+        #[arg(long, value_enum, default_value = "md")]
+        format: aura::export::ExportFormat,
+        /// Write to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Update the standalone binary to the latest release
+    SelfUpdate {
+        /// Only report whether a newer release exists
+        #[arg(long)]
+        check: bool,
+    },
 }
 
-fn init_tracing(verbose: u8) {
-    let default_level = match verbose {
-        0 => "warn",
-        1 => "info",
-        2 => "debug",
-        _ => "trace",
-    };
-    let filter =
-        EnvFilter::try_from_env("AURA_LOG").unwrap_or_else(|_| EnvFilter::new(default_level));
-    fmt().with_env_filter(filter).with_target(false).init();
+#[derive(clap::Subcommand)]
+enum SessionsCommand {
+    /// Snooze a session so it no longer drives the indicator
+    Snooze {
+        /// Session ID (or a unique prefix)
+        session_id: String,
+        /// How long to snooze, e.g. 30m, 2h, 1h30m
+        #[arg(value_parser = aura::cli::parse_duration, default_value = "30m")]
+        duration: Duration,
+    },
+    /// Clear a session's snooze
+    Unsnooze {
+        /// Session ID (or a unique prefix)
+        session_id: String,
+    },
+    /// List sessions tracked by the daemon
+    List {
+        /// Show archived sessions instead of the visible ones
+        #[arg(long)]
+        archived: bool,
+        /// Only sessions with this tag
+        #[arg(long)]
+        tag: Option<String>,
+        /// Print versioned JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show one session in detail (including files it changed)
+    Show {
+        /// Session ID (or a unique prefix)
+        session_id: String,
+        /// Print versioned JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+    /// Hide a session from the HUD without forgetting it
+    Archive {
+        /// Session ID (or a unique prefix)
+        session_id: String,
+    },
+    /// Bring an archived session back into the HUD
+    Restore {
+        /// Session ID (or a unique prefix)
+        session_id: String,
+    },
+    /// Set a session's display name
+    Rename {
+        /// Session ID (or a unique prefix)
+        session_id: String,
+        /// New name
+        name: String,
+    },
+    /// Add tags to a session
+    Tag {
+        /// Session ID (or a unique prefix)
+        session_id: String,
+        /// Tags to add
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+    /// Remove tags from a session
+    Untag {
+        /// Session ID (or a unique prefix)
+        session_id: String,
+        /// Tags to remove
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+    /// Apply an action to all visible sessions
+    Bulk {
+        #[arg(value_enum)]
+        action: BulkAction,
+    },
+}
+
+#[derive(clap::Subcommand)]
+enum DebugCommand {
+    /// Append hook payloads and Codex rollout lines to a capture file
+    /// (for bug reports) until stopped
+    Record {
+        /// Capture file (JSONL, appended to)
+        #[arg(required_unless_present = "stop")]
+        output: Option<std::path::PathBuf>,
+        /// Only record this session (ID or prefix)
+        #[arg(long)]
+        session: Option<String>,
+        /// Stop the recording in progress
+        #[arg(long, conflicts_with_all = ["output", "session"])]
+        stop: bool,
+    },
+    /// Feed a capture through the parsers and print the resulting sessions
+    Replay {
+        /// Capture file written by `aura debug record`
+        capture: std::path::PathBuf,
+        /// Also print every event, as JSON
+        #[arg(long)]
+        events: bool,
+    },
+}
+
+#[derive(clap::Subcommand)]
+enum ShortcutCommand {
+    /// Visible sessions as JSON
+    List,
+    /// Aggregate indicator state and per-state counts
+    State,
+    /// Snooze a session, or every visible session with `all`
+    Snooze {
+        /// Session ID, unique prefix, or `all`
+        session_id: String,
+        /// How long to snooze, e.g. 30m, 2h, 1h30m
+        #[arg(value_parser = aura::cli::parse_duration, default_value = "30m")]
+        duration: Duration,
+    },
+    /// Clear a session's snooze, or every visible session's with `all`
+    Unsnooze {
+        /// Session ID, unique prefix, or `all`
+        session_id: String,
+    },
+    /// Set a session's display name
+    Rename {
+        /// Session ID (or a unique prefix)
+        session_id: String,
+        /// New name
+        name: String,
+    },
+}
+
+/// Report a CLI handler error and exit non-zero.
+fn exit_on_error(result: Result<(), String>) {
+    if let Err(message) = result {
+        eprintln!("aura: {message}");
+        std::process::exit(1);
+    }
 }
 
 #[cfg(target_os = "macos")]
@@ -135,6 +368,127 @@ fn install_cli_tool() {
 #[cfg(not(target_os = "macos"))]
 fn install_cli_tool() {}
 
+/// Background tasks of the daemon, restarted by [`aura::supervisor`] when
+/// they fail; runs until the runtime shuts down.
+async fn run_services(registry: SharedRegistry) {
+    aura::health::daemon_started();
+
+    // Spawn stale detection task — sleeps until the next session is due
+    // to go stale (or a completed tool leaves the snapshot, held events
+    // expire, or a stale session is due for retention) instead of
+    // polling at a fixed interval, rechecking whenever the registry changes.
+    let stale_registry = registry.clone();
+    aura::supervisor::spawn("stale detection", move || {
+        let stale_registry = stale_registry.clone();
+        async move {
+            let mut changes = stale_registry.subscribe();
+            loop {
+                let sleep_duration = stale_registry
+                    .read(|reg| {
+                        let now = reg.clock().now();
+                        let next = [
+                            reg.next_stale_at(STALE_TIMEOUT),
+                            reg.next_tool_expiry(),
+                            reg.next_gc_at(),
+                            reg.next_pending_expiry(),
+                        ];
+                        next.into_iter()
+                            .flatten()
+                            .min()
+                            .map(|t| t.saturating_duration_since(now) + Duration::from_millis(100))
+                            .unwrap_or(Duration::from_secs(30))
+                    })
+                    .unwrap_or(Duration::from_secs(5));
+
+                tokio::select! {
+                    _ = tokio::time::sleep(sleep_duration) => {
+                        stale_registry.update(|reg| {
+                            reg.expire_pending();
+                            reg.mark_stale(STALE_TIMEOUT);
+                            reg.collect_garbage();
+                        });
+                    }
+                    changed = changes.changed() => {
+                        if changed.is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    });
+
+    // Hook and Codex events reach the registry in 50ms batches
+    let events = aura::coalesce::Coalescer::spawn(registry.clone());
+
+    // Spawn Codex session rollout watcher (event stream producer)
+    let codex_stream = aura::agents::codex::spawn();
+    let codex_events = events.clone();
+    aura::supervisor::spawn("codex events", move || {
+        let mut rx = codex_stream.subscribe();
+        let codex_events = codex_events.clone();
+        async move {
+            while let Some(event) = rx.recv().await {
+                codex_events.send(event, aura::AgentType::Codex);
+            }
+        }
+    });
+
+    // Count prompts queued behind Claude Code turns (transcript `queue-operation` lines)
+    let (queue_registry, queue_events) = (registry.clone(), events.clone());
+    aura::supervisor::spawn("queue", move || {
+        aura::queue::run(queue_registry.clone(), queue_events.clone())
+    });
+
+    // Sample each agent's CPU use (row badge, animations held on battery)
+    if aura::config::load_config().ui.show_cpu_load {
+        let sysinfo_registry = registry.clone();
+        aura::supervisor::spawn("cpu sampler", move || {
+            aura::sysinfo::run(sysinfo_registry.clone())
+        });
+    }
+
+    // Post Slack / Discord notifications while the user is away
+    let notifications = aura::config::load_config().notifications;
+    if notifications.is_enabled() {
+        tokio::spawn(aura::notifier::run(notifications, registry.clone()));
+    }
+
+    // Remind about finished sessions nobody reviewed (config `ui.review_reminder_minutes`)
+    if let Some(after) = aura::config::load_config().ui.review_reminder() {
+        tokio::spawn(aura::reminder::run(after, registry.clone()));
+    }
+
+    // Publish session states for `aura prompt-segment`
+    tokio::spawn(aura::prompt::run(registry.clone()));
+
+    // Run user commands on session transitions (config `hooks`)
+    let hooks = aura::config::load_config().hooks;
+    if !hooks.commands.is_empty() {
+        tokio::spawn(aura::hooks::run(hooks, registry.clone()));
+    }
+
+    // Serve session state to editor extensions (config `editor_api`)
+    let editor_api = aura::config::load_config().editor_api;
+    if editor_api.enabled {
+        tokio::spawn(aura::editor_api::serve(editor_api, registry.clone()));
+    }
+
+    // The binary `aura self-update` replaced has exited by now
+    aura::update::remove_replaced_binary();
+
+    // Offer new releases (Aura.app only; the CLI uses `aura self-update`)
+    if aura::config::load_config().check_for_updates && aura::update::running_from_app_bundle() {
+        tokio::spawn(aura::update::run());
+    }
+
+    // Start IPC server (hook events arrive on the socket or named pipe)
+    aura::supervisor::supervise("ipc server", move || {
+        aura::server::start(registry.clone(), events.clone())
+    })
+    .await;
+}
+
 fn main() {
     let cli = Cli::parse();
 
@@ -144,80 +498,163 @@ fn main() {
             println!("Session name updated to: {name}");
             return;
         }
+        Some(Command::SetTag { tags }) => {
+            println!(
+                "Session tagged: {}",
+                aura::tags::format_tags(&aura::tags::parse_tags(&tags.join(" ")))
+            );
+            return;
+        }
         Some(Command::Hook { ref agent }) => {
             aura::agents::claude_code::run(agent);
             return;
         }
+        Some(Command::Status { json, follow }) => {
+            exit_on_error(aura::cli::status(json, follow));
+            return;
+        }
+        Some(Command::Ack { session_id }) => {
+            exit_on_error(aura::cli::ack(&session_id));
+            return;
+        }
+        Some(Command::Pause { source }) => {
+            exit_on_error(aura::cli::set_paused(source, true));
+            return;
+        }
+        Some(Command::Resume { source }) => {
+            exit_on_error(aura::cli::set_paused(source, false));
+            return;
+        }
+        Some(Command::Sessions { command }) => {
+            let result = match command {
+                SessionsCommand::Snooze {
+                    session_id,
+                    duration,
+                } => aura::cli::snooze(&session_id, duration),
+                SessionsCommand::Unsnooze { session_id } => aura::cli::unsnooze(&session_id),
+                SessionsCommand::List {
+                    archived,
+                    tag,
+                    json,
+                } => aura::cli::list_sessions(archived, tag.as_deref(), json),
+                SessionsCommand::Show { session_id, json } => aura::cli::show(&session_id, json),
+                SessionsCommand::Tag { session_id, tags } => aura::cli::tag(&session_id, &tags),
+                SessionsCommand::Untag { session_id, tags } => aura::cli::untag(&session_id, &tags),
+                SessionsCommand::Archive { session_id } => aura::cli::archive(&session_id),
+                SessionsCommand::Restore { session_id } => aura::cli::restore(&session_id),
+                SessionsCommand::Rename { session_id, name } => {
+                    aura::cli::rename(&session_id, &name)
+                }
+                SessionsCommand::Bulk { action } => aura::cli::bulk(action),
+            };
+            exit_on_error(result);
+            return;
+        }
+        Some(Command::PromptSegment { cwd }) => {
+            aura::prompt::print_segment(cwd.as_deref());
+            return;
+        }
+        Some(Command::Shortcut { command }) => {
+            aura::shortcut::print(match command {
+                ShortcutCommand::List => aura::shortcut::list(),
+                ShortcutCommand::State => aura::shortcut::state(),
+                ShortcutCommand::Snooze {
+                    session_id,
+                    duration,
+                } => aura::shortcut::snooze(&session_id, duration),
+                ShortcutCommand::Unsnooze { session_id } => aura::shortcut::unsnooze(&session_id),
+                ShortcutCommand::Rename { session_id, name } => {
+                    aura::shortcut::rename(&session_id, &name)
+                }
+            });
+            return;
+        }
+        Some(Command::Debug { command }) => {
+            exit_on_error(match command {
+                DebugCommand::Record {
+                    output,
+                    session,
+                    stop,
+                } => aura::cli::record(output.as_deref(), session, stop),
+                DebugCommand::Replay { capture, events } => aura::cli::replay(&capture, events),
+            });
+            return;
+        }
+        #[cfg(feature = "simulator")]
+        Some(Command::Simulate { sessions, script }) => {
+            let script = match script {
+                Some(path) => aura::simulator::Script::load(&path),
+                None => Ok(aura::simulator::Script::default()),
+            };
+            exit_on_error(script.and_then(|script| aura::simulator::run(script, sessions)));
+            return;
+        }
+        Some(Command::Export {
+            session_id,
+            format,
+            output,
+        }) => {
+            exit_on_error(aura::cli::export(&session_id, format, output.as_deref()));
+            return;
+        }
+        Some(Command::SelfUpdate { check }) => {
+            exit_on_error(aura::update::self_update(check));
+            return;
+        }
         None => {}
     }
 
-    init_tracing(cli.verbose);
+    // Console output plus the daemon log file; the guard flushes it on exit
+    let _log_guard = aura::logging::init(cli.verbose, true);
+
+    // Demo sessions only: no agents, IPC or background tasks
+    if let Some(scenario) = cli.demo {
+        aura::demo::set_enabled(aura::demo::Options {
+            scenario,
+            theme: cli.demo_theme,
+            screenshot: cli.screenshot,
+        });
+        ui::run_hud(SharedRegistry::new(aura::demo::registry(scenario)));
+        return;
+    }
 
     // Install CLI tool if running from app bundle (macOS only).
     install_cli_tool();
 
-    // Shared registry between background tasks and UI
-    // Using std::sync::Mutex so it's accessible from both tokio and gpui threads
-    let registry = Arc::new(Mutex::new(SessionRegistry::new()));
-    let registry_dirty = Arc::new(AtomicBool::new(true));
+    // Move config/state files left in older locations
+    aura::paths::migrate_legacy_files();
+
+    // Shared registry between background tasks and UI: writers lock it
+    // briefly, readers use the snapshots it publishes
+    let mut initial_registry = SessionRegistry::new();
+    initial_registry.set_manual_order(aura::config::load_state().session_order);
+    let config = aura::config::load_config();
+    aura::privacy::set_enabled(config.privacy);
+    initial_registry.set_tool_categories(config.tool_categories());
+    initial_registry.set_tag_rules(config.tags);
+    initial_registry.set_filters(config.filters);
+    initial_registry.set_retention(config.retention.policy());
+    let registry = SharedRegistry::new(initial_registry);
+
+    // Without the HUD the daemon runs on the main thread
+    if cli.headless || cfg!(windows) {
+        let rt = tokio::runtime::Runtime::new().expect("Failed to create tokio runtime");
+        rt.block_on(async move {
+            tokio::spawn(aura::headless::run(registry.clone()));
+            run_services(registry).await;
+        });
+        return;
+    }
 
     // Spawn tokio runtime in background thread
-    let bg_registry = Arc::clone(&registry);
-    let bg_dirty = Arc::clone(&registry_dirty);
+    let bg_registry = registry.clone();
     std::thread::spawn(move || {
         let rt = tokio::runtime::Runtime::new().expect("Failed to create tokio runtime");
-        rt.block_on(async move {
-            // Spawn stale detection task — sleeps until the next session is due
-            // to go stale instead of polling at a fixed interval.
-            let stale_registry = Arc::clone(&bg_registry);
-            let stale_dirty = Arc::clone(&bg_dirty);
-            tokio::spawn(async move {
-                loop {
-                    let sleep_duration = {
-                        if let Ok(reg) = stale_registry.lock() {
-                            reg.next_stale_at(STALE_TIMEOUT)
-                                .map(|t| {
-                                    t.saturating_duration_since(Instant::now())
-                                        + Duration::from_millis(100)
-                                })
-                                .unwrap_or(Duration::from_secs(30))
-                        } else {
-                            Duration::from_secs(5)
-                        }
-                    };
-
-                    tokio::time::sleep(sleep_duration).await;
-
-                    if let Ok(mut reg) = stale_registry.lock() {
-                        reg.mark_stale(STALE_TIMEOUT);
-                        stale_dirty.store(true, Ordering::Relaxed);
-                    }
-                }
-            });
-
-            // Spawn Codex session rollout watcher (event stream producer)
-            let codex_stream = aura::agents::codex::spawn();
-            let codex_registry = Arc::clone(&bg_registry);
-            let codex_dirty = Arc::clone(&bg_dirty);
-            tokio::spawn(async move {
-                let mut rx = codex_stream.subscribe();
-                while let Some(event) = rx.recv().await {
-                    if let Ok(mut reg) = codex_registry.lock() {
-                        reg.process_event_from(event, aura::AgentType::Codex);
-                        codex_dirty.store(true, Ordering::Relaxed);
-                    }
-                }
-            });
-
-            // Start IPC socket server (accepts hook events via Unix socket)
-            let ipc_registry = Arc::clone(&bg_registry);
-            let ipc_dirty = Arc::clone(&bg_dirty);
-            aura::server::start(ipc_registry, ipc_dirty).await;
-        });
+        rt.block_on(run_services(bg_registry));
     });
 
     // Run gpui on main thread (blocks)
-    ui::run_hud(registry, registry_dirty);
+    ui::run_hud(registry);
 }
 
 #[cfg(test)]
@@ -230,6 +667,28 @@ mod tests {
         let cli = Cli::try_parse_from(["aura"]).unwrap();
         assert!(cli.command.is_none());
         assert_eq!(cli.verbose, 0);
+        assert!(!cli.headless);
+    }
+
+    #[test]
+    fn cli_headless() {
+        let cli = Cli::try_parse_from(["aura", "--headless", "-v"]).unwrap();
+        assert!(cli.headless);
+        assert!(cli.command.is_none());
+    }
+
+    #[test]
+    fn cli_demo_screenshot_needs_demo() {
+        let cli = Cli::try_parse_from(["aura", "--demo", "--screenshot", "hud.png"]).unwrap();
+        assert_eq!(cli.demo, Some(aura::demo::Scenario::All));
+        assert_eq!(cli.screenshot, Some(PathBuf::from("hud.png")));
+        let cli = Cli::try_parse_from(["aura", "--demo", "empty", "--demo-theme", "liquid-light"])
+            .unwrap();
+        assert_eq!(cli.demo, Some(aura::demo::Scenario::Empty));
+        assert_eq!(cli.demo_theme.as_deref(), Some("liquid-light"));
+        assert!(Cli::try_parse_from(["aura", "--demo", "--demo-theme", "sepia"]).is_err());
+        assert!(Cli::try_parse_from(["aura", "--screenshot", "hud.png"]).is_err());
+        assert!(Cli::try_parse_from(["aura", "--demo", "--headless"]).is_err());
     }
 
     #[test]
@@ -253,6 +712,89 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cli_shortcut_snooze_all() {
+        let cli = Cli::try_parse_from(["aura", "shortcut", "snooze", "all", "2h"]).unwrap();
+        match cli.command {
+            Some(Command::Shortcut {
+                command:
+                    ShortcutCommand::Snooze {
+                        session_id,
+                        duration,
+                    },
+            }) => {
+                assert_eq!(session_id, aura::shortcut::ALL_SESSIONS);
+                assert_eq!(duration, Duration::from_secs(2 * 60 * 60));
+            }
+            _ => panic!("expected Shortcut Snooze command"),
+        }
+    }
+
+    #[test]
+    fn cli_prompt_segment() {
+        let cli = Cli::try_parse_from(["aura", "prompt-segment", "--cwd", "/code/aura"]).unwrap();
+        match cli.command {
+            Some(Command::PromptSegment { cwd }) => {
+                assert_eq!(cwd.as_deref(), Some(std::path::Path::new("/code/aura")))
+            }
+            _ => panic!("expected PromptSegment command"),
+        }
+    }
+
+    #[test]
+    fn cli_export() {
+        let cli =
+            Cli::try_parse_from(["aura", "export", "abc", "--format", "json", "-o", "s.json"])
+                .unwrap();
+        match cli.command {
+            Some(Command::Export {
+                session_id,
+                format,
+                output,
+            }) => {
+                assert_eq!(session_id, "abc");
+                assert_eq!(format, aura::export::ExportFormat::Json);
+                assert_eq!(output.as_deref(), Some(std::path::Path::new("s.json")));
+            }
+            _ => panic!("expected Export command"),
+        }
+
+        let cli = Cli::try_parse_from(["aura", "export", "abc"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Export {
+                format: aura::export::ExportFormat::Md,
+                output: None,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn cli_debug_record() {
+        let cli =
+            Cli::try_parse_from(["aura", "debug", "record", "c.jsonl", "--session", "ab"]).unwrap();
+        match cli.command {
+            Some(Command::Debug {
+                command:
+                    DebugCommand::Record {
+                        output,
+                        session,
+                        stop,
+                    },
+            }) => {
+                assert_eq!(output.as_deref(), Some(std::path::Path::new("c.jsonl")));
+                assert_eq!(session.as_deref(), Some("ab"));
+                assert!(!stop);
+            }
+            _ => panic!("expected Debug Record command"),
+        }
+
+        assert!(Cli::try_parse_from(["aura", "debug", "record", "--stop"]).is_ok());
+        assert!(Cli::try_parse_from(["aura", "debug", "record"]).is_err());
+        assert!(Cli::try_parse_from(["aura", "debug", "record", "c.jsonl", "--stop"]).is_err());
+    }
+
     #[test]
     fn cli_hook_claude_code() {
         let cli = Cli::try_parse_from(["aura", "hook", "--agent", "claude-code"]).unwrap();
@@ -261,4 +803,219 @@ mod tests {
             _ => panic!("expected Hook command"),
         }
     }
+
+    #[test]
+    fn cli_sessions_snooze() {
+        let cli = Cli::try_parse_from(["aura", "sessions", "snooze", "abc123", "1h30m"]).unwrap();
+        match cli.command {
+            Some(Command::Sessions {
+                command:
+                    SessionsCommand::Snooze {
+                        session_id,
+                        duration,
+                    },
+            }) => {
+                assert_eq!(session_id, "abc123");
+                assert_eq!(duration, Duration::from_secs(90 * 60));
+            }
+            _ => panic!("expected Sessions Snooze command"),
+        }
+    }
+
+    #[test]
+    fn cli_sessions_snooze_default_duration() {
+        let cli = Cli::try_parse_from(["aura", "sessions", "snooze", "abc123"]).unwrap();
+        match cli.command {
+            Some(Command::Sessions {
+                command: SessionsCommand::Snooze { duration, .. },
+            }) => assert_eq!(duration, Duration::from_secs(30 * 60)),
+            _ => panic!("expected Sessions Snooze command"),
+        }
+    }
+
+    #[test]
+    fn cli_sessions_snooze_rejects_bad_duration() {
+        assert!(Cli::try_parse_from(["aura", "sessions", "snooze", "abc123", "soon"]).is_err());
+    }
+
+    #[test]
+    fn cli_sessions_unsnooze() {
+        let cli = Cli::try_parse_from(["aura", "sessions", "unsnooze", "abc123"]).unwrap();
+        match cli.command {
+            Some(Command::Sessions {
+                command: SessionsCommand::Unsnooze { session_id },
+            }) => assert_eq!(session_id, "abc123"),
+            _ => panic!("expected Sessions Unsnooze command"),
+        }
+    }
+
+    #[test]
+    fn cli_ack() {
+        let cli = Cli::try_parse_from(["aura", "ack", "abc123"]).unwrap();
+        match cli.command {
+            Some(Command::Ack { session_id }) => assert_eq!(session_id, "abc123"),
+            _ => panic!("expected Ack command"),
+        }
+    }
+
+    #[test]
+    fn cli_pause_and_resume() {
+        let cli = Cli::try_parse_from(["aura", "pause", "codex"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Pause {
+                source: aura::pause::Source::Codex
+            })
+        ));
+        let cli = Cli::try_parse_from(["aura", "resume", "claude-code"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Resume {
+                source: aura::pause::Source::ClaudeCode
+            })
+        ));
+    }
+
+    #[test]
+    fn cli_sessions_list_archived() {
+        let cli = Cli::try_parse_from(["aura", "sessions", "list"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Sessions {
+                command: SessionsCommand::List {
+                    archived: false,
+                    tag: None,
+                    json: false
+                }
+            })
+        ));
+
+        let cli = Cli::try_parse_from(["aura", "sessions", "list", "--archived"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Sessions {
+                command: SessionsCommand::List {
+                    archived: true,
+                    tag: None,
+                    json: false
+                }
+            })
+        ));
+    }
+
+    #[test]
+    fn cli_tags() {
+        let cli = Cli::try_parse_from(["aura", "set-tag", "billing", "q3"]).unwrap();
+        match cli.command {
+            Some(Command::SetTag { tags }) => assert_eq!(tags, vec!["billing", "q3"]),
+            _ => panic!("expected SetTag command"),
+        }
+        assert!(Cli::try_parse_from(["aura", "set-tag"]).is_err());
+
+        let cli = Cli::try_parse_from(["aura", "sessions", "untag", "abc", "billing"]).unwrap();
+        match cli.command {
+            Some(Command::Sessions {
+                command: SessionsCommand::Untag { session_id, tags },
+            }) => {
+                assert_eq!(session_id, "abc");
+                assert_eq!(tags, vec!["billing"]);
+            }
+            _ => panic!("expected Sessions Untag command"),
+        }
+
+        let cli = Cli::try_parse_from(["aura", "sessions", "list", "--tag", "billing"]).unwrap();
+        match cli.command {
+            Some(Command::Sessions {
+                command: SessionsCommand::List { tag, .. },
+            }) => assert_eq!(tag.as_deref(), Some("billing")),
+            _ => panic!("expected Sessions List command"),
+        }
+    }
+
+    #[test]
+    fn cli_json_flags() {
+        let cli = Cli::try_parse_from(["aura", "sessions", "list", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Sessions {
+                command: SessionsCommand::List {
+                    archived: false,
+                    tag: None,
+                    json: true
+                }
+            })
+        ));
+
+        let cli = Cli::try_parse_from(["aura", "status", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Status {
+                json: true,
+                follow: false
+            })
+        ));
+        let cli = Cli::try_parse_from(["aura", "status"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Status { json: false, .. })
+        ));
+        let cli = Cli::try_parse_from(["aura", "status", "-f"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Status { follow: true, .. })
+        ));
+    }
+
+    #[test]
+    fn cli_sessions_show() {
+        let cli = Cli::try_parse_from(["aura", "sessions", "show", "abc", "--json"]).unwrap();
+        match cli.command {
+            Some(Command::Sessions {
+                command: SessionsCommand::Show { session_id, json },
+            }) => {
+                assert_eq!(session_id, "abc");
+                assert!(json);
+            }
+            _ => panic!("expected Sessions Show command"),
+        }
+    }
+
+    #[test]
+    fn cli_sessions_restore() {
+        let cli = Cli::try_parse_from(["aura", "sessions", "restore", "abc"]).unwrap();
+        match cli.command {
+            Some(Command::Sessions {
+                command: SessionsCommand::Restore { session_id },
+            }) => assert_eq!(session_id, "abc"),
+            _ => panic!("expected Sessions Restore command"),
+        }
+    }
+
+    #[test]
+    fn cli_sessions_rename() {
+        let cli = Cli::try_parse_from(["aura", "sessions", "rename", "abc", "fix login"]).unwrap();
+        match cli.command {
+            Some(Command::Sessions {
+                command: SessionsCommand::Rename { session_id, name },
+            }) => {
+                assert_eq!(session_id, "abc");
+                assert_eq!(name, "fix login");
+            }
+            _ => panic!("expected Sessions Rename command"),
+        }
+    }
+
+    #[test]
+    fn cli_sessions_bulk() {
+        let cli = Cli::try_parse_from(["aura", "sessions", "bulk", "clear-stale"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Sessions {
+                command: SessionsCommand::Bulk {
+                    action: BulkAction::ClearStale
+                }
+            })
+        ));
+        assert!(Cli::try_parse_from(["aura", "sessions", "bulk", "nuke"]).is_err());
+    }
 }
@@ -0,0 +1,473 @@
+//! Away notifications - post to Slack or Discord when a session needs you
+//!
+//! A background task snapshots the registry every few seconds and queues a
+//! notification whenever a session enters one of the configured states
+//! (`notifications.states`). With `only_when_idle_minutes` set, queued
+//! notifications wait until the user has been away from keyboard and mouse
+//! that long, and are dropped if the session moves on first - so nothing is
+//! sent while you're at your desk watching the HUD.
+//!
+//! Messages are posted with `curl`, keeping the daemon free of an HTTP stack.
+//! Webhook URLs and tokens are passed on stdin, not the command line.
+
+use crate::config::{NotificationsConfig, SlackConfig};
+use crate::registry::SharedRegistry;
+use crate::ui::session_list::extract_session_name;
+use crate::{SessionInfo, SessionState};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// How often the registry is checked for state changes
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Slack Web API endpoint used with a bot token
+const SLACK_POST_MESSAGE_URL: &str = "https://slack.com/api/chat.postMessage";
+
+/// A session that entered a notify state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub session_id: String,
+    pub name: String,
+    pub project: String,
+    pub cwd: String,
+    pub state: SessionState,
+    /// Time since the daemon first saw the session
+    pub duration: Duration,
+}
+
+impl Notification {
+    /// Hide the session's name and paths (privacy mode)
+    pub fn redact(&mut self) {
+        self.name = crate::privacy::redacted_name(&self.session_id);
+        self.project = "(hidden)".to_string();
+        self.cwd = "(hidden)".to_string();
+    }
+}
+
+/// Tracks session states between snapshots and decides what to send
+#[derive(Debug)]
+pub struct Notifier {
+    config: NotificationsConfig,
+    /// Last seen state and first-seen time per session
+    seen: HashMap<String, (SessionState, Instant)>,
+    /// Notifications waiting for the idle condition
+    pending: Vec<Notification>,
+}
+
+impl Notifier {
+    pub fn new(config: NotificationsConfig) -> Self {
+        Self {
+            config,
+            seen: HashMap::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Diff a registry snapshot against the previous one and return the
+    /// notifications that are due, given how long the user has been idle
+    /// (`None` when unknown, which counts as away).
+    pub fn update(
+        &mut self,
+        sessions: &[SessionInfo],
+        now: Instant,
+        user_idle: Option<Duration>,
+    ) -> Vec<Notification> {
+        let mut next = HashMap::with_capacity(sessions.len());
+        for session in sessions {
+            let (previous, first_seen) = match self.seen.get(&session.session_id) {
+                Some((state, first_seen)) => (Some(*state), *first_seen),
+                None => (None, now),
+            };
+            next.insert(session.session_id.clone(), (session.state, first_seen));
+
+            let entered = previous.is_some_and(|previous| previous != session.state);
+            if entered && self.config.states.contains(&session.state) && !session.is_snoozed() {
+                let project = extract_session_name(&session.cwd);
+                self.pending.retain(|n| n.session_id != session.session_id);
+                self.pending.push(Notification {
+                    session_id: session.session_id.clone(),
+                    name: session.name.clone().unwrap_or_else(|| project.clone()),
+                    project,
+                    cwd: session.cwd.clone(),
+                    state: session.state,
+                    duration: now.saturating_duration_since(first_seen),
+                });
+            }
+        }
+        self.seen = next;
+
+        // Drop notifications for sessions that moved on or went away
+        let seen = &self.seen;
+        self.pending.retain(|n| {
+            seen.get(&n.session_id)
+                .is_some_and(|(state, _)| *state == n.state)
+        });
+
+        let away = match (self.config.only_when_idle_minutes, user_idle) {
+            (Some(minutes), Some(idle)) => idle >= Duration::from_secs(minutes * 60),
+            _ => true,
+        };
+        if away {
+            std::mem::take(&mut self.pending)
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Fill a message template's placeholders
+pub fn render_template(template: &str, notification: &Notification) -> String {
+    template
+        .replace("{name}", &notification.name)
+        .replace("{project}", &notification.project)
+        .replace("{state}", state_label(notification.state))
+        .replace("{duration}", &format_duration(notification.duration))
+        .replace("{cwd}", &notification.cwd)
+}
+
+fn state_label(state: SessionState) -> &'static str {
+    match state {
+        SessionState::Running => "running",
+        SessionState::Idle => "done",
+        SessionState::Attention => "waiting for permission",
+        SessionState::Waiting => "waiting for input",
+        SessionState::Compacting => "compacting",
+        SessionState::Stale => "stale",
+    }
+}
+
+/// "2h 5m", "12m", "<1m"
+fn format_duration(duration: Duration) -> String {
+    let minutes = duration.as_secs() / 60;
+    match (minutes / 60, minutes % 60) {
+        (0, 0) => "<1m".to_string(),
+        (0, m) => format!("{m}m"),
+        (h, m) => format!("{h}h {m}m"),
+    }
+}
+
+/// One HTTP POST to make: (url, bearer token, JSON body)
+#[derive(Debug, Clone, PartialEq)]
+struct Post {
+    url: String,
+    bearer: Option<String>,
+    body: serde_json::Value,
+}
+
+/// Build the requests for every configured destination
+fn posts(config: &NotificationsConfig, notification: &Notification) -> Vec<Post> {
+    let mut posts = Vec::new();
+    if let Some(slack) = &config.slack {
+        let text = render_template(
+            slack.template.as_deref().unwrap_or(&config.template),
+            notification,
+        );
+        posts.extend(slack_post(slack, text));
+    }
+    if let Some(discord) = &config.discord {
+        let content = render_template(
+            discord.template.as_deref().unwrap_or(&config.template),
+            notification,
+        );
+        posts.push(Post {
+            url: discord.webhook_url.clone(),
+            bearer: None,
+            body: serde_json::json!({ "content": content }),
+        });
+    }
+    posts
+}
+
+fn slack_post(slack: &SlackConfig, text: String) -> Option<Post> {
+    if let Some(url) = &slack.webhook_url {
+        return Some(Post {
+            url: url.clone(),
+            bearer: None,
+            body: serde_json::json!({ "text": text }),
+        });
+    }
+    match (&slack.bot_token, &slack.channel) {
+        (Some(token), Some(channel)) => Some(Post {
+            url: SLACK_POST_MESSAGE_URL.to_string(),
+            bearer: Some(token.clone()),
+            body: serde_json::json!({ "channel": channel, "text": text }),
+        }),
+        _ => {
+            tracing::warn!("Slack notifications need webhook_url, or bot_token and channel");
+            None
+        }
+    }
+}
+
+/// curl config for a post, read from stdin so the URL and token (both
+/// secrets) never show up in the process list
+fn curl_config(post: &Post) -> String {
+    let quote = |value: &str| format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""));
+    let mut config = format!("url = {}\n", quote(&post.url));
+    config.push_str("request = \"POST\"\n");
+    config.push_str("header = \"Content-Type: application/json\"\n");
+    if let Some(token) = &post.bearer {
+        config.push_str(&format!(
+            "header = {}\n",
+            quote(&format!("Authorization: Bearer {token}"))
+        ));
+    }
+    config.push_str(&format!(
+        "data-binary = {}\n",
+        quote(&post.body.to_string())
+    ));
+    config
+}
+
+/// POST with curl, logging failures
+async fn send(post: Post) {
+    use tokio::io::AsyncWriteExt;
+
+    let spawned = tokio::process::Command::new("curl")
+        .args(["-sS", "-f", "--max-time", "10", "--config", "-"])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .spawn();
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(e) => {
+            tracing::warn!("Failed to run curl for notification: {}", e);
+            return;
+        }
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(curl_config(&post).as_bytes()).await;
+    }
+    match child.wait_with_output().await {
+        Ok(output) if output.status.success() => {}
+        Ok(output) => tracing::warn!(
+            "Notification POST failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+        Err(e) => tracing::warn!("Notification POST failed: {}", e),
+    }
+}
+
+/// Watch the registry and post notifications until the runtime shuts down.
+pub async fn run(config: NotificationsConfig, registry: SharedRegistry) {
+    let mut notifier = Notifier::new(config.clone());
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+        let sessions = registry.snapshot();
+        let user_idle = if config.only_when_idle_minutes.is_some() {
+            user_idle_time().await
+        } else {
+            None
+        };
+        for mut notification in notifier.update(&sessions, Instant::now(), user_idle) {
+            if crate::privacy::enabled() {
+                notification.redact();
+            }
+            tracing::info!(
+                "Notifying: {} is {:?}",
+                notification.name,
+                notification.state
+            );
+            for post in posts(&config, &notification) {
+                tokio::spawn(send(post));
+            }
+        }
+    }
+}
+
+/// Time since the last keyboard or mouse input (macOS `HIDIdleTime`)
+async fn user_idle_time() -> Option<Duration> {
+    if !cfg!(target_os = "macos") {
+        return None;
+    }
+    let output = tokio::process::Command::new("ioreg")
+        .args(["-c", "IOHIDSystem", "-d", "4"])
+        .output()
+        .await
+        .ok()?;
+    parse_hid_idle_time(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Parse `"HIDIdleTime" = <nanoseconds>` out of `ioreg` output
+fn parse_hid_idle_time(output: &str) -> Option<Duration> {
+    let rest = output.split("\"HIDIdleTime\" = ").nth(1)?;
+    let nanos: u64 = rest
+        .split(|c: char| !c.is_ascii_digit())
+        .next()?
+        .parse()
+        .ok()?;
+    Some(Duration::from_nanos(nanos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DiscordConfig;
+
+    fn session(id: &str, state: SessionState) -> SessionInfo {
+        SessionInfo {
+            session_id: id.into(),
+            cwd: "/code/aura".into(),
+            state,
+            ..Default::default()
+        }
+    }
+
+    fn config(idle_minutes: Option<u64>) -> NotificationsConfig {
+        NotificationsConfig {
+            discord: Some(DiscordConfig {
+                webhook_url: "https://discord.test/hook".into(),
+                template: None,
+            }),
+            only_when_idle_minutes: idle_minutes,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn notifies_on_entering_a_notify_state() {
+        let mut notifier = Notifier::new(config(None));
+        let start = Instant::now();
+        // First sighting only records the state
+        assert!(
+            notifier
+                .update(&[session("s1", SessionState::Attention)], start, None)
+                .is_empty()
+        );
+        notifier.update(&[session("s1", SessionState::Running)], start, None);
+
+        let later = start + Duration::from_secs(600);
+        let sent = notifier.update(&[session("s1", SessionState::Attention)], later, None);
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].name, "aura");
+        assert_eq!(sent[0].duration, Duration::from_secs(600));
+
+        // Staying in the state doesn't repeat
+        assert!(
+            notifier
+                .update(&[session("s1", SessionState::Attention)], later, None)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn ignores_unlisted_states_and_snoozed_sessions() {
+        let mut notifier = Notifier::new(config(None));
+        let now = Instant::now();
+        notifier.update(&[session("s1", SessionState::Running)], now, None);
+        assert!(
+            notifier
+                .update(&[session("s1", SessionState::Compacting)], now, None)
+                .is_empty()
+        );
+
+        let mut snoozed = session("s1", SessionState::Attention);
+        snoozed.snoozed_until = Some(u64::MAX);
+        assert!(notifier.update(&[snoozed], now, None).is_empty());
+    }
+
+    #[test]
+    fn waits_until_user_is_idle() {
+        let mut notifier = Notifier::new(config(Some(5)));
+        let now = Instant::now();
+        let at_desk = Some(Duration::from_secs(10));
+        let away = Some(Duration::from_secs(6 * 60));
+
+        notifier.update(&[session("s1", SessionState::Running)], now, at_desk);
+        assert!(
+            notifier
+                .update(&[session("s1", SessionState::Waiting)], now, at_desk)
+                .is_empty()
+        );
+        let sent = notifier.update(&[session("s1", SessionState::Waiting)], now, away);
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].state, SessionState::Waiting);
+    }
+
+    #[test]
+    fn pending_dropped_when_session_moves_on() {
+        let mut notifier = Notifier::new(config(Some(5)));
+        let now = Instant::now();
+        let at_desk = Some(Duration::from_secs(10));
+        let away = Some(Duration::from_secs(6 * 60));
+
+        notifier.update(&[session("s1", SessionState::Running)], now, at_desk);
+        notifier.update(&[session("s1", SessionState::Attention)], now, at_desk);
+        notifier.update(&[session("s1", SessionState::Running)], now, at_desk);
+        assert!(
+            notifier
+                .update(&[session("s1", SessionState::Running)], now, away)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn template_placeholders() {
+        let notification = Notification {
+            session_id: "s1".into(),
+            name: "fix login".into(),
+            project: "aura".into(),
+            cwd: "/code/aura".into(),
+            state: SessionState::Attention,
+            duration: Duration::from_secs(3900),
+        };
+        assert_eq!(
+            render_template(
+                "{name} ({project}) is {state} after {duration}",
+                &notification
+            ),
+            "fix login (aura) is waiting for permission after 1h 5m"
+        );
+    }
+
+    #[test]
+    fn posts_per_destination() {
+        let mut config = config(None);
+        config.slack = Some(SlackConfig {
+            bot_token: Some("xoxb-1".into()),
+            channel: Some("#agents".into()),
+            ..Default::default()
+        });
+        let notification = Notification {
+            session_id: "s1".into(),
+            name: "aura".into(),
+            project: "aura".into(),
+            cwd: "/code/aura".into(),
+            state: SessionState::Idle,
+            duration: Duration::from_secs(30),
+        };
+        let posts = posts(&config, &notification);
+        assert_eq!(posts.len(), 2);
+        assert_eq!(posts[0].url, SLACK_POST_MESSAGE_URL);
+        assert_eq!(posts[0].bearer.as_deref(), Some("xoxb-1"));
+        assert_eq!(posts[0].body["channel"], "#agents");
+        assert_eq!(posts[1].url, "https://discord.test/hook");
+        assert_eq!(posts[1].body["content"], "aura (aura) is done after <1m");
+    }
+
+    #[test]
+    fn curl_config_escapes_values() {
+        let post = Post {
+            url: "https://hooks.slack.test/T1".into(),
+            bearer: Some("xoxb-1".into()),
+            body: serde_json::json!({ "text": "say \"hi\"" }),
+        };
+        let config = curl_config(&post);
+        assert!(config.contains("url = \"https://hooks.slack.test/T1\"\n"));
+        assert!(config.contains("header = \"Authorization: Bearer xoxb-1\"\n"));
+        assert!(config.contains(r#"data-binary = "{\"text\":\"say \\\"hi\\\"\"}""#));
+    }
+
+    #[test]
+    fn parse_hid_idle_time_reads_nanoseconds() {
+        let output = r#"    | |   "HIDIdleTime" = 4523000000
+    | |   "HIDIdleTimeDelta" = 1"#;
+        assert_eq!(
+            parse_hid_idle_time(output),
+            Some(Duration::from_millis(4523))
+        );
+        assert_eq!(parse_hid_idle_time("nothing"), None);
+    }
+}
@@ -0,0 +1,160 @@
+//! File locations
+//!
+//! | File                         | macOS                                   | Linux                                              |
+//! |------------------------------|-----------------------------------------|----------------------------------------------------|
+//! | `config.json`                | `~/Library/Application Support/aura/`   | `$XDG_CONFIG_HOME/aura/` (`~/.config/aura/`)       |
+//! | `state.json`                 | `~/Library/Application Support/aura/`   | `$XDG_STATE_HOME/aura/` (`~/.local/state/aura/`)   |
+//! | logs                         | `~/Library/Logs/aura/`                  | `$XDG_STATE_HOME/aura/logs/`                       |
+//! | socket, prompt cache         | `$TMPDIR`                               | `$XDG_RUNTIME_DIR/aura/` (else the temp dir)       |
+//!
+//! `AURA_RUNTIME_DIR` overrides the runtime directory (socket and prompt
+//! cache), e.g. to run a second daemon side by side. Files from older
+//! locations are moved by [`migrate_legacy_files`] when the daemon starts.
+
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Environment variable overriding [`runtime_dir`]
+pub const RUNTIME_DIR_ENV: &str = "AURA_RUNTIME_DIR";
+
+/// Directory of `config.json`
+pub fn config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("aura"))
+}
+
+/// Directory of `state.json`
+pub fn state_dir() -> Option<PathBuf> {
+    // `state_dir` is XDG-only; macOS keeps state next to the config
+    dirs::state_dir()
+        .or_else(dirs::data_dir)
+        .map(|dir| dir.join("aura"))
+}
+
+/// Directory for log files
+pub fn log_dir() -> Option<PathBuf> {
+    if cfg!(target_os = "macos") {
+        return dirs::home_dir().map(|home| home.join("Library/Logs/aura"));
+    }
+    state_dir().map(|dir| dir.join("logs"))
+}
+
+/// Directory of the daemon socket and other per-login files
+pub fn runtime_dir() -> PathBuf {
+    runtime_dir_from(
+        std::env::var_os(RUNTIME_DIR_ENV).map(PathBuf::from),
+        dirs::runtime_dir(),
+    )
+}
+
+fn runtime_dir_from(overridden: Option<PathBuf>, xdg_runtime: Option<PathBuf>) -> PathBuf {
+    overridden
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .or_else(|| xdg_runtime.map(|dir| dir.join("aura")))
+        .unwrap_or_else(std::env::temp_dir)
+}
+
+/// Create `dir` (owner-only on Unix) if it doesn't exist yet.
+pub fn ensure_private_dir(dir: &Path) -> std::io::Result<()> {
+    if dir.is_dir() {
+        return Ok(());
+    }
+    let mut builder = std::fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder.create(dir)
+}
+
+/// Move files left in older locations (Linux `state.json` under
+/// `~/.local/share/aura/`) to where Aura looks for them now.
+pub fn migrate_legacy_files() {
+    let (Some(old), Some(new)) = (dirs::data_dir().map(|dir| dir.join("aura")), state_dir()) else {
+        return;
+    };
+    if old != new {
+        migrate_file(&old.join("state.json"), &new.join("state.json"));
+    }
+}
+
+/// Move `old` to `new` unless `new` already exists. Returns whether it moved.
+fn migrate_file(old: &Path, new: &Path) -> bool {
+    if !old.is_file() || new.exists() {
+        return false;
+    }
+    let moved = new
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| {
+            std::fs::rename(old, new).or_else(|_| {
+                // Across filesystems: copy, then drop the original
+                std::fs::copy(old, new)?;
+                std::fs::remove_file(old)
+            })
+        });
+    match moved {
+        Ok(()) => {
+            info!("Moved {} to {}", old.display(), new.display());
+            true
+        }
+        Err(e) => {
+            warn!(
+                "Failed to move {} to {}: {}",
+                old.display(),
+                new.display(),
+                e
+            );
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runtime_dir_prefers_override_then_xdg() {
+        let xdg = Some(PathBuf::from("/run/user/501"));
+        assert_eq!(
+            runtime_dir_from(Some("/tmp/aura-dev".into()), xdg.clone()),
+            PathBuf::from("/tmp/aura-dev")
+        );
+        assert_eq!(
+            runtime_dir_from(Some(PathBuf::new()), xdg.clone()),
+            PathBuf::from("/run/user/501/aura")
+        );
+        assert_eq!(runtime_dir_from(None, None), std::env::temp_dir());
+    }
+
+    #[test]
+    fn migrate_file_moves_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let old = dir.path().join("share/aura/state.json");
+        let new = dir.path().join("state/aura/state.json");
+        std::fs::create_dir_all(old.parent().unwrap()).unwrap();
+        std::fs::write(&old, "{}").unwrap();
+
+        assert!(migrate_file(&old, &new));
+        assert!(!old.exists());
+        assert_eq!(std::fs::read_to_string(&new).unwrap(), "{}");
+
+        // Never overwrites a file already in the new location
+        std::fs::write(&old, "{\"stale\":true}").unwrap();
+        assert!(!migrate_file(&old, &new));
+        assert_eq!(std::fs::read_to_string(&new).unwrap(), "{}");
+    }
+
+    #[test]
+    fn ensure_private_dir_creates_owner_only_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let runtime = dir.path().join("run/aura");
+        ensure_private_dir(&runtime).unwrap();
+        assert!(runtime.is_dir());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&runtime).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o700);
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Paused sources - ignore one event source without stopping the daemon
+//!
+//! `aura pause codex` / `aura resume codex` (IPC `Pause` and `Resume`, or
+//! "Pause Monitoring" in the app menu) stop the [`crate::coalesce::Coalescer`]
+//! from applying that source's events, e.g. while cleaning up old Codex
+//! rollouts in bulk. Events arriving meanwhile are dropped, so sessions stay
+//! as they were until their next event after resuming. Pauses aren't saved;
+//! the Diagnostics window shows which sources are paused.
+
+use crate::AgentType;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Where agent events come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum Source {
+    /// Claude Code (and other agents') hooks, over the IPC socket
+    #[value(name = "claude-code")]
+    ClaudeCode,
+    /// The Codex rollout watcher
+    Codex,
+}
+
+static CLAUDE_CODE_PAUSED: AtomicBool = AtomicBool::new(false);
+static CODEX_PAUSED: AtomicBool = AtomicBool::new(false);
+
+impl Source {
+    pub const ALL: [Source; 2] = [Source::ClaudeCode, Source::Codex];
+
+    /// Source of events sent for `agent` (as reported by the adapter)
+    pub fn of(agent: &AgentType) -> Self {
+        match agent {
+            AgentType::Codex => Self::Codex,
+            _ => Self::ClaudeCode,
+        }
+    }
+
+    /// Name shown in diagnostics and CLI output
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ClaudeCode => "Claude Code hooks",
+            Self::Codex => "Codex rollouts",
+        }
+    }
+
+    fn flag(self) -> &'static AtomicBool {
+        match self {
+            Self::ClaudeCode => &CLAUDE_CODE_PAUSED,
+            Self::Codex => &CODEX_PAUSED,
+        }
+    }
+}
+
+/// Pause or resume `source` for the whole daemon
+pub fn set_paused(source: Source, paused: bool) {
+    source.flag().store(paused, Ordering::Relaxed);
+}
+
+pub fn is_paused(source: Source) -> bool {
+    source.flag().load(Ordering::Relaxed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn agents_map_to_their_source() {
+        assert_eq!(Source::of(&AgentType::Codex), Source::Codex);
+        assert_eq!(Source::of(&AgentType::ClaudeCode), Source::ClaudeCode);
+        assert_eq!(Source::of(&AgentType::GeminiCli), Source::ClaudeCode);
+        assert_eq!(
+            serde_json::to_string(&Source::ClaudeCode).unwrap(),
+            "\"claude_code\""
+        );
+    }
+}
@@ -0,0 +1,134 @@
+//! Privacy mode - hide what sessions work on while screen-sharing
+//!
+//! Turned on with `"privacy": true` in config.json or "Toggle Privacy Mode"
+//! in the app menu (which saves it). While it's on, every surface that shows
+//! or sends session details gets [`redact`]ed copies: the HUD rows, IPC
+//! replies (`aura sessions`, `aura shortcut`, prompt segments), headless
+//! output, the editor API and Slack/Discord notifications. States, counts,
+//! tags, models and tool names stay; names, working directories, tool labels,
+//! recent activity, plan steps, file paths and the transcript go, so the
+//! transcript viewer, "Open in …" actions and `aura export` are unavailable
+//! and prompt segments (matched by directory) stay empty.
+
+use crate::SessionInfo;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Turn privacy mode on or off for the whole daemon
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Name shown instead of a session's own: "session 3f2a1b9c"
+pub fn redacted_name(session_id: &str) -> String {
+    let short_id: String = session_id.chars().take(8).collect();
+    format!("session {short_id}")
+}
+
+/// `session` reduced to its states and counts
+pub fn redact(session: &SessionInfo) -> SessionInfo {
+    let mut redacted = session.clone();
+    redacted.name = Some(redacted_name(&session.session_id));
+    redacted.cwd.clear();
+    redacted.transcript_path = None;
+    redacted.recent_activity.clear();
+    // Codex reports the command or file awaiting approval; keep only the
+    // plan review marker
+    if !session.is_reviewing_plan() {
+        redacted.permission_tool = None;
+    }
+    let tools = redacted.running_tools.iter_mut().chain(
+        redacted
+            .subagents
+            .iter_mut()
+            .flat_map(|a| &mut a.running_tools),
+    );
+    for tool in tools {
+        tool.tool_label = None;
+    }
+    for change in &mut redacted.changed_files {
+        change.path.clear();
+    }
+    for (i, file) in redacted.file_conflicts.iter_mut().enumerate() {
+        *file = format!("file {}", i + 1);
+    }
+    if let Some(progress) = &mut redacted.progress {
+        progress.current = None;
+    }
+    redacted.subtitle = redacted.progress.as_ref().and_then(|p| p.subtitle());
+    redacted
+}
+
+/// `session` as it may be shown right now: redacted in privacy mode
+pub fn apply(session: SessionInfo) -> SessionInfo {
+    if enabled() { redact(&session) } else { session }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::{RunningTool, SubagentInfo};
+    use crate::{FileChange, PlanProgress, SessionState};
+
+    #[test]
+    fn redact_keeps_states_and_counts_only() {
+        let tool = RunningTool {
+            tool_id: "t1".into(),
+            tool_name: "Read".into(),
+            tool_label: Some("secret.rs".into()),
+            tool_full_label: None,
+            category: crate::tools::ToolCategory::Read,
+        };
+        let session = SessionInfo {
+            session_id: "3f2a1b9c-0000".into(),
+            cwd: "/code/secret-project".into(),
+            state: SessionState::Running,
+            name: Some("acquire competitor".into()),
+            running_tools: vec![tool.clone()],
+            subagents: vec![SubagentInfo {
+                agent_id: "a1".into(),
+                agent_type: Some("Explore".into()),
+                running_tools: vec![tool],
+            }],
+            recent_activity: vec!["secret.rs".into()],
+            permission_tool: Some("cat secret.env".into()),
+            transcript_path: Some("/home/me/.claude/projects/x.jsonl".into()),
+            changed_files: vec![FileChange {
+                path: "/code/secret-project/src/deal.rs".into(),
+                added: 12,
+                removed: 3,
+            }],
+            file_conflicts: vec!["/code/secret-project/src/deal.rs".into()],
+            progress: Some(PlanProgress {
+                completed: 1,
+                total: 3,
+                current: Some("draft the offer".into()),
+            }),
+            subtitle: Some("1/3 · draft the offer".into()),
+            tags: vec!["deals".into()],
+            ..Default::default()
+        };
+
+        let redacted = redact(&session);
+        assert_eq!(redacted.name.as_deref(), Some("session 3f2a1b9c"));
+        assert!(redacted.cwd.is_empty());
+        assert!(redacted.transcript_path.is_none());
+        assert!(redacted.recent_activity.is_empty());
+        assert!(redacted.permission_tool.is_none());
+        assert_eq!(redacted.running_tools[0].tool_name, "Read");
+        assert!(redacted.running_tools[0].tool_label.is_none());
+        assert!(redacted.subagents[0].running_tools[0].tool_label.is_none());
+        assert_eq!(redacted.change_summary(), session.change_summary());
+        assert_eq!(redacted.file_conflicts, vec!["file 1"]);
+        assert_eq!(redacted.subtitle.as_deref(), Some("1/3"));
+        assert_eq!(redacted.state, session.state);
+        assert_eq!(redacted.tags, session.tags);
+        let json = serde_json::to_string(&redacted).unwrap();
+        assert!(!json.contains("secret") && !json.contains("offer"));
+    }
+}
@@ -0,0 +1,200 @@
+//! Shell prompt segment - `aura prompt-segment` for starship and friends
+//!
+//! Prompts render on every command, so the segment never talks to the
+//! daemon. Instead the daemon keeps a small cache file next to its socket
+//! (rewritten when sessions change, and periodically as a heartbeat) and the
+//! segment only reads it. A cache that hasn't been touched recently means the
+//! daemon is gone, and the segment prints nothing.
+
+use crate::registry::SharedRegistry;
+use crate::ui::session_list::extract_session_name;
+use crate::{SessionInfo, SessionState};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// How often the daemon checks for changes to publish
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// The cache is rewritten at least this often, even without changes
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+/// Caches older than this are ignored (daemon not running)
+const MAX_CACHE_AGE: Duration = Duration::from_secs(90);
+
+/// Cache file read by `aura prompt-segment`
+pub fn cache_path() -> PathBuf {
+    crate::paths::runtime_dir().join("aura-prompt.json")
+}
+
+/// One session as published for prompts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PromptEntry {
+    pub cwd: String,
+    pub name: String,
+    pub state: SessionState,
+    #[serde(default)]
+    pub snoozed: bool,
+}
+
+impl From<&SessionInfo> for PromptEntry {
+    fn from(session: &SessionInfo) -> Self {
+        Self {
+            cwd: session.cwd.clone(),
+            name: session
+                .name
+                .clone()
+                .unwrap_or_else(|| extract_session_name(&session.cwd)),
+            state: session.state,
+            snoozed: session.is_snoozed(),
+        }
+    }
+}
+
+/// Glyph shown for a session state
+fn state_glyph(state: SessionState) -> &'static str {
+    match state {
+        SessionState::Attention => "!",
+        SessionState::Waiting => "?",
+        SessionState::Idle => "✓",
+        SessionState::Running => "●",
+        SessionState::Compacting => "◐",
+        SessionState::Stale => "·",
+    }
+}
+
+/// Segment text for a working directory, e.g. `? fix login`.
+///
+/// A session matches when `cwd` is its directory or anywhere below it; the
+/// deepest match wins, then the state that most needs the user.
+pub fn segment(entries: &[PromptEntry], cwd: &Path) -> Option<String> {
+    let entry = entries
+        .iter()
+        .filter(|e| cwd.starts_with(Path::new(&e.cwd)))
+        .max_by_key(|e| {
+            (
+                Path::new(&e.cwd).components().count(),
+                !e.snoozed,
+                e.state.urgency(),
+            )
+        })?;
+    Some(format!("{} {}", state_glyph(entry.state), entry.name))
+}
+
+/// `aura prompt-segment [--cwd <path>]` - prints nothing when there's no
+/// matching session or the daemon isn't running.
+pub fn print_segment(cwd: Option<&Path>) {
+    let cwd = match cwd {
+        Some(cwd) => cwd.to_path_buf(),
+        None => match std::env::current_dir() {
+            Ok(cwd) => cwd,
+            Err(_) => return,
+        },
+    };
+    if let Some(segment) = read_cache(&cache_path()).and_then(|e| segment(&e, &cwd)) {
+        println!("{segment}");
+    }
+}
+
+/// Read the cache if the daemon has refreshed it recently
+fn read_cache(path: &Path) -> Option<Vec<PromptEntry>> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    let age = SystemTime::now()
+        .duration_since(modified)
+        .unwrap_or_default();
+    if age > MAX_CACHE_AGE {
+        return None;
+    }
+    serde_json::from_slice(&std::fs::read(path).ok()?).ok()
+}
+
+/// Atomically replace the cache file
+fn write_cache(path: &Path, entries: &[PromptEntry]) -> std::io::Result<()> {
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let mut file = tempfile::NamedTempFile::new_in(dir)?;
+    serde_json::to_writer(&mut file, entries).map_err(std::io::Error::other)?;
+    file.flush()?;
+    file.persist(path).map_err(|e| e.error)?;
+    Ok(())
+}
+
+/// Keep the prompt cache current until the runtime shuts down.
+pub async fn run(registry: SharedRegistry) {
+    let path = cache_path();
+    if let Some(dir) = path.parent()
+        && let Err(e) = crate::paths::ensure_private_dir(dir)
+    {
+        tracing::debug!("Failed to create {}: {}", dir.display(), e);
+    }
+    let mut last: Option<Vec<PromptEntry>> = None;
+    let mut last_write = std::time::Instant::now();
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+        let entries: Vec<PromptEntry> = registry.snapshot().iter().map(PromptEntry::from).collect();
+        if last.as_ref() == Some(&entries) && last_write.elapsed() < HEARTBEAT_INTERVAL {
+            continue;
+        }
+        match write_cache(&path, &entries) {
+            Ok(()) => {
+                last = Some(entries);
+                last_write = std::time::Instant::now();
+            }
+            Err(e) => tracing::debug!("Failed to write prompt cache {}: {}", path.display(), e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(cwd: &str, name: &str, state: SessionState) -> PromptEntry {
+        PromptEntry {
+            cwd: cwd.into(),
+            name: name.into(),
+            state,
+            snoozed: false,
+        }
+    }
+
+    #[test]
+    fn segment_matches_cwd_and_subdirectories() {
+        let entries = vec![entry("/code/aura", "aura", SessionState::Waiting)];
+        assert_eq!(
+            segment(&entries, Path::new("/code/aura")).as_deref(),
+            Some("? aura")
+        );
+        assert_eq!(
+            segment(&entries, Path::new("/code/aura/src")).as_deref(),
+            Some("? aura")
+        );
+        assert_eq!(segment(&entries, Path::new("/code/other")), None);
+        assert_eq!(segment(&entries, Path::new("/code/aurora")), None);
+    }
+
+    #[test]
+    fn segment_prefers_deepest_then_most_urgent() {
+        let entries = vec![
+            entry("/code", "mono", SessionState::Attention),
+            entry("/code/aura", "build", SessionState::Running),
+            entry("/code/aura", "fix login", SessionState::Attention),
+        ];
+        assert_eq!(
+            segment(&entries, Path::new("/code/aura/src")).as_deref(),
+            Some("! fix login")
+        );
+    }
+
+    #[test]
+    fn cache_round_trips_and_expires() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("aura-prompt.json");
+        let entries = vec![entry("/code/aura", "aura", SessionState::Idle)];
+        write_cache(&path, &entries).unwrap();
+        assert_eq!(read_cache(&path), Some(entries));
+
+        let old = SystemTime::now() - MAX_CACHE_AGE - Duration::from_secs(1);
+        filetime::set_file_mtime(&path, filetime::FileTime::from_system_time(old)).unwrap();
+        assert_eq!(read_cache(&path), None);
+    }
+}
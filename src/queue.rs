@@ -0,0 +1,122 @@
+//! Prompt queue - count prompts waiting behind a session's current turn
+//!
+//! Claude Code lets the user type more prompts while a turn runs and records
+//! each as a `queue-operation` line in the transcript. This task follows the
+//! transcripts of busy sessions with a [`MetaTracker`] (reading only appended
+//! bytes) and reports the queue length as `PromptsQueued`, shown as a
+//! "+2 queued" badge on the row. Finishing the turn (Idle) clears the count.
+
+use crate::coalesce::Coalescer;
+use crate::registry::SharedRegistry;
+use crate::transcript::MetaTracker;
+use crate::{AgentEvent, AgentType, SessionInfo, SessionState};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How often busy sessions' transcripts are checked
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Whether a session can have prompts queued (a turn is in progress)
+fn is_busy(session: &SessionInfo) -> bool {
+    matches!(
+        session.state,
+        SessionState::Running | SessionState::Attention | SessionState::Compacting
+    )
+}
+
+/// `PromptsQueued` for every busy session whose queue length changed; stops
+/// tracking transcripts of sessions that are no longer busy
+pub fn poll(tracker: &mut MetaTracker, sessions: &[SessionInfo]) -> Vec<AgentEvent> {
+    let mut events = Vec::new();
+    let mut busy = HashSet::new();
+    for session in sessions.iter().filter(|s| is_busy(s)) {
+        let Some(path) = session.transcript_path.as_deref().map(Path::new) else {
+            continue;
+        };
+        busy.insert(path.to_path_buf());
+        let Ok(meta) = tracker.update(path) else {
+            continue;
+        };
+        if meta.queued != session.queued_prompts {
+            events.push(AgentEvent::PromptsQueued {
+                session_id: session.session_id.clone(),
+                count: meta.queued,
+            });
+        }
+    }
+    let idle: Vec<PathBuf> = tracker
+        .paths()
+        .filter(|path| !busy.contains(*path))
+        .cloned()
+        .collect();
+    for path in idle {
+        tracker.forget(&path);
+    }
+    events
+}
+
+/// Follow busy sessions' transcripts until the runtime shuts down.
+pub async fn run(registry: SharedRegistry, events: Coalescer) {
+    let mut tracker = MetaTracker::new();
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+        let sessions = registry.snapshot();
+        // The first read of a transcript covers the whole file
+        let polled = tokio::task::spawn_blocking(move || {
+            let updates = poll(&mut tracker, &sessions);
+            (tracker, updates)
+        })
+        .await;
+        let Ok((returned, updates)) = polled else {
+            break;
+        };
+        tracker = returned;
+        for event in updates {
+            events.send(event, AgentType::ClaudeCode);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn poll_reports_changed_queues_of_busy_sessions() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s1.jsonl");
+        let mut file = std::fs::File::create(&path).unwrap();
+        for _ in 0..2 {
+            writeln!(
+                file,
+                r#"{{"type":"queue-operation","operation":"enqueue"}}"#
+            )
+            .unwrap();
+        }
+        let mut session = SessionInfo {
+            session_id: "s1".into(),
+            state: SessionState::Running,
+            transcript_path: Some(path.display().to_string()),
+            ..Default::default()
+        };
+
+        let mut tracker = MetaTracker::new();
+        let events = poll(&mut tracker, std::slice::from_ref(&session));
+        assert!(matches!(
+            events.as_slice(),
+            [AgentEvent::PromptsQueued { count: 2, .. }]
+        ));
+
+        // Already known: nothing to send
+        session.queued_prompts = 2;
+        assert!(poll(&mut tracker, std::slice::from_ref(&session)).is_empty());
+
+        // Finished: the transcript is no longer followed
+        session.state = SessionState::Idle;
+        assert!(poll(&mut tracker, &[session]).is_empty());
+        assert!(tracker.is_empty());
+    }
+}
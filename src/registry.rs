@@ -1,33 +1,167 @@
 //! Session registry - tracks active sessions and their state
-
-use crate::{AgentEvent, AgentType, RunningTool, SessionInfo, SessionState};
-use std::collections::{HashMap, VecDeque};
+//!
+//! The daemon shares one registry through [`SharedRegistry`]: writers (event
+//! batches, IPC requests, UI actions) lock it briefly to mutate, and every
+//! mutation publishes an immutable [`Published`] list of the visible sessions
+//! on a watch channel. Readers - the HUD, notifier, hooks, prompt cache,
+//! editor API - use the latest one and never wait on the lock.
+//!
+//! A publish only rebuilds the sessions touched since the last one (plus
+//! those whose expiring tools or snooze, or shared-cwd flags, may have
+//! moved); the rest are shared with the previous list. Each publish that
+//! changes the list bumps the registry's generation. The HUD keeps its own
+//! copy of the list and asks the published list for
+//! [`changes_since`](Published::changes_since) its generation, cloning only
+//! the sessions that were added, updated or removed.
+
+use crate::clock::SharedClock;
+use crate::config::TagRule;
+use crate::filter::{EventFilter, FilterRule};
+use crate::ipc::EventStamp;
+use crate::logging;
+use crate::pause::Source;
+use crate::tools::{ToolCategories, ToolCategory};
+use crate::{
+    AgentEvent, AgentType, FileChange, PlanProgress, RunningTool, SessionInfo, SessionState,
+    StateChange, SubagentInfo, TerminalInfo,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Duration, Instant};
-use tracing::{debug, info, trace};
-
-/// Convert an Instant to a Unix timestamp (seconds since epoch)
-fn instant_to_unix_timestamp(instant: Instant) -> u64 {
-    let elapsed = Instant::now().saturating_duration_since(instant);
-    let system_time = std::time::SystemTime::now() - elapsed;
-    system_time
-        .duration_since(std::time::SystemTime::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs()
-}
+use tokio::sync::{mpsc, watch};
+use tracing::{debug, info, trace, warn};
 
 /// Minimum duration to keep completed tools visible
 const MIN_TOOL_DISPLAY: Duration = Duration::from_secs(1);
 /// Maximum number of recent activity items to keep
 const RECENT_ACTIVITY_MAX: usize = 6;
+/// States remembered per session for `aura sessions show`
+const STATE_HISTORY_MAX: usize = 20;
+/// Maximum number of modified files remembered per session
+const MAX_CHANGED_FILES: usize = 1000;
+
+/// How long events for an unknown session wait for its `SessionStarted`
+const PENDING_TTL: Duration = Duration::from_secs(5);
+/// Events held per unknown session
+const MAX_PENDING_EVENTS: usize = 32;
+/// Unknown sessions holding events at once
+const MAX_PENDING_SESSIONS: usize = 64;
+
+/// How long a stamped hook event waits (from its stamp, or its arrival when
+/// that's earlier) for hooks fired before it that connect later
+const REORDER_WINDOW: Duration = Duration::from_millis(100);
+
+/// Width of the time buckets repeated tool events are deduplicated in
+const DEDUP_BUCKET_SECS: u64 = 5;
+
+/// Removals of forgotten sessions remembered for [`Published::changes_since`];
+/// older ones are dropped (callers rebuild from `order`, so they lose nothing)
+const MAX_UNLISTED: usize = 1024;
 
 /// Prefix for recent tool IDs in the visible tools list
 const RECENT_TOOL_PREFIX: &str = "recent_";
 
+/// Operation applied to many sessions at once (session list header menu, IPC, CLI)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkAction {
+    /// Archive every Idle session
+    ClearIdle,
+    /// Archive every Stale session
+    ClearStale,
+    /// Acknowledge every Attention/Waiting/Idle session
+    MarkAllSeen,
+}
+
+impl BulkAction {
+    pub const ALL: [BulkAction; 3] = [
+        BulkAction::ClearIdle,
+        BulkAction::ClearStale,
+        BulkAction::MarkAllSeen,
+    ];
+}
+
+/// How long finished sessions are kept (config `retention`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Retention {
+    /// Archive sessions that have been Stale this long; never when `None`
+    pub remove_stale_after: Option<Duration>,
+    /// Sessions kept in the registry; past it the least recently active
+    /// archived, then Stale, sessions are dropped. Live ones never are.
+    pub max_sessions: usize,
+}
+
+impl Default for Retention {
+    /// Keep everything
+    fn default() -> Self {
+        Self {
+            remove_stale_after: None,
+            max_sessions: usize::MAX,
+        }
+    }
+}
+
+/// Sessions a garbage collection pass archived and evicted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GcStats {
+    pub archived: u64,
+    pub evicted: u64,
+}
+
+/// What the registry holds, for checking that a long-running daemon stays
+/// bounded (see `tests/soak_test.rs`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Footprint {
+    /// Sessions, archived ones included
+    pub sessions: usize,
+    /// Running and recently completed tools, over all sessions
+    pub tools: usize,
+    /// Events held for unknown sessions or the reorder window
+    pub held_events: usize,
+    /// Tool deliveries remembered for dropping repeats
+    pub deliveries: usize,
+    /// Sessions remembered as having left the list
+    pub unlisted: usize,
+    /// Sessions the event filter tracks
+    pub filtered: usize,
+}
+
+/// Events for a session that doesn't exist yet, in arrival order
+#[derive(Debug)]
+struct Pending {
+    since: Instant,
+    events: Vec<(AgentEvent, AgentType)>,
+}
+
+/// A stamped event waiting in [`SessionRegistry::process_stamped`]'s window
+#[derive(Debug)]
+struct Reordered {
+    stamp: EventStamp,
+    /// Unix milliseconds from which it's applied
+    release_ms: u64,
+    event: AgentEvent,
+    agent: AgentType,
+}
+
+/// One delivery of a tool event, for dropping hooks Claude Code re-fires on
+/// retries
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct DeliveryKey {
+    session_id: String,
+    tool_id: String,
+    kind: &'static str,
+    /// Unix time over [`DEDUP_BUCKET_SECS`]
+    bucket: u64,
+}
+
 /// A tool that was recently completed but should remain visible briefly
 #[derive(Debug, Clone)]
 pub(crate) struct RecentTool {
     pub(crate) tool_name: String,
     pub(crate) tool_label: Option<String>,
+    pub(crate) tool_full_label: Option<String>,
+    pub(crate) category: ToolCategory,
     pub(crate) expires_at: Instant,
 }
 
@@ -42,6 +176,10 @@ pub struct Session {
     pub(crate) recent_tools: Vec<RecentTool>,
     pub(crate) recent_activity: VecDeque<String>,
     pub(crate) last_activity: Instant,
+    /// When the session registered
+    pub(crate) started_at: Instant,
+    /// When the session entered its current state
+    pub(crate) state_changed_at: Instant,
     /// Custom session name (if set by user via `aura set-name`)
     pub(crate) name: Option<String>,
     /// When the session became idle
@@ -50,10 +188,39 @@ pub struct Session {
     pub(crate) stale_at: Option<Instant>,
     /// Tool requesting permission (from NeedsAttention message)
     pub(crate) permission_tool: Option<String>,
+    /// Snoozed until this instant (excluded from the aggregate indicator)
+    pub(crate) snoozed_until: Option<Instant>,
+    /// Archived sessions are hidden from `get_all()` but can be restored
+    pub(crate) archived: bool,
+    /// Current Attention/Waiting state was acknowledged by the user
+    pub(crate) seen: bool,
+    /// Transcript file reported by the agent
+    pub(crate) transcript_path: Option<String>,
+    /// User tags (normalized, see [`crate::tags`])
+    pub(crate) tags: BTreeSet<String>,
+    /// Files the agent modified: path -> estimated (added, removed) lines,
+    /// capped at `MAX_CHANGED_FILES`
+    pub(crate) changed_files: BTreeMap<String, (u32, u32)>,
+    /// The agent's current plan, if it has one
+    pub(crate) plan: Option<PlanProgress>,
+    /// In plan mode (researching and drafting a plan, not editing)
+    pub(crate) plan_mode: bool,
+    /// Model the agent last reported
+    pub(crate) model: Option<String>,
+    /// Terminal the agent runs in, as its `SessionStarted` reported
+    pub(crate) terminal: Option<TerminalInfo>,
+    /// Latest CPU sample, see [`crate::sysinfo`]
+    pub(crate) cpu_percent: Option<u16>,
+    /// Prompts queued behind the current turn
+    pub(crate) queued_prompts: usize,
+    /// Parallel subagents, in start order, holding the tools they run
+    pub(crate) subagents: Vec<SubagentInfo>,
+    /// States entered and when, capped at `STATE_HISTORY_MAX`
+    pub(crate) state_history: VecDeque<(SessionState, Instant)>,
 }
 
 impl Session {
-    fn new(session_id: String, cwd: String, agent: AgentType) -> Self {
+    fn new(session_id: String, cwd: String, agent: AgentType, now: Instant) -> Self {
         Self {
             session_id,
             cwd,
@@ -62,14 +229,73 @@ impl Session {
             running_tools: Vec::new(),
             recent_tools: Vec::new(),
             recent_activity: VecDeque::new(),
-            last_activity: Instant::now(),
+            last_activity: now,
+            started_at: now,
+            state_changed_at: now,
             name: None,
             stopped_at: None,
             stale_at: None,
             permission_tool: None,
+            snoozed_until: None,
+            archived: false,
+            seen: false,
+            transcript_path: None,
+            tags: BTreeSet::new(),
+            changed_files: BTreeMap::new(),
+            plan: None,
+            plan_mode: false,
+            model: None,
+            terminal: None,
+            cpu_percent: None,
+            queued_prompts: 0,
+            subagents: Vec::new(),
+            state_history: VecDeque::from([(SessionState::Running, now)]),
+        }
+    }
+
+    /// New session tagged by the config rules matching its working directory
+    fn started(
+        session_id: String,
+        cwd: String,
+        agent: AgentType,
+        rules: &[TagRule],
+        now: Instant,
+    ) -> Self {
+        let mut session = Self::new(session_id, cwd, agent, now);
+        session.tags = crate::tags::inferred_tags(rules, &session.cwd)
+            .into_iter()
+            .collect();
+        session
+    }
+
+    /// Note that the session entered its current state at `now`
+    fn entered_state(&mut self, now: Instant) {
+        self.state_changed_at = now;
+        self.state_history.push_back((self.state, now));
+        while self.state_history.len() > STATE_HISTORY_MAX {
+            self.state_history.pop_front();
+        }
+    }
+
+    /// Add file changes to the per-file totals
+    fn record_changes(&mut self, changes: Vec<FileChange>) {
+        for change in changes {
+            if self.changed_files.len() >= MAX_CHANGED_FILES
+                && !self.changed_files.contains_key(&change.path)
+            {
+                continue;
+            }
+            let (added, removed) = self.changed_files.entry(change.path).or_default();
+            *added = added.saturating_add(change.added);
+            *removed = removed.saturating_add(change.removed);
         }
     }
 
+    /// Counts toward cwd/file conflicts (not archived, not Stale)
+    fn is_live(&self) -> bool {
+        !self.archived && self.state != SessionState::Stale
+    }
+
     /// Clear timestamp fields when transitioning to Running state
     fn clear_timestamps(&mut self) {
         self.stopped_at = None;
@@ -77,23 +303,73 @@ impl Session {
         self.permission_tool = None;
     }
 
+    /// Move to `next` if [`SessionState::can_transition_to`] allows it,
+    /// logging and ignoring the event otherwise
+    fn enter(&mut self, next: SessionState) -> bool {
+        if !self.state.can_transition_to(next) {
+            warn!(
+                target: logging::REGISTRY,
+                session_id = %self.session_id,
+                from = ?self.state,
+                to = ?next,
+                "illegal state transition"
+            );
+            return false;
+        }
+        self.state = next;
+        true
+    }
+
     /// Transition to Running state, clearing all timestamps and permission_tool
     fn transition_to_running(&mut self) {
-        self.state = SessionState::Running;
-        self.clear_timestamps();
+        if self.enter(SessionState::Running) {
+            self.clear_timestamps();
+        }
     }
 
-    /// Transition to Running and add a tool to the running tools list
+    /// Transition to Running and add a tool to the running tools list,
+    /// replacing a running tool with the same ID (Codex restarts a call
+    /// once it's approved)
     fn add_tool(&mut self, tool: RunningTool) {
         self.transition_to_running();
+        self.running_tools.retain(|t| t.tool_id != tool.tool_id);
         self.running_tools.push(tool);
     }
 
+    /// The subagent with `agent_id`, added if it's new
+    fn subagent(&mut self, agent_id: String) -> &mut SubagentInfo {
+        let index = match self.subagents.iter().position(|a| a.agent_id == agent_id) {
+            Some(index) => index,
+            None => {
+                self.subagents.push(SubagentInfo {
+                    agent_id,
+                    agent_type: None,
+                    running_tools: Vec::new(),
+                });
+                self.subagents.len() - 1
+            }
+        };
+        &mut self.subagents[index]
+    }
+
+    /// Move a running tool to the subagent running it, so parallel agents
+    /// don't share (and cycle through) the session's tool line
+    fn assign_tool(&mut self, agent_id: String, tool_id: &str) {
+        let Some(pos) = self.running_tools.iter().position(|t| t.tool_id == tool_id) else {
+            return;
+        };
+        let tool = self.running_tools.remove(pos);
+        self.subagent(agent_id).running_tools.push(tool);
+    }
+
     /// Complete a tool: ensure Running state, move tool to recent, record activity
-    fn complete_tool(&mut self, tool_id: &str) {
+    fn complete_tool(&mut self, tool_id: &str, now: Instant) {
         if self.state != SessionState::Running {
             self.transition_to_running();
         }
+        for subagent in &mut self.subagents {
+            subagent.running_tools.retain(|t| t.tool_id != tool_id);
+        }
         if let Some(pos) = self.running_tools.iter().position(|t| t.tool_id == tool_id) {
             let tool = self.running_tools.remove(pos);
             let label = tool
@@ -103,22 +379,31 @@ impl Session {
             self.recent_tools.push(RecentTool {
                 tool_name: tool.tool_name,
                 tool_label: tool.tool_label,
-                expires_at: Instant::now() + MIN_TOOL_DISPLAY,
+                tool_full_label: tool.tool_full_label,
+                category: tool.category,
+                expires_at: now + MIN_TOOL_DISPLAY,
             });
             self.push_recent_activity(label);
         }
     }
 
     /// Transition to Idle state, clearing running tools and setting stopped_at
-    fn set_idle(&mut self) {
-        self.state = SessionState::Idle;
+    fn set_idle(&mut self, now: Instant) {
+        if !self.enter(SessionState::Idle) {
+            return;
+        }
         self.running_tools.clear();
-        self.stopped_at = Some(Instant::now());
+        self.stopped_at = Some(now);
         self.permission_tool = None;
+        // Claude Code runs queued prompts before it stops
+        self.queued_prompts = 0;
+        self.subagents.clear();
+        // Finished work is unreviewed until marked seen (see `crate::reminder`)
+        self.seen = false;
     }
 
-    fn touch(&mut self) {
-        self.last_activity = Instant::now();
+    fn touch(&mut self, now: Instant) {
+        self.last_activity = now;
     }
 
     fn push_recent_activity(&mut self, label: String) {
@@ -146,8 +431,7 @@ impl Session {
     }
 
     /// Get all visible tools (running + non-expired recent)
-    fn visible_tools(&self) -> Vec<RunningTool> {
-        let now = Instant::now();
+    fn visible_tools(&self, now: Instant) -> Vec<RunningTool> {
         let mut tools = self.running_tools.clone();
 
         tools.extend(
@@ -158,23 +442,65 @@ impl Session {
                     tool_id: format!("{}{}", RECENT_TOOL_PREFIX, t.tool_name),
                     tool_name: t.tool_name.clone(),
                     tool_label: t.tool_label.clone(),
+                    tool_full_label: t.tool_full_label.clone(),
+                    category: t.category,
                 }),
         );
 
         tools
     }
 
-    pub fn to_info(&self) -> SessionInfo {
+    pub fn to_info(&self, clock: &SharedClock) -> SessionInfo {
+        let now = clock.now();
         SessionInfo {
             session_id: self.session_id.clone(),
             cwd: self.cwd.clone(),
+            agent: Some(self.agent.clone()),
             state: self.state,
-            running_tools: self.visible_tools(),
+            running_tools: self.visible_tools(now),
             name: self.name.clone(),
-            stopped_at: self.stopped_at.map(instant_to_unix_timestamp),
-            stale_at: self.stale_at.map(instant_to_unix_timestamp),
+            stopped_at: self.stopped_at.map(|at| clock.unix_timestamp(at)),
+            stale_at: self.stale_at.map(|at| clock.unix_timestamp(at)),
+            last_activity_at: Some(clock.unix_timestamp(self.last_activity)),
+            started_at: Some(clock.unix_timestamp(self.started_at)),
+            state_changed_at: Some(clock.unix_timestamp(self.state_changed_at)),
             permission_tool: self.permission_tool.clone(),
             recent_activity: self.recent_activity.iter().cloned().collect(),
+            snoozed_until: self
+                .snoozed_until
+                .filter(|until| *until > now)
+                .map(|until| clock.unix_timestamp(until)),
+            archived: self.archived,
+            seen: self.seen,
+            transcript_path: self.transcript_path.clone(),
+            tags: self.tags.iter().cloned().collect(),
+            cwd_conflict: false,
+            file_conflicts: Vec::new(),
+            changed_files: self
+                .changed_files
+                .iter()
+                .map(|(path, &(added, removed))| FileChange {
+                    path: path.clone(),
+                    added,
+                    removed,
+                })
+                .collect(),
+            subtitle: self.plan.as_ref().and_then(PlanProgress::subtitle),
+            progress: self.plan.clone(),
+            plan_mode: self.plan_mode,
+            model: self.model.clone(),
+            terminal: self.terminal.clone(),
+            cpu_percent: self.cpu_percent,
+            queued_prompts: self.queued_prompts,
+            subagents: self.subagents.clone(),
+            state_history: self
+                .state_history
+                .iter()
+                .map(|&(state, at)| StateChange {
+                    state,
+                    at: clock.unix_timestamp(at),
+                })
+                .collect(),
         }
     }
 }
@@ -183,6 +509,247 @@ impl Session {
 #[derive(Debug, Default)]
 pub struct SessionRegistry {
     sessions: HashMap<String, Session>,
+    /// Manual project order (cwds) set by dragging rows in the session list.
+    /// Sessions in projects not listed here sort by most recent activity.
+    manual_order: Vec<String>,
+    /// Config rules tagging sessions when they start
+    tag_rules: Vec<TagRule>,
+    /// Tool name to category table (built-in plus config `tool_categories`)
+    tool_categories: ToolCategories,
+    /// Bumped by [`publish`](Self::publish) whenever the listed sessions change
+    generation: u64,
+    /// Each listed session as last published, with the generation it changed
+    published: HashMap<String, (u64, Arc<SessionInfo>)>,
+    /// Sessions mutated since the last publish
+    dirty: HashSet<String>,
+    /// When each session left the list (removed or archived)
+    unlisted: Arc<HashMap<String, Unlisted>>,
+    /// Listed session ids in display order, as of the last publish
+    order: Vec<String>,
+    /// The last publish, handed out again while nothing changes
+    latest: Arc<Published>,
+    /// Source of "now" for activity, stale and expiry times
+    clock: SharedClock,
+    /// Garbage collection policy
+    retention: Retention,
+    /// Sessions garbage collected since the daemon started
+    gc_totals: GcStats,
+    /// Events held until their session starts (see
+    /// [`process_event_from`](Self::process_event_from))
+    pending: HashMap<String, Pending>,
+    /// Config `filters`, applied before anything else
+    filter: EventFilter,
+    /// Tool events seen in the current and previous time bucket
+    delivered: HashSet<DeliveryKey>,
+    /// Stamped events per session waiting for earlier ones, in stamp order
+    reordering: HashMap<String, Vec<Reordered>>,
+    /// Senders from [`observe`](Self::observe), dropped with their receiver
+    observers: Vec<mpsc::UnboundedSender<RegistryChange>>,
+}
+
+/// A change to one listed session, sent to [observers](SessionRegistry::observe)
+/// when it's published
+#[derive(Debug, Clone, PartialEq)]
+pub enum RegistryChange {
+    /// A session joined the list: it started, or was restored
+    Added(SessionInfo),
+    /// A listed session changed
+    Updated {
+        previous: Box<SessionInfo>,
+        current: SessionInfo,
+    },
+    /// A session left the list, as it was last published; `archived` when
+    /// it's only hidden
+    Removed {
+        session: SessionInfo,
+        archived: bool,
+    },
+}
+
+impl RegistryChange {
+    pub fn session_id(&self) -> &str {
+        match self {
+            Self::Added(session)
+            | Self::Updated {
+                current: session, ..
+            }
+            | Self::Removed { session, .. } => &session.session_id,
+        }
+    }
+
+    /// The session as it is now, unless it left the list
+    pub fn current(&self) -> Option<&SessionInfo> {
+        match self {
+            Self::Added(session)
+            | Self::Updated {
+                current: session, ..
+            } => Some(session),
+            Self::Removed { .. } => None,
+        }
+    }
+}
+
+/// Changes to the listed sessions since a generation
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionChanges {
+    /// Generation the changes bring the caller up to
+    pub generation: u64,
+    /// Added or updated sessions
+    pub updated: Vec<SessionInfo>,
+    /// Sessions that left the list
+    pub removed: Vec<String>,
+    /// Those of `removed` the registry forgot (they ended or were evicted)
+    /// rather than archived
+    pub ended: Vec<String>,
+    /// Ids of every listed session, in display order
+    pub order: Vec<String>,
+}
+
+impl SessionChanges {
+    /// Whether nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.updated.is_empty() && self.removed.is_empty()
+    }
+
+    /// Bring `sessions` (the list as of the requested generation) up to date
+    pub fn apply(self, sessions: &mut Vec<SessionInfo>) {
+        let mut by_id: HashMap<String, SessionInfo> = sessions
+            .drain(..)
+            .map(|session| (session.session_id.clone(), session))
+            .collect();
+        for session in self.updated {
+            by_id.insert(session.session_id.clone(), session);
+        }
+        *sessions = self
+            .order
+            .iter()
+            .filter_map(|id| by_id.remove(id))
+            .collect();
+    }
+}
+
+/// When a session left the list
+#[derive(Debug, Clone, Copy)]
+struct Unlisted {
+    generation: u64,
+    /// It was removed from the registry, not archived
+    forgotten: bool,
+}
+
+/// The listed sessions as of one [`publish`](SessionRegistry::publish).
+///
+/// Cheap to build and to clone: each session is shared with earlier lists
+/// until it changes. Readers that want a plain list get one from
+/// [`sessions`](Self::sessions), built once per publish.
+#[derive(Debug, Default)]
+pub struct Published {
+    generation: u64,
+    /// Listed sessions in display order, with the generation each changed
+    sessions: Vec<(u64, Arc<SessionInfo>)>,
+    unlisted: Arc<HashMap<String, Unlisted>>,
+    /// `sessions` as one list, built on first use
+    list: OnceLock<Snapshot>,
+}
+
+impl Published {
+    /// Registry generation this list reflects
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+
+    /// Listed sessions in display order
+    pub fn iter(&self) -> impl Iterator<Item = &SessionInfo> {
+        self.sessions.iter().map(|(_, session)| session.as_ref())
+    }
+
+    /// Listed sessions as one list, cloned only for the first caller
+    pub fn sessions(&self) -> Snapshot {
+        self.list
+            .get_or_init(|| Arc::new(self.iter().cloned().collect()))
+            .clone()
+    }
+
+    /// Sessions added, updated or removed after `generation`, cloning only those
+    pub fn changes_since(&self, generation: u64) -> SessionChanges {
+        let order = self
+            .iter()
+            .map(|session| session.session_id.clone())
+            .collect();
+        if generation >= self.generation {
+            return SessionChanges {
+                generation: self.generation,
+                order,
+                ..Default::default()
+            };
+        }
+        let updated = self
+            .sessions
+            .iter()
+            .filter(|(changed_at, _)| *changed_at > generation)
+            .map(|(_, session)| SessionInfo::clone(session))
+            .collect();
+        let mut removed = Vec::new();
+        let mut ended = Vec::new();
+        for (id, unlisted) in self.unlisted.iter() {
+            if unlisted.generation > generation {
+                removed.push(id.clone());
+                if unlisted.forgotten {
+                    ended.push(id.clone());
+                }
+            }
+        }
+        SessionChanges {
+            generation: self.generation,
+            updated,
+            removed,
+            ended,
+            order,
+        }
+    }
+}
+
+/// Overlaps between live sessions, computed once per snapshot
+#[derive(Debug, Default)]
+struct Conflicts<'a> {
+    /// Working directories with more than one session
+    cwds: HashSet<&'a str>,
+    /// Files modified by more than one session, with how many
+    files: HashMap<&'a str, usize>,
+}
+
+impl Conflicts<'_> {
+    /// Whether another live session shares `session`'s cwd
+    fn cwd_conflict(&self, session: &Session) -> bool {
+        self.cwds.contains(session.cwd.trim_end_matches('/'))
+    }
+
+    /// Files `session` modified that another live session did too
+    fn file_conflicts<'s>(&self, session: &'s Session) -> impl Iterator<Item = &'s String> {
+        session
+            .changed_files
+            .keys()
+            .filter(move |path| self.files.contains_key(path.as_str()))
+    }
+
+    /// Whether `published`'s conflict flags still hold for `session`
+    fn unchanged(&self, session: &Session, published: &SessionInfo) -> bool {
+        if !session.is_live() {
+            return !published.cwd_conflict && published.file_conflicts.is_empty();
+        }
+        published.cwd_conflict == self.cwd_conflict(session)
+            && published
+                .file_conflicts
+                .iter()
+                .eq(self.file_conflicts(session))
+    }
 }
 
 impl SessionRegistry {
@@ -190,6 +757,26 @@ impl SessionRegistry {
         Self::default()
     }
 
+    /// Registry reading the time from `clock` (tests pass a
+    /// [`ManualClock`](crate::clock::ManualClock))
+    pub fn with_clock(clock: SharedClock) -> Self {
+        Self {
+            clock,
+            ..Self::default()
+        }
+    }
+
+    pub fn clock(&self) -> &SharedClock {
+        &self.clock
+    }
+
+    /// A session to change, rebuilt on the next [`publish`](Self::publish)
+    fn session_mut(&mut self, session_id: &str) -> Option<&mut Session> {
+        let session = self.sessions.get_mut(session_id)?;
+        self.dirty.insert(session_id.to_string());
+        Some(session)
+    }
+
     /// Get existing session or create a new one (late registration), update it, and touch.
     fn update_session<F>(
         &mut self,
@@ -200,44 +787,303 @@ impl SessionRegistry {
     ) where
         F: FnOnce(&mut Session),
     {
+        let now = self.clock.now();
+        self.dirty.insert(session_id.to_string());
         let session = self
             .sessions
             .entry(session_id.to_string())
             .or_insert_with(|| {
-                info!(%session_id, %cwd, ?default_agent, "late session registration");
-                Session::new(session_id.to_string(), cwd.to_string(), default_agent)
+                info!(
+                    target: logging::REGISTRY,
+                    %session_id,
+                    %cwd,
+                    ?default_agent,
+                    "late session registration"
+                );
+                Session::started(
+                    session_id.to_string(),
+                    cwd.to_string(),
+                    default_agent,
+                    &self.tag_rules,
+                    now,
+                )
             });
-        session.touch();
+        session.touch(now);
+        let before = session.state;
         updater(session);
+        if session.state != before {
+            session.entered_state(now);
+        }
     }
 
     /// Process an agent event with an explicit default agent type for late registration.
     ///
     /// When a session is created implicitly (late registration), the given
     /// `default_agent` is used instead of hardcoding `AgentType::ClaudeCode`.
+    ///
+    /// Events can beat `SessionStarted`: hooks run as separate processes, and
+    /// a Codex rollout or transcript may be read before its meta line. Events
+    /// for an unknown session that carry no cwd (metadata, or a rollout whose
+    /// meta hasn't been read) are held for up to `PENDING_TTL` and applied,
+    /// in order, when the session starts or an event with a cwd registers it.
+    /// Past the TTL they're applied anyway.
+    ///
+    /// Events the config `filters` rule out are dropped first (see
+    /// [`crate::filter`]), along with anything held for their session, and
+    /// so are repeated hook deliveries of a tool event (see
+    /// [`is_redelivery`](Self::is_redelivery)).
     pub fn process_event_from(&mut self, event: AgentEvent, default_agent: AgentType) {
+        if let AgentEvent::Unknown = event {
+            return;
+        }
+        self.expire_pending();
+        let session_id = event.session_id().to_string();
+        let Some(event) = self.filter.apply(event, &default_agent, self.clock.now()) else {
+            self.pending.remove(&session_id);
+            return;
+        };
+        if Source::of(&default_agent) == Source::ClaudeCode && self.is_redelivery(&event) {
+            debug!(target: logging::REGISTRY, %session_id, "dropped repeated tool event");
+            return;
+        }
+        if self.sessions.contains_key(&session_id) {
+            self.apply_event(event, default_agent);
+            return;
+        }
+        if matches!(event, AgentEvent::SessionStarted { .. }) {
+            self.apply_event(event, default_agent);
+            self.release_pending(&session_id);
+        } else if matches!(event, AgentEvent::SessionEnded { .. }) {
+            self.pending.remove(&session_id);
+            self.apply_event(event, default_agent);
+        } else if event.cwd().is_empty() {
+            self.hold(event, default_agent);
+        } else {
+            // Late registration; held events came first
+            if self.pending.contains_key(&session_id) {
+                let cwd = event.cwd().to_string();
+                self.update_session(&session_id, &cwd, default_agent.clone(), |_| {});
+                self.release_pending(&session_id);
+            }
+            self.apply_event(event, default_agent);
+        }
+    }
+
+    /// Whether `event` repeats a tool event already applied: same session,
+    /// tool ID and kind (started or completed) in the current or previous
+    /// [`DEDUP_BUCKET_SECS`] bucket. Claude Code re-fires `PreToolUse` and
+    /// `PostToolUse` hooks on retries, which would otherwise start a finished
+    /// tool again or list it twice in recent activity. Only hook events are
+    /// checked: a Codex call starts a second time once it's approved. Calls
+    /// without a tool ID can't be told apart, so they're never dropped.
+    fn is_redelivery(&mut self, event: &AgentEvent) -> bool {
+        let (session_id, tool_id, kind) = match event {
+            AgentEvent::ToolStarted {
+                session_id,
+                tool_id,
+                ..
+            } => (session_id, tool_id, "started"),
+            AgentEvent::ToolCompleted {
+                session_id,
+                tool_id,
+                ..
+            } => (session_id, tool_id, "completed"),
+            AgentEvent::SessionEnded { session_id } => {
+                self.delivered.retain(|key| key.session_id != *session_id);
+                return false;
+            }
+            _ => return false,
+        };
+        if tool_id.is_empty() || tool_id == crate::UNKNOWN_TOOL_ID {
+            return false;
+        }
+        let bucket = self.clock.unix_timestamp(self.clock.now()) / DEDUP_BUCKET_SECS;
+        self.delivered.retain(|key| key.bucket + 1 >= bucket);
+        let mut key = DeliveryKey {
+            session_id: session_id.clone(),
+            tool_id: tool_id.clone(),
+            kind,
+            bucket: bucket.saturating_sub(1),
+        };
+        if self.delivered.contains(&key) {
+            return true;
+        }
+        key.bucket = bucket;
+        !self.delivered.insert(key)
+    }
+
+    /// Process an event stamped by the hook that sent it. Hooks run as
+    /// separate processes, so a slow one can connect after a hook fired
+    /// later: stamped events wait [`REORDER_WINDOW`] and are applied per
+    /// session in stamp order, rather than in arrival order. Events arriving
+    /// after a later stamped one was applied go through as they come, and
+    /// unstamped events (Codex, the CLI, older hooks) aren't held.
+    pub fn process_stamped(
+        &mut self,
+        event: AgentEvent,
+        default_agent: AgentType,
+        stamp: Option<EventStamp>,
+    ) {
+        let Some(stamp) = stamp else {
+            self.process_event_from(event, default_agent);
+            return;
+        };
+        let now_ms = self.now_unix_ms();
+        let held = self
+            .reordering
+            .entry(event.session_id().to_string())
+            .or_default();
+        let at = held.partition_point(|held| held.stamp <= stamp);
+        held.insert(
+            at,
+            Reordered {
+                stamp,
+                release_ms: stamp.sent_at_ms.min(now_ms) + REORDER_WINDOW.as_millis() as u64,
+                event,
+                agent: default_agent,
+            },
+        );
+        self.release_reordered();
+    }
+
+    /// Apply stamped events whose window has passed, each session's in
+    /// stamp order
+    pub fn release_reordered(&mut self) {
+        let now_ms = self.now_unix_ms();
+        let mut due = Vec::new();
+        for held in self.reordering.values_mut() {
+            let ready = held
+                .iter()
+                .position(|held| held.release_ms > now_ms)
+                .unwrap_or(held.len());
+            due.extend(held.drain(..ready));
+        }
+        self.reordering.retain(|_, held| !held.is_empty());
+        for held in due {
+            self.process_event_from(held.event, held.agent);
+        }
+    }
+
+    /// When the next stamped event's window passes
+    pub fn next_reorder_release(&self) -> Option<Instant> {
+        let release_ms = self
+            .reordering
+            .values()
+            .filter_map(|held| held.first())
+            .map(|held| held.release_ms)
+            .min()?;
+        let wait = release_ms.saturating_sub(self.now_unix_ms());
+        Some(self.clock.now() + Duration::from_millis(wait))
+    }
+
+    fn now_unix_ms(&self) -> u64 {
+        self.clock
+            .system_now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |since| since.as_millis() as u64)
+    }
+
+    /// Hold an event until its session starts
+    fn hold(&mut self, event: AgentEvent, agent: AgentType) {
+        let session_id = event.session_id().to_string();
+        let full = match self.pending.get(&session_id) {
+            Some(pending) => pending.events.len() >= MAX_PENDING_EVENTS,
+            None => self.pending.len() >= MAX_PENDING_SESSIONS,
+        };
+        if full {
+            debug!(
+                target: logging::REGISTRY,
+                %session_id,
+                "too many held events; applying without SessionStarted"
+            );
+            self.release_pending(&session_id);
+            self.apply_event(event, agent);
+            return;
+        }
+        trace!(target: logging::REGISTRY, %session_id, "holding event until session starts");
+        let since = self.clock.now();
+        self.pending
+            .entry(session_id)
+            .or_insert_with(|| Pending {
+                since,
+                events: Vec::new(),
+            })
+            .events
+            .push((event, agent));
+    }
+
+    /// Apply the events held for a session
+    fn release_pending(&mut self, session_id: &str) {
+        let Some(pending) = self.pending.remove(session_id) else {
+            return;
+        };
+        debug!(
+            target: logging::REGISTRY,
+            %session_id,
+            events = pending.events.len(),
+            "applying held events"
+        );
+        for (event, agent) in pending.events {
+            self.apply_event(event, agent);
+        }
+    }
+
+    /// Apply events held longer than `PENDING_TTL`; their session never
+    /// started, so they register it late (or are dropped, for metadata)
+    pub fn expire_pending(&mut self) {
+        let now = self.clock.now();
+        let expired: Vec<String> = self
+            .pending
+            .iter()
+            .filter(|(_, pending)| now.duration_since(pending.since) >= PENDING_TTL)
+            .map(|(session_id, _)| session_id.clone())
+            .collect();
+        for session_id in expired {
+            self.release_pending(&session_id);
+        }
+    }
+
+    /// When the oldest held events expire
+    pub fn next_pending_expiry(&self) -> Option<Instant> {
+        self.pending
+            .values()
+            .map(|pending| pending.since + PENDING_TTL)
+            .min()
+    }
+
+    fn apply_event(&mut self, event: AgentEvent, default_agent: AgentType) {
+        let now = self.clock.now();
+        self.dirty.insert(event.session_id().to_string());
         match event {
             AgentEvent::SessionStarted {
                 session_id,
                 cwd,
                 agent,
+                terminal,
             } => {
                 if let Some(session) = self.sessions.get_mut(&session_id) {
-                    // Session already exists (e.g., subagent transcript discovered).
-                    // Update metadata but keep tool/state history.
+                    // Session already exists (e.g., subagent transcript discovered
+                    // or an archived session resumed). Update metadata but keep
+                    // tool/state history.
                     session.cwd = cwd;
                     session.agent = agent;
-                    session.touch();
+                    if terminal.is_some() {
+                        session.terminal = terminal;
+                    }
+                    session.archived = false;
+                    session.touch(now);
                 } else {
-                    info!(%session_id, %cwd, ?agent, "session started");
-                    self.sessions
-                        .insert(session_id.clone(), Session::new(session_id, cwd, agent));
-                    debug!("{} total session(s)", self.sessions.len());
+                    info!(target: logging::REGISTRY, %session_id, %cwd, ?agent, "session started");
+                    let mut session =
+                        Session::started(session_id.clone(), cwd, agent, &self.tag_rules, now);
+                    session.terminal = terminal;
+                    self.sessions.insert(session_id, session);
+                    debug!(target: logging::REGISTRY, "{} total session(s)", self.sessions.len());
                 }
             }
 
             AgentEvent::Activity { session_id, cwd } => {
-                trace!(%session_id, "activity");
+                trace!(target: logging::REGISTRY, %session_id, "activity");
                 self.update_session(&session_id, &cwd, default_agent.clone(), |session| {
                     if session.state == SessionState::Idle || session.state == SessionState::Stale {
                         session.transition_to_running();
@@ -251,13 +1097,17 @@ impl SessionRegistry {
                 tool_id,
                 tool_name,
                 tool_label,
+                tool_full_label,
             } => {
-                debug!(%session_id, %tool_name, "tool started");
+                debug!(target: logging::REGISTRY, %session_id, %tool_name, "tool started");
+                let category = self.tool_categories.categorize(&tool_name);
                 self.update_session(&session_id, &cwd, default_agent.clone(), |session| {
                     session.add_tool(RunningTool {
                         tool_id,
                         tool_name,
                         tool_label,
+                        tool_full_label,
+                        category,
                     });
                 });
             }
@@ -267,9 +1117,9 @@ impl SessionRegistry {
                 cwd,
                 tool_id,
             } => {
-                debug!(%session_id, %tool_id, "tool completed");
+                debug!(target: logging::REGISTRY, %session_id, %tool_id, "tool completed");
                 self.update_session(&session_id, &cwd, default_agent.clone(), |session| {
-                    session.complete_tool(&tool_id);
+                    session.complete_tool(&tool_id, now);
                 });
             }
 
@@ -278,10 +1128,12 @@ impl SessionRegistry {
                 cwd,
                 message,
             } => {
-                info!(%session_id, "needs attention");
+                info!(target: logging::REGISTRY, %session_id, "needs attention");
                 self.update_session(&session_id, &cwd, default_agent.clone(), |session| {
-                    session.state = SessionState::Attention;
-                    session.permission_tool = message;
+                    if session.enter(SessionState::Attention) {
+                        session.permission_tool = message;
+                        session.seen = false;
+                    }
                 });
             }
 
@@ -290,39 +1142,145 @@ impl SessionRegistry {
                 cwd,
                 message: _,
             } => {
-                info!(%session_id, "waiting for input");
+                info!(target: logging::REGISTRY, %session_id, "waiting for input");
                 self.update_session(&session_id, &cwd, default_agent.clone(), |session| {
-                    session.state = SessionState::Waiting;
+                    if session.enter(SessionState::Waiting) {
+                        session.seen = false;
+                    }
                 });
             }
 
             AgentEvent::Compacting { session_id, cwd } => {
-                info!(%session_id, "compacting");
+                info!(target: logging::REGISTRY, %session_id, "compacting");
                 self.update_session(&session_id, &cwd, default_agent.clone(), |session| {
-                    session.state = SessionState::Compacting;
+                    session.enter(SessionState::Compacting);
                 });
             }
 
             AgentEvent::Idle { session_id, cwd } => {
-                debug!(%session_id, "idle");
+                debug!(target: logging::REGISTRY, %session_id, "idle");
                 self.update_session(&session_id, &cwd, default_agent, |session| {
-                    session.set_idle();
+                    session.set_idle(now);
                 });
             }
 
             AgentEvent::SessionEnded { session_id } => {
-                info!(%session_id, "session ended");
+                info!(target: logging::REGISTRY, %session_id, "session ended");
                 self.sessions.remove(&session_id);
-                debug!("{} total session(s)", self.sessions.len());
+                debug!(target: logging::REGISTRY, "{} total session(s)", self.sessions.len());
             }
 
             AgentEvent::SessionNameUpdated { session_id, name } => {
-                info!(%session_id, %name, "session name updated");
+                info!(target: logging::REGISTRY, %session_id, %name, "session name updated");
                 if let Some(session) = self.sessions.get_mut(&session_id) {
                     session.name = Some(name);
-                    session.touch();
+                    session.touch(now);
+                }
+            }
+
+            AgentEvent::SessionTagged { session_id, tags } => {
+                info!(target: logging::REGISTRY, %session_id, ?tags, "session tagged");
+                if let Some(session) = self.sessions.get_mut(&session_id) {
+                    session.tags.extend(tags);
+                }
+            }
+
+            AgentEvent::FilesTouched {
+                session_id,
+                changes,
+            } => {
+                debug!(
+                    target: logging::REGISTRY,
+                    %session_id,
+                    files = changes.len(),
+                    "files touched"
+                );
+                if let Some(session) = self.sessions.get_mut(&session_id) {
+                    session.record_changes(changes);
+                }
+            }
+
+            AgentEvent::TranscriptPath { session_id, path } => {
+                debug!(target: logging::REGISTRY, %session_id, %path, "transcript path");
+                if let Some(session) = self.sessions.get_mut(&session_id) {
+                    session.transcript_path = Some(path);
+                }
+            }
+
+            AgentEvent::PlanUpdated {
+                session_id,
+                progress,
+            } => {
+                debug!(
+                    target: logging::REGISTRY,
+                    %session_id,
+                    completed = progress.completed,
+                    total = progress.total,
+                    "plan updated"
+                );
+                if let Some(session) = self.sessions.get_mut(&session_id) {
+                    session.plan = (progress.total > 0).then_some(progress);
+                }
+            }
+
+            AgentEvent::PlanModeChanged { session_id, active } => {
+                if let Some(session) = self.sessions.get_mut(&session_id)
+                    && session.plan_mode != active
+                {
+                    debug!(target: logging::REGISTRY, %session_id, active, "plan mode");
+                    session.plan_mode = active;
+                }
+            }
+
+            AgentEvent::ModelUpdated { session_id, model } => {
+                if let Some(session) = self.sessions.get_mut(&session_id)
+                    && session.model.as_deref() != Some(model.as_str())
+                {
+                    debug!(target: logging::REGISTRY, %session_id, %model, "model");
+                    session.model = Some(model);
+                }
+            }
+
+            AgentEvent::SubagentStarted {
+                session_id,
+                agent_id,
+                agent_type,
+            } => {
+                debug!(target: logging::REGISTRY, %session_id, %agent_id, "subagent started");
+                if let Some(session) = self.sessions.get_mut(&session_id) {
+                    let subagent = session.subagent(agent_id);
+                    subagent.agent_type = agent_type.or(subagent.agent_type.take());
                 }
             }
+
+            AgentEvent::SubagentTool {
+                session_id,
+                agent_id,
+                tool_id,
+            } => {
+                if let Some(session) = self.sessions.get_mut(&session_id) {
+                    session.assign_tool(agent_id, &tool_id);
+                }
+            }
+
+            AgentEvent::SubagentStopped {
+                session_id,
+                agent_id,
+            } => {
+                debug!(target: logging::REGISTRY, %session_id, %agent_id, "subagent stopped");
+                if let Some(session) = self.sessions.get_mut(&session_id) {
+                    session.subagents.retain(|a| a.agent_id != agent_id);
+                }
+            }
+
+            AgentEvent::PromptsQueued { session_id, count } => {
+                debug!(target: logging::REGISTRY, %session_id, count, "prompts queued");
+                if let Some(session) = self.sessions.get_mut(&session_id) {
+                    session.queued_prompts = count;
+                }
+            }
+
+            AgentEvent::Unknown => {}
         }
     }
 
@@ -342,49 +1300,632 @@ impl SessionRegistry {
         self.sessions
             .values()
             .filter(|s| {
-                s.state != SessionState::Stale
-                    && s.state != SessionState::Idle
-                    && s.state != SessionState::Waiting
+                s.state != SessionState::Stale && s.state.can_transition_to(SessionState::Stale)
             })
             .map(|s| s.last_activity + timeout)
             .min()
     }
 
+    /// When the next completed tool drops out of `running_tools`, so a
+    /// published snapshot can be refreshed
+    pub fn next_tool_expiry(&self) -> Option<Instant> {
+        let now = self.clock.now();
+        self.sessions
+            .values()
+            .flat_map(|s| s.recent_tools.iter().map(|t| t.expires_at))
+            .filter(|at| *at > now)
+            .min()
+    }
+
     /// Mark sessions as stale if no activity for the given duration
     pub fn mark_stale(&mut self, timeout: Duration) {
-        let now = Instant::now();
-        for session in self.sessions.values_mut() {
+        let now = self.clock.now();
+        for (id, session) in &mut self.sessions {
             // Clean up expired recent tools
+            let tools = session.recent_tools.len();
             session.recent_tools.retain(|t| t.expires_at > now);
+            if session.recent_tools.len() != tools {
+                self.dirty.insert(id.clone());
+            }
 
             if now.duration_since(session.last_activity) > timeout {
-                // Only mark stale if not already in a terminal state
-                if session.state != SessionState::Idle
-                    && session.state != SessionState::Waiting
-                    && session.state != SessionState::Stale
+                // Idle and Waiting sessions are waiting on the user; going
+                // quiet there isn't staleness
+                if session.state != SessionState::Stale
+                    && session.state.can_transition_to(SessionState::Stale)
+                    && session.enter(SessionState::Stale)
                 {
-                    session.state = SessionState::Stale;
-                    session.stale_at = Some(Instant::now());
+                    session.stale_at = Some(now);
+                    session.entered_state(now);
+                    self.dirty.insert(id.clone());
                 }
             }
         }
     }
 
-    /// Get all sessions as SessionInfo
-    pub fn get_all(&self) -> Vec<SessionInfo> {
-        self.sessions.values().map(|s| s.to_info()).collect()
+    /// Garbage collection policy applied by [`collect_garbage`](Self::collect_garbage)
+    pub fn set_retention(&mut self, retention: Retention) {
+        self.retention = retention;
     }
 
-    /// Remove a session by ID (used by UI when clicking the remove button)
-    pub fn remove_session(&mut self, session_id: &str) {
-        info!(%session_id, "session removed via UI");
-        self.sessions.remove(session_id);
+    /// Sessions garbage collected so far
+    pub fn gc_totals(&self) -> GcStats {
+        self.gc_totals
     }
 
-    /// Get session count
-    pub fn len(&self) -> usize {
-        self.sessions.len()
-    }
+    /// Apply the retention policy: archive sessions stale longer than
+    /// `remove_stale_after`, drop the tool buffers of archived sessions, and
+    /// evict the oldest archived or Stale sessions over `max_sessions`.
+    /// Event filter verdicts of sessions that went quiet are dropped too.
+    ///
+    /// Returns what this pass did; the totals are logged.
+    pub fn collect_garbage(&mut self) -> GcStats {
+        let now = self.clock.now();
+        self.filter.forget_idle(now);
+        let mut stats = GcStats::default();
+        for session in self.sessions.values_mut() {
+            let expired = session.state == SessionState::Stale
+                && self
+                    .retention
+                    .remove_stale_after
+                    .zip(session.stale_at)
+                    .is_some_and(|(after, stale_at)| now.duration_since(stale_at) >= after);
+            if expired && !session.archived {
+                session.archived = true;
+                stats.archived += 1;
+            }
+            if session.archived {
+                session.running_tools.clear();
+                session.recent_tools.clear();
+            }
+        }
+
+        let excess = self
+            .sessions
+            .len()
+            .saturating_sub(self.retention.max_sessions);
+        if excess > 0 {
+            let mut candidates: Vec<(bool, Instant, String)> = self
+                .sessions
+                .values()
+                .filter(|s| s.archived || s.state == SessionState::Stale)
+                .map(|s| (!s.archived, s.last_activity, s.session_id.clone()))
+                .collect();
+            // Archived before Stale, least recently active first
+            candidates.sort();
+            for (_, _, session_id) in candidates.into_iter().take(excess) {
+                self.sessions.remove(&session_id);
+                self.filter.forget(&session_id);
+                stats.evicted += 1;
+            }
+        }
+
+        if stats != GcStats::default() {
+            self.gc_totals.archived += stats.archived;
+            self.gc_totals.evicted += stats.evicted;
+            info!(
+                target: logging::REGISTRY,
+                archived = stats.archived,
+                evicted = stats.evicted,
+                total_archived = self.gc_totals.archived,
+                total_evicted = self.gc_totals.evicted,
+                sessions = self.sessions.len(),
+                "sessions garbage collected"
+            );
+        }
+        stats
+    }
+
+    /// When the next Stale session is due to be archived by
+    /// [`collect_garbage`](Self::collect_garbage)
+    pub fn next_gc_at(&self) -> Option<Instant> {
+        let after = self.retention.remove_stale_after?;
+        self.sessions
+            .values()
+            .filter(|s| s.state == SessionState::Stale && !s.archived)
+            .filter_map(|s| s.stale_at)
+            .map(|stale_at| stale_at + after)
+            .min()
+    }
+
+    /// Working directories and files shared by more than one live session.
+    ///
+    /// Two agents editing the same checkout (often a worktree that wasn't
+    /// created) or the same file tend to clobber each other. Archived and
+    /// stale sessions don't count; trailing slashes on cwds are ignored.
+    fn conflicts(sessions: &HashMap<String, Session>) -> Conflicts<'_> {
+        let mut cwds = HashSet::new();
+        let mut conflicts = Conflicts::default();
+        for session in sessions.values().filter(|s| s.is_live()) {
+            let cwd = session.cwd.trim_end_matches('/');
+            if !cwd.is_empty() && !cwds.insert(cwd) {
+                conflicts.cwds.insert(cwd);
+            }
+            for path in session.changed_files.keys() {
+                *conflicts.files.entry(path.as_str()).or_default() += 1;
+            }
+        }
+        conflicts.files.retain(|_, editors| *editors > 1);
+        conflicts
+    }
+
+    /// SessionInfo with `cwd_conflict` and `file_conflicts` filled in
+    fn session_info(
+        session: &Session,
+        conflicts: &Conflicts<'_>,
+        clock: &SharedClock,
+    ) -> SessionInfo {
+        let info = session.to_info(clock);
+        if !session.is_live() {
+            return info;
+        }
+        SessionInfo {
+            cwd_conflict: conflicts.cwd_conflict(session),
+            file_conflicts: conflicts.file_conflicts(session).cloned().collect(),
+            ..info
+        }
+    }
+
+    /// Non-archived sessions in display order (see [`get_all`](Self::get_all))
+    fn listed<'a>(
+        sessions: &'a HashMap<String, Session>,
+        manual_order: &[String],
+    ) -> Vec<&'a Session> {
+        let mut listed: Vec<&Session> = sessions.values().filter(|s| !s.archived).collect();
+        listed.sort_by_key(|s| {
+            let position = manual_order
+                .iter()
+                .position(|cwd| *cwd == s.cwd)
+                .unwrap_or(usize::MAX);
+            (position, std::cmp::Reverse(s.last_activity))
+        });
+        listed
+    }
+
+    /// Get all non-archived sessions as SessionInfo, in display order
+    ///
+    /// Projects in the manual order come first (in that order); everything
+    /// else follows, most recently active first. Sessions sharing a cwd or
+    /// modified files with another live session are flagged (`cwd_conflict`,
+    /// `file_conflicts`).
+    pub fn get_all(&self) -> Vec<SessionInfo> {
+        let conflicts = Self::conflicts(&self.sessions);
+        Self::listed(&self.sessions, &self.manual_order)
+            .into_iter()
+            .map(|s| Self::session_info(s, &conflicts, &self.clock))
+            .collect()
+    }
+
+    /// Manual project order (cwds), for persisting to state.json
+    pub fn manual_order(&self) -> &[String] {
+        &self.manual_order
+    }
+
+    /// Replace the manual project order (e.g. restored from state.json)
+    pub fn set_manual_order(&mut self, order: Vec<String>) {
+        self.manual_order = order;
+    }
+
+    /// Move a session's project to the position of another session's project.
+    ///
+    /// Used when a row is dragged onto another row. The resulting display
+    /// order of all visible projects becomes the manual order; previously
+    /// ordered projects without visible sessions keep their place at the end.
+    /// Returns `false` if either session is missing or both share a project.
+    pub fn move_session_to(&mut self, session_id: &str, target_id: &str) -> bool {
+        let (Some(moving), Some(target)) = (
+            self.sessions.get(session_id).map(|s| s.cwd.clone()),
+            self.sessions.get(target_id).map(|s| s.cwd.clone()),
+        ) else {
+            return false;
+        };
+        if moving == target {
+            return false;
+        }
+
+        let mut order: Vec<String> = Vec::new();
+        for session in self.get_all() {
+            if !order.contains(&session.cwd) {
+                order.push(session.cwd);
+            }
+        }
+        let (Some(from), Some(to)) = (
+            order.iter().position(|cwd| *cwd == moving),
+            order.iter().position(|cwd| *cwd == target),
+        ) else {
+            return false;
+        };
+        let cwd = order.remove(from);
+        order.insert(to, cwd);
+
+        for cwd in &self.manual_order {
+            if !order.contains(cwd) {
+                order.push(cwd.clone());
+            }
+        }
+        info!(target: logging::REGISTRY, %session_id, %target_id, "session reordered");
+        self.manual_order = order;
+        true
+    }
+
+    /// Get archived sessions as SessionInfo
+    pub fn get_archived(&self) -> Vec<SessionInfo> {
+        self.sessions
+            .values()
+            .filter(|s| s.archived)
+            .map(|s| s.to_info(&self.clock))
+            .collect()
+    }
+
+    /// Get a single session as SessionInfo
+    pub fn get(&self, session_id: &str) -> Option<SessionInfo> {
+        let conflicts = Self::conflicts(&self.sessions);
+        self.sessions
+            .get(session_id)
+            .map(|s| Self::session_info(s, &conflicts, &self.clock))
+    }
+
+    /// Current generation (see [`publish`](Self::publish))
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Record the listed sessions and return them, in display order.
+    ///
+    /// Only sessions mutated since the last publish are rebuilt, along with
+    /// those whose time-dependent fields (expiring tools, snooze) or
+    /// conflict flags may have moved; the others are shared with the last
+    /// list. Sessions that differ from the last publish (or appeared, or
+    /// left the list) are stamped with a new generation for
+    /// [`Published::changes_since`] and sent to observers as
+    /// [`RegistryChange`]s.
+    pub fn publish(&mut self) -> Arc<Published> {
+        let next = self.generation + 1;
+        let observed = !self.observers.is_empty();
+        let mut observed_changes = Vec::new();
+        let live = &self.sessions;
+        let listed = Self::listed(live, &self.manual_order);
+        let conflicts = Self::conflicts(live);
+
+        let listed_ids: HashSet<&str> = listed.iter().map(|s| s.session_id.as_str()).collect();
+        let before = self.published.len();
+        let unlisted = &mut self.unlisted;
+        self.published.retain(|id, (_, session)| {
+            let keep = listed_ids.contains(id.as_str());
+            if !keep {
+                let forgotten = !live.contains_key(id);
+                if observed {
+                    observed_changes.push(RegistryChange::Removed {
+                        session: SessionInfo::clone(session),
+                        archived: !forgotten,
+                    });
+                }
+                Arc::make_mut(unlisted).insert(
+                    id.clone(),
+                    Unlisted {
+                        generation: next,
+                        forgotten,
+                    },
+                );
+            }
+            keep
+        });
+        let left = self.published.len() != before;
+        let mut changed = left;
+
+        // Sharing a cwd or a file depends on the other sessions too
+        let recheck_conflicts = left || !self.dirty.is_empty();
+        for session in &listed {
+            let id = session.session_id.as_str();
+            let stale = match self.published.get(id) {
+                None => true,
+                Some((_, published)) => {
+                    self.dirty.contains(id)
+                        || !session.recent_tools.is_empty()
+                        || session.snoozed_until.is_some()
+                        || (recheck_conflicts && !conflicts.unchanged(session, published))
+                }
+            };
+            if !stale {
+                continue;
+            }
+            let info = Self::session_info(session, &conflicts, &self.clock);
+            if self
+                .published
+                .get(id)
+                .is_some_and(|(_, published)| **published == info)
+            {
+                continue;
+            }
+            changed = true;
+            if self.unlisted.contains_key(id) {
+                Arc::make_mut(&mut self.unlisted).remove(id);
+            }
+            let info = Arc::new(info);
+            let previous = self
+                .published
+                .insert(id.to_string(), (next, Arc::clone(&info)));
+            if observed {
+                observed_changes.push(match previous {
+                    Some((_, previous)) => RegistryChange::Updated {
+                        previous: Box::new(SessionInfo::clone(&previous)),
+                        current: SessionInfo::clone(&info),
+                    },
+                    None => RegistryChange::Added(SessionInfo::clone(&info)),
+                });
+            }
+        }
+        self.dirty.clear();
+
+        let reordered = !listed.iter().map(|s| &s.session_id).eq(self.order.iter());
+        if reordered {
+            self.order = listed.iter().map(|s| s.session_id.clone()).collect();
+        }
+        if left {
+            self.forget_unlisted();
+        }
+        if !observed_changes.is_empty() {
+            self.observers.retain(|observer| {
+                observed_changes
+                    .iter()
+                    .all(|change| observer.send(change.clone()).is_ok())
+            });
+        }
+
+        if changed || reordered {
+            self.generation = next;
+            self.latest = Arc::new(Published {
+                generation: next,
+                sessions: self
+                    .order
+                    .iter()
+                    .filter_map(|id| self.published.get(id))
+                    .map(|(changed_at, session)| (*changed_at, Arc::clone(session)))
+                    .collect(),
+                unlisted: Arc::clone(&self.unlisted),
+                list: OnceLock::new(),
+            });
+        }
+        Arc::clone(&self.latest)
+    }
+
+    /// Drop the oldest removals of sessions that are gone past [`MAX_UNLISTED`]
+    fn forget_unlisted(&mut self) {
+        let excess = self.unlisted.len().saturating_sub(MAX_UNLISTED);
+        if excess == 0 {
+            return;
+        }
+        let mut gone: Vec<(u64, String)> = self
+            .unlisted
+            .iter()
+            .filter(|(id, _)| !self.sessions.contains_key(*id))
+            .map(|(id, removed)| (removed.generation, id.clone()))
+            .collect();
+        gone.sort_unstable();
+        let unlisted = Arc::make_mut(&mut self.unlisted);
+        for (_, id) in gone.into_iter().take(excess) {
+            unlisted.remove(&id);
+        }
+    }
+
+    /// Receive every [`RegistryChange`] published from now on, for components
+    /// that react to sessions changing rather than diffing snapshots. The
+    /// channel is unbounded, so receivers should keep up (or be dropped).
+    pub fn observe(&mut self) -> mpsc::UnboundedReceiver<RegistryChange> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.observers.push(tx);
+        rx
+    }
+
+    /// Sessions added, updated or removed after `generation`, as of the
+    /// last publish (see [`Published::changes_since`])
+    pub fn changes_since(&self, generation: u64) -> SessionChanges {
+        self.latest.changes_since(generation)
+    }
+
+    /// Permanently remove a session by ID
+    pub fn remove_session(&mut self, session_id: &str) {
+        info!(target: logging::REGISTRY, %session_id, "session removed");
+        self.sessions.remove(session_id);
+    }
+
+    /// Archive a session (used by UI when clicking the remove button).
+    ///
+    /// Archived sessions keep receiving events but are hidden from `get_all()`
+    /// until restored. Returns `false` if the session doesn't exist.
+    pub fn archive_session(&mut self, session_id: &str) -> bool {
+        let Some(session) = self.session_mut(session_id) else {
+            return false;
+        };
+        info!(target: logging::REGISTRY, %session_id, "session archived");
+        session.archived = true;
+        true
+    }
+
+    /// Restore an archived session to the list.
+    ///
+    /// Returns `false` if the session doesn't exist.
+    pub fn restore_session(&mut self, session_id: &str) -> bool {
+        let Some(session) = self.session_mut(session_id) else {
+            return false;
+        };
+        info!(target: logging::REGISTRY, %session_id, "session restored");
+        session.archived = false;
+        true
+    }
+
+    /// Apply a bulk action to all visible sessions, returning how many changed.
+    pub fn apply_bulk(&mut self, action: BulkAction) -> usize {
+        let mut count = 0;
+        for (id, session) in self.sessions.iter_mut().filter(|(_, s)| !s.archived) {
+            let changed = match action {
+                BulkAction::ClearIdle => session.state == SessionState::Idle,
+                BulkAction::ClearStale => session.state == SessionState::Stale,
+                BulkAction::MarkAllSeen => {
+                    !session.seen
+                        && matches!(
+                            session.state,
+                            SessionState::Attention | SessionState::Waiting | SessionState::Idle
+                        )
+                }
+            };
+            if !changed {
+                continue;
+            }
+            match action {
+                BulkAction::ClearIdle | BulkAction::ClearStale => session.archived = true,
+                BulkAction::MarkAllSeen => session.seen = true,
+            }
+            self.dirty.insert(id.clone());
+            count += 1;
+        }
+        info!(target: logging::REGISTRY, ?action, count, "bulk action applied");
+        count
+    }
+
+    /// Acknowledge a session's Attention, Waiting or Idle state without
+    /// changing it, so it stops driving the indicator until the next prompt.
+    ///
+    /// Returns `false` if the session doesn't exist.
+    pub fn mark_seen(&mut self, session_id: &str) -> bool {
+        let Some(session) = self.session_mut(session_id) else {
+            return false;
+        };
+        if matches!(
+            session.state,
+            SessionState::Attention | SessionState::Waiting | SessionState::Idle
+        ) {
+            info!(target: logging::REGISTRY, %session_id, "session marked seen");
+            session.seen = true;
+        }
+        true
+    }
+
+    /// Snooze a session for the given duration.
+    ///
+    /// Returns `false` if the session doesn't exist.
+    pub fn snooze_session(&mut self, session_id: &str, duration: Duration) -> bool {
+        let until = self.clock.now() + duration;
+        let Some(session) = self.session_mut(session_id) else {
+            return false;
+        };
+        info!(target: logging::REGISTRY, %session_id, ?duration, "session snoozed");
+        session.snoozed_until = Some(until);
+        true
+    }
+
+    /// Clear a session's snooze.
+    ///
+    /// Returns `false` if the session doesn't exist.
+    pub fn unsnooze_session(&mut self, session_id: &str) -> bool {
+        let Some(session) = self.session_mut(session_id) else {
+            return false;
+        };
+        info!(target: logging::REGISTRY, %session_id, "session unsnoozed");
+        session.snoozed_until = None;
+        true
+    }
+
+    /// Rename a session (used by the session list context menu and CLI).
+    ///
+    /// Goes through the same path as `SessionNameUpdated` from `aura set-name`.
+    /// Returns `false` if the session doesn't exist.
+    pub fn rename_session(&mut self, session_id: &str, name: &str) -> bool {
+        if !self.sessions.contains_key(session_id) {
+            return false;
+        }
+        self.process_event(AgentEvent::SessionNameUpdated {
+            session_id: session_id.to_string(),
+            name: name.to_string(),
+        });
+        true
+    }
+
+    /// Replace every session's CPU load with `loads` (by session ID); sessions
+    /// left out weren't sampled.
+    pub fn set_cpu_loads(&mut self, loads: &HashMap<String, u16>) {
+        for (id, session) in &mut self.sessions {
+            let load = loads.get(id).copied();
+            if session.cpu_percent != load {
+                session.cpu_percent = load;
+                self.dirty.insert(id.clone());
+            }
+        }
+    }
+
+    /// Add tags to a session (used by the CLI; same path as `aura set-tag`).
+    ///
+    /// Returns `false` if the session doesn't exist.
+    pub fn tag_session(&mut self, session_id: &str, tags: &[String]) -> bool {
+        if !self.sessions.contains_key(session_id) {
+            return false;
+        }
+        self.process_event(AgentEvent::SessionTagged {
+            session_id: session_id.to_string(),
+            tags: tags.to_vec(),
+        });
+        true
+    }
+
+    /// Remove tags from a session.
+    ///
+    /// Returns `false` if the session doesn't exist.
+    pub fn untag_session(&mut self, session_id: &str, tags: &[String]) -> bool {
+        let Some(session) = self.session_mut(session_id) else {
+            return false;
+        };
+        info!(target: logging::REGISTRY, %session_id, ?tags, "session untagged");
+        session.tags.retain(|tag| !tags.contains(tag));
+        true
+    }
+
+    /// Replace a session's tags (the session list's "Edit tags").
+    ///
+    /// Returns `false` if the session doesn't exist.
+    pub fn set_session_tags(&mut self, session_id: &str, tags: Vec<String>) -> bool {
+        let Some(session) = self.session_mut(session_id) else {
+            return false;
+        };
+        info!(target: logging::REGISTRY, %session_id, ?tags, "session tags set");
+        session.tags = tags.into_iter().collect();
+        true
+    }
+
+    /// Rules from config `tags`, applied to sessions that start afterwards
+    pub fn set_tag_rules(&mut self, rules: Vec<TagRule>) {
+        self.tag_rules = rules;
+    }
+
+    /// Filter rules for sessions seen afterwards
+    pub fn set_filters(&mut self, rules: Vec<FilterRule>) {
+        self.filter = EventFilter::new(rules);
+    }
+
+    /// Category table for tools started afterwards
+    pub fn set_tool_categories(&mut self, categories: ToolCategories) {
+        self.tool_categories = categories;
+    }
+
+    /// Resolve a user-supplied session ID (exact match or unique prefix).
+    pub fn resolve_session_id(&self, query: &str) -> Option<String> {
+        if self.sessions.contains_key(query) {
+            return Some(query.to_string());
+        }
+        if query.is_empty() {
+            return None;
+        }
+        let mut matches = self.sessions.keys().filter(|id| id.starts_with(query));
+        let first = matches.next()?;
+        if matches.next().is_some() {
+            return None;
+        }
+        Some(first.clone())
+    }
+
+    /// Get session count
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
 
     /// Check if a session exists by ID
     pub fn has_session(&self, session_id: &str) -> bool {
@@ -395,11 +1936,96 @@ impl SessionRegistry {
     pub fn is_empty(&self) -> bool {
         self.sessions.is_empty()
     }
+
+    /// Sizes of everything the registry holds
+    pub fn footprint(&self) -> Footprint {
+        Footprint {
+            sessions: self.sessions.len(),
+            tools: self
+                .sessions
+                .values()
+                .map(|s| s.running_tools.len() + s.recent_tools.len())
+                .sum(),
+            held_events: self.pending.values().map(|p| p.events.len()).sum::<usize>()
+                + self.reordering.values().map(Vec::len).sum::<usize>(),
+            deliveries: self.delivered.len(),
+            unlisted: self.unlisted.len(),
+            filtered: self.filter.tracked(),
+        }
+    }
+}
+
+/// Visible sessions as of the last registry change
+pub type Snapshot = Arc<Vec<SessionInfo>>;
+
+/// Registry shared between the tokio tasks and the HUD; cheap to clone
+#[derive(Debug, Clone)]
+pub struct SharedRegistry {
+    registry: Arc<Mutex<SessionRegistry>>,
+    published: Arc<watch::Sender<Arc<Published>>>,
+}
+
+impl SharedRegistry {
+    pub fn new(mut registry: SessionRegistry) -> Self {
+        let published = registry.publish();
+        Self {
+            registry: Arc::new(Mutex::new(registry)),
+            published: Arc::new(watch::Sender::new(published)),
+        }
+    }
+
+    /// Mutate the registry and publish a new snapshot if the list changed.
+    ///
+    /// Returns `None` if the lock is poisoned.
+    pub fn update<R>(&self, action: impl FnOnce(&mut SessionRegistry) -> R) -> Option<R> {
+        let Ok(mut registry) = self.registry.lock() else {
+            warn!(target: logging::REGISTRY, "session registry lock poisoned");
+            return None;
+        };
+        let result = action(&mut registry);
+        let generation = registry.generation();
+        let published = registry.publish();
+        // Still under the lock, so a snapshot read after `observe` is never
+        // older than the changes it will be sent
+        if published.generation() != generation {
+            self.published.send_replace(published);
+        }
+        Some(result)
+    }
+
+    /// Query the registry directly (archived sessions, lookups by id).
+    ///
+    /// Prefer [`snapshot`](Self::snapshot) for the visible list.
+    pub fn read<R>(&self, query: impl FnOnce(&SessionRegistry) -> R) -> Option<R> {
+        self.registry.lock().ok().map(|registry| query(&registry))
+    }
+
+    /// Latest published sessions (never waits on the registry lock)
+    pub fn snapshot(&self) -> Snapshot {
+        let published = Arc::clone(&self.published.borrow());
+        published.sessions()
+    }
+
+    /// Receiver notified whenever a new list is published; the HUD applies
+    /// its [`changes_since`](Published::changes_since) without the lock
+    pub fn subscribe(&self) -> watch::Receiver<Arc<Published>> {
+        self.published.subscribe()
+    }
+
+    /// Each change to the listed sessions from now on (see
+    /// [`SessionRegistry::observe`]); `None` if the lock is poisoned
+    pub fn observe(&self) -> Option<mpsc::UnboundedReceiver<RegistryChange>> {
+        self.registry
+            .lock()
+            .ok()
+            .map(|mut registry| registry.observe())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::ManualClock;
 
     #[test]
     fn session_lifecycle() {
@@ -410,6 +2036,7 @@ mod tests {
             session_id: "s1".into(),
             cwd: "/tmp".into(),
             agent: AgentType::ClaudeCode,
+            terminal: None,
         });
         assert_eq!(registry.len(), 1);
 
@@ -423,6 +2050,7 @@ mod tests {
             tool_id: "t1".into(),
             tool_name: "Read".into(),
             tool_label: Some("config.rs".into()),
+            tool_full_label: None,
         });
         let sessions = registry.get_all();
         assert_eq!(sessions[0].running_tools.len(), 1);
@@ -465,6 +2093,7 @@ mod tests {
             session_id: "s1".into(),
             cwd: "/tmp".into(),
             agent: AgentType::ClaudeCode,
+            terminal: None,
         });
 
         registry.process_event(AgentEvent::NeedsAttention {
@@ -477,6 +2106,47 @@ mod tests {
         assert_eq!(sessions[0].state, SessionState::Attention);
     }
 
+    #[test]
+    fn illegal_transitions_are_dropped() {
+        let (_clock, mut registry) = manual_registry();
+        registry.process_event(AgentEvent::SessionStarted {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            agent: AgentType::ClaudeCode,
+            terminal: None,
+        });
+        registry.process_event(AgentEvent::Idle {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+        });
+        registry.mark_seen("s1");
+        let history = registry.get("s1").unwrap().state_history.len();
+
+        // A permission prompt left over from the turn that stopped
+        registry.process_event(AgentEvent::NeedsAttention {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            message: Some("Bash".into()),
+        });
+        let session = registry.get("s1").unwrap();
+        assert_eq!(session.state, SessionState::Idle);
+        assert_eq!(session.permission_tool, None);
+        assert!(session.seen);
+        assert_eq!(session.state_history.len(), history);
+
+        // A new turn can still ask
+        registry.process_event(AgentEvent::Activity {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+        });
+        registry.process_event(AgentEvent::NeedsAttention {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            message: Some("Bash".into()),
+        });
+        assert_eq!(registry.get("s1").unwrap().state, SessionState::Attention);
+    }
+
     #[test]
     fn compacting_state() {
         let mut registry = SessionRegistry::new();
@@ -485,6 +2155,7 @@ mod tests {
             session_id: "s1".into(),
             cwd: "/tmp".into(),
             agent: AgentType::ClaudeCode,
+            terminal: None,
         });
 
         registry.process_event(AgentEvent::Compacting {
@@ -504,6 +2175,7 @@ mod tests {
             session_id: "s1".into(),
             cwd: "/tmp".into(),
             agent: AgentType::ClaudeCode,
+            terminal: None,
         });
 
         // Start two tools
@@ -513,6 +2185,7 @@ mod tests {
             tool_id: "t1".into(),
             tool_name: "Read".into(),
             tool_label: Some("main.rs".into()),
+            tool_full_label: None,
         });
         registry.process_event(AgentEvent::ToolStarted {
             session_id: "s1".into(),
@@ -520,6 +2193,7 @@ mod tests {
             tool_id: "t2".into(),
             tool_name: "Bash".into(),
             tool_label: Some("cargo build".into()),
+            tool_full_label: None,
         });
 
         let sessions = registry.get_all();
@@ -554,6 +2228,7 @@ mod tests {
             tool_id: "t1".into(),
             tool_name: "Read".into(),
             tool_label: None,
+            tool_full_label: None,
         });
 
         assert_eq!(registry.len(), 1);
@@ -578,6 +2253,7 @@ mod tests {
                 tool_id: "t1".into(),
                 tool_name: "npm".into(),
                 tool_label: Some("npm test".into()),
+                tool_full_label: None,
             },
             AgentType::Codex,
         );
@@ -596,6 +2272,7 @@ mod tests {
             session_id: "s1".into(),
             cwd: "/tmp".into(),
             agent: AgentType::ClaudeCode,
+            terminal: None,
         });
 
         // Start and immediately complete a tool
@@ -605,6 +2282,7 @@ mod tests {
             tool_id: "t1".into(),
             tool_name: "Read".into(),
             tool_label: Some("test.rs".into()),
+            tool_full_label: None,
         });
         registry.process_event(AgentEvent::ToolCompleted {
             session_id: "s1".into(),
@@ -637,6 +2315,7 @@ mod tests {
             session_id: "s1".into(),
             cwd: "/tmp".into(),
             agent: AgentType::ClaudeCode,
+            terminal: None,
         });
 
         // Manually add an expired recent tool
@@ -645,6 +2324,8 @@ mod tests {
             session.recent_tools.push(RecentTool {
                 tool_name: "OldTool".into(),
                 tool_label: None,
+                tool_full_label: None,
+                category: ToolCategory::Other,
                 expires_at: Instant::now() - Duration::from_secs(10),
             });
         }
@@ -664,6 +2345,7 @@ mod tests {
             session_id: "s1".into(),
             cwd: "/tmp".into(),
             agent: AgentType::ClaudeCode,
+            terminal: None,
         });
 
         // Initially no stopped_at
@@ -680,7 +2362,7 @@ mod tests {
         assert!(session.stopped_at.is_some());
 
         // Verify to_info() converts to Unix timestamp
-        let info = session.to_info();
+        let info = session.to_info(&registry.clock);
         assert!(info.stopped_at.is_some());
         assert!(info.stopped_at.unwrap() > 0);
     }
@@ -693,6 +2375,7 @@ mod tests {
             session_id: "s1".into(),
             cwd: "/tmp".into(),
             agent: AgentType::ClaudeCode,
+            terminal: None,
         });
 
         registry.process_event(AgentEvent::NeedsAttention {
@@ -704,7 +2387,7 @@ mod tests {
         let session = registry.sessions.get("s1").unwrap();
         assert_eq!(session.permission_tool, Some("Bash".into()));
 
-        let info = session.to_info();
+        let info = session.to_info(&registry.clock);
         assert_eq!(info.permission_tool, Some("Bash".into()));
     }
 
@@ -716,6 +2399,7 @@ mod tests {
             session_id: "s1".into(),
             cwd: "/tmp".into(),
             agent: AgentType::ClaudeCode,
+            terminal: None,
         });
 
         // Go idle to set stopped_at
@@ -747,6 +2431,7 @@ mod tests {
             session_id: "s1".into(),
             cwd: "/tmp".into(),
             agent: AgentType::ClaudeCode,
+            terminal: None,
         });
 
         // Manually set last_activity to the past
@@ -767,219 +2452,1638 @@ mod tests {
         assert!(session.stale_at.is_some());
 
         // Verify to_info() converts to Unix timestamp
-        let info = session.to_info();
+        let info = session.to_info(&registry.clock);
         assert!(info.stale_at.is_some());
         assert!(info.stale_at.unwrap() > 0);
     }
 
-    #[test]
-    fn waiting_state() {
-        let mut registry = SessionRegistry::new();
+    fn manual_registry() -> (Arc<ManualClock>, SessionRegistry) {
+        let clock = Arc::new(ManualClock::new());
+        let registry = SessionRegistry::with_clock(SharedClock::new(clock.clone()));
+        (clock, registry)
+    }
 
+    #[test]
+    fn stale_promotion_waits_past_the_timeout() {
+        let (clock, mut registry) = manual_registry();
+        let timeout = Duration::from_secs(600);
         registry.process_event(AgentEvent::SessionStarted {
             session_id: "s1".into(),
             cwd: "/tmp".into(),
             agent: AgentType::ClaudeCode,
+            terminal: None,
         });
+        let started = clock.now();
+        assert_eq!(registry.next_stale_at(timeout), Some(started + timeout));
 
-        registry.process_event(AgentEvent::WaitingForInput {
-            session_id: "s1".into(),
-            cwd: "/tmp".into(),
-            message: None,
-        });
-
-        let sessions = registry.get_all();
-        assert_eq!(sessions[0].state, SessionState::Waiting);
-    }
-
-    #[test]
-    fn codex_sessions_stay_when_stale() {
-        let mut registry = SessionRegistry::new();
+        // Exactly at the timeout is still fresh
+        clock.advance(timeout);
+        registry.mark_stale(timeout);
+        assert_eq!(registry.get_all()[0].state, SessionState::Running);
 
-        registry.process_event_from(
-            AgentEvent::SessionStarted {
-                session_id: "codex-1".into(),
-                cwd: "/project".into(),
-                agent: AgentType::Codex,
-            },
-            AgentType::Codex,
+        clock.advance(Duration::from_millis(1));
+        registry.mark_stale(timeout);
+        let info = &registry.get_all()[0];
+        assert_eq!(info.state, SessionState::Stale);
+        assert_eq!(
+            info.stale_at,
+            Some(registry.clock().unix_timestamp(clock.now()))
         );
-
-        // Set last_activity to the past
-        {
-            let session = registry.sessions.get_mut("codex-1").unwrap();
-            session.last_activity = Instant::now() - Duration::from_secs(700);
-        }
-
-        registry.mark_stale(Duration::from_secs(600));
-
-        // Session should still exist but be Stale
-        assert_eq!(registry.len(), 1);
-        let session = registry.sessions.get("codex-1").unwrap();
-        assert_eq!(session.state, SessionState::Stale);
+        assert_eq!(registry.next_stale_at(timeout), None);
     }
 
     #[test]
-    fn next_stale_at_returns_earliest() {
-        let mut registry = SessionRegistry::new();
-
-        // No sessions -> None
-        assert!(registry.next_stale_at(Duration::from_secs(600)).is_none());
-
-        // Add a session
+    fn activity_resets_the_stale_timer_and_demotes_stale() {
+        let (clock, mut registry) = manual_registry();
+        let timeout = Duration::from_secs(600);
         registry.process_event(AgentEvent::SessionStarted {
             session_id: "s1".into(),
             cwd: "/tmp".into(),
             agent: AgentType::ClaudeCode,
+            terminal: None,
         });
 
-        // Should have a future stale time
-        let next = registry.next_stale_at(Duration::from_secs(600));
-        assert!(next.is_some());
-        assert!(next.unwrap() > Instant::now());
+        clock.advance(Duration::from_secs(500));
+        registry.process_event(AgentEvent::Compacting {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+        });
+        clock.advance(Duration::from_secs(500));
+        registry.mark_stale(timeout);
+        assert_eq!(registry.get_all()[0].state, SessionState::Compacting);
 
-        // Stale sessions should be excluded
-        {
-            let session = registry.sessions.get_mut("s1").unwrap();
-            session.state = SessionState::Stale;
-        }
-        assert!(registry.next_stale_at(Duration::from_secs(600)).is_none());
+        clock.advance(Duration::from_secs(101));
+        registry.mark_stale(timeout);
+        assert_eq!(registry.get_all()[0].state, SessionState::Stale);
 
-        // Idle sessions should also be excluded (won't transition to Stale)
-        {
-            let session = registry.sessions.get_mut("s1").unwrap();
-            session.state = SessionState::Idle;
+        registry.process_event(AgentEvent::Activity {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+        });
+        let info = &registry.get_all()[0];
+        assert_eq!(info.state, SessionState::Running);
+        assert_eq!(info.stale_at, None);
+        assert_eq!(registry.next_stale_at(timeout), Some(clock.now() + timeout));
+    }
+
+    #[test]
+    fn idle_and_waiting_never_go_stale() {
+        let (clock, mut registry) = manual_registry();
+        let timeout = Duration::from_secs(600);
+        for id in ["idle", "waiting"] {
+            registry.process_event(AgentEvent::SessionStarted {
+                session_id: id.into(),
+                cwd: format!("/tmp/{id}"),
+                agent: AgentType::ClaudeCode,
+                terminal: None,
+            });
         }
-        assert!(registry.next_stale_at(Duration::from_secs(600)).is_none());
+        registry.process_event(AgentEvent::Idle {
+            session_id: "idle".into(),
+            cwd: "/tmp/idle".into(),
+        });
+        registry.process_event(AgentEvent::WaitingForInput {
+            session_id: "waiting".into(),
+            cwd: "/tmp/waiting".into(),
+            message: None,
+        });
+        assert_eq!(registry.next_stale_at(timeout), None);
 
-        // Waiting sessions should also be excluded
-        {
-            let session = registry.sessions.get_mut("s1").unwrap();
-            session.state = SessionState::Waiting;
+        clock.advance(Duration::from_secs(24 * 3600));
+        registry.mark_stale(timeout);
+        for session in registry.get_all() {
+            assert_ne!(session.state, SessionState::Stale, "{}", session.session_id);
         }
-        assert!(registry.next_stale_at(Duration::from_secs(600)).is_none());
     }
 
     #[test]
-    fn session_name_update_flow() {
-        let mut registry = SessionRegistry::new();
+    fn completed_tools_expire_on_the_clock() {
+        let (clock, mut registry) = manual_registry();
+        registry.process_event(AgentEvent::ToolStarted {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            tool_id: "t1".into(),
+            tool_name: "Read".into(),
+            tool_label: None,
+            tool_full_label: None,
+        });
+        registry.process_event(AgentEvent::ToolCompleted {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            tool_id: "t1".into(),
+        });
+        assert_eq!(
+            registry.next_tool_expiry(),
+            Some(clock.now() + MIN_TOOL_DISPLAY)
+        );
+        assert_eq!(registry.get_all()[0].running_tools.len(), 1);
+
+        clock.advance(MIN_TOOL_DISPLAY);
+        assert!(registry.get_all()[0].running_tools.is_empty());
+        assert_eq!(registry.next_tool_expiry(), None);
+    }
 
+    #[test]
+    fn last_activity_follows_the_latest_event() {
+        let (clock, mut registry) = manual_registry();
         registry.process_event(AgentEvent::SessionStarted {
             session_id: "s1".into(),
             cwd: "/tmp".into(),
             agent: AgentType::ClaudeCode,
+            terminal: None,
         });
+        let started = registry.get_all()[0].last_activity_at.unwrap();
 
-        // Initially no name
-        let sessions = registry.get_all();
-        assert!(sessions[0].name.is_none());
-
-        // Update name
-        registry.process_event(AgentEvent::SessionNameUpdated {
+        clock.advance(Duration::from_secs(90));
+        registry.process_event(AgentEvent::Idle {
             session_id: "s1".into(),
-            name: "fix login bug".into(),
+            cwd: "/tmp".into(),
         });
-
-        let sessions = registry.get_all();
-        assert_eq!(sessions[0].name, Some("fix login bug".into()));
+        assert_eq!(registry.get_all()[0].last_activity_at, Some(started + 90));
     }
 
     #[test]
-    fn compacting_returns_to_running_on_activity() {
-        let mut registry = SessionRegistry::new();
-
-        registry.process_event(AgentEvent::SessionStarted {
+    fn state_changed_at_moves_only_on_transitions() {
+        let (clock, mut registry) = manual_registry();
+        registry.process_event(AgentEvent::NeedsAttention {
             session_id: "s1".into(),
             cwd: "/tmp".into(),
-            agent: AgentType::ClaudeCode,
+            message: None,
         });
+        let entered = registry.get_all()[0].state_changed_at.unwrap();
 
-        registry.process_event(AgentEvent::Compacting {
+        // Still asking: the state is unchanged, so is its start
+        clock.advance(Duration::from_secs(60));
+        registry.process_event(AgentEvent::Activity {
             session_id: "s1".into(),
             cwd: "/tmp".into(),
         });
-        assert_eq!(registry.get_all()[0].state, SessionState::Compacting);
+        assert_eq!(registry.get_all()[0].state_changed_at, Some(entered));
 
-        // Tool start should return to Running
-        registry.process_event(AgentEvent::ToolStarted {
+        clock.advance(Duration::from_secs(3600));
+        registry.mark_stale(Duration::from_secs(600));
+        let stale = &registry.get_all()[0];
+        assert_eq!(stale.state, SessionState::Stale);
+        assert_eq!(stale.state_changed_at, Some(entered + 3660));
+
+        clock.advance(Duration::from_secs(5));
+        registry.process_event(AgentEvent::Idle {
             session_id: "s1".into(),
             cwd: "/tmp".into(),
-            tool_id: "t1".into(),
-            tool_name: "Read".into(),
-            tool_label: None,
         });
-        assert_eq!(registry.get_all()[0].state, SessionState::Running);
+        assert_eq!(registry.get_all()[0].state_changed_at, Some(entered + 3665));
+
+        // Every change is kept in the history
+        let history: Vec<_> = registry.get_all()[0]
+            .state_history
+            .iter()
+            .map(|change| (change.state, change.at - entered))
+            .collect();
+        assert_eq!(
+            history,
+            [
+                (SessionState::Running, 0),
+                (SessionState::Attention, 0),
+                (SessionState::Stale, 3660),
+                (SessionState::Idle, 3665),
+            ]
+        );
     }
 
     #[test]
-    fn stale_to_running_on_activity() {
+    fn gc_archives_long_stale_sessions_and_evicts_past_the_cap() {
+        let (clock, mut registry) = manual_registry();
+        let timeout = Duration::from_secs(600);
+        registry.set_retention(Retention {
+            remove_stale_after: Some(Duration::from_secs(2 * 3600)),
+            max_sessions: 3,
+        });
+        for id in ["older", "old", "live"] {
+            registry.process_event(AgentEvent::SessionStarted {
+                session_id: id.into(),
+                cwd: format!("/tmp/{id}"),
+                agent: AgentType::ClaudeCode,
+                terminal: None,
+            });
+            clock.advance(Duration::from_secs(10));
+        }
+        registry.archive_session("older");
+        clock.advance(Duration::from_secs(591));
+        registry.process_event(AgentEvent::Activity {
+            session_id: "live".into(),
+            cwd: "/tmp/live".into(),
+        });
+        registry.mark_stale(timeout);
+        assert_eq!(registry.collect_garbage(), GcStats::default());
+        let due = registry.next_gc_at().unwrap();
+        assert_eq!(due, clock.now() + Duration::from_secs(2 * 3600));
+
+        clock.advance(Duration::from_secs(2 * 3600));
+        registry.process_event(AgentEvent::SessionStarted {
+            session_id: "new".into(),
+            cwd: "/tmp/new".into(),
+            agent: AgentType::ClaudeCode,
+            terminal: None,
+        });
+        let stats = registry.collect_garbage();
+        // "old" went stale 2h ago; "older" was archived first, so it's evicted
+        assert_eq!(
+            stats,
+            GcStats {
+                archived: 1,
+                evicted: 1
+            }
+        );
+        assert!(registry.sessions.get("older").is_none());
+        assert!(registry.sessions["old"].archived);
+        assert!(registry.sessions.contains_key("live"));
+        assert_eq!(registry.next_gc_at(), None);
+        assert_eq!(registry.gc_totals(), stats);
+    }
+
+    #[test]
+    fn gc_never_evicts_live_sessions() {
+        let (_clock, mut registry) = manual_registry();
+        registry.set_retention(Retention {
+            remove_stale_after: None,
+            max_sessions: 1,
+        });
+        for id in ["a", "b"] {
+            registry.process_event(AgentEvent::SessionStarted {
+                session_id: id.into(),
+                cwd: format!("/tmp/{id}"),
+                agent: AgentType::ClaudeCode,
+                terminal: None,
+            });
+        }
+        assert_eq!(registry.collect_garbage(), GcStats::default());
+        assert_eq!(registry.get_all().len(), 2);
+    }
+
+    #[test]
+    fn metadata_before_session_started_is_applied_once_it_starts() {
         let mut registry = SessionRegistry::new();
+        registry.process_event(AgentEvent::TranscriptPath {
+            session_id: "s1".into(),
+            path: "/tmp/s1.jsonl".into(),
+        });
+        registry.process_event(AgentEvent::SessionNameUpdated {
+            session_id: "s1".into(),
+            name: "fix login".into(),
+        });
+        assert!(registry.get_all().is_empty());
 
         registry.process_event(AgentEvent::SessionStarted {
             session_id: "s1".into(),
             cwd: "/tmp".into(),
             agent: AgentType::ClaudeCode,
+            terminal: None,
         });
+        let info = &registry.get_all()[0];
+        assert_eq!(info.name.as_deref(), Some("fix login"));
+        assert_eq!(info.transcript_path.as_deref(), Some("/tmp/s1.jsonl"));
+        assert!(registry.pending.is_empty());
+    }
 
-        // Make it stale
-        {
-            let session = registry.sessions.get_mut("s1").unwrap();
-            session.last_activity = Instant::now() - Duration::from_secs(700);
-        }
-        registry.mark_stale(Duration::from_secs(600));
-        assert_eq!(registry.get_all()[0].state, SessionState::Stale);
+    #[test]
+    fn rollout_events_before_meta_get_the_real_cwd() {
+        let mut registry = SessionRegistry::new();
+        registry.process_event_from(
+            AgentEvent::ToolStarted {
+                session_id: "cx1".into(),
+                cwd: String::new(),
+                tool_id: "c1".into(),
+                tool_name: "Bash".into(),
+                tool_label: None,
+                tool_full_label: None,
+            },
+            AgentType::Codex,
+        );
+        assert!(registry.get_all().is_empty());
 
-        // Activity should return to Running
-        registry.process_event(AgentEvent::Activity {
+        registry.process_event_from(
+            AgentEvent::SessionStarted {
+                session_id: "cx1".into(),
+                cwd: "/code/api".into(),
+                agent: AgentType::Codex,
+                terminal: None,
+            },
+            AgentType::Codex,
+        );
+        let info = &registry.get_all()[0];
+        assert_eq!(info.cwd, "/code/api");
+        assert_eq!(info.state, SessionState::Running);
+        assert_eq!(info.running_tools.len(), 1);
+    }
+
+    #[test]
+    fn late_registration_applies_held_events_first() {
+        let mut registry = SessionRegistry::new();
+        registry.process_event(AgentEvent::FilesTouched {
+            session_id: "s1".into(),
+            changes: vec![FileChange {
+                path: "/tmp/a.rs".into(),
+                added: 3,
+                removed: 1,
+            }],
+        });
+        // A hook that raced ahead of SessionStart
+        registry.process_event(AgentEvent::Idle {
             session_id: "s1".into(),
             cwd: "/tmp".into(),
         });
-        assert_eq!(registry.get_all()[0].state, SessionState::Running);
+        let info = &registry.get_all()[0];
+        assert_eq!(info.state, SessionState::Idle);
+        assert_eq!(info.changed_files.len(), 1);
     }
 
     #[test]
-    fn recent_activity_tracking() {
+    fn held_events_expire_into_late_registration() {
+        let (clock, mut registry) = manual_registry();
+        registry.process_event(AgentEvent::SessionTagged {
+            session_id: "s1".into(),
+            tags: vec!["lost".into()],
+        });
+        registry.process_event(AgentEvent::Activity {
+            session_id: "s1".into(),
+            cwd: String::new(),
+        });
+        assert_eq!(
+            registry.next_pending_expiry(),
+            Some(clock.now() + PENDING_TTL)
+        );
+
+        clock.advance(PENDING_TTL);
+        registry.expire_pending();
+        let sessions = registry.get_all();
+        assert_eq!(sessions.len(), 1);
+        // The tag had no session to land on
+        assert!(sessions[0].tags.is_empty());
+        assert_eq!(registry.next_pending_expiry(), None);
+    }
+
+    #[test]
+    fn session_ended_discards_held_events() {
         let mut registry = SessionRegistry::new();
+        registry.process_event(AgentEvent::SessionNameUpdated {
+            session_id: "s1".into(),
+            name: "gone".into(),
+        });
+        registry.process_event(AgentEvent::SessionEnded {
+            session_id: "s1".into(),
+        });
+        assert!(registry.pending.is_empty());
+        assert!(registry.get_all().is_empty());
+    }
 
-        registry.process_event(AgentEvent::SessionStarted {
+    #[test]
+    fn stamped_events_apply_in_firing_order() {
+        let (clock, mut registry) = manual_registry();
+        start(&mut registry, "s1", "/tmp");
+        let fired = registry.now_unix_ms();
+        let stamp = |offset: u64, seq: u32| {
+            Some(EventStamp {
+                sent_at_ms: fired + offset,
+                seq,
+            })
+        };
+        // PostToolUse's hook connects before the slower PreToolUse's
+        let completed = AgentEvent::ToolCompleted {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            tool_id: "t1".into(),
+        };
+        registry.process_stamped(completed, AgentType::ClaudeCode, stamp(20, 0));
+        let started = AgentEvent::ToolStarted {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            tool_id: "t1".into(),
+            tool_name: "Bash".into(),
+            tool_label: None,
+            tool_full_label: None,
+        };
+        registry.process_stamped(started, AgentType::ClaudeCode, stamp(0, 0));
+        assert!(registry.get("s1").unwrap().recent_activity.is_empty());
+        assert_eq!(
+            registry.next_reorder_release(),
+            Some(clock.now() + REORDER_WINDOW)
+        );
+
+        // Unstamped events don't wait
+        registry.process_stamped(
+            AgentEvent::SessionNameUpdated {
+                session_id: "s1".into(),
+                name: "now".into(),
+            },
+            AgentType::ClaudeCode,
+            None,
+        );
+        assert_eq!(registry.get("s1").unwrap().name.as_deref(), Some("now"));
+
+        clock.advance(REORDER_WINDOW + Duration::from_millis(20));
+        registry.release_reordered();
+        let info = registry.get("s1").unwrap();
+        assert!(info.running_tools.is_empty());
+        assert_eq!(info.recent_activity, vec!["Bash".to_string()]);
+        assert_eq!(registry.next_reorder_release(), None);
+
+        // Stamped long ago: applied right away
+        registry.process_stamped(
+            AgentEvent::Idle {
+                session_id: "s1".into(),
+                cwd: "/tmp".into(),
+            },
+            AgentType::ClaudeCode,
+            stamp(0, 1),
+        );
+        assert_eq!(registry.get("s1").unwrap().state, SessionState::Idle);
+    }
+
+    #[test]
+    fn retried_tool_hooks_apply_once() {
+        let (clock, mut registry) = manual_registry();
+        start(&mut registry, "s1", "/tmp");
+        let pre = AgentEvent::ToolStarted {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            tool_id: "t1".into(),
+            tool_name: "Bash".into(),
+            tool_label: None,
+            tool_full_label: None,
+        };
+        let post = AgentEvent::ToolCompleted {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            tool_id: "t1".into(),
+        };
+        registry.process_event(pre.clone());
+        registry.process_event(post.clone());
+        // A retried PreToolUse doesn't start the finished tool again
+        registry.process_event(pre);
+        assert!(registry.get("s1").unwrap().running_tools.is_empty());
+        // Nor a retried PostToolUse wake the session after it stopped
+        registry.process_event(AgentEvent::Idle {
             session_id: "s1".into(),
             cwd: "/tmp".into(),
-            agent: AgentType::ClaudeCode,
         });
+        clock.advance(Duration::from_secs(DEDUP_BUCKET_SECS));
+        registry.process_event(post.clone());
+        assert_eq!(registry.get("s1").unwrap().state, SessionState::Idle);
 
-        // Complete tools to build activity
-        for i in 0..3 {
+        // Codex starts an approved call a second time
+        registry.process_event_from(
+            AgentEvent::ToolStarted {
+                session_id: "s1".into(),
+                cwd: "/tmp".into(),
+                tool_id: "t1".into(),
+                tool_name: "Bash".into(),
+                tool_label: None,
+                tool_full_label: None,
+            },
+            AgentType::Codex,
+        );
+        assert_eq!(registry.get("s1").unwrap().running_tools.len(), 1);
+
+        // Long after, the same delivery counts again
+        clock.advance(Duration::from_secs(DEDUP_BUCKET_SECS * 2));
+        registry.process_event(post);
+        assert!(registry.get("s1").unwrap().running_tools.is_empty());
+        assert!(registry.delivered.iter().all(|key| key.kind == "completed"));
+    }
+
+    #[test]
+    fn tool_hooks_without_an_id_are_never_dropped() {
+        let (_clock, mut registry) = manual_registry();
+        start(&mut registry, "s1", "/tmp");
+        for tool_name in ["Read", "Bash"] {
             registry.process_event(AgentEvent::ToolStarted {
                 session_id: "s1".into(),
                 cwd: "/tmp".into(),
-                tool_id: format!("t{}", i),
-                tool_name: "Read".into(),
-                tool_label: Some(format!("file{}.rs", i)),
+                tool_id: crate::UNKNOWN_TOOL_ID.into(),
+                tool_name: tool_name.into(),
+                tool_label: None,
+                tool_full_label: None,
             });
+            let running = &registry.sessions["s1"].running_tools;
+            assert_eq!(running.len(), 1);
+            assert_eq!(running[0].tool_name, tool_name);
             registry.process_event(AgentEvent::ToolCompleted {
                 session_id: "s1".into(),
                 cwd: "/tmp".into(),
-                tool_id: format!("t{}", i),
+                tool_id: crate::UNKNOWN_TOOL_ID.into(),
             });
         }
+        assert!(registry.sessions["s1"].running_tools.is_empty());
+        assert_eq!(
+            registry.get("s1").unwrap().recent_activity,
+            ["Read", "Bash"]
+        );
+        assert!(registry.delivered.is_empty());
+    }
 
+    #[test]
+    fn filtered_sessions_never_register() {
+        let mut registry = SessionRegistry::new();
+        registry.set_filters(
+            serde_json::from_str(r#"[{ "action": "allow", "cwd": "/code/*" }]"#).unwrap(),
+        );
+        registry.process_event(AgentEvent::SessionNameUpdated {
+            session_id: "other".into(),
+            name: "held".into(),
+        });
+        for (session_id, cwd) in [("other", "/home/bob/app"), ("mine", "/code/aura")] {
+            registry.process_event(AgentEvent::SessionStarted {
+                session_id: session_id.into(),
+                cwd: cwd.into(),
+                agent: AgentType::ClaudeCode,
+                terminal: None,
+            });
+        }
         let sessions = registry.get_all();
-        assert_eq!(sessions[0].recent_activity.len(), 3);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].session_id, "mine");
+        assert!(registry.pending.is_empty());
     }
 
     #[test]
-    fn remove_session_from_registry() {
+    fn waiting_state() {
         let mut registry = SessionRegistry::new();
 
         registry.process_event(AgentEvent::SessionStarted {
             session_id: "s1".into(),
             cwd: "/tmp".into(),
             agent: AgentType::ClaudeCode,
+            terminal: None,
         });
-        assert_eq!(registry.len(), 1);
 
-        registry.remove_session("s1");
-        assert_eq!(registry.len(), 0);
-        assert!(registry.is_empty());
+        registry.process_event(AgentEvent::WaitingForInput {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            message: None,
+        });
+
+        let sessions = registry.get_all();
+        assert_eq!(sessions[0].state, SessionState::Waiting);
+    }
+
+    #[test]
+    fn codex_sessions_stay_when_stale() {
+        let mut registry = SessionRegistry::new();
+
+        registry.process_event_from(
+            AgentEvent::SessionStarted {
+                session_id: "codex-1".into(),
+                cwd: "/project".into(),
+                agent: AgentType::Codex,
+                terminal: None,
+            },
+            AgentType::Codex,
+        );
+
+        // Set last_activity to the past
+        {
+            let session = registry.sessions.get_mut("codex-1").unwrap();
+            session.last_activity = Instant::now() - Duration::from_secs(700);
+        }
+
+        registry.mark_stale(Duration::from_secs(600));
+
+        // Session should still exist but be Stale
+        assert_eq!(registry.len(), 1);
+        let session = registry.sessions.get("codex-1").unwrap();
+        assert_eq!(session.state, SessionState::Stale);
+    }
+
+    #[test]
+    fn next_stale_at_returns_earliest() {
+        let mut registry = SessionRegistry::new();
+
+        // No sessions -> None
+        assert!(registry.next_stale_at(Duration::from_secs(600)).is_none());
+
+        // Add a session
+        registry.process_event(AgentEvent::SessionStarted {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            agent: AgentType::ClaudeCode,
+            terminal: None,
+        });
+
+        // Should have a future stale time
+        let next = registry.next_stale_at(Duration::from_secs(600));
+        assert!(next.is_some());
+        assert!(next.unwrap() > Instant::now());
+
+        // Stale sessions should be excluded
+        {
+            let session = registry.sessions.get_mut("s1").unwrap();
+            session.state = SessionState::Stale;
+        }
+        assert!(registry.next_stale_at(Duration::from_secs(600)).is_none());
+
+        // Idle sessions should also be excluded (won't transition to Stale)
+        {
+            let session = registry.sessions.get_mut("s1").unwrap();
+            session.state = SessionState::Idle;
+        }
+        assert!(registry.next_stale_at(Duration::from_secs(600)).is_none());
+
+        // Waiting sessions should also be excluded
+        {
+            let session = registry.sessions.get_mut("s1").unwrap();
+            session.state = SessionState::Waiting;
+        }
+        assert!(registry.next_stale_at(Duration::from_secs(600)).is_none());
+    }
+
+    #[test]
+    fn session_name_update_flow() {
+        let mut registry = SessionRegistry::new();
+
+        registry.process_event(AgentEvent::SessionStarted {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            agent: AgentType::ClaudeCode,
+            terminal: None,
+        });
+
+        // Initially no name
+        let sessions = registry.get_all();
+        assert!(sessions[0].name.is_none());
+
+        // Update name
+        registry.process_event(AgentEvent::SessionNameUpdated {
+            session_id: "s1".into(),
+            name: "fix login bug".into(),
+        });
+
+        let sessions = registry.get_all();
+        assert_eq!(sessions[0].name, Some("fix login bug".into()));
+    }
+
+    #[test]
+    fn compacting_returns_to_running_on_activity() {
+        let mut registry = SessionRegistry::new();
+
+        registry.process_event(AgentEvent::SessionStarted {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            agent: AgentType::ClaudeCode,
+            terminal: None,
+        });
+
+        registry.process_event(AgentEvent::Compacting {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+        });
+        assert_eq!(registry.get_all()[0].state, SessionState::Compacting);
+
+        // Tool start should return to Running
+        registry.process_event(AgentEvent::ToolStarted {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            tool_id: "t1".into(),
+            tool_name: "Read".into(),
+            tool_label: None,
+            tool_full_label: None,
+        });
+        assert_eq!(registry.get_all()[0].state, SessionState::Running);
+    }
+
+    #[test]
+    fn stale_to_running_on_activity() {
+        let mut registry = SessionRegistry::new();
+
+        registry.process_event(AgentEvent::SessionStarted {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            agent: AgentType::ClaudeCode,
+            terminal: None,
+        });
+
+        // Make it stale
+        {
+            let session = registry.sessions.get_mut("s1").unwrap();
+            session.last_activity = Instant::now() - Duration::from_secs(700);
+        }
+        registry.mark_stale(Duration::from_secs(600));
+        assert_eq!(registry.get_all()[0].state, SessionState::Stale);
+
+        // Activity should return to Running
+        registry.process_event(AgentEvent::Activity {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+        });
+        assert_eq!(registry.get_all()[0].state, SessionState::Running);
+    }
+
+    #[test]
+    fn recent_activity_tracking() {
+        let mut registry = SessionRegistry::new();
+
+        registry.process_event(AgentEvent::SessionStarted {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            agent: AgentType::ClaudeCode,
+            terminal: None,
+        });
+
+        // Complete tools to build activity
+        for i in 0..3 {
+            registry.process_event(AgentEvent::ToolStarted {
+                session_id: "s1".into(),
+                cwd: "/tmp".into(),
+                tool_id: format!("t{}", i),
+                tool_name: "Read".into(),
+                tool_label: Some(format!("file{}.rs", i)),
+                tool_full_label: None,
+            });
+            registry.process_event(AgentEvent::ToolCompleted {
+                session_id: "s1".into(),
+                cwd: "/tmp".into(),
+                tool_id: format!("t{}", i),
+            });
+        }
+
+        let sessions = registry.get_all();
+        assert_eq!(sessions[0].recent_activity.len(), 3);
+    }
+
+    #[test]
+    fn remove_session_from_registry() {
+        let mut registry = SessionRegistry::new();
+
+        registry.process_event(AgentEvent::SessionStarted {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            agent: AgentType::ClaudeCode,
+            terminal: None,
+        });
+        assert_eq!(registry.len(), 1);
+
+        registry.remove_session("s1");
+        assert_eq!(registry.len(), 0);
+        assert!(registry.is_empty());
+    }
+
+    #[test]
+    fn archive_hides_session_until_restored() {
+        let mut registry = SessionRegistry::new();
+        registry.process_event(AgentEvent::SessionStarted {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            agent: AgentType::ClaudeCode,
+            terminal: None,
+        });
+
+        assert!(registry.archive_session("s1"));
+        assert!(registry.get_all().is_empty());
+        assert_eq!(registry.get_archived().len(), 1);
+        assert!(registry.get_archived()[0].archived);
+        assert!(registry.has_session("s1"));
+
+        // Events keep updating archived sessions without unhiding them
+        registry.process_event(AgentEvent::Idle {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+        });
+        assert!(registry.get_all().is_empty());
+        assert_eq!(registry.get("s1").unwrap().state, SessionState::Idle);
+
+        assert!(registry.restore_session("s1"));
+        assert_eq!(registry.get_all().len(), 1);
+        assert!(registry.get_archived().is_empty());
+
+        assert!(!registry.archive_session("missing"));
+        assert!(!registry.restore_session("missing"));
+    }
+
+    #[test]
+    fn session_started_restores_archived_session() {
+        let mut registry = SessionRegistry::new();
+        let started = || AgentEvent::SessionStarted {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            agent: AgentType::ClaudeCode,
+            terminal: None,
+        };
+        registry.process_event(started());
+        registry.archive_session("s1");
+
+        registry.process_event(started());
+        assert_eq!(registry.get_all().len(), 1);
+    }
+
+    #[test]
+    fn session_started_keeps_the_reported_terminal() {
+        let mut registry = SessionRegistry::new();
+        let terminal = crate::TerminalInfo {
+            tmux_pane: Some("%3".into()),
+            ..Default::default()
+        };
+        let started = |terminal| AgentEvent::SessionStarted {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            agent: AgentType::ClaudeCode,
+            terminal,
+        };
+        registry.process_event(started(Some(terminal.clone())));
+        // A resume that couldn't tell doesn't forget it
+        registry.process_event(started(None));
+        assert_eq!(registry.get_all()[0].terminal, Some(terminal));
+    }
+
+    #[test]
+    fn cpu_loads_replace_the_previous_sample() {
+        let mut registry = SessionRegistry::new();
+        for id in ["busy", "quiet"] {
+            registry.process_event(AgentEvent::SessionStarted {
+                session_id: id.into(),
+                cwd: format!("/tmp/{id}"),
+                agent: AgentType::ClaudeCode,
+                terminal: None,
+            });
+        }
+        registry.set_cpu_loads(&HashMap::from([("busy".to_string(), 140)]));
+        let load =
+            |registry: &SessionRegistry, id: &str| registry.get(id).and_then(|s| s.cpu_percent);
+        assert_eq!(load(&registry, "busy"), Some(140));
+        assert_eq!(load(&registry, "quiet"), None);
+
+        registry.set_cpu_loads(&HashMap::new());
+        assert_eq!(load(&registry, "busy"), None);
+    }
+
+    #[test]
+    fn bulk_clear_idle_and_stale() {
+        let mut registry = SessionRegistry::new();
+        for id in ["idle", "stale", "running"] {
+            registry.process_event(AgentEvent::SessionStarted {
+                session_id: id.into(),
+                cwd: "/tmp".into(),
+                agent: AgentType::ClaudeCode,
+                terminal: None,
+            });
+        }
+        registry.process_event(AgentEvent::Idle {
+            session_id: "idle".into(),
+            cwd: "/tmp".into(),
+        });
+        registry.sessions.get_mut("stale").unwrap().state = SessionState::Stale;
+
+        assert_eq!(registry.apply_bulk(BulkAction::ClearIdle), 1);
+        assert_eq!(registry.apply_bulk(BulkAction::ClearStale), 1);
+        // Already archived sessions aren't counted again
+        assert_eq!(registry.apply_bulk(BulkAction::ClearIdle), 0);
+
+        let visible = registry.get_all();
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible[0].session_id, "running");
+        assert_eq!(registry.get_archived().len(), 2);
+    }
+
+    #[test]
+    fn bulk_mark_all_seen_until_next_prompt() {
+        let mut registry = SessionRegistry::new();
+        registry.process_event(AgentEvent::NeedsAttention {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            message: None,
+        });
+        registry.process_event(AgentEvent::WaitingForInput {
+            session_id: "s2".into(),
+            cwd: "/tmp".into(),
+            message: None,
+        });
+        registry.process_event(AgentEvent::Activity {
+            session_id: "s3".into(),
+            cwd: "/tmp".into(),
+        });
+
+        assert_eq!(registry.apply_bulk(BulkAction::MarkAllSeen), 2);
+        assert!(registry.get("s1").unwrap().seen);
+        assert!(registry.get("s2").unwrap().seen);
+        assert!(!registry.get("s3").unwrap().seen);
+        assert_eq!(registry.get("s1").unwrap().state, SessionState::Attention);
+
+        // A new prompt needs to be seen again
+        registry.process_event(AgentEvent::NeedsAttention {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            message: None,
+        });
+        assert!(!registry.get("s1").unwrap().seen);
+    }
+
+    #[test]
+    fn mark_seen_keeps_state() {
+        let mut registry = SessionRegistry::new();
+        registry.process_event(AgentEvent::NeedsAttention {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            message: None,
+        });
+        registry.process_event(AgentEvent::Activity {
+            session_id: "s2".into(),
+            cwd: "/tmp".into(),
+        });
+
+        assert!(registry.mark_seen("s1"));
+        let session = registry.get("s1").unwrap();
+        assert!(session.seen);
+        assert_eq!(session.state, SessionState::Attention);
+
+        // Nothing to acknowledge while running
+        assert!(registry.mark_seen("s2"));
+        assert!(!registry.get("s2").unwrap().seen);
+        assert!(!registry.mark_seen("missing"));
+    }
+
+    #[test]
+    fn idle_sessions_are_unseen_until_marked() {
+        let mut registry = SessionRegistry::new();
+        let idle = |registry: &mut SessionRegistry| {
+            registry.process_event(AgentEvent::Idle {
+                session_id: "s1".into(),
+                cwd: "/tmp".into(),
+            })
+        };
+        idle(&mut registry);
+        assert!(!registry.get("s1").unwrap().seen);
+
+        assert_eq!(registry.apply_bulk(BulkAction::MarkAllSeen), 1);
+        assert!(registry.get("s1").unwrap().seen);
+
+        // Finishing another turn needs a new review
+        idle(&mut registry);
+        assert!(!registry.get("s1").unwrap().seen);
+    }
+
+    fn start(registry: &mut SessionRegistry, session_id: &str, cwd: &str) {
+        registry.process_event(AgentEvent::SessionStarted {
+            session_id: session_id.into(),
+            cwd: cwd.into(),
+            agent: AgentType::ClaudeCode,
+            terminal: None,
+        });
+    }
+
+    fn display_ids(registry: &SessionRegistry) -> Vec<String> {
+        registry
+            .get_all()
+            .into_iter()
+            .map(|s| s.session_id)
+            .collect()
+    }
+
+    #[test]
+    fn get_all_sorts_by_recent_activity() {
+        let mut registry = SessionRegistry::new();
+        start(&mut registry, "old", "/a");
+        start(&mut registry, "new", "/b");
+        registry.sessions.get_mut("old").unwrap().last_activity =
+            Instant::now() - Duration::from_secs(60);
+
+        assert_eq!(display_ids(&registry), ["new", "old"]);
+    }
+
+    #[test]
+    fn manual_order_before_activity_order() {
+        let mut registry = SessionRegistry::new();
+        start(&mut registry, "a", "/a");
+        start(&mut registry, "b", "/b");
+        start(&mut registry, "c", "/c");
+        registry.set_manual_order(vec!["/c".into(), "/a".into()]);
+
+        // Unordered project "/b" falls back to activity order after the manual ones
+        assert_eq!(display_ids(&registry), ["c", "a", "b"]);
+    }
+
+    #[test]
+    fn move_session_to_reorders_projects() {
+        let mut registry = SessionRegistry::new();
+        start(&mut registry, "a", "/a");
+        start(&mut registry, "b", "/b");
+        start(&mut registry, "c", "/c");
+        registry.set_manual_order(vec!["/a".into(), "/b".into(), "/c".into(), "/gone".into()]);
+
+        // Drag down: "a" takes "c"'s place
+        assert!(registry.move_session_to("a", "c"));
+        assert_eq!(display_ids(&registry), ["b", "c", "a"]);
+
+        // Drag up: "a" takes "b"'s place
+        assert!(registry.move_session_to("a", "b"));
+        assert_eq!(display_ids(&registry), ["a", "b", "c"]);
+
+        // Projects without visible sessions stay in the persisted order
+        assert_eq!(registry.manual_order().last().unwrap(), "/gone");
+
+        assert!(!registry.move_session_to("a", "a"));
+        assert!(!registry.move_session_to("a", "missing"));
+    }
+
+    #[test]
+    fn snooze_and_unsnooze_session() {
+        let mut registry = SessionRegistry::new();
+
+        registry.process_event(AgentEvent::SessionStarted {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            agent: AgentType::ClaudeCode,
+            terminal: None,
+        });
+
+        assert!(registry.snooze_session("s1", Duration::from_secs(60)));
+        let info = &registry.get_all()[0];
+        assert!(info.snoozed_until.is_some());
+        assert!(info.is_snoozed());
+
+        // Snooze survives further events
+        registry.process_event(AgentEvent::NeedsAttention {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            message: None,
+        });
+        assert!(registry.get_all()[0].is_snoozed());
+
+        assert!(registry.unsnooze_session("s1"));
+        assert!(registry.get_all()[0].snoozed_until.is_none());
+
+        assert!(!registry.snooze_session("missing", Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn expired_snooze_not_reported() {
+        let mut registry = SessionRegistry::new();
+
+        registry.process_event(AgentEvent::SessionStarted {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            agent: AgentType::ClaudeCode,
+            terminal: None,
+        });
+        registry.sessions.get_mut("s1").unwrap().snoozed_until =
+            Some(Instant::now() - Duration::from_secs(1));
+
+        assert!(registry.get_all()[0].snoozed_until.is_none());
+    }
+
+    #[test]
+    fn resolve_session_id_by_prefix() {
+        let mut registry = SessionRegistry::new();
+
+        for id in ["abc123", "abd456"] {
+            registry.process_event(AgentEvent::SessionStarted {
+                session_id: id.into(),
+                cwd: "/tmp".into(),
+                agent: AgentType::ClaudeCode,
+                terminal: None,
+            });
+        }
+
+        assert_eq!(registry.resolve_session_id("abc123"), Some("abc123".into()));
+        assert_eq!(registry.resolve_session_id("abd"), Some("abd456".into()));
+        // Ambiguous prefix
+        assert_eq!(registry.resolve_session_id("ab"), None);
+        assert_eq!(registry.resolve_session_id("zzz"), None);
+        assert_eq!(registry.resolve_session_id(""), None);
+    }
+
+    #[test]
+    fn rename_session_sets_name() {
+        let mut registry = SessionRegistry::new();
+        start(&mut registry, "s1", "/tmp/aura");
+
+        assert!(registry.rename_session("s1", "fix login"));
+        assert_eq!(
+            registry.get("s1").unwrap().name.as_deref(),
+            Some("fix login")
+        );
+        assert!(!registry.rename_session("missing", "x"));
+    }
+
+    #[test]
+    fn transcript_path_recorded() {
+        let mut registry = SessionRegistry::new();
+        start(&mut registry, "s1", "/tmp/aura");
+
+        registry.process_event(AgentEvent::TranscriptPath {
+            session_id: "s1".into(),
+            path: "/tmp/s1.jsonl".into(),
+        });
+
+        assert_eq!(
+            registry.get("s1").unwrap().transcript_path.as_deref(),
+            Some("/tmp/s1.jsonl")
+        );
+    }
+
+    #[test]
+    fn plan_updates_set_and_clear_the_subtitle() {
+        let mut registry = SessionRegistry::new();
+        start(&mut registry, "s1", "/tmp/aura");
+
+        registry.process_event(AgentEvent::PlanUpdated {
+            session_id: "s1".into(),
+            progress: PlanProgress::from_steps([
+                ("completed", "read config"),
+                ("in_progress", "migrate config loader"),
+            ]),
+        });
+        assert_eq!(
+            registry.get("s1").unwrap().subtitle.as_deref(),
+            Some("1/2 · migrate config loader")
+        );
+
+        registry.process_event(AgentEvent::PlanUpdated {
+            session_id: "s1".into(),
+            progress: PlanProgress::default(),
+        });
+        assert_eq!(registry.get("s1").unwrap().subtitle, None);
+        assert_eq!(registry.get("s1").unwrap().progress, None);
+    }
+
+    #[test]
+    fn tools_are_categorized_with_config_overrides() {
+        let mut registry = SessionRegistry::new();
+        registry.set_tool_categories(ToolCategories::new(&BTreeMap::from([(
+            "mcp__linear__*".to_string(),
+            ToolCategory::Vcs,
+        )])));
+        start(&mut registry, "s1", "/tmp/aura");
+        for (tool_id, tool_name) in [("t1", "Bash"), ("t2", "mcp__linear__create_issue")] {
+            registry.process_event(AgentEvent::ToolStarted {
+                session_id: "s1".into(),
+                cwd: "/tmp/aura".into(),
+                tool_id: tool_id.into(),
+                tool_name: tool_name.into(),
+                tool_label: None,
+                tool_full_label: None,
+            });
+        }
+        let categories: Vec<_> = registry
+            .get("s1")
+            .unwrap()
+            .running_tools
+            .iter()
+            .map(|t| t.category)
+            .collect();
+        assert_eq!(categories, vec![ToolCategory::Shell, ToolCategory::Vcs]);
+    }
+
+    #[test]
+    fn subagents_hold_their_own_tools() {
+        let mut registry = SessionRegistry::new();
+        start(&mut registry, "s1", "/tmp/aura");
+        for (agent_id, tool_id) in [("a1", "t1"), ("a2", "t2")] {
+            registry.process_event(AgentEvent::SubagentStarted {
+                session_id: "s1".into(),
+                agent_id: agent_id.into(),
+                agent_type: Some("Explore".into()),
+            });
+            registry.process_event(AgentEvent::ToolStarted {
+                session_id: "s1".into(),
+                cwd: "/tmp/aura".into(),
+                tool_id: tool_id.into(),
+                tool_name: "Grep".into(),
+                tool_label: None,
+                tool_full_label: None,
+            });
+            registry.process_event(AgentEvent::SubagentTool {
+                session_id: "s1".into(),
+                agent_id: agent_id.into(),
+                tool_id: tool_id.into(),
+            });
+        }
+        let info = registry.get("s1").unwrap();
+        assert!(info.running_tools.is_empty());
+        let tools: Vec<_> = info
+            .subagents
+            .iter()
+            .map(|a| (a.agent_id.as_str(), a.running_tools.len()))
+            .collect();
+        assert_eq!(tools, vec![("a1", 1), ("a2", 1)]);
+
+        registry.process_event(AgentEvent::ToolCompleted {
+            session_id: "s1".into(),
+            cwd: "/tmp/aura".into(),
+            tool_id: "t1".into(),
+        });
+        registry.process_event(AgentEvent::SubagentStopped {
+            session_id: "s1".into(),
+            agent_id: "a2".into(),
+        });
+        let info = registry.get("s1").unwrap();
+        assert_eq!(info.subagents.len(), 1);
+        assert!(info.subagents[0].running_tools.is_empty());
+
+        registry.process_event(AgentEvent::Idle {
+            session_id: "s1".into(),
+            cwd: "/tmp/aura".into(),
+        });
+        assert!(registry.get("s1").unwrap().subagents.is_empty());
+    }
+
+    #[test]
+    fn queued_prompts_clear_when_idle() {
+        let mut registry = SessionRegistry::new();
+        start(&mut registry, "s1", "/tmp/aura");
+        registry.process_event(AgentEvent::PromptsQueued {
+            session_id: "s1".into(),
+            count: 2,
+        });
+        assert_eq!(registry.get("s1").unwrap().queued_prompts, 2);
+        registry.process_event(AgentEvent::Idle {
+            session_id: "s1".into(),
+            cwd: "/tmp/aura".into(),
+        });
+        assert_eq!(registry.get("s1").unwrap().queued_prompts, 0);
+    }
+
+    #[test]
+    fn model_updates_replace_the_model() {
+        let mut registry = SessionRegistry::new();
+        start(&mut registry, "s1", "/tmp/aura");
+        assert_eq!(registry.get("s1").unwrap().model, None);
+        for model in ["claude-sonnet-4-5", "claude-opus-4-1"] {
+            registry.process_event(AgentEvent::ModelUpdated {
+                session_id: "s1".into(),
+                model: model.into(),
+            });
+            assert_eq!(registry.get("s1").unwrap().model.as_deref(), Some(model));
+        }
+    }
+
+    #[test]
+    fn plan_mode_and_plan_review() {
+        let mut registry = SessionRegistry::new();
+        start(&mut registry, "s1", "/tmp/aura");
+        registry.process_event(AgentEvent::PlanModeChanged {
+            session_id: "s1".into(),
+            active: true,
+        });
+        let info = registry.get("s1").unwrap();
+        assert!(info.plan_mode);
+        assert!(!info.is_reviewing_plan());
+
+        registry.process_event(AgentEvent::NeedsAttention {
+            session_id: "s1".into(),
+            cwd: "/tmp/aura".into(),
+            message: Some("ExitPlanMode".into()),
+        });
+        assert!(registry.get("s1").unwrap().is_reviewing_plan());
+
+        // Approved: Claude Code leaves plan mode and starts working
+        registry.process_event(AgentEvent::PlanModeChanged {
+            session_id: "s1".into(),
+            active: false,
+        });
+        registry.process_event(AgentEvent::Activity {
+            session_id: "s1".into(),
+            cwd: "/tmp/aura".into(),
+        });
+        let info = registry.get("s1").unwrap();
+        assert!(!info.plan_mode);
+        assert!(!info.is_reviewing_plan());
+    }
+
+    #[test]
+    fn tags_added_removed_and_replaced() {
+        let mut registry = SessionRegistry::new();
+        start(&mut registry, "s1", "/code/aura");
+        registry.process_event(AgentEvent::SessionTagged {
+            session_id: "s1".into(),
+            tags: vec!["billing".into(), "q3".into()],
+        });
+        assert_eq!(registry.get("s1").unwrap().tags, vec!["billing", "q3"]);
+
+        assert!(registry.untag_session("s1", &["q3".into()]));
+        assert_eq!(registry.get("s1").unwrap().tags, vec!["billing"]);
+
+        assert!(registry.set_session_tags("s1", vec!["hotfix".into()]));
+        assert_eq!(registry.get("s1").unwrap().tags, vec!["hotfix"]);
+        assert!(!registry.tag_session("missing", &["x".into()]));
+    }
+
+    #[test]
+    fn tag_rules_apply_on_start() {
+        let mut registry = SessionRegistry::new();
+        registry.set_tag_rules(vec![TagRule {
+            tag: "billing".into(),
+            cwd: Some("*/billing-*".into()),
+            branch: None,
+        }]);
+        start(&mut registry, "s1", "/code/billing-api");
+        start(&mut registry, "s2", "/code/aura");
+        assert_eq!(registry.get("s1").unwrap().tags, vec!["billing"]);
+        assert!(registry.get("s2").unwrap().tags.is_empty());
+    }
+
+    #[test]
+    fn shared_cwd_flags_live_sessions() {
+        let mut registry = SessionRegistry::new();
+        start(&mut registry, "s1", "/code/aura");
+        start(&mut registry, "s2", "/code/aura/");
+        start(&mut registry, "s3", "/code/aura-wt");
+        let conflicted = |registry: &SessionRegistry| -> Vec<String> {
+            let mut ids: Vec<String> = registry
+                .get_all()
+                .into_iter()
+                .filter(|s| s.cwd_conflict)
+                .map(|s| s.session_id)
+                .collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(conflicted(&registry), vec!["s1", "s2"]);
+        assert!(registry.get("s2").unwrap().cwd_conflict);
+        assert!(!registry.get("s3").unwrap().cwd_conflict);
+
+        // Archiving one of the pair clears the flag on the other
+        assert!(registry.archive_session("s2"));
+        assert!(conflicted(&registry).is_empty());
+        assert!(!registry.get("s2").unwrap().cwd_conflict);
+    }
+
+    #[test]
+    fn overlapping_files_flag_both_sessions() {
+        let mut registry = SessionRegistry::new();
+        start(&mut registry, "s1", "/code/aura");
+        start(&mut registry, "s2", "/code/aura-wt");
+        let touch = |registry: &mut SessionRegistry, id: &str, paths: &[&str]| {
+            registry.process_event(AgentEvent::FilesTouched {
+                session_id: id.into(),
+                changes: paths
+                    .iter()
+                    .map(|p| FileChange {
+                        path: p.to_string(),
+                        ..Default::default()
+                    })
+                    .collect(),
+            });
+        };
+        touch(
+            &mut registry,
+            "s1",
+            &["/code/shared/api.rs", "/code/aura/a.rs"],
+        );
+        touch(&mut registry, "s2", &["/code/aura-wt/a.rs"]);
+        assert!(registry.get("s1").unwrap().file_conflicts.is_empty());
+
+        touch(&mut registry, "s2", &["/code/shared/api.rs"]);
+        assert_eq!(
+            registry.get("s1").unwrap().file_conflicts,
+            vec!["/code/shared/api.rs"]
+        );
+        assert_eq!(
+            registry.get("s2").unwrap().file_conflicts,
+            vec!["/code/shared/api.rs"]
+        );
+
+        assert!(registry.archive_session("s1"));
+        assert!(registry.get("s2").unwrap().file_conflicts.is_empty());
+    }
+
+    #[test]
+    fn file_changes_accumulate_per_path() {
+        let mut registry = SessionRegistry::new();
+        start(&mut registry, "s1", "/code/aura");
+        let change = |path: &str, added, removed| FileChange {
+            path: path.into(),
+            added,
+            removed,
+        };
+        registry.process_event(AgentEvent::FilesTouched {
+            session_id: "s1".into(),
+            changes: vec![
+                change("/code/aura/b.rs", 10, 2),
+                change("/code/aura/a.rs", 1, 0),
+            ],
+        });
+        registry.process_event(AgentEvent::FilesTouched {
+            session_id: "s1".into(),
+            changes: vec![change("/code/aura/b.rs", 5, 5)],
+        });
+
+        let info = registry.get("s1").unwrap();
+        assert_eq!(
+            info.changed_files,
+            vec![
+                change("/code/aura/a.rs", 1, 0),
+                change("/code/aura/b.rs", 15, 7)
+            ]
+        );
+        assert_eq!(info.change_summary().as_deref(), Some("2 files, +16/−7"));
+    }
+
+    #[test]
+    fn shared_registry_publishes_snapshots() {
+        let shared = SharedRegistry::new(SessionRegistry::new());
+        let mut changes = shared.subscribe();
+        assert!(shared.snapshot().is_empty());
+
+        shared.update(|registry| {
+            registry.process_event(AgentEvent::Activity {
+                session_id: "s1".into(),
+                cwd: "/tmp".into(),
+            })
+        });
+        assert!(changes.has_changed().unwrap());
+        let published = changes.borrow_and_update().clone();
+        assert_eq!(published.generation(), 1);
+        assert_eq!(shared.snapshot()[0].session_id, "s1");
+
+        // Reads and no-op updates don't publish
+        assert_eq!(shared.read(|registry| registry.len()), Some(1));
+        shared.update(|registry| registry.mark_seen("missing"));
+        assert!(!changes.has_changed().unwrap());
+    }
+
+    #[test]
+    fn changes_since_returns_only_changed_sessions() {
+        let mut registry = SessionRegistry::new();
+        for (id, cwd) in [("s1", "/a"), ("s2", "/b")] {
+            registry.process_event(AgentEvent::SessionStarted {
+                session_id: id.into(),
+                cwd: cwd.into(),
+                agent: AgentType::ClaudeCode,
+                terminal: None,
+            });
+        }
+        let mut ui = registry.publish().sessions().to_vec();
+        let generation = registry.generation();
+        assert_eq!(generation, 1);
+
+        // Nothing changed: same generation, empty diff
+        registry.publish();
+        assert_eq!(registry.generation(), generation);
+        assert!(registry.changes_since(generation).is_empty());
+
+        registry.rename_session("s1", "login");
+        registry.publish();
+        let changes = registry.changes_since(generation);
+        assert_eq!(changes.generation, generation + 1);
+        assert_eq!(changes.updated.len(), 1);
+        assert_eq!(changes.updated[0].name.as_deref(), Some("login"));
+        assert!(changes.removed.is_empty());
+
+        let generation = changes.generation;
+        changes.apply(&mut ui);
+        assert_eq!(ui, registry.get_all());
+
+        registry.archive_session("s2");
+        registry.publish();
+        let changes = registry.changes_since(generation);
+        assert!(changes.updated.is_empty());
+        assert_eq!(changes.removed, vec!["s2".to_string()]);
+        changes.apply(&mut ui);
+        assert_eq!(ui, registry.get_all());
+
+        // A caller that never synced gets everything
+        let changes = registry.changes_since(0);
+        assert_eq!(changes.updated, registry.get_all());
+    }
+
+    #[test]
+    fn publish_rebuilds_only_touched_sessions() {
+        let (_clock, mut registry) = manual_registry();
+        for (id, cwd) in [("s1", "/a"), ("s2", "/a"), ("s3", "/b")] {
+            registry.process_event(AgentEvent::SessionStarted {
+                session_id: id.into(),
+                cwd: cwd.into(),
+                agent: AgentType::ClaudeCode,
+                terminal: None,
+            });
+        }
+        let published = registry.publish();
+        assert_eq!(published.iter().filter(|s| s.cwd_conflict).count(), 2);
+        let s3 = Arc::clone(&registry.published["s3"].1);
+
+        registry.mark_seen("s1");
+        registry.rename_session("s1", "login");
+        let published = registry.publish();
+        assert!(Arc::ptr_eq(&s3, &registry.published["s3"].1));
+        assert_eq!(*published.sessions(), registry.get_all());
+
+        // Archiving s2 clears s1's shared-cwd flag without touching s1
+        registry.archive_session("s2");
+        let published = registry.publish();
+        assert_eq!(*published.sessions(), registry.get_all());
+        assert!(published.iter().all(|s| !s.cwd_conflict));
+        let changes = published.changes_since(published.generation() - 1);
+        assert_eq!(changes.updated.len(), 1);
+        assert_eq!(changes.updated[0].session_id, "s1");
+        assert_eq!(changes.removed, vec!["s2".to_string()]);
+        assert!(changes.ended.is_empty());
+
+        registry.process_event(AgentEvent::SessionEnded {
+            session_id: "s3".into(),
+        });
+        let changes = registry.publish().changes_since(registry.generation() - 1);
+        assert_eq!(changes.ended, vec!["s3".to_string()]);
+    }
+
+    #[test]
+    fn observers_receive_published_changes() {
+        let mut registry = SessionRegistry::new();
+        let mut changes = registry.observe();
+        let mut received = || std::iter::from_fn(|| changes.try_recv().ok()).collect::<Vec<_>>();
+        for id in ["s1", "s2"] {
+            registry.process_event(AgentEvent::SessionStarted {
+                session_id: id.into(),
+                cwd: "/a".into(),
+                agent: AgentType::ClaudeCode,
+                terminal: None,
+            });
+        }
+        // Nothing is sent until the list is published
+        assert!(received().is_empty());
+        registry.publish();
+        let added = received();
+        assert_eq!(added.len(), 2);
+        assert!(matches!(&added[0], RegistryChange::Added(s) if s.session_id == "s1"));
+
+        registry.rename_session("s1", "login");
+        registry.publish();
+        registry.publish();
+        let updated = received();
+        assert_eq!(updated.len(), 1);
+        let RegistryChange::Updated { previous, current } = &updated[0] else {
+            panic!("expected an update: {:?}", updated[0]);
+        };
+        assert_eq!(previous.name, None);
+        assert_eq!(current.name.as_deref(), Some("login"));
+
+        registry.archive_session("s2");
+        registry.process_event(AgentEvent::SessionEnded {
+            session_id: "s1".into(),
+        });
+        registry.publish();
+        let removed = received();
+        assert_eq!(removed.len(), 2);
+        let archived = |id: &str| {
+            removed.iter().find_map(|change| match change {
+                RegistryChange::Removed { session, archived } if session.session_id == id => {
+                    Some(*archived)
+                }
+                _ => None,
+            })
+        };
+        assert_eq!(archived("s2"), Some(true));
+        assert_eq!(archived("s1"), Some(false));
+
+        // Dropped receivers are forgotten on the next publish
+        drop(changes);
+        registry.restore_session("s2");
+        registry.publish();
+        assert!(registry.observers.is_empty());
+    }
+
+    #[test]
+    fn removals_of_forgotten_sessions_stay_bounded() {
+        let mut registry = SessionRegistry::new();
+        let ids: Vec<String> = (0..MAX_UNLISTED + 10).map(|i| format!("s{i}")).collect();
+        for id in &ids {
+            registry.process_event(AgentEvent::SessionStarted {
+                session_id: id.clone(),
+                cwd: "/a".into(),
+                agent: AgentType::ClaudeCode,
+                terminal: None,
+            });
+        }
+        let mut ui = registry.publish().sessions().to_vec();
+        let generation = registry.generation();
+        for id in &ids[1..] {
+            registry.process_event(AgentEvent::SessionEnded {
+                session_id: id.clone(),
+            });
+        }
+        registry.publish();
+        assert_eq!(registry.unlisted.len(), MAX_UNLISTED);
+
+        // A caller that synced before still ends up with the right list
+        registry.changes_since(generation).apply(&mut ui);
+        assert_eq!(ui, registry.get_all());
+        assert_eq!(ui.len(), 1);
+    }
+
+    #[test]
+    fn next_tool_expiry_tracks_recent_tools() {
+        let mut registry = SessionRegistry::new();
+        registry.process_event(AgentEvent::ToolStarted {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            tool_id: "t1".into(),
+            tool_name: "Read".into(),
+            tool_label: None,
+            tool_full_label: None,
+        });
+        assert!(registry.next_tool_expiry().is_none());
+        registry.process_event(AgentEvent::ToolCompleted {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+            tool_id: "t1".into(),
+        });
+        assert!(registry.next_tool_expiry().unwrap() > Instant::now());
     }
 }
@@ -0,0 +1,190 @@
+//! Review reminders - nudge when a finished session sits unreviewed
+//!
+//! With `ui.review_reminder_minutes` set, a session that has been Idle that
+//! long without being marked seen, archived or snoozed gets a clock badge in
+//! the session list and one macOS notification. Marking it seen (row click,
+//! `aura ack`, "Mark all seen") clears the reminder.
+
+use crate::registry::SharedRegistry;
+use crate::ui::session_list::extract_session_name;
+use crate::{SessionInfo, SessionState};
+use std::collections::HashSet;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How often the registry is checked for overdue sessions
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Whether a session has been waiting for review longer than `after`
+pub fn needs_review(session: &SessionInfo, after: Duration, now_unix: u64) -> bool {
+    session.state == SessionState::Idle
+        && !session.seen
+        && !session.archived
+        && !session.is_snoozed()
+        && session
+            .stopped_at
+            .is_some_and(|stopped| now_unix.saturating_sub(stopped) >= after.as_secs())
+}
+
+/// Current Unix time in seconds
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Decides which overdue sessions still need their notification
+#[derive(Debug)]
+pub struct Reminders {
+    after: Duration,
+    /// (session, stopped_at) pairs already reminded; a session that runs and
+    /// stops again gets a new `stopped_at` and a new reminder
+    reminded: HashSet<(String, u64)>,
+}
+
+impl Reminders {
+    pub fn new(after: Duration) -> Self {
+        Self {
+            after,
+            reminded: HashSet::new(),
+        }
+    }
+
+    /// Sessions that just became overdue
+    pub fn update(&mut self, sessions: &[SessionInfo], now_unix: u64) -> Vec<SessionInfo> {
+        let mut due = Vec::new();
+        let mut overdue = HashSet::new();
+        for session in sessions {
+            if !needs_review(session, self.after, now_unix) {
+                continue;
+            }
+            let key = (
+                session.session_id.clone(),
+                session.stopped_at.unwrap_or_default(),
+            );
+            if !self.reminded.contains(&key) {
+                due.push(session.clone());
+            }
+            overdue.insert(key);
+        }
+        // Forget sessions that were reviewed or went away
+        self.reminded = overdue;
+        due
+    }
+}
+
+/// Notification body, e.g. "fix login finished 25m ago"
+fn reminder_text(session: &SessionInfo, now_unix: u64) -> String {
+    let name = session
+        .name
+        .clone()
+        .unwrap_or_else(|| extract_session_name(&session.cwd));
+    let minutes = now_unix.saturating_sub(session.stopped_at.unwrap_or(now_unix)) / 60;
+    format!("{name} finished {minutes}m ago")
+}
+
+/// Quote a string for AppleScript
+pub(crate) fn applescript_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Show a macOS notification, logging failures
+async fn notify(text: String) {
+    let script = format!(
+        "display notification {} with title \"Aura\" subtitle \"Ready for review\"",
+        applescript_string(&text)
+    );
+    match tokio::process::Command::new("osascript")
+        .args(["-e", &script])
+        .output()
+        .await
+    {
+        Ok(output) if output.status.success() => {}
+        Ok(output) => tracing::warn!(
+            "Review reminder failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+        Err(e) => tracing::warn!("Failed to run osascript for review reminder: {}", e),
+    }
+}
+
+/// Watch the registry and post reminders until the runtime shuts down.
+pub async fn run(after: Duration, registry: SharedRegistry) {
+    let mut reminders = Reminders::new(after);
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+        let sessions = registry.snapshot();
+        let now = unix_now();
+        for session in reminders.update(&sessions, now) {
+            tracing::info!(session_id = %session.session_id, "review reminder");
+            tokio::spawn(notify(reminder_text(&session, now)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINUTE: u64 = 60;
+
+    fn idle(id: &str, stopped_at: u64) -> SessionInfo {
+        SessionInfo {
+            session_id: id.into(),
+            cwd: "/code/aura".into(),
+            state: SessionState::Idle,
+            stopped_at: Some(stopped_at),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn needs_review_after_threshold_unless_seen() {
+        let after = Duration::from_secs(15 * MINUTE);
+        let now = 100 * MINUTE;
+        assert!(!needs_review(&idle("s1", now - 14 * MINUTE), after, now));
+        assert!(needs_review(&idle("s1", now - 15 * MINUTE), after, now));
+
+        let seen = SessionInfo {
+            seen: true,
+            ..idle("s1", 0)
+        };
+        assert!(!needs_review(&seen, after, now));
+        let running = SessionInfo {
+            state: SessionState::Running,
+            ..idle("s1", 0)
+        };
+        assert!(!needs_review(&running, after, now));
+    }
+
+    #[test]
+    fn reminds_once_per_stop() {
+        let mut reminders = Reminders::new(Duration::from_secs(10 * MINUTE));
+        let now = 100 * MINUTE;
+        let due = reminders.update(&[idle("s1", now - 20 * MINUTE)], now);
+        assert_eq!(due.len(), 1);
+        assert!(
+            reminders
+                .update(&[idle("s1", now - 20 * MINUTE)], now + MINUTE)
+                .is_empty()
+        );
+
+        // Ran again and stopped later: a new reminder once overdue
+        let later = now + 30 * MINUTE;
+        assert_eq!(reminders.update(&[idle("s1", now)], later).len(), 1);
+    }
+
+    #[test]
+    fn reminder_text_names_the_session() {
+        let session = SessionInfo {
+            name: Some("fix login".into()),
+            ..idle("s1", 40 * MINUTE)
+        };
+        assert_eq!(
+            reminder_text(&session, 65 * MINUTE),
+            "fix login finished 25m ago"
+        );
+        assert_eq!(applescript_string(r#"a "b""#), r#""a \"b\"""#);
+    }
+}
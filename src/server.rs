@@ -1,77 +1,650 @@
-//! Unix socket server for receiving agent events from aura-hook
+//! IPC server for receiving agent events from aura-hook
 //!
-//! Listens on `std::env::temp_dir()/aura.sock` (via [`crate::ipc::socket_path`]) for
-//! newline-delimited JSON messages.
-//! Each message is deserialized directly as an `AgentEvent`.
-
-use crate::AgentEvent;
-use crate::ipc;
-use std::sync::{
-    Arc, Mutex,
-    atomic::{AtomicBool, Ordering},
+//! Listens on `aura.sock` in the runtime directory (via [`crate::ipc::socket_path`]), or
+//! on a named pipe on Windows, for newline-delimited JSON messages.
+//! Each message is deserialized as an `AgentEvent` (with the stamp of a
+//! [`StampedEvent`](ipc::StampedEvent)) and queued on the [`Coalescer`];
+//! lines that aren't agent events are tried as an [`IpcMessage`] control
+//! request and answered on the same connection.
+
+use crate::coalesce::Coalescer;
+use crate::health::{self, SocketState};
+use crate::ipc::{self, IpcMessage, IpcResponse};
+use crate::logging;
+use crate::{AgentEvent, AgentType};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{
+    AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader, Lines,
 };
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::net::UnixListener;
+use tokio::sync::watch;
 use tracing::{debug, info, trace, warn};
 
-use crate::registry::SessionRegistry;
+use crate::registry::{Published, SessionRegistry, SharedRegistry};
 
 /// Start the Unix socket server.
 ///
 /// Removes any stale socket file, binds to the path, and spawns a task
-/// that accepts connections and processes messages.
-pub async fn start(registry: Arc<Mutex<SessionRegistry>>, dirty: Arc<AtomicBool>) {
+/// per connection to process its messages.
+#[cfg(unix)]
+pub async fn start(registry: SharedRegistry, events: Coalescer) {
     let path = ipc::socket_path();
+    if let Some(dir) = path.parent()
+        && let Err(e) = crate::paths::ensure_private_dir(dir)
+    {
+        warn!(target: logging::IPC, "Failed to create runtime dir {}: {}", dir.display(), e);
+        health::set_socket(SocketState::Failed);
+        return;
+    }
 
     // Remove stale socket if it exists
     if path.exists()
         && let Err(e) = std::fs::remove_file(&path)
     {
-        warn!("Failed to remove stale socket {}: {}", path.display(), e);
+        warn!(target: logging::IPC, "Failed to remove stale socket {}: {}", path.display(), e);
+        health::set_socket(SocketState::Failed);
         return;
     }
 
-    let listener = match UnixListener::bind(&path) {
+    let listener = match tokio::net::UnixListener::bind(&path) {
         Ok(l) => l,
         Err(e) => {
-            warn!("Failed to bind Unix socket {}: {}", path.display(), e);
+            warn!(target: logging::IPC, "Failed to bind Unix socket {}: {}", path.display(), e);
+            health::set_socket(SocketState::Failed);
             return;
         }
     };
 
-    info!("IPC server listening on {}", path.display());
+    info!(target: logging::IPC, "IPC server listening on {}", path.display());
+    health::set_socket(SocketState::Listening);
 
     loop {
         match listener.accept().await {
             Ok((stream, _addr)) => {
-                let reg = Arc::clone(&registry);
-                let dirty = Arc::clone(&dirty);
-                tokio::spawn(async move {
-                    let reader = BufReader::new(stream);
-                    let mut lines = reader.lines();
-
-                    while let Ok(Some(line)) = lines.next_line().await {
-                        if line.is_empty() {
-                            continue;
-                        }
-                        match serde_json::from_str::<AgentEvent>(&line) {
-                            Ok(event) => {
-                                debug!(?event, "ipc event");
-                                if let Ok(mut reg) = reg.lock() {
-                                    reg.process_event(event);
-                                    dirty.store(true, Ordering::Relaxed);
-                                }
-                            }
-                            Err(e) => {
-                                trace!("Failed to parse IPC message: {} (line: {})", e, line);
-                            }
-                        }
-                    }
-                });
+                tokio::spawn(serve_connection(stream, registry.clone(), events.clone()));
             }
             Err(e) => {
-                warn!("Failed to accept socket connection: {}", e);
+                warn!(target: logging::IPC, "Failed to accept socket connection: {}", e);
+            }
+        }
+    }
+}
+
+/// Start the named pipe server.
+///
+/// A pipe instance serves one client, so a fresh instance is created before
+/// each connected one is handed off.
+#[cfg(windows)]
+pub async fn start(registry: SharedRegistry, events: Coalescer) {
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    let path = ipc::socket_path();
+    let mut server = match ServerOptions::new().first_pipe_instance(true).create(&path) {
+        Ok(server) => server,
+        Err(e) => {
+            warn!(target: logging::IPC, "Failed to create named pipe {}: {}", path.display(), e);
+            health::set_socket(SocketState::Failed);
+            return;
+        }
+    };
+
+    info!(target: logging::IPC, "IPC server listening on {}", path.display());
+    health::set_socket(SocketState::Listening);
+
+    loop {
+        if let Err(e) = server.connect().await {
+            warn!(target: logging::IPC, "Failed to accept pipe connection: {}", e);
+            continue;
+        }
+        let connected = match ServerOptions::new().create(&path) {
+            Ok(next) => std::mem::replace(&mut server, next),
+            Err(e) => {
+                warn!(
+                    target: logging::IPC,
+                    "Failed to create named pipe {}: {}",
+                    path.display(),
+                    e
+                );
+                health::set_socket(SocketState::Failed);
+                return;
+            }
+        };
+        tokio::spawn(serve_connection(
+            connected,
+            registry.clone(),
+            events.clone(),
+        ));
+    }
+}
+
+/// Read events and requests from one client until it disconnects.
+async fn serve_connection<S>(stream: S, registry: SharedRegistry, events: Coalescer)
+where
+    S: AsyncRead + AsyncWrite,
+{
+    let (read_half, mut write_half) = tokio::io::split(stream);
+    let reader = BufReader::new(read_half);
+    let mut lines = reader.lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<ipc::StampedEvent>(&line) {
+            Ok(ipc::StampedEvent {
+                event: AgentEvent::Unknown,
+                version,
+                ..
+            }) => {
+                debug!(target: logging::IPC, ?version, "skipping unknown event type: {}", line);
+            }
+            Ok(stamped) => {
+                let stamp = stamped.stamp();
+                debug!(target: logging::IPC, event = ?stamped.event, ?stamp, "ipc event");
+                events.send_stamped(stamped.event, AgentType::ClaudeCode, stamp);
+            }
+            Err(e) => {
+                let Ok(message) = serde_json::from_str::<IpcMessage>(&line) else {
+                    trace!(
+                        target: logging::IPC,
+                        "Failed to parse IPC message: {} (line: {})",
+                        e,
+                        line
+                    );
+                    continue;
+                };
+                debug!(target: logging::IPC, ?message, "ipc request");
+                let restart = matches!(message, IpcMessage::Restart);
+                // Subscribed before the first answer, so no change is missed
+                let watching = matches!(message, IpcMessage::Watch).then(|| registry.subscribe());
+                let response = registry
+                    .update(|registry| handle_message(registry, message))
+                    .map(redact_response)
+                    .unwrap_or_else(|| IpcResponse::Error {
+                        message: "registry unavailable".into(),
+                    });
+                let Ok(mut json) = serde_json::to_string(&response) else {
+                    continue;
+                };
+                json.push('\n');
+                if let Err(e) = write_half.write_all(json.as_bytes()).await {
+                    debug!(target: logging::IPC, "Failed to write IPC response: {}", e);
+                    break;
+                }
+                if restart {
+                    info!(target: logging::IPC, "restarting onto the updated binary");
+                    let e = crate::update::restart_daemon();
+                    warn!(target: logging::IPC, "Failed to restart: {}", e);
+                }
+                if let Some(snapshots) = watching {
+                    push_changes(snapshots, &mut lines, &mut write_half).await;
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Send a `Watch` client the visible sessions whenever they change, until it
+/// disconnects or sends anything else
+async fn push_changes<R, W>(
+    mut snapshots: watch::Receiver<Arc<Published>>,
+    lines: &mut Lines<R>,
+    write_half: &mut W,
+) where
+    R: AsyncBufRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    loop {
+        tokio::select! {
+            changed = snapshots.changed() => {
+                if changed.is_err() {
+                    return;
+                }
+            }
+            _ = lines.next_line() => return,
+        }
+        let sessions = snapshots.borrow_and_update().sessions().to_vec();
+        let response = redact_response(IpcResponse::Sessions { sessions });
+        let Ok(mut json) = serde_json::to_string(&response) else {
+            continue;
+        };
+        json.push('\n');
+        if let Err(e) = write_half.write_all(json.as_bytes()).await {
+            debug!(target: logging::IPC, "Failed to push sessions: {}", e);
+            return;
+        }
+    }
+}
+
+/// Reply to `Ping`: version, protocol and health
+fn pong() -> IpcResponse {
+    IpcResponse::Pong {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        protocol: ipc::PROTOCOL_VERSION,
+        health: Some(health::report()),
+    }
+}
+
+/// Apply a control request to the registry and build the response.
+fn handle_message(registry: &mut SessionRegistry, message: IpcMessage) -> IpcResponse {
+    match message {
+        IpcMessage::Ping | IpcMessage::Restart => pong(),
+        IpcMessage::Unknown => IpcResponse::Error {
+            message: format!(
+                "unknown command (daemon protocol {}); update the daemon",
+                ipc::PROTOCOL_VERSION
+            ),
+        },
+        IpcMessage::Pause { source } => {
+            info!(target: logging::IPC, ?source, "source paused");
+            crate::pause::set_paused(source, true);
+            pong()
+        }
+        IpcMessage::Resume { source } => {
+            info!(target: logging::IPC, ?source, "source resumed");
+            crate::pause::set_paused(source, false);
+            pong()
+        }
+        IpcMessage::Snooze {
+            session_id,
+            duration_secs,
+        } => with_session(registry, &session_id, |registry, id| {
+            registry.snooze_session(id, Duration::from_secs(duration_secs));
+        }),
+        IpcMessage::Unsnooze { session_id } => {
+            with_session(registry, &session_id, |registry, id| {
+                registry.unsnooze_session(id);
+            })
+        }
+        IpcMessage::Ack { session_id } => with_session(registry, &session_id, |registry, id| {
+            registry.mark_seen(id);
+        }),
+        IpcMessage::ListSessions { archived } => IpcResponse::Sessions {
+            sessions: if archived {
+                registry.get_archived()
+            } else {
+                registry.get_all()
+            },
+        },
+        // Later lists are pushed by `push_changes`
+        IpcMessage::Watch => IpcResponse::Sessions {
+            sessions: registry.get_all(),
+        },
+        IpcMessage::Archive { session_id } => {
+            with_session(registry, &session_id, |registry, id| {
+                registry.archive_session(id);
+            })
+        }
+        IpcMessage::Restore { session_id } => {
+            with_session(registry, &session_id, |registry, id| {
+                registry.restore_session(id);
+            })
+        }
+        IpcMessage::Bulk { action } => IpcResponse::Affected {
+            count: registry.apply_bulk(action),
+        },
+        IpcMessage::Rename { session_id, name } => {
+            let name = name.trim();
+            if name.is_empty() {
+                return IpcResponse::Error {
+                    message: "session name must not be empty".to_string(),
+                };
+            }
+            with_session(registry, &session_id, |registry, id| {
+                registry.rename_session(id, name);
+            })
+        }
+        IpcMessage::GetSession { session_id } => with_session(registry, &session_id, |_, _| {}),
+        IpcMessage::Tag { session_id, tags } => {
+            with_tags(registry, &session_id, &tags, SessionRegistry::tag_session)
+        }
+        IpcMessage::Untag { session_id, tags } => {
+            with_tags(registry, &session_id, &tags, SessionRegistry::untag_session)
+        }
+    }
+}
+
+/// Sessions in `response` as privacy mode allows them to be shown
+fn redact_response(response: IpcResponse) -> IpcResponse {
+    match response {
+        IpcResponse::Session { session } => IpcResponse::Session {
+            session: crate::privacy::apply(session),
+        },
+        IpcResponse::Sessions { sessions } => IpcResponse::Sessions {
+            sessions: sessions.into_iter().map(crate::privacy::apply).collect(),
+        },
+        other => other,
+    }
+}
+
+/// Normalize `tags`, then apply `action` to the session like [`with_session`].
+fn with_tags(
+    registry: &mut SessionRegistry,
+    query: &str,
+    tags: &[String],
+    action: fn(&mut SessionRegistry, &str, &[String]) -> bool,
+) -> IpcResponse {
+    let tags = crate::tags::parse_tags(&tags.join(" "));
+    if tags.is_empty() {
+        return IpcResponse::Error {
+            message: "no valid tags given".to_string(),
+        };
+    }
+    with_session(registry, query, |registry, id| {
+        action(registry, id, &tags);
+    })
+}
+
+/// Resolve `query` to a session, apply `action`, and respond with the updated session.
+fn with_session(
+    registry: &mut SessionRegistry,
+    query: &str,
+    action: impl FnOnce(&mut SessionRegistry, &str),
+) -> IpcResponse {
+    let Some(session_id) = registry.resolve_session_id(query) else {
+        return IpcResponse::Error {
+            message: format!("no unique session matching '{query}'"),
+        };
+    };
+    action(registry, &session_id);
+    match registry.get(&session_id) {
+        Some(session) => IpcResponse::Session { session },
+        None => IpcResponse::Error {
+            message: format!("session '{session_id}' disappeared"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::BulkAction;
+
+    fn registry_with_session(session_id: &str) -> SessionRegistry {
+        let mut registry = SessionRegistry::new();
+        registry.process_event(AgentEvent::SessionStarted {
+            session_id: session_id.into(),
+            cwd: "/tmp".into(),
+            agent: AgentType::ClaudeCode,
+            terminal: None,
+        });
+        registry
+    }
+
+    #[tokio::test]
+    async fn connection_answers_requests_and_queues_events() {
+        use tokio::io::AsyncReadExt;
+
+        let registry = SharedRegistry::new(SessionRegistry::new());
+        let mut changes = registry.subscribe();
+        let events = Coalescer::spawn(registry.clone());
+        let (mut client, server) = tokio::io::duplex(4096);
+        tokio::spawn(serve_connection(server, registry, events));
+
+        // Skipped: an event type from a newer hook
+        client
+            .write_all(b"{\"type\":\"future\",\"session_id\":\"s0\",\"version\":9}\n")
+            .await
+            .unwrap();
+        client
+            .write_all(b"{\"type\":\"activity\",\"session_id\":\"s1\",\"cwd\":\"/tmp\"}\n")
+            .await
+            .unwrap();
+        client.write_all(b"{\"command\":\"ping\"}\n").await.unwrap();
+        let mut reply = vec![0; 4096];
+        let n = client.read(&mut reply).await.unwrap();
+        let pong: IpcResponse = serde_json::from_slice(&reply[..n]).unwrap();
+        assert!(ipc::incompatibility(&pong).is_none());
+
+        changes.changed().await.unwrap();
+        let snapshot = changes.borrow_and_update().sessions();
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot[0].session_id, "s1");
+    }
+
+    #[tokio::test]
+    async fn watch_pushes_the_sessions_until_the_client_leaves() {
+        let registry = SharedRegistry::new(SessionRegistry::new());
+        let events = Coalescer::spawn(registry.clone());
+        let (client, server) = tokio::io::duplex(4096);
+        let connection = tokio::spawn(serve_connection(server, registry.clone(), events));
+        let (read_half, mut write_half) = tokio::io::split(client);
+        let mut pushes = BufReader::new(read_half).lines();
+        let sessions = |line: Option<String>| match serde_json::from_str(&line.unwrap()).unwrap() {
+            IpcResponse::Sessions { sessions } => sessions,
+            other => panic!("unexpected response: {other:?}"),
+        };
+
+        write_half
+            .write_all(b"{\"command\":\"watch\"}\n")
+            .await
+            .unwrap();
+        assert!(sessions(pushes.next_line().await.unwrap()).is_empty());
+        registry.update(|registry| {
+            registry.process_event(AgentEvent::SessionStarted {
+                session_id: "s1".into(),
+                cwd: "/tmp".into(),
+                agent: AgentType::ClaudeCode,
+                terminal: None,
+            })
+        });
+        let pushed = sessions(pushes.next_line().await.unwrap());
+        assert_eq!(pushed[0].session_id, "s1");
+
+        // Anything the client sends ends the watch
+        write_half.write_all(b"\n").await.unwrap();
+        connection.await.unwrap();
+    }
+
+    #[test]
+    fn ping_returns_pong() {
+        let mut registry = SessionRegistry::new();
+        assert!(matches!(
+            handle_message(&mut registry, IpcMessage::Ping),
+            IpcResponse::Pong {
+                protocol: ipc::PROTOCOL_VERSION,
+                health: Some(_),
+                ..
+            }
+        ));
+        assert!(matches!(
+            handle_message(&mut registry, IpcMessage::Unknown),
+            IpcResponse::Error { .. }
+        ));
+    }
+
+    #[test]
+    fn snooze_by_prefix() {
+        let mut registry = registry_with_session("abc123");
+        let response = handle_message(
+            &mut registry,
+            IpcMessage::Snooze {
+                session_id: "abc".into(),
+                duration_secs: 60,
+            },
+        );
+        match response {
+            IpcResponse::Session { session } => {
+                assert_eq!(session.session_id, "abc123");
+                assert!(session.is_snoozed());
             }
+            other => panic!("unexpected response: {other:?}"),
         }
+
+        handle_message(
+            &mut registry,
+            IpcMessage::Unsnooze {
+                session_id: "abc123".into(),
+            },
+        );
+        assert!(!registry.get_all()[0].is_snoozed());
+    }
+
+    #[test]
+    fn snooze_unknown_session_errors() {
+        let mut registry = SessionRegistry::new();
+        let response = handle_message(
+            &mut registry,
+            IpcMessage::Snooze {
+                session_id: "nope".into(),
+                duration_secs: 60,
+            },
+        );
+        assert!(matches!(response, IpcResponse::Error { .. }));
+    }
+
+    #[test]
+    fn archive_and_list_archived() {
+        let mut registry = registry_with_session("abc123");
+        handle_message(
+            &mut registry,
+            IpcMessage::Archive {
+                session_id: "abc".into(),
+            },
+        );
+
+        let list = |registry: &mut SessionRegistry, archived| match handle_message(
+            registry,
+            IpcMessage::ListSessions { archived },
+        ) {
+            IpcResponse::Sessions { sessions } => sessions,
+            other => panic!("unexpected response: {other:?}"),
+        };
+        assert!(list(&mut registry, false).is_empty());
+        assert_eq!(list(&mut registry, true)[0].session_id, "abc123");
+
+        handle_message(
+            &mut registry,
+            IpcMessage::Restore {
+                session_id: "abc123".into(),
+            },
+        );
+        assert_eq!(list(&mut registry, false).len(), 1);
+        assert!(list(&mut registry, true).is_empty());
+    }
+
+    #[test]
+    fn ack_marks_seen() {
+        let mut registry = registry_with_session("abc123");
+        registry.process_event(AgentEvent::NeedsAttention {
+            session_id: "abc123".into(),
+            cwd: "/tmp".into(),
+            message: None,
+        });
+        let response = handle_message(
+            &mut registry,
+            IpcMessage::Ack {
+                session_id: "abc".into(),
+            },
+        );
+        match response {
+            IpcResponse::Session { session } => {
+                assert!(session.seen);
+                assert_eq!(session.state, crate::SessionState::Attention);
+            }
+            other => panic!("unexpected response: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn bulk_reports_affected_count() {
+        let mut registry = registry_with_session("s1");
+        registry.process_event(AgentEvent::Idle {
+            session_id: "s1".into(),
+            cwd: "/tmp".into(),
+        });
+        let response = handle_message(
+            &mut registry,
+            IpcMessage::Bulk {
+                action: BulkAction::ClearIdle,
+            },
+        );
+        assert!(matches!(response, IpcResponse::Affected { count: 1 }));
+        assert!(registry.get_all().is_empty());
+    }
+
+    #[test]
+    fn rename_trims_and_rejects_empty() {
+        let mut registry = registry_with_session("abc123");
+        let response = handle_message(
+            &mut registry,
+            IpcMessage::Rename {
+                session_id: "abc".into(),
+                name: "  fix login  ".into(),
+            },
+        );
+        match response {
+            IpcResponse::Session { session } => {
+                assert_eq!(session.name.as_deref(), Some("fix login"))
+            }
+            other => panic!("unexpected response: {other:?}"),
+        }
+
+        let response = handle_message(
+            &mut registry,
+            IpcMessage::Rename {
+                session_id: "abc".into(),
+                name: "   ".into(),
+            },
+        );
+        assert!(matches!(response, IpcResponse::Error { .. }));
+    }
+
+    #[test]
+    fn tag_and_untag_normalize() {
+        let mut registry = registry_with_session("abc123");
+        let response = handle_message(
+            &mut registry,
+            IpcMessage::Tag {
+                session_id: "abc".into(),
+                tags: vec!["#Billing".into(), "q3".into()],
+            },
+        );
+        match response {
+            IpcResponse::Session { session } => assert_eq!(session.tags, vec!["billing", "q3"]),
+            other => panic!("unexpected response: {other:?}"),
+        }
+
+        handle_message(
+            &mut registry,
+            IpcMessage::Untag {
+                session_id: "abc".into(),
+                tags: vec!["billing".into()],
+            },
+        );
+        assert_eq!(registry.get("abc123").unwrap().tags, vec!["q3"]);
+
+        let response = handle_message(
+            &mut registry,
+            IpcMessage::Tag {
+                session_id: "abc".into(),
+                tags: vec!["#".into()],
+            },
+        );
+        assert!(matches!(response, IpcResponse::Error { .. }));
+    }
+
+    #[test]
+    fn get_session_by_prefix() {
+        let mut registry = registry_with_session("abc123");
+        let response = handle_message(
+            &mut registry,
+            IpcMessage::GetSession {
+                session_id: "abc".into(),
+            },
+        );
+        match response {
+            IpcResponse::Session { session } => assert_eq!(session.session_id, "abc123"),
+            other => panic!("unexpected response: {other:?}"),
+        }
+
+        let response = handle_message(
+            &mut registry,
+            IpcMessage::GetSession {
+                session_id: "zzz".into(),
+            },
+        );
+        assert!(matches!(response, IpcResponse::Error { .. }));
     }
 }
@@ -3,18 +3,47 @@
 use serde::{Deserialize, Serialize};
 
 /// A currently running tool
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RunningTool {
     pub tool_id: String,
     pub tool_name: String,
     pub tool_label: Option<String>,
+    /// The full command or path behind a shortened `tool_label`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_full_label: Option<String>,
+    /// Normalized kind, see [`crate::tools`]
+    #[serde(default)]
+    pub category: crate::tools::ToolCategory,
+}
+
+/// A parallel subagent working inside a session (Claude Code `agent_id`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubagentInfo {
+    pub agent_id: String,
+    /// Kind of subagent, e.g. "Explore"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_type: Option<String>,
+    /// Tools the subagent is running (not in the session's `running_tools`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub running_tools: Vec<RunningTool>,
+}
+
+/// A state a session entered, see [`SessionInfo::state_history`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateChange {
+    pub state: SessionState,
+    /// Unix timestamp
+    pub at: u64,
 }
 
 /// Session information for UI rendering
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct SessionInfo {
     pub session_id: String,
     pub cwd: String,
+    /// Agent running the session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<crate::AgentType>,
     pub state: SessionState,
     pub running_tools: Vec<RunningTool>,
     /// Custom session name (if set by user)
@@ -26,12 +55,139 @@ pub struct SessionInfo {
     /// Unix timestamp when became stale
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub stale_at: Option<u64>,
+    /// Unix timestamp of the session's last event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_activity_at: Option<u64>,
+    /// Unix timestamp when the session started
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<u64>,
+    /// Unix timestamp when the session entered its current state
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_changed_at: Option<u64>,
     /// Tool requesting permission
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub permission_tool: Option<String>,
     /// Recent activity labels (most recent last)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub recent_activity: Vec<String>,
+    /// Unix timestamp until which the session is snoozed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snoozed_until: Option<u64>,
+    /// Hidden from the session list but still tracked (restorable)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub archived: bool,
+    /// Attention/Waiting acknowledged by the user (cleared on the next prompt)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub seen: bool,
+    /// Transcript file reported by the agent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transcript_path: Option<String>,
+    /// User tags, sorted (see [`crate::tags`])
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Shares its working directory with another live session (see
+    /// `SessionRegistry::get_all`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cwd_conflict: bool,
+    /// Files this session modified that another live session also modified
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub file_conflicts: Vec<String>,
+    /// Files the session modified, sorted by path
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changed_files: Vec<crate::FileChange>,
+    /// Plan progress shown under the name, e.g. "3/7 · migrate config loader"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subtitle: Option<String>,
+    /// The agent's plan (Codex) or todo list (Claude Code TodoWrite)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progress: Option<crate::PlanProgress>,
+    /// In plan mode (Claude Code `permission_mode: "plan"`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub plan_mode: bool,
+    /// Model the agent last reported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Terminal the agent runs in (Claude Code hooks only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub terminal: Option<crate::TerminalInfo>,
+    /// CPU percent of the agent and its child processes, when sampled (see
+    /// [`crate::sysinfo`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_percent: Option<u16>,
+    /// Prompts queued behind the current turn
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub queued_prompts: usize,
+    /// Parallel subagents, in start order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subagents: Vec<SubagentInfo>,
+    /// States entered, oldest first (the latest few, see `aura sessions show`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub state_history: Vec<StateChange>,
+}
+
+fn is_zero(count: &usize) -> bool {
+    *count == 0
+}
+
+impl SessionInfo {
+    /// Whether the session is currently snoozed.
+    ///
+    /// Snoozed sessions stay in the list but don't contribute to the
+    /// aggregate indicator state.
+    pub fn is_snoozed(&self) -> bool {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        self.snoozed_until.is_some_and(|until| until > now)
+    }
+
+    /// How long the session has been in its current state, if known
+    pub fn time_in_state(&self, now_unix: u64) -> Option<std::time::Duration> {
+        let since = self.state_changed_at?;
+        Some(std::time::Duration::from_secs(
+            now_unix.saturating_sub(since),
+        ))
+    }
+
+    /// State and time in it, e.g. "waiting 4m" (HUD rows and `aura sessions`)
+    pub fn state_age_label(&self, now_unix: u64) -> Option<String> {
+        let elapsed = self.time_in_state(now_unix)?;
+        Some(format!(
+            "{} {}",
+            format!("{:?}", self.state).to_lowercase(),
+            crate::health::format_uptime(elapsed.as_secs())
+        ))
+    }
+
+    /// "7 files, +412/−88", or `None` if the session changed no files
+    pub fn change_summary(&self) -> Option<String> {
+        let files = self.changed_files.len();
+        if files == 0 {
+            return None;
+        }
+        let added: u64 = self.changed_files.iter().map(|c| u64::from(c.added)).sum();
+        let removed: u64 = self
+            .changed_files
+            .iter()
+            .map(|c| u64::from(c.removed))
+            .sum();
+        let plural = if files == 1 { "" } else { "s" };
+        Some(format!("{files} file{plural}, +{added}/−{removed}"))
+    }
+
+    /// Whether the agent is waiting for the user to approve its plan
+    /// (Claude Code's ExitPlanMode permission prompt), which calls for
+    /// reading the plan rather than answering a permission prompt
+    pub fn is_reviewing_plan(&self) -> bool {
+        self.state == SessionState::Attention
+            && self.permission_tool.as_deref() == Some("ExitPlanMode")
+    }
+
+    /// Whether the row shows the conflict badge (shared cwd or files)
+    pub fn has_conflict(&self) -> bool {
+        self.cwd_conflict || !self.file_conflicts.is_empty()
+    }
 }
 
 /// Placeholder texts displayed when agent is thinking/processing
@@ -47,7 +203,7 @@ pub const PLACEHOLDER_TEXTS: &[&str] = &[
 ];
 
 /// Session state in the HUD
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum SessionState {
     /// Actively working
@@ -95,11 +251,41 @@ impl SessionState {
             Self::Running => "#22C55E",    // Green
             Self::Idle => "#3B82F6",       // Blue
             Self::Attention => "#EAB308",  // Yellow
-            Self::Waiting => "#EAB308",    // Yellow (same as Attention)
+            Self::Waiting => "#38BDF8",    // Sky blue
             Self::Compacting => "#A855F7", // Purple
             Self::Stale => "#6B7280",      // Gray
         }
     }
+
+    /// How much this state needs the user (higher first); picks one state
+    /// when several sessions share a project
+    pub fn urgency(&self) -> u8 {
+        match self {
+            Self::Attention => 5,
+            Self::Waiting => 4,
+            Self::Idle => 3,
+            Self::Running => 2,
+            Self::Compacting => 1,
+            Self::Stale => 0,
+        }
+    }
+
+    /// Whether a session in this state may move to `next`.
+    ///
+    /// Staying put is always allowed, and any event showing the agent at
+    /// work may restart it. Only a session the agent is still responsible
+    /// for can go stale: `Idle` and `Waiting` are waiting on the user, so
+    /// silence there is expected. A permission request needs a running
+    /// turn, so one reaching an `Idle` session is left over from the turn
+    /// that stopped. New states must be added here first.
+    pub fn can_transition_to(self, next: Self) -> bool {
+        use SessionState::*;
+        match (self, next) {
+            (from, to) if from == to => true,
+            (Idle | Waiting, Stale) | (Idle, Attention) => false,
+            (_, Running | Idle | Attention | Waiting | Compacting | Stale) => true,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -116,12 +302,55 @@ mod tests {
         assert_eq!(SessionState::Stale.icon(), StateIcon::Pause);
     }
 
+    #[test]
+    fn state_age_labels() {
+        let mut session = SessionInfo {
+            state: SessionState::Waiting,
+            ..Default::default()
+        };
+        assert_eq!(session.state_age_label(1_000), None);
+        session.state_changed_at = Some(1_000 - 4 * 60 - 10);
+        assert_eq!(
+            session.state_age_label(1_000).as_deref(),
+            Some("waiting 4m")
+        );
+        assert_eq!(
+            session.time_in_state(1_000),
+            Some(std::time::Duration::from_secs(250))
+        );
+        session.state = SessionState::Attention;
+        session.state_changed_at = Some(1_000 + 3);
+        assert_eq!(
+            session.state_age_label(1_000).as_deref(),
+            Some("attention 0s")
+        );
+    }
+
+    #[test]
+    fn state_transitions() {
+        use SessionState::*;
+        let all = [Running, Idle, Attention, Waiting, Compacting, Stale];
+        for from in all {
+            assert!(from.can_transition_to(from), "{from:?} -> {from:?}");
+            assert!(from.can_transition_to(Running), "{from:?} -> Running");
+            assert!(from.can_transition_to(Idle), "{from:?} -> Idle");
+        }
+        assert!(Running.can_transition_to(Stale));
+        assert!(Attention.can_transition_to(Stale));
+        assert!(Compacting.can_transition_to(Stale));
+        assert!(!Idle.can_transition_to(Stale));
+        assert!(!Waiting.can_transition_to(Stale));
+        assert!(!Idle.can_transition_to(Attention));
+        assert!(Waiting.can_transition_to(Attention));
+        assert!(Stale.can_transition_to(Attention));
+    }
+
     #[test]
     fn state_colors() {
         assert_eq!(SessionState::Running.color(), "#22C55E");
         assert_eq!(SessionState::Idle.color(), "#3B82F6");
         assert_eq!(SessionState::Attention.color(), "#EAB308");
-        assert_eq!(SessionState::Waiting.color(), "#EAB308");
+        assert_eq!(SessionState::Waiting.color(), "#38BDF8");
         assert_eq!(SessionState::Compacting.color(), "#A855F7");
         assert_eq!(SessionState::Stale.color(), "#6B7280");
     }
@@ -131,6 +360,22 @@ mod tests {
         assert_eq!(SessionState::default(), SessionState::Running);
     }
 
+    #[test]
+    fn snoozed_until_in_future_is_snoozed() {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let mut info = SessionInfo::default();
+        assert!(!info.is_snoozed());
+
+        info.snoozed_until = Some(now + 60);
+        assert!(info.is_snoozed());
+
+        info.snoozed_until = Some(now - 60);
+        assert!(!info.is_snoozed());
+    }
+
     #[test]
     fn state_serialization() {
         assert_eq!(
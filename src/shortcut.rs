@@ -0,0 +1,208 @@
+//! JSON facade for macOS Shortcuts and AppleScript
+//!
+//! `aura shortcut <action>` wraps the core daemon actions with stable JSON
+//! on stdout, so a Shortcuts "Run Shell Script" step (or `do shell script`)
+//! can parse the result without scraping the human CLI output. Failures print
+//! `{"error": "..."}` and exit non-zero.
+//!
+//! Example automation: when a Work Focus turns on, run
+//! `aura shortcut snooze all 2h`; when it turns off, `aura shortcut unsnooze all`.
+
+use crate::config::IndicatorRule;
+use crate::ipc::{IpcMessage, IpcResponse};
+use crate::ui::indicator::{IndicatorState, determine_state};
+use crate::ui::session_list::extract_session_name;
+use crate::{SessionInfo, SessionState};
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::time::Duration;
+
+/// Session id argument that targets every visible session
+pub const ALL_SESSIONS: &str = "all";
+
+/// Session as exposed to automations
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ShortcutSession {
+    pub id: String,
+    pub name: String,
+    pub cwd: String,
+    pub state: SessionState,
+    pub snoozed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snoozed_until: Option<u64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub cwd_conflict: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub file_conflicts: Vec<String>,
+}
+
+impl From<&SessionInfo> for ShortcutSession {
+    fn from(session: &SessionInfo) -> Self {
+        Self {
+            id: session.session_id.clone(),
+            name: session
+                .name
+                .clone()
+                .unwrap_or_else(|| extract_session_name(&session.cwd)),
+            cwd: session.cwd.clone(),
+            state: session.state,
+            snoozed: session.is_snoozed(),
+            snoozed_until: session.snoozed_until,
+            tags: session.tags.clone(),
+            cwd_conflict: session.cwd_conflict,
+            file_conflicts: session.file_conflicts.clone(),
+        }
+    }
+}
+
+/// `aura shortcut list`
+pub fn list() -> Result<Value, String> {
+    let sessions = visible_sessions()?;
+    let sessions: Vec<ShortcutSession> = sessions.iter().map(ShortcutSession::from).collect();
+    Ok(json!({ "sessions": sessions }))
+}
+
+/// `aura shortcut state` - the indicator's aggregate state plus counts
+pub fn state() -> Result<Value, String> {
+    Ok(state_summary(
+        &visible_sessions()?,
+        &crate::config::load_config().ui.indicator_priority,
+    ))
+}
+
+/// `aura shortcut snooze <id|all> [duration]`
+pub fn snooze(target: &str, duration: Duration) -> Result<Value, String> {
+    let sessions = for_each_target(target, |session_id| IpcMessage::Snooze {
+        session_id,
+        duration_secs: duration.as_secs(),
+    })?;
+    Ok(json!({ "snoozed": sessions }))
+}
+
+/// `aura shortcut unsnooze <id|all>`
+pub fn unsnooze(target: &str) -> Result<Value, String> {
+    let sessions = for_each_target(target, |session_id| IpcMessage::Unsnooze { session_id })?;
+    Ok(json!({ "unsnoozed": sessions }))
+}
+
+/// `aura shortcut rename <id> <name>`
+pub fn rename(session_id: &str, name: &str) -> Result<Value, String> {
+    let session = request_session(IpcMessage::Rename {
+        session_id: session_id.to_string(),
+        name: name.to_string(),
+    })?;
+    Ok(json!({ "session": ShortcutSession::from(&session) }))
+}
+
+/// Print a result as JSON, exiting non-zero on error
+pub fn print(result: Result<Value, String>) {
+    match result {
+        Ok(value) => println!("{value}"),
+        Err(message) => {
+            println!("{}", json!({ "error": message }));
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Aggregate state and counts (shared with `aura status --json`)
+pub(crate) fn state_summary(sessions: &[SessionInfo], priority: &[IndicatorRule]) -> Value {
+    let state = match determine_state(sessions, priority) {
+        IndicatorState::Attention => "attention",
+        IndicatorState::Waiting => "waiting",
+        IndicatorState::Running => "running",
+        IndicatorState::NoSessions => "none",
+    };
+    let count = |state: SessionState| sessions.iter().filter(|s| s.state == state).count();
+    json!({
+        "state": state,
+        "sessions": sessions.len(),
+        "attention": count(SessionState::Attention),
+        "waiting": count(SessionState::Waiting),
+        "snoozed": sessions.iter().filter(|s| s.is_snoozed()).count(),
+    })
+}
+
+/// Send one request per targeted session (`all` = every visible session)
+fn for_each_target(
+    target: &str,
+    message: impl Fn(String) -> IpcMessage,
+) -> Result<Vec<ShortcutSession>, String> {
+    let ids = if target == ALL_SESSIONS {
+        visible_sessions()?
+            .into_iter()
+            .map(|s| s.session_id)
+            .collect()
+    } else {
+        vec![target.to_string()]
+    };
+    ids.into_iter()
+        .map(|id| request_session(message(id)).map(|s| ShortcutSession::from(&s)))
+        .collect()
+}
+
+fn visible_sessions() -> Result<Vec<SessionInfo>, String> {
+    match crate::cli::request(IpcMessage::ListSessions { archived: false })? {
+        IpcResponse::Sessions { sessions } => Ok(sessions),
+        other => Err(format!("unexpected daemon response: {other:?}")),
+    }
+}
+
+fn request_session(message: IpcMessage) -> Result<SessionInfo, String> {
+    match crate::cli::request(message)? {
+        IpcResponse::Session { session } => Ok(session),
+        other => Err(format!("unexpected daemon response: {other:?}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(id: &str, state: SessionState) -> SessionInfo {
+        SessionInfo {
+            session_id: id.into(),
+            cwd: "/code/aura".into(),
+            state,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn shortcut_session_json_shape() {
+        let json = serde_json::to_value(ShortcutSession::from(&session(
+            "abc",
+            SessionState::Waiting,
+        )))
+        .unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "id": "abc",
+                "name": "aura",
+                "cwd": "/code/aura",
+                "state": "waiting",
+                "snoozed": false
+            })
+        );
+    }
+
+    #[test]
+    fn state_summary_matches_indicator() {
+        let priority = crate::config::UiConfig::default().indicator_priority;
+        assert_eq!(state_summary(&[], &priority)["state"], "none");
+
+        let sessions = vec![
+            session("a", SessionState::Running),
+            session("b", SessionState::Attention),
+            session("c", SessionState::Waiting),
+        ];
+        let summary = state_summary(&sessions, &priority);
+        assert_eq!(summary["state"], "attention");
+        assert_eq!(summary["sessions"], 3);
+        assert_eq!(summary["attention"], 1);
+        assert_eq!(summary["waiting"], 1);
+    }
+}
@@ -0,0 +1,458 @@
+//! Fake agents - synthetic sessions for demos and UI testing
+//!
+//! `aura simulate --sessions 5 [--script demo.toml]` (built with the
+//! `simulator` feature) connects to the running daemon like `aura hook` and
+//! sends made-up [`AgentEvent`]s, so the HUD can be demoed and screenshot
+//! without real agents. Each fake session is a small state machine: while
+//! Running it starts and completes tools picked from a weighted mix; after a
+//! tool it may stop (Idle), ask for permission (Attention), wait for input or
+//! compact, and from there it resumes with some probability. Steps are
+//! `tick_ms` apart, give or take `jitter`. The same `seed` replays the same
+//! streams.
+//!
+//! ```toml
+//! seed = 7
+//! tick_ms = 800
+//! jitter = 0.5
+//! duration_secs = 300
+//! names = ["fix login", "billing export"]
+//! cwds = ["/code/aura", "/code/api"]
+//!
+//! [[tools]]
+//! name = "Read"
+//! labels = ["src/main.rs", "Cargo.toml"]
+//! weight = 3
+//!
+//! [transitions]
+//! idle = 0.08
+//! attention = 0.04
+//! waiting = 0.03
+//! compacting = 0.01
+//! resume = 0.25
+//! ```
+
+use crate::{AgentEvent, AgentType, ipc};
+use serde::Deserialize;
+use std::io::Write;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Simulation script; every field has a default
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Script {
+    /// Seed for the pseudo-random streams
+    pub seed: u64,
+    /// Base delay between steps
+    pub tick_ms: u64,
+    /// Random +/- fraction of `tick_ms` per step
+    pub jitter: f64,
+    /// Stop (ending every session) after this long; 0 runs until killed
+    pub duration_secs: u64,
+    /// Session names, assigned in turn
+    pub names: Vec<String>,
+    /// Working directories, assigned in turn
+    pub cwds: Vec<String>,
+    /// Tool mix
+    pub tools: Vec<ToolMix>,
+    pub transitions: Transitions,
+}
+
+/// A tool the fake agents use
+#[derive(Debug, Clone, Deserialize)]
+pub struct ToolMix {
+    pub name: String,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Relative frequency
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+/// Per-step probabilities of state changes
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Transitions {
+    /// Running → Idle, after a tool completes
+    pub idle: f64,
+    /// Running → Attention
+    pub attention: f64,
+    /// Running → Waiting
+    pub waiting: f64,
+    /// Running → Compacting
+    pub compacting: f64,
+    /// Any other state → Running
+    pub resume: f64,
+}
+
+impl Default for Transitions {
+    fn default() -> Self {
+        Self {
+            idle: 0.08,
+            attention: 0.04,
+            waiting: 0.03,
+            compacting: 0.01,
+            resume: 0.25,
+        }
+    }
+}
+
+impl Default for Script {
+    fn default() -> Self {
+        let tool = |name: &str, labels: &[&str], weight| ToolMix {
+            name: name.to_string(),
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            weight,
+        };
+        Self {
+            seed: 1,
+            tick_ms: 800,
+            jitter: 0.5,
+            duration_secs: 0,
+            names: ["fix login", "billing export", "flaky tests", "docs pass"]
+                .map(String::from)
+                .to_vec(),
+            cwds: ["/code/aura", "/code/api", "/code/web"]
+                .map(String::from)
+                .to_vec(),
+            tools: vec![
+                tool("Read", &["src/main.rs", "Cargo.toml", "README.md"], 4),
+                tool("Edit", &["src/registry.rs", "src/ui/mod.rs"], 3),
+                tool("Bash", &["cargo test", "npm run build", "git status"], 3),
+                tool("Grep", &["SessionState", "TODO"], 2),
+                tool("WebSearch", &["gpui scroll handle"], 1),
+            ],
+            transitions: Transitions::default(),
+        }
+    }
+}
+
+impl Script {
+    /// Read a script file (TOML)
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("can't read {}: {e}", path.display()))?;
+        toml::from_str(&text).map_err(|e| format!("invalid script {}: {e}", path.display()))
+    }
+}
+
+/// xorshift64* - small, seedable, good enough for demo jitter
+#[derive(Debug, Clone)]
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Uniform in [0, 1)
+    fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n.max(1) as u64) as usize
+    }
+
+    fn chance(&mut self, p: f64) -> bool {
+        self.unit() < p
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Running,
+    /// A tool is in flight
+    Tool,
+    Stopped,
+}
+
+#[derive(Debug, Clone)]
+struct FakeSession {
+    id: String,
+    cwd: String,
+    phase: Phase,
+    tool_seq: u64,
+}
+
+/// Generates the event streams of a set of fake sessions
+#[derive(Debug, Clone)]
+pub struct Simulator {
+    script: Script,
+    rng: Rng,
+    sessions: Vec<FakeSession>,
+}
+
+impl Simulator {
+    pub fn new(script: Script, sessions: usize) -> Self {
+        let rng = Rng::new(script.seed);
+        let sessions = (0..sessions)
+            .map(|i| FakeSession {
+                id: format!("sim-{:04x}-{i}", script.seed & 0xffff),
+                cwd: pick(&script.cwds, i).unwrap_or("/tmp").to_string(),
+                phase: Phase::Running,
+                tool_seq: 0,
+            })
+            .collect();
+        Self {
+            script,
+            rng,
+            sessions,
+        }
+    }
+
+    /// Register every session (alternating Claude Code and Codex) and name it
+    pub fn start(&self) -> Vec<AgentEvent> {
+        let mut events = Vec::new();
+        for (i, session) in self.sessions.iter().enumerate() {
+            events.push(AgentEvent::SessionStarted {
+                session_id: session.id.clone(),
+                cwd: session.cwd.clone(),
+                agent: if i % 2 == 0 {
+                    AgentType::ClaudeCode
+                } else {
+                    AgentType::Codex
+                },
+                terminal: None,
+            });
+            if let Some(name) = pick(&self.script.names, i) {
+                events.push(AgentEvent::SessionNameUpdated {
+                    session_id: session.id.clone(),
+                    name: name.to_string(),
+                });
+            }
+        }
+        events
+    }
+
+    /// End every session
+    pub fn finish(&self) -> Vec<AgentEvent> {
+        self.sessions
+            .iter()
+            .map(|session| AgentEvent::SessionEnded {
+                session_id: session.id.clone(),
+            })
+            .collect()
+    }
+
+    /// Advance one random session by one step
+    pub fn step(&mut self) -> Vec<AgentEvent> {
+        if self.sessions.is_empty() {
+            return Vec::new();
+        }
+        let index = self.rng.below(self.sessions.len());
+        let transitions = self.script.transitions.clone();
+        let tool = self.pick_tool();
+        let rng = &mut self.rng;
+        let session = &mut self.sessions[index];
+        let (session_id, cwd) = (session.id.clone(), session.cwd.clone());
+
+        match session.phase {
+            Phase::Running => {
+                let Some((tool_name, tool_label)) = tool else {
+                    return vec![AgentEvent::Activity { session_id, cwd }];
+                };
+                session.tool_seq += 1;
+                session.phase = Phase::Tool;
+                vec![AgentEvent::ToolStarted {
+                    session_id,
+                    cwd,
+                    tool_id: format!("tool-{}", session.tool_seq),
+                    tool_name,
+                    tool_label,
+                    tool_full_label: None,
+                }]
+            }
+            Phase::Tool => {
+                let mut events = vec![AgentEvent::ToolCompleted {
+                    session_id: session_id.clone(),
+                    cwd: cwd.clone(),
+                    tool_id: format!("tool-{}", session.tool_seq),
+                }];
+                session.phase = Phase::Running;
+                let roll = rng.unit();
+                let mut threshold = 0.0;
+                let mut next = |p: f64| {
+                    threshold += p;
+                    roll < threshold
+                };
+                let stop = if next(transitions.idle) {
+                    Some(AgentEvent::Idle { session_id, cwd })
+                } else if next(transitions.attention) {
+                    Some(AgentEvent::NeedsAttention {
+                        session_id,
+                        cwd,
+                        message: Some("Permission needed".to_string()),
+                    })
+                } else if next(transitions.waiting) {
+                    Some(AgentEvent::WaitingForInput {
+                        session_id,
+                        cwd,
+                        message: None,
+                    })
+                } else if next(transitions.compacting) {
+                    Some(AgentEvent::Compacting { session_id, cwd })
+                } else {
+                    None
+                };
+                if let Some(stop) = stop {
+                    session.phase = Phase::Stopped;
+                    events.push(stop);
+                }
+                events
+            }
+            Phase::Stopped => {
+                if !rng.chance(transitions.resume) {
+                    return Vec::new();
+                }
+                session.phase = Phase::Running;
+                vec![AgentEvent::Activity { session_id, cwd }]
+            }
+        }
+    }
+
+    /// Delay before the next step
+    pub fn next_delay(&mut self) -> Duration {
+        let spread = self.script.jitter.clamp(0.0, 1.0) * (self.rng.unit() * 2.0 - 1.0);
+        Duration::from_millis((self.script.tick_ms as f64 * (1.0 + spread)) as u64)
+    }
+
+    /// A weighted random tool and label
+    fn pick_tool(&mut self) -> Option<(String, Option<String>)> {
+        let total: u32 = self.script.tools.iter().map(|t| t.weight).sum();
+        if total == 0 {
+            return None;
+        }
+        let mut roll = self.rng.below(total as usize) as u32;
+        let tool = self.script.tools.iter().find(|tool| {
+            if roll < tool.weight {
+                return true;
+            }
+            roll -= tool.weight;
+            false
+        })?;
+        let label = (!tool.labels.is_empty())
+            .then(|| tool.labels[self.rng.below(tool.labels.len())].clone());
+        Some((tool.name.clone(), label))
+    }
+}
+
+/// The `i`th entry, cycling
+fn pick(items: &[String], i: usize) -> Option<&str> {
+    (!items.is_empty()).then(|| items[i % items.len()].as_str())
+}
+
+/// Send the simulated streams to the running daemon until `duration_secs`
+/// passes (forever when 0).
+pub fn run(script: Script, sessions: usize) -> Result<(), String> {
+    let duration = Duration::from_secs(script.duration_secs);
+    let mut simulator = Simulator::new(script, sessions);
+    let mut stream = ipc::connect()
+        .map_err(|e| format!("daemon not running ({}): {e}", ipc::socket_path().display()))?;
+    let mut send = |events: Vec<AgentEvent>| -> Result<(), String> {
+        for event in events {
+            let json = serde_json::to_string(&event).map_err(|e| e.to_string())?;
+            writeln!(stream, "{json}").map_err(|e| format!("daemon connection lost: {e}"))?;
+        }
+        Ok(())
+    };
+
+    send(simulator.start())?;
+    let started = Instant::now();
+    while duration.is_zero() || started.elapsed() < duration {
+        std::thread::sleep(simulator.next_delay());
+        send(simulator.step())?;
+    }
+    send(simulator.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::SessionRegistry;
+
+    #[test]
+    fn same_seed_same_stream() {
+        let events = |seed| {
+            let mut simulator = Simulator::new(
+                Script {
+                    seed,
+                    ..Script::default()
+                },
+                3,
+            );
+            let mut events = simulator.start();
+            for _ in 0..200 {
+                events.extend(simulator.step());
+            }
+            serde_json::to_string(&events).unwrap()
+        };
+        assert_eq!(events(7), events(7));
+        assert_ne!(events(7), events(8));
+    }
+
+    #[test]
+    fn streams_drive_the_registry() {
+        let mut simulator = Simulator::new(Script::default(), 4);
+        let mut registry = SessionRegistry::new();
+        for event in simulator.start() {
+            registry.process_event(event);
+        }
+        let sessions = registry.get_all();
+        assert_eq!(sessions.len(), 4);
+        assert!(
+            sessions
+                .iter()
+                .any(|s| s.name.as_deref() == Some("fix login"))
+        );
+
+        for _ in 0..500 {
+            for event in simulator.step() {
+                registry.process_event(event);
+            }
+        }
+        for event in simulator.finish() {
+            registry.process_event(event);
+        }
+        assert!(registry.get_all().is_empty());
+    }
+
+    #[test]
+    fn script_parses_with_defaults() {
+        let script: Script = toml::from_str(
+            r#"
+            seed = 9
+            [[tools]]
+            name = "Read"
+            [transitions]
+            idle = 0.5
+            "#,
+        )
+        .unwrap();
+        assert_eq!(script.seed, 9);
+        assert_eq!(script.tick_ms, 800);
+        assert_eq!(script.tools[0].weight, 1);
+        assert_eq!(script.transitions.idle, 0.5);
+        assert_eq!(script.transitions.resume, 0.25);
+    }
+
+    #[test]
+    fn jitter_stays_in_range() {
+        let mut simulator = Simulator::new(Script::default(), 1);
+        for _ in 0..100 {
+            let delay = simulator.next_delay();
+            assert!(delay >= Duration::from_millis(400) && delay <= Duration::from_millis(1200));
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! Supervisor - restart background tasks that panic or stop
+//!
+//! The daemon's long-running tasks (the Codex watcher, the IPC server, stale
+//! detection, ...) are started through [`spawn`]/[`supervise`]. When one
+//! panics or returns, the supervisor logs it and starts it again after an
+//! exponential backoff, reset once a run has stayed up for a while. A task
+//! that keeps failing is listed in [`failing`], which puts a warning badge on
+//! the indicator until a restart of it stays up.
+
+use std::collections::BTreeSet;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+/// Tasks whose latest restarts all failed quickly
+static FAILING: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+
+/// Names of the tasks currently failing, for the indicator badge
+pub fn failing() -> Vec<&'static str> {
+    FAILING
+        .lock()
+        .map(|tasks| tasks.iter().copied().collect())
+        .unwrap_or_default()
+}
+
+fn set_failing(name: &'static str, failing: bool) {
+    if let Ok(mut tasks) = FAILING.lock() {
+        if failing {
+            tasks.insert(name);
+        } else {
+            tasks.remove(name);
+        }
+    }
+}
+
+/// Restart timing
+#[derive(Debug, Clone, Copy)]
+struct Policy {
+    /// Delay before the first restart, doubled for each further failure
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    /// A run this long counts as healthy: the backoff starts over and the
+    /// task stops counting as failing
+    healthy_after: Duration,
+    /// Consecutive failures before the task counts as failing
+    failing_after: u32,
+}
+
+const POLICY: Policy = Policy {
+    initial_backoff: Duration::from_secs(1),
+    max_backoff: Duration::from_secs(5 * 60),
+    healthy_after: Duration::from_secs(60),
+    failing_after: 3,
+};
+
+impl Policy {
+    /// Delay before restarting after `failures` consecutive failures (≥ 1)
+    fn backoff(&self, failures: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(failures.saturating_sub(1)))
+            .min(self.max_backoff)
+    }
+}
+
+/// Spawn `make()`'s task under supervision
+pub fn spawn<F, Fut>(name: &'static str, make: F)
+where
+    F: Fn() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    tokio::spawn(supervise(name, make));
+}
+
+/// Run `make()`'s task, restarting it whenever it panics or returns; only
+/// returns when the runtime shuts down
+pub async fn supervise<F, Fut>(name: &'static str, make: F)
+where
+    F: Fn() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    supervise_with(POLICY, name, make).await;
+}
+
+async fn supervise_with<F, Fut>(policy: Policy, name: &'static str, make: F)
+where
+    F: Fn() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let mut failures = 0u32;
+    loop {
+        let mut task = tokio::spawn(make());
+        let healthy = tokio::time::sleep(policy.healthy_after);
+        tokio::pin!(healthy);
+        let result = tokio::select! {
+            result = &mut task => result,
+            () = &mut healthy => {
+                if failures > 0 {
+                    info!(task = name, "task recovered");
+                }
+                failures = 0;
+                set_failing(name, false);
+                (&mut task).await
+            }
+        };
+
+        match result {
+            Err(e) if e.is_cancelled() => return,
+            Err(e) => {
+                let payload = e.into_panic();
+                let message = payload
+                    .downcast_ref::<&str>()
+                    .copied()
+                    .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                    .unwrap_or("(no message)");
+                error!(task = name, panic = message, "task panicked");
+            }
+            Ok(()) => warn!(task = name, "task stopped"),
+        }
+
+        failures = failures.saturating_add(1);
+        if failures == policy.failing_after {
+            error!(task = name, failures, "task keeps failing");
+            set_failing(name, true);
+        }
+        let delay = policy.backoff(failures);
+        info!(task = name, ?delay, "restarting task");
+        tokio::time::sleep(delay).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        assert_eq!(POLICY.backoff(1), Duration::from_secs(1));
+        assert_eq!(POLICY.backoff(2), Duration::from_secs(2));
+        assert_eq!(POLICY.backoff(5), Duration::from_secs(16));
+        assert_eq!(POLICY.backoff(12), POLICY.max_backoff);
+        assert_eq!(POLICY.backoff(u32::MAX), POLICY.max_backoff);
+    }
+
+    #[tokio::test]
+    async fn panicking_task_is_restarted_and_flagged_until_it_stays_up() {
+        let policy = Policy {
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(4),
+            healthy_after: Duration::from_millis(50),
+            failing_after: 2,
+        };
+        let runs = Arc::new(AtomicU32::new(0));
+        let task_runs = Arc::clone(&runs);
+        let supervisor = tokio::spawn(supervise_with(policy, "test task", move || {
+            let run = task_runs.fetch_add(1, Ordering::SeqCst) + 1;
+            async move {
+                if run <= 3 {
+                    panic!("run {run} fails");
+                }
+                std::future::pending::<()>().await;
+            }
+        }));
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 4);
+        assert!(failing().contains(&"test task"));
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!failing().contains(&"test task"));
+        supervisor.abort();
+    }
+}
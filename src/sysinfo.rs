@@ -0,0 +1,230 @@
+//! System load - how hard each session's agent works the machine
+//!
+//! Optional (`ui.show_cpu_load`). Every [`SAMPLE_INTERVAL`] one `ps` call
+//! lists all processes; a session's load is the CPU of its agent process plus
+//! everything it spawned (tools, builds, test runs). Agents are found through
+//! the hook's parent PID ([`crate::TerminalInfo::pid`], Claude Code) or the
+//! process holding the transcript open (`lsof`, Codex rollouts), looked up
+//! once per session. Busy rows get a CPU badge, and while a session is over
+//! [`HIGH_LOAD`] on battery power the HUD stops animating
+//! ([`power_saving`]).
+
+use crate::SessionInfo;
+use crate::registry::SharedRegistry;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// How often processes are sampled
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+/// Loads are rounded to this many percent, so small swings don't redraw
+const LOAD_STEP: f32 = 5.0;
+/// CPU percent (of one core) from which a session counts as busy
+pub const HIGH_LOAD: u16 = 80;
+
+/// On battery with a busy session
+static POWER_SAVING: AtomicBool = AtomicBool::new(false);
+
+/// Whether the HUD should hold its animations to save power
+pub fn power_saving() -> bool {
+    POWER_SAVING.load(Ordering::Relaxed)
+}
+
+/// One `ps` row
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Process {
+    pid: u32,
+    ppid: u32,
+    cpu: f32,
+}
+
+/// Rows of `ps -A -o pid=,ppid=,%cpu=`
+fn parse_ps(output: &str) -> Vec<Process> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            Some(Process {
+                pid: fields.next()?.parse().ok()?,
+                ppid: fields.next()?.parse().ok()?,
+                cpu: fields.next()?.replace(',', ".").parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// CPU of `root` and all its descendants; `None` once `root` is gone
+fn tree_cpu(processes: &[Process], root: u32) -> Option<f32> {
+    let mut children: HashMap<u32, Vec<&Process>> = HashMap::new();
+    for process in processes {
+        children.entry(process.ppid).or_default().push(process);
+    }
+    let mut total = processes.iter().find(|p| p.pid == root)?.cpu;
+    let mut stack = vec![root];
+    while let Some(pid) = stack.pop() {
+        for child in children.get(&pid).into_iter().flatten() {
+            // pid 0 parents itself on some systems
+            if child.pid != pid {
+                total += child.cpu;
+                stack.push(child.pid);
+            }
+        }
+    }
+    Some(total)
+}
+
+fn round_load(cpu: f32) -> u16 {
+    ((cpu / LOAD_STEP).round() * LOAD_STEP).clamp(0.0, f32::from(u16::MAX)) as u16
+}
+
+fn list_processes() -> Vec<Process> {
+    std::process::Command::new("ps")
+        .args(["-A", "-o", "pid=,ppid=,%cpu="])
+        .output()
+        .map(|output| parse_ps(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default()
+}
+
+/// Process holding `path` open
+fn file_holder(path: &str) -> Option<u32> {
+    let output = std::process::Command::new("lsof")
+        .args(["-t", "--", path])
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.trim().parse().ok())
+}
+
+/// Whether `pmset -g batt` reports battery power
+fn parse_pmset(output: &str) -> bool {
+    output.contains("'Battery Power'")
+}
+
+fn on_battery() -> bool {
+    cfg!(target_os = "macos")
+        && std::process::Command::new("pmset")
+            .args(["-g", "batt"])
+            .output()
+            .is_ok_and(|output| parse_pmset(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Agent process per session, `None` when it couldn't be found
+#[derive(Debug, Default)]
+struct AgentPids(HashMap<String, Option<u32>>);
+
+impl AgentPids {
+    fn get(&mut self, session: &SessionInfo) -> Option<u32> {
+        if let Some(pid) = session.terminal.as_ref().and_then(|t| t.pid) {
+            return Some(pid);
+        }
+        *self
+            .0
+            .entry(session.session_id.clone())
+            .or_insert_with(|| session.transcript_path.as_deref().and_then(file_holder))
+    }
+
+    /// Forget sessions no longer listed
+    fn retain(&mut self, sessions: &[SessionInfo]) {
+        self.0
+            .retain(|id, _| sessions.iter().any(|s| s.session_id == *id));
+    }
+}
+
+/// Rounded load of every session whose agent is running
+fn loads(
+    processes: &[Process],
+    sessions: &[SessionInfo],
+    mut pid: impl FnMut(&SessionInfo) -> Option<u32>,
+) -> HashMap<String, u16> {
+    sessions
+        .iter()
+        .filter(|session| session.state != crate::SessionState::Stale)
+        .filter_map(|session| {
+            let cpu = tree_cpu(processes, pid(session)?)?;
+            Some((session.session_id.clone(), round_load(cpu)))
+        })
+        .collect()
+}
+
+/// Sample until the runtime shuts down.
+pub async fn run(registry: SharedRegistry) {
+    let mut pids = AgentPids::default();
+    let mut interval = tokio::time::interval(SAMPLE_INTERVAL);
+    loop {
+        interval.tick().await;
+        let sessions = registry.snapshot();
+        let sampled = tokio::task::spawn_blocking(move || {
+            pids.retain(&sessions);
+            let loads = loads(&list_processes(), &sessions, |s| pids.get(s));
+            (pids, sessions, loads, on_battery())
+        })
+        .await;
+        let Ok((returned, sessions, loads, battery)) = sampled else {
+            break;
+        };
+        pids = returned;
+
+        POWER_SAVING.store(
+            battery && loads.values().any(|load| *load >= HIGH_LOAD),
+            Ordering::Relaxed,
+        );
+        let changed = sessions
+            .iter()
+            .any(|s| s.cpu_percent != loads.get(&s.session_id).copied());
+        if changed {
+            registry.update(|reg| reg.set_cpu_loads(&loads));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_covers_the_agent_and_its_descendants() {
+        let processes = parse_ps(concat!(
+            "    1     0   0.0\n",
+            "  100     1  12.5\n",
+            "  200   100  80,1\n",
+            "  300   200  20.0\n",
+            "  400     1  50.0\n",
+        ));
+        assert_eq!(processes.len(), 5);
+        let agent = tree_cpu(&processes, 100).unwrap();
+        assert!((agent - 112.6).abs() < 0.01);
+        assert_eq!(tree_cpu(&processes, 400), Some(50.0));
+        assert_eq!(tree_cpu(&processes, 999), None);
+        assert_eq!(round_load(112.6), 115);
+        assert_eq!(round_load(1.0), 0);
+    }
+
+    #[test]
+    fn loads_skip_stale_sessions_and_vanished_agents() {
+        let processes = parse_ps("100 1 42.0\n");
+        let session = |id: &str, state| SessionInfo {
+            session_id: id.into(),
+            state,
+            ..Default::default()
+        };
+        let sessions = [
+            session("running", crate::SessionState::Running),
+            session("stale", crate::SessionState::Stale),
+            session("gone", crate::SessionState::Running),
+        ];
+        let loads = loads(&processes, &sessions, |s| match s.session_id.as_str() {
+            "gone" => Some(555),
+            _ => Some(100),
+        });
+        assert_eq!(loads, HashMap::from([("running".to_string(), 40)]));
+    }
+
+    #[test]
+    fn battery_from_pmset() {
+        assert!(parse_pmset(
+            "Now drawing from 'Battery Power'\n -InternalBattery-0 (id=1)\t81%; discharging;"
+        ));
+        assert!(!parse_pmset("Now drawing from 'AC Power'\n"));
+    }
+}
@@ -0,0 +1,199 @@
+//! Session tags - free-form labels for grouping and filtering sessions
+//!
+//! Tags come from three places:
+//! - the agent running `aura set-tag billing` (picked up from its Bash tool call,
+//!   like `aura set-name`),
+//! - the session list's "Edit tags" row action and `aura sessions tag`,
+//! - rules in config.json applied when a session starts:
+//!
+//! ```json
+//! "tags": [
+//!   { "tag": "billing", "cwd": "*/billing-*" },
+//!   { "tag": "hotfix", "branch": "hotfix/*" }
+//! ]
+//! ```
+//!
+//! Tags are lowercase, without whitespace or a leading `#` (`#Billing` and
+//! `billing` are the same tag).
+
+use crate::config::TagRule;
+use std::path::Path;
+
+/// Normalize user input into a tag, or `None` if nothing is left
+pub fn normalize_tag(input: &str) -> Option<String> {
+    let tag = input.trim().trim_start_matches('#').to_lowercase();
+    (!tag.is_empty() && !tag.chars().any(char::is_whitespace)).then_some(tag)
+}
+
+/// Split `billing, hotfix #q3` into tags (commas and whitespace separate)
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter_map(normalize_tag)
+    {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+/// `#billing #hotfix` for display
+pub fn format_tags(tags: &[String]) -> String {
+    tags.iter()
+        .map(|tag| format!("#{tag}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Shell-style match supporting `*` (any run of characters) and `?` (one)
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text index it was tried at
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, tried)) => {
+                    p = star + 1;
+                    t = tried + 1;
+                    backtrack = Some((star, tried + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Current git branch of the repository containing `cwd` (reads `HEAD`
+/// directly; `None` when detached or not in a repository)
+pub fn git_branch(cwd: &Path) -> Option<String> {
+    let dot_git = cwd
+        .ancestors()
+        .map(|dir| dir.join(".git"))
+        .find(|p| p.exists())?;
+    let git_dir = if dot_git.is_file() {
+        // Worktrees and submodules: ".git" is a "gitdir: <path>" pointer
+        let pointer = std::fs::read_to_string(&dot_git).ok()?;
+        let target = pointer.trim().strip_prefix("gitdir:")?.trim();
+        dot_git.parent()?.join(target)
+    } else {
+        dot_git
+    };
+    let head = std::fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    head.trim()
+        .strip_prefix("ref: refs/heads/")
+        .map(str::to_string)
+}
+
+/// Tags from config rules for a session starting in `cwd`.
+///
+/// A rule applies when all of its patterns match; a rule without patterns
+/// never applies.
+pub fn inferred_tags(rules: &[TagRule], cwd: &str) -> Vec<String> {
+    if rules.is_empty() {
+        return Vec::new();
+    }
+    let mut branch: Option<Option<String>> = None;
+    let mut tags = Vec::new();
+    for rule in rules {
+        if rule.cwd.is_none() && rule.branch.is_none() {
+            continue;
+        }
+        let cwd_matches = rule.cwd.as_deref().is_none_or(|p| glob_match(p, cwd));
+        let branch_matches = rule.branch.as_deref().is_none_or(|p| {
+            branch
+                .get_or_insert_with(|| git_branch(Path::new(cwd)))
+                .as_deref()
+                .is_some_and(|b| glob_match(p, b))
+        });
+        if cwd_matches
+            && branch_matches
+            && let Some(tag) = normalize_tag(&rule.tag)
+            && !tags.contains(&tag)
+        {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(tag: &str, cwd: Option<&str>, branch: Option<&str>) -> TagRule {
+        TagRule {
+            tag: tag.into(),
+            cwd: cwd.map(Into::into),
+            branch: branch.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn normalize_and_parse_tags() {
+        assert_eq!(normalize_tag(" #Billing "), Some("billing".into()));
+        assert_eq!(normalize_tag("#"), None);
+        assert_eq!(
+            parse_tags("billing, Hotfix #q3 billing"),
+            vec!["billing", "hotfix", "q3"]
+        );
+        assert_eq!(format_tags(&parse_tags("a b")), "#a #b");
+    }
+
+    #[test]
+    fn glob_match_wildcards() {
+        assert!(glob_match("hotfix/*", "hotfix/login"));
+        assert!(glob_match("*/billing-*", "/code/billing-api"));
+        assert!(glob_match("v?.*", "v1.2"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("hotfix/*", "feature/hotfix/x"));
+        assert!(!glob_match("*billing", "/code/billing-api"));
+    }
+
+    #[test]
+    fn git_branch_reads_head() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = dir.path().join(".git");
+        std::fs::create_dir(&git).unwrap();
+        std::fs::write(git.join("HEAD"), "ref: refs/heads/hotfix/login\n").unwrap();
+        let sub = dir.path().join("src");
+        std::fs::create_dir(&sub).unwrap();
+        assert_eq!(git_branch(&sub).as_deref(), Some("hotfix/login"));
+
+        std::fs::write(
+            git.join("HEAD"),
+            "4b825dc642cb6eb9a060e54bf8d69288fbee4904\n",
+        )
+        .unwrap();
+        assert_eq!(git_branch(&sub), None);
+    }
+
+    #[test]
+    fn inferred_tags_match_cwd_and_branch() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join(".git")).unwrap();
+        std::fs::write(dir.path().join(".git/HEAD"), "ref: refs/heads/hotfix/x\n").unwrap();
+        let cwd = dir.path().to_str().unwrap();
+
+        let rules = vec![
+            rule("Hotfix", None, Some("hotfix/*")),
+            rule("tmp", Some("*"), None),
+            rule("release", None, Some("release/*")),
+            rule("never", None, None),
+        ];
+        assert_eq!(inferred_tags(&rules, cwd), vec!["hotfix", "tmp"]);
+    }
+}
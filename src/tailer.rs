@@ -0,0 +1,592 @@
+//! JSONL file tailing shared by the Codex rollout watcher and the transcript viewer
+//!
+//! A [`JsonlTail`] remembers how far into a file it has read and carries a
+//! trailing partial line to the next read (as bytes, so a multi-byte character
+//! split across reads stays intact). Callers parse each complete line with
+//! their own closure, and decide what a truncated file means: the transcript
+//! viewer starts over, the Codex watcher re-bootstraps. [`Replay`] keeps the
+//! last few parsed items for a bounded bootstrap replay.
+//!
+//! Memory stays bounded however large the file: reads go through a
+//! [`READ_CHUNK`] buffer, a line longer than [`MAX_LINE`] is dropped instead of
+//! buffered, and [`JsonlTail::seek_near_end`] starts a bootstrap inside a
+//! window at the end of the file.
+
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tracing::{debug, warn};
+
+/// Bytes read from the file at a time
+pub const READ_CHUNK: usize = 256 * 1024;
+
+/// Longest line kept; longer ones are skipped
+pub const MAX_LINE: usize = 8 * 1024 * 1024;
+
+/// Outcome of one [`JsonlTail`] read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Progress {
+    /// The file didn't grow
+    Unchanged,
+    /// New bytes were read (the last line may still be incomplete)
+    Appended,
+    /// The file shrank; the cursor is back at the start and nothing was read
+    Truncated,
+}
+
+/// Read position in one JSONL file
+#[derive(Debug)]
+pub struct JsonlTail {
+    path: PathBuf,
+    offset: u64,
+    /// Trailing partial line carried to the next read
+    buffer: Vec<u8>,
+    max_line: usize,
+    /// Dropping the rest of a line (over `max_line`, or cut by a seek) until
+    /// the next newline
+    skipping: bool,
+}
+
+impl JsonlTail {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self::at(path, 0)
+    }
+
+    /// Start reading at `offset` (e.g. the file length, to skip history)
+    pub fn at(path: impl AsRef<Path>, offset: u64) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            offset,
+            buffer: Vec::new(),
+            max_line: MAX_LINE,
+            skipping: false,
+        }
+    }
+
+    /// Skip lines longer than `max_line` bytes instead of [`MAX_LINE`]
+    pub fn with_max_line(mut self, max_line: usize) -> Self {
+        self.max_line = max_line;
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Bytes consumed so far, including a pending partial line
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Bytes of a trailing line still waiting for its newline
+    pub fn pending(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Move the cursor, dropping any partial line
+    pub fn seek_to(&mut self, offset: u64) {
+        self.offset = offset;
+        self.buffer.clear();
+        self.skipping = false;
+    }
+
+    /// Move the cursor to the last `window` bytes of a `len`-byte file (or its
+    /// start, if it's smaller). A line cut by the window is skipped; one
+    /// starting right at it is kept.
+    pub fn seek_near_end(&mut self, len: u64, window: u64) {
+        let start = len.saturating_sub(window);
+        // Skip from the byte before the window: if that's the previous line's
+        // newline, it's all that gets dropped
+        self.seek_to(start.saturating_sub(1));
+        self.skipping = start > 0;
+    }
+
+    /// Read what was appended since the last call, passing each complete line
+    /// to `on_line`. Malformed lines are skipped.
+    pub fn read_new(&mut self, mut on_line: impl FnMut(&Value)) -> std::io::Result<Progress> {
+        let mut file = std::fs::File::open(&self.path)?;
+        let len = file.metadata()?.len();
+        if let Some(progress) = self.check_len(len) {
+            return Ok(progress);
+        }
+
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut chunk = vec![0; READ_CHUNK.min((len - self.offset) as usize)];
+        while self.offset < len {
+            let want = chunk.len().min((len - self.offset) as usize);
+            let read = file.read(&mut chunk[..want])?;
+            if read == 0 {
+                break;
+            }
+            self.ingest(&chunk[..read], &mut on_line);
+        }
+        Ok(Progress::Appended)
+    }
+
+    /// [`read_new`](Self::read_new) without blocking the runtime
+    pub async fn read_new_async(
+        &mut self,
+        mut on_line: impl FnMut(&Value),
+    ) -> std::io::Result<Progress> {
+        let mut file = tokio::fs::File::open(&self.path).await?;
+        let len = file.metadata().await?.len();
+        if let Some(progress) = self.check_len(len) {
+            return Ok(progress);
+        }
+
+        file.seek(SeekFrom::Start(self.offset)).await?;
+        let mut chunk = vec![0; READ_CHUNK.min((len - self.offset) as usize)];
+        while self.offset < len {
+            let want = chunk.len().min((len - self.offset) as usize);
+            let read = file.read(&mut chunk[..want]).await?;
+            if read == 0 {
+                break;
+            }
+            self.ingest(&chunk[..read], &mut on_line);
+        }
+        Ok(Progress::Appended)
+    }
+
+    fn check_len(&mut self, len: u64) -> Option<Progress> {
+        if len < self.offset {
+            self.seek_to(0);
+            return Some(Progress::Truncated);
+        }
+        (len == self.offset).then_some(Progress::Unchanged)
+    }
+
+    fn ingest(&mut self, bytes: &[u8], mut on_line: impl FnMut(&Value)) {
+        self.offset += bytes.len() as u64;
+        let mut bytes = bytes;
+        if self.skipping {
+            let Some(newline) = bytes.iter().position(|b| *b == b'\n') else {
+                return;
+            };
+            bytes = &bytes[newline + 1..];
+            self.skipping = false;
+        }
+        self.buffer.extend_from_slice(bytes);
+
+        if let Some(last_newline) = self.buffer.iter().rposition(|b| *b == b'\n') {
+            let rest = self.buffer.split_off(last_newline + 1);
+            let complete = std::mem::replace(&mut self.buffer, rest);
+            for line in complete.split(|b| *b == b'\n') {
+                if line.len() > self.max_line {
+                    warn!(path = %self.path.display(), bytes = line.len(), "skipping overlong JSONL line");
+                    continue;
+                }
+                let line = String::from_utf8_lossy(line);
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                match serde_json::from_str::<Value>(line) {
+                    Ok(value) => on_line(&value),
+                    // Avoid logging the line itself (may contain user content)
+                    Err(e) => {
+                        debug!(path = %self.path.display(), error = %e, "skipping malformed JSONL line")
+                    }
+                }
+            }
+        }
+
+        if self.buffer.len() > self.max_line {
+            warn!(path = %self.path.display(), bytes = self.buffer.len(), "skipping overlong JSONL line");
+            self.buffer.clear();
+            self.skipping = true;
+        }
+    }
+}
+
+/// The last `max` items pushed, oldest first
+#[derive(Debug)]
+pub struct Replay<T> {
+    max: usize,
+    items: VecDeque<T>,
+}
+
+impl<T> Replay<T> {
+    pub fn new(max: usize) -> Self {
+        Self {
+            max,
+            items: VecDeque::with_capacity(max),
+        }
+    }
+
+    /// Keep `item`, dropping the oldest once `max` are held
+    pub fn push(&mut self, item: T) {
+        if self.max == 0 {
+            return;
+        }
+        if self.items.len() == self.max {
+            self.items.pop_front();
+        }
+        self.items.push_back(item);
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl<T> IntoIterator for Replay<T> {
+    type Item = T;
+    type IntoIter = std::collections::vec_deque::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn ids(tail: &mut JsonlTail) -> (Progress, Vec<u64>) {
+        let mut ids = Vec::new();
+        let progress = tail
+            .read_new(|value| ids.extend(value["id"].as_u64()))
+            .unwrap();
+        (progress, ids)
+    }
+
+    #[test]
+    fn reads_complete_lines_and_keeps_partial_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("t.jsonl");
+        let mut file = std::fs::File::create(&path).unwrap();
+        write!(file, "{{\"id\":1}}\n\n{{\"id\":").unwrap();
+        file.flush().unwrap();
+
+        let mut tail = JsonlTail::new(&path);
+        assert_eq!(ids(&mut tail), (Progress::Appended, vec![1]));
+        assert_eq!(tail.pending(), 6);
+
+        writeln!(file, "2}}\nnot json\n{{\"id\":3}}").unwrap();
+        file.flush().unwrap();
+        assert_eq!(ids(&mut tail), (Progress::Appended, vec![2, 3]));
+        assert_eq!(ids(&mut tail), (Progress::Unchanged, vec![]));
+        assert_eq!(tail.offset(), std::fs::metadata(&path).unwrap().len());
+    }
+
+    #[test]
+    fn keeps_multibyte_characters_split_across_reads() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("t.jsonl");
+        let line = "{\"text\":\"→\"}\n".as_bytes();
+        let split = line.iter().position(|b| *b == 0xE2).unwrap() + 1;
+        std::fs::write(&path, &line[..split]).unwrap();
+
+        let mut tail = JsonlTail::new(&path);
+        let mut texts = Vec::new();
+        tail.read_new(|value| texts.push(value["text"].as_str().unwrap().to_string()))
+            .unwrap();
+        assert!(texts.is_empty());
+
+        std::fs::write(&path, line).unwrap();
+        tail.read_new(|value| texts.push(value["text"].as_str().unwrap().to_string()))
+            .unwrap();
+        assert_eq!(texts, vec!["→"]);
+    }
+
+    #[test]
+    fn reports_truncation_without_reading() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("t.jsonl");
+        std::fs::write(&path, "{\"id\":1}\n{\"id\":2}\n").unwrap();
+        let mut tail = JsonlTail::new(&path);
+        ids(&mut tail);
+
+        std::fs::write(&path, "{\"id\":3}\n").unwrap();
+        assert_eq!(ids(&mut tail), (Progress::Truncated, vec![]));
+        assert_eq!(tail.offset(), 0);
+        assert_eq!(ids(&mut tail), (Progress::Appended, vec![3]));
+    }
+
+    #[tokio::test]
+    async fn async_read_matches_blocking_read() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("t.jsonl");
+        std::fs::write(&path, "{\"id\":1}\n{\"id\":2}\n{\"id\"").unwrap();
+
+        let mut tail = JsonlTail::at(&path, 0);
+        let mut seen = Vec::new();
+        let progress = tail
+            .read_new_async(|value| seen.extend(value["id"].as_u64()))
+            .await
+            .unwrap();
+        assert_eq!(progress, Progress::Appended);
+        assert_eq!(seen, vec![1, 2]);
+        assert_eq!(tail.pending(), 5);
+    }
+
+    #[test]
+    fn replay_keeps_the_latest_items() {
+        let mut replay = Replay::new(2);
+        for i in 0..5 {
+            replay.push(i);
+        }
+        assert_eq!(replay.len(), 2);
+        assert_eq!(replay.into_iter().collect::<Vec<_>>(), vec![3, 4]);
+
+        let mut none = Replay::new(0);
+        none.push(1);
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn skips_lines_over_the_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("t.jsonl");
+        let long = format!("{{\"id\":9,\"pad\":\"{}\"}}", "x".repeat(1000));
+        let mut file = std::fs::File::create(&path).unwrap();
+        writeln!(file, "{{\"id\":1}}").unwrap();
+        // An overlong line arriving across several reads
+        write!(file, "{}", &long[..500]).unwrap();
+        file.flush().unwrap();
+
+        let mut tail = JsonlTail::new(&path).with_max_line(64);
+        assert_eq!(ids(&mut tail), (Progress::Appended, vec![1]));
+        assert_eq!(tail.pending(), 0);
+
+        writeln!(file, "{}", &long[500..]).unwrap();
+        writeln!(file, "{{\"id\":2}}\n{long}\n{{\"id\":3}}").unwrap();
+        file.flush().unwrap();
+        assert_eq!(ids(&mut tail), (Progress::Appended, vec![2, 3]));
+        assert_eq!(tail.pending(), 0);
+    }
+
+    #[test]
+    fn reads_files_larger_than_a_chunk() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("t.jsonl");
+        let line = format!("{{\"id\":7,\"pad\":\"{}\"}}\n", "x".repeat(1000));
+        let count = READ_CHUNK / line.len() * 3;
+        std::fs::write(&path, line.repeat(count)).unwrap();
+
+        let mut tail = JsonlTail::new(&path);
+        let (_, seen) = ids(&mut tail);
+        assert_eq!(seen.len(), count);
+    }
+
+    #[test]
+    fn seek_near_end_skips_the_cut_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("t.jsonl");
+        std::fs::write(&path, "{\"id\":1}\n{\"id\":2}\n{\"id\":3}\n").unwrap();
+        let len = std::fs::metadata(&path).unwrap().len();
+
+        let mut tail = JsonlTail::new(&path);
+        tail.seek_near_end(len, 12);
+        assert_eq!(ids(&mut tail), (Progress::Appended, vec![3]));
+
+        tail.seek_near_end(len, 1000);
+        assert_eq!(tail.offset(), 0);
+        assert_eq!(ids(&mut tail).1, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn seek_near_end_keeps_a_line_starting_at_the_window() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("t.jsonl");
+        std::fs::write(&path, "{\"id\":1}\n{\"id\":2}\n{\"id\":3}\n").unwrap();
+        let len = std::fs::metadata(&path).unwrap().len();
+
+        let mut tail = JsonlTail::new(&path);
+        tail.seek_near_end(len, 9);
+        assert_eq!(ids(&mut tail), (Progress::Appended, vec![3]));
+
+        tail.seek_near_end(len, 18);
+        assert_eq!(ids(&mut tail).1, vec![2, 3]);
+    }
+
+    mod properties {
+        use super::*;
+        use proptest::prelude::*;
+
+        /// One JSONL record: an id and text that may hold any characters
+        fn record(id: usize, text: &str) -> String {
+            serde_json::json!({ "id": id, "text": text }).to_string()
+        }
+
+        /// `texts` as JSONL, each line ended by `\n` or, when its flag is
+        /// set, `\r\n`
+        fn jsonl(texts: &[(String, bool)]) -> String {
+            texts
+                .iter()
+                .enumerate()
+                .map(|(id, (text, crlf))| {
+                    let end = if *crlf { "\r\n" } else { "\n" };
+                    format!("{}{end}", record(id, text))
+                })
+                .collect()
+        }
+
+        /// `bytes` cut at the given positions (any byte, mid-character too)
+        fn chunks(bytes: &[u8], cuts: &[prop::sample::Index]) -> Vec<Vec<u8>> {
+            let mut cuts: Vec<usize> = cuts.iter().map(|cut| cut.index(bytes.len() + 1)).collect();
+            cuts.extend([0, bytes.len()]);
+            cuts.sort_unstable();
+            cuts.windows(2)
+                .map(|w| bytes[w[0]..w[1]].to_vec())
+                .collect()
+        }
+
+        fn texts() -> impl Strategy<Value = Vec<(String, bool)>> {
+            prop::collection::vec((any::<String>(), any::<bool>()), 0..20)
+        }
+
+        fn parsed(values: &[Value]) -> Vec<(u64, String)> {
+            values
+                .iter()
+                .map(|value| {
+                    let text = value["text"].as_str().unwrap_or_default().to_string();
+                    (value["id"].as_u64().unwrap(), text)
+                })
+                .collect()
+        }
+
+        fn expected(texts: &[(String, bool)]) -> Vec<(u64, String)> {
+            texts
+                .iter()
+                .zip(0u64..)
+                .map(|((text, _), id)| (id, text.clone()))
+                .collect()
+        }
+
+        proptest! {
+            #[test]
+            fn any_chunking_yields_every_line_once(
+                texts in texts(),
+                cuts in prop::collection::vec(any::<prop::sample::Index>(), 0..40),
+                partial in any::<String>(),
+            ) {
+                // A record still being written follows the complete ones
+                let mut content = jsonl(&texts).into_bytes();
+                let last = record(texts.len(), &partial);
+                let partial = &last.as_bytes()[..last.len() - 1];
+                content.extend_from_slice(partial);
+
+                let mut tail = JsonlTail::new("unused");
+                let mut seen = Vec::new();
+                for chunk in chunks(&content, &cuts) {
+                    tail.ingest(&chunk, |value| seen.push(value.clone()));
+                }
+                prop_assert_eq!(parsed(&seen), expected(&texts));
+                prop_assert_eq!(tail.offset(), content.len() as u64);
+                prop_assert_eq!(tail.pending(), partial.len());
+
+                // Its newline completes it, however many reads it spanned
+                tail.ingest(b"}\n", |value| seen.push(value.clone()));
+                prop_assert_eq!(seen.len(), texts.len() + 1);
+            }
+
+            #[test]
+            fn overlong_lines_never_swallow_their_neighbours(
+                texts in prop::collection::vec((any::<String>(), any::<bool>()), 1..12),
+                long in prop::collection::vec(any::<bool>(), 1..12),
+                cuts in prop::collection::vec(any::<prop::sample::Index>(), 0..40),
+            ) {
+                let max_line = 256;
+                let mut content = String::new();
+                let mut kept = Vec::new();
+                let lines = texts.iter().zip(long.iter().cycle()).enumerate();
+                for (id, ((text, crlf), &long)) in lines {
+                    let text: String = if long {
+                        "x".repeat(max_line)
+                    } else {
+                        text.chars().take(20).collect()
+                    };
+                    let end = if *crlf { "\r\n" } else { "\n" };
+                    content.push_str(&format!("{}{end}", record(id, &text)));
+                    if !long {
+                        kept.push((id as u64, text));
+                    }
+                }
+
+                let mut tail = JsonlTail::new("unused").with_max_line(max_line);
+                let mut seen = Vec::new();
+                for chunk in chunks(content.as_bytes(), &cuts) {
+                    tail.ingest(&chunk, |value| seen.push(value.clone()));
+                }
+                prop_assert_eq!(parsed(&seen), kept);
+                prop_assert_eq!(tail.pending(), 0);
+            }
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(48))]
+
+            #[test]
+            fn appends_and_truncation_keep_offsets_consistent(
+                before in texts(),
+                after in texts(),
+                cuts in prop::collection::vec(any::<prop::sample::Index>(), 0..8),
+            ) {
+                let dir = tempfile::tempdir().unwrap();
+                let path = dir.path().join("t.jsonl");
+                let mut file = std::fs::File::create(&path).unwrap();
+                let mut tail = JsonlTail::new(&path);
+                let mut seen = Vec::new();
+
+                // Appended a piece at a time, read after every write
+                let content = jsonl(&before).into_bytes();
+                for chunk in chunks(&content, &cuts) {
+                    file.write_all(&chunk).unwrap();
+                    file.flush().unwrap();
+                    tail.read_new(|value| seen.push(value.clone())).unwrap();
+                }
+                prop_assert_eq!(parsed(&seen), expected(&before));
+                prop_assert_eq!(tail.offset(), content.len() as u64);
+
+                // Rewritten shorter: start over and read only the new lines
+                let rewritten = jsonl(&after);
+                if rewritten.len() < content.len() {
+                    std::fs::write(&path, &rewritten).unwrap();
+                    seen.clear();
+                    let progress = tail.read_new(|value| seen.push(value.clone())).unwrap();
+                    prop_assert_eq!(progress, Progress::Truncated);
+                    prop_assert!(seen.is_empty());
+                    tail.read_new(|value| seen.push(value.clone())).unwrap();
+                    prop_assert_eq!(parsed(&seen), expected(&after));
+                    prop_assert_eq!(tail.offset(), rewritten.len() as u64);
+                }
+            }
+
+            #[test]
+            fn seek_near_end_yields_a_suffix_of_whole_lines(
+                texts in texts(),
+                window in 0u64..4096,
+            ) {
+                let dir = tempfile::tempdir().unwrap();
+                let path = dir.path().join("t.jsonl");
+                let content = jsonl(&texts);
+                std::fs::write(&path, &content).unwrap();
+
+                let mut tail = JsonlTail::new(&path);
+                tail.seek_near_end(content.len() as u64, window);
+                let mut seen = Vec::new();
+                tail.read_new(|value| seen.push(value.clone())).unwrap();
+
+                // The lines starting inside the window (or right at it), none cut
+                let start = (content.len() as u64).saturating_sub(window) as usize;
+                let mut line_start = 0;
+                let mut whole = Vec::new();
+                for (line, (id, text)) in content.split_inclusive('\n').zip(expected(&texts)) {
+                    if line_start >= start {
+                        whole.push((id, text));
+                    }
+                    line_start += line.len();
+                }
+                prop_assert_eq!(parsed(&seen), whole);
+            }
+        }
+    }
+}
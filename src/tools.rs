@@ -0,0 +1,159 @@
+//! Tool categories - normalize raw tool names across agents
+//!
+//! Claude Code reports tools by name (`Bash`, `Edit`, `mcp__github__create_pr`),
+//! Codex by call type or the program it runs (`apply_patch`, `rg`, `git`).
+//! Each name maps to a [`ToolCategory`] through a built-in table, which the
+//! config's `tool_categories` object extends: keys are tool names, or
+//! prefixes ending in `*` (`"mcp__linear__*": "vcs"`). Categories pick the
+//! fallback icon for tools without one of their own and group tool usage in
+//! `aura export`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Normalized kind of tool
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolCategory {
+    /// Shell commands
+    Shell,
+    /// File edits and writes
+    Edit,
+    /// File reads
+    Read,
+    /// Code and file search
+    Search,
+    /// Web fetches and searches
+    Web,
+    /// Version control and code hosting
+    Vcs,
+    /// MCP server tools
+    Mcp,
+    /// Subagents and task management
+    Agent,
+    #[default]
+    Other,
+}
+
+impl ToolCategory {
+    /// Lowercase name, as written in config
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Shell => "shell",
+            Self::Edit => "edit",
+            Self::Read => "read",
+            Self::Search => "search",
+            Self::Web => "web",
+            Self::Vcs => "vcs",
+            Self::Mcp => "mcp",
+            Self::Agent => "agent",
+            Self::Other => "other",
+        }
+    }
+}
+
+/// Category of a tool name from the built-in table
+pub fn builtin_category(tool_name: &str) -> ToolCategory {
+    match tool_name {
+        "Bash" | "BashOutput" | "KillShell" | "exec_command" | "shell" | "local_shell"
+        | "write_stdin" | "cargo" | "npm" | "pnpm" | "yarn" | "make" | "just" | "python"
+        | "python3" | "node" | "go" | "mix" => ToolCategory::Shell,
+        "Edit" | "MultiEdit" | "Write" | "NotebookEdit" | "apply_patch" => ToolCategory::Edit,
+        "Read" | "NotebookRead" | "cat" | "head" | "tail" | "sed" | "nl" => ToolCategory::Read,
+        "Grep" | "Glob" | "LS" | "rg" | "grep" | "find" | "fd" | "ls" => ToolCategory::Search,
+        "WebFetch" | "WebSearch" | "web_search" | "curl" | "wget" => ToolCategory::Web,
+        "git" | "gh" | "jj" | "hg" => ToolCategory::Vcs,
+        "Task" | "TodoWrite" | "update_plan" | "ExitPlanMode" => ToolCategory::Agent,
+        name if name.starts_with("mcp__") => ToolCategory::Mcp,
+        _ => ToolCategory::Other,
+    }
+}
+
+/// The built-in table plus config overrides
+#[derive(Debug, Clone, Default)]
+pub struct ToolCategories {
+    exact: BTreeMap<String, ToolCategory>,
+    /// (prefix, category), longest prefix first
+    prefixes: Vec<(String, ToolCategory)>,
+}
+
+impl ToolCategories {
+    /// Table from config `tool_categories`
+    pub fn new(overrides: &BTreeMap<String, ToolCategory>) -> Self {
+        let mut categories = Self::default();
+        for (pattern, &category) in overrides {
+            match pattern.strip_suffix('*') {
+                Some(prefix) => categories.prefixes.push((prefix.to_string(), category)),
+                None => {
+                    categories.exact.insert(pattern.clone(), category);
+                }
+            }
+        }
+        categories
+            .prefixes
+            .sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(&b.0)));
+        categories
+    }
+
+    /// Category of `tool_name`: an exact override, then the longest matching
+    /// prefix override, then the built-in table
+    pub fn categorize(&self, tool_name: &str) -> ToolCategory {
+        if let Some(&category) = self.exact.get(tool_name) {
+            return category;
+        }
+        self.prefixes
+            .iter()
+            .find(|(prefix, _)| tool_name.starts_with(prefix.as_str()))
+            .map_or_else(|| builtin_category(tool_name), |&(_, category)| category)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_table_covers_both_agents() {
+        assert_eq!(builtin_category("Bash"), ToolCategory::Shell);
+        assert_eq!(builtin_category("exec_command"), ToolCategory::Shell);
+        assert_eq!(builtin_category("apply_patch"), ToolCategory::Edit);
+        assert_eq!(builtin_category("rg"), ToolCategory::Search);
+        assert_eq!(
+            builtin_category("mcp__github__create_pr"),
+            ToolCategory::Mcp
+        );
+        assert_eq!(builtin_category("frobnicate"), ToolCategory::Other);
+    }
+
+    #[test]
+    fn overrides_win_exact_then_longest_prefix() {
+        let overrides = BTreeMap::from([
+            ("mcp__*".to_string(), ToolCategory::Web),
+            ("mcp__github__*".to_string(), ToolCategory::Vcs),
+            ("deploy".to_string(), ToolCategory::Shell),
+            ("Read".to_string(), ToolCategory::Search),
+        ]);
+        let categories = ToolCategories::new(&overrides);
+        assert_eq!(
+            categories.categorize("mcp__github__create_pr"),
+            ToolCategory::Vcs
+        );
+        assert_eq!(
+            categories.categorize("mcp__linear__list"),
+            ToolCategory::Web
+        );
+        assert_eq!(categories.categorize("deploy"), ToolCategory::Shell);
+        assert_eq!(categories.categorize("Read"), ToolCategory::Search);
+        assert_eq!(categories.categorize("Edit"), ToolCategory::Edit);
+    }
+
+    #[test]
+    fn categories_parse_from_config_names() {
+        let overrides: BTreeMap<String, ToolCategory> =
+            serde_json::from_str(r#"{"just": "shell", "mcp__jira__*": "vcs"}"#).unwrap();
+        assert_eq!(overrides["mcp__jira__*"], ToolCategory::Vcs);
+        assert_eq!(ToolCategory::Vcs.as_str(), "vcs");
+    }
+}
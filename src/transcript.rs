@@ -0,0 +1,597 @@
+//! Transcript parsing for the transcript viewer
+//!
+//! Reads Claude Code transcripts (`~/.claude/projects/**/<session>.jsonl`) and
+//! Codex rollouts (`~/.codex/sessions/**/rollout-*.jsonl`) into a flat list of
+//! conversation turns. Both are JSONL; [`TranscriptTail`] follows the file as
+//! the agent appends to it (see [`crate::tailer`]). [`MetaTracker`] keeps
+//! per-file [`TranscriptMeta`] totals current by parsing only appended bytes.
+
+use crate::tailer::{JsonlTail, Progress};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::{BufRead, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+/// Max characters of a tool label shown in a collapsed tool call
+const TOOL_LABEL_MAX: usize = 60;
+/// Bytes read from the end of a transcript by [`latest_model`]
+const MODEL_SCAN_WINDOW: u64 = 64 * 1024;
+
+/// One rendered item of a transcript
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranscriptEntry {
+    /// A prompt typed by the user
+    User { text: String },
+    /// Assistant reply text
+    Assistant { text: String },
+    /// A tool invocation; `input` is the pretty-printed arguments
+    ToolCall {
+        name: String,
+        label: Option<String>,
+        input: String,
+    },
+}
+
+/// Parse one transcript line (either format) into zero or more entries.
+///
+/// Tool results, thinking blocks, system/meta lines and injected context
+/// are skipped.
+pub fn parse_line(value: &Value) -> Vec<TranscriptEntry> {
+    match value.get("type").and_then(Value::as_str).unwrap_or("") {
+        // Claude Code
+        "user" | "assistant" => {
+            if value.get("isMeta").and_then(Value::as_bool) == Some(true) {
+                return Vec::new();
+            }
+            let Some(message) = value.get("message") else {
+                return Vec::new();
+            };
+            let is_user = value.get("type").and_then(Value::as_str) == Some("user");
+            parse_claude_message(message, is_user)
+        }
+        // Codex
+        "response_item" => value
+            .get("payload")
+            .map(parse_codex_item)
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+/// Model named by a transcript line: a Claude Code assistant message's
+/// `message.model`, or a Codex `turn_context`'s `payload.model`.
+///
+/// Claude Code's `<synthetic>` placeholder (locally generated messages) is
+/// not a model.
+pub fn line_model(value: &Value) -> Option<&str> {
+    let model = match value.get("type").and_then(Value::as_str)? {
+        "assistant" => value.pointer("/message/model")?.as_str()?,
+        "turn_context" => value.pointer("/payload/model")?.as_str()?,
+        _ => return None,
+    };
+    (!model.is_empty() && !model.starts_with('<')).then_some(model)
+}
+
+/// Latest model named near the end of a transcript file
+pub fn latest_model(path: &Path) -> Option<String> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let len = file.metadata().ok()?.len();
+    file.seek(SeekFrom::Start(len.saturating_sub(MODEL_SCAN_WINDOW)))
+        .ok()?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes).ok()?;
+    // A cut first line just fails to parse
+    String::from_utf8_lossy(&bytes)
+        .lines()
+        .rev()
+        .find_map(|line| {
+            let value = serde_json::from_str::<Value>(line.trim()).ok()?;
+            line_model(&value).map(str::to_string)
+        })
+}
+
+fn parse_claude_message(message: &Value, is_user: bool) -> Vec<TranscriptEntry> {
+    let text_entry = |text: &str| {
+        let text = text.trim().to_string();
+        if text.is_empty() {
+            None
+        } else if is_user {
+            Some(TranscriptEntry::User { text })
+        } else {
+            Some(TranscriptEntry::Assistant { text })
+        }
+    };
+
+    match message.get("content") {
+        Some(Value::String(text)) => text_entry(text).into_iter().collect(),
+        Some(Value::Array(blocks)) => blocks
+            .iter()
+            .filter_map(|block| match block.get("type").and_then(Value::as_str)? {
+                "text" => text_entry(block.get("text")?.as_str()?),
+                "tool_use" => {
+                    let name = block.get("name")?.as_str()?.to_string();
+                    let input = block.get("input").cloned().unwrap_or(Value::Null);
+                    Some(tool_call(name, &input))
+                }
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn parse_codex_item(payload: &Value) -> Vec<TranscriptEntry> {
+    match payload.get("type").and_then(Value::as_str).unwrap_or("") {
+        "message" => {
+            let role = payload.get("role").and_then(Value::as_str).unwrap_or("");
+            let Some(blocks) = payload.get("content").and_then(Value::as_array) else {
+                return Vec::new();
+            };
+            blocks
+                .iter()
+                .filter_map(|block| block.get("text")?.as_str())
+                .map(str::trim)
+                // Codex injects environment/instructions as tagged user messages
+                .filter(|text| !text.is_empty() && !text.starts_with('<'))
+                .filter_map(|text| match role {
+                    "user" => Some(TranscriptEntry::User {
+                        text: text.to_string(),
+                    }),
+                    "assistant" => Some(TranscriptEntry::Assistant {
+                        text: text.to_string(),
+                    }),
+                    _ => None,
+                })
+                .collect()
+        }
+        "function_call" | "custom_tool_call" => {
+            let Some(name) = payload.get("name").and_then(Value::as_str) else {
+                return Vec::new();
+            };
+            // Arguments are a JSON-encoded string
+            let input = payload
+                .get("arguments")
+                .or_else(|| payload.get("input"))
+                .map(|args| match args.as_str() {
+                    Some(s) => serde_json::from_str(s).unwrap_or_else(|_| args.clone()),
+                    None => args.clone(),
+                })
+                .unwrap_or(Value::Null);
+            vec![tool_call(name.to_string(), &input)]
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn tool_call(name: String, input: &Value) -> TranscriptEntry {
+    let label = [
+        "command",
+        "cmd",
+        "file_path",
+        "path",
+        "pattern",
+        "query",
+        "url",
+    ]
+    .iter()
+    .find_map(|key| input.get(*key)?.as_str())
+    .map(|value| {
+        let value = value.lines().next().unwrap_or_default();
+        crate::agents::truncate(value, TOOL_LABEL_MAX).to_string()
+    });
+    let input = match input {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => serde_json::to_string_pretty(other).unwrap_or_default(),
+    };
+    TranscriptEntry::ToolCall { name, label, input }
+}
+
+/// Result of one [`TranscriptTail::read_new`] call
+#[derive(Debug, Default)]
+pub struct TailRead {
+    /// The file shrank (rewritten); `entries` start from the top again
+    pub reset: bool,
+    pub entries: Vec<TranscriptEntry>,
+}
+
+/// Follows a transcript file, parsing lines as they're appended
+#[derive(Debug)]
+pub struct TranscriptTail {
+    tail: JsonlTail,
+}
+
+impl TranscriptTail {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            tail: JsonlTail::new(path),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        self.tail.path()
+    }
+
+    /// Read everything appended since the last call.
+    pub fn read_new(&mut self) -> std::io::Result<TailRead> {
+        let mut entries = Vec::new();
+        let mut parse = |value: &Value| entries.extend(parse_line(value));
+        let reset = self.tail.read_new(&mut parse)? == Progress::Truncated;
+        if reset {
+            self.tail.read_new(&mut parse)?;
+        }
+        Ok(TailRead { reset, entries })
+    }
+}
+
+/// Totals for one transcript
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct TranscriptMeta {
+    /// User prompts so far
+    pub prompts: usize,
+    /// Tool calls so far
+    pub tool_calls: usize,
+    /// Text of the latest prompt
+    pub last_prompt: Option<String>,
+    /// Prompts queued behind the current turn (Claude Code `queue-operation`
+    /// lines)
+    pub queued: usize,
+    /// Input tokens, cached ones included
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// ID and (input, output) tokens of the latest Claude Code message, which
+    /// repeats its usage on every line of a multi-block reply
+    #[serde(skip)]
+    message_usage: Option<(String, u64, u64)>,
+}
+
+impl TranscriptMeta {
+    /// Totals of a whole transcript, read from the top.
+    pub fn scan(transcript: impl BufRead) -> Self {
+        let mut meta = Self::default();
+        for line in transcript.lines().map_while(Result::ok) {
+            if let Ok(value) = serde_json::from_str::<Value>(line.trim()) {
+                meta.apply(&value);
+            }
+        }
+        meta
+    }
+
+    fn apply(&mut self, value: &Value) {
+        if value.get("type").and_then(Value::as_str) == Some("queue-operation") {
+            match value.get("operation").and_then(Value::as_str) {
+                Some("enqueue") => self.queued += 1,
+                Some("dequeue" | "remove") => self.queued = self.queued.saturating_sub(1),
+                Some("popAll") => self.queued = 0,
+                _ => {}
+            }
+            return;
+        }
+        self.count_tokens(value);
+        for entry in parse_line(value) {
+            match entry {
+                TranscriptEntry::User { text } => {
+                    self.prompts += 1;
+                    self.last_prompt = Some(text);
+                }
+                TranscriptEntry::Assistant { .. } => {}
+                TranscriptEntry::ToolCall { .. } => self.tool_calls += 1,
+            }
+        }
+    }
+
+    /// Claude Code assistant messages report their own usage, Codex
+    /// `token_count` events the running totals
+    fn count_tokens(&mut self, value: &Value) {
+        let count = |usage: &Value, key: &str| usage.get(key).and_then(Value::as_u64).unwrap_or(0);
+        match value.get("type").and_then(Value::as_str) {
+            Some("assistant") => {
+                let Some(usage) = value.pointer("/message/usage") else {
+                    return;
+                };
+                let input = count(usage, "input_tokens")
+                    + count(usage, "cache_creation_input_tokens")
+                    + count(usage, "cache_read_input_tokens");
+                let output = count(usage, "output_tokens");
+                let id = value.pointer("/message/id").and_then(Value::as_str);
+                // Another line of the same reply: replace its earlier count
+                if let Some((_, seen_input, seen_output)) = self
+                    .message_usage
+                    .take_if(|(seen, ..)| Some(seen.as_str()) == id)
+                {
+                    self.input_tokens -= seen_input;
+                    self.output_tokens -= seen_output;
+                }
+                self.input_tokens += input;
+                self.output_tokens += output;
+                self.message_usage = id.map(|id| (id.to_string(), input, output));
+            }
+            Some("event_msg")
+                if value.pointer("/payload/type").and_then(Value::as_str)
+                    == Some("token_count") =>
+            {
+                let Some(usage) = value.pointer("/payload/info/total_token_usage") else {
+                    return;
+                };
+                self.input_tokens = count(usage, "input_tokens");
+                self.output_tokens = count(usage, "output_tokens");
+            }
+            _ => {}
+        }
+    }
+}
+
+/// [`TranscriptMeta`] per transcript file, updated from appended bytes only
+#[derive(Debug, Default)]
+pub struct MetaTracker {
+    files: HashMap<PathBuf, (JsonlTail, TranscriptMeta)>,
+}
+
+impl MetaTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bring `path`'s totals up to date. The first call reads the whole file;
+    /// later calls read what was appended, starting over if it shrank.
+    pub fn update(&mut self, path: &Path) -> std::io::Result<&TranscriptMeta> {
+        let (tail, meta) = self
+            .files
+            .entry(path.to_path_buf())
+            .or_insert_with(|| (JsonlTail::new(path), TranscriptMeta::default()));
+        if tail.read_new(|value| meta.apply(value))? == Progress::Truncated {
+            *meta = TranscriptMeta::default();
+            tail.read_new(|value| meta.apply(value))?;
+        }
+        Ok(meta)
+    }
+
+    /// Stop tracking `path`
+    pub fn forget(&mut self, path: &Path) {
+        self.files.remove(path);
+    }
+
+    /// Files being tracked
+    pub fn paths(&self) -> impl Iterator<Item = &PathBuf> {
+        self.files.keys()
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::io::Write;
+
+    #[test]
+    fn claude_user_and_assistant_turns() {
+        let user =
+            json!({ "type": "user", "message": { "role": "user", "content": "fix the bug" } });
+        assert_eq!(
+            parse_line(&user),
+            vec![TranscriptEntry::User {
+                text: "fix the bug".into()
+            }]
+        );
+
+        let assistant = json!({
+            "type": "assistant",
+            "message": { "content": [
+                { "type": "thinking", "thinking": "hmm" },
+                { "type": "text", "text": "Running tests." },
+                { "type": "tool_use", "name": "Bash", "input": { "command": "cargo test" } }
+            ] }
+        });
+        let entries = parse_line(&assistant);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            entries[0],
+            TranscriptEntry::Assistant {
+                text: "Running tests.".into()
+            }
+        );
+        match &entries[1] {
+            TranscriptEntry::ToolCall { name, label, input } => {
+                assert_eq!(name, "Bash");
+                assert_eq!(label.as_deref(), Some("cargo test"));
+                assert!(input.contains("\"command\""));
+            }
+            other => panic!("unexpected entry: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn queue_operations_count_queued_prompts() {
+        let op = |operation: &str| json!({"type": "queue-operation", "operation": operation});
+        let mut meta = TranscriptMeta::default();
+        for operation in ["enqueue", "enqueue", "enqueue", "dequeue"] {
+            meta.apply(&op(operation));
+        }
+        assert_eq!(meta.queued, 2);
+        meta.apply(&op("popAll"));
+        meta.apply(&op("remove"));
+        assert_eq!(meta.queued, 0);
+        assert_eq!(meta.prompts, 0);
+    }
+
+    #[test]
+    fn latest_model_reads_the_last_named_model() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("t.jsonl");
+        let assistant = |model: &str| json!({"type": "assistant", "message": {"model": model}});
+        let lines = [
+            json!({"type": "turn_context", "payload": {"model": "gpt-5-codex"}}),
+            assistant("claude-sonnet-4-5"),
+            assistant("<synthetic>"),
+            json!({"type": "user", "message": {"content": "next"}}),
+        ];
+        let mut file = std::fs::File::create(&path).unwrap();
+        for line in &lines {
+            writeln!(file, "{line}").unwrap();
+        }
+        assert_eq!(line_model(&lines[0]), Some("gpt-5-codex"));
+        assert_eq!(latest_model(&path).as_deref(), Some("claude-sonnet-4-5"));
+        assert_eq!(latest_model(&dir.path().join("missing.jsonl")), None);
+    }
+
+    #[test]
+    fn claude_tool_results_and_meta_are_skipped() {
+        let result = json!({
+            "type": "user",
+            "message": { "content": [{ "type": "tool_result", "tool_use_id": "t1", "content": "ok" }] }
+        });
+        assert!(parse_line(&result).is_empty());
+
+        let meta = json!({ "type": "user", "isMeta": true, "message": { "content": "caveat" } });
+        assert!(parse_line(&meta).is_empty());
+        assert!(parse_line(&json!({ "type": "summary", "summary": "x" })).is_empty());
+    }
+
+    #[test]
+    fn codex_messages_and_function_calls() {
+        let user = json!({
+            "type": "response_item",
+            "payload": { "type": "message", "role": "user", "content": [
+                { "type": "input_text", "text": "<environment_context>...</environment_context>" },
+                { "type": "input_text", "text": "add a flag" }
+            ] }
+        });
+        assert_eq!(
+            parse_line(&user),
+            vec![TranscriptEntry::User {
+                text: "add a flag".into()
+            }]
+        );
+
+        let call = json!({
+            "type": "response_item",
+            "payload": {
+                "type": "function_call",
+                "name": "exec_command",
+                "arguments": "{\"cmd\":\"ls -la\"}"
+            }
+        });
+        match &parse_line(&call)[0] {
+            TranscriptEntry::ToolCall { name, label, .. } => {
+                assert_eq!(name, "exec_command");
+                assert_eq!(label.as_deref(), Some("ls -la"));
+            }
+            other => panic!("unexpected entry: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn tail_reads_only_complete_new_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("t.jsonl");
+        let mut file = std::fs::File::create(&path).unwrap();
+        writeln!(file, r#"{{"type":"user","message":{{"content":"one"}}}}"#).unwrap();
+        write!(file, r#"{{"type":"user","message":"#).unwrap();
+        file.flush().unwrap();
+
+        let mut tail = TranscriptTail::new(&path);
+        let read = tail.read_new().unwrap();
+        assert!(!read.reset);
+        assert_eq!(read.entries.len(), 1);
+
+        writeln!(file, r#"{{"content":"two"}}}}"#).unwrap();
+        file.flush().unwrap();
+        let read = tail.read_new().unwrap();
+        assert_eq!(
+            read.entries,
+            vec![TranscriptEntry::User { text: "two".into() }]
+        );
+        assert!(tail.read_new().unwrap().entries.is_empty());
+    }
+
+    #[test]
+    fn tail_resets_when_file_shrinks() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("t.jsonl");
+        std::fs::write(
+            &path,
+            "{\"type\":\"user\",\"message\":{\"content\":\"a long first prompt\"}}\n",
+        )
+        .unwrap();
+
+        let mut tail = TranscriptTail::new(&path);
+        tail.read_new().unwrap();
+
+        std::fs::write(
+            &path,
+            "{\"type\":\"user\",\"message\":{\"content\":\"b\"}}\n",
+        )
+        .unwrap();
+        let read = tail.read_new().unwrap();
+        assert!(read.reset);
+        assert_eq!(
+            read.entries,
+            vec![TranscriptEntry::User { text: "b".into() }]
+        );
+    }
+
+    #[test]
+    fn meta_counts_tokens() {
+        let claude = [
+            r#"{"type":"assistant","message":{"id":"m1","usage":{"input_tokens":10,"cache_read_input_tokens":90,"output_tokens":1},"content":[{"type":"text","text":"a"}]}}"#,
+            r#"{"type":"assistant","message":{"id":"m1","usage":{"input_tokens":10,"cache_read_input_tokens":90,"output_tokens":30},"content":[]}}"#,
+            r#"{"type":"assistant","message":{"id":"m2","usage":{"input_tokens":5,"output_tokens":7},"content":[]}}"#,
+        ];
+        let meta = TranscriptMeta::scan(claude.join("\n").as_bytes());
+        assert_eq!((meta.input_tokens, meta.output_tokens), (105, 37));
+
+        // Codex reports running totals
+        let codex = r#"{"type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":1200,"cached_input_tokens":1000,"output_tokens":80}}}}"#;
+        let meta = TranscriptMeta::scan(format!("{codex}\n{codex}").as_bytes());
+        assert_eq!((meta.input_tokens, meta.output_tokens), (1200, 80));
+    }
+
+    #[test]
+    fn meta_tracker_matches_full_scan() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("t.jsonl");
+        let prompt = |text: &str| format!(r#"{{"type":"user","message":{{"content":"{text}"}}}}"#);
+        let tool = r#"{"type":"assistant","message":{"content":[{"type":"tool_use","name":"Read","input":{"file_path":"a.rs"}}]}}"#;
+        let mut file = std::fs::File::create(&path).unwrap();
+        writeln!(file, "{}\n{tool}", prompt("one")).unwrap();
+        file.flush().unwrap();
+
+        let mut tracker = MetaTracker::new();
+        let meta = tracker.update(&path).unwrap().clone();
+        assert_eq!(meta.prompts, 1);
+        assert_eq!(meta.tool_calls, 1);
+
+        writeln!(file, "{}\n{tool}\n{tool}", prompt("two")).unwrap();
+        file.flush().unwrap();
+        let meta = tracker.update(&path).unwrap().clone();
+        assert_eq!(
+            meta,
+            TranscriptMeta {
+                prompts: 2,
+                tool_calls: 3,
+                last_prompt: Some("two".into()),
+                ..TranscriptMeta::default()
+            }
+        );
+        let file = std::fs::File::open(&path).unwrap();
+        assert_eq!(meta, TranscriptMeta::scan(std::io::BufReader::new(file)));
+
+        // Rewritten shorter: totals start over
+        std::fs::write(&path, prompt("three") + "\n").unwrap();
+        let meta = tracker.update(&path).unwrap();
+        assert_eq!(meta.prompts, 1);
+        assert_eq!(meta.last_prompt.as_deref(), Some("three"));
+
+        tracker.forget(&path);
+        assert!(tracker.is_empty());
+    }
+}
@@ -0,0 +1,305 @@
+//! Active context - which session the user is currently looking at
+//!
+//! A background thread probes the frontmost app and publishes the working
+//! directories of the shells under it. The session list highlights the row
+//! whose cwd matches, so the HUD follows whatever terminal is in front.
+//!
+//! Opt-in (`ui.follow_front_terminal`), and the thread only runs while there
+//! are live sessions to highlight. Probing is macOS-only and shells out to
+//! system tools:
+//! - `lsappinfo` for the frontmost app's bundle id and pid
+//! - `osascript` for the front tab's tty (Terminal.app and iTerm2 only)
+//! - `ps` to find shells under that app (or on that tty)
+//! - `lsof` for their working directories
+
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Working directories of the frontmost app's shells (empty when unknown)
+pub(crate) type ActiveCwds = Arc<Mutex<Vec<String>>>;
+
+/// The frontmost app's shell cwds, and the probe thread keeping them current
+#[derive(Default)]
+pub(crate) struct ActiveContext {
+    cwds: ActiveCwds,
+    /// Set while a probe thread runs; clearing it stops the thread
+    running: Option<Arc<AtomicBool>>,
+}
+
+impl ActiveContext {
+    /// Working directories of the frontmost app's shells
+    pub(crate) fn cwds(&self) -> Vec<String> {
+        self.cwds.lock().map(|a| a.clone()).unwrap_or_default()
+    }
+
+    /// Start or stop probing the frontmost app in the background.
+    ///
+    /// While probing, the list is replaced after every probe that changes it.
+    /// Probes that land on aura itself (e.g. the user clicked the HUD) keep
+    /// the previous value so the highlight doesn't flicker away. Stopping
+    /// clears it; the thread exits before its next probe.
+    pub(crate) fn set_probing(&mut self, probing: bool) {
+        if probing == self.running.is_some() {
+            return;
+        }
+        match self.running.take() {
+            Some(running) => {
+                running.store(false, Ordering::Relaxed);
+                if let Ok(mut cwds) = self.cwds.lock() {
+                    cwds.clear();
+                }
+            }
+            None => {
+                let running = Arc::new(AtomicBool::new(true));
+                #[cfg(target_os = "macos")]
+                probe::spawn(self.cwds.clone(), running.clone());
+                self.running = Some(running);
+            }
+        }
+    }
+}
+
+impl Drop for ActiveContext {
+    fn drop(&mut self) {
+        self.set_probing(false);
+    }
+}
+
+/// Pick the session cwd that best matches the frontmost shells.
+///
+/// A session matches when a shell sits in its cwd or anywhere below it; the
+/// deepest matching cwd wins so nested projects highlight the inner one.
+pub(crate) fn focused_cwd<'a>(
+    session_cwds: impl IntoIterator<Item = &'a str>,
+    active: &[String],
+) -> Option<&'a str> {
+    session_cwds
+        .into_iter()
+        .filter(|cwd| {
+            active
+                .iter()
+                .any(|active| Path::new(active).starts_with(Path::new(cwd)))
+        })
+        .max_by_key(|cwd| Path::new(cwd).components().count())
+}
+
+#[cfg(target_os = "macos")]
+mod probe {
+    use super::ActiveCwds;
+    use std::collections::{HashMap, HashSet};
+    use std::process::Command;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+
+    /// How often the frontmost app is probed
+    const PROBE_INTERVAL: Duration = Duration::from_millis(1500);
+
+    /// Terminals whose front tab's tty can be read over AppleScript
+    const TTY_SCRIPTS: &[(&str, &str)] = &[
+        (
+            "com.apple.Terminal",
+            r#"tell application "Terminal" to get tty of selected tab of front window"#,
+        ),
+        (
+            "com.googlecode.iterm2",
+            r#"tell application "iTerm2" to get tty of current session of current window"#,
+        ),
+    ];
+
+    pub(super) fn spawn(shared: ActiveCwds, running: Arc<AtomicBool>) {
+        let spawned = std::thread::Builder::new()
+            .name("aura-active-context".into())
+            .spawn(move || {
+                while running.load(Ordering::Relaxed) {
+                    // Checked under the lock, so a probe finishing as it's
+                    // stopped can't bring back the cleared list
+                    if let Some(cwds) = frontmost_cwds()
+                        && let Ok(mut current) = shared.lock()
+                        && running.load(Ordering::Relaxed)
+                        && *current != cwds
+                    {
+                        tracing::trace!(target: crate::logging::UI, "Frontmost cwds: {:?}", cwds);
+                        *current = cwds;
+                    }
+                    std::thread::sleep(PROBE_INTERVAL);
+                }
+            });
+        if let Err(e) = spawned {
+            tracing::warn!(
+                target: crate::logging::UI,
+                "Failed to start active context probe: {}",
+                e
+            );
+        }
+    }
+
+    /// Probe the frontmost app. `None` means "no answer, keep the last one".
+    fn frontmost_cwds() -> Option<Vec<String>> {
+        let asn = run("lsappinfo", &["front"])?;
+        let info = run("lsappinfo", &["info", asn.trim()])?;
+        let (bundle_id, pid) = super::parse_app_info(&info)?;
+        if pid == std::process::id() {
+            return None;
+        }
+
+        let pids = match front_tab_tty(&bundle_id) {
+            Some(tty) => tty_pids(&tty),
+            None => descendant_pids(pid),
+        };
+        if pids.is_empty() {
+            return Some(Vec::new());
+        }
+        let pid_list: Vec<String> = pids.iter().map(u32::to_string).collect();
+        let lsof = run(
+            "lsof",
+            &["-a", "-d", "cwd", "-Fn", "-p", &pid_list.join(",")],
+        )
+        .unwrap_or_default();
+        Some(super::parse_lsof_cwds(&lsof))
+    }
+
+    fn front_tab_tty(bundle_id: &str) -> Option<String> {
+        let (_, script) = TTY_SCRIPTS.iter().find(|(id, _)| *id == bundle_id)?;
+        let tty = run("osascript", &["-e", script])?;
+        let tty = tty.trim().trim_start_matches("/dev/");
+        (!tty.is_empty()).then(|| tty.to_string())
+    }
+
+    fn tty_pids(tty: &str) -> Vec<u32> {
+        run("ps", &["-t", tty, "-o", "pid="])
+            .map(|out| {
+                out.split_whitespace()
+                    .filter_map(|p| p.parse().ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn descendant_pids(root: u32) -> Vec<u32> {
+        let Some(out) = run("ps", &["-A", "-o", "pid=,ppid="]) else {
+            return Vec::new();
+        };
+        let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+        for line in out.lines() {
+            let mut fields = line.split_whitespace().filter_map(|f| f.parse().ok());
+            if let (Some(pid), Some(ppid)) = (fields.next(), fields.next()) {
+                children.entry(ppid).or_default().push(pid);
+            }
+        }
+
+        let mut seen = HashSet::new();
+        let mut stack = children.get(&root).cloned().unwrap_or_default();
+        while let Some(pid) = stack.pop() {
+            if seen.insert(pid)
+                && let Some(kids) = children.get(&pid)
+            {
+                stack.extend(kids);
+            }
+        }
+        seen.into_iter().collect()
+    }
+
+    fn run(program: &str, args: &[&str]) -> Option<String> {
+        let output = Command::new(program).args(args).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        String::from_utf8(output.stdout).ok()
+    }
+}
+
+/// Parse `bundleID="…"` and `pid = N` out of `lsappinfo info` output
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_app_info(info: &str) -> Option<(String, u32)> {
+    let bundle_id = info
+        .split("bundleID=\"")
+        .nth(1)
+        .and_then(|rest| rest.split('"').next())
+        .unwrap_or_default()
+        .to_string();
+    let pid = info
+        .split("pid = ")
+        .nth(1)?
+        .split(|c: char| !c.is_ascii_digit())
+        .next()?
+        .parse()
+        .ok()?;
+    Some((bundle_id, pid))
+}
+
+/// Collect the `n<path>` lines of `lsof -Fn` output, deduplicated
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_lsof_cwds(output: &str) -> Vec<String> {
+    let mut cwds: Vec<String> = output
+        .lines()
+        .filter_map(|line| line.strip_prefix('n'))
+        .map(str::to_string)
+        .collect();
+    cwds.sort();
+    cwds.dedup();
+    cwds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn active(paths: &[&str]) -> Vec<String> {
+        paths.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn focused_cwd_matches_exact_and_subdirectories() {
+        let sessions = ["/code/aura", "/code/other"];
+        assert_eq!(
+            focused_cwd(sessions, &active(&["/code/aura"])),
+            Some("/code/aura")
+        );
+        assert_eq!(
+            focused_cwd(sessions, &active(&["/code/aura/src/ui"])),
+            Some("/code/aura")
+        );
+        assert_eq!(focused_cwd(sessions, &active(&["/code/aur"])), None);
+        assert_eq!(focused_cwd(sessions, &[]), None);
+    }
+
+    #[test]
+    fn focused_cwd_prefers_deepest_project() {
+        let sessions = ["/code", "/code/aura"];
+        assert_eq!(
+            focused_cwd(sessions, &active(&["/code/aura/src"])),
+            Some("/code/aura")
+        );
+    }
+
+    #[test]
+    fn stopping_the_probe_clears_the_cwds() {
+        let mut context = ActiveContext::default();
+        context.set_probing(true);
+        context.cwds.lock().unwrap().push("/code/aura".into());
+        context.set_probing(false);
+        assert!(context.cwds().is_empty());
+        assert!(context.running.is_none());
+    }
+
+    #[test]
+    fn parse_app_info_reads_bundle_and_pid() {
+        let info = r#""Terminal" ASN:0x0-0x1a01a:
+    bundleID="com.apple.Terminal"
+    bundle path="/System/Applications/Utilities/Terminal.app"
+    pid = 519 type="Foreground" flavor=3"#;
+        assert_eq!(
+            parse_app_info(info),
+            Some(("com.apple.Terminal".to_string(), 519))
+        );
+        assert_eq!(parse_app_info("garbage"), None);
+    }
+
+    #[test]
+    fn parse_lsof_cwds_collects_names() {
+        let output = "p101\nfcwd\nn/code/aura\np102\nfcwd\nn/code/aura\np103\nfcwd\nn/tmp\n";
+        assert_eq!(parse_lsof_cwds(output), active(&["/code/aura", "/tmp"]));
+    }
+}
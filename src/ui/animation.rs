@@ -1,7 +1,70 @@
 //! Animation utilities for tool cycling and text marquee
-
+//!
+//! Every calculation honors the process-wide animation level (config
+//! `ui.animations`, lowered by macOS "Reduce Motion"), set once at startup
+//! with [`set_animations`]; reduced levels return the resting value. While
+//! [`crate::sysinfo::power_saving`] holds, nothing animates. The marquee
+//! follows config `ui.marquee`, set with [`set_marquee`].
+
+use crate::config::{Animations, MarqueeConfig};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU8, Ordering};
 use std::time::Instant;
 
+/// Current animation level (`Animations` as u8)
+static ANIMATIONS: AtomicU8 = AtomicU8::new(Animations::Full as u8);
+
+/// Set the animation level used by every calculation in this module
+pub fn set_animations(level: Animations) {
+    ANIMATIONS.store(level as u8, Ordering::Relaxed);
+}
+
+/// Current animation level
+pub fn animations() -> Animations {
+    if crate::sysinfo::power_saving() {
+        return Animations::Off;
+    }
+    match ANIMATIONS.load(Ordering::Relaxed) {
+        0 => Animations::Full,
+        1 => Animations::Reduced,
+        _ => Animations::Off,
+    }
+}
+
+/// Marquee settings (config `ui.marquee`)
+static MARQUEE: OnceLock<MarqueeConfig> = OnceLock::new();
+
+/// Set the marquee speed, delay and whether long labels scroll at all
+pub fn set_marquee(config: MarqueeConfig) {
+    let _ = MARQUEE.set(config);
+}
+
+/// Current marquee settings
+pub fn marquee() -> MarqueeConfig {
+    MARQUEE.get().copied().unwrap_or_default()
+}
+
+/// Whether movement (shake, spin, breathing, slides) is disabled
+fn motion_reduced() -> bool {
+    animations() != Animations::Full
+}
+
+/// Level to use given the config and macOS "Reduce Motion"
+pub fn effective_animations(configured: Animations, system_reduce_motion: bool) -> Animations {
+    match configured {
+        Animations::Full if system_reduce_motion => Animations::Reduced,
+        level => level,
+    }
+}
+
+/// Whether macOS "Reduce Motion" (Accessibility > Display) is on
+pub fn system_reduce_motion() -> bool {
+    std::process::Command::new("defaults")
+        .args(["read", "com.apple.universalaccess", "reduceMotion"])
+        .output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).trim() == "1")
+}
+
 /// Animation timing constants
 pub const CYCLE_DURATION_MIN_MS: u64 = 1500; // Min time showing each tool
 pub const CYCLE_DURATION_MAX_MS: u64 = 2000; // Max time showing each tool
@@ -25,6 +88,16 @@ pub fn get_cycle_duration(cycle: u64, seed: u64) -> u64 {
 /// Calculate animation state based on elapsed time
 /// Returns (tool_index, fade_progress)
 pub fn calculate_animation_state(start_time: Instant, seed: u64) -> (usize, f32) {
+    match animations() {
+        Animations::Full => {}
+        // Switch tools without cross-fading
+        Animations::Reduced => return (calculate_animation_state_full(start_time, seed).0, 0.0),
+        Animations::Off => return (0, 0.0),
+    }
+    calculate_animation_state_full(start_time, seed)
+}
+
+fn calculate_animation_state_full(start_time: Instant, seed: u64) -> (usize, f32) {
     let elapsed_ms = start_time.elapsed().as_millis() as u64;
 
     // Find which cycle we're in by iterating (since durations vary)
@@ -81,11 +154,27 @@ const SHAKE_AMPLITUDE: f32 = 1.5; // Maximum horizontal displacement in pixels
 /// Calculate horizontal shake offset for attention animation.
 /// Returns x-offset in pixels oscillating between -amplitude and +amplitude.
 pub fn calculate_shake_offset(start_time: Instant) -> f32 {
+    if motion_reduced() {
+        return 0.0;
+    }
     let elapsed_ms = start_time.elapsed().as_millis() as f32;
     let phase = (elapsed_ms / SHAKE_PERIOD_MS) * std::f32::consts::TAU;
     phase.sin() * SHAKE_AMPLITUDE
 }
 
+/// Spin animation period (one full counter-clockwise turn)
+const SPIN_PERIOD_MS: f32 = 2000.0;
+
+/// Calculate rotation for the waiting spin animation, in radians
+/// (negative for counter-clockwise).
+pub fn calculate_spin_radians(start_time: Instant) -> f32 {
+    if motion_reduced() {
+        return 0.0;
+    }
+    let elapsed_ms = start_time.elapsed().as_millis() as f32;
+    -((elapsed_ms / SPIN_PERIOD_MS) * std::f32::consts::TAU)
+}
+
 /// Breathe animation constants
 const BREATHE_CYCLE_MS: f32 = 4000.0; // Full cycle duration in milliseconds
 const BREATHE_MIN_OPACITY: f32 = 0.3; // Minimum opacity
@@ -94,15 +183,49 @@ const BREATHE_MAX_OPACITY: f32 = 0.5; // Maximum opacity
 /// Calculate breathe animation opacity for stale sessions.
 /// Cycles between 0.3 and 0.5 over 4 seconds using sine wave.
 pub fn calculate_breathe_opacity(start_time: Instant) -> f32 {
+    // Map sine [-1, 1] to [0.3, 0.5]: center = 0.4, amplitude = 0.1
+    let center = (BREATHE_MIN_OPACITY + BREATHE_MAX_OPACITY) / 2.0;
+    if motion_reduced() {
+        return center;
+    }
     let elapsed_ms = start_time.elapsed().as_millis() as f32;
     let t = (elapsed_ms % BREATHE_CYCLE_MS) / BREATHE_CYCLE_MS;
     let sine = (t * std::f32::consts::TAU).sin();
-    // Map sine [-1, 1] to [0.3, 0.5]: center = 0.4, amplitude = 0.1
-    let center = (BREATHE_MIN_OPACITY + BREATHE_MAX_OPACITY) / 2.0;
     let amplitude = (BREATHE_MAX_OPACITY - BREATHE_MIN_OPACITY) / 2.0;
     center + amplitude * sine
 }
 
+/// Calculate the marquee's x-offset for a label `overflow` px wider than
+/// its row, hovered since `hover_started`: it rests, scrolls left to the
+/// end, rests again and jumps back. Off under reduced motion.
+pub fn calculate_marquee_offset(hover_started: Instant, overflow: f32) -> f32 {
+    let config = marquee();
+    if !config.enabled || motion_reduced() {
+        return 0.0;
+    }
+    marquee_offset(
+        hover_started.elapsed().as_millis() as u64,
+        overflow,
+        &config,
+    )
+}
+
+fn marquee_offset(elapsed_ms: u64, overflow: f32, config: &MarqueeConfig) -> f32 {
+    if overflow <= 0.0 {
+        return 0.0;
+    }
+    let speed = config.speed.max(1.0); // px per second
+    let scroll_ms = (overflow / speed * 1000.0).ceil() as u64;
+    let pos = elapsed_ms % (config.delay_ms * 2 + scroll_ms).max(1);
+    if pos < config.delay_ms {
+        0.0
+    } else if pos < config.delay_ms + scroll_ms {
+        -((pos - config.delay_ms) as f32 * speed / 1000.0).min(overflow)
+    } else {
+        -overflow
+    }
+}
+
 /// Row slide-in animation duration in milliseconds
 pub const ROW_SLIDE_IN_MS: u64 = 350;
 
@@ -113,7 +236,7 @@ pub const ROW_SLIDE_IN_MS: u64 = 350;
 pub fn calculate_row_slide_in(appeared_at: Instant) -> (f32, f32) {
     let elapsed_ms = appeared_at.elapsed().as_millis() as u64;
 
-    if elapsed_ms >= ROW_SLIDE_IN_MS {
+    if elapsed_ms >= ROW_SLIDE_IN_MS || motion_reduced() {
         return (1.0, 0.0); // Animation complete
     }
 
@@ -136,7 +259,7 @@ pub const ROW_SLIDE_OUT_MS: u64 = 300;
 pub fn calculate_row_slide_out(removed_at: Instant) -> (f32, f32, bool) {
     let elapsed_ms = removed_at.elapsed().as_millis() as u64;
 
-    if elapsed_ms >= ROW_SLIDE_OUT_MS {
+    if elapsed_ms >= ROW_SLIDE_OUT_MS || motion_reduced() {
         return (0.0, 12.0, true); // Animation complete, should be removed
     }
 
@@ -150,6 +273,37 @@ pub fn calculate_row_slide_out(removed_at: Instant) -> (f32, f32, bool) {
     (opacity, x_offset, false)
 }
 
+/// Session list window resize duration in milliseconds
+pub const WINDOW_RESIZE_MS: u64 = 200;
+
+/// Calculate the session list height while animating a resize.
+/// Returns (height, done) easing from `from` to `to`.
+pub fn calculate_window_resize(from: f32, to: f32, started_at: Instant) -> (f32, bool) {
+    let elapsed_ms = started_at.elapsed().as_millis() as u64;
+
+    if elapsed_ms >= WINDOW_RESIZE_MS || motion_reduced() {
+        return (to, true);
+    }
+
+    let progress = elapsed_ms as f32 / WINDOW_RESIZE_MS as f32;
+    (from + (to - from) * ease_out(progress), false)
+}
+
+/// Indicator fade-in duration when auto-hide reveals it, in milliseconds
+pub const HUD_FADE_IN_MS: u64 = 300;
+
+/// Calculate the indicator window alpha while fading in.
+/// Returns (alpha, done).
+pub fn calculate_fade_in(started_at: Instant) -> (f32, bool) {
+    let elapsed_ms = started_at.elapsed().as_millis() as u64;
+
+    if elapsed_ms >= HUD_FADE_IN_MS || animations() == Animations::Off {
+        return (1.0, true);
+    }
+
+    (ease_out(elapsed_ms as f32 / HUD_FADE_IN_MS as f32), false)
+}
+
 /// Icon swap animation duration in milliseconds
 pub const ICON_SWAP_MS: u64 = 300;
 
@@ -157,22 +311,29 @@ pub const ICON_SWAP_MS: u64 = 300;
 /// Returns (state_opacity, state_x, remove_opacity, remove_x) for the swap animation.
 /// - state_icon: opacity 1→0, x 0→16
 /// - remove_icon: opacity 0→1, x -16→0
+///
+/// Reduced motion cross-fades in place; with animations off the swap is instant.
 pub fn calculate_icon_swap(
     hover_started: Option<Instant>,
     is_hovered: bool,
 ) -> (f32, f32, f32, f32) {
+    let instant = animations() == Animations::Off;
+    let swap_progress = |start: Instant| {
+        let elapsed = start.elapsed().as_millis() as u64;
+        if instant {
+            1.0
+        } else {
+            (elapsed as f32 / ICON_SWAP_MS as f32).min(1.0)
+        }
+    };
     let (progress, reverse) = match (hover_started, is_hovered) {
         (Some(start), true) => {
             // Hovering - animate state icon out, remove icon in
-            let elapsed = start.elapsed().as_millis() as u64;
-            let p = (elapsed as f32 / ICON_SWAP_MS as f32).min(1.0);
-            (ease_out(p), false)
+            (ease_out(swap_progress(start)), false)
         }
         (Some(start), false) => {
             // Not hovering but have a start time - animating back
-            let elapsed = start.elapsed().as_millis() as u64;
-            let p = (elapsed as f32 / ICON_SWAP_MS as f32).min(1.0);
-            (ease_out(p), true)
+            (ease_out(swap_progress(start)), true)
         }
         (None, _) => (0.0, false),
     };
@@ -195,6 +356,9 @@ pub fn calculate_icon_swap(
         )
     };
 
+    if motion_reduced() {
+        return (state_opacity, 0.0, remove_opacity, 0.0);
+    }
     (state_opacity, state_x, remove_opacity, remove_x)
 }
 
@@ -263,6 +427,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_system_reduce_motion_lowers_full_only() {
+        assert_eq!(
+            effective_animations(Animations::Full, false),
+            Animations::Full
+        );
+        assert_eq!(
+            effective_animations(Animations::Full, true),
+            Animations::Reduced
+        );
+        assert_eq!(effective_animations(Animations::Off, true), Animations::Off);
+        assert_eq!(animations(), Animations::Full);
+    }
+
+    #[test]
+    fn test_marquee_rests_scrolls_and_rests() {
+        let config = MarqueeConfig {
+            enabled: true,
+            speed: 20.0,
+            delay_ms: 1000,
+        };
+        // 40px over at 20px/s: 1s rest, 2s scroll, 1s rest
+        assert_eq!(marquee_offset(500, 40.0, &config), 0.0);
+        assert_eq!(marquee_offset(2000, 40.0, &config), -20.0);
+        assert_eq!(marquee_offset(3500, 40.0, &config), -40.0);
+        assert_eq!(marquee_offset(4500, 40.0, &config), 0.0);
+        assert_eq!(marquee_offset(2000, 0.0, &config), 0.0);
+    }
+
     #[test]
     fn test_ease_out() {
         assert_eq!(ease_out(0.0), 0.0);
@@ -342,4 +535,33 @@ mod tests {
         let h3 = cycle_hash(0, 100);
         assert_ne!(h1, h3);
     }
+
+    #[test]
+    fn window_resize_starts_at_from() {
+        let (height, done) = calculate_window_resize(100.0, 200.0, Instant::now());
+        assert!(!done);
+        assert!((height - 100.0).abs() < 5.0);
+    }
+
+    #[test]
+    fn window_resize_ends_at_to() {
+        let started = Instant::now() - std::time::Duration::from_millis(WINDOW_RESIZE_MS);
+        assert_eq!(
+            calculate_window_resize(100.0, 200.0, started),
+            (200.0, true)
+        );
+    }
+
+    #[test]
+    fn fade_in_starts_transparent() {
+        let (alpha, done) = calculate_fade_in(Instant::now());
+        assert!(alpha < 0.1);
+        assert!(!done);
+    }
+
+    #[test]
+    fn fade_in_ends_opaque() {
+        let started = Instant::now() - std::time::Duration::from_millis(HUD_FADE_IN_MS);
+        assert_eq!(calculate_fade_in(started), (1.0, true));
+    }
 }
@@ -44,6 +44,9 @@ impl AssetSource for Assets {
                 include_bytes!("../../assets/icons/binoculars.svg").as_slice()
             }
             "icons/plug.svg" => include_bytes!("../../assets/icons/plug.svg").as_slice(),
+            "icons/git-branch.svg" => {
+                include_bytes!("../../assets/icons/git-branch.svg").as_slice()
+            }
             "icons/ticket.svg" => include_bytes!("../../assets/icons/ticket.svg").as_slice(),
 
             // UI icons (Lucide)
@@ -51,7 +54,13 @@ impl AssetSource for Assets {
                 include_bytes!("../../assets/icons/audio-lines.svg").as_slice()
             }
             "icons/bomb.svg" => include_bytes!("../../assets/icons/bomb.svg").as_slice(),
+            "icons/bell-off.svg" => include_bytes!("../../assets/icons/bell-off.svg").as_slice(),
+            "icons/ellipsis.svg" => include_bytes!("../../assets/icons/ellipsis.svg").as_slice(),
             "icons/x.svg" => include_bytes!("../../assets/icons/x.svg").as_slice(),
+            "icons/clock.svg" => include_bytes!("../../assets/icons/clock.svg").as_slice(),
+            "icons/triangle-alert.svg" => {
+                include_bytes!("../../assets/icons/triangle-alert.svg").as_slice()
+            }
 
             // Indicator icons (Lucide)
             "icons/panda.svg" => include_bytes!("../../assets/icons/panda.svg").as_slice(),
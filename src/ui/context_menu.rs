@@ -0,0 +1,99 @@
+//! Context menu - small dropdown overlay shared by the header and row menus
+//!
+//! HUD windows are non-activating popups, so menus are drawn as absolute
+//! overlays inside the session list window rather than native NSMenus.
+//! Callers position the container and attach click handlers to the items.
+
+use super::font;
+use super::theme::ThemeColors;
+use gpui::{
+    Div, ElementId, InteractiveElement, ParentElement, SharedString, Stateful,
+    StatefulInteractiveElement, Styled, div, px,
+};
+
+/// Menu width (fits the longest row action label)
+pub const MENU_WIDTH: f32 = 160.0;
+/// Height of one item (4px vertical padding + 11px text line)
+pub const ITEM_HEIGHT: f32 = 22.0;
+/// Part of [`ITEM_HEIGHT`] that grows with the font size
+const ITEM_TEXT_HEIGHT: f32 = 14.0;
+/// Inner padding around the items
+const MENU_PADDING: f32 = 4.0;
+/// Keep menus this far from the window edges
+const EDGE_MARGIN: f32 = 4.0;
+
+/// Total menu height for `item_count` items
+pub(crate) fn menu_height(item_count: usize) -> f32 {
+    item_count as f32 * item_height() + MENU_PADDING * 2.0
+}
+
+/// [`ITEM_HEIGHT`] at the configured font size
+fn item_height() -> f32 {
+    ITEM_HEIGHT + ITEM_TEXT_HEIGHT * (font::scale() - 1.0)
+}
+
+/// Clamp a menu's left edge so it stays inside a window of `window_width`
+pub(crate) fn clamp_left(x: f32, window_width: f32) -> f32 {
+    x.min(window_width - MENU_WIDTH - EDGE_MARGIN)
+        .max(EDGE_MARGIN)
+}
+
+/// Menu container; position it with `.absolute()` and add items as children
+pub(crate) fn container(theme: &ThemeColors) -> Div {
+    div()
+        .p(px(MENU_PADDING))
+        .flex()
+        .flex_col()
+        .rounded(px(8.0))
+        .bg(theme.container_bg)
+        .border_1()
+        .border_color(theme.border)
+        .font_family(font::family())
+        .text_size(font::size(11.0))
+        .text_color(theme.text_primary)
+}
+
+/// A menu item. Disabled items are dimmed and get no hover highlight or
+/// pointer cursor; callers should skip their click handler.
+pub(crate) fn item(
+    id: impl Into<ElementId>,
+    label: impl Into<SharedString>,
+    enabled: bool,
+    theme: &ThemeColors,
+) -> Stateful<Div> {
+    let hover_bg = theme.row_hover_bg;
+    let base = div()
+        .id(id)
+        .h(px(item_height()))
+        .px(px(10.0))
+        .flex()
+        .items_center()
+        .rounded(px(6.0))
+        .whitespace_nowrap()
+        .child(label.into());
+
+    if enabled {
+        base.cursor(gpui::CursorStyle::PointingHand)
+            .hover(move |style| style.bg(hover_bg))
+    } else {
+        base.text_color(theme.text_secondary).opacity(0.5)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn menu_height_includes_padding() {
+        assert_eq!(menu_height(0), MENU_PADDING * 2.0);
+        assert_eq!(menu_height(3), 3.0 * ITEM_HEIGHT + MENU_PADDING * 2.0);
+    }
+
+    #[test]
+    fn clamp_left_keeps_menu_inside_window() {
+        assert_eq!(clamp_left(20.0, 320.0), 20.0);
+        assert_eq!(clamp_left(300.0, 320.0), 320.0 - MENU_WIDTH - EDGE_MARGIN);
+        assert_eq!(clamp_left(-5.0, 320.0), EDGE_MARGIN);
+    }
+}
@@ -0,0 +1,99 @@
+//! Deep links - `aura://session/<id>` URLs that jump back to a session
+//!
+//! The app bundle registers the `aura` scheme (assets/Info.plist). macOS
+//! delivers opened URLs to the running daemon, which queues the session id;
+//! the indicator drains the queue each frame, opens the session list, and the
+//! list scrolls to and briefly highlights that row.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// URL scheme registered in Info.plist
+const SCHEME: &str = "aura";
+
+/// How long a linked row stays highlighted
+pub(crate) const HIGHLIGHT_DURATION: Duration = Duration::from_secs(3);
+
+/// Session ids from opened links, waiting for the UI thread
+pub(crate) type PendingLinks = Arc<Mutex<Vec<String>>>;
+
+/// Link to a session, e.g. `aura://session/abc-123`
+pub(crate) fn session_link(session_id: &str) -> String {
+    format!("{SCHEME}://session/{session_id}")
+}
+
+/// Session id of an `aura://session/<id>` URL
+pub(crate) fn parse_session_link(url: &str) -> Option<String> {
+    let rest = url.trim().strip_prefix(SCHEME)?.strip_prefix("://")?;
+    let id = rest.strip_prefix("session/")?;
+    // Tolerate a trailing slash or query some apps append
+    let id = id.split(['?', '#']).next()?.trim_end_matches('/');
+    (!id.is_empty() && !id.contains('/')).then(|| id.to_string())
+}
+
+/// Queue the sessions of opened URLs, ignoring anything that isn't a link
+pub(crate) fn enqueue(pending: &PendingLinks, urls: Vec<String>) {
+    let ids: Vec<String> = urls
+        .iter()
+        .filter_map(|url| {
+            let id = parse_session_link(url);
+            if id.is_none() {
+                tracing::warn!(target: crate::logging::UI, "Ignoring unsupported URL: {}", url);
+            }
+            id
+        })
+        .collect();
+    if ids.is_empty() {
+        return;
+    }
+    if let Ok(mut pending) = pending.lock() {
+        pending.extend(ids);
+    }
+}
+
+/// Take the most recent queued session id (older ones are superseded)
+pub(crate) fn take_latest(pending: &PendingLinks) -> Option<String> {
+    let mut pending = pending.lock().ok()?;
+    let latest = pending.pop();
+    pending.clear();
+    latest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_link_round_trips() {
+        let link = session_link("0199a2b4-7c1e");
+        assert_eq!(link, "aura://session/0199a2b4-7c1e");
+        assert_eq!(parse_session_link(&link).as_deref(), Some("0199a2b4-7c1e"));
+    }
+
+    #[test]
+    fn parse_session_link_rejects_other_urls() {
+        assert_eq!(
+            parse_session_link("aura://session/abc/").as_deref(),
+            Some("abc")
+        );
+        assert_eq!(parse_session_link("aura://session/"), None);
+        assert_eq!(parse_session_link("aura://settings/abc"), None);
+        assert_eq!(parse_session_link("https://session/abc"), None);
+        assert_eq!(parse_session_link("aura://session/a/b"), None);
+    }
+
+    #[test]
+    fn latest_link_wins() {
+        let pending = PendingLinks::default();
+        enqueue(
+            &pending,
+            vec![
+                "aura://session/one".into(),
+                "https://example.com".into(),
+                "aura://session/two".into(),
+            ],
+        );
+        assert_eq!(take_latest(&pending).as_deref(), Some("two"));
+        assert_eq!(take_latest(&pending), None);
+    }
+}
@@ -0,0 +1,206 @@
+//! Diagnostics window - the daemon's health at a glance
+//!
+//! Opened from the app menu ("Diagnostics"). Lists the current warnings, the
+//! daemon's version and uptime, the IPC socket and Codex watcher status, and
+//! when each event source last reported (and whether it's paused), from
+//! [`crate::health::report`].
+
+use super::SharedHudState;
+use crate::health::{self, HealthReport, SocketState, WatcherState};
+use gpui::{
+    App, AppContext, Bounds, Context, Entity, IntoElement, ParentElement, Render, Styled,
+    TitlebarOptions, Window, WindowBounds, WindowOptions, div, px, size,
+};
+use std::time::{Duration, Instant};
+
+/// How often the report is refreshed
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const WINDOW_WIDTH: f32 = 420.0;
+const WINDOW_HEIGHT: f32 = 260.0;
+
+/// Diagnostics window view
+pub(crate) struct DiagnosticsView {
+    state: Entity<SharedHudState>,
+    report: HealthReport,
+    last_poll: Instant,
+}
+
+/// Open the diagnostics window.
+pub(crate) fn open(app: &mut App, state: Entity<SharedHudState>) {
+    let result = app.open_window(
+        WindowOptions {
+            window_bounds: Some(WindowBounds::Windowed(Bounds::centered(
+                None,
+                size(px(WINDOW_WIDTH), px(WINDOW_HEIGHT)),
+                app,
+            ))),
+            titlebar: Some(TitlebarOptions {
+                title: Some("Aura Diagnostics".into()),
+                ..Default::default()
+            }),
+            focus: true,
+            show: true,
+            is_resizable: true,
+            ..Default::default()
+        },
+        |_window, app| {
+            app.new(|_cx| DiagnosticsView {
+                state,
+                report: health::report(),
+                last_poll: Instant::now(),
+            })
+        },
+    );
+    if let Err(e) = result {
+        tracing::warn!(target: crate::logging::UI, "Failed to open diagnostics window: {}", e);
+    }
+}
+
+/// (label, value) lines describing `report`
+fn report_lines(report: &HealthReport) -> Vec<(String, String)> {
+    let socket = match report.socket {
+        SocketState::Listening => {
+            format!("listening on {}", crate::ipc::socket_path().display())
+        }
+        SocketState::Starting => "starting".to_string(),
+        SocketState::Failed => "not listening (see the log)".to_string(),
+    };
+    let watcher = match report.watcher {
+        WatcherState::Watching => match report.codex_bootstrap {
+            Some(stats) => format!(
+                "watching ({} rollouts scanned in {:.1}s)",
+                stats.rollouts,
+                stats.millis as f64 / 1000.0
+            ),
+            None => "watching".to_string(),
+        },
+        WatcherState::WaitingForCodex => "waiting for Codex to be installed".to_string(),
+        WatcherState::Starting => "starting".to_string(),
+        WatcherState::Stopped => "not running (see the log)".to_string(),
+    };
+    let mut lines = vec![
+        (
+            "Daemon".to_string(),
+            format!(
+                "{}, up {}",
+                crate::update::current_version(),
+                health::format_uptime(report.uptime_secs)
+            ),
+        ),
+        ("Hook socket".to_string(), socket),
+        ("Codex watcher".to_string(), watcher),
+    ];
+    lines.extend(report.sources.iter().map(|source| {
+        let last = source.last_event_secs.map_or_else(
+            || "no events yet".to_string(),
+            |secs| {
+                format!(
+                    "last event {}",
+                    health::format_ago(Duration::from_secs(secs))
+                )
+            },
+        );
+        let value = if source.paused {
+            format!("paused, {last}")
+        } else {
+            last
+        };
+        (source.name.clone(), value)
+    }));
+    lines
+}
+
+impl Render for DiagnosticsView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // Continuous frames drive polling, like the log viewer
+        window.request_animation_frame();
+        if self.last_poll.elapsed() >= POLL_INTERVAL {
+            self.report = health::report();
+            self.last_poll = Instant::now();
+        }
+
+        let appearance = window.appearance();
+        let theme = self.state.update(cx, |state, _cx| {
+            state.update_system_appearance(appearance);
+            state.theme_colors()
+        });
+
+        let warnings = self.report.warnings();
+        let summary = if warnings.is_empty() {
+            "Everything is running".to_string()
+        } else {
+            warnings.join("\n")
+        };
+        let lines = report_lines(&self.report)
+            .into_iter()
+            .map(|(label, value)| {
+                div()
+                    .flex()
+                    .gap(px(8.0))
+                    .child(
+                        div()
+                            .w(px(140.0))
+                            .flex_shrink_0()
+                            .text_color(theme.text_secondary)
+                            .child(label),
+                    )
+                    .child(div().child(value))
+            });
+
+        div()
+            .size_full()
+            .p(px(12.0))
+            .flex()
+            .flex_col()
+            .gap(px(6.0))
+            .bg(theme.container_bg)
+            .text_color(theme.text_primary)
+            .font_family("Maple Mono NF CN")
+            .text_size(px(12.0))
+            .child(
+                div()
+                    .text_color(theme.text_header)
+                    .pb(px(6.0))
+                    .child(summary),
+            )
+            .children(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::health::SourceHealth;
+
+    #[test]
+    fn report_lines_describe_each_source() {
+        let report = HealthReport {
+            uptime_secs: 3 * 3600 + 120,
+            sources: vec![
+                SourceHealth {
+                    name: "Claude Code hooks".into(),
+                    last_event_secs: Some(90),
+                    paused: false,
+                },
+                SourceHealth {
+                    name: "Codex rollouts".into(),
+                    last_event_secs: None,
+                    paused: true,
+                },
+            ],
+            socket: SocketState::Failed,
+            watcher: WatcherState::WaitingForCodex,
+            codex_bootstrap: None,
+            failing_tasks: Vec::new(),
+        };
+        let lines = report_lines(&report);
+        assert!(lines[0].1.ends_with(", up 3h 2m"));
+        assert_eq!(
+            lines[1],
+            ("Hook socket".into(), "not listening (see the log)".into())
+        );
+        assert_eq!(lines[2].1, "waiting for Codex to be installed");
+        assert_eq!(lines[3].1, "last event 1m ago");
+        assert_eq!(lines[4].1, "paused, no events yet");
+    }
+}
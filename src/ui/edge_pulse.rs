@@ -0,0 +1,198 @@
+//! Screen edge pulse - a colored bar flashing along the top of the display
+//!
+//! Optional (`ui.edge_pulse`), for users who hide the HUD or keep it out of
+//! sight. When a session enters one of `ui.edge_pulse_states` (Attention by
+//! default, Waiting too if listed) the indicator opens a borderless,
+//! click-through window across the top edge of its display; it pulses
+//! [`PULSES`] times (shows steadily with reduced animations) in that state's
+//! color and closes itself.
+
+use super::window_level;
+use crate::config::{Animations, WindowLevel};
+use crate::{SessionInfo, SessionState};
+use gpui::{
+    App, AppContext, Bounds, Context, Hsla, IntoElement, Pixels, Render, Styled, Window,
+    WindowBackgroundAppearance, WindowBounds, WindowKind, WindowOptions, div, hsla, px, size,
+};
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+/// Bar height
+const HEIGHT: f32 = 6.0;
+/// How long the bar stays up
+const DURATION: Duration = Duration::from_millis(1500);
+/// Flashes within [`DURATION`]
+const PULSES: u32 = 2;
+
+/// Bar color for `state` unless config `state_colors` sets one
+pub(crate) fn default_color(state: SessionState) -> Hsla {
+    match state {
+        SessionState::Waiting => hsla(0.58, 0.8, 0.62, 1.0),
+        _ => hsla(0.02, 0.85, 0.6, 1.0),
+    }
+}
+
+/// The most urgent of `states` a session entered since the last call, if
+/// any. `pulsed` holds the sessions currently in one of them, so one pulses
+/// again once it left (or switched between them) and comes back; snoozed
+/// and already seen sessions never pulse.
+pub(crate) fn entered(
+    pulsed: &mut HashSet<(String, SessionState)>,
+    sessions: &[SessionInfo],
+    states: &[SessionState],
+) -> Option<SessionState> {
+    let current: HashSet<(String, SessionState)> = sessions
+        .iter()
+        .filter(|s| states.contains(&s.state) && !s.seen && !s.is_snoozed())
+        .map(|s| (s.session_id.clone(), s.state))
+        .collect();
+    let entered = current
+        .iter()
+        .filter(|entry| !pulsed.contains(*entry))
+        .map(|(_, state)| *state)
+        .max_by_key(SessionState::urgency);
+    *pulsed = current;
+    entered
+}
+
+/// Bar opacity `elapsed` into the pulse, and whether it's over
+fn pulse_opacity(elapsed: Duration, animations: Animations) -> (f32, bool) {
+    if elapsed >= DURATION {
+        return (0.0, true);
+    }
+    if animations != Animations::Full {
+        return (1.0, false);
+    }
+    let phase = elapsed.as_secs_f32() / DURATION.as_secs_f32() * PULSES as f32;
+    ((phase.fract() * std::f32::consts::PI).sin(), false)
+}
+
+struct EdgePulseView {
+    color: Hsla,
+    started: Instant,
+}
+
+impl Render for EdgePulseView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let (opacity, done) = pulse_opacity(self.started.elapsed(), super::animation::animations());
+        if done {
+            cx.defer_in(window, |_, window, _| window.remove_window());
+        } else {
+            window.request_animation_frame();
+        }
+        div().size_full().bg(Hsla {
+            a: self.color.a * opacity,
+            ..self.color
+        })
+    }
+}
+
+/// Flash the top edge of `display` (the display's bounds) in `color`
+pub(crate) fn show(app: &mut App, display: Bounds<Pixels>, color: Hsla) {
+    let bounds = Bounds {
+        origin: display.origin,
+        size: size(display.size.width, px(HEIGHT)),
+    };
+    let result = app.open_window(
+        WindowOptions {
+            window_bounds: Some(WindowBounds::Windowed(bounds)),
+            titlebar: None,
+            focus: false,
+            show: true,
+            kind: WindowKind::PopUp,
+            is_movable: false,
+            is_resizable: false,
+            window_background: WindowBackgroundAppearance::Transparent,
+            ..Default::default()
+        },
+        |window, app| {
+            window_level::apply(window, WindowLevel::AboveFullscreen, true);
+            window_level::ignore_mouse(window);
+            app.new(|_cx| EdgePulseView {
+                color,
+                started: Instant::now(),
+            })
+        },
+    );
+    if let Err(e) = result {
+        tracing::warn!(target: crate::logging::UI, "Failed to open edge pulse: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(id: &str, state: SessionState) -> SessionInfo {
+        SessionInfo {
+            session_id: id.into(),
+            state,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn pulses_once_per_entry_into_attention() {
+        let states = [SessionState::Attention];
+        let entered_attention = |pulsed: &mut _, sessions: &[SessionInfo]| {
+            entered(pulsed, sessions, &states) == Some(SessionState::Attention)
+        };
+        let mut pulsed = HashSet::new();
+        let mut sessions = vec![session("a", SessionState::Running)];
+        assert!(!entered_attention(&mut pulsed, &sessions));
+
+        sessions[0].state = SessionState::Attention;
+        assert!(entered_attention(&mut pulsed, &sessions));
+        assert!(!entered_attention(&mut pulsed, &sessions));
+
+        // Back to work, then asking again
+        sessions[0].state = SessionState::Running;
+        assert!(!entered_attention(&mut pulsed, &sessions));
+        sessions[0].state = SessionState::Attention;
+        assert!(entered_attention(&mut pulsed, &sessions));
+
+        let mut seen = session("b", SessionState::Attention);
+        seen.seen = true;
+        let mut snoozed = session("c", SessionState::Attention);
+        snoozed.snoozed_until = Some(u64::MAX);
+        sessions.extend([seen, snoozed]);
+        assert!(!entered_attention(&mut pulsed, &sessions));
+
+        // Waiting isn't listed
+        sessions[0].state = SessionState::Waiting;
+        assert_eq!(entered(&mut pulsed, &sessions, &states), None);
+    }
+
+    #[test]
+    fn waiting_pulses_when_listed() {
+        let states = [SessionState::Attention, SessionState::Waiting];
+        let mut pulsed = HashSet::new();
+        let mut sessions = vec![session("a", SessionState::Waiting)];
+        assert_eq!(
+            entered(&mut pulsed, &sessions, &states),
+            Some(SessionState::Waiting)
+        );
+        // Switching to Attention pulses again, and Attention wins a tie
+        sessions[0].state = SessionState::Attention;
+        sessions.push(session("b", SessionState::Waiting));
+        assert_eq!(
+            entered(&mut pulsed, &sessions, &states),
+            Some(SessionState::Attention)
+        );
+        assert_eq!(entered(&mut pulsed, &sessions, &states), None);
+        assert_ne!(
+            default_color(SessionState::Waiting),
+            default_color(SessionState::Attention)
+        );
+    }
+
+    #[test]
+    fn pulse_fades_in_and_out_unless_animations_are_reduced() {
+        let at = |millis| Duration::from_millis(millis);
+        assert_eq!(pulse_opacity(at(0), Animations::Full), (0.0, false));
+        let (peak, _) = pulse_opacity(DURATION / (PULSES * 2), Animations::Full);
+        assert!((peak - 1.0).abs() < 1e-3);
+        assert_eq!(pulse_opacity(at(0), Animations::Reduced), (1.0, false));
+        assert_eq!(pulse_opacity(DURATION, Animations::Off), (0.0, true));
+    }
+}
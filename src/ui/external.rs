@@ -0,0 +1,81 @@
+//! Opening session paths in other apps (row context menu)
+//!
+//! Uses macOS `open`; elsewhere falls back to `xdg-open` and running the
+//! configured app name as a command.
+
+use std::path::Path;
+use std::process::Command;
+
+/// Where to open a path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OpenTarget<'a> {
+    /// Default file manager (Finder)
+    FileManager,
+    /// A named app, e.g. `ui.terminal_app` or `ui.editor_app`
+    App(&'a str),
+    /// Select the file in the file manager
+    Reveal,
+}
+
+/// Build the command line that opens `path` with `target`
+pub(crate) fn open_command(target: OpenTarget<'_>, path: &str) -> (String, Vec<String>) {
+    let path = path.to_string();
+    if cfg!(target_os = "macos") {
+        let args = match target {
+            OpenTarget::FileManager => vec![path],
+            OpenTarget::App(app) => vec!["-a".into(), app.into(), path],
+            OpenTarget::Reveal => vec!["-R".into(), path],
+        };
+        return ("open".into(), args);
+    }
+
+    match target {
+        OpenTarget::FileManager => ("xdg-open".into(), vec![path]),
+        OpenTarget::App(app) => (app.into(), vec![path]),
+        OpenTarget::Reveal => {
+            let parent = Path::new(&path)
+                .parent()
+                .map(|p| p.display().to_string())
+                .unwrap_or(path);
+            ("xdg-open".into(), vec![parent])
+        }
+    }
+}
+
+/// Open `path` with `target` without waiting for the app
+pub(crate) fn open(target: OpenTarget<'_>, path: &str) {
+    let (program, args) = open_command(target, path);
+    if let Err(e) = Command::new(&program).args(&args).spawn() {
+        tracing::warn!(target: crate::logging::UI, "Failed to run {} {:?}: {}", program, args, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn macos_uses_open() {
+        assert_eq!(
+            open_command(OpenTarget::App("Terminal"), "/code/aura"),
+            (
+                "open".into(),
+                vec!["-a".into(), "Terminal".into(), "/code/aura".into()]
+            )
+        );
+        assert_eq!(
+            open_command(OpenTarget::Reveal, "/t/s.jsonl"),
+            ("open".into(), vec!["-R".into(), "/t/s.jsonl".into()])
+        );
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn reveal_opens_parent_directory() {
+        assert_eq!(
+            open_command(OpenTarget::Reveal, "/t/s.jsonl"),
+            ("xdg-open".into(), vec!["/t".into()])
+        );
+    }
+}
@@ -0,0 +1,112 @@
+//! HUD font - the embedded Maple Mono unless config picks an installed one
+//!
+//! `ui.font_family` and `ui.font_size` are resolved once at startup
+//! ([`resolve`], [`set_font`]). Text takes its family from [`family`] and its
+//! size from [`size`], which scales the sizes the layout was designed for
+//! (12px body text) to the configured one; row heights follow [`scale`].
+
+use gpui::{Pixels, px};
+use std::sync::OnceLock;
+
+/// Family of the font bundled in `assets/fonts`
+pub const EMBEDDED_FAMILY: &str = "Maple Mono NF CN";
+/// Body text size the layout constants were designed for
+pub const DEFAULT_SIZE: f32 = 12.0;
+const MIN_SIZE: f32 = 9.0;
+const MAX_SIZE: f32 = 18.0;
+
+/// Font the HUD draws its text with
+#[derive(Debug, Clone, PartialEq)]
+pub struct Font {
+    pub family: String,
+    /// Body text size in points
+    pub size: f32,
+}
+
+impl Default for Font {
+    fn default() -> Self {
+        Self {
+            family: EMBEDDED_FAMILY.to_string(),
+            size: DEFAULT_SIZE,
+        }
+    }
+}
+
+static FONT: OnceLock<Font> = OnceLock::new();
+
+/// Set the font used by every HUD text element
+pub fn set_font(font: Font) {
+    let _ = FONT.set(font);
+}
+
+/// Current font family
+pub fn family() -> &'static str {
+    FONT.get()
+        .map_or(EMBEDDED_FAMILY, |font| font.family.as_str())
+}
+
+/// Configured size relative to [`DEFAULT_SIZE`]
+pub fn scale() -> f32 {
+    FONT.get().map_or(1.0, |font| font.size / DEFAULT_SIZE)
+}
+
+/// Text size for an element designed at `designed` px, rounded to half pixels
+pub fn size(designed: f32) -> Pixels {
+    px((designed * scale() * 2.0).round() / 2.0)
+}
+
+/// Font for config `ui.font_family` / `ui.font_size`: the embedded family
+/// when none is set or it isn't among `installed`, sizes clamped to 9-18
+pub fn resolve(family: Option<&str>, size: Option<f32>, installed: &[String]) -> Font {
+    let family = family.map(str::trim).filter(|f| !f.is_empty());
+    let family = match family {
+        Some(wanted) => match installed.iter().find(|f| f.eq_ignore_ascii_case(wanted)) {
+            Some(found) => found.clone(),
+            None => {
+                tracing::warn!(
+                    target: crate::logging::UI,
+                    "Font {:?} isn't installed, using {}",
+                    wanted,
+                    EMBEDDED_FAMILY
+                );
+                EMBEDDED_FAMILY.to_string()
+            }
+        },
+        None => EMBEDDED_FAMILY.to_string(),
+    };
+    let size = size
+        .filter(|size| size.is_finite())
+        .map_or(DEFAULT_SIZE, |size| size.clamp(MIN_SIZE, MAX_SIZE));
+    Font { family, size }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_falls_back_to_the_embedded_font() {
+        let installed = vec!["SF Mono".to_string(), "Menlo".to_string()];
+        assert_eq!(resolve(None, None, &installed), Font::default());
+
+        let font = resolve(Some("sf mono"), Some(14.0), &installed);
+        assert_eq!(font.family, "SF Mono");
+        assert_eq!(font.size, 14.0);
+
+        let missing = resolve(Some("Comic Sans"), Some(40.0), &installed);
+        assert_eq!(missing.family, EMBEDDED_FAMILY);
+        assert_eq!(missing.size, MAX_SIZE);
+        assert_eq!(
+            resolve(Some(" "), Some(f32::NAN), &installed),
+            Font::default()
+        );
+    }
+
+    #[test]
+    fn sizes_scale_from_the_default() {
+        // Never set in tests, so the designed sizes come through unchanged
+        assert_eq!(scale(), 1.0);
+        assert_eq!(size(11.0), px(11.0));
+        assert_eq!(family(), EMBEDDED_FAMILY);
+    }
+}
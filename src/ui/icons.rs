@@ -3,6 +3,10 @@
 //! Icons from Lucide (https://lucide.dev), 24x24 viewBox
 //! Rendered at 16x16px in the HUD using gpui's svg() element
 
+use super::theme::ThemeColors;
+use crate::tools::ToolCategory;
+use gpui::Hsla;
+
 /// Get SVG asset path for a state
 pub fn state_icon_path(state: crate::SessionState) -> &'static str {
     match state {
@@ -15,8 +19,14 @@ pub fn state_icon_path(state: crate::SessionState) -> &'static str {
     }
 }
 
-/// Get SVG asset path for a tool name
-pub fn tool_icon_asset(tool_name: &str) -> &'static str {
+/// Color of a state icon: the state's configured color, else the theme's
+pub fn state_icon_color(state: crate::SessionState, theme: &ThemeColors) -> Hsla {
+    theme.states.get(state).unwrap_or(theme.icon_state)
+}
+
+/// Get SVG asset path for a tool: its own icon for Claude Code's tools,
+/// otherwise its category's
+pub fn tool_icon_asset(tool_name: &str, category: ToolCategory) -> &'static str {
     match tool_name {
         "Task" => "icons/bot.svg",
         "Bash" => "icons/terminal.svg",
@@ -27,8 +37,22 @@ pub fn tool_icon_asset(tool_name: &str) -> &'static str {
         "Write" => "icons/file-braces.svg",
         "WebFetch" => "icons/monitor-down.svg",
         "WebSearch" => "icons/binoculars.svg",
-        name if name.starts_with("mcp__") => "icons/plug.svg",
-        _ => "icons/ticket.svg",
+        _ => category_icon_asset(category),
+    }
+}
+
+/// Get SVG asset path for a tool category
+pub fn category_icon_asset(category: ToolCategory) -> &'static str {
+    match category {
+        ToolCategory::Shell => "icons/terminal.svg",
+        ToolCategory::Edit => "icons/file-pen-line.svg",
+        ToolCategory::Read => "icons/newspaper.svg",
+        ToolCategory::Search => "icons/file-search.svg",
+        ToolCategory::Web => "icons/globe.svg",
+        ToolCategory::Vcs => "icons/git-branch.svg",
+        ToolCategory::Mcp => "icons/plug.svg",
+        ToolCategory::Agent => "icons/bot.svg",
+        ToolCategory::Other => "icons/ticket.svg",
     }
 }
 
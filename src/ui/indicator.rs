@@ -4,17 +4,29 @@
 //! - Attention: bell_ring (bright icon, shaking animation)
 //! - Running (sessions exist): cycles through 11 creative icons every 2500ms
 //! - No sessions: panda (dim, static)
+//! - Aura itself is unhealthy (see [`crate::health`]): small triangle_alert
+//!   badge in the top-right corner, on top of any state
+//!
+//! With `ui.indicator_style: "segments"` the icon is ringed by up to
+//! [`MAX_SEGMENTS`] arcs, one per live session in priority order, colored by
+//! its state - "3 running, 1 attention" without opening the list.
 //!
 //! Visual design:
 //! - Liquid glass: translucent background with border
 //! - Gloss overlay: top half for depth
 //! - Icon: themed color (white for dark, black for light)
 
-use super::animation::{calculate_shake_offset, ease_out};
+use super::animation::{animations, calculate_shake_offset, calculate_spin_radians, ease_out};
 use super::icons;
 use super::theme::ThemeColors;
+use crate::config::Animations;
+use crate::config::{IndicatorRule, IndicatorStyle};
 use crate::{SessionInfo, SessionState};
-use gpui::{Div, Hsla, ParentElement, Styled, Transformation, div, px, radians, svg};
+use gpui::{
+    Canvas, Div, Hsla, ParentElement, PathBuilder, Styled, Transformation, canvas, div, hsla,
+    point, prelude::FluentBuilder, px, radians, svg,
+};
+use std::f32::consts::TAU;
 use std::time::Instant;
 
 /// Indicator dimensions (matching React prototype: 36x36px rounded square)
@@ -38,6 +50,18 @@ const ICON_TRANSITION_MS: u64 = 400;
 const ICON_ATTENTION: &str = "icons/bell-ring.svg";
 const ICON_WAITING: &str = "icons/fan.svg";
 const ICON_NO_SESSIONS: &str = "icons/panda.svg";
+const ICON_WARNING: &str = "icons/triangle-alert.svg";
+
+/// Health warning badge size and inset from the indicator's corner
+const WARNING_BADGE_SIZE: f32 = 10.0;
+const WARNING_BADGE_INSET: f32 = 3.0;
+
+/// Most session arcs in the segments style
+const MAX_SEGMENTS: usize = 8;
+/// Radius of the arcs' center line, stroke width, and gap between arcs
+const SEGMENT_RADIUS: f32 = 13.5;
+const SEGMENT_WIDTH: f32 = 2.5;
+const SEGMENT_GAP: f32 = 0.35;
 
 /// Indicator state
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -53,24 +77,230 @@ pub(crate) enum IndicatorState {
 }
 
 /// Determine the current indicator state from sessions
-pub(crate) fn determine_state(sessions: &[SessionInfo]) -> IndicatorState {
-    if sessions.is_empty() {
-        IndicatorState::NoSessions
-    } else if sessions.iter().any(|s| s.state == SessionState::Attention) {
-        IndicatorState::Attention
-    } else if sessions.iter().any(|s| s.state == SessionState::Waiting) {
-        IndicatorState::Waiting
+///
+/// `priority` (config `ui.indicator_priority`) lists the states that count,
+/// highest priority first; the first rule matched by a session picks the
+/// indicator look. Snoozed sessions and sessions in unlisted states are
+/// ignored, and Attention/Waiting/Idle sessions the user already marked seen
+/// don't escalate it.
+pub(crate) fn determine_state(
+    sessions: &[SessionInfo],
+    priority: &[IndicatorRule],
+) -> IndicatorState {
+    let sessions: Vec<&SessionInfo> = sessions
+        .iter()
+        .filter(|s| !s.is_snoozed() && priority.iter().any(|rule| rule.state == s.state))
+        .collect();
+    let acknowledged = |s: &SessionInfo| {
+        s.seen
+            && matches!(
+                s.state,
+                SessionState::Attention | SessionState::Waiting | SessionState::Idle
+            )
+    };
+    let matched = priority.iter().find(|rule| {
+        sessions
+            .iter()
+            .any(|s| s.state == rule.state && !acknowledged(s))
+    });
+    match matched.map(IndicatorRule::shown_state) {
+        _ if sessions.is_empty() => IndicatorState::NoSessions,
+        Some(SessionState::Attention) => IndicatorState::Attention,
+        Some(SessionState::Waiting) => IndicatorState::Waiting,
+        _ => IndicatorState::Running,
+    }
+}
+
+/// Whether any session is still live (not stale); drives `ui.auto_hide`
+pub(crate) fn has_active_sessions(sessions: &[SessionInfo]) -> bool {
+    sessions.iter().any(|s| s.state != SessionState::Stale)
+}
+
+/// One arc per live session for the segments style, highest priority first:
+/// the state it shows as, and whether the user already saw it
+///
+/// Counts the sessions [`determine_state`] does, except Stale ones; at most
+/// [`MAX_SEGMENTS`].
+pub(crate) fn segments(
+    sessions: &[SessionInfo],
+    priority: &[IndicatorRule],
+) -> Vec<(SessionState, bool)> {
+    let mut ranked: Vec<(usize, &IndicatorRule, &SessionInfo)> = sessions
+        .iter()
+        .filter(|s| !s.is_snoozed() && s.state != SessionState::Stale)
+        .filter_map(|s| {
+            let (rank, rule) = priority
+                .iter()
+                .enumerate()
+                .find(|(_, rule)| rule.state == s.state)?;
+            Some((rank, rule, s))
+        })
+        .collect();
+    ranked.sort_by_key(|(rank, _, _)| *rank);
+    ranked
+        .into_iter()
+        .take(MAX_SEGMENTS)
+        .map(|(_, rule, s)| {
+            let seen = s.seen
+                && matches!(
+                    s.state,
+                    SessionState::Attention | SessionState::Waiting | SessionState::Idle
+                );
+            (rule.shown_state(), seen)
+        })
+        .collect()
+}
+
+/// Start and end angle of each of `count` arcs, in radians clockwise from
+/// 12 o'clock; a single arc closes the ring
+fn segment_angles(count: usize) -> Vec<(f32, f32)> {
+    let span = TAU / count.max(1) as f32;
+    let gap = if count > 1 { SEGMENT_GAP } else { 0.0 };
+    (0..count)
+        .map(|i| {
+            let start = i as f32 * span + gap / 2.0;
+            (start, start + span - gap)
+        })
+        .collect()
+}
+
+/// Arc color: the state's configured color, else a fixed hue; seen arcs fade
+fn segment_color(state: SessionState, seen: bool, theme: &ThemeColors) -> Hsla {
+    let color = theme.states.get(state).unwrap_or_else(|| match state {
+        SessionState::Attention => hsla(0.02, 0.85, 0.6, 1.0),
+        SessionState::Waiting => hsla(0.58, 0.8, 0.62, 1.0),
+        SessionState::Running => hsla(0.38, 0.65, 0.5, 1.0),
+        SessionState::Compacting => hsla(0.78, 0.6, 0.66, 1.0),
+        SessionState::Idle | SessionState::Stale => Hsla {
+            a: theme.indicator_icon.a * 0.4,
+            ..theme.indicator_icon
+        },
+    });
+    if seen {
+        Hsla {
+            a: color.a * 0.5,
+            ..color
+        }
     } else {
-        IndicatorState::Running
+        color
+    }
+}
+
+/// Ring of session arcs covering the indicator
+fn render_segments(segments: &[(SessionState, bool)], theme: &ThemeColors) -> Canvas<()> {
+    let arcs: Vec<((f32, f32), Hsla)> = segment_angles(segments.len())
+        .into_iter()
+        .zip(
+            segments
+                .iter()
+                .map(|(state, seen)| segment_color(*state, *seen, theme)),
+        )
+        .collect();
+    canvas(
+        |_bounds, _window, _cx| {},
+        move |bounds, (), window, _cx| {
+            let center = bounds.center();
+            let at = |angle: f32| {
+                point(
+                    center.x + px(SEGMENT_RADIUS * angle.sin()),
+                    center.y - px(SEGMENT_RADIUS * angle.cos()),
+                )
+            };
+            let radii = point(px(SEGMENT_RADIUS), px(SEGMENT_RADIUS));
+            for ((start, end), color) in arcs {
+                // Two halves, so no arc needs the large-arc flag
+                let mut path = PathBuilder::stroke(px(SEGMENT_WIDTH));
+                path.move_to(at(start));
+                path.arc_to(radii, px(0.0), false, true, at((start + end) / 2.0));
+                path.arc_to(radii, px(0.0), false, true, at(end));
+                if let Ok(path) = path.build() {
+                    window.paint_path(path, color);
+                }
+            }
+        },
+    )
+    .absolute()
+    .top_0()
+    .left_0()
+    .size(px(INDICATOR_SIZE))
+}
+
+/// What VoiceOver reads for the indicator (its window title), e.g.
+/// "Aura: 3 sessions, 1 needs attention, 2 running"
+pub(crate) fn accessibility_label(sessions: &[SessionInfo]) -> String {
+    let live: Vec<&SessionInfo> = sessions
+        .iter()
+        .filter(|s| s.state != SessionState::Stale)
+        .collect();
+    if live.is_empty() {
+        return "Aura: no sessions".to_string();
+    }
+    let count = |state: SessionState| live.iter().filter(|s| s.state == state).count();
+    let mut parts = vec![format!(
+        "{} session{}",
+        live.len(),
+        if live.len() == 1 { "" } else { "s" }
+    )];
+    let attention = count(SessionState::Attention);
+    if attention > 0 {
+        let verb = if attention == 1 { "needs" } else { "need" };
+        parts.push(format!("{attention} {verb} attention"));
+    }
+    for (state, label) in [
+        (SessionState::Waiting, "waiting for input"),
+        (SessionState::Running, "running"),
+        (SessionState::Compacting, "compacting"),
+        (SessionState::Idle, "done"),
+    ] {
+        let n = count(state);
+        if n > 0 {
+            parts.push(format!("{n} {label}"));
+        }
+    }
+    format!("Aura: {}", parts.join(", "))
+}
+
+/// When the indicator needs to redraw without a registry change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Redraw {
+    /// An animation is running (shake, spin, icon slide)
+    EveryFrame,
+    /// Static until the next running-icon cycle
+    At(Instant),
+    /// Static until something changes
+    OnChange,
+}
+
+/// Next redraw the indicator needs for `state` at `now`
+pub(crate) fn next_redraw(state: IndicatorState, animation_start: Instant, now: Instant) -> Redraw {
+    let level = animations();
+    match state {
+        IndicatorState::Attention | IndicatorState::Waiting if level == Animations::Full => {
+            Redraw::EveryFrame
+        }
+        IndicatorState::Running if level != Animations::Off => {
+            let elapsed_ms = now.saturating_duration_since(animation_start).as_millis() as u64;
+            if level == Animations::Full && elapsed_ms % ICON_CYCLE_MS < ICON_TRANSITION_MS {
+                Redraw::EveryFrame
+            } else {
+                let next_cycle = (elapsed_ms / ICON_CYCLE_MS + 1) * ICON_CYCLE_MS;
+                Redraw::At(animation_start + std::time::Duration::from_millis(next_cycle))
+            }
+        }
+        _ => Redraw::OnChange,
     }
 }
 
 /// Get icon state for running animation - returns (current_icon, prev_icon, transition_progress)
 /// transition_progress: 0.0-1.0 during first 400ms of cycle, 1.0 after transition complete
+/// (icons swap without sliding under reduced motion and stay put with animations off)
 pub(crate) fn get_running_icon_state(
     animation_start: Instant,
 ) -> (&'static str, &'static str, f32) {
-    let elapsed_ms = animation_start.elapsed().as_millis() as u64;
+    let elapsed_ms = match animations() {
+        Animations::Off => 0,
+        _ => animation_start.elapsed().as_millis() as u64,
+    };
     let icon_count = icons::INDICATOR_RUNNING_ASSETS.len();
 
     let cycle = (elapsed_ms / ICON_CYCLE_MS) as usize;
@@ -86,11 +316,12 @@ pub(crate) fn get_running_icon_state(
     let current_icon = icons::INDICATOR_RUNNING_ASSETS[current_idx];
     let prev_icon = icons::INDICATOR_RUNNING_ASSETS[prev_idx];
 
-    let transition_progress = if pos_in_cycle < ICON_TRANSITION_MS {
-        pos_in_cycle as f32 / ICON_TRANSITION_MS as f32
-    } else {
-        1.0
-    };
+    let transition_progress =
+        if pos_in_cycle < ICON_TRANSITION_MS && animations() == Animations::Full {
+            pos_in_cycle as f32 / ICON_TRANSITION_MS as f32
+        } else {
+            1.0
+        };
 
     (current_icon, prev_icon, transition_progress)
 }
@@ -100,13 +331,18 @@ pub(crate) fn get_running_icon_state(
 /// When `is_hovered` is true, applies enhanced visual effect:
 /// - Increased background opacity
 /// - Brighter gloss highlight
+///
+/// `warning` adds the health warning badge; `style` adds the session ring.
 pub fn render(
     sessions: &[SessionInfo],
+    priority: &[IndicatorRule],
+    style: IndicatorStyle,
     animation_start: Instant,
     is_hovered: bool,
+    warning: bool,
     theme: &ThemeColors,
 ) -> Div {
-    let state = determine_state(sessions);
+    let state = determine_state(sessions, priority);
 
     // Get running icon state (may include transition)
     let running_state = if state == IndicatorState::Running {
@@ -151,9 +387,7 @@ pub fn render(
 
     // Calculate rotation for waiting state (2 second full rotation, counter-clockwise)
     let rotation_radians = if state == IndicatorState::Waiting {
-        let elapsed_ms = animation_start.elapsed().as_millis() as f32;
-        let rotation_period_ms = 2000.0; // 2 seconds per full rotation
-        -((elapsed_ms / rotation_period_ms) * std::f32::consts::TAU) // Negative for counter-clockwise
+        calculate_spin_radians(animation_start)
     } else {
         0.0
     };
@@ -164,10 +398,18 @@ pub fn render(
         ..theme.indicator_bg
     };
     let border_color = theme.indicator_border;
-    // Icon color from theme with state-based alpha
+    // Icon color from theme (or the state's configured color) with
+    // state-based alpha
+    let base_icon_color = match state {
+        IndicatorState::Attention => theme.states.attention,
+        IndicatorState::Waiting => theme.states.waiting,
+        IndicatorState::Running => theme.states.running,
+        IndicatorState::NoSessions => None,
+    }
+    .unwrap_or(theme.indicator_icon);
     let icon_color = Hsla {
-        a: theme.indicator_icon.a * icon_alpha,
-        ..theme.indicator_icon
+        a: base_icon_color.a * icon_alpha,
+        ..base_icon_color
     };
     // Gloss color from theme with state adjustments
     let gloss_color = Hsla {
@@ -195,6 +437,9 @@ pub fn render(
                 .h(px(INDICATOR_SIZE / 2.0))
                 .bg(gloss_color),
         )
+        .when(style == IndicatorStyle::Segments, |this| {
+            this.child(render_segments(&segments(sessions, priority), theme))
+        })
         // SVG Icon (centered, with shake animation for attention, slide for running)
         .child(
             div()
@@ -310,6 +555,20 @@ pub fn render(
                     },
                 ),
         )
+        .when(warning, |this| {
+            this.child(
+                div()
+                    .absolute()
+                    .top(px(WARNING_BADGE_INSET))
+                    .right(px(WARNING_BADGE_INSET))
+                    .child(
+                        svg()
+                            .path(ICON_WARNING)
+                            .size(px(WARNING_BADGE_SIZE))
+                            .text_color(theme.indicator_icon),
+                    ),
+            )
+        })
 }
 
 #[cfg(test)]
@@ -318,17 +577,20 @@ mod tests {
     use crate::{SessionInfo, SessionState};
     use std::time::{Duration, Instant};
 
+    fn default_priority() -> Vec<IndicatorRule> {
+        crate::config::UiConfig::default().indicator_priority
+    }
+
+    fn rule(state: SessionState, show_as: Option<SessionState>) -> IndicatorRule {
+        IndicatorRule { state, show_as }
+    }
+
     fn make_session(state: SessionState) -> SessionInfo {
         SessionInfo {
             session_id: "test".into(),
             cwd: "/tmp".into(),
             state,
-            running_tools: vec![],
-            name: None,
-            stopped_at: None,
-            stale_at: None,
-            permission_tool: None,
-            recent_activity: vec![],
+            ..Default::default()
         }
     }
 
@@ -336,13 +598,19 @@ mod tests {
 
     #[test]
     fn determine_state_no_sessions() {
-        assert_eq!(determine_state(&[]), IndicatorState::NoSessions);
+        assert_eq!(
+            determine_state(&[], &default_priority()),
+            IndicatorState::NoSessions
+        );
     }
 
     #[test]
     fn determine_state_one_running() {
         let sessions = vec![make_session(SessionState::Running)];
-        assert_eq!(determine_state(&sessions), IndicatorState::Running);
+        assert_eq!(
+            determine_state(&sessions, &default_priority()),
+            IndicatorState::Running
+        );
     }
 
     #[test]
@@ -351,7 +619,10 @@ mod tests {
             make_session(SessionState::Running),
             make_session(SessionState::Attention),
         ];
-        assert_eq!(determine_state(&sessions), IndicatorState::Attention);
+        assert_eq!(
+            determine_state(&sessions, &default_priority()),
+            IndicatorState::Attention
+        );
     }
 
     #[test]
@@ -360,7 +631,35 @@ mod tests {
             make_session(SessionState::Running),
             make_session(SessionState::Waiting),
         ];
-        assert_eq!(determine_state(&sessions), IndicatorState::Waiting);
+        assert_eq!(
+            determine_state(&sessions, &default_priority()),
+            IndicatorState::Waiting
+        );
+    }
+
+    #[test]
+    fn determine_state_ignores_snoozed() {
+        let mut snoozed = make_session(SessionState::Attention);
+        snoozed.snoozed_until = Some(u64::MAX);
+        let sessions = vec![make_session(SessionState::Running), snoozed.clone()];
+        assert_eq!(
+            determine_state(&sessions, &default_priority()),
+            IndicatorState::Running
+        );
+        assert_eq!(
+            determine_state(&[snoozed], &default_priority()),
+            IndicatorState::NoSessions
+        );
+    }
+
+    #[test]
+    fn determine_state_ignores_seen_attention() {
+        let mut seen = make_session(SessionState::Attention);
+        seen.seen = true;
+        assert_eq!(
+            determine_state(&[seen], &default_priority()),
+            IndicatorState::Running
+        );
     }
 
     #[test]
@@ -369,7 +668,10 @@ mod tests {
             make_session(SessionState::Idle),
             make_session(SessionState::Stale),
         ];
-        assert_eq!(determine_state(&sessions), IndicatorState::Running);
+        assert_eq!(
+            determine_state(&sessions, &default_priority()),
+            IndicatorState::Running
+        );
     }
 
     #[test]
@@ -378,7 +680,150 @@ mod tests {
             make_session(SessionState::Waiting),
             make_session(SessionState::Attention),
         ];
-        assert_eq!(determine_state(&sessions), IndicatorState::Attention);
+        assert_eq!(
+            determine_state(&sessions, &default_priority()),
+            IndicatorState::Attention
+        );
+    }
+
+    #[test]
+    fn determine_state_custom_priority() {
+        // Idle ignored entirely, Waiting escalated to Attention
+        let priority = vec![
+            rule(SessionState::Attention, None),
+            rule(SessionState::Waiting, Some(SessionState::Attention)),
+            rule(SessionState::Running, None),
+        ];
+        let idle = make_session(SessionState::Idle);
+        assert_eq!(
+            determine_state(&[idle.clone()], &priority),
+            IndicatorState::NoSessions
+        );
+        assert_eq!(
+            determine_state(&[idle, make_session(SessionState::Waiting)], &priority),
+            IndicatorState::Attention
+        );
+
+        // Waiting ranked above Attention
+        let priority = vec![
+            rule(SessionState::Waiting, None),
+            rule(SessionState::Attention, None),
+        ];
+        let sessions = vec![
+            make_session(SessionState::Attention),
+            make_session(SessionState::Waiting),
+        ];
+        assert_eq!(
+            determine_state(&sessions, &priority),
+            IndicatorState::Waiting
+        );
+    }
+
+    #[test]
+    fn next_redraw_only_animates_when_needed() {
+        use std::time::Duration;
+        let start = Instant::now();
+        assert_eq!(
+            next_redraw(IndicatorState::Attention, start, start),
+            Redraw::EveryFrame
+        );
+        assert_eq!(
+            next_redraw(IndicatorState::NoSessions, start, start),
+            Redraw::OnChange
+        );
+
+        // Running: frames during the icon slide, then sleep until the next cycle
+        let during = start + Duration::from_millis(ICON_CYCLE_MS + 100);
+        assert_eq!(
+            next_redraw(IndicatorState::Running, start, during),
+            Redraw::EveryFrame
+        );
+        let after = start + Duration::from_millis(ICON_CYCLE_MS + ICON_TRANSITION_MS);
+        assert_eq!(
+            next_redraw(IndicatorState::Running, start, after),
+            Redraw::At(start + Duration::from_millis(2 * ICON_CYCLE_MS))
+        );
+    }
+
+    #[test]
+    fn segments_follow_priority_and_skip_stale_and_snoozed() {
+        let mut snoozed = make_session(SessionState::Attention);
+        snoozed.snoozed_until = Some(u64::MAX);
+        let mut seen = make_session(SessionState::Waiting);
+        seen.seen = true;
+        let sessions = vec![
+            make_session(SessionState::Running),
+            make_session(SessionState::Stale),
+            snoozed,
+            seen,
+            make_session(SessionState::Attention),
+            make_session(SessionState::Running),
+        ];
+        assert_eq!(
+            segments(&sessions, &default_priority()),
+            vec![
+                (SessionState::Attention, false),
+                (SessionState::Waiting, true),
+                (SessionState::Running, false),
+                (SessionState::Running, false),
+            ]
+        );
+
+        let many = vec![make_session(SessionState::Idle); MAX_SEGMENTS + 3];
+        assert_eq!(segments(&many, &default_priority()).len(), MAX_SEGMENTS);
+    }
+
+    #[test]
+    fn segment_angles_split_the_ring() {
+        assert!(segment_angles(0).is_empty());
+        assert_eq!(segment_angles(1), vec![(0.0, TAU)]);
+        let arcs = segment_angles(4);
+        assert_eq!(arcs.len(), 4);
+        for (i, (start, end)) in arcs.iter().enumerate() {
+            let expected = i as f32 * TAU / 4.0 + SEGMENT_GAP / 2.0;
+            assert!((start - expected).abs() < 1e-5);
+            assert!((end - start - (TAU / 4.0 - SEGMENT_GAP)).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn segment_colors_follow_configured_state_colors() {
+        let mut theme = ThemeColors::liquid_dark();
+        let default = segment_color(SessionState::Attention, false, &theme);
+        let orange = crate::ui::theme::parse_hex_color("#ff9f0a").unwrap();
+        theme.states.attention = Some(orange);
+        assert_ne!(default, orange);
+        assert_eq!(
+            segment_color(SessionState::Attention, false, &theme),
+            orange
+        );
+        assert_eq!(segment_color(SessionState::Attention, true, &theme).a, 0.5);
+    }
+
+    #[test]
+    fn accessibility_label_counts_live_sessions_by_state() {
+        assert_eq!(accessibility_label(&[]), "Aura: no sessions");
+        let sessions = vec![
+            make_session(SessionState::Running),
+            make_session(SessionState::Running),
+            make_session(SessionState::Attention),
+            make_session(SessionState::Idle),
+            make_session(SessionState::Stale),
+        ];
+        assert_eq!(
+            accessibility_label(&sessions),
+            "Aura: 4 sessions, 1 needs attention, 2 running, 1 done"
+        );
+    }
+
+    #[test]
+    fn has_active_sessions_ignores_stale() {
+        assert!(!has_active_sessions(&[]));
+        assert!(!has_active_sessions(&[make_session(SessionState::Stale)]));
+        assert!(has_active_sessions(&[
+            make_session(SessionState::Stale),
+            make_session(SessionState::Idle),
+        ]));
     }
 
     // -- Running icon cycling --
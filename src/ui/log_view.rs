@@ -0,0 +1,226 @@
+//! Log viewer - debug window tailing the daemon log file
+//!
+//! Opened from the app menu ("Show Log"). Shows the last lines of the newest
+//! file from [`crate::logging::latest_file`], follows appends and switches
+//! to the next file when the log rotates.
+
+use super::SharedHudState;
+use gpui::{
+    App, AppContext, Bounds, Context, Entity, IntoElement, ParentElement, Render, ScrollHandle,
+    StatefulInteractiveElement, Styled, TitlebarOptions, Window, WindowBounds, WindowOptions, div,
+    px, size,
+};
+use std::collections::VecDeque;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// How often the log file is checked for new lines
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Lines kept and rendered
+const MAX_LINES: usize = 1000;
+/// Bytes read from the end of the file when it's first opened
+const INITIAL_WINDOW: u64 = 256 * 1024;
+const WINDOW_WIDTH: f32 = 820.0;
+const WINDOW_HEIGHT: f32 = 520.0;
+
+/// Reads lines appended to a text file
+#[derive(Debug)]
+struct TextTail {
+    path: PathBuf,
+    offset: u64,
+    /// Bytes after the last newline
+    partial: Vec<u8>,
+}
+
+impl TextTail {
+    /// Start near the end of `path`, skipping the first (likely cut) line
+    fn near_end(path: PathBuf) -> Self {
+        let len = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        let offset = len.saturating_sub(INITIAL_WINDOW);
+        let mut tail = Self {
+            path,
+            offset,
+            partial: Vec::new(),
+        };
+        if offset > 0 {
+            // Drop the partial first line
+            let _ = tail.read_new();
+            tail.partial.clear();
+        }
+        tail
+    }
+
+    /// Complete lines appended since the last read; starts over if the file shrank
+    fn read_new(&mut self) -> std::io::Result<Vec<String>> {
+        let mut file = std::fs::File::open(&self.path)?;
+        let len = file.metadata()?.len();
+        if len < self.offset {
+            self.offset = 0;
+            self.partial.clear();
+        }
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut bytes = Vec::new();
+        file.take(len - self.offset).read_to_end(&mut bytes)?;
+        self.offset += bytes.len() as u64;
+
+        self.partial.extend_from_slice(&bytes);
+        let Some(last_newline) = self.partial.iter().rposition(|&b| b == b'\n') else {
+            return Ok(Vec::new());
+        };
+        let rest = self.partial.split_off(last_newline + 1);
+        let complete = std::mem::replace(&mut self.partial, rest);
+        Ok(String::from_utf8_lossy(&complete)
+            .lines()
+            .map(str::to_string)
+            .collect())
+    }
+}
+
+/// Log viewer window view
+pub(crate) struct LogView {
+    state: Entity<SharedHudState>,
+    tail: Option<TextTail>,
+    lines: VecDeque<String>,
+    last_poll: Option<Instant>,
+    error: Option<String>,
+    scroll_handle: ScrollHandle,
+}
+
+/// Open the log viewer window.
+pub(crate) fn open(app: &mut App, state: Entity<SharedHudState>) {
+    let result = app.open_window(
+        WindowOptions {
+            window_bounds: Some(WindowBounds::Windowed(Bounds::centered(
+                None,
+                size(px(WINDOW_WIDTH), px(WINDOW_HEIGHT)),
+                app,
+            ))),
+            titlebar: Some(TitlebarOptions {
+                title: Some("Aura Log".into()),
+                ..Default::default()
+            }),
+            focus: true,
+            show: true,
+            is_resizable: true,
+            ..Default::default()
+        },
+        |_window, app| {
+            app.new(|_cx| LogView {
+                state,
+                tail: None,
+                lines: VecDeque::new(),
+                last_poll: None,
+                error: None,
+                scroll_handle: ScrollHandle::new(),
+            })
+        },
+    );
+    if let Err(e) = result {
+        tracing::warn!(target: crate::logging::UI, "Failed to open log window: {}", e);
+    }
+}
+
+impl LogView {
+    /// Read appended lines if the poll interval has passed
+    fn poll(&mut self) {
+        if self
+            .last_poll
+            .is_some_and(|last| last.elapsed() < POLL_INTERVAL)
+        {
+            return;
+        }
+        self.last_poll = Some(Instant::now());
+
+        // Follow rotation to a newer file
+        if let Some(latest) = crate::logging::latest_file()
+            && self.tail.as_ref().is_none_or(|tail| tail.path != latest)
+        {
+            self.tail = Some(TextTail::near_end(latest));
+            self.lines.clear();
+        }
+        let Some(tail) = self.tail.as_mut() else {
+            self.error = Some("No log file yet".to_string());
+            return;
+        };
+
+        match tail.read_new() {
+            Ok(lines) => {
+                self.error = None;
+                if lines.is_empty() {
+                    return;
+                }
+                self.lines.extend(lines);
+                let excess = self.lines.len().saturating_sub(MAX_LINES);
+                self.lines.drain(..excess);
+                self.scroll_handle.scroll_to_bottom();
+            }
+            Err(e) => {
+                self.error = Some(format!("Can't read {}: {}", tail.path.display(), e));
+            }
+        }
+    }
+}
+
+impl Render for LogView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // Continuous frames drive polling, like the transcript viewer
+        window.request_animation_frame();
+        self.poll();
+
+        let appearance = window.appearance();
+        let theme = self.state.update(cx, |state, _cx| {
+            state.update_system_appearance(appearance);
+            state.theme_colors()
+        });
+
+        let lines: Vec<_> = self
+            .lines
+            .iter()
+            .map(|line| div().whitespace_nowrap().child(line.clone()))
+            .collect();
+        let error_note = self
+            .error
+            .clone()
+            .map(|error| div().text_color(theme.text_secondary).child(error));
+
+        div()
+            .id("log-scroll")
+            .size_full()
+            .overflow_scroll()
+            .track_scroll(&self.scroll_handle)
+            .p(px(10.0))
+            .flex()
+            .flex_col()
+            .bg(theme.container_bg)
+            .text_color(theme.text_primary)
+            .font_family("Maple Mono NF CN")
+            .text_size(px(11.0))
+            .children(lines)
+            .children(error_note)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn text_tail_returns_complete_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("aura.2026-10-14.log");
+        let mut file = std::fs::File::create(&path).unwrap();
+        write!(file, "one\ntw").unwrap();
+
+        let mut tail = TextTail::near_end(path.clone());
+        assert_eq!(tail.read_new().unwrap(), vec!["one"]);
+        write!(file, "o\nthree\n").unwrap();
+        assert_eq!(tail.read_new().unwrap(), vec!["two", "three"]);
+        assert!(tail.read_new().unwrap().is_empty());
+
+        // Truncated: start over
+        std::fs::write(&path, "fresh\n").unwrap();
+        assert_eq!(tail.read_new().unwrap(), vec!["fresh"]);
+    }
+}
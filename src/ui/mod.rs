@@ -2,70 +2,124 @@
 //!
 //! Architecture:
 //! - Two separate popup windows: Indicator (36x36) and Session List (320xN)
+//! - active_context.rs: Frontmost terminal probe for focus-follows-activity
 //! - assets.rs: SVG icon asset source
+//! - context_menu.rs: Dropdown overlay for the header and row menus
+//! - deep_link.rs: `aura://session/<id>` links
+//! - diagnostics.rs: Health report window (event sources, socket, watcher)
+//! - edge_pulse.rs: Bar flashing along the screen's top edge on Attention (or Waiting)
+//! - external.rs: Open session paths in Finder, a terminal, or an editor
+//! - font.rs: HUD font family and size from `ui.font_family` / `ui.font_size`
 //! - indicator.rs: Single centered icon showing aggregate state
+//! - log_view.rs: Debug window tailing the daemon log file
 //! - session_list.rs: Expanded session row rendering
-//! - animation.rs: Tool cycling, marquee, and shake animations
+//! - animation.rs: Tool cycling, marquee, and shake animations (honoring `ui.animations`)
 //! - icons.rs: Icon paths and colors
 //! - theme.rs: Theme system with Dark, Light, and System modes
+//! - tooltip.rs: Hover popover shared by HUD elements (session rows)
+//! - transcript_view.rs: Read-only transcript viewer window with live tail
+//! - window_level.rs: macOS window level and Spaces behavior
 
+mod active_context;
 mod animation;
 pub(crate) mod assets;
+mod context_menu;
+mod deep_link;
+mod diagnostics;
+mod edge_pulse;
+mod external;
+mod font;
 mod glass;
 pub(crate) mod icons;
 pub(crate) mod indicator;
+mod log_view;
 pub(crate) mod session_list;
 pub(crate) mod theme;
+mod tooltip;
+mod transcript_view;
+mod window_level;
 
-use crate::registry::SessionRegistry;
+use crate::clock::SharedClock;
+use crate::logging;
+use crate::registry::{BulkAction, Published, SharedRegistry};
 use crate::{SessionInfo, SessionState};
 use animation::{
-    calculate_animation_state, calculate_breathe_opacity, calculate_icon_swap,
-    calculate_row_slide_in, calculate_row_slide_out,
+    calculate_animation_state, calculate_breathe_opacity, calculate_fade_in, calculate_icon_swap,
+    calculate_row_slide_in, calculate_row_slide_out, calculate_window_resize,
 };
 use assets::Assets;
 use gpui::{
-    App, AppContext, Application, Bounds, Context, Entity, InteractiveElement, IntoElement, Menu,
-    MenuItem, ParentElement, Pixels, Point, Render, SharedString, StatefulInteractiveElement,
-    Styled, Window, WindowBackgroundAppearance, WindowBounds, WindowHandle, WindowKind,
-    WindowOptions, actions, div, point, prelude::FluentBuilder, px, size, uniform_list,
+    App, AppContext, Application, Bounds, ClipboardItem, Context, Entity, FocusHandle,
+    InteractiveElement, IntoElement, KeyBinding, Menu, MenuItem, ParentElement, Pixels, Point,
+    Render, ScrollStrategy, SharedString, StatefulInteractiveElement, Styled,
+    UniformListScrollHandle, Window, WindowBackgroundAppearance, WindowBounds, WindowHandle,
+    WindowKind, WindowOptions, actions, div, point, prelude::FluentBuilder, px, size, uniform_list,
 };
 use indicator::{HEIGHT as COLLAPSED_HEIGHT, WIDTH as COLLAPSED_WIDTH};
 use session_list::{
-    MAX_SESSIONS, ROW_GAP, WIDTH as EXPANDED_WIDTH, calculate_expanded_height, extract_session_name,
+    InlineField, ROW_GAP, RenameKey, RowAction, WIDTH as EXPANDED_WIDTH, calculate_expanded_height,
+    extract_session_name,
 };
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{
-    Arc, Mutex,
+    Arc,
     atomic::{AtomicBool, Ordering},
 };
-use std::time::Instant;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
 
 // Define application actions
 actions!(
     aura,
     [
         Quit,
+        FocusSessionList,
         SetThemeSystem,
         SetThemeLiquidDark,
-        SetThemeLiquidLight
+        SetThemeLiquidLight,
+        ShowLog,
+        ShowDiagnostics,
+        TogglePrivacy,
+        ToggleFollowFrontTerminal,
+        TogglePauseClaudeCode,
+        TogglePauseCodex
     ]
 );
 
 /// Gap between indicator and session list windows
 const WINDOW_GAP: f32 = 4.0;
 
+/// Snooze duration applied by the row's snooze toggle
+const DEFAULT_SNOOZE: Duration = Duration::from_secs(30 * 60);
+
+/// Most ended sessions kept under the "Ended recently" row
+const ENDED_MAX: usize = 20;
+
 /// Shared HUD state between indicator and session list windows
 pub(crate) struct SharedHudState {
     /// Current sessions to display (refreshed from registry)
     sessions: Vec<SessionInfo>,
+    /// `sessions` narrowed to `tag_filter` and `state_filter` - the rows of
+    /// the session list
+    listed_sessions: Vec<SessionInfo>,
+    /// Tag chosen in the session list's header menu
+    tag_filter: Option<String>,
+    /// State whose header summary segment was clicked
+    state_filter: Option<SessionState>,
     /// Animation start time for time-based tool cycling
     animation_start: Instant,
     /// Random seed for animation timing (fixed per session)
     animation_seed: u64,
-    /// Registry reference for refreshing session data
-    registry: Arc<Mutex<SessionRegistry>>,
+    /// Registry for UI actions (archive, rename, reorder, ...)
+    registry: SharedRegistry,
+    /// The registry's clock, which row timestamps are relative to (frozen in
+    /// demo mode)
+    clock: SharedClock,
+    /// Notified when the registry publishes a new generation
+    snapshots: watch::Receiver<Arc<Published>>,
+    /// Registry generation `sessions` reflects
+    generation: u64,
     /// Whether the session list window should be visible
     session_list_visible: bool,
     /// Session list window handle (for tracking open/close state)
@@ -78,18 +132,64 @@ pub(crate) struct SharedHudState {
     theme_style: theme::ThemeStyle,
     /// Whether the system is currently in dark mode (detected from OS)
     system_is_dark: bool,
-    /// Whether registry data changed and needs refresh
+    /// Per-state colors of each theme (config `state_colors`)
+    state_colors: theme::ThemeStateColors,
+    /// Whether sessions need a refresh regardless of new snapshots
+    /// (e.g. an opened link to reveal)
     registry_dirty: Arc<AtomicBool>,
+    /// Rows shown before the overflow footer (config `ui.max_visible_sessions`)
+    max_visible_sessions: usize,
+    /// List or grid of sessions (config `ui.layout`)
+    layout: crate::config::Layout,
+    /// macOS window level for HUD windows (config `ui.window_level`)
+    window_level: crate::config::WindowLevel,
+    /// Show HUD windows on every Space (config `ui.all_spaces`)
+    all_spaces: bool,
+    /// Hide the indicator while no sessions are live (config `ui.auto_hide`)
+    auto_hide: bool,
+    /// States that flash the screen's top edge, none when off (config
+    /// `ui.edge_pulse`, `ui.edge_pulse_states`)
+    edge_pulse: Vec<SessionState>,
+    /// Idle time before an unseen session gets the review badge
+    /// (config `ui.review_reminder_minutes`)
+    review_reminder: Option<Duration>,
+    /// How long ended sessions stay under the "Ended recently" row, `None`
+    /// when off (config `ui.ended_visible_minutes`)
+    ended_visible: Option<Duration>,
+    /// Sessions that ended, most recent first, with when they left the list
+    ended: Vec<(SessionInfo, Instant)>,
+    /// Time in Attention/Waiting after which rows flag it
+    /// (config `ui.state_age_warn_minutes`, `ui.state_age_alert_minutes`)
+    state_age_thresholds: crate::config::StateAgeThresholds,
+    /// States driving the indicator, highest priority first
+    /// (config `ui.indicator_priority`)
+    indicator_priority: Vec<crate::config::IndicatorRule>,
+    /// Icon or session segments (config `ui.indicator_style`)
+    indicator_style: crate::config::IndicatorStyle,
+    /// Whether to probe the frontmost terminal (config `ui.follow_front_terminal`)
+    follow_front_terminal: bool,
+    /// Working directories of the frontmost terminal's shells
+    active_context: active_context::ActiveContext,
+    /// Session ids from opened `aura://` links
+    pending_links: deep_link::PendingLinks,
+    /// Session to reveal and highlight, and when its link was opened
+    linked_session: Option<(String, Instant)>,
 }
 
 #[cfg(test)]
 impl SharedHudState {
     /// Create a SharedHudState for testing with given sessions
     pub(crate) fn new_for_test(sessions: Vec<SessionInfo>) -> Self {
-        let registry = Arc::new(Mutex::new(SessionRegistry::new()));
+        let registry = SharedRegistry::new(crate::registry::SessionRegistry::new());
         let registry_dirty = Arc::new(AtomicBool::new(false));
         Self {
+            snapshots: registry.subscribe(),
+            clock: SharedClock::default(),
+            generation: 0,
+            listed_sessions: sessions.clone(),
             sessions,
+            tag_filter: None,
+            state_filter: None,
             animation_start: Instant::now(),
             animation_seed: 42,
             registry,
@@ -99,37 +199,162 @@ impl SharedHudState {
             indicator_window: None,
             theme_style: theme::ThemeStyle::System,
             system_is_dark: true,
+            state_colors: theme::ThemeStateColors::default(),
             registry_dirty,
+            max_visible_sessions: crate::config::UiConfig::default().max_visible_sessions,
+            layout: crate::config::Layout::default(),
+            window_level: crate::config::WindowLevel::default(),
+            all_spaces: true,
+            auto_hide: false,
+            edge_pulse: Vec::new(),
+            review_reminder: None,
+            ended_visible: crate::config::UiConfig::default().ended_visible(),
+            ended: Vec::new(),
+            state_age_thresholds: crate::config::UiConfig::default().state_age_thresholds(),
+            indicator_priority: crate::config::UiConfig::default().indicator_priority,
+            indicator_style: crate::config::IndicatorStyle::default(),
+            follow_front_terminal: false,
+            active_context: Default::default(),
+            pending_links: Default::default(),
+            linked_session: None,
         }
     }
 }
 
 impl SharedHudState {
     /// Refresh sessions from registry
-    /// - Shows all sessions (including Idle and Stale)
+    /// - Shows all non-archived sessions (including Idle and Stale)
+    /// - Applies only the sessions changed since the last refresh
+    /// - Keeps sessions that ended (rather than were archived) for
+    ///   [`recently_ended`](Self::recently_ended)
     fn refresh_from_registry(&mut self) {
-        if let Ok(registry) = self.registry.lock() {
-            self.sessions = registry.get_all();
-            tracing::debug!(
-                "UI refresh: {} sessions ({})",
-                self.sessions.len(),
-                self.sessions
-                    .iter()
-                    .map(|s| format!(
-                        "{}:{:?}",
-                        &s.session_id[..8.min(s.session_id.len())],
-                        s.state
-                    ))
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            );
+        let changes = self
+            .snapshots
+            .borrow_and_update()
+            .changes_since(self.generation);
+        let (updated, removed) = (changes.updated.len(), changes.removed.len());
+        self.generation = changes.generation;
+        if self.ended_visible.is_some() {
+            let now = self.clock.now();
+            let ended = self
+                .sessions
+                .iter()
+                .filter(|s| changes.ended.contains(&s.session_id))
+                .map(|s| (s.clone(), now));
+            self.ended.splice(0..0, ended);
+        }
+        changes.apply(&mut self.sessions);
+        // A session that came back (same id) is live again
+        let sessions = &self.sessions;
+        self.ended
+            .retain(|(ended, _)| !sessions.iter().any(|s| s.session_id == ended.session_id));
+        self.forget_ended();
+        self.apply_filters();
+        self.update_probe();
+        tracing::debug!(
+            target: logging::UI,
+            "UI refresh to generation {}: {} updated, {} removed, {} sessions ({})",
+            self.generation,
+            updated,
+            removed,
+            self.sessions.len(),
+            self.sessions
+                .iter()
+                .map(|s| format!(
+                    "{}:{:?}",
+                    &s.session_id[..8.min(s.session_id.len())],
+                    s.state
+                ))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
+    /// Probe the frontmost terminal only while it's wanted and some session
+    /// is live to highlight
+    fn update_probe(&mut self) {
+        let live = self.sessions.iter().any(|s| s.state != SessionState::Stale);
+        self.active_context
+            .set_probing(self.follow_front_terminal && live);
+    }
+
+    /// Drop ended sessions past `ended_visible`, and the oldest beyond
+    /// [`ENDED_MAX`]
+    fn forget_ended(&mut self) {
+        let Some(visible) = self.ended_visible else {
+            self.ended.clear();
+            return;
+        };
+        let now = self.clock.now();
+        self.ended
+            .retain(|(_, ended_at)| now.saturating_duration_since(*ended_at) < visible);
+        self.ended.truncate(ENDED_MAX);
+    }
+
+    /// Sessions kept since they ended, most recent first, with how long ago
+    /// (redacted in privacy mode)
+    fn recently_ended(&self) -> Vec<(SessionInfo, Duration)> {
+        let now = self.clock.now();
+        self.ended
+            .iter()
+            .map(|(session, ended_at)| {
+                (
+                    crate::privacy::apply(session.clone()),
+                    now.saturating_duration_since(*ended_at),
+                )
+            })
+            .collect()
+    }
+
+    /// Current Unix time on the registry's clock
+    fn now_unix(&self) -> u64 {
+        self.clock.unix_timestamp(self.clock.now())
+    }
+
+    /// Whether the registry published a snapshot since the last refresh
+    fn needs_refresh(&self) -> bool {
+        self.snapshots.has_changed().unwrap_or(false) || self.registry_dirty.load(Ordering::Relaxed)
+    }
+
+    /// Narrow the session list to `tag`, or show every session with `None`
+    fn set_tag_filter(&mut self, tag: Option<String>) {
+        self.tag_filter = tag;
+        self.apply_filters();
+    }
+
+    /// Narrow the session list to sessions in `state`, or show every state
+    /// with `None`
+    fn set_state_filter(&mut self, state: Option<SessionState>) {
+        self.state_filter = state;
+        self.apply_filters();
+    }
+
+    /// Recompute `listed_sessions` (redacted in privacy mode); a filter
+    /// no session matches any more is dropped rather than leaving an empty
+    /// list
+    fn apply_filters(&mut self) {
+        if let Some(tag) = &self.tag_filter
+            && !self.sessions.iter().any(|s| s.tags.contains(tag))
+        {
+            self.tag_filter = None;
+        }
+        let tagged = session_list::filter_by_tag(&self.sessions, self.tag_filter.as_deref());
+        if let Some(state) = self.state_filter
+            && !tagged.iter().any(|s| s.state == state)
+        {
+            self.state_filter = None;
         }
+        self.listed_sessions = tagged
+            .into_iter()
+            .filter(|s| self.state_filter.is_none_or(|state| s.state == state))
+            .map(crate::privacy::apply)
+            .collect();
     }
 
     /// Get the current resolved theme colors
     fn theme_colors(&self) -> theme::ThemeColors {
         let resolved = self.theme_style.resolve(self.system_is_dark);
-        theme::ThemeColors::for_style(resolved)
+        theme::ThemeColors::for_style(resolved).with_state_colors(&self.state_colors, resolved)
     }
 
     /// Update system appearance from window
@@ -145,27 +370,216 @@ struct IndicatorView {
     is_hovered: bool,
     /// Track window position at mouse down (for drag detection)
     window_pos_at_mouse_down: Option<Point<Pixels>>,
+    /// Window is hidden by `ui.auto_hide`
+    auto_hidden: bool,
+    /// When the current auto-hide fade-in started
+    fade_in_at: Option<Instant>,
+    /// Next redraw an idle animation needs (`None` = only on changes)
+    redraw_at: Option<Instant>,
+    /// When the last frame was rendered
+    last_frame_at: Instant,
+    /// Frames rendered since `frames_logged_at` (debug logging)
+    frames: u64,
+    frames_logged_at: Instant,
+    /// Window title, which VoiceOver reads as the indicator's label
+    title: String,
+    /// Sessions (and the state) the edge pulse already flashed for
+    edge_pulsed: HashSet<(String, SessionState)>,
+}
+
+/// How often an idle indicator checks for registry changes and due redraws
+const WAKE_POLL: Duration = Duration::from_millis(100);
+
+/// Longest an idle indicator goes without redrawing (picks up appearance
+/// changes and opened links)
+const MAX_IDLE_REDRAW: Duration = Duration::from_secs(1);
+
+/// How often the indicator's frame count is logged
+const FRAME_LOG_INTERVAL: Duration = Duration::from_secs(60);
+
+impl IndicatorView {
+    fn new(state: Entity<SharedHudState>) -> Self {
+        let now = Instant::now();
+        Self {
+            state,
+            is_hovered: false,
+            window_pos_at_mouse_down: None,
+            auto_hidden: false,
+            fade_in_at: None,
+            redraw_at: None,
+            last_frame_at: now,
+            frames: 0,
+            frames_logged_at: now,
+            title: String::new(),
+            edge_pulsed: HashSet::new(),
+        }
+    }
+
+    /// Redraw the indicator when the registry changes or an idle animation is
+    /// due, instead of requesting frames continuously.
+    fn spawn_waker(cx: &mut Context<Self>) {
+        cx.spawn(async move |this, cx| {
+            loop {
+                cx.background_executor().timer(WAKE_POLL).await;
+                let updated = this.update(cx, |view, cx| {
+                    if view.redraw_due(cx) {
+                        cx.notify();
+                    }
+                });
+                if updated.is_err() {
+                    break; // Window closed
+                }
+            }
+        })
+        .detach();
+    }
+
+    fn redraw_due(&self, cx: &App) -> bool {
+        let now = Instant::now();
+        self.state.read(cx).needs_refresh()
+            || self.redraw_at.is_some_and(|at| now >= at)
+            || now.duration_since(self.last_frame_at) >= MAX_IDLE_REDRAW
+    }
+
+    /// Count rendered frames, logging the rate once per `FRAME_LOG_INTERVAL`
+    fn count_frame(&mut self) {
+        let now = Instant::now();
+        self.last_frame_at = now;
+        self.frames += 1;
+        let elapsed = now.duration_since(self.frames_logged_at);
+        if elapsed >= FRAME_LOG_INTERVAL {
+            tracing::debug!(
+                target: logging::UI,
+                frames = self.frames,
+                secs = elapsed.as_secs(),
+                "indicator frames rendered"
+            );
+            self.frames = 0;
+            self.frames_logged_at = now;
+        }
+    }
+
+    /// Hide the window when no sessions are live and fade it back in once one
+    /// appears. Never hides while the session list is open.
+    fn update_auto_hide(&mut self, window: &Window, cx: &Context<Self>) {
+        let hud_state = self.state.read(cx);
+        if !hud_state.auto_hide {
+            return;
+        }
+        let active =
+            indicator::has_active_sessions(&hud_state.sessions) || hud_state.session_list_visible;
+
+        if !active && !self.auto_hidden {
+            self.auto_hidden = true;
+            self.fade_in_at = None;
+            window_level::set_alpha(window, 0.0);
+        } else if active && self.auto_hidden {
+            self.auto_hidden = false;
+            self.fade_in_at = Some(Instant::now());
+        }
+
+        if let Some(started) = self.fade_in_at {
+            let (alpha, done) = calculate_fade_in(started);
+            window_level::set_alpha(window, alpha);
+            if done {
+                self.fade_in_at = None;
+            }
+        }
+    }
+
+    /// Flash the top edge of the indicator's display when a session enters
+    /// Attention, or Waiting if configured (`ui.edge_pulse_states`)
+    fn pulse_edge(&mut self, window: &Window, cx: &mut Context<Self>) {
+        let hud_state = self.state.read(cx);
+        if hud_state.edge_pulse.is_empty() {
+            return;
+        }
+        let Some(state) = edge_pulse::entered(
+            &mut self.edge_pulsed,
+            &hud_state.sessions,
+            &hud_state.edge_pulse,
+        ) else {
+            return;
+        };
+        let color = hud_state
+            .theme_colors()
+            .states
+            .get(state)
+            .unwrap_or_else(|| edge_pulse::default_color(state));
+        let Some(display) = window.display(cx).map(|display| display.bounds()) else {
+            return;
+        };
+        // Opening a window mid-render isn't allowed; do it after this frame
+        cx.defer(move |app| edge_pulse::show(app, display, color));
+    }
+
+    /// Reveal the session of the latest opened `aura://` link
+    fn open_pending_link(&mut self, window: &Window, cx: &mut Context<Self>) {
+        let list_visible = self.state.update(cx, |state, _cx| {
+            let session_id = deep_link::take_latest(&state.pending_links)?;
+            tracing::info!(target: logging::UI, "Opening link to session {}", session_id);
+            state.linked_session = Some((session_id, Instant::now()));
+            state.registry_dirty.store(true, Ordering::Relaxed);
+            Some(state.session_list_visible)
+        });
+        if list_visible == Some(false) {
+            // Opening a window mid-render isn't allowed; do it after this frame
+            let state = self.state.clone();
+            let indicator_origin = window.bounds().origin;
+            cx.defer(move |app| show_session_list(app, &state, indicator_origin));
+        }
+    }
 }
 
 impl Render for IndicatorView {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        // Request continuous animation frames
-        window.request_animation_frame();
+        self.count_frame();
 
         // Update system appearance and refresh sessions from registry on each frame
         let appearance = window.appearance();
         self.state.update(cx, |state, _cx| {
             state.update_system_appearance(appearance);
-            if state.registry_dirty.swap(false, Ordering::Relaxed) {
+            if state.needs_refresh() {
+                state.registry_dirty.store(false, Ordering::Relaxed);
                 state.refresh_from_registry();
             }
         });
+        self.update_auto_hide(window, cx);
+        self.pulse_edge(window, cx);
+        self.open_pending_link(window, cx);
 
         let hud_state = self.state.read(cx);
         let sessions = &hud_state.sessions;
         let animation_start = hud_state.animation_start;
         let theme_colors = hud_state.theme_colors();
-        let sessions_for_render: Vec<_> = sessions.iter().take(MAX_SESSIONS).cloned().collect();
+        let sessions_for_render: Vec<_> = sessions.to_vec();
+        let indicator_priority = hud_state.indicator_priority.clone();
+        let indicator_style = hud_state.indicator_style;
+        let title = indicator::accessibility_label(sessions);
+        if title != self.title {
+            window.set_window_title(&title);
+            self.title = title;
+        }
+
+        // Only animations need continuous frames; otherwise the waker
+        // redraws on registry changes or when the next animation step is due
+        let redraw = if self.fade_in_at.is_some() {
+            indicator::Redraw::EveryFrame
+        } else {
+            indicator::next_redraw(
+                indicator::determine_state(sessions, &indicator_priority),
+                animation_start,
+                Instant::now(),
+            )
+        };
+        self.redraw_at = match redraw {
+            indicator::Redraw::EveryFrame => {
+                window.request_animation_frame();
+                None
+            }
+            indicator::Redraw::At(at) => Some(at),
+            indicator::Redraw::OnChange => None,
+        };
 
         let is_hovered = self.is_hovered;
 
@@ -174,8 +588,9 @@ impl Render for IndicatorView {
             .id("indicator-container")
             .size_full()
             .cursor(gpui::CursorStyle::OpenHand)
-            .on_hover(cx.listener(|this, hovered: &bool, _window, _cx| {
+            .on_hover(cx.listener(|this, hovered: &bool, _window, cx| {
                 this.is_hovered = *hovered;
+                cx.notify();
             }))
             .on_mouse_down(
                 gpui::MouseButton::Left,
@@ -195,6 +610,7 @@ impl Render for IndicatorView {
                             state.theme_style = state.theme_style.next();
                             save_theme(state.theme_style);
                         });
+                        app.notify();
                     }
                 }),
             )
@@ -210,10 +626,9 @@ impl Render for IndicatorView {
                         if dx > threshold || dx < -threshold || dy > threshold || dy < -threshold {
                             // Window was dragged — save new position
                             let pos = current_pos;
-                            let state = crate::config::State {
-                                indicator_x: Some(f32::from(pos.x) as f64),
-                                indicator_y: Some(f32::from(pos.y) as f64),
-                            };
+                            let mut state = crate::config::load_state();
+                            state.indicator_x = Some(f32::from(pos.x) as f64);
+                            state.indicator_y = Some(f32::from(pos.y) as f64);
                             let _ = crate::config::save_state(&state);
                             return;
                         }
@@ -249,42 +664,53 @@ impl Render for IndicatorView {
                             state.session_list_window = None;
                         });
                     } else if should_open {
-                        let indicator_origin = window.bounds().origin;
-                        let session_list_origin = point(
-                            indicator_origin.x - px((EXPANDED_WIDTH - COLLAPSED_WIDTH) / 2.0),
-                            indicator_origin.y + px(COLLAPSED_HEIGHT + WINDOW_GAP),
-                        );
-                        let session_count = hud_state.sessions.len().clamp(1, MAX_SESSIONS);
-                        let height = calculate_expanded_height(session_count);
-
-                        state_for_click.update(app, |state, _cx| {
-                            state.session_list_visible = true;
-                            state.session_list_origin = session_list_origin;
-                        });
-                        if window_handle.is_none() {
-                            open_session_list_window_sync(app, state_for_click.clone());
-                        } else if let Some(handle) = window_handle {
-                            let _ = handle.update(app, |_view, window, _cx| {
-                                window.resize(size(px(EXPANDED_WIDTH), px(height)));
-                            });
-                        }
+                        show_session_list(app, &state_for_click, window.bounds().origin);
                     }
                 })
             })
             .child(indicator::render(
                 &sessions_for_render,
+                &indicator_priority,
+                indicator_style,
                 animation_start,
                 is_hovered,
+                crate::health::report().is_degraded(),
                 &theme_colors,
             ))
     }
 }
 
+/// Drag payload (and drag preview) for reordering session rows
+#[derive(Clone)]
+struct DraggedSession {
+    session_id: String,
+    name: String,
+    theme: theme::ThemeColors,
+}
+
+impl Render for DraggedSession {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .px(px(10.0))
+            .py(px(4.0))
+            .rounded(px(8.0))
+            .bg(self.theme.container_bg)
+            .border_1()
+            .border_color(self.theme.border)
+            .font_family(font::family())
+            .text_size(font::size(12.0))
+            .text_color(self.theme.text_primary)
+            .child(self.name.clone())
+    }
+}
+
 /// Session list window view (320px wide, shows/hides on demand)
 struct SessionListView {
     state: Entity<SharedHudState>,
     /// Last known session count (for resize detection)
     last_session_count: usize,
+    /// Session columns currently laid out (config `ui.layout`)
+    columns: usize,
     /// Track when each session was first seen (for slide-in animation)
     appeared_at: HashMap<String, Instant>,
     /// Track hover start time per row for icon swap animation
@@ -294,9 +720,516 @@ struct SessionListView {
     removing: HashMap<String, (SessionInfo, Instant)>,
     /// Cache of last known session info (for exit animation)
     session_cache: HashMap<String, SessionInfo>,
+    /// Whether the header's bulk action menu is open
+    header_menu_open: bool,
+    /// Whether the overflow footer was clicked to reveal every session (scrolls)
+    show_all: bool,
+    /// Whether the "Ended recently" row was clicked to list the ended sessions
+    ended_expanded: bool,
+    /// Height of the "Ended recently" row the window was last sized for
+    graveyard_height: f32,
+    /// Scroll state of the session rows (kept across frames and refreshes)
+    scroll_handle: UniformListScrollHandle,
+    /// Session id of the top visible row and its index, for scroll anchoring
+    scroll_anchor: Option<(String, usize)>,
+    /// Whether the pointer is over the rows (shows the scrollbar)
+    list_hovered: bool,
+    /// In-flight window height animation: (from, to, started)
+    height_anim: Option<(f32, f32, Instant)>,
+    /// Open row context menu: (session id, click position in the window)
+    row_menu: Option<(String, Point<Pixels>)>,
+    /// Inline rename (or tag edit) in progress: (session id, edit buffer)
+    renaming: Option<(String, String)>,
+    /// What the inline field edits (name or tags)
+    inline_field: InlineField,
+    /// Receives key presses (inline rename, keyboard navigation)
+    focus_handle: FocusHandle,
+    /// Row picked with the arrow keys (session id)
+    selected: Option<String>,
+    /// Window title, which VoiceOver reads: the selected row, else the header
+    title: String,
+    /// Linked session already scrolled into view
+    revealed_link: Option<String>,
 }
 
 impl SessionListView {
+    /// Render the "Ended recently (N)" row, and the ended sessions below it
+    /// once expanded; `None` without ended sessions
+    fn render_graveyard(
+        &self,
+        ended: &[(SessionInfo, Duration)],
+        theme_colors: &theme::ThemeColors,
+        cx: &mut Context<Self>,
+    ) -> Option<gpui::Div> {
+        if ended.is_empty() {
+            return None;
+        }
+        let header = div()
+            .id("session-list-ended")
+            .cursor(gpui::CursorStyle::PointingHand)
+            .child(session_list::render_graveyard_header(
+                session_list::graveyard_header_text(ended.len(), self.ended_expanded),
+                theme_colors,
+            ))
+            .on_click(
+                cx.listener(|this, _event: &gpui::ClickEvent, _window, _cx| {
+                    this.ended_expanded = !this.ended_expanded;
+                }),
+            );
+        let rows = self.ended_expanded.then(|| {
+            ended
+                .iter()
+                .map(|(session, ago)| session_list::render_ended_row(session, *ago, theme_colors))
+        });
+        Some(
+            div()
+                .flex_shrink_0()
+                .child(header)
+                .children(rows.into_iter().flatten()),
+        )
+    }
+
+    /// Render the header menu button (ellipsis, right side of the header)
+    fn render_header_menu_button(
+        &self,
+        theme_colors: &theme::ThemeColors,
+        cx: &mut Context<Self>,
+    ) -> gpui::Stateful<gpui::Div> {
+        div()
+            .id("session-list-header-menu-btn")
+            .absolute()
+            .right(px(10.0))
+            .top(px(7.0))
+            .w(px(14.0))
+            .h(px(14.0))
+            .cursor(gpui::CursorStyle::PointingHand)
+            .child(
+                gpui::svg()
+                    .path("icons/ellipsis.svg")
+                    .size(px(14.0))
+                    .text_color(theme_colors.text_header),
+            )
+            .on_click(
+                cx.listener(|this, _event: &gpui::ClickEvent, _window, _cx| {
+                    if this.header_menu_open {
+                        this.close_header_menu();
+                    } else {
+                        this.header_menu_open = true;
+                    }
+                }),
+            )
+    }
+
+    /// Render the header summary, one segment per state ("2 running");
+    /// clicking a segment lists only that state's sessions, clicking it
+    /// again lists all
+    fn render_header_summary(
+        &self,
+        theme_colors: &theme::ThemeColors,
+        cx: &mut Context<Self>,
+    ) -> gpui::Div {
+        let hud_state = self.state.read(cx);
+        let active = hud_state.state_filter;
+        let tag = hud_state.tag_filter.clone();
+        let segments = session_list::state_summary(&hud_state.sessions, tag.as_deref());
+
+        let mut summary = div().flex().items_center().gap(px(4.0));
+        for (i, (state, text)) in segments.into_iter().enumerate() {
+            if i > 0 {
+                summary = summary.child("·");
+            }
+            let is_active = active == Some(state);
+            let highlight = theme_colors.text_primary;
+            summary = summary.child(
+                div()
+                    .id(SharedString::from(format!("header-state-{state:?}")))
+                    .cursor(gpui::CursorStyle::PointingHand)
+                    .when(is_active, |this| {
+                        this.text_color(highlight)
+                            .font_weight(gpui::FontWeight::SEMIBOLD)
+                    })
+                    .hover(move |style| style.text_color(highlight))
+                    .child(text)
+                    .on_click(
+                        cx.listener(move |this, _event: &gpui::ClickEvent, _window, cx| {
+                            this.show_all = false;
+                            let state = (!is_active).then_some(state);
+                            this.state
+                                .update(cx, |hud_state, _cx| hud_state.set_state_filter(state));
+                        }),
+                    ),
+            );
+        }
+        if let Some(tag) = tag {
+            summary = summary.child("·").child(format!("#{tag}"));
+        }
+        summary
+    }
+
+    /// Render the bulk action dropdown below the header, followed by one
+    /// filter item per tag in use
+    fn render_header_menu(
+        &self,
+        theme_colors: &theme::ThemeColors,
+        cx: &mut Context<Self>,
+    ) -> gpui::Div {
+        let mut items: Vec<_> = BulkAction::ALL
+            .iter()
+            .map(|&action| {
+                context_menu::item(
+                    SharedString::from(format!("header-menu-{:?}", action)),
+                    session_list::bulk_action_label(action),
+                    true,
+                    theme_colors,
+                )
+                .on_click(cx.listener(
+                    move |this, _event: &gpui::ClickEvent, _window, cx| {
+                        this.close_header_menu();
+                        this.state.update(cx, |state, _cx| {
+                            state
+                                .registry
+                                .update(|registry| registry.apply_bulk(action));
+                        });
+                    },
+                ))
+            })
+            .collect();
+
+        let hud_state = self.state.read(cx);
+        let active = hud_state.tag_filter.clone();
+        for tag in session_list::all_tags(&hud_state.sessions) {
+            let is_active = active.as_ref() == Some(&tag);
+            items.push(
+                context_menu::item(
+                    SharedString::from(format!("header-menu-tag-{tag}")),
+                    session_list::tag_filter_label(&tag, is_active),
+                    true,
+                    theme_colors,
+                )
+                .on_click(cx.listener(
+                    move |this, _event: &gpui::ClickEvent, _window, cx| {
+                        this.close_header_menu();
+                        this.show_all = false;
+                        let tag = (!is_active).then(|| tag.clone());
+                        this.state
+                            .update(cx, |state, _cx| state.set_tag_filter(tag));
+                    },
+                )),
+            );
+        }
+
+        context_menu::container(theme_colors)
+            .absolute()
+            .top(px(session_list::HEADER_HEIGHT))
+            .right(px(10.0))
+            .children(items)
+    }
+
+    /// Render the right-click menu for a session row at the click position
+    fn render_row_menu(
+        &self,
+        session: &SessionInfo,
+        position: Point<Pixels>,
+        theme_colors: &theme::ThemeColors,
+        cx: &mut Context<Self>,
+    ) -> gpui::Stateful<gpui::Div> {
+        let info_lines = session_list::menu_info_lines(session)
+            .into_iter()
+            .enumerate()
+            .map(|(i, line)| {
+                context_menu::item(
+                    SharedString::from(format!("row-menu-info-{i}")),
+                    line,
+                    false,
+                    theme_colors,
+                )
+            });
+        let items: Vec<_> = RowAction::ALL
+            .iter()
+            .map(|&action| {
+                let enabled = action.is_enabled(session);
+                let item = context_menu::item(
+                    SharedString::from(format!("row-menu-{:?}", action)),
+                    session_list::row_action_label(action),
+                    enabled,
+                    theme_colors,
+                );
+                if !enabled {
+                    return item;
+                }
+                let session = session.clone();
+                item.on_click(
+                    cx.listener(move |this, _event: &gpui::ClickEvent, window, cx| {
+                        this.close_row_menu();
+                        this.perform_row_action(action, &session, window, cx);
+                    }),
+                )
+            })
+            .collect();
+
+        let left = context_menu::clamp_left(
+            f32::from(position.x),
+            session_list::expanded_width(self.columns),
+        );
+        context_menu::container(theme_colors)
+            .id("row-menu")
+            .absolute()
+            .left(px(left))
+            .top(position.y)
+            .w(px(context_menu::MENU_WIDTH))
+            .on_mouse_down_out(
+                cx.listener(|this, _event: &gpui::MouseDownEvent, _window, _cx| {
+                    this.close_row_menu();
+                }),
+            )
+            .children(info_lines)
+            .children(items)
+    }
+
+    /// Close the header menu and let the window shrink back to fit the rows
+    fn close_header_menu(&mut self) {
+        if std::mem::take(&mut self.header_menu_open) {
+            self.last_session_count = 0;
+        }
+    }
+
+    /// Close the row menu and let the window shrink back to fit the rows
+    fn close_row_menu(&mut self) {
+        if self.row_menu.take().is_some() {
+            self.last_session_count = 0;
+        }
+    }
+
+    /// Run a row context menu action
+    fn perform_row_action(
+        &mut self,
+        action: RowAction,
+        session: &SessionInfo,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let session_id = session.session_id.clone();
+        match action {
+            RowAction::Rename => self.start_rename(session, window),
+            RowAction::EditTags => self.start_tag_edit(session, window),
+            RowAction::CopyId => cx.write_to_clipboard(ClipboardItem::new_string(session_id)),
+            RowAction::CopyLink => cx.write_to_clipboard(ClipboardItem::new_string(
+                deep_link::session_link(&session_id),
+            )),
+            RowAction::OpenInFinder => {
+                external::open(external::OpenTarget::FileManager, &session.cwd)
+            }
+            RowAction::OpenInTerminal => {
+                let app = crate::config::load_config().ui.terminal_app;
+                external::open(external::OpenTarget::App(&app), &session.cwd);
+            }
+            RowAction::OpenInEditor => {
+                let app = crate::config::load_config().ui.editor_app;
+                external::open(external::OpenTarget::App(&app), &session.cwd);
+            }
+            RowAction::ViewTranscript => {
+                transcript_view::open(cx, self.state.clone(), session);
+            }
+            RowAction::RevealTranscript => {
+                if let Some(path) = &session.transcript_path {
+                    external::open(external::OpenTarget::Reveal, path);
+                }
+            }
+            RowAction::Archive => {
+                self.state.update(cx, |state, _cx| {
+                    state
+                        .registry
+                        .update(|registry| registry.archive_session(&session_id));
+                });
+            }
+        }
+    }
+
+    /// Turn a row's name into the inline rename field
+    fn start_rename(&mut self, session: &SessionInfo, window: &mut Window) {
+        // In privacy mode the row shows a placeholder name; start empty so
+        // confirming unchanged doesn't save the placeholder
+        let current = match &session.name {
+            _ if crate::privacy::enabled() => String::new(),
+            Some(name) => name.clone(),
+            None => extract_session_name(&session.cwd),
+        };
+        self.start_inline_edit(session, current, InlineField::Name, window);
+    }
+
+    /// Edit a row's tags in the inline field (space-separated)
+    fn start_tag_edit(&mut self, session: &SessionInfo, window: &mut Window) {
+        let current = session.tags.join(" ");
+        self.start_inline_edit(session, current, InlineField::Tags, window);
+    }
+
+    fn start_inline_edit(
+        &mut self,
+        session: &SessionInfo,
+        buffer: String,
+        field: InlineField,
+        window: &mut Window,
+    ) {
+        self.renaming = Some((session.session_id.clone(), buffer));
+        self.inline_field = field;
+        // Popups don't take key focus on their own
+        window.activate_window();
+        window.focus(&self.focus_handle);
+    }
+
+    /// Save the inline field to the registry. Names go through
+    /// `SessionNameUpdated` and blank names are discarded; tags replace the
+    /// session's tags (blank clears them).
+    fn commit_rename(&mut self, cx: &mut Context<Self>) {
+        let Some((session_id, buffer)) = self.renaming.take() else {
+            return;
+        };
+        let field = std::mem::take(&mut self.inline_field);
+        let text = buffer.trim();
+        if field == InlineField::Name && text.is_empty() {
+            return;
+        }
+        self.state.update(cx, |state, _cx| {
+            state.registry.update(|registry| match field {
+                InlineField::Name => {
+                    registry.rename_session(&session_id, text);
+                }
+                InlineField::Tags => {
+                    registry.set_session_tags(&session_id, crate::tags::parse_tags(text));
+                }
+            });
+        });
+    }
+
+    /// Key handling for the session list (inline rename, keyboard navigation)
+    fn handle_key_down(
+        &mut self,
+        event: &gpui::KeyDownEvent,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let keystroke = &event.keystroke;
+        let Some((_, buffer)) = self.renaming.as_mut() else {
+            self.handle_navigation_key(&keystroke.key, cx);
+            return;
+        };
+
+        let modified = keystroke.modifiers.platform || keystroke.modifiers.control;
+        if modified {
+            match keystroke.key.as_str() {
+                // Cmd-V pastes the first line of the clipboard
+                "v" => {
+                    if let Some(text) = cx.read_from_clipboard().and_then(|item| item.text()) {
+                        buffer.push_str(session_list::single_line(&text));
+                    }
+                }
+                // Cmd-Backspace clears the field
+                "backspace" => buffer.clear(),
+                _ => {}
+            }
+            cx.stop_propagation();
+            return;
+        }
+
+        match session_list::apply_rename_key(buffer, &keystroke.key, keystroke.key_char.as_deref())
+        {
+            RenameKey::Edit => {}
+            RenameKey::Cancel => {
+                self.renaming = None;
+                self.inline_field = InlineField::Name;
+            }
+            RenameKey::Commit => self.commit_rename(cx),
+        }
+        cx.stop_propagation();
+    }
+
+    /// Arrow keys move the selection (by a grid row for up/down), Return
+    /// acknowledges the selected session like a click, Delete archives it,
+    /// and Escape closes the menus, then clears the selection
+    fn handle_navigation_key(&mut self, key: &str, cx: &mut Context<Self>) {
+        let hud_state = self.state.read(cx);
+        let ids: Vec<String> = hud_state
+            .listed_sessions
+            .iter()
+            .map(|s| s.session_id.clone())
+            .collect();
+        let max_visible = hud_state.max_visible_sessions.max(1);
+        let columns = self.columns.max(1);
+        let current = self
+            .selected
+            .as_ref()
+            .and_then(|id| ids.iter().position(|s| s == id));
+
+        let delta = match key {
+            "down" => columns as isize,
+            "up" => -(columns as isize),
+            "right" => 1,
+            "left" => -1,
+            _ => 0,
+        };
+        if delta != 0 {
+            if let Some(index) = session_list::step_selection(current, delta, ids.len()) {
+                self.selected = Some(ids[index].clone());
+                if index >= max_visible {
+                    self.show_all = true;
+                }
+                self.scroll_handle
+                    .scroll_to_item(index / columns, ScrollStrategy::Center);
+            }
+            cx.stop_propagation();
+            return;
+        }
+
+        match key {
+            "enter" => {
+                if let Some(id) = current.map(|index| ids[index].clone()) {
+                    self.state.update(cx, |state, _cx| {
+                        state.registry.update(|registry| registry.mark_seen(&id));
+                    });
+                }
+            }
+            "delete" | "backspace" => {
+                if let Some(index) = current {
+                    let id = ids[index].clone();
+                    // Keep a neighbour selected so repeated presses work
+                    self.selected = ids
+                        .get(index + 1)
+                        .or_else(|| ids.get(index.checked_sub(1)?))
+                        .cloned();
+                    self.state.update(cx, |state, _cx| {
+                        state
+                            .registry
+                            .update(|registry| registry.archive_session(&id));
+                    });
+                }
+            }
+            "escape" => {
+                if self.row_menu.is_some() || self.header_menu_open {
+                    self.close_row_menu();
+                    self.close_header_menu();
+                } else {
+                    self.selected = None;
+                }
+            }
+            _ => return,
+        }
+        cx.stop_propagation();
+    }
+
+    /// Bring the list to the front with key focus, selecting the first row
+    fn focus_from_keyboard(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        window.activate_window();
+        window.focus(&self.focus_handle);
+        if self.selected.is_none() {
+            self.selected = self
+                .state
+                .read(cx)
+                .listed_sessions
+                .first()
+                .map(|s| s.session_id.clone());
+        }
+    }
+
     /// Render a session row with hover-based marquee scrolling and slide-in animation
     fn render_session_row(
         &mut self,
@@ -304,6 +1237,7 @@ impl SessionListView {
         tool_index: usize,
         fade_progress: f32,
         animation_start: Instant,
+        is_focused: bool,
         theme_colors: &theme::ThemeColors,
         cx: &mut Context<Self>,
     ) -> gpui::Stateful<gpui::Div> {
@@ -335,6 +1269,7 @@ impl SessionListView {
 
         // State-based row opacity for visual hierarchy
         let row_opacity = match session.state {
+            _ if session_list::is_acknowledged(session) => 0.85,
             SessionState::Running => 1.0,
             SessionState::Attention => 1.0,
             SessionState::Waiting => 1.0,
@@ -350,11 +1285,93 @@ impl SessionListView {
         // Check if remove icon is visible enough to be clickable
         let remove_clickable = remove_opacity > 0.5;
 
+        // Snooze toggle is clickable on hover, or any time while snoozed
+        let is_snoozed = session.is_snoozed();
+        let snooze_clickable = is_snoozed || remove_clickable;
+        let session_id_for_snooze = session_id.clone();
+        let state_for_snooze = self.state.clone();
+
+        // Drag-to-reorder: dropping a row onto this one moves its project here
+        let dragged = DraggedSession {
+            session_id: session_id.clone(),
+            name: session_name.clone(),
+            theme: *theme_colors,
+        };
+        let session_id_for_drop = session_id.clone();
+        let drop_highlight = theme_colors.row_hover_bg;
+
+        // Right-click opens the row context menu at the pointer
+        let session_id_for_menu = session_id.clone();
+        // Left-click acknowledges an Attention/Waiting/Idle session
+        let session_id_for_ack = session_id.clone();
+        let state_for_ack = self.state.clone();
+        let rename_buffer = self
+            .renaming
+            .as_ref()
+            .filter(|(id, _)| *id == session_id)
+            .map(|(_, buffer)| buffer.clone());
+        let review_due = self.state.read(cx).review_reminder.is_some_and(|after| {
+            crate::reminder::needs_review(session, after, crate::reminder::unix_now())
+        });
+        let is_selected = self.selected.as_ref() == Some(&session_id);
+        let (now_unix, state_age_thresholds) = {
+            let hud_state = self.state.read(cx);
+            (hud_state.now_unix(), hud_state.state_age_thresholds)
+        };
+        // Option-click on the event line copies the full tool command/path
+        let copyable_label = session_list::copyable_tool_label(session, tool_index);
+        // Hover tooltip with what the row truncates
+        let tooltip_title = session_name.clone();
+        let tooltip_lines = session_list::tooltip_lines(session);
+        let tooltip_theme = *theme_colors;
+
         div()
             .id(SharedString::from(format!("session-row-{}", session_id)))
+            .tooltip(move |_window, cx| {
+                tooltip::Tooltip::new(tooltip_title.clone(), &tooltip_theme)
+                    .lines(tooltip_lines.clone())
+                    .build(cx)
+            })
             .relative() // For absolute positioning of remove overlay
             .opacity(row_opacity * slide_opacity) // Combine state opacity with slide-in
             .ml(px(slide_x_offset)) // Slide from left
+            .when(is_focused, |this| this.bg(theme_colors.row_hover_bg)) // Frontmost terminal
+            .on_drag(dragged, |dragged, _offset, _window, cx| {
+                cx.new(|_cx| dragged.clone())
+            })
+            .drag_over::<DraggedSession>(move |style, _dragged, _window, _cx| {
+                style.bg(drop_highlight)
+            })
+            .on_drop(
+                cx.listener(move |this, dragged: &DraggedSession, _window, cx| {
+                    this.state.update(cx, |state, _cx| {
+                        let order = state.registry.update(|registry| {
+                            registry
+                                .move_session_to(&dragged.session_id, &session_id_for_drop)
+                                .then(|| registry.manual_order().to_vec())
+                        });
+                        if let Some(order) = order.flatten() {
+                            let mut saved = crate::config::load_state();
+                            saved.session_order = order;
+                            let _ = crate::config::save_state(&saved);
+                        }
+                    });
+                }),
+            )
+            .on_click(move |_event, _window, app| {
+                state_for_ack.update(app, |state, _cx| {
+                    state
+                        .registry
+                        .update(|registry| registry.mark_seen(&session_id_for_ack));
+                });
+            })
+            .on_mouse_down(
+                gpui::MouseButton::Right,
+                cx.listener(move |this, event: &gpui::MouseDownEvent, _window, _cx| {
+                    this.close_header_menu();
+                    this.row_menu = Some((session_id_for_menu.clone(), event.position));
+                }),
+            )
             .on_hover(cx.listener(move |this, hovered: &bool, _window, _cx| {
                 // Track hover timing for icon swap animation
                 let now = Instant::now();
@@ -380,8 +1397,24 @@ impl SessionListView {
                     remove_opacity,
                     remove_x,
                     theme: theme_colors,
+                    rename_buffer: rename_buffer.as_deref(),
+                    review_due,
+                    now_unix,
+                    state_age_thresholds,
+                    marquee_since: icon_hover_start.filter(|_| icon_is_hovered),
                 },
             ))
+            // Focus ring around the row picked with the arrow keys
+            .when(is_selected, |this| {
+                this.child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .rounded(px(theme::WINDOW_RADIUS))
+                        .border_2()
+                        .border_color(theme_colors.text_secondary),
+                )
+            })
             // Remove button overlay - positioned over the state icon area
             // Only clickable when remove icon is visible (hover state)
             .when(remove_clickable, |this| {
@@ -398,12 +1431,85 @@ impl SessionListView {
                         .h(px(14.0)) // State icon height
                         .cursor(gpui::CursorStyle::PointingHand)
                         .on_click(move |_event, _window, app| {
-                            // Remove session from registry
+                            // Archive session (hidden from the list, restorable via CLI)
                             state_for_remove.update(app, |state, _cx| {
-                                if let Ok(mut registry) = state.registry.lock() {
-                                    registry.remove_session(&session_id_for_remove);
-                                }
-                                state.registry_dirty.store(true, Ordering::Relaxed);
+                                state.registry.update(|registry| {
+                                    registry.archive_session(&session_id_for_remove)
+                                });
+                            });
+                        }),
+                )
+            })
+            // Name overlay: double-click renames; while renaming, clicking
+            // anywhere else saves the edit
+            .child({
+                let session_for_rename = session.clone();
+                let is_renaming = rename_buffer.is_some();
+                div()
+                    .absolute()
+                    .left(px(36.0)) // Row padding (14) + state icon (14) + gap (8)
+                    .right(px(36.0)) // Leave the snooze toggle clickable
+                    .top(px(10.0)) // Row top padding
+                    .h(px(18.0)) // Header line height
+                    .on_mouse_down(
+                        gpui::MouseButton::Left,
+                        cx.listener(move |this, event: &gpui::MouseDownEvent, window, cx| {
+                            if event.click_count >= 2 && this.renaming.is_none() {
+                                this.close_row_menu();
+                                this.start_rename(&session_for_rename, window);
+                                cx.stop_propagation();
+                            }
+                        }),
+                    )
+                    .when(is_renaming, |this| {
+                        this.on_mouse_down_out(cx.listener(
+                            |this, _event: &gpui::MouseDownEvent, _window, cx| {
+                                this.commit_rename(cx);
+                            },
+                        ))
+                    })
+            })
+            // Tool label overlay - positioned over the event line
+            .when_some(copyable_label, |this, label| {
+                this.child(
+                    div()
+                        .absolute()
+                        .left(px(38.0)) // Row padding (14) + event indent (24)
+                        .right(px(14.0)) // Row right padding
+                        .top(px(31.0)) // Row padding (10) + header (18) + gap (3)
+                        .h(px(18.0)) // Event line height
+                        .on_mouse_down(gpui::MouseButton::Left, move |event, _window, app| {
+                            if event.modifiers.alt {
+                                app.write_to_clipboard(ClipboardItem::new_string(label.clone()));
+                                app.stop_propagation();
+                            }
+                        }),
+                )
+            })
+            // Snooze toggle overlay - positioned over the header's trailing icon
+            .when(snooze_clickable, |this| {
+                this.child(
+                    div()
+                        .id(SharedString::from(format!(
+                            "snooze-btn-{}",
+                            session_id_for_snooze
+                        )))
+                        .absolute()
+                        .right(px(14.0)) // Row right padding
+                        .top(px(10.0)) // Row top padding
+                        .w(px(14.0))
+                        .h(px(14.0))
+                        .cursor(gpui::CursorStyle::PointingHand)
+                        .on_click(move |_event, _window, app| {
+                            state_for_snooze.update(app, |state, _cx| {
+                                state.registry.update(|registry| {
+                                    if is_snoozed {
+                                        registry.unsnooze_session(&session_id_for_snooze);
+                                    } else {
+                                        registry
+                                            .snooze_session(&session_id_for_snooze, DEFAULT_SNOOZE);
+                                    }
+                                });
                             });
                         }),
                 )
@@ -419,6 +1525,7 @@ impl SessionListView {
         fade_progress: f32,
         animation_start: Instant,
         theme_colors: &theme::ThemeColors,
+        (now_unix, state_age_thresholds): (u64, crate::config::StateAgeThresholds),
     ) -> gpui::Div {
         let _session_id = session.session_id.clone();
         let session_name = session
@@ -454,6 +1561,11 @@ impl SessionListView {
                     remove_opacity: 0.0, // Remove icon hidden
                     remove_x: -16.0,     // Remove icon off-screen
                     theme: theme_colors,
+                    rename_buffer: None,
+                    review_due: false,
+                    now_unix,
+                    state_age_thresholds,
+                    marquee_since: None,
                 },
             ))
     }
@@ -477,27 +1589,78 @@ impl Render for SessionListView {
         self.state.update(cx, |state, _cx| {
             state.update_system_appearance(appearance);
             state.session_list_origin = current_origin;
+            state.forget_ended();
         });
 
         let hud_state = self.state.read(cx);
-        let sessions = &hud_state.sessions;
+        let sessions = &hud_state.listed_sessions;
         let total_count = sessions.len();
         let animation_start = hud_state.animation_start;
         let theme_colors = hud_state.theme_colors();
+        let max_visible = hud_state.max_visible_sessions.max(1);
+        let columns = session_list::grid_columns(hud_state.layout, total_count);
+        let list_width = session_list::expanded_width(columns);
+        let ended = hud_state.recently_ended();
+        if ended.is_empty() {
+            self.ended_expanded = false;
+        }
+        let graveyard_height = session_list::graveyard_height(ended.len(), self.ended_expanded);
 
         // Resize window if session count changed
         // Include removing sessions in count to prevent height jump during exit animation
-        let visible_count = (total_count + self.removing.len()).min(MAX_SESSIONS);
-        if visible_count != self.last_session_count && visible_count > 0 {
+        // (max_visible + 1 stands for "capped rows plus overflow footer")
+        let visible_count = (total_count + self.removing.len()).min(max_visible + 1);
+        if (visible_count != self.last_session_count
+            || columns != self.columns
+            || graveyard_height != self.graveyard_height)
+            && visible_count > 0
+        {
             self.last_session_count = visible_count;
-            let height = calculate_expanded_height(visible_count);
-            window.resize(size(px(EXPANDED_WIDTH), px(height)));
+            self.columns = columns;
+            self.graveyard_height = graveyard_height;
+            let from = f32::from(window.bounds().size.height);
+            let to =
+                calculate_expanded_height(visible_count, max_visible, columns) + graveyard_height;
+            self.height_anim = Some((from, to, Instant::now()));
+        }
+        // Grow the window while a menu would extend past its bottom edge
+        if self.header_menu_open {
+            let needed = session_list::HEADER_HEIGHT
+                + context_menu::menu_height(
+                    BulkAction::ALL.len() + session_list::all_tags(&hud_state.sessions).len(),
+                );
+            let current = f32::from(window.bounds().size.height);
+            if needed > current && self.height_anim.is_none() {
+                self.height_anim = Some((current, needed, Instant::now()));
+            }
+        }
+        if let Some((id, position)) = &self.row_menu {
+            let info_lines = sessions
+                .iter()
+                .find(|s| s.session_id == *id)
+                .map_or(0, |s| session_list::menu_info_lines(s).len());
+            let needed = f32::from(position.y)
+                + context_menu::menu_height(RowAction::ALL.len() + info_lines);
+            let current = f32::from(window.bounds().size.height);
+            if needed > current && self.height_anim.is_none() {
+                self.height_anim = Some((current, needed, Instant::now()));
+            }
+        }
+        if let Some((from, to, started)) = self.height_anim {
+            let (height, done) = calculate_window_resize(from, to, started);
+            window.resize(size(px(list_width), px(height)));
+            if done {
+                self.height_anim = None;
+            }
         }
 
         // Handle empty sessions case - show placeholder
         if sessions.is_empty() {
-            let height = calculate_expanded_height(1);
+            self.height_anim = None;
+            self.columns = 1;
+            let height = calculate_expanded_height(1, max_visible, 1) + graveyard_height;
             window.resize(size(px(EXPANDED_WIDTH), px(height)));
+            let graveyard = self.render_graveyard(&ended, &theme_colors, cx);
             return div()
                 .id("session-list-container")
                 .size_full()
@@ -525,8 +1688,8 @@ impl Render for SessionListView {
                                 .flex()
                                 .items_center()
                                 .justify_center()
-                                .font_family("Maple Mono NF CN")
-                                .text_size(px(11.0))
+                                .font_family(font::family())
+                                .text_size(font::size(11.0))
                                 .font_weight(gpui::FontWeight::NORMAL)
                                 .text_color(theme_colors.text_header)
                                 .child("0 sessions".to_string()),
@@ -543,11 +1706,12 @@ impl Render for SessionListView {
                                 .flex()
                                 .items_center()
                                 .justify_center()
-                                .font_family("Maple Mono NF CN")
-                                .text_size(px(11.0))
+                                .font_family(font::family())
+                                .text_size(font::size(11.0))
                                 .text_color(theme_colors.text_secondary)
                                 .child("No active sessions".to_string()),
-                        ),
+                        )
+                        .children(graveyard),
                 )
                 .into_any_element();
         }
@@ -556,18 +1720,47 @@ impl Render for SessionListView {
         let (tool_index, fade_progress) =
             calculate_animation_state(animation_start, hud_state.animation_seed);
 
-        let sessions_for_render: Vec<_> = sessions.to_vec();
-        let session_count = total_count;
+        // Session of a recently opened aura:// link
+        let linked = hud_state
+            .linked_session
+            .as_ref()
+            .filter(|(_, opened_at)| opened_at.elapsed() < deep_link::HIGHLIGHT_DURATION)
+            .and_then(|(id, _)| {
+                let index = sessions.iter().position(|s| &s.session_id == id)?;
+                Some((id.clone(), index))
+            });
+        let reveal_index = match &linked {
+            Some((id, index)) if self.revealed_link.as_ref() != Some(id) => {
+                self.revealed_link = Some(id.clone());
+                Some(*index)
+            }
+            _ => None,
+        };
+
+        // Rows beyond the cap are hidden behind the "+N more…" footer until expanded
+        let hidden_count = session_list::overflow_count(total_count, max_visible);
+        if hidden_count == 0 {
+            self.show_all = false;
+        } else if reveal_index.is_some_and(|index| index >= max_visible) {
+            self.show_all = true;
+        }
+        let row_count = if self.show_all {
+            total_count
+        } else {
+            total_count.min(max_visible)
+        };
         let list_theme_colors = theme_colors;
 
         // Build current session IDs set
-        let current_ids: std::collections::HashSet<_> = sessions_for_render
-            .iter()
-            .map(|s| s.session_id.clone())
-            .collect();
+        let current_ids: std::collections::HashSet<_> =
+            sessions.iter().map(|s| s.session_id.clone()).collect();
 
-        // Update session cache with current sessions (so we have info for exit animation)
-        for session in &sessions_for_render {
+        // Update session cache with rendered sessions (so we have info for exit animation).
+        // Only rows that have appeared can animate out, so hidden rows aren't cloned.
+        for session in sessions
+            .iter()
+            .filter(|s| self.appeared_at.contains_key(&s.session_id))
+        {
             self.session_cache
                 .insert(session.session_id.clone(), session.clone());
         }
@@ -597,29 +1790,117 @@ impl Render for SessionListView {
             self.session_cache.remove(id);
         }
 
+        // Keep the top visible row anchored on its session id when a refresh
+        // inserts, removes, or reorders rows above it (list items are rows of
+        // `columns` sessions)
+        let mut top_index = self.scroll_handle.logical_scroll_top_index() * columns;
+        if let Some(new_index) = session_list::reanchor_index(
+            self.scroll_anchor.as_ref(),
+            sessions.iter().map(|s| s.session_id.as_str()),
+        ) && new_index < row_count
+        {
+            self.scroll_handle
+                .scroll_to_item(new_index / columns, ScrollStrategy::Top);
+            top_index = new_index / columns * columns;
+        }
+        if let Some(index) = reveal_index {
+            self.scroll_handle
+                .scroll_to_item(index / columns, ScrollStrategy::Center);
+        }
+        self.scroll_anchor = sessions
+            .get(top_index)
+            .map(|s| (s.session_id.clone(), top_index));
+
+        // Hover scrollbar geometry (only when rows overflow the viewport)
+        let scrollbar = self.list_hovered.then(|| {
+            let scroll_state = self.scroll_handle.0.borrow();
+            let item_size = scroll_state.last_item_size.as_ref()?;
+            let offset = -f32::from(scroll_state.base_handle.offset().y);
+            session_list::scrollbar_thumb(
+                f32::from(item_size.item.height),
+                f32::from(item_size.contents.height),
+                offset,
+            )
+        });
+        let scrollbar = scrollbar.flatten().map(|(top, height)| {
+            div()
+                .absolute()
+                .right(px(3.0))
+                .top(px(10.0 + top)) // Content top padding
+                .w(px(session_list::SCROLLBAR_WIDTH))
+                .h(px(height))
+                .rounded(px(session_list::SCROLLBAR_WIDTH / 2.0))
+                .bg(theme_colors.text_secondary)
+                .opacity(0.5)
+        });
+
+        // Highlight the session whose terminal is frontmost
+        let focused_cwd = {
+            let active = hud_state.active_context.cwds();
+            active_context::focused_cwd(sessions.iter().map(|s| s.cwd.as_str()), &active)
+                .map(str::to_string)
+        };
+
+        let linked_id = linked.map(|(id, _)| id);
+
+        // uniform_list only asks for the visible range, so clone just those rows;
+        // in the grid each item is a row of `columns` sessions
         let session_list = uniform_list(
             "sessions",
-            sessions_for_render.len(),
+            row_count.div_ceil(columns),
             cx.processor(move |this, range, _window, cx| {
                 let mut items = Vec::new();
                 for ix in range {
-                    if let Some(session) = sessions_for_render.get(ix) {
-                        items.push(this.render_session_row(
+                    let first = ix * columns;
+                    let sessions: Vec<SessionInfo> = this
+                        .state
+                        .read(cx)
+                        .listed_sessions
+                        .iter()
+                        .take(row_count)
+                        .skip(first)
+                        .take(columns)
+                        .cloned()
+                        .collect();
+                    let mut cells = Vec::new();
+                    for session in &sessions {
+                        let is_focused = focused_cwd.as_deref() == Some(session.cwd.as_str())
+                            || linked_id.as_deref() == Some(session.session_id.as_str());
+                        cells.push(this.render_session_row(
                             session,
                             tool_index,
                             fade_progress,
                             animation_start,
+                            is_focused,
                             &list_theme_colors,
                             cx,
                         ));
                     }
+                    if columns == 1 {
+                        items.extend(cells.into_iter().map(IntoElement::into_any_element));
+                    } else {
+                        items.push(
+                            div()
+                                .flex()
+                                .gap(px(ROW_GAP))
+                                .children(cells.into_iter().map(|cell| {
+                                    div().w(px(session_list::COLUMN_WIDTH)).child(cell)
+                                }))
+                                .into_any_element(),
+                        );
+                    }
                 }
                 items
             }),
         )
+        .track_scroll(self.scroll_handle.clone())
         .h_full();
 
         // Build removing session rows (exit animation)
+        let row_clock = {
+            let hud_state = self.state.read(cx);
+            (hud_state.now_unix(), hud_state.state_age_thresholds)
+        };
         let removing_rows: Vec<_> = self
             .removing
             .iter()
@@ -631,7 +1912,9 @@ impl Render for SessionListView {
                     fade_progress,
                     animation_start,
                     &theme_colors,
+                    row_clock,
                 )
+                .when(columns > 1, |row| row.w(px(session_list::COLUMN_WIDTH)))
             })
             .collect();
 
@@ -639,9 +1922,77 @@ impl Render for SessionListView {
         self.appeared_at.retain(|id, _| current_ids.contains(id));
         self.icon_hover_at.retain(|id, _| current_ids.contains(id));
 
+        let overflow_footer = (hidden_count > 0).then(|| {
+            div()
+                .id("session-list-footer")
+                .cursor(gpui::CursorStyle::PointingHand)
+                .child(session_list::render_overflow_footer(
+                    session_list::overflow_footer_text(hidden_count, self.show_all),
+                    &theme_colors,
+                ))
+                .on_click(
+                    cx.listener(|this, _event: &gpui::ClickEvent, _window, _cx| {
+                        this.show_all = !this.show_all;
+                    }),
+                )
+        });
+
+        let graveyard = self.render_graveyard(&ended, &theme_colors, cx);
+        let header_summary = self.render_header_summary(&theme_colors, cx);
+        let header_menu_button = self.render_header_menu_button(&theme_colors, cx);
+        let header_menu = self
+            .header_menu_open
+            .then(|| self.render_header_menu(&theme_colors, cx));
+
+        // Drop menu/rename state for sessions that went away
+        if let Some((id, _)) = &self.row_menu
+            && !current_ids.contains(id)
+        {
+            self.close_row_menu();
+        }
+        if let Some((id, _)) = &self.renaming
+            && !current_ids.contains(id)
+        {
+            self.renaming = None;
+        }
+        if self
+            .selected
+            .as_ref()
+            .is_some_and(|id| !current_ids.contains(id))
+        {
+            self.selected = None;
+        }
+        let title = {
+            let hud_state = self.state.read(cx);
+            let sessions = &hud_state.listed_sessions;
+            self.selected
+                .as_ref()
+                .and_then(|id| sessions.iter().find(|s| s.session_id == *id))
+                .map(session_list::accessibility_label)
+                .unwrap_or_else(|| {
+                    session_list::header_text(&hud_state.sessions, hud_state.tag_filter.as_deref())
+                })
+        };
+        if title != self.title {
+            window.set_window_title(&title);
+            self.title = title;
+        }
+        let row_menu = self.row_menu.clone().and_then(|(id, position)| {
+            let session = self
+                .state
+                .read(cx)
+                .listed_sessions
+                .iter()
+                .find(|s| s.session_id == id)?
+                .clone();
+            Some(self.render_row_menu(&session, position, &theme_colors, cx))
+        });
+
         // Session list container with liquid glass effect
         div()
             .id("session-list-container")
+            .track_focus(&self.focus_handle)
+            .on_key_down(cx.listener(Self::handle_key_down))
             .size_full()
             .relative()
             .rounded(px(theme::WINDOW_RADIUS))
@@ -661,30 +2012,29 @@ impl Render for SessionListView {
                     .size_full()
                     .flex()
                     .flex_col()
-                    // Header: "N sessions" text at top
+                    // Header: "N sessions" text at top, bulk action menu on the right
                     // Note: Drag disabled due to gpui "window not found" error after move+close
                     .child(
                         div()
                             .id("session-list-header")
+                            .relative()
                             .w_full()
                             .h(px(28.0))
                             .flex()
                             .items_center()
                             .justify_center()
-                            .font_family("Maple Mono NF CN")
-                            .text_size(px(11.0))
+                            .font_family(font::family())
+                            .text_size(font::size(11.0))
                             .font_weight(gpui::FontWeight::NORMAL)
                             .text_color(theme_colors.text_header)
-                            .child(format!(
-                                "{} session{}",
-                                session_count,
-                                if session_count == 1 { "" } else { "s" }
-                            )),
+                            .child(header_summary)
+                            .child(header_menu_button),
                     )
                     // Content: session rows below header
                     .child(
                         div()
                             .id("session-list-content")
+                            .relative() // For the hover scrollbar overlay
                             .p(px(10.0))
                             .flex_1()
                             .rounded(px(theme::WINDOW_RADIUS))
@@ -696,10 +2046,21 @@ impl Render for SessionListView {
                             .gap(px(ROW_GAP))
                             .overflow_y_scroll()
                             .scrollbar_width(px(0.0))
+                            .on_hover(cx.listener(|this, hovered: &bool, _window, _cx| {
+                                this.list_hovered = *hovered;
+                            }))
                             .child(session_list)
-                            .children(removing_rows),
-                    ),
+                            .children(removing_rows)
+                            .children(scrollbar),
+                    )
+                    // Footer: "+N more…" when sessions exceed the configured cap
+                    .children(overflow_footer)
+                    // Sessions that ended in the last `ui.ended_visible_minutes`
+                    .children(graveyard),
             )
+            // Bulk action and row menus overlay the rows
+            .children(header_menu)
+            .children(row_menu)
             .into_any_element()
     }
 }
@@ -708,12 +2069,114 @@ impl Render for SessionListView {
 ///
 /// Called when user clicks indicator to show session list.
 /// Creates a new window positioned below the indicator's current position.
+/// Show the session list below the indicator, opening its window if needed
+fn show_session_list(
+    app: &mut App,
+    state: &Entity<SharedHudState>,
+    indicator_origin: Point<Pixels>,
+) {
+    let hud_state = state.read(app);
+    let session_count = hud_state.listed_sessions.len().max(1);
+    let columns = session_list::grid_columns(hud_state.layout, session_count);
+    let width = session_list::expanded_width(columns);
+    let graveyard_height = session_list::graveyard_height(hud_state.ended.len(), false);
+    let height = calculate_expanded_height(session_count, hud_state.max_visible_sessions, columns)
+        + graveyard_height;
+    let window_handle = hud_state.session_list_window;
+    let session_list_origin = point(
+        indicator_origin.x - px((width - COLLAPSED_WIDTH) / 2.0),
+        indicator_origin.y + px(COLLAPSED_HEIGHT + WINDOW_GAP),
+    );
+
+    state.update(app, |state, _cx| {
+        state.session_list_visible = true;
+        state.session_list_origin = session_list_origin;
+    });
+    if let Some(handle) = window_handle {
+        let _ = handle.update(app, |view, window, _cx| {
+            view.columns = columns;
+            view.ended_expanded = false;
+            view.graveyard_height = graveyard_height;
+            window.resize(size(px(width), px(height)));
+        });
+    } else {
+        open_session_list_window_sync(app, state.clone());
+    }
+}
+
+/// Show the session list (below the indicator) and move key focus to it
+fn focus_session_list(app: &mut App, state: &Entity<SharedHudState>) {
+    let (visible, indicator) = {
+        let state = state.read(app);
+        (state.session_list_visible, state.indicator_window)
+    };
+    if !visible {
+        let Some(indicator_origin) = indicator.and_then(|handle| {
+            handle
+                .update(app, |_, window, _| window.bounds().origin)
+                .ok()
+        }) else {
+            return;
+        };
+        show_session_list(app, state, indicator_origin);
+    }
+    let list_window = state.read(app).session_list_window;
+    if let Some(handle) = list_window {
+        let _ = handle.update(app, |view, window, cx| view.focus_from_keyboard(window, cx));
+    }
+}
+
+/// Glass over whatever is behind the HUD; opaque for demo screenshots, so
+/// they don't depend on the desktop
+fn window_background() -> WindowBackgroundAppearance {
+    if crate::demo::screenshot_path().is_some() {
+        WindowBackgroundAppearance::Opaque
+    } else {
+        WindowBackgroundAppearance::Blurred
+    }
+}
+
+/// `aura --demo --screenshot`: open the session list, let the HUD settle,
+/// save both windows to `path` and quit
+fn take_demo_screenshot(
+    app: &mut App,
+    state: Entity<SharedHudState>,
+    indicator_bounds: Bounds<Pixels>,
+    path: &'static std::path::Path,
+) {
+    show_session_list(app, &state, indicator_bounds.origin);
+    app.spawn(async move |cx| {
+        cx.background_executor()
+            .timer(crate::demo::SCREENSHOT_DELAY)
+            .await;
+        let list_window = cx
+            .update(|app| state.read(app).session_list_window)
+            .ok()
+            .flatten();
+        let list_bounds =
+            list_window.and_then(|handle| handle.update(cx, |_, window, _| window.bounds()).ok());
+        let area = list_bounds.map_or(indicator_bounds, |list| indicator_bounds.union(&list));
+        let area = (
+            f32::from(area.origin.x),
+            f32::from(area.origin.y),
+            f32::from(area.size.width),
+            f32::from(area.size.height),
+        );
+        match crate::demo::capture(area, path) {
+            Ok(()) => tracing::info!(target: logging::UI, "Saved screenshot to {}", path.display()),
+            Err(e) => tracing::warn!(target: logging::UI, "Failed to save screenshot: {}", e),
+        }
+        let _ = cx.update(|app| app.quit());
+    })
+    .detach();
+}
+
 fn open_session_list_window_sync(app: &mut App, state: Entity<SharedHudState>) {
     // Use saved session list origin (persists across open/close cycles)
     // Initial value is calculated relative to indicator at startup
     let origin = state.read(app).session_list_origin;
 
-    let initial_height = calculate_expanded_height(1);
+    let initial_height = calculate_expanded_height(1, state.read(app).max_visible_sessions, 1);
     let list_bounds = Bounds {
         origin,
         size: size(px(EXPANDED_WIDTH), px(initial_height)),
@@ -730,17 +2193,33 @@ fn open_session_list_window_sync(app: &mut App, state: Entity<SharedHudState>) {
                 kind: WindowKind::PopUp,
                 is_movable: true,
                 is_resizable: false,
-                window_background: WindowBackgroundAppearance::Blurred,
+                window_background: window_background(),
                 ..Default::default()
             },
             |_window, app| {
-                app.new(|_cx| SessionListView {
+                app.new(|cx| SessionListView {
                     state: state_for_list,
                     last_session_count: 0,
+                    columns: 1,
                     appeared_at: HashMap::new(),
                     icon_hover_at: HashMap::new(),
                     removing: HashMap::new(),
                     session_cache: HashMap::new(),
+                    header_menu_open: false,
+                    show_all: false,
+                    ended_expanded: false,
+                    graveyard_height: 0.0,
+                    scroll_handle: UniformListScrollHandle::new(),
+                    scroll_anchor: None,
+                    list_hovered: false,
+                    height_anim: None,
+                    row_menu: None,
+                    renaming: None,
+                    inline_field: InlineField::Name,
+                    focus_handle: cx.focus_handle(),
+                    selected: None,
+                    title: String::new(),
+                    revealed_link: None,
                 })
             },
         )
@@ -748,6 +2227,13 @@ fn open_session_list_window_sync(app: &mut App, state: Entity<SharedHudState>) {
 
     // Store window handle in shared state
     if let Some(handle) = window_handle {
+        let (level, all_spaces) = {
+            let state = state.read(app);
+            (state.window_level, state.all_spaces)
+        };
+        let _ = handle.update(app, |_, window, _| {
+            window_level::apply(window, level, all_spaces);
+        });
         state.update(app, |state, _cx| {
             state.session_list_window = Some(handle);
         });
@@ -761,21 +2247,70 @@ fn save_theme(style: theme::ThemeStyle) {
     let _ = crate::config::save_config(&config);
 }
 
+/// Pause `source` if it's running, resume it otherwise
+fn toggle_pause(source: crate::pause::Source) {
+    let paused = !crate::pause::is_paused(source);
+    crate::pause::set_paused(source, paused);
+    tracing::info!(target: logging::UI, ?source, paused, "source pause toggled");
+}
+
+/// Persist the privacy mode setting to config.json.
+fn save_privacy(enabled: bool) {
+    let mut config = crate::config::load_config();
+    config.privacy = enabled;
+    let _ = crate::config::save_config(&config);
+}
+
+/// Persist the `ui.follow_front_terminal` setting to config.json.
+fn save_follow_front_terminal(enabled: bool) {
+    let mut config = crate::config::load_config();
+    config.ui.follow_front_terminal = enabled;
+    let _ = crate::config::save_config(&config);
+}
+
 /// Run the HUD application with two separate windows
 ///
 /// This function blocks and runs the gpui event loop.
 /// Call from main thread only.
-pub fn run_hud(registry: Arc<Mutex<SessionRegistry>>, registry_dirty: Arc<AtomicBool>) {
-    Application::new().with_assets(Assets).run(|app: &mut App| {
+pub fn run_hud(registry: SharedRegistry) {
+    let application = Application::new().with_assets(Assets);
+
+    // aura:// links arrive outside the app context; queue them for the indicator
+    let pending_links = deep_link::PendingLinks::default();
+    application.on_open_urls({
+        let pending_links = pending_links.clone();
+        move |urls| deep_link::enqueue(&pending_links, urls)
+    });
+
+    application.run(move |app: &mut App| {
         // Load saved theme preference from config.json
         let saved_config = crate::config::load_config();
-        let initial_theme = theme::ThemeStyle::from_config_str(&saved_config.theme);
+        let initial_theme =
+            theme::ThemeStyle::from_config_str(crate::demo::theme().unwrap_or(&saved_config.theme));
+        animation::set_animations(if crate::demo::screenshot_path().is_some() {
+            crate::config::Animations::Off
+        } else {
+            animation::effective_animations(
+                saved_config.ui.animations,
+                animation::system_reduce_motion(),
+            )
+        });
+
+        animation::set_marquee(saved_config.ui.marquee);
 
         // Register embedded Maple Mono font for consistent marquee rendering
         let font_data = include_bytes!("../../assets/fonts/MapleMono-NF-CN-Regular.ttf");
         app.text_system()
             .add_fonts(vec![Cow::Borrowed(font_data.as_slice())])
             .expect("Failed to load Maple Mono font");
+        // Demo screenshots always use the embedded font
+        if !crate::demo::enabled() {
+            font::set_font(font::resolve(
+                saved_config.ui.font_family.as_deref(),
+                saved_config.ui.font_size,
+                &app.text_system().all_font_names(),
+            ));
+        }
 
         // Register quit action handler
         app.on_action(|_: &Quit, cx: &mut App| {
@@ -795,6 +2330,18 @@ pub fn run_hud(registry: Arc<Mutex<SessionRegistry>>, registry_dirty: Arc<Atomic
                         MenuItem::action("Liquid Light", SetThemeLiquidLight),
                     ],
                 }),
+                MenuItem::action("Focus Session List", FocusSessionList),
+                MenuItem::action("Toggle Privacy Mode", TogglePrivacy),
+                MenuItem::action("Toggle Follow Front Terminal", ToggleFollowFrontTerminal),
+                MenuItem::submenu(Menu {
+                    name: "Pause Monitoring".into(),
+                    items: vec![
+                        MenuItem::action("Claude Code Hooks", TogglePauseClaudeCode),
+                        MenuItem::action("Codex Rollouts", TogglePauseCodex),
+                    ],
+                }),
+                MenuItem::action("Diagnostics", ShowDiagnostics),
+                MenuItem::action("Show Log", ShowLog),
                 MenuItem::separator(),
                 MenuItem::action("Quit", Quit),
             ],
@@ -810,13 +2357,22 @@ pub fn run_hud(registry: Arc<Mutex<SessionRegistry>>, registry_dirty: Arc<Atomic
         let screen_width = display_bounds.size.width;
 
         // Get initial sessions from registry
-        let initial_sessions = registry.lock().map(|r| r.get_all()).unwrap_or_default();
+        let snapshots = registry.subscribe();
+        let initial = snapshots.borrow().changes_since(0);
+        let initial_generation = initial.generation;
+        let mut initial_sessions = Vec::new();
+        initial.apply(&mut initial_sessions);
 
         // Generate random seed from system time for varied animation timing
-        let animation_seed = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|d| d.as_nanos() as u64)
-            .unwrap_or(0);
+        // (fixed in demo mode, so screenshots repeat)
+        let animation_seed = if crate::demo::enabled() {
+            crate::demo::ANIMATION_SEED
+        } else {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos() as u64)
+                .unwrap_or(0)
+        };
 
         // Load saved indicator position, clamped to visible display bounds
         let saved_state = crate::config::load_state();
@@ -861,18 +2417,53 @@ pub fn run_hud(registry: Arc<Mutex<SessionRegistry>>, registry_dirty: Arc<Atomic
 
         // Create shared state between both windows
         let shared_state = app.new(|_cx| SharedHudState {
+            listed_sessions: initial_sessions.clone(),
             sessions: initial_sessions,
+            tag_filter: None,
+            state_filter: None,
             animation_start: Instant::now(),
             animation_seed,
+            generation: initial_generation,
+            clock: registry
+                .read(|registry| registry.clock().clone())
+                .unwrap_or_default(),
             registry,
+            snapshots,
             session_list_visible: false,
             session_list_window: None,
             session_list_origin,
             indicator_window: None, // Will be set after window creation
             theme_style: initial_theme,
             system_is_dark: initial_system_is_dark,
-            registry_dirty,
+            // Demo screenshots keep the default colors
+            state_colors: if crate::demo::enabled() {
+                theme::ThemeStateColors::default()
+            } else {
+                theme::ThemeStateColors::from_config(&saved_config.state_colors)
+            },
+            registry_dirty: Arc::new(AtomicBool::new(false)),
+            max_visible_sessions: saved_config.ui.max_visible_sessions.max(1),
+            layout: saved_config.ui.layout,
+            window_level: saved_config.ui.window_level,
+            all_spaces: saved_config.ui.all_spaces,
+            auto_hide: saved_config.ui.auto_hide,
+            edge_pulse: if crate::demo::enabled() {
+                Vec::new()
+            } else {
+                saved_config.ui.edge_pulse_states()
+            },
+            review_reminder: saved_config.ui.review_reminder(),
+            ended_visible: saved_config.ui.ended_visible(),
+            ended: Vec::new(),
+            state_age_thresholds: saved_config.ui.state_age_thresholds(),
+            indicator_priority: saved_config.ui.indicator_priority.clone(),
+            indicator_style: saved_config.ui.indicator_style,
+            follow_front_terminal: saved_config.ui.follow_front_terminal && !crate::demo::enabled(),
+            active_context: Default::default(),
+            pending_links,
+            linked_session: None,
         });
+        shared_state.update(app, |state, _cx| state.update_probe());
 
         // Register theme action handlers
         let state_for_system = shared_state.clone();
@@ -899,6 +2490,58 @@ pub fn run_hud(registry: Arc<Mutex<SessionRegistry>>, registry_dirty: Arc<Atomic
             });
         });
 
+        let state_for_privacy = shared_state.clone();
+        app.on_action(move |_: &TogglePrivacy, cx: &mut App| {
+            let enabled = !crate::privacy::enabled();
+            crate::privacy::set_enabled(enabled);
+            save_privacy(enabled);
+            tracing::info!(target: logging::UI, enabled, "privacy mode toggled");
+            state_for_privacy.update(cx, |state, _cx| {
+                state.apply_filters();
+                state.registry_dirty.store(true, Ordering::Relaxed);
+            });
+        });
+
+        let state_for_front_terminal = shared_state.clone();
+        app.on_action(move |_: &ToggleFollowFrontTerminal, cx: &mut App| {
+            state_for_front_terminal.update(cx, |state, _cx| {
+                state.follow_front_terminal = !state.follow_front_terminal;
+                save_follow_front_terminal(state.follow_front_terminal);
+                tracing::info!(
+                    target: logging::UI,
+                    enabled = state.follow_front_terminal,
+                    "front terminal following toggled"
+                );
+                state.update_probe();
+            });
+        });
+
+        // Pausing a source is per daemon, like `aura pause`; Diagnostics
+        // shows which ones are paused
+        app.on_action(|_: &TogglePauseClaudeCode, _cx: &mut App| {
+            toggle_pause(crate::pause::Source::ClaudeCode);
+        });
+        app.on_action(|_: &TogglePauseCodex, _cx: &mut App| {
+            toggle_pause(crate::pause::Source::Codex);
+        });
+
+        let state_for_log = shared_state.clone();
+        app.on_action(move |_: &ShowLog, cx: &mut App| {
+            log_view::open(cx, state_for_log.clone());
+        });
+
+        // Keyboard-only access: open the list and give it key focus
+        let state_for_focus = shared_state.clone();
+        app.on_action(move |_: &FocusSessionList, cx: &mut App| {
+            focus_session_list(cx, &state_for_focus);
+        });
+        app.bind_keys([KeyBinding::new("cmd-shift-l", FocusSessionList, None)]);
+
+        let state_for_diagnostics = shared_state.clone();
+        app.on_action(move |_: &ShowDiagnostics, cx: &mut App| {
+            diagnostics::open(cx, state_for_diagnostics.clone());
+        });
+
         // Create indicator window (always visible, 36x36)
         let indicator_bounds = Bounds {
             origin: point(indicator_x, indicator_y),
@@ -916,19 +2559,26 @@ pub fn run_hud(registry: Arc<Mutex<SessionRegistry>>, registry_dirty: Arc<Atomic
                     kind: WindowKind::PopUp,
                     is_movable: true,
                     is_resizable: false,
-                    window_background: WindowBackgroundAppearance::Blurred,
+                    window_background: window_background(),
                     ..Default::default()
                 },
                 |_window, app| {
-                    app.new(|_cx| IndicatorView {
-                        state: state_for_indicator,
-                        is_hovered: false,
-                        window_pos_at_mouse_down: None,
+                    app.new(|cx| {
+                        IndicatorView::spawn_waker(cx);
+                        IndicatorView::new(state_for_indicator)
                     })
                 },
             )
             .expect("Failed to open indicator window");
 
+        let _ = indicator_handle.update(app, |_, window, _| {
+            window_level::apply(
+                window,
+                saved_config.ui.window_level,
+                saved_config.ui.all_spaces,
+            );
+        });
+
         // Store indicator window handle in shared state
         shared_state.update(app, |state, _cx| {
             state.indicator_window = Some(indicator_handle);
@@ -936,6 +2586,9 @@ pub fn run_hud(registry: Arc<Mutex<SessionRegistry>>, registry_dirty: Arc<Atomic
 
         // Session list window is opened on demand when user clicks indicator
         // (see open_session_list_window function)
+        if let Some(path) = crate::demo::screenshot_path() {
+            take_demo_screenshot(app, shared_state.clone(), indicator_bounds, path);
+        }
 
         // Keep shared state alive
         let _ = shared_state;
@@ -945,6 +2598,7 @@ pub fn run_hud(registry: Arc<Mutex<SessionRegistry>>, registry_dirty: Arc<Atomic
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::registry::SessionRegistry;
     use gpui::TestAppContext;
 
     fn make_session(id: &str, state: SessionState) -> SessionInfo {
@@ -952,12 +2606,7 @@ mod tests {
             session_id: id.to_string(),
             cwd: "/test/project".to_string(),
             state,
-            running_tools: vec![],
-            name: None,
-            stopped_at: None,
-            stale_at: None,
-            permission_tool: None,
-            recent_activity: vec![],
+            ..Default::default()
         }
     }
 
@@ -965,23 +2614,22 @@ mod tests {
 
     #[gpui::test]
     async fn shared_state_refresh_from_registry(cx: &mut TestAppContext) {
-        let registry = Arc::new(Mutex::new(SessionRegistry::new()));
-        let registry_dirty = Arc::new(AtomicBool::new(false));
+        let registry = SharedRegistry::new(SessionRegistry::new());
 
         // Seed registry with a session
-        {
-            let mut reg = registry.lock().unwrap();
+        registry.update(|reg| {
             reg.process_event(crate::AgentEvent::SessionStarted {
                 session_id: "s1".into(),
                 agent: crate::AgentType::ClaudeCode,
+                terminal: None,
                 cwd: "/test/project".into(),
-            });
-        }
+            })
+        });
 
         let state = cx.new(|_cx| {
             let mut s = SharedHudState::new_for_test(vec![]);
+            s.snapshots = registry.subscribe();
             s.registry = registry.clone();
-            s.registry_dirty = registry_dirty.clone();
             s
         });
 
@@ -1001,6 +2649,65 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn shared_state_tag_filter(cx: &mut TestAppContext) {
+        let mut tagged = make_session("s1", SessionState::Running);
+        tagged.tags = vec!["billing".into()];
+        let state = cx.new(|_cx| {
+            SharedHudState::new_for_test(vec![tagged, make_session("s2", SessionState::Idle)])
+        });
+
+        state.update(cx, |s, _| s.set_tag_filter(Some("billing".into())));
+        state.read_with(cx, |s, _| {
+            assert_eq!(s.sessions.len(), 2);
+            assert_eq!(s.listed_sessions.len(), 1);
+            assert_eq!(s.listed_sessions[0].session_id, "s1");
+        });
+
+        // A tag nobody carries any more clears the filter
+        state.update(cx, |s, _| {
+            s.sessions[0].tags.clear();
+            s.apply_filters();
+        });
+        state.read_with(cx, |s, _| {
+            assert_eq!(s.tag_filter, None);
+            assert_eq!(s.listed_sessions.len(), 2);
+        });
+    }
+
+    #[gpui::test]
+    async fn shared_state_state_filter(cx: &mut TestAppContext) {
+        let state = cx.new(|_cx| {
+            SharedHudState::new_for_test(vec![
+                make_session("s1", SessionState::Running),
+                make_session("s2", SessionState::Idle),
+                make_session("s3", SessionState::Running),
+            ])
+        });
+
+        state.update(cx, |s, _| s.set_state_filter(Some(SessionState::Running)));
+        state.read_with(cx, |s, _| {
+            let ids: Vec<_> = s
+                .listed_sessions
+                .iter()
+                .map(|s| s.session_id.as_str())
+                .collect();
+            assert_eq!(ids, ["s1", "s3"]);
+        });
+
+        // Once no session is in the state, the whole list comes back
+        state.update(cx, |s, _| {
+            for session in &mut s.sessions {
+                session.state = SessionState::Idle;
+            }
+            s.apply_filters();
+        });
+        state.read_with(cx, |s, _| {
+            assert_eq!(s.state_filter, None);
+            assert_eq!(s.listed_sessions.len(), 3);
+        });
+    }
+
     #[gpui::test]
     async fn shared_state_starts_with_list_hidden(cx: &mut TestAppContext) {
         let state = cx.new(|_cx| SharedHudState::new_for_test(vec![]));
@@ -1182,17 +2889,15 @@ mod tests {
         let sessions = vec![make_session("s1", SessionState::Running)];
         let state = cx.new(|_cx| SharedHudState::new_for_test(sessions));
 
-        let window = cx.add_window(|_window, _cx| IndicatorView {
-            state: state.clone(),
-            is_hovered: false,
-            window_pos_at_mouse_down: None,
-        });
+        let window = cx.add_window(|_window, _cx| IndicatorView::new(state.clone()));
 
         let view = window.root(cx).unwrap();
 
         view.read_with(cx, |v, _| {
             assert!(!v.is_hovered);
             assert!(v.window_pos_at_mouse_down.is_none());
+            assert!(!v.auto_hidden);
+            assert!(v.redraw_at.is_none());
         });
     }
 
@@ -1203,13 +2908,29 @@ mod tests {
         let sessions = vec![make_session("s1", SessionState::Running)];
         let state = cx.new(|_cx| SharedHudState::new_for_test(sessions));
 
-        let window = cx.add_window(|_window, _cx| SessionListView {
+        let window = cx.add_window(|_window, cx| SessionListView {
             state: state.clone(),
             last_session_count: 0,
+            columns: 1,
             appeared_at: HashMap::new(),
             icon_hover_at: HashMap::new(),
             removing: HashMap::new(),
             session_cache: HashMap::new(),
+            header_menu_open: false,
+            show_all: false,
+            ended_expanded: false,
+            graveyard_height: 0.0,
+            scroll_handle: UniformListScrollHandle::new(),
+            scroll_anchor: None,
+            list_hovered: false,
+            height_anim: None,
+            row_menu: None,
+            renaming: None,
+            inline_field: InlineField::Name,
+            focus_handle: cx.focus_handle(),
+            selected: None,
+            title: String::new(),
+            revealed_link: None,
         });
 
         let view = window.root(cx).unwrap();
@@ -1219,31 +2940,43 @@ mod tests {
             assert!(v.appeared_at.is_empty());
             assert!(v.removing.is_empty());
         });
+
+        // Keyboard navigation: Up with nothing selected picks the last row
+        view.update(cx, |v, cx| {
+            v.handle_navigation_key("up", cx);
+            assert_eq!(v.selected.as_deref(), Some("s1"));
+            v.handle_navigation_key("down", cx);
+            assert_eq!(v.selected.as_deref(), Some("s1"));
+            v.handle_navigation_key("escape", cx);
+            assert!(v.selected.is_none());
+        });
     }
 
     // --- 2.8: Session removal from registry via state ---
 
     #[gpui::test]
     async fn session_removal_from_registry(cx: &mut TestAppContext) {
-        let registry = Arc::new(Mutex::new(SessionRegistry::new()));
+        let registry = SharedRegistry::new(SessionRegistry::new());
 
         // Add two sessions
-        {
-            let mut reg = registry.lock().unwrap();
+        registry.update(|reg| {
             reg.process_event(crate::AgentEvent::SessionStarted {
                 session_id: "s1".into(),
                 agent: crate::AgentType::ClaudeCode,
+                terminal: None,
                 cwd: "/test/a".into(),
             });
             reg.process_event(crate::AgentEvent::SessionStarted {
                 session_id: "s2".into(),
                 agent: crate::AgentType::ClaudeCode,
+                terminal: None,
                 cwd: "/test/b".into(),
             });
-        }
+        });
 
         let state = cx.new(|_cx| {
             let mut s = SharedHudState::new_for_test(vec![]);
+            s.snapshots = registry.subscribe();
             s.registry = registry.clone();
             s
         });
@@ -1257,17 +2990,60 @@ mod tests {
             assert_eq!(s.sessions.len(), 2);
         });
 
-        // Remove one session
+        // Archive one session (what the remove button does)
         state.update(cx, |s, _| {
-            if let Ok(mut reg) = s.registry.lock() {
-                reg.remove_session("s1");
-            }
+            s.registry.update(|reg| reg.archive_session("s1"));
+            assert!(s.needs_refresh());
             s.refresh_from_registry();
         });
 
         state.read_with(cx, |s, _| {
             assert_eq!(s.sessions.len(), 1);
             assert_eq!(s.sessions[0].session_id, "s2");
+            // Archived, not ended
+            assert!(s.recently_ended().is_empty());
+        });
+    }
+
+    #[gpui::test]
+    async fn ended_sessions_stay_for_a_while(cx: &mut TestAppContext) {
+        let clock = Arc::new(crate::clock::ManualClock::new());
+        let registry = SharedRegistry::new(SessionRegistry::new());
+        registry.update(|reg| {
+            reg.process_event(crate::AgentEvent::SessionStarted {
+                session_id: "s1".into(),
+                agent: crate::AgentType::ClaudeCode,
+                terminal: None,
+                cwd: "/test/a".into(),
+            })
+        });
+        let state = cx.new(|_cx| {
+            let mut s = SharedHudState::new_for_test(vec![]);
+            s.snapshots = registry.subscribe();
+            s.registry = registry.clone();
+            s.clock = SharedClock::new(clock.clone());
+            s.refresh_from_registry();
+            s
+        });
+
+        state.update(cx, |s, _| {
+            s.registry.update(|reg| {
+                reg.process_event(crate::AgentEvent::SessionEnded {
+                    session_id: "s1".into(),
+                })
+            });
+            s.refresh_from_registry();
+            assert!(s.sessions.is_empty());
+            clock.advance(Duration::from_secs(60));
+            let ended = s.recently_ended();
+            assert_eq!(ended.len(), 1);
+            assert_eq!(ended[0].0.session_id, "s1");
+            assert_eq!(ended[0].1, Duration::from_secs(60));
+
+            // Gone once `ui.ended_visible_minutes` passed
+            clock.advance(s.ended_visible.unwrap());
+            s.forget_ended();
+            assert!(s.recently_ended().is_empty());
         });
     }
 
@@ -1,26 +1,50 @@
 //! Session list rendering - expanded view with session rows
 //!
 //! Each row displays in two-line vertical layout:
-//! - Line 1 (header): State icon (16x16) + session name
-//! - Line 2 (event): Current tool with icon (or state-specific placeholder)
+//! - Line 1 (header): State icon (16x16) + session name + tags + snooze toggle
+//! - Line 2 (event): Current tool with icon (or state-specific placeholder),
+//!   then a mini progress bar and "3/7 · migrate config loader" when the
+//!   agent has a plan or todo list
+//!
+//! A tool label too long for its row scrolls while the row is hovered, or
+//! with `ui.marquee.enabled` off is cut in the middle, see
+//! [`render_tool_with_icon`].
+//!
+//! With `ui.layout: "grid"` rows are laid out in up to [`MAX_GRID_COLUMNS`]
+//! columns, see [`grid_columns`].
 //!
 //! Uses liquid glass theme with themed text colors on translucent backgrounds.
 
-use super::animation::{calculate_shake_offset, ease_in_out};
+use super::animation::{
+    self, calculate_marquee_offset, calculate_shake_offset, calculate_spin_radians, ease_in_out,
+};
+use super::font;
 use super::icons;
 use super::theme::{ThemeColors, WINDOW_RADIUS};
-use crate::{PLACEHOLDER_TEXTS, RunningTool, SessionInfo, SessionState};
+use crate::config::{Layout, StateAgeThresholds};
+use crate::registry::BulkAction;
+use crate::{PLACEHOLDER_TEXTS, PlanProgress, RunningTool, SessionInfo, SessionState};
 use chrono::{DateTime, Local, Utc};
 use gpui::{
-    Div, Hsla, InteractiveElement, ParentElement, Styled, Transformation, div, px, radians, svg,
+    Div, Hsla, InteractiveElement, ParentElement, Styled, Transformation, div, hsla,
+    prelude::FluentBuilder, px, radians, svg,
 };
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 /// Session list dimensions
 pub const WIDTH: f32 = 320.0; // Match prototype width
 pub const ROW_HEIGHT: f32 = 56.0; // Two-line layout needs more height
 pub const ROW_GAP: f32 = 4.0; // Gap between session rows
-pub const MAX_SESSIONS: usize = 5;
+pub const FOOTER_HEIGHT: f32 = 24.0; // "+N more…" overflow footer
+pub const ENDED_ROW_HEIGHT: f32 = 20.0; // One line per session under "Ended recently"
+/// Part of [`ROW_HEIGHT`] taken by the two text lines (the rest is padding),
+/// which grows with `ui.font_size`
+const ROW_TEXT_HEIGHT: f32 = 36.0;
+
+/// [`ROW_HEIGHT`] at the configured font size
+fn row_height() -> f32 {
+    ROW_HEIGHT + ROW_TEXT_HEIGHT * (font::scale() - 1.0)
+}
 
 /// Layout constants (matching React prototype)
 const STATE_ICON_SIZE: f32 = 14.0; // State icon in session row
@@ -37,6 +61,16 @@ pub(crate) struct RowRenderArgs<'a> {
     pub(crate) remove_opacity: f32,
     pub(crate) remove_x: f32,
     pub(crate) theme: &'a ThemeColors,
+    /// Inline rename buffer, replacing the name while editing
+    pub(crate) rename_buffer: Option<&'a str>,
+    /// Idle and unseen past `ui.review_reminder_minutes` (clock badge)
+    pub(crate) review_due: bool,
+    /// Current Unix time, for the "2m ago" and time in state labels
+    pub(crate) now_unix: u64,
+    /// When the time in state turns amber and red
+    pub(crate) state_age_thresholds: StateAgeThresholds,
+    /// Since when the row is hovered, scrolling a long tool label
+    pub(crate) marquee_since: Option<Instant>,
 }
 
 /// Render the content of a session row (two-line vertical layout)
@@ -77,13 +111,17 @@ pub(crate) fn render_row_content(
         .bg(args.theme.row_bg)
         .hover(|style| style.bg(args.theme.row_hover_bg))
         // Session header (Line 1): icon + name
-        .child(render_session_header(session.state, session_name, args))
+        .child(render_session_header(session, session_name, args))
         // Session event (Line 2): tool or placeholder
         .child(render_session_event(session, args))
 }
 
 /// Render the session header (Line 1): state icon + session name
-fn render_session_header(state: SessionState, session_name: &str, args: &RowRenderArgs<'_>) -> Div {
+fn render_session_header(
+    session: &SessionInfo,
+    session_name: &str,
+    args: &RowRenderArgs<'_>,
+) -> Div {
     div()
         .w_full()
         .h(px(18.0)) // Explicit height for h_full children
@@ -93,7 +131,7 @@ fn render_session_header(state: SessionState, session_name: &str, args: &RowRend
         .gap(px(HEADER_GAP))
         // State icon (fixed width, with opacity + shake)
         .child(render_state_indicator(
-            state,
+            session,
             args.animation_start,
             args.state_opacity,
             args.state_x,
@@ -101,19 +139,209 @@ fn render_session_header(state: SessionState, session_name: &str, args: &RowRend
             args.remove_x,
             args.theme,
         ))
-        // Session name (with ellipsis truncation)
-        .child(
-            div()
+        // Session name (with ellipsis truncation), or the rename field
+        .child(match args.rename_buffer {
+            Some(buffer) => render_rename_field(buffer, args.theme),
+            None => div()
                 .flex_1()
                 .min_w_0()
                 .overflow_hidden()
-                .font_family("Maple Mono NF CN")
-                .text_size(px(14.0))
+                .font_family(font::family())
+                .text_size(font::size(14.0))
                 .font_weight(gpui::FontWeight::MEDIUM)
                 .text_color(args.theme.text_primary)
                 .whitespace_nowrap()
                 .text_ellipsis()
                 .child(session_name.to_string()),
+        })
+        // Tags after the name (hidden while editing)
+        .when(
+            args.rename_buffer.is_none() && !session.tags.is_empty(),
+            |this| {
+                this.child(
+                    div()
+                        .flex_none()
+                        .max_w(px(TAGS_MAX_WIDTH))
+                        .overflow_hidden()
+                        .font_family(font::family())
+                        .text_size(font::size(11.0))
+                        .text_color(args.theme.text_secondary)
+                        .whitespace_nowrap()
+                        .text_ellipsis()
+                        .child(crate::tags::format_tags(&session.tags)),
+                )
+            },
+        )
+        // Model, dimmed (hidden while editing)
+        .when_some(
+            session
+                .model
+                .as_deref()
+                .filter(|_| args.rename_buffer.is_none()),
+            |this, model| {
+                this.child(
+                    div()
+                        .flex_none()
+                        .max_w(px(MODEL_MAX_WIDTH))
+                        .overflow_hidden()
+                        .font_family(font::family())
+                        .text_size(font::size(10.0))
+                        .text_color(args.theme.text_secondary)
+                        .opacity(0.7)
+                        .whitespace_nowrap()
+                        .text_ellipsis()
+                        .child(short_model_name(model).to_string()),
+                )
+            },
+        )
+        // More prompts waiting behind the current turn
+        .when(session.queued_prompts > 0, |this| {
+            this.child(
+                div()
+                    .flex_none()
+                    .font_family(font::family())
+                    .text_size(font::size(10.0))
+                    .text_color(args.theme.text_secondary)
+                    .whitespace_nowrap()
+                    .child(queued_label(session.queued_prompts)),
+            )
+        })
+        // Time since the last event, dimmed (hidden while editing)
+        .when_some(
+            last_activity_label(session, args.now_unix).filter(|_| args.rename_buffer.is_none()),
+            |this, label| {
+                this.child(
+                    div()
+                        .flex_none()
+                        .font_family(font::family())
+                        .text_size(font::size(10.0))
+                        .text_color(args.theme.text_secondary)
+                        .opacity(0.7)
+                        .whitespace_nowrap()
+                        .child(label),
+                )
+            },
+        )
+        // Time in the current state, escalating while the user keeps an
+        // Attention or Waiting session waiting (hidden while editing)
+        .when_some(
+            session
+                .state_age_label(args.now_unix)
+                .filter(|_| args.rename_buffer.is_none()),
+            |this, label| {
+                let level = state_age_level(session, args.now_unix, args.state_age_thresholds);
+                this.child(
+                    div()
+                        .flex_none()
+                        .font_family(font::family())
+                        .text_size(font::size(10.0))
+                        .text_color(state_age_color(level, args.theme))
+                        .when(level != StateAgeLevel::Normal, |this| {
+                            this.font_weight(gpui::FontWeight::MEDIUM)
+                        })
+                        .whitespace_nowrap()
+                        .child(label),
+                )
+            },
+        )
+        // Agent (and its tools) keeping the CPU busy
+        .when_some(
+            session
+                .cpu_percent
+                .filter(|load| *load >= crate::sysinfo::HIGH_LOAD),
+            |this, load| {
+                this.child(render_header_badge("icons/cpu.svg", args.theme))
+                    .child(
+                        div()
+                            .flex_none()
+                            .font_family(font::family())
+                            .text_size(font::size(10.0))
+                            .text_color(args.theme.text_secondary)
+                            .whitespace_nowrap()
+                            .child(format!("{load}%")),
+                    )
+            },
+        )
+        // Finished a while ago and still unreviewed
+        .when(args.review_due, |this| {
+            this.child(render_header_badge("icons/clock.svg", args.theme))
+        })
+        // Drafting a plan (Claude Code plan mode)
+        .when(session.plan_mode && !session.is_reviewing_plan(), |this| {
+            this.child(render_header_badge("icons/book-open.svg", args.theme))
+        })
+        // Another live session works in the same directory or files
+        .when(session.has_conflict(), |this| {
+            this.child(render_header_badge("icons/triangle-alert.svg", args.theme))
+        })
+        // Snooze toggle (always shown while snoozed, hinted on hover)
+        .child(render_snooze_indicator(
+            session.is_snoozed(),
+            args.remove_opacity,
+            args.theme,
+        ))
+}
+
+/// Render the inline rename field: buffer text, caret, and an underline
+fn render_rename_field(buffer: &str, theme: &ThemeColors) -> Div {
+    div()
+        .flex_1()
+        .min_w_0()
+        .overflow_hidden()
+        .border_b_1()
+        .border_color(theme.text_secondary)
+        .font_family(font::family())
+        .text_size(font::size(14.0))
+        .font_weight(gpui::FontWeight::MEDIUM)
+        .text_color(theme.text_primary)
+        .whitespace_nowrap()
+        .child(format!("{buffer}▏"))
+}
+
+/// Render a status badge (review reminder, plan mode, conflict warning) shown before
+/// the snooze icon
+fn render_header_badge(icon: &'static str, theme: &ThemeColors) -> Div {
+    div()
+        .flex_shrink_0()
+        .w(px(STATE_ICON_SIZE))
+        .h(px(STATE_ICON_SIZE))
+        .flex()
+        .items_center()
+        .justify_center()
+        .child(
+            svg()
+                .path(icon)
+                .size(px(STATE_ICON_SIZE))
+                .text_color(theme.icon_state),
+        )
+}
+
+/// Snooze icon opacity: steady while snoozed, faint hint while hovering otherwise
+pub(crate) fn snooze_icon_opacity(snoozed: bool, hover_opacity: f32) -> f32 {
+    if snoozed { 0.8 } else { 0.4 * hover_opacity }
+}
+
+/// Render the snooze (bell-off) icon at the end of the session header
+///
+/// The click target is an overlay created in mod.rs, like the remove button.
+fn render_snooze_indicator(snoozed: bool, hover_opacity: f32, theme: &ThemeColors) -> Div {
+    let color = Hsla {
+        a: snooze_icon_opacity(snoozed, hover_opacity),
+        ..theme.icon_state
+    };
+
+    div()
+        .flex_shrink_0()
+        .w(px(STATE_ICON_SIZE))
+        .h(px(STATE_ICON_SIZE))
+        .flex()
+        .items_center()
+        .justify_center()
+        .child(
+            svg()
+                .path("icons/bell-off.svg")
+                .size(px(STATE_ICON_SIZE))
+                .text_color(color),
         )
 }
 
@@ -131,8 +359,152 @@ fn render_session_event(session: &SessionInfo, args: &RowRenderArgs<'_>) -> Div
             args.tool_index,
             args.fade_progress,
             args.animation_start,
+            args.marquee_since,
             args.theme,
         ))
+        .when_some(session.progress.as_ref(), |this, progress| {
+            this.child(render_progress(
+                progress,
+                session.subtitle.as_deref(),
+                args.theme,
+            ))
+        })
+}
+
+/// Subagents and what each is doing, e.g. "Explore: login.rs · Plan"
+pub(crate) fn subagent_summary(session: &SessionInfo) -> String {
+    session
+        .subagents
+        .iter()
+        .map(|agent| {
+            let kind = agent.agent_type.as_deref().unwrap_or("agent");
+            match agent.running_tools.last() {
+                Some(tool) => format!(
+                    "{kind}: {}",
+                    tool.tool_label.as_deref().unwrap_or(&tool.tool_name)
+                ),
+                None => kind.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" · ")
+}
+
+/// Queued prompts badge text, e.g. "+2 queued"
+pub(crate) fn queued_label(count: usize) -> String {
+    format!("+{count} queued")
+}
+
+/// Model badge text: the model id without the vendor prefix or date suffix,
+/// e.g. "sonnet-4-5" for "claude-sonnet-4-5-20250929"
+pub(crate) fn short_model_name(model: &str) -> &str {
+    let model = model.strip_prefix("claude-").unwrap_or(model);
+    match model.rsplit_once('-') {
+        Some((name, date)) if date.len() == 8 && date.bytes().all(|b| b.is_ascii_digit()) => name,
+        _ => model,
+    }
+}
+
+/// Fraction of a plan completed, for the progress bar fill
+pub(crate) fn progress_fraction(progress: &PlanProgress) -> f32 {
+    if progress.total == 0 {
+        return 0.0;
+    }
+    (progress.completed as f32 / progress.total as f32).clamp(0.0, 1.0)
+}
+
+/// Render the mini progress bar and plan subtitle after the current tool
+fn render_progress(progress: &PlanProgress, subtitle: Option<&str>, theme: &ThemeColors) -> Div {
+    let bar = div()
+        .flex_none()
+        .w(px(PROGRESS_BAR_WIDTH))
+        .h(px(3.0))
+        .rounded(px(1.5))
+        .bg(theme.border)
+        .child(
+            div()
+                .h_full()
+                .w(px(PROGRESS_BAR_WIDTH * progress_fraction(progress)))
+                .rounded(px(1.5))
+                .bg(theme.text_secondary),
+        );
+    div()
+        .flex_none()
+        .flex()
+        .flex_row()
+        .items_center()
+        .gap(px(4.0))
+        .ml(px(6.0))
+        .child(bar)
+        .when_some(subtitle, |this, subtitle| {
+            this.child(
+                div()
+                    .max_w(px(SUBTITLE_MAX_WIDTH))
+                    .overflow_hidden()
+                    .font_family(font::family())
+                    .text_size(font::size(11.0))
+                    .text_color(theme.text_secondary)
+                    .whitespace_nowrap()
+                    .text_ellipsis()
+                    .child(subtitle.to_string()),
+            )
+        })
+}
+
+/// "2m ago" since the session's last event, `None` if it never had one
+pub(crate) fn last_activity_label(session: &SessionInfo, now_unix: u64) -> Option<String> {
+    let at = session.last_activity_at?;
+    Some(crate::health::format_ago(Duration::from_secs(
+        now_unix.saturating_sub(at),
+    )))
+}
+
+/// How urgently a row flags its time in state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum StateAgeLevel {
+    Normal,
+    /// Past `ui.state_age_warn_minutes`
+    Warn,
+    /// Past `ui.state_age_alert_minutes`
+    Alert,
+}
+
+/// Escalation of a session's time in state; only Attention and Waiting,
+/// which wait on the user, escalate (snoozed and seen ones don't)
+pub(crate) fn state_age_level(
+    session: &SessionInfo,
+    now_unix: u64,
+    thresholds: StateAgeThresholds,
+) -> StateAgeLevel {
+    let waiting_on_user = matches!(
+        session.state,
+        SessionState::Attention | SessionState::Waiting
+    );
+    let Some(elapsed) = session
+        .time_in_state(now_unix)
+        .filter(|_| waiting_on_user && !session.seen && !session.is_snoozed())
+    else {
+        return StateAgeLevel::Normal;
+    };
+    let past = |threshold: Option<Duration>| threshold.is_some_and(|t| elapsed >= t);
+    if past(thresholds.alert) {
+        StateAgeLevel::Alert
+    } else if past(thresholds.warn) {
+        StateAgeLevel::Warn
+    } else {
+        StateAgeLevel::Normal
+    }
+}
+
+fn state_age_color(level: StateAgeLevel, theme: &ThemeColors) -> Hsla {
+    match level {
+        StateAgeLevel::Normal => theme.text_secondary,
+        StateAgeLevel::Warn => hsla(0.11, 0.9, 0.55, 1.0),
+        StateAgeLevel::Alert => theme
+            .states
+            .get(SessionState::Attention)
+            .unwrap_or_else(|| super::edge_pulse::default_color(SessionState::Attention)),
+    }
 }
 
 /// Format a Unix timestamp as "Jan 17, 14:30"
@@ -170,6 +542,7 @@ pub(crate) fn get_placeholder_text(session: &SessionInfo) -> String {
                 "inactive".to_string()
             }
         }
+        SessionState::Attention if session.is_reviewing_plan() => "reviewing plan".to_string(),
         SessionState::Attention => {
             let tool = session.permission_tool.as_deref().unwrap_or("Tool");
             format!("{} needs permission", tool)
@@ -180,9 +553,10 @@ pub(crate) fn get_placeholder_text(session: &SessionInfo) -> String {
     }
 }
 
-/// Get placeholder icon path for a state
-fn get_placeholder_icon(state: SessionState) -> &'static str {
-    match state {
+/// Get placeholder icon path for a session's state
+fn get_placeholder_icon(session: &SessionInfo) -> &'static str {
+    match session.state {
+        SessionState::Attention if session.is_reviewing_plan() => "icons/book-open.svg",
         SessionState::Waiting => "icons/wind.svg",
         _ => "icons/audio-lines.svg",
     }
@@ -221,8 +595,8 @@ fn render_placeholder(text: &str, icon_path: &'static str, theme: &ThemeColors)
                 .flex_1()
                 .min_w_0()
                 .overflow_hidden()
-                .font_family("Maple Mono NF CN")
-                .text_size(px(12.0))
+                .font_family(font::family())
+                .text_size(font::size(12.0))
                 .italic()
                 .text_color(theme.text_secondary)
                 .whitespace_nowrap()
@@ -238,10 +612,30 @@ fn render_tool_or_placeholder(
     tool_index: usize,
     fade_progress: f32,
     animation_start: Instant,
+    marquee_since: Option<Instant>,
     theme: &ThemeColors,
 ) -> Div {
-    if session.running_tools.is_empty() {
-        if let Some(activity_text) = get_recent_activity_text(session, animation_start) {
+    // Parallel subagents: one grouped line instead of cycling through
+    // every agent's tools
+    if !session.subagents.is_empty() && !session.is_reviewing_plan() {
+        return div()
+            .flex_1()
+            .min_w_0()
+            .h(px(18.0))
+            .overflow_hidden()
+            .child(render_placeholder(
+                &subagent_summary(session),
+                "icons/bot.svg",
+                theme,
+            ));
+    }
+
+    // A plan awaiting approval replaces the ExitPlanMode tool it's waiting on
+    let reviewing_plan = session.is_reviewing_plan();
+    if session.running_tools.is_empty() || reviewing_plan {
+        if !reviewing_plan
+            && let Some(activity_text) = get_recent_activity_text(session, animation_start)
+        {
             return div()
                 .flex_1()
                 .min_w_0()
@@ -252,7 +646,7 @@ fn render_tool_or_placeholder(
 
         // Show state-specific placeholder
         let placeholder_text = get_placeholder_text(session);
-        let icon_path = get_placeholder_icon(session.state);
+        let icon_path = get_placeholder_icon(session);
         return div()
             .flex_1()
             .min_w_0() // Allow shrinking for text ellipsis
@@ -262,15 +656,23 @@ fn render_tool_or_placeholder(
     }
 
     // Render tools with vertical slide (ticker) animation
-    render_current_tool(&session.running_tools, tool_index, fade_progress, theme)
+    render_current_tool(
+        &session.running_tools,
+        tool_index,
+        fade_progress,
+        (tool_label_width(session), marquee_since),
+        theme,
+    )
 }
 
 /// Render current tool with vertical slide (ticker) animation
-/// Shows one tool at a time, cycling through the list
+/// Shows one tool at a time, cycling through the list; only the current one
+/// scrolls while hovered
 fn render_current_tool(
     tools: &[RunningTool],
     tool_index: usize,
     fade_progress: f32,
+    (label_width, marquee_since): (f32, Option<Instant>),
     theme: &ThemeColors,
 ) -> Div {
     // Get current and next tool indices
@@ -308,7 +710,12 @@ fn render_current_tool(
                 .items_center()
                 .overflow_hidden()
                 .opacity(current_opacity)
-                .child(render_tool_with_icon(current_tool, theme)),
+                .child(render_tool_with_icon(
+                    current_tool,
+                    label_width,
+                    marquee_since,
+                    theme,
+                )),
         )
         // Next tool (sliding up from below)
         .child(
@@ -322,7 +729,7 @@ fn render_current_tool(
                 .items_center()
                 .overflow_hidden()
                 .opacity(next_opacity)
-                .child(render_tool_with_icon(next_tool, theme)),
+                .child(render_tool_with_icon(next_tool, label_width, None, theme)),
         )
 }
 
@@ -356,8 +763,8 @@ fn render_activity_text(text: &str, theme: &ThemeColors) -> Div {
             div()
                 .flex_1()
                 .min_w_0()
-                .font_family("Maple Mono NF CN")
-                .text_size(px(12.0))
+                .font_family(font::family())
+                .text_size(font::size(12.0))
                 .text_color(theme.text_secondary)
                 .whitespace_nowrap()
                 .text_ellipsis()
@@ -367,6 +774,50 @@ fn render_activity_text(text: &str, theme: &ThemeColors) -> Div {
 
 /// Icon width for consistent alignment
 const TOOL_ICON_WIDTH: f32 = 12.0;
+/// Gap between the tool icon and its label
+const TOOL_ICON_GAP: f32 = 6.0;
+/// Row padding on either side
+const ROW_PADDING_X: f32 = 14.0;
+/// Advance of one character of the monospace HUD font, in ems
+const CHAR_WIDTH_EM: f32 = 0.6;
+
+/// Estimated width of `text` set at `designed` px (scaled like [`font::size`])
+pub(crate) fn text_width(text: &str, designed: f32) -> f32 {
+    text.chars().count() as f32 * designed * font::scale() * CHAR_WIDTH_EM
+}
+
+/// Characters of text set at `designed` px that fit in `width`
+fn chars_fitting(width: f32, designed: f32) -> usize {
+    (width / (designed * font::scale() * CHAR_WIDTH_EM))
+        .floor()
+        .max(0.0) as usize
+}
+
+/// Width the event line leaves the tool label, after the plan progress
+pub(crate) fn tool_label_width(session: &SessionInfo) -> f32 {
+    let mut width =
+        COLUMN_WIDTH - ROW_PADDING_X * 2.0 - EVENT_PADDING_LEFT - TOOL_ICON_WIDTH - TOOL_ICON_GAP;
+    if session.progress.is_some() {
+        width -= 6.0 + PROGRESS_BAR_WIDTH;
+        if let Some(subtitle) = &session.subtitle {
+            width -= 4.0 + text_width(subtitle, 11.0).min(SUBTITLE_MAX_WIDTH);
+        }
+    }
+    width.max(0.0)
+}
+
+/// Keep both ends of `text` around a middle "…" so it's at most `max`
+/// characters, e.g. "cargo test -p aur…ry_tests"
+pub(crate) fn middle_ellipsis(text: &str, max: usize) -> String {
+    let count = text.chars().count();
+    if count <= max {
+        return text.to_string();
+    }
+    let keep = max.saturating_sub(1);
+    let head: String = text.chars().take(keep.div_ceil(2)).collect();
+    let tail: String = text.chars().skip(count - keep / 2).collect();
+    format!("{head}…{tail}")
+}
 
 /// Format the display text for a tool, handling MCP server prefixes and special cases.
 pub(crate) fn format_tool_display_text(tool_name: &str, tool_label: Option<&str>) -> String {
@@ -386,17 +837,46 @@ pub(crate) fn format_tool_display_text(tool_name: &str, tool_label: Option<&str>
     }
 }
 
+/// The untruncated command or path behind the tool the event line is
+/// showing, for option-click copying; `None` while it shows anything else
+pub(crate) fn copyable_tool_label(session: &SessionInfo, tool_index: usize) -> Option<String> {
+    let tools = &session.running_tools;
+    if tools.is_empty() || !session.subagents.is_empty() || session.is_reviewing_plan() {
+        return None;
+    }
+    let tool = &tools[tool_index % tools.len()];
+    tool.tool_full_label
+        .clone()
+        .or_else(|| tool.tool_label.clone())
+}
+
 /// Render a tool with its SVG icon (using theme colors)
-pub(crate) fn render_tool_with_icon(tool: &RunningTool, theme: &ThemeColors) -> Div {
-    let icon_path = icons::tool_icon_asset(&tool.tool_name);
+///
+/// A label wider than `label_width` scrolls while `marquee_since` is set,
+/// else ends in "…"; with `ui.marquee.enabled` off it's cut in the middle.
+pub(crate) fn render_tool_with_icon(
+    tool: &RunningTool,
+    label_width: f32,
+    marquee_since: Option<Instant>,
+    theme: &ThemeColors,
+) -> Div {
+    let icon_path = icons::tool_icon_asset(&tool.tool_name, tool.category);
     let display_text = format_tool_display_text(&tool.tool_name, tool.tool_label.as_deref());
+    let (display_text, offset) = if animation::marquee().enabled {
+        let overflow = text_width(&display_text, 12.0) - label_width;
+        let offset = marquee_since.map_or(0.0, |since| calculate_marquee_offset(since, overflow));
+        (display_text, offset)
+    } else {
+        let max = chars_fitting(label_width, 12.0);
+        (middle_ellipsis(&display_text, max), 0.0)
+    };
 
     div()
         .w_full() // Fill parent container width
         .flex()
         .flex_row()
         .items_center()
-        .gap(px(6.0)) // Per design spec: event gap = 6px
+        .gap(px(TOOL_ICON_GAP)) // Per design spec: event gap = 6px
         .overflow_hidden()
         .min_w_0()
         // Tool icon (SVG)
@@ -415,19 +895,24 @@ pub(crate) fn render_tool_with_icon(tool: &RunningTool, theme: &ThemeColors) ->
                         .text_color(theme.icon_tool),
                 ),
         )
-        // Tool label (italic per design spec, with ellipsis)
+        // Tool label (italic per design spec, with ellipsis unless scrolling)
         .child(
             div()
                 .flex_1()
                 .min_w_0()
                 .overflow_hidden()
-                .font_family("Maple Mono NF CN")
-                .text_size(px(12.0))
+                .font_family(font::family())
+                .text_size(font::size(12.0))
                 .italic()
                 .text_color(theme.text_secondary)
                 .whitespace_nowrap()
-                .text_ellipsis()
-                .child(display_text),
+                .map(|this| {
+                    if offset == 0.0 {
+                        this.text_ellipsis().child(display_text)
+                    } else {
+                        this.child(div().flex_none().ml(px(offset)).child(display_text))
+                    }
+                }),
         )
 }
 
@@ -440,12 +925,81 @@ pub(crate) fn extract_session_name(cwd: &str) -> String {
         .to_string()
 }
 
+/// What VoiceOver reads for a row, e.g. "invoice export, running, src/export.rs"
+pub(crate) fn accessibility_label(session: &SessionInfo) -> String {
+    let name = session
+        .name
+        .clone()
+        .unwrap_or_else(|| extract_session_name(&session.cwd));
+    let state = match session.state {
+        SessionState::Running => "running",
+        SessionState::Idle => "done",
+        SessionState::Attention => "needs attention",
+        SessionState::Waiting => "waiting for input",
+        SessionState::Compacting => "compacting",
+        SessionState::Stale => "stale",
+    };
+    let mut parts = vec![name, state.to_string()];
+    if let Some(tool) = session.running_tools.first() {
+        parts.push(format_tool_display_text(
+            &tool.tool_name,
+            tool.tool_label.as_deref(),
+        ));
+    }
+    if session.is_snoozed() {
+        parts.push("snoozed".to_string());
+    }
+    parts.join(", ")
+}
+
+/// Detail lines of a row's hover tooltip: full cwd, session id, agent,
+/// model and start time (whatever is known)
+pub(crate) fn tooltip_lines(session: &SessionInfo) -> Vec<String> {
+    let mut lines = Vec::new();
+    if !session.cwd.is_empty() {
+        lines.push(session.cwd.clone());
+    }
+    lines.push(format!("id {}", session.session_id));
+    if let Some(agent) = &session.agent {
+        lines.push(format!("agent {}", agent.display_name()));
+    }
+    if let Some(model) = &session.model {
+        lines.push(format!("model {model}"));
+    }
+    if let Some(started_at) = session.started_at {
+        lines.push(format!("started {}", format_datetime(started_at)));
+    }
+    lines
+}
+
+/// Row index after an arrow key moves the selection by `delta` among `count`
+/// rows; the first (down) or last (up) row when nothing was selected
+pub(crate) fn step_selection(current: Option<usize>, delta: isize, count: usize) -> Option<usize> {
+    let last = count.checked_sub(1)?;
+    Some(match current {
+        None if delta < 0 => last,
+        None => 0,
+        Some(index) => index.saturating_add_signed(delta).min(last),
+    })
+}
+
+/// Whether an Attention/Waiting row was acknowledged (`aura ack`, row click)
+/// and is shown without its urgency animation
+pub(crate) fn is_acknowledged(session: &SessionInfo) -> bool {
+    session.seen
+        && matches!(
+            session.state,
+            SessionState::Attention | SessionState::Waiting
+        )
+}
+
 /// Render state indicator with SVG icon and opacity
 ///
 /// Uses themed icon color with varying opacity based on state urgency.
+/// Acknowledged sessions keep their icon but stop shaking/rotating.
 /// On hover, swaps to remove (X) icon with slide animation.
 fn render_state_indicator(
-    state: SessionState,
+    session: &SessionInfo,
     animation_start: Instant,
     state_opacity: f32,
     state_x: f32,
@@ -453,21 +1007,21 @@ fn render_state_indicator(
     remove_x: f32,
     theme: &ThemeColors,
 ) -> Div {
+    let state = session.state;
     let icon_path = icons::state_icon_path(state);
     let base_opacity = state_to_opacity(state);
+    let animate = !is_acknowledged(session);
 
     // Calculate shake offset for Attention state
-    let shake_offset = if state == SessionState::Attention {
+    let shake_offset = if animate && state == SessionState::Attention {
         calculate_shake_offset(animation_start)
     } else {
         0.0
     };
 
     // Calculate rotation for Waiting state (2 second full rotation, counter-clockwise)
-    let rotation_radians = if state == SessionState::Waiting {
-        let elapsed_ms = animation_start.elapsed().as_millis() as f32;
-        let rotation_period_ms = 2000.0;
-        -((elapsed_ms / rotation_period_ms) * std::f32::consts::TAU) // Negative for counter-clockwise
+    let rotation_radians = if animate && state == SessionState::Waiting {
+        calculate_spin_radians(animation_start)
     } else {
         0.0
     };
@@ -475,7 +1029,7 @@ fn render_state_indicator(
     // Themed icon with state-based opacity
     let state_icon_color = Hsla {
         a: base_opacity * state_opacity,
-        ..theme.icon_state
+        ..icons::state_icon_color(state, theme)
     };
 
     let remove_icon_color = Hsla {
@@ -547,14 +1101,413 @@ pub(crate) fn state_to_opacity(state: SessionState) -> f32 {
     }
 }
 
+/// Width of the hover scrollbar thumb
+pub const SCROLLBAR_WIDTH: f32 = 3.0;
+
+/// Minimum scrollbar thumb height so it stays grabbable by eye
+const SCROLLBAR_MIN_THUMB: f32 = 16.0;
+
+/// Scrollbar thumb geometry: `(top, height)` within a track of `viewport` height.
+///
+/// `offset` is the scroll distance from the top (positive). Returns `None`
+/// when the content fits and no scrollbar is needed.
+pub(crate) fn scrollbar_thumb(viewport: f32, content: f32, offset: f32) -> Option<(f32, f32)> {
+    if viewport <= 0.0 || content <= viewport {
+        return None;
+    }
+    let height = (viewport * viewport / content).max(SCROLLBAR_MIN_THUMB);
+    let max_offset = content - viewport;
+    let fraction = (offset / max_offset).clamp(0.0, 1.0);
+    Some((fraction * (viewport - height), height))
+}
+
+/// New index of the scroll anchor row, if a refresh moved it.
+///
+/// `anchor` is the session id that was the top visible row and the index it
+/// had then. Returns `Some(new_index)` when that session is still present at a
+/// different index, so the list can scroll to keep it in place. Anchoring only
+/// applies once the user has scrolled (index > 0); at the top, new sessions
+/// should push in from above.
+pub(crate) fn reanchor_index<'a>(
+    anchor: Option<&(String, usize)>,
+    ids: impl IntoIterator<Item = &'a str>,
+) -> Option<usize> {
+    let (anchor_id, old_index) = anchor?;
+    if *old_index == 0 {
+        return None;
+    }
+    let new_index = ids.into_iter().position(|id| id == anchor_id)?;
+    (new_index != *old_index).then_some(new_index)
+}
+
+/// Label for a bulk action in the header menu
+pub(crate) fn bulk_action_label(action: BulkAction) -> &'static str {
+    match action {
+        BulkAction::ClearIdle => "Clear idle",
+        BulkAction::ClearStale => "Clear stale",
+        BulkAction::MarkAllSeen => "Mark all seen",
+    }
+}
+
+/// Widest the tags next to a session name get before truncating
+const TAGS_MAX_WIDTH: f32 = 96.0;
+/// Widest the model badge gets
+const MODEL_MAX_WIDTH: f32 = 72.0;
+/// Widest the plan progress next to the current tool gets
+const SUBTITLE_MAX_WIDTH: f32 = 120.0;
+/// Mini progress bar before the plan progress
+const PROGRESS_BAR_WIDTH: f32 = 24.0;
+
+/// Every tag used by `sessions`, sorted
+pub(crate) fn all_tags(sessions: &[SessionInfo]) -> Vec<String> {
+    let tags: std::collections::BTreeSet<&String> =
+        sessions.iter().flat_map(|s| s.tags.iter()).collect();
+    tags.into_iter().cloned().collect()
+}
+
+/// Sessions carrying `tag` (all of them without a filter)
+pub(crate) fn filter_by_tag(sessions: &[SessionInfo], tag: Option<&str>) -> Vec<SessionInfo> {
+    sessions
+        .iter()
+        .filter(|s| has_tag(s, tag))
+        .cloned()
+        .collect()
+}
+
+fn has_tag(session: &SessionInfo, tag: Option<&str>) -> bool {
+    tag.is_none_or(|tag| session.tags.iter().any(|t| t == tag))
+}
+
+/// Header summary order, most urgent first
+const SUMMARY_STATES: [SessionState; 6] = [
+    SessionState::Attention,
+    SessionState::Waiting,
+    SessionState::Running,
+    SessionState::Compacting,
+    SessionState::Idle,
+    SessionState::Stale,
+];
+
+/// Header segments, e.g. `[(Running, "2 running"), (Idle, "1 idle")]` for the
+/// sessions carrying `tag` (all with `None`); states without sessions are
+/// left out
+pub(crate) fn state_summary(
+    sessions: &[SessionInfo],
+    tag: Option<&str>,
+) -> Vec<(SessionState, String)> {
+    SUMMARY_STATES
+        .into_iter()
+        .filter_map(|state| {
+            let count = sessions
+                .iter()
+                .filter(|s| s.state == state && has_tag(s, tag))
+                .count();
+            (count > 0).then(|| (state, format!("{count} {}", summary_label(state))))
+        })
+        .collect()
+}
+
+fn summary_label(state: SessionState) -> &'static str {
+    match state {
+        SessionState::Running => "running",
+        SessionState::Idle => "idle",
+        SessionState::Attention => "attention",
+        SessionState::Waiting => "waiting",
+        SessionState::Compacting => "compacting",
+        SessionState::Stale => "stale",
+    }
+}
+
+/// Header menu label for a tag filter item (checked while active)
+pub(crate) fn tag_filter_label(tag: &str, active: bool) -> String {
+    format!("{} #{tag}", if active { "✓" } else { " " })
+}
+
+/// Header text, e.g. "2 running · 1 idle" or "1 waiting · #billing"
+pub(crate) fn header_text(sessions: &[SessionInfo], tag_filter: Option<&str>) -> String {
+    let mut parts: Vec<String> = state_summary(sessions, tag_filter)
+        .into_iter()
+        .map(|(_, text)| text)
+        .collect();
+    if parts.is_empty() {
+        parts.push("0 sessions".to_string());
+    }
+    if let Some(tag) = tag_filter {
+        parts.push(format!("#{tag}"));
+    }
+    parts.join(" · ")
+}
+
+/// What the inline edit field in a row is changing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum InlineField {
+    #[default]
+    Name,
+    /// Space-separated tags; saving replaces the session's tags
+    Tags,
+}
+
+/// Actions in a session row's right-click menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RowAction {
+    Rename,
+    EditTags,
+    CopyId,
+    CopyLink,
+    OpenInFinder,
+    OpenInTerminal,
+    OpenInEditor,
+    ViewTranscript,
+    RevealTranscript,
+    Archive,
+}
+
+impl RowAction {
+    pub(crate) const ALL: [RowAction; 10] = [
+        RowAction::Rename,
+        RowAction::EditTags,
+        RowAction::CopyId,
+        RowAction::CopyLink,
+        RowAction::OpenInFinder,
+        RowAction::OpenInTerminal,
+        RowAction::OpenInEditor,
+        RowAction::ViewTranscript,
+        RowAction::RevealTranscript,
+        RowAction::Archive,
+    ];
+
+    /// Whether the action applies to this session
+    pub(crate) fn is_enabled(self, session: &SessionInfo) -> bool {
+        match self {
+            RowAction::ViewTranscript | RowAction::RevealTranscript => {
+                session.transcript_path.is_some()
+            }
+            // Privacy mode hides the working directory
+            RowAction::OpenInFinder | RowAction::OpenInTerminal | RowAction::OpenInEditor => {
+                !session.cwd.is_empty()
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Files listed at most in the row menu's conflict section
+const CONFLICT_MENU_MAX_FILES: usize = 4;
+
+/// Informational lines heading the row menu: the change summary, then (when
+/// other sessions modified the same files) a caption, file names, and "+N more"
+pub(crate) fn menu_info_lines(session: &SessionInfo) -> Vec<String> {
+    let mut lines: Vec<String> = session.change_summary().into_iter().collect();
+    let files = &session.file_conflicts;
+    if files.is_empty() {
+        return lines;
+    }
+    lines.push("Shared with others:".to_string());
+    lines.extend(
+        files
+            .iter()
+            .take(CONFLICT_MENU_MAX_FILES)
+            .map(|path| crate::agents::truncate(&crate::agents::short_path(path), 20).to_string()),
+    );
+    if files.len() > CONFLICT_MENU_MAX_FILES {
+        lines.push(format!("+{} more", files.len() - CONFLICT_MENU_MAX_FILES));
+    }
+    lines
+}
+
+/// Label for a row action in the context menu
+pub(crate) fn row_action_label(action: RowAction) -> &'static str {
+    match action {
+        RowAction::Rename => "Rename",
+        RowAction::EditTags => "Edit tags",
+        RowAction::CopyId => "Copy session id",
+        RowAction::CopyLink => "Copy link",
+        RowAction::OpenInFinder => "Open in Finder",
+        RowAction::OpenInTerminal => "Open in terminal",
+        RowAction::OpenInEditor => "Open in editor",
+        RowAction::ViewTranscript => "View transcript",
+        RowAction::RevealTranscript => "Reveal transcript",
+        RowAction::Archive => "Archive",
+    }
+}
+
+/// Outcome of a key press while renaming a session inline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RenameKey {
+    /// Buffer edited (or key ignored); keep editing
+    Edit,
+    /// Enter: save the buffer
+    Commit,
+    /// Escape: discard the buffer
+    Cancel,
+}
+
+/// Apply a key press to the inline rename buffer
+///
+/// `key` is gpui's key name (`"backspace"`, `"enter"`, …) and `key_char` the
+/// text it produces, if any.
+pub(crate) fn apply_rename_key(
+    buffer: &mut String,
+    key: &str,
+    key_char: Option<&str>,
+) -> RenameKey {
+    match key {
+        "enter" => RenameKey::Commit,
+        "escape" => RenameKey::Cancel,
+        "backspace" => {
+            buffer.pop();
+            RenameKey::Edit
+        }
+        _ => {
+            if let Some(text) = key_char.filter(|t| !t.chars().any(char::is_control)) {
+                buffer.push_str(text);
+            }
+            RenameKey::Edit
+        }
+    }
+}
+
+/// First line of pasted text, for the single-line rename field
+pub(crate) fn single_line(text: &str) -> &str {
+    text.lines().next().unwrap_or_default()
+}
+
 /// Header bar height (28px per prototype)
 pub const HEADER_HEIGHT: f32 = 28.0;
 
+/// Most columns of the grid layout
+pub(crate) const MAX_GRID_COLUMNS: usize = 3;
+
+/// Width of one session column (the window's 10px padding on either side)
+pub(crate) const COLUMN_WIDTH: f32 = WIDTH - 20.0;
+
+/// Columns for `session_count` sessions
+///
+/// The list layout always has one; the grid keeps itself about as wide as
+/// tall (1 session: 1, 2-4: 2, 5 or more: [`MAX_GRID_COLUMNS`]).
+pub(crate) fn grid_columns(layout: Layout, session_count: usize) -> usize {
+    match layout {
+        Layout::List => 1,
+        Layout::Grid => (1..MAX_GRID_COLUMNS)
+            .find(|columns| columns * columns >= session_count)
+            .unwrap_or(MAX_GRID_COLUMNS),
+    }
+}
+
+/// Expanded window width for `columns` session columns
+pub(crate) fn expanded_width(columns: usize) -> f32 {
+    WIDTH + (COLUMN_WIDTH + ROW_GAP) * columns.saturating_sub(1) as f32
+}
+
 /// Calculate expanded window height based on session count
-pub(crate) fn calculate_expanded_height(session_count: usize) -> f32 {
-    let count = session_count.min(MAX_SESSIONS);
-    // Header (28px) + rows + container padding (10px top + 10px bottom)
-    HEADER_HEIGHT + (ROW_HEIGHT + ROW_GAP) * count as f32 + 20.0
+///
+/// At most `max_visible` sessions are shown, `columns` per row; beyond that
+/// the overflow footer is added.
+pub(crate) fn calculate_expanded_height(
+    session_count: usize,
+    max_visible: usize,
+    columns: usize,
+) -> f32 {
+    let count = session_count.min(max_visible).div_ceil(columns.max(1));
+    let footer = if overflow_count(session_count, max_visible) > 0 {
+        FOOTER_HEIGHT
+    } else {
+        0.0
+    };
+    // Header (28px) + rows + container padding (10px top + 10px bottom) + footer
+    HEADER_HEIGHT + (row_height() + ROW_GAP) * count as f32 + 20.0 + footer
+}
+
+/// Number of sessions that don't fit in `max_visible` rows
+pub(crate) fn overflow_count(session_count: usize, max_visible: usize) -> usize {
+    session_count.saturating_sub(max_visible)
+}
+
+/// Footer label: "+4 more…" when collapsed, "Show less" once expanded
+pub(crate) fn overflow_footer_text(hidden: usize, show_all: bool) -> String {
+    if show_all {
+        "Show less".to_string()
+    } else {
+        format!("+{hidden} more…")
+    }
+}
+
+/// Render the overflow footer row below the session rows
+///
+/// The click handler is attached in mod.rs (needs `cx.listener()`).
+pub(crate) fn render_overflow_footer(text: String, theme: &ThemeColors) -> Div {
+    div()
+        .w_full()
+        .h(px(FOOTER_HEIGHT))
+        .flex()
+        .items_center()
+        .justify_center()
+        .font_family(font::family())
+        .text_size(font::size(11.0))
+        .text_color(theme.text_secondary)
+        .hover(|style| style.text_color(theme.text_primary))
+        .child(text)
+}
+
+/// Height of the "Ended recently" row, plus a line per ended session once
+/// expanded; nothing without ended sessions
+pub(crate) fn graveyard_height(ended: usize, expanded: bool) -> f32 {
+    match (ended, expanded) {
+        (0, _) => 0.0,
+        (_, false) => FOOTER_HEIGHT,
+        (count, true) => FOOTER_HEIGHT + ENDED_ROW_HEIGHT * font::scale() * count as f32,
+    }
+}
+
+/// "Ended recently (3)" when collapsed, "Hide ended (3)" once expanded
+pub(crate) fn graveyard_header_text(count: usize, expanded: bool) -> String {
+    if expanded {
+        format!("Hide ended ({count})")
+    } else {
+        format!("Ended recently ({count})")
+    }
+}
+
+/// An ended session's name, e.g. "fix login" (or its folder)
+pub(crate) fn ended_name(session: &SessionInfo) -> String {
+    session
+        .name
+        .clone()
+        .unwrap_or_else(|| extract_session_name(&session.cwd))
+}
+
+/// Render the collapsible "Ended recently" row below the session rows
+///
+/// The click handler is attached in mod.rs (needs `cx.listener()`).
+pub(crate) fn render_graveyard_header(text: String, theme: &ThemeColors) -> Div {
+    render_overflow_footer(text, theme)
+        .border_t_1()
+        .border_color(theme.content_highlight)
+}
+
+/// Render one ended session: its name and how long ago it ended, dimmed
+pub(crate) fn render_ended_row(session: &SessionInfo, ago: Duration, theme: &ThemeColors) -> Div {
+    div()
+        .w_full()
+        .h(px(ENDED_ROW_HEIGHT * font::scale()))
+        .px(px(16.0))
+        .flex()
+        .items_center()
+        .gap(px(HEADER_GAP))
+        .font_family(font::family())
+        .text_size(font::size(11.0))
+        .text_color(theme.text_secondary)
+        .opacity(state_to_opacity(SessionState::Stale))
+        .child(
+            div()
+                .flex_1()
+                .min_w_0()
+                .overflow_hidden()
+                .whitespace_nowrap()
+                .text_ellipsis()
+                .child(ended_name(session)),
+        )
+        .child(div().flex_shrink_0().child(crate::health::format_ago(ago)))
 }
 
 #[cfg(test)]
@@ -567,12 +1520,7 @@ mod tests {
             session_id: "test-session".into(),
             cwd: "/home/user/project".into(),
             state,
-            running_tools: vec![],
-            name: None,
-            stopped_at: None,
-            stale_at: None,
-            permission_tool: None,
-            recent_activity: vec![],
+            ..Default::default()
         }
     }
 
@@ -614,6 +1562,53 @@ mod tests {
         assert!(text.starts_with("waiting since "), "got: {}", text);
     }
 
+    #[test]
+    fn last_activity_is_relative_to_now() {
+        let mut session = make_session(SessionState::Idle);
+        assert_eq!(last_activity_label(&session, 1_000), None);
+        session.last_activity_at = Some(1_000 - 130);
+        assert_eq!(
+            last_activity_label(&session, 1_000).as_deref(),
+            Some("2m ago")
+        );
+        // A clock running behind the registry's reads as just now
+        session.last_activity_at = Some(1_010);
+        assert_eq!(
+            last_activity_label(&session, 1_000).as_deref(),
+            Some("just now")
+        );
+    }
+
+    #[test]
+    fn state_age_escalates_while_waiting_on_the_user() {
+        let thresholds = StateAgeThresholds {
+            warn: Some(Duration::from_secs(5 * 60)),
+            alert: Some(Duration::from_secs(15 * 60)),
+        };
+        let mut session = make_session(SessionState::Waiting);
+        let level = |session: &SessionInfo, minutes: u64| {
+            state_age_level(session, 10_000 + minutes * 60, thresholds)
+        };
+        assert_eq!(level(&session, 60), StateAgeLevel::Normal);
+
+        session.state_changed_at = Some(10_000);
+        assert_eq!(level(&session, 4), StateAgeLevel::Normal);
+        assert_eq!(level(&session, 5), StateAgeLevel::Warn);
+        assert_eq!(level(&session, 15), StateAgeLevel::Alert);
+
+        let off = StateAgeThresholds::default();
+        assert_eq!(
+            state_age_level(&session, 10_000 + 3600, off),
+            StateAgeLevel::Normal
+        );
+
+        session.seen = true;
+        assert_eq!(level(&session, 15), StateAgeLevel::Normal);
+        let mut running = make_session(SessionState::Running);
+        running.state_changed_at = Some(10_000);
+        assert_eq!(level(&running, 60), StateAgeLevel::Normal);
+    }
+
     #[test]
     fn placeholder_idle_without_timestamp() {
         let session = make_session(SessionState::Idle);
@@ -635,6 +1630,14 @@ mod tests {
         assert_eq!(get_placeholder_text(&session), "Read needs permission");
     }
 
+    #[test]
+    fn placeholder_reviewing_plan() {
+        let mut session = make_session(SessionState::Attention);
+        session.permission_tool = Some("ExitPlanMode".into());
+        assert_eq!(get_placeholder_text(&session), "reviewing plan");
+        assert_eq!(get_placeholder_icon(&session), "icons/book-open.svg");
+    }
+
     #[test]
     fn placeholder_waiting() {
         let session = make_session(SessionState::Waiting);
@@ -665,6 +1668,117 @@ mod tests {
         assert!(crate::PLACEHOLDER_TEXTS.contains(&b));
     }
 
+    #[test]
+    fn acknowledged_only_for_seen_attention_and_waiting() {
+        let session = |state, seen| SessionInfo {
+            state,
+            seen,
+            ..Default::default()
+        };
+        assert!(is_acknowledged(&session(SessionState::Attention, true)));
+        assert!(is_acknowledged(&session(SessionState::Waiting, true)));
+        assert!(!is_acknowledged(&session(SessionState::Attention, false)));
+        assert!(!is_acknowledged(&session(SessionState::Idle, true)));
+    }
+
+    #[test]
+    fn accessibility_label_reads_name_state_and_tool() {
+        let mut session = make_session(SessionState::Running);
+        assert_eq!(accessibility_label(&session), "project, running");
+        session.name = Some("invoice export".into());
+        session.running_tools = vec![RunningTool {
+            tool_id: "t1".into(),
+            tool_name: "Read".into(),
+            tool_label: Some("main.rs".into()),
+            tool_full_label: None,
+            category: crate::tools::ToolCategory::Read,
+        }];
+        session.snoozed_until = Some(u64::MAX);
+        assert_eq!(
+            accessibility_label(&session),
+            "invoice export, running, main.rs, snoozed"
+        );
+    }
+
+    #[test]
+    fn middle_ellipsis_keeps_both_ends() {
+        assert_eq!(middle_ellipsis("cargo test", 10), "cargo test");
+        assert_eq!(middle_ellipsis("cargo test --workspace", 11), "cargo…space");
+        assert_eq!(middle_ellipsis("src/ui/session_list.rs", 2), "s…");
+        assert_eq!(middle_ellipsis("abc", 0), "…");
+    }
+
+    #[test]
+    fn tool_label_width_leaves_room_for_progress() {
+        let mut session = make_session(SessionState::Running);
+        let full = tool_label_width(&session);
+        assert_eq!(full, COLUMN_WIDTH - 28.0 - 24.0 - 12.0 - 6.0);
+        session.progress = Some(PlanProgress {
+            completed: 1,
+            total: 3,
+            current: None,
+        });
+        assert_eq!(tool_label_width(&session), full - 6.0 - PROGRESS_BAR_WIDTH);
+        assert!(text_width("cargo test", 12.0) > 0.0);
+    }
+
+    #[test]
+    fn copyable_tool_label_prefers_the_full_value() {
+        let mut session = make_session(SessionState::Running);
+        assert_eq!(copyable_tool_label(&session, 0), None);
+        let tool = |id: &str, label: &str, full: Option<&str>| RunningTool {
+            tool_id: id.into(),
+            tool_name: "Bash".into(),
+            tool_label: Some(label.into()),
+            tool_full_label: full.map(String::from),
+            category: crate::tools::ToolCategory::Shell,
+        };
+        session.running_tools = vec![
+            tool("t1", "cargo test --wor…", Some("cargo test --workspace")),
+            tool("t2", "npm test", None),
+        ];
+        assert_eq!(
+            copyable_tool_label(&session, 0).as_deref(),
+            Some("cargo test --workspace")
+        );
+        assert_eq!(
+            copyable_tool_label(&session, 3).as_deref(),
+            Some("npm test")
+        );
+    }
+
+    #[test]
+    fn tooltip_lists_what_the_row_truncates() {
+        let mut session = make_session(SessionState::Running);
+        session.session_id = "3f2a1b9c-0000".into();
+        session.cwd = "/Users/me/code/very/deep/project".into();
+        assert_eq!(
+            tooltip_lines(&session),
+            ["/Users/me/code/very/deep/project", "id 3f2a1b9c-0000"]
+        );
+
+        session.agent = Some(crate::AgentType::Codex);
+        session.model = Some("gpt-5-codex".into());
+        session.started_at = Some(0);
+        let lines = tooltip_lines(&session);
+        assert_eq!(lines[2..4], ["agent Codex", "model gpt-5-codex"]);
+        assert_eq!(lines[4], format!("started {}", format_datetime(0)));
+        // Privacy mode clears the cwd
+        session.cwd.clear();
+        assert_eq!(tooltip_lines(&session)[0], "id 3f2a1b9c-0000");
+    }
+
+    #[test]
+    fn step_selection_starts_at_the_ends_and_clamps() {
+        assert_eq!(step_selection(None, 1, 0), None);
+        assert_eq!(step_selection(None, 1, 4), Some(0));
+        assert_eq!(step_selection(None, -1, 4), Some(3));
+        assert_eq!(step_selection(Some(1), 2, 4), Some(3));
+        assert_eq!(step_selection(Some(3), 3, 4), Some(3));
+        assert_eq!(step_selection(Some(1), -3, 4), Some(0));
+        assert_eq!(step_selection(Some(6), 0, 4), Some(3));
+    }
+
     // --- state_to_opacity tests ---
 
     #[test]
@@ -720,19 +1834,339 @@ mod tests {
     #[test]
     fn expanded_height_one_session() {
         let expected = HEADER_HEIGHT + (ROW_HEIGHT + ROW_GAP) * 1.0 + 20.0;
-        assert_eq!(calculate_expanded_height(1), expected);
+        assert_eq!(calculate_expanded_height(1, 5, 1), expected);
     }
 
     #[test]
     fn expanded_height_max_sessions() {
         let expected = HEADER_HEIGHT + (ROW_HEIGHT + ROW_GAP) * 5.0 + 20.0;
-        assert_eq!(calculate_expanded_height(5), expected);
+        assert_eq!(calculate_expanded_height(5, 5, 1), expected);
     }
 
     #[test]
     fn expanded_height_capped() {
-        // 10 sessions should be capped at MAX_SESSIONS (5)
-        assert_eq!(calculate_expanded_height(10), calculate_expanded_height(5));
+        // 10 sessions with a cap of 5: five rows plus the overflow footer
+        assert_eq!(
+            calculate_expanded_height(10, 5, 1),
+            calculate_expanded_height(5, 5, 1) + FOOTER_HEIGHT
+        );
+        assert_eq!(
+            calculate_expanded_height(10, 5, 1),
+            calculate_expanded_height(6, 5, 1)
+        );
+    }
+
+    #[test]
+    fn expanded_height_respects_configured_cap() {
+        let expected = HEADER_HEIGHT + (ROW_HEIGHT + ROW_GAP) * 3.0 + 20.0 + FOOTER_HEIGHT;
+        assert_eq!(calculate_expanded_height(7, 3, 1), expected);
+    }
+
+    #[test]
+    fn grid_adds_columns_as_sessions_accumulate() {
+        assert_eq!(grid_columns(Layout::List, 9), 1);
+        assert_eq!(grid_columns(Layout::Grid, 0), 1);
+        assert_eq!(grid_columns(Layout::Grid, 1), 1);
+        assert_eq!(grid_columns(Layout::Grid, 2), 2);
+        assert_eq!(grid_columns(Layout::Grid, 4), 2);
+        assert_eq!(grid_columns(Layout::Grid, 5), 3);
+        assert_eq!(grid_columns(Layout::Grid, 40), MAX_GRID_COLUMNS);
+
+        assert_eq!(expanded_width(1), WIDTH);
+        assert_eq!(expanded_width(2), WIDTH + COLUMN_WIDTH + ROW_GAP);
+        // 5 sessions in 3 columns take two rows
+        assert_eq!(
+            calculate_expanded_height(5, 5, 3),
+            calculate_expanded_height(2, 5, 1)
+        );
+    }
+
+    // --- overflow footer tests ---
+
+    #[test]
+    fn overflow_count_beyond_cap() {
+        assert_eq!(overflow_count(3, 5), 0);
+        assert_eq!(overflow_count(5, 5), 0);
+        assert_eq!(overflow_count(9, 5), 4);
+    }
+
+    #[test]
+    fn overflow_footer_text_toggles() {
+        assert_eq!(overflow_footer_text(4, false), "+4 more…");
+        assert_eq!(overflow_footer_text(4, true), "Show less");
+    }
+
+    // --- scrollbar_thumb tests ---
+
+    #[test]
+    fn graveyard_grows_when_expanded() {
+        assert_eq!(graveyard_height(0, true), 0.0);
+        assert_eq!(graveyard_height(3, false), FOOTER_HEIGHT);
+        assert_eq!(
+            graveyard_height(3, true),
+            FOOTER_HEIGHT + 3.0 * ENDED_ROW_HEIGHT
+        );
+        assert_eq!(graveyard_header_text(3, false), "Ended recently (3)");
+        assert_eq!(graveyard_header_text(3, true), "Hide ended (3)");
+
+        let mut session = make_session(SessionState::Idle);
+        session.cwd = "/code/aura".into();
+        assert_eq!(ended_name(&session), "aura");
+        session.name = Some("fix login".into());
+        assert_eq!(ended_name(&session), "fix login");
+    }
+
+    #[test]
+    fn scrollbar_hidden_when_content_fits() {
+        assert_eq!(scrollbar_thumb(300.0, 300.0, 0.0), None);
+        assert_eq!(scrollbar_thumb(300.0, 120.0, 0.0), None);
+    }
+
+    #[test]
+    fn scrollbar_thumb_tracks_offset() {
+        // Half the content visible: thumb is half the track
+        assert_eq!(scrollbar_thumb(300.0, 600.0, 0.0), Some((0.0, 150.0)));
+        assert_eq!(scrollbar_thumb(300.0, 600.0, 300.0), Some((150.0, 150.0)));
+    }
+
+    #[test]
+    fn scrollbar_thumb_has_minimum_height() {
+        let (_, height) = scrollbar_thumb(100.0, 100_000.0, 0.0).unwrap();
+        assert_eq!(height, SCROLLBAR_MIN_THUMB);
+    }
+
+    // --- reanchor_index tests ---
+
+    #[test]
+    fn reanchor_follows_session_after_insert_above() {
+        let anchor = ("b".to_string(), 1);
+        assert_eq!(reanchor_index(Some(&anchor), ["new", "a", "b"]), Some(2));
+    }
+
+    #[test]
+    fn reanchor_noop_when_unmoved_or_missing() {
+        let anchor = ("b".to_string(), 1);
+        assert_eq!(reanchor_index(Some(&anchor), ["a", "b"]), None);
+        assert_eq!(reanchor_index(Some(&anchor), ["a", "c"]), None);
+        assert_eq!(reanchor_index(None, ["a", "b"]), None);
+    }
+
+    #[test]
+    fn reanchor_skipped_at_top() {
+        let anchor = ("a".to_string(), 0);
+        assert_eq!(reanchor_index(Some(&anchor), ["new", "a"]), None);
+    }
+
+    // --- bulk_action_label tests ---
+
+    #[test]
+    fn bulk_action_labels_are_distinct() {
+        let labels: std::collections::HashSet<_> = BulkAction::ALL
+            .iter()
+            .map(|a| bulk_action_label(*a))
+            .collect();
+        assert_eq!(labels.len(), BulkAction::ALL.len());
+    }
+
+    #[test]
+    fn row_action_labels_are_distinct() {
+        let labels: std::collections::HashSet<_> = RowAction::ALL
+            .iter()
+            .map(|a| row_action_label(*a))
+            .collect();
+        assert_eq!(labels.len(), RowAction::ALL.len());
+    }
+
+    // --- tag filter tests ---
+
+    #[test]
+    fn tag_filter_narrows_sessions() {
+        let mut billing = make_session(SessionState::Running);
+        billing.session_id = "a".into();
+        billing.tags = vec!["billing".into(), "q3".into()];
+        let mut other = make_session(SessionState::Idle);
+        other.session_id = "b".into();
+        other.tags = vec!["docs".into()];
+        let sessions = vec![billing, other];
+
+        assert_eq!(all_tags(&sessions), vec!["billing", "docs", "q3"]);
+        assert_eq!(filter_by_tag(&sessions, None).len(), 2);
+        let filtered = filter_by_tag(&sessions, Some("billing"));
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].session_id, "a");
+    }
+
+    #[test]
+    fn header_text_shows_filter() {
+        let mut tagged = make_session(SessionState::Waiting);
+        tagged.tags = vec!["billing".into()];
+        let sessions = vec![
+            make_session(SessionState::Idle),
+            make_session(SessionState::Running),
+            tagged,
+            make_session(SessionState::Running),
+        ];
+        assert_eq!(header_text(&[], None), "0 sessions");
+        assert_eq!(
+            header_text(&sessions, None),
+            "1 waiting · 2 running · 1 idle"
+        );
+        assert_eq!(
+            header_text(&sessions, Some("billing")),
+            "1 waiting · #billing"
+        );
+        assert_eq!(
+            state_summary(&sessions, None)[1],
+            (SessionState::Running, "2 running".to_string())
+        );
+    }
+
+    #[test]
+    fn transcript_actions_need_a_path() {
+        let mut session = make_session(SessionState::Running);
+        assert!(!RowAction::ViewTranscript.is_enabled(&session));
+        assert!(!RowAction::RevealTranscript.is_enabled(&session));
+        assert!(RowAction::Archive.is_enabled(&session));
+
+        session.transcript_path = Some("/tmp/t.jsonl".into());
+        assert!(RowAction::ViewTranscript.is_enabled(&session));
+        assert!(RowAction::RevealTranscript.is_enabled(&session));
+    }
+
+    #[test]
+    fn open_actions_need_a_cwd() {
+        let session = make_session(SessionState::Running);
+        assert!(RowAction::OpenInFinder.is_enabled(&session));
+        let redacted = crate::privacy::redact(&session);
+        assert!(!RowAction::OpenInFinder.is_enabled(&redacted));
+        assert!(!RowAction::OpenInEditor.is_enabled(&redacted));
+        assert!(RowAction::Rename.is_enabled(&redacted));
+    }
+
+    #[test]
+    fn menu_info_lists_changes_and_overlapping_files() {
+        let mut session = make_session(SessionState::Running);
+        assert!(menu_info_lines(&session).is_empty());
+
+        session.changed_files = vec![crate::FileChange {
+            path: "/code/src/f1.rs".into(),
+            added: 3,
+            removed: 1,
+        }];
+        assert_eq!(menu_info_lines(&session), vec!["1 file, +3/−1"]);
+
+        session.file_conflicts = (1..=6).map(|i| format!("/code/src/f{i}.rs")).collect();
+        assert_eq!(
+            menu_info_lines(&session),
+            vec![
+                "1 file, +3/−1",
+                "Shared with others:",
+                "f1.rs",
+                "f2.rs",
+                "f3.rs",
+                "f4.rs",
+                "+2 more"
+            ]
+        );
+    }
+
+    #[test]
+    fn rename_keys_edit_commit_and_cancel() {
+        let mut buffer = "fix".to_string();
+        assert_eq!(
+            apply_rename_key(&mut buffer, "space", Some(" ")),
+            RenameKey::Edit
+        );
+        assert_eq!(
+            apply_rename_key(&mut buffer, "l", Some("l")),
+            RenameKey::Edit
+        );
+        assert_eq!(buffer, "fix l");
+        assert_eq!(
+            apply_rename_key(&mut buffer, "backspace", None),
+            RenameKey::Edit
+        );
+        assert_eq!(buffer, "fix ");
+        assert_eq!(
+            apply_rename_key(&mut buffer, "tab", Some("\t")),
+            RenameKey::Edit
+        );
+        assert_eq!(buffer, "fix ");
+        assert_eq!(
+            apply_rename_key(&mut buffer, "enter", None),
+            RenameKey::Commit
+        );
+        assert_eq!(
+            apply_rename_key(&mut buffer, "escape", None),
+            RenameKey::Cancel
+        );
+    }
+
+    #[test]
+    fn single_line_keeps_first_line() {
+        assert_eq!(single_line("fix login\nsecond"), "fix login");
+        assert_eq!(single_line(""), "");
+    }
+
+    // --- snooze_icon_opacity tests ---
+
+    #[test]
+    fn snooze_icon_visible_while_snoozed() {
+        assert_eq!(snooze_icon_opacity(true, 0.0), 0.8);
+    }
+
+    #[test]
+    fn snooze_icon_hidden_without_hover() {
+        assert_eq!(snooze_icon_opacity(false, 0.0), 0.0);
+        assert!(snooze_icon_opacity(false, 1.0) > 0.0);
+    }
+
+    #[test]
+    fn subagent_summary_groups_agents() {
+        let mut session = make_session(SessionState::Running);
+        session.subagents = vec![
+            crate::SubagentInfo {
+                agent_id: "a1".into(),
+                agent_type: Some("Explore".into()),
+                running_tools: vec![RunningTool {
+                    tool_id: "t1".into(),
+                    tool_name: "Read".into(),
+                    tool_label: Some("login.rs".into()),
+                    tool_full_label: None,
+                    category: crate::tools::ToolCategory::Read,
+                }],
+            },
+            crate::SubagentInfo {
+                agent_id: "a2".into(),
+                agent_type: None,
+                running_tools: Vec::new(),
+            },
+        ];
+        assert_eq!(subagent_summary(&session), "Explore: login.rs · agent");
+    }
+
+    #[test]
+    fn queued_badge_label() {
+        assert_eq!(queued_label(2), "+2 queued");
+    }
+
+    #[test]
+    fn short_model_names() {
+        assert_eq!(short_model_name("claude-sonnet-4-5-20250929"), "sonnet-4-5");
+        assert_eq!(short_model_name("claude-opus-4-1"), "opus-4-1");
+        assert_eq!(short_model_name("gpt-5-codex"), "gpt-5-codex");
+    }
+
+    #[test]
+    fn progress_fraction_of_plan() {
+        let progress = |completed, total| PlanProgress {
+            completed,
+            total,
+            current: None,
+        };
+        assert_eq!(progress_fraction(&progress(0, 0)), 0.0);
+        assert_eq!(progress_fraction(&progress(3, 4)), 0.75);
+        assert_eq!(progress_fraction(&progress(5, 4)), 1.0);
     }
 
     // --- format_tool_display_text tests ---
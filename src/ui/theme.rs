@@ -3,8 +3,14 @@
 //! Provides 2 liquid glass theme styles plus System (auto-detect):
 //! - Liquid Dark: transparent glass on dark backgrounds
 //! - Liquid Light: transparent glass on light backgrounds
+//!
+//! Both are grayscale; config `state_colors` can give each session state its
+//! own color per theme ([`StateColors`]), e.g. a palette colorblind users can
+//! tell apart.
 
-use gpui::{Hsla, WindowAppearance};
+use crate::SessionState;
+use crate::config::{StateColorConfig, StatePalette};
+use gpui::{Hsla, Rgba, WindowAppearance};
 
 /// Theme style preference
 #[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
@@ -126,6 +132,99 @@ pub struct ThemeColors {
     pub indicator_icon: Hsla,
     /// Indicator border
     pub indicator_border: Hsla,
+
+    // === State Colors ===
+    /// Configured per-state colors (unset states use the grays above)
+    pub states: StateColors,
+}
+
+/// Icon color per session state, from config `state_colors`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StateColors {
+    pub running: Option<Hsla>,
+    pub attention: Option<Hsla>,
+    pub waiting: Option<Hsla>,
+    pub compacting: Option<Hsla>,
+    pub idle: Option<Hsla>,
+    pub stale: Option<Hsla>,
+}
+
+impl StateColors {
+    /// Parse one theme's palette; invalid colors are logged and left unset
+    pub fn from_palette(palette: &StatePalette) -> Self {
+        let color = |state| {
+            let value = palette.get(state)?;
+            let parsed = parse_hex_color(value);
+            if parsed.is_none() {
+                tracing::warn!(
+                    target: crate::logging::UI,
+                    "Ignoring state color {:?} for {:?}, expected #rrggbb",
+                    value,
+                    state
+                );
+            }
+            parsed
+        };
+        Self {
+            running: color(SessionState::Running),
+            attention: color(SessionState::Attention),
+            waiting: color(SessionState::Waiting),
+            compacting: color(SessionState::Compacting),
+            idle: color(SessionState::Idle),
+            stale: color(SessionState::Stale),
+        }
+    }
+
+    /// Color configured for `state`
+    pub fn get(&self, state: SessionState) -> Option<Hsla> {
+        match state {
+            SessionState::Running => self.running,
+            SessionState::Attention => self.attention,
+            SessionState::Waiting => self.waiting,
+            SessionState::Compacting => self.compacting,
+            SessionState::Idle => self.idle,
+            SessionState::Stale => self.stale,
+        }
+    }
+}
+
+/// Configured state colors of both themes
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ThemeStateColors {
+    dark: StateColors,
+    light: StateColors,
+}
+
+impl ThemeStateColors {
+    pub fn from_config(config: &StateColorConfig) -> Self {
+        Self {
+            dark: StateColors::from_palette(&config.dark),
+            light: StateColors::from_palette(&config.light),
+        }
+    }
+
+    pub fn for_style(&self, style: ResolvedStyle) -> StateColors {
+        match style {
+            ResolvedStyle::LiquidDark => self.dark,
+            ResolvedStyle::LiquidLight => self.light,
+        }
+    }
+}
+
+/// `"#rrggbb"` (or `"rrggbb"`) as an opaque color
+pub fn parse_hex_color(value: &str) -> Option<Hsla> {
+    let hex = value.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    let rgba = Rgba {
+        r: f32::from(channel(0)?) / 255.0,
+        g: f32::from(channel(2)?) / 255.0,
+        b: f32::from(channel(4)?) / 255.0,
+        a: 1.0,
+    };
+    Some(rgba.into())
 }
 
 /// All theme colors are achromatic (h=0, s=0), so each color is fully
@@ -183,6 +282,7 @@ fn build_theme(p: &Palette) -> ThemeColors {
         gloss: gray(p.gloss.0, p.gloss.1),
         indicator_icon: gray(p.indicator_icon.0, p.indicator_icon.1),
         indicator_border: gray(p.indicator_border.0, p.indicator_border.1),
+        states: StateColors::default(),
     }
 }
 
@@ -256,6 +356,14 @@ impl ThemeColors {
             ResolvedStyle::LiquidLight => Self::liquid_light(),
         }
     }
+
+    /// These colors with the configured state colors of `style`
+    pub fn with_state_colors(self, colors: &ThemeStateColors, style: ResolvedStyle) -> Self {
+        Self {
+            states: colors.for_style(style),
+            ..self
+        }
+    }
 }
 
 // === Layout Constants (theme-independent) ===
@@ -280,6 +388,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn hex_colors() {
+        let orange = parse_hex_color("#ff9f0a").unwrap();
+        let rgba = Rgba::from(orange);
+        assert!((rgba.r - 1.0).abs() < 1e-3);
+        assert!((rgba.g - 159.0 / 255.0).abs() < 1e-3);
+        assert!((rgba.b - 10.0 / 255.0).abs() < 1e-3);
+        assert_eq!(parse_hex_color("FF9F0A"), Some(orange));
+        assert_eq!(parse_hex_color("#fff"), None);
+        assert_eq!(parse_hex_color("#gg0000"), None);
+    }
+
+    #[test]
+    fn state_colors_per_theme() {
+        let config: StateColorConfig =
+            serde_json::from_str(r##"{"liquid-dark":{"waiting":"#0a84ff","idle":"blue"}}"##)
+                .unwrap();
+        let colors = ThemeStateColors::from_config(&config);
+        let dark = colors.for_style(ResolvedStyle::LiquidDark);
+        assert_eq!(dark.get(SessionState::Waiting), parse_hex_color("#0a84ff"));
+        assert_eq!(dark.get(SessionState::Idle), None);
+        assert_eq!(
+            colors.for_style(ResolvedStyle::LiquidLight),
+            StateColors::default()
+        );
+        let theme =
+            ThemeColors::liquid_dark().with_state_colors(&colors, ResolvedStyle::LiquidDark);
+        assert_eq!(theme.states, dark);
+    }
+
     #[test]
     fn config_str_unknown_defaults_to_system() {
         assert_eq!(ThemeStyle::from_config_str("unknown"), ThemeStyle::System);
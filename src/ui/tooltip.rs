@@ -0,0 +1,72 @@
+//! Tooltip - hover popover shared by HUD elements
+//!
+//! gpui draws tooltips inside the hovered element's window after its hover
+//! delay; `.tooltip()` only needs a view, which [`Tooltip::build`] makes from
+//! a title and detail lines. Callers capture the theme colors, since the
+//! builder gets no access to `SharedHudState`.
+
+use super::font;
+use super::theme::ThemeColors;
+use gpui::{
+    AnyView, App, AppContext, Context, IntoElement, ParentElement, Render, SharedString, Styled,
+    Window, div, px,
+};
+
+/// Widest a tooltip grows before wrapping (fits inside the session list)
+const MAX_WIDTH: f32 = 280.0;
+
+/// A title over dimmer detail lines
+pub(crate) struct Tooltip {
+    title: SharedString,
+    lines: Vec<SharedString>,
+    theme: ThemeColors,
+}
+
+impl Tooltip {
+    pub(crate) fn new(title: impl Into<SharedString>, theme: &ThemeColors) -> Self {
+        Self {
+            title: title.into(),
+            lines: Vec::new(),
+            theme: *theme,
+        }
+    }
+
+    /// Add detail lines below the title
+    pub(crate) fn lines(
+        mut self,
+        lines: impl IntoIterator<Item = impl Into<SharedString>>,
+    ) -> Self {
+        self.lines.extend(lines.into_iter().map(Into::into));
+        self
+    }
+
+    /// The view to return from a `.tooltip()` builder
+    pub(crate) fn build(self, cx: &mut App) -> AnyView {
+        cx.new(|_cx| self).into()
+    }
+}
+
+impl Render for Tooltip {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .max_w(px(MAX_WIDTH))
+            .px(px(8.0))
+            .py(px(6.0))
+            .flex()
+            .flex_col()
+            .gap(px(2.0))
+            .rounded(px(6.0))
+            .bg(self.theme.container_bg)
+            .border_1()
+            .border_color(self.theme.border)
+            .font_family(font::family())
+            .text_size(font::size(11.0))
+            .text_color(self.theme.text_secondary)
+            .child(
+                div()
+                    .text_color(self.theme.text_primary)
+                    .child(self.title.clone()),
+            )
+            .children(self.lines.iter().cloned().map(|line| div().child(line)))
+    }
+}
@@ -0,0 +1,257 @@
+//! Transcript viewer - read-only window showing a session's conversation
+//!
+//! Opened from the row context menu ("View transcript"). Renders user and
+//! assistant turns with tool calls collapsed to one line (click to expand),
+//! and tails the transcript file so new turns appear while the agent works.
+//! The view stays pinned to the bottom unless the user has scrolled up.
+
+use super::SharedHudState;
+use super::theme::ThemeColors;
+use crate::SessionInfo;
+use crate::transcript::{TranscriptEntry, TranscriptTail};
+use gpui::{
+    App, AppContext, Bounds, Context, Entity, InteractiveElement, IntoElement, ParentElement,
+    Render, ScrollHandle, SharedString, StatefulInteractiveElement, Styled, TitlebarOptions,
+    Window, WindowBounds, WindowOptions, div, prelude::FluentBuilder, px, size,
+};
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+/// How often the transcript file is checked for new lines
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Only the most recent entries are rendered
+const MAX_RENDERED_ENTRIES: usize = 500;
+const WINDOW_WIDTH: f32 = 560.0;
+const WINDOW_HEIGHT: f32 = 680.0;
+/// Distance from the bottom (px) that still counts as "following"
+const FOLLOW_SLACK: f32 = 8.0;
+
+/// Transcript viewer window view
+pub(crate) struct TranscriptView {
+    state: Entity<SharedHudState>,
+    tail: TranscriptTail,
+    entries: Vec<TranscriptEntry>,
+    /// Entry indices of tool calls expanded to show their input
+    expanded: HashSet<usize>,
+    last_poll: Option<Instant>,
+    /// Last read error (e.g. transcript deleted), shown instead of silence
+    error: Option<String>,
+    scroll_handle: ScrollHandle,
+}
+
+/// Open a viewer window for the session's transcript (no-op without one).
+pub(crate) fn open(app: &mut App, state: Entity<SharedHudState>, session: &SessionInfo) {
+    let Some(path) = session.transcript_path.clone() else {
+        return;
+    };
+    let name = session
+        .name
+        .clone()
+        .unwrap_or_else(|| super::session_list::extract_session_name(&session.cwd));
+
+    let result = app.open_window(
+        WindowOptions {
+            window_bounds: Some(WindowBounds::Windowed(Bounds::centered(
+                None,
+                size(px(WINDOW_WIDTH), px(WINDOW_HEIGHT)),
+                app,
+            ))),
+            titlebar: Some(TitlebarOptions {
+                title: Some(format!("Transcript — {name}").into()),
+                ..Default::default()
+            }),
+            focus: true,
+            show: true,
+            is_resizable: true,
+            ..Default::default()
+        },
+        |_window, app| {
+            app.new(|_cx| TranscriptView {
+                state,
+                tail: TranscriptTail::new(path),
+                entries: Vec::new(),
+                expanded: HashSet::new(),
+                last_poll: None,
+                error: None,
+                scroll_handle: ScrollHandle::new(),
+            })
+        },
+    );
+    if let Err(e) = result {
+        tracing::warn!(target: crate::logging::UI, "Failed to open transcript window: {}", e);
+    }
+}
+
+/// Whether the scroll position is at (or near) the bottom
+fn is_following(offset_y: f32, max_offset_y: f32) -> bool {
+    -offset_y >= max_offset_y - FOLLOW_SLACK
+}
+
+/// Index of the first rendered entry
+fn first_rendered(entry_count: usize) -> usize {
+    entry_count.saturating_sub(MAX_RENDERED_ENTRIES)
+}
+
+impl TranscriptView {
+    /// Read appended lines if the poll interval has passed
+    fn poll(&mut self) {
+        if self
+            .last_poll
+            .is_some_and(|last| last.elapsed() < POLL_INTERVAL)
+        {
+            return;
+        }
+        self.last_poll = Some(Instant::now());
+
+        let following = is_following(
+            f32::from(self.scroll_handle.offset().y),
+            f32::from(self.scroll_handle.max_offset().height),
+        );
+        match self.tail.read_new() {
+            Ok(read) => {
+                self.error = None;
+                if read.reset {
+                    self.entries.clear();
+                    self.expanded.clear();
+                }
+                if read.entries.is_empty() {
+                    return;
+                }
+                self.entries.extend(read.entries);
+                if following {
+                    self.scroll_handle.scroll_to_bottom();
+                }
+            }
+            Err(e) => {
+                self.error = Some(format!("Can't read {}: {}", self.tail.path().display(), e));
+            }
+        }
+    }
+
+    fn render_entry(
+        &self,
+        index: usize,
+        entry: &TranscriptEntry,
+        theme: &ThemeColors,
+        cx: &mut Context<Self>,
+    ) -> gpui::Stateful<gpui::Div> {
+        let base = div()
+            .id(SharedString::from(format!("transcript-entry-{index}")))
+            .w_full()
+            .flex()
+            .flex_col()
+            .gap(px(2.0))
+            .px(px(10.0))
+            .py(px(6.0))
+            .rounded(px(8.0));
+
+        match entry {
+            TranscriptEntry::User { text } => base
+                .bg(theme.row_hover_bg)
+                .child(role_label("You", theme))
+                .child(div().text_color(theme.text_primary).child(text.clone())),
+            TranscriptEntry::Assistant { text } => base
+                .child(role_label("Assistant", theme))
+                .child(div().text_color(theme.text_primary).child(text.clone())),
+            TranscriptEntry::ToolCall { name, label, input } => {
+                let expanded = self.expanded.contains(&index);
+                let summary = match label {
+                    Some(label) => format!("{} {name}  {label}", if expanded { "▾" } else { "▸" }),
+                    None => format!("{} {name}", if expanded { "▾" } else { "▸" }),
+                };
+                base.cursor(gpui::CursorStyle::PointingHand)
+                    .text_color(theme.text_secondary)
+                    .child(div().whitespace_nowrap().text_ellipsis().child(summary))
+                    .when(expanded && !input.is_empty(), |this| {
+                        this.child(div().pl(px(14.0)).text_size(px(11.0)).child(input.clone()))
+                    })
+                    .on_click(
+                        cx.listener(move |this, _event: &gpui::ClickEvent, _window, _cx| {
+                            if !this.expanded.remove(&index) {
+                                this.expanded.insert(index);
+                            }
+                        }),
+                    )
+            }
+        }
+    }
+}
+
+fn role_label(role: &'static str, theme: &ThemeColors) -> gpui::Div {
+    div()
+        .text_size(px(10.0))
+        .text_color(theme.text_header)
+        .child(role)
+}
+
+impl Render for TranscriptView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // Continuous frames drive polling, like the HUD windows
+        window.request_animation_frame();
+        self.poll();
+
+        let appearance = window.appearance();
+        let theme = self.state.update(cx, |state, _cx| {
+            state.update_system_appearance(appearance);
+            state.theme_colors()
+        });
+
+        let first = first_rendered(self.entries.len());
+        let entries: Vec<_> = self.entries[first..]
+            .iter()
+            .enumerate()
+            .map(|(offset, entry)| self.render_entry(first + offset, entry, &theme, cx))
+            .collect();
+        let hidden_note = (first > 0).then(|| {
+            div()
+                .text_color(theme.text_secondary)
+                .child(format!("{first} earlier entries not shown"))
+        });
+        let empty_note = (self.entries.is_empty() && self.error.is_none()).then(|| {
+            div()
+                .text_color(theme.text_secondary)
+                .child("No messages yet")
+        });
+        let error_note = self
+            .error
+            .clone()
+            .map(|error| div().text_color(theme.text_secondary).child(error));
+
+        div()
+            .id("transcript-scroll")
+            .size_full()
+            .overflow_y_scroll()
+            .track_scroll(&self.scroll_handle)
+            .p(px(12.0))
+            .flex()
+            .flex_col()
+            .gap(px(6.0))
+            .bg(theme.container_bg)
+            .font_family("Maple Mono NF CN")
+            .text_size(px(12.0))
+            .children(hidden_note)
+            .children(entries)
+            .children(empty_note)
+            .children(error_note)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn following_when_at_bottom() {
+        assert!(is_following(-400.0, 400.0));
+        assert!(is_following(-395.0, 400.0));
+        assert!(!is_following(-100.0, 400.0));
+        // Content shorter than the window
+        assert!(is_following(0.0, 0.0));
+    }
+
+    #[test]
+    fn only_recent_entries_rendered() {
+        assert_eq!(first_rendered(10), 0);
+        assert_eq!(first_rendered(MAX_RENDERED_ENTRIES + 25), 25);
+    }
+}
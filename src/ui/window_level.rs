@@ -0,0 +1,173 @@
+//! Window layering - macOS window level, Spaces behavior and visibility
+//!
+//! gpui's `WindowKind::PopUp` pins windows above everything. This module
+//! re-applies the level and collection behavior from `ui.window_level` and
+//! `ui.all_spaces` on the underlying `NSWindow` after a window opens, and
+//! sets the window alpha for `ui.auto_hide` (and mouse pass-through for the
+//! edge pulse).
+
+use crate::config::WindowLevel;
+use gpui::Window;
+
+/// Apply the configured level and Spaces behavior to a HUD window.
+#[cfg(target_os = "macos")]
+pub(crate) fn apply(window: &Window, level: WindowLevel, all_spaces: bool) {
+    use objc::{msg_send, sel, sel_impl};
+
+    let Some(ns_window) = ns_window(window) else {
+        return;
+    };
+    // SAFETY: `ns_window` is the live NSWindow backing the gpui window we
+    // borrow; these are plain AppKit setters called on the main thread.
+    unsafe {
+        let _: () = msg_send![ns_window, setLevel: ns_window_level(level)];
+        let _: () = msg_send![
+            ns_window,
+            setCollectionBehavior: collection_behavior(level, all_spaces)
+        ];
+    }
+}
+
+/// Window levels are only configurable on macOS.
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn apply(_window: &Window, _level: WindowLevel, _all_spaces: bool) {}
+
+/// Set the window alpha. A fully transparent window also ignores the mouse,
+/// so a hidden indicator can't be clicked or dragged by accident.
+///
+/// The window stays ordered in (rather than `orderOut:`) so gpui keeps
+/// delivering animation frames and can reveal it on the next registry change.
+#[cfg(target_os = "macos")]
+pub(crate) fn set_alpha(window: &Window, alpha: f32) {
+    use objc::runtime::{BOOL, NO, YES};
+    use objc::{msg_send, sel, sel_impl};
+
+    let Some(ns_window) = ns_window(window) else {
+        return;
+    };
+    let ignores_mouse: BOOL = if alpha <= 0.0 { YES } else { NO };
+    // SAFETY: see `apply`
+    unsafe {
+        let _: () = msg_send![ns_window, setAlphaValue: alpha.clamp(0.0, 1.0) as f64];
+        let _: () = msg_send![ns_window, setIgnoresMouseEvents: ignores_mouse];
+    }
+}
+
+/// Window alpha is only controllable on macOS.
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn set_alpha(_window: &Window, _alpha: f32) {}
+
+/// Let clicks pass through the window to whatever is below it.
+#[cfg(target_os = "macos")]
+pub(crate) fn ignore_mouse(window: &Window) {
+    use objc::runtime::YES;
+    use objc::{msg_send, sel, sel_impl};
+
+    let Some(ns_window) = ns_window(window) else {
+        return;
+    };
+    // SAFETY: see `apply`
+    unsafe {
+        let _: () = msg_send![ns_window, setIgnoresMouseEvents: YES];
+    }
+}
+
+/// Mouse pass-through is only controllable on macOS.
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn ignore_mouse(_window: &Window) {}
+
+/// The `NSWindow` behind a gpui window
+#[cfg(target_os = "macos")]
+fn ns_window(window: &Window) -> Option<*mut objc::runtime::Object> {
+    use objc::runtime::Object;
+    use objc::{msg_send, sel, sel_impl};
+    use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+
+    // `Window::window_handle` (inherent) returns gpui's handle, not the raw one
+    let Ok(handle) = HasWindowHandle::window_handle(window) else {
+        tracing::debug!(target: crate::logging::UI, "No raw window handle for HUD window");
+        return None;
+    };
+    let RawWindowHandle::AppKit(appkit) = handle.as_raw() else {
+        return None;
+    };
+    let ns_view = appkit.ns_view.as_ptr() as *mut Object;
+
+    // SAFETY: `ns_view` is a live NSView owned by the gpui window we borrow
+    let ns_window: *mut Object = unsafe { msg_send![ns_view, window] };
+    (!ns_window.is_null()).then_some(ns_window)
+}
+
+/// `NSWindowLevel` for a configured level
+#[cfg(target_os = "macos")]
+fn ns_window_level(level: WindowLevel) -> isize {
+    /// NSPopUpMenuWindowLevel (what gpui uses for `WindowKind::PopUp`)
+    const POP_UP_MENU: isize = 101;
+    /// NSFloatingWindowLevel
+    const FLOATING: isize = 3;
+    /// kCGDesktopIconWindowLevelKey
+    const DESKTOP_ICON_KEY: i32 = 18;
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    unsafe extern "C" {
+        fn CGWindowLevelForKey(key: i32) -> i32;
+    }
+
+    match level {
+        WindowLevel::AboveFullscreen => POP_UP_MENU,
+        WindowLevel::Floating => FLOATING,
+        // SAFETY: pure lookup of a CoreGraphics constant
+        WindowLevel::Desktop => (unsafe { CGWindowLevelForKey(DESKTOP_ICON_KEY) }) as isize,
+    }
+}
+
+/// `NSWindowCollectionBehavior` bits for a configured level
+#[cfg(target_os = "macos")]
+fn collection_behavior(level: WindowLevel, all_spaces: bool) -> usize {
+    const CAN_JOIN_ALL_SPACES: usize = 1 << 0;
+    const MOVE_TO_ACTIVE_SPACE: usize = 1 << 1;
+    const STATIONARY: usize = 1 << 4;
+    const IGNORES_CYCLE: usize = 1 << 6;
+    const FULL_SCREEN_AUXILIARY: usize = 1 << 8;
+
+    let mut behavior = IGNORES_CYCLE;
+    behavior |= if all_spaces {
+        CAN_JOIN_ALL_SPACES
+    } else {
+        MOVE_TO_ACTIVE_SPACE
+    };
+    match level {
+        WindowLevel::AboveFullscreen => behavior |= FULL_SCREEN_AUXILIARY,
+        WindowLevel::Floating => {}
+        // Desktop windows shouldn't slide around with Mission Control
+        WindowLevel::Desktop => behavior |= STATIONARY,
+    }
+    behavior
+}
+
+#[cfg(all(test, target_os = "macos"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn above_fullscreen_joins_fullscreen_spaces() {
+        let behavior = collection_behavior(WindowLevel::AboveFullscreen, true);
+        assert_ne!(behavior & (1 << 8), 0);
+        assert_ne!(behavior & 1, 0);
+    }
+
+    #[test]
+    fn single_space_moves_to_active_space() {
+        let behavior = collection_behavior(WindowLevel::Floating, false);
+        assert_eq!(behavior & 1, 0);
+        assert_ne!(behavior & (1 << 1), 0);
+    }
+
+    #[test]
+    fn levels_are_ordered() {
+        assert!(ns_window_level(WindowLevel::Desktop) < 0);
+        assert!(
+            ns_window_level(WindowLevel::Floating) < ns_window_level(WindowLevel::AboveFullscreen)
+        );
+    }
+}
@@ -0,0 +1,423 @@
+//! Updates - `aura self-update` and Aura.app's update prompt
+//!
+//! Releases are published on GitHub (`fahchen/aura`), each with one archive
+//! per platform, `aura-<arch>-<os>.tar.gz` (e.g. `aura-aarch64-macos.tar.gz`),
+//! and a `<archive>.sha256` checksum next to it. `aura self-update` downloads
+//! this platform's archive, verifies it against the checksum, swaps the
+//! binary in place and asks a running daemon to restart onto it; `--check`
+//! only reports whether a newer release exists. The checksum comes from the
+//! same release as the archive, so it catches a corrupt or truncated
+//! download, not a tampered release.
+//!
+//! Aura.app doesn't replace its own binary (that would break the bundle's
+//! signature). Its daemon checks once a day instead and offers to open the
+//! release page in a dialog; `"check_for_updates": false` turns that off.
+//!
+//! Downloads go through `curl` and checksums through `shasum`/`sha256sum`.
+
+use crate::ipc::{self, IpcMessage};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+/// GitHub API endpoint for the newest release
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/fahchen/aura/releases/latest";
+/// How often Aura.app checks for a new release
+const CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+/// Delay before the first check, so launching stays quiet
+const FIRST_CHECK_DELAY: Duration = Duration::from_secs(60);
+
+/// A GitHub release (the fields Aura uses)
+#[derive(Debug, Clone, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    pub html_url: String,
+    #[serde(default)]
+    pub assets: Vec<Asset>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Asset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+impl Release {
+    fn asset(&self, name: &str) -> Option<&Asset> {
+        self.assets.iter().find(|asset| asset.name == name)
+    }
+}
+
+/// Version of the running binary
+pub fn current_version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
+
+/// `(major, minor, patch)` of "v1.2.3" or "1.2"; pre-release and build
+/// suffixes are ignored
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let core = version
+        .trim()
+        .trim_start_matches('v')
+        .split(['-', '+'])
+        .next()?;
+    let mut parts = core.split('.').map(|part| part.parse::<u64>().ok());
+    let major = parts.next()??;
+    let minor = parts.next().flatten().unwrap_or(0);
+    let patch = parts.next().flatten().unwrap_or(0);
+    Some((major, minor, patch))
+}
+
+/// Whether release `tag` is newer than version `current`
+pub fn is_newer(tag: &str, current: &str) -> bool {
+    match (parse_version(tag), parse_version(current)) {
+        (Some(tag), Some(current)) => tag > current,
+        _ => false,
+    }
+}
+
+/// Release archive for this platform
+pub fn asset_name() -> String {
+    format!(
+        "aura-{}-{}.tar.gz",
+        std::env::consts::ARCH,
+        std::env::consts::OS
+    )
+}
+
+/// The checksum for `file_name` in a `.sha256` file: `<hex>  <name>` lines
+/// (`sha256sum` output) or a bare hash
+fn expected_checksum(text: &str, file_name: &str) -> Option<String> {
+    text.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let hash = fields.next()?;
+        let named = fields.next().map(|name| name.trim_start_matches('*'));
+        let valid = hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit());
+        (valid && named.is_none_or(|name| name == file_name)).then(|| hash.to_ascii_lowercase())
+    })
+}
+
+/// Whether `exe` lives inside a macOS app bundle
+pub fn is_app_bundle(exe: &Path) -> bool {
+    exe.to_string_lossy().contains(".app/Contents/MacOS")
+}
+
+/// Whether this process runs from Aura.app
+pub fn running_from_app_bundle() -> bool {
+    std::env::current_exe().is_ok_and(|exe| is_app_bundle(&exe))
+}
+
+/// Fetch `url` with curl
+fn download(url: &str) -> Result<Vec<u8>, String> {
+    let output = Command::new("curl")
+        .args(["-fsSL", "--retry", "2", url])
+        .output()
+        .map_err(|e| format!("can't run curl: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "can't download {url}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(output.stdout)
+}
+
+/// The newest published release
+pub fn latest_release() -> Result<Release, String> {
+    serde_json::from_slice(&download(LATEST_RELEASE_URL)?)
+        .map_err(|e| format!("unexpected release data: {e}"))
+}
+
+/// SHA-256 of a file, lowercase hex
+fn sha256_file(path: &Path) -> Result<String, String> {
+    for (program, args) in [("shasum", &["-a", "256"][..]), ("sha256sum", &[][..])] {
+        let Ok(output) = Command::new(program).args(args).arg(path).output() else {
+            continue;
+        };
+        if output.status.success()
+            && let Some(hash) = String::from_utf8_lossy(&output.stdout)
+                .split_whitespace()
+                .next()
+        {
+            return Ok(hash.to_ascii_lowercase());
+        }
+    }
+    Err("can't compute a checksum (no shasum or sha256sum)".to_string())
+}
+
+/// The `aura` binary in an unpacked archive: at its root or one directory down
+fn find_binary(dir: &Path) -> Option<PathBuf> {
+    let name = if cfg!(windows) { "aura.exe" } else { "aura" };
+    let root = dir.join(name);
+    if root.is_file() {
+        return Some(root);
+    }
+    std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path().join(name))
+        .find(|path| path.is_file())
+}
+
+/// Put `new` at `exe` with one rename, so nothing ever runs a half-written
+/// binary
+fn replace_binary(new: &Path, exe: &Path) -> Result<(), String> {
+    let dir = exe.parent().ok_or("the binary has no parent directory")?;
+    let cant_write =
+        |e: std::io::Error| format!("can't write to {}: {e} (rerun with sudo?)", dir.display());
+    let staged = tempfile::Builder::new()
+        .prefix(".aura-update")
+        .tempfile_in(dir)
+        .map_err(cant_write)?;
+    std::fs::copy(new, staged.path()).map_err(cant_write)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(staged.path(), std::fs::Permissions::from_mode(0o755))
+            .map_err(cant_write)?;
+    }
+    // A running executable can be renamed on Windows, but not replaced
+    #[cfg(windows)]
+    let old = {
+        remove_old_binary(exe);
+        let old = old_binary(exe);
+        std::fs::rename(exe, &old).map_err(cant_write)?;
+        old
+    };
+    if let Err(e) = staged.persist(exe) {
+        #[cfg(windows)]
+        let _ = std::fs::rename(&old, exe);
+        return Err(cant_write(e.error));
+    }
+    // Fails while the old binary still runs; the next start removes it
+    #[cfg(windows)]
+    remove_old_binary(exe);
+    Ok(())
+}
+
+/// Where [`replace_binary`] moves the running binary aside on Windows
+#[cfg(windows)]
+fn old_binary(exe: &Path) -> PathBuf {
+    exe.with_extension("old.exe")
+}
+
+/// Delete the binary an earlier update moved aside (fails while it still runs)
+#[cfg(windows)]
+fn remove_old_binary(exe: &Path) {
+    let _ = std::fs::remove_file(old_binary(exe));
+}
+
+/// Clean up after the last `aura self-update` (Windows keeps the binary it
+/// replaced until that process exits)
+pub fn remove_replaced_binary() {
+    #[cfg(windows)]
+    if let Ok(exe) = std::env::current_exe() {
+        remove_old_binary(&exe);
+    }
+}
+
+/// `aura self-update [--check]`
+pub fn self_update(check_only: bool) -> Result<(), String> {
+    let exe = std::env::current_exe().map_err(|e| format!("can't locate the binary: {e}"))?;
+    let release = latest_release()?;
+    let current = current_version();
+    let tag = &release.tag_name;
+    if !is_newer(tag, current) {
+        println!("aura {current} is up to date");
+        return Ok(());
+    }
+    if check_only {
+        println!("aura {tag} is available (installed: {current})");
+        println!("{}", release.html_url);
+        return Ok(());
+    }
+    if is_app_bundle(&exe) {
+        return Err(format!(
+            "Aura.app can't replace its binary in place; download {tag} from {}",
+            release.html_url
+        ));
+    }
+
+    let name = asset_name();
+    let archive = release
+        .asset(&name)
+        .ok_or_else(|| format!("release {tag} has no {name}"))?;
+    let checksum = release
+        .asset(&format!("{name}.sha256"))
+        .ok_or_else(|| format!("release {tag} has no checksum for {name}"))?;
+
+    let dir = tempfile::tempdir().map_err(|e| format!("can't create a temp dir: {e}"))?;
+    let archive_path = dir.path().join(&name);
+    std::fs::write(&archive_path, download(&archive.browser_download_url)?)
+        .map_err(|e| format!("can't save {name}: {e}"))?;
+    let checksums = download(&checksum.browser_download_url)?;
+    let expected = expected_checksum(&String::from_utf8_lossy(&checksums), &name)
+        .ok_or_else(|| format!("checksum file for {name} is malformed"))?;
+    let actual = sha256_file(&archive_path)?;
+    if actual != expected {
+        return Err(format!(
+            "checksum mismatch for {name}: expected {expected}, got {actual}"
+        ));
+    }
+
+    let unpacked = dir.path().join("unpacked");
+    std::fs::create_dir(&unpacked).map_err(|e| format!("can't unpack {name}: {e}"))?;
+    let status = Command::new("tar")
+        .arg("-xzf")
+        .arg(&archive_path)
+        .arg("-C")
+        .arg(&unpacked)
+        .status()
+        .map_err(|e| format!("can't run tar: {e}"))?;
+    if !status.success() {
+        return Err(format!("can't unpack {name}"));
+    }
+    let binary = find_binary(&unpacked).ok_or_else(|| format!("{name} has no aura binary"))?;
+    replace_binary(&binary, &exe)?;
+    println!("Updated aura {current} → {tag}");
+
+    // The daemon answers, then restarts onto the new binary
+    if ipc::send_request(&IpcMessage::Restart).is_ok() {
+        println!("Restarting the running daemon");
+    }
+    Ok(())
+}
+
+/// The running binary's path, even after it was replaced on disk
+fn current_exe_path() -> std::io::Result<PathBuf> {
+    let exe = std::env::current_exe()?;
+    // Linux reports a replaced binary as "<path> (deleted)"
+    Ok(exe
+        .to_str()
+        .and_then(|path| path.strip_suffix(" (deleted)"))
+        .map(PathBuf::from)
+        .unwrap_or(exe))
+}
+
+/// Replace this process with a fresh start of the binary at its path, with
+/// the same arguments; only returns (with the error) if that fails
+pub fn restart_daemon() -> std::io::Error {
+    let exe = match current_exe_path() {
+        Ok(exe) => exe,
+        Err(e) => return e,
+    };
+    let mut command = Command::new(exe);
+    command.args(std::env::args_os().skip(1));
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.exec()
+    }
+    #[cfg(not(unix))]
+    match command.spawn() {
+        Ok(_) => std::process::exit(0),
+        Err(e) => e,
+    }
+}
+
+/// Ask whether to open the release page; true for "Download"
+async fn prompt(release: &Release) -> bool {
+    let text = format!(
+        "Aura {} is available - you have {}.",
+        release.tag_name.trim_start_matches('v'),
+        current_version()
+    );
+    let script = format!(
+        "display dialog {} with title \"Aura\" buttons {{\"Later\", \"Download\"}} default button \"Download\"",
+        crate::reminder::applescript_string(&text)
+    );
+    match tokio::process::Command::new("osascript")
+        .args(["-e", &script])
+        .output()
+        .await
+    {
+        Ok(output) => String::from_utf8_lossy(&output.stdout).contains("Download"),
+        Err(e) => {
+            tracing::warn!("Failed to run osascript for update prompt: {}", e);
+            false
+        }
+    }
+}
+
+/// Check for new releases once a day and offer each one once, until the
+/// runtime shuts down (Aura.app only).
+pub async fn run() {
+    tokio::time::sleep(FIRST_CHECK_DELAY).await;
+    let mut offered: Option<String> = None;
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+    loop {
+        interval.tick().await;
+        let release = match tokio::task::spawn_blocking(latest_release).await {
+            Ok(Ok(release)) => release,
+            Ok(Err(e)) => {
+                tracing::debug!("Update check failed: {}", e);
+                continue;
+            }
+            Err(_) => break,
+        };
+        if !is_newer(&release.tag_name, current_version())
+            || offered.as_deref() == Some(release.tag_name.as_str())
+        {
+            continue;
+        }
+        tracing::info!(tag = %release.tag_name, "update available");
+        offered = Some(release.tag_name.clone());
+        if prompt(&release).await
+            && let Err(e) = tokio::process::Command::new("open")
+                .arg(&release.html_url)
+                .status()
+                .await
+        {
+            tracing::warn!("Failed to open {}: {}", release.html_url, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn versions_compare_numerically() {
+        assert!(is_newer("v0.10.0", "0.9.3"));
+        assert!(is_newer("1.0", "0.2.0"));
+        assert!(is_newer("v0.2.1-rc.1", "0.2.0"));
+        assert!(!is_newer("v0.2.0", "0.2.0"));
+        assert!(!is_newer("v0.1.9", "0.2.0"));
+        assert!(!is_newer("nightly", "0.2.0"));
+    }
+
+    #[test]
+    fn checksum_files_name_the_archive() {
+        let hash = "A".repeat(64);
+        let other = "b".repeat(64);
+        let text =
+            format!("{other}  aura-x86_64-linux.tar.gz\n{hash} *aura-aarch64-macos.tar.gz\n");
+        assert_eq!(
+            expected_checksum(&text, "aura-aarch64-macos.tar.gz"),
+            Some("a".repeat(64))
+        );
+        assert_eq!(expected_checksum(&other, "anything"), Some(other.clone()));
+        assert_eq!(
+            expected_checksum("not a hash  aura.tar.gz", "aura.tar.gz"),
+            None
+        );
+    }
+
+    #[test]
+    fn releases_parse_from_github_json() {
+        let release: Release = serde_json::from_str(
+            r#"{"tag_name":"v0.3.0","html_url":"https://github.com/fahchen/aura/releases/tag/v0.3.0",
+                "assets":[{"name":"aura-aarch64-macos.tar.gz","browser_download_url":"https://example.com/a"}]}"#,
+        )
+        .unwrap();
+        assert!(release.asset("aura-aarch64-macos.tar.gz").is_some());
+        assert!(release.asset("aura-x86_64-linux.tar.gz").is_none());
+        assert!(asset_name().starts_with("aura-") && asset_name().ends_with(".tar.gz"));
+        assert!(is_app_bundle(Path::new(
+            "/Applications/Aura.app/Contents/MacOS/aura"
+        )));
+        assert!(!is_app_bundle(Path::new("/usr/local/bin/aura")));
+    }
+}
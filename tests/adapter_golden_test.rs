@@ -0,0 +1,88 @@
+//! Golden tests for the agent adapters
+//!
+//! Every `tests/fixtures/claude/*.jsonl` file holds hook payloads (one per
+//! line, as `aura hook --agent claude-code` reads them) and every
+//! `tests/fixtures/codex/*.jsonl` file a Codex rollout. The events an adapter
+//! produces for a fixture must match the `<name>.golden.json` next to it.
+//!
+//! To add a case, drop an anonymized fixture in and run
+//! `UPDATE_GOLDEN=1 cargo test --test adapter_golden_test`, then review the
+//! generated golden file before checking it in.
+
+use aura::AgentEvent;
+use aura::agents::claude_code::{self, HookAgent};
+use aura::agents::codex::sessions::parse_rollout;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+fn fixtures(agent: &str) -> Vec<PathBuf> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(agent);
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("can't read {}: {e}", dir.display()))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty(), "no fixtures in {}", dir.display());
+    paths
+}
+
+fn read_lines(path: &Path) -> Vec<Value> {
+    std::fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .unwrap_or_else(|e| panic!("{}:{}: {e}", path.display(), i + 1))
+        })
+        .collect()
+}
+
+/// Compare `events` with the fixture's golden file (or rewrite it with
+/// `UPDATE_GOLDEN` set)
+fn assert_golden(fixture: &Path, events: &[AgentEvent]) {
+    let golden = fixture.with_extension("golden.json");
+    let actual = serde_json::to_value(events).unwrap();
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        let json = serde_json::to_string_pretty(&actual).unwrap() + "\n";
+        std::fs::write(&golden, json).unwrap();
+        return;
+    }
+    let expected: Value = serde_json::from_str(
+        &std::fs::read_to_string(&golden)
+            .unwrap_or_else(|e| panic!("can't read {}: {e}", golden.display())),
+    )
+    .unwrap();
+    assert_eq!(
+        actual,
+        expected,
+        "{} doesn't match {}; rerun with UPDATE_GOLDEN=1 to accept",
+        fixture.display(),
+        golden.display()
+    );
+}
+
+#[test]
+fn claude_code_hooks_match_golden() {
+    for fixture in fixtures("claude") {
+        let events: Vec<AgentEvent> = read_lines(&fixture)
+            .iter()
+            .flat_map(|hook| claude_code::convert(&HookAgent::ClaudeCode, hook).unwrap_or_default())
+            .collect();
+        assert_golden(&fixture, &events);
+    }
+}
+
+#[test]
+fn codex_rollouts_match_golden() {
+    for fixture in fixtures("codex") {
+        let lines = read_lines(&fixture);
+        let fallback_id = fixture.file_stem().unwrap().to_string_lossy();
+        let events = parse_rollout(&fallback_id, &lines);
+        assert_golden(&fixture, &events);
+    }
+}
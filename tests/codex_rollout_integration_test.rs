@@ -1,19 +1,11 @@
+use aura::config::AgentHome;
 use aura::{AgentEvent, AgentType};
 use chrono::Datelike;
-use std::ffi::OsString;
 use std::path::Path;
-use std::sync::OnceLock;
 use std::time::Duration;
 use tempfile::TempDir;
-use tokio::sync::Mutex;
 use tokio::time::timeout;
 
-static ENV_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
-
-fn env_lock() -> &'static Mutex<()> {
-    ENV_LOCK.get_or_init(|| Mutex::new(()))
-}
-
 fn write_jsonl(path: &Path, lines: &[serde_json::Value]) {
     let mut out = String::new();
     for line in lines {
@@ -37,38 +29,12 @@ fn today_rollout_dir(base: &Path) -> std::path::PathBuf {
         .join(format!("{:02}", today.day()))
 }
 
-struct EnvVarGuard {
-    key: &'static str,
-    original: Option<OsString>,
-}
-
-impl EnvVarGuard {
-    fn set(key: &'static str, value: &Path) -> Self {
-        let original = std::env::var_os(key);
-
-        // `set_var` / `remove_var` are `unsafe` on Rust 2024 because mutating the
-        // process environment can be undefined behavior if other threads call
-        // into `getenv` concurrently. This test serializes environment mutation
-        // with a global mutex and uses a single-threaded runtime.
-        unsafe {
-            std::env::set_var(key, value);
-        }
-
-        Self { key, original }
-    }
-}
-
-impl Drop for EnvVarGuard {
-    fn drop(&mut self) {
-        match &self.original {
-            Some(value) => unsafe {
-                std::env::set_var(self.key, value);
-            },
-            None => unsafe {
-                std::env::remove_var(self.key);
-            },
-        }
-    }
+/// Watch only `tmp`, whatever the user's config lists
+fn home(tmp: &TempDir) -> Vec<AgentHome> {
+    vec![AgentHome {
+        path: tmp.path().display().to_string(),
+        profile: None,
+    }]
 }
 
 async fn recv_until(
@@ -103,10 +69,7 @@ async fn recv_until(
 
 #[tokio::test(flavor = "current_thread")]
 async fn codex_bootstrap_emits_activity_from_existing_rollout() {
-    let _guard = env_lock().lock().await;
-
     let tmp = tempdir_in_repo();
-    let _env = EnvVarGuard::set("CODEX_HOME", tmp.path());
 
     let rollout_dir = today_rollout_dir(tmp.path());
     std::fs::create_dir_all(&rollout_dir).unwrap();
@@ -135,7 +98,7 @@ async fn codex_bootstrap_emits_activity_from_existing_rollout() {
         ],
     );
 
-    let stream = aura::agents::codex::spawn();
+    let stream = aura::agents::codex::spawn_with_homes(home(&tmp));
     let mut rx = stream.subscribe();
 
     let events = recv_until(&mut rx, Duration::from_secs(2), |ev| {
@@ -151,6 +114,7 @@ async fn codex_bootstrap_emits_activity_from_existing_rollout() {
             session_id: sid,
             cwd,
             agent,
+            ..
         } => sid == session_id && cwd == "/tmp/project" && agent == &AgentType::Codex,
         _ => false,
     });
@@ -170,10 +134,7 @@ async fn codex_bootstrap_emits_activity_from_existing_rollout() {
 
 #[tokio::test(flavor = "current_thread")]
 async fn codex_tail_emits_activity_on_rollout_append() {
-    let _guard = env_lock().lock().await;
-
     let tmp = tempdir_in_repo();
-    let _env = EnvVarGuard::set("CODEX_HOME", tmp.path());
 
     let rollout_dir = today_rollout_dir(tmp.path());
     std::fs::create_dir_all(&rollout_dir).unwrap();
@@ -191,7 +152,7 @@ async fn codex_tail_emits_activity_on_rollout_append() {
         })],
     );
 
-    let stream = aura::agents::codex::spawn();
+    let stream = aura::agents::codex::spawn_with_homes(home(&tmp));
     let mut rx = stream.subscribe();
 
     let initial = recv_until(&mut rx, Duration::from_secs(2), |ev| {
@@ -245,15 +206,12 @@ async fn codex_tail_emits_activity_on_rollout_append() {
 
 #[tokio::test(flavor = "current_thread")]
 async fn codex_discovers_new_rollout_created_after_startup() {
-    let _guard = env_lock().lock().await;
-
     let tmp = tempdir_in_repo();
-    let _env = EnvVarGuard::set("CODEX_HOME", tmp.path());
 
     let sessions_root = tmp.path().join("sessions");
     std::fs::create_dir_all(&sessions_root).unwrap();
 
-    let stream = aura::agents::codex::spawn();
+    let stream = aura::agents::codex::spawn_with_homes(home(&tmp));
     let mut rx = stream.subscribe();
 
     let rollout_dir = today_rollout_dir(tmp.path());
@@ -0,0 +1,329 @@
+//! End-to-end test - hook process → socket → registry
+//!
+//! Starts the real IPC server (with the coalescer in front of the registry)
+//! on a socket in a temporary `AURA_RUNTIME_DIR`, then runs
+//! `aura hook --agent claude-code` processes against it with the Claude Code
+//! fixture payloads and checks what the registry ends up with. Also covers
+//! malformed input, a daemon that's gone or hung, a client stalling mid-line
+//! and many hooks connecting at once.
+//!
+//! All tests share one daemon, so each uses its own session IDs.
+
+#![cfg(unix)]
+
+use aura::coalesce::Coalescer;
+use aura::ipc::{self, IpcMessage, IpcResponse, StampedEvent};
+use aura::paths::RUNTIME_DIR_ENV;
+use aura::registry::{SessionRegistry, SharedRegistry};
+use aura::{AgentEvent, AgentType, SessionState};
+use serde_json::Value;
+use std::io::Write;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+use std::sync::OnceLock;
+use std::thread;
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+/// Session of `tests/fixtures/claude/session_lifecycle.jsonl`
+const FIXTURE_SESSION: &str = "c0ffee01-0000-4000-8000-000000000001";
+/// How long a hook process may take, including the 2s ping timeout
+const HOOK_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long to wait for events to go through the socket and the coalescer
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(5);
+/// Hook processes started at once
+const CONCURRENT_HOOKS: usize = 16;
+
+struct Daemon {
+    registry: SharedRegistry,
+    /// Runtime directory (socket) and home of the hook processes
+    dir: TempDir,
+}
+
+/// The shared daemon, started on first use
+fn daemon() -> &'static Daemon {
+    static DAEMON: OnceLock<Daemon> = OnceLock::new();
+    DAEMON.get_or_init(|| {
+        // Under /tmp rather than $TMPDIR, so the socket path stays short
+        let dir = tempfile::Builder::new()
+            .prefix("aura-e2e")
+            .tempdir_in("/tmp")
+            .unwrap();
+        // Set before any test gets past `daemon()`, so before other threads
+        // spawn processes (which read the environment)
+        unsafe { std::env::set_var(RUNTIME_DIR_ENV, dir.path()) };
+
+        let registry = SharedRegistry::new(SessionRegistry::new());
+        let server_registry = registry.clone();
+        thread::spawn(move || {
+            let runtime = tokio::runtime::Runtime::new().unwrap();
+            runtime.block_on(async move {
+                let events = Coalescer::spawn(server_registry.clone());
+                aura::server::start(server_registry, events).await;
+            });
+        });
+
+        let deadline = Instant::now() + DELIVERY_TIMEOUT;
+        while ipc::send_request(&IpcMessage::Ping).is_err() {
+            assert!(Instant::now() < deadline, "daemon didn't start listening");
+            thread::sleep(Duration::from_millis(10));
+        }
+        Daemon { registry, dir }
+    })
+}
+
+/// Hook payloads of the lifecycle fixture, one JSON line each
+fn fixture() -> Vec<String> {
+    let path =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/claude/session_lifecycle.jsonl");
+    std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("can't read {}: {e}", path.display()))
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// The fixture's SessionStart payload for `session_id`
+fn session_start(session_id: &str) -> String {
+    let mut payload: Value = serde_json::from_str(&fixture()[0]).unwrap();
+    assert_eq!(payload["hook_event_name"], "SessionStart");
+    payload["session_id"] = session_id.into();
+    payload.to_string()
+}
+
+/// Run `aura hook --agent claude-code` against the daemon in `runtime_dir`
+/// with `payload` on stdin, failing if it takes longer than [`HOOK_TIMEOUT`]
+fn hook(runtime_dir: &Path, payload: &str) -> Output {
+    // Keeps the user's config (ignore list, filters) out of the test
+    let home = daemon().dir.path().join("home");
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aura"))
+        .args(["hook", "--agent", "claude-code"])
+        .env(RUNTIME_DIR_ENV, runtime_dir)
+        .env("HOME", &home)
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // Dropped right away, so the hook sees the end of its input
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(payload.as_bytes())
+        .unwrap();
+
+    let deadline = Instant::now() + HOOK_TIMEOUT;
+    while child.try_wait().unwrap().is_none() {
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            panic!("hook still running after {HOOK_TIMEOUT:?}");
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    child.wait_with_output().unwrap()
+}
+
+/// [`hook`] against the shared daemon, which must succeed
+fn run_hook(payload: &str) {
+    let output = hook(daemon().dir.path(), payload);
+    assert!(
+        output.status.success(),
+        "hook failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+fn wait_for(what: &str, check: impl Fn(&SessionRegistry) -> bool) {
+    let deadline = Instant::now() + DELIVERY_TIMEOUT;
+    while !daemon().registry.read(&check).unwrap_or(false) {
+        assert!(Instant::now() < deadline, "timed out waiting for {what}");
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+fn has_session(session_id: &str) -> bool {
+    daemon()
+        .registry
+        .read(|registry| registry.get(session_id).is_some())
+        .unwrap()
+}
+
+fn connect() -> UnixStream {
+    UnixStream::connect(daemon().dir.path().join("aura.sock")).unwrap()
+}
+
+/// A `SessionStarted` line as a hook sends it
+fn started_line(session_id: &str) -> String {
+    let event = AgentEvent::SessionStarted {
+        session_id: session_id.into(),
+        cwd: "/code/e2e".into(),
+        agent: AgentType::ClaudeCode,
+        terminal: None,
+    };
+    serde_json::to_string(&StampedEvent::new(event, 0, 0)).unwrap()
+}
+
+#[test]
+fn fixture_hooks_drive_the_session_through_the_daemon() {
+    daemon();
+    for (i, payload) in fixture().iter().enumerate() {
+        run_hook(payload);
+
+        let hook: Value = serde_json::from_str(payload).unwrap();
+        assert_eq!(hook["session_id"], FIXTURE_SESSION);
+        match (
+            hook["hook_event_name"].as_str().unwrap(),
+            hook["tool_name"].as_str(),
+            hook["notification_type"].as_str(),
+        ) {
+            ("SessionStart", ..) => wait_for("the session to register", |registry| {
+                registry
+                    .get(FIXTURE_SESSION)
+                    .is_some_and(|s| s.cwd == "/home/dev/projects/demo")
+            }),
+            ("PostToolUse", Some("Bash"), _) => wait_for("the session name", |registry| {
+                registry
+                    .get(FIXTURE_SESSION)
+                    .is_some_and(|s| s.name.as_deref() == Some("fix login"))
+            }),
+            ("Notification", _, Some("permission_prompt")) => {
+                wait_for("the permission prompt", |registry| {
+                    registry
+                        .get(FIXTURE_SESSION)
+                        .is_some_and(|s| s.state == SessionState::Attention)
+                })
+            }
+            ("SessionEnd", ..) => wait_for("the session to end", |registry| {
+                registry.get(FIXTURE_SESSION).is_none()
+            }),
+            _ => assert!(has_session(FIXTURE_SESSION), "session gone after hook {i}"),
+        }
+    }
+}
+
+#[test]
+fn malformed_payloads_are_ignored() {
+    daemon();
+    // Hooks exit quietly on input they can't use
+    let truncated = session_start("e2e-truncated");
+    for payload in [
+        "",
+        "not json",
+        &truncated[..truncated.len() / 2],
+        r#"{"hook_event_name":"FutureHook","session_id":"e2e-future","cwd":"/code/e2e"}"#,
+    ] {
+        let output = hook(daemon().dir.path(), payload);
+        assert!(output.status.success());
+        assert!(output.stderr.is_empty(), "{payload:?}");
+    }
+
+    // Bad lines on a connection don't end it; the event after them applies
+    let mut stream = connect();
+    for line in [
+        "garbage",
+        r#"{"type":"session_started","session_id":"e2e-half""#,
+        r#"{"type":"session_started","session_id":"e2e-no-cwd","cwd":}"#,
+        "[1, 2, 3]",
+        "",
+        &started_line("e2e-after-garbage"),
+    ] {
+        writeln!(stream, "{line}").unwrap();
+    }
+    wait_for("the event after the garbage", |registry| {
+        registry.get("e2e-after-garbage").is_some()
+    });
+    for session_id in ["e2e-truncated", "e2e-future", "e2e-half", "e2e-no-cwd"] {
+        assert!(!has_session(session_id), "{session_id}");
+    }
+}
+
+#[test]
+fn stalled_clients_do_not_hold_up_others() {
+    daemon();
+    let _idle = connect();
+    let mut stalled = connect();
+    let line = started_line("e2e-stalled");
+    let (head, tail) = line.split_at(line.len() / 2);
+    stalled.write_all(head.as_bytes()).unwrap();
+
+    // Requests are answered within the client's timeout...
+    assert!(matches!(
+        ipc::send_request(&IpcMessage::Ping),
+        Ok(IpcResponse::Pong { .. })
+    ));
+    // ...and other hooks' events still apply
+    run_hook(&session_start("e2e-beside-stalled"));
+    wait_for("the hook beside the stalled client", |registry| {
+        registry.get("e2e-beside-stalled").is_some()
+    });
+    assert!(!has_session("e2e-stalled"));
+
+    // The stalled line completes where it left off
+    writeln!(stalled, "{tail}").unwrap();
+    wait_for("the stalled line", |registry| {
+        registry.get("e2e-stalled").is_some()
+    });
+}
+
+#[test]
+fn concurrent_hooks_all_register() {
+    daemon();
+    let session_ids: Vec<String> = (0..CONCURRENT_HOOKS)
+        .map(|i| format!("e2e-concurrent-{i}"))
+        .collect();
+    // Each SessionStart hook pings the daemon, then sends its events
+    let hooks: Vec<_> = session_ids
+        .iter()
+        .map(|session_id| {
+            let payload = session_start(session_id);
+            thread::spawn(move || run_hook(&payload))
+        })
+        .collect();
+    // Raw connections at the same time
+    let writers: Vec<_> = (0..CONCURRENT_HOOKS)
+        .map(|i| {
+            thread::spawn(move || {
+                let mut stream = connect();
+                writeln!(stream, "{}", started_line(&format!("e2e-raw-{i}"))).unwrap();
+            })
+        })
+        .collect();
+    for handle in hooks.into_iter().chain(writers) {
+        handle.join().unwrap();
+    }
+
+    wait_for("every concurrent session", |registry| {
+        (0..CONCURRENT_HOOKS).all(|i| {
+            registry.get(&format!("e2e-concurrent-{i}")).is_some()
+                && registry.get(&format!("e2e-raw-{i}")).is_some()
+        })
+    });
+}
+
+#[test]
+fn hooks_exit_promptly_without_a_working_daemon() {
+    daemon();
+    let payload = session_start("e2e-no-daemon");
+
+    // Nothing listening: the socket file is left over from a crashed daemon
+    let crashed = tempfile::tempdir_in("/tmp").unwrap();
+    drop(UnixListener::bind(crashed.path().join("aura.sock")).unwrap());
+    let output = hook(crashed.path(), &payload);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("daemon not running"));
+
+    // Hung: connections are accepted by the kernel but never read, so the
+    // SessionStart ping runs into its timeout and the events are dropped
+    let hung = tempfile::tempdir_in("/tmp").unwrap();
+    let _listener = UnixListener::bind(hung.path().join("aura.sock")).unwrap();
+    let started = Instant::now();
+    let output = hook(hung.path(), &payload);
+    assert!(output.status.success());
+    assert!(started.elapsed() < HOOK_TIMEOUT);
+
+    assert!(!has_session("e2e-no-daemon"));
+}
@@ -0,0 +1,427 @@
+//! Soak test - days of daemon traffic on an accelerated clock
+//!
+//! Runs the daemon's headless pieces (IPC server, coalescer, Codex watcher
+//! and the stale detection / garbage collection pass) with the registry on a
+//! `ManualClock`, and simulates `AURA_SOAK_DAYS` days (default 1) a minute at
+//! a time: Claude Code sessions that start, run tools and then end or go
+//! quiet mid-turn, a hook process talking to the socket every hour, and Codex
+//! rollouts growing on disk. Every simulated hour it checks that the
+//! registry's [`Footprint`], the open file descriptors and (on Linux) the
+//! resident memory stay bounded, and that quiet sessions go Stale, are
+//! archived and get evicted on schedule.
+//!
+//! For a longer run: `AURA_SOAK_DAYS=30 cargo test --release --test soak_test`.
+
+#![cfg(unix)]
+
+use aura::clock::{ManualClock, SharedClock};
+use aura::coalesce::Coalescer;
+use aura::config::AgentHome;
+use aura::filter::FilterRule;
+use aura::registry::{Footprint, RegistryChange, Retention, SessionRegistry, SharedRegistry};
+use aura::{AgentEvent, AgentType, SessionState};
+use chrono::Datelike;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::net::UnixStream;
+
+/// The daemon's stale timeout
+const STALE_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+/// Stale sessions are archived after this long
+const REMOVE_STALE_AFTER_MINUTES: u64 = 120;
+/// Kept low so a one-day run evicts
+const MAX_SESSIONS: usize = 50;
+/// Simulated hours before the baselines are taken
+const WARM_UP_HOURS: u64 = 6;
+/// A Claude Code session starts every this many minutes...
+const SESSION_EVERY: u64 = 7;
+/// ...and works this long before it ends or goes quiet
+const SESSION_MINUTES: u64 = 45;
+/// Sessions in this directory are filtered out
+const PRIVATE_CWD: &str = "/code/private";
+/// File descriptors allowed beyond the warmed-up baseline
+const FD_SLACK: usize = 16;
+/// Resident memory growth allowed beyond the warmed-up baseline
+const RSS_SLACK_KB: u64 = 64 * 1024;
+/// How long to wait for events that go through the socket or the watcher
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+fn claude_id(i: u64) -> String {
+    format!("c1a0de00-0000-4000-8000-{i:012x}")
+}
+
+fn codex_id(i: u64) -> String {
+    format!("0199c0de-0000-7000-8000-{i:012x}")
+}
+
+/// What a simulated Claude Code session does in `minute`
+fn claude_events(i: u64, minute: u64) -> Vec<AgentEvent> {
+    let (session_id, start) = (claude_id(i), i * SESSION_EVERY);
+    let cwd = if i % 5 == 4 {
+        PRIVATE_CWD.to_string()
+    } else {
+        format!("/code/project-{}", i % 4)
+    };
+    let Some(offset) = minute.checked_sub(start) else {
+        return Vec::new();
+    };
+    match offset {
+        0 => vec![AgentEvent::SessionStarted {
+            session_id,
+            cwd,
+            agent: AgentType::ClaudeCode,
+            terminal: None,
+        }],
+        offset if offset < SESSION_MINUTES => {
+            let tool_id = format!("toolu_{minute}");
+            vec![
+                AgentEvent::ToolStarted {
+                    session_id: session_id.clone(),
+                    cwd: cwd.clone(),
+                    tool_id: tool_id.clone(),
+                    tool_name: "Bash".into(),
+                    tool_label: Some("cargo test".into()),
+                    tool_full_label: None,
+                },
+                AgentEvent::ToolCompleted {
+                    session_id,
+                    cwd,
+                    tool_id,
+                },
+            ]
+        }
+        // A third end properly; the rest are killed mid-turn and go quiet
+        offset if offset == SESSION_MINUTES && i % 3 == 0 => {
+            vec![AgentEvent::SessionEnded { session_id }]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Checks one simulated Claude Code session against the stale and retention
+/// schedule, `minute` minutes in
+fn check_schedule(registry: &SessionRegistry, i: u64, minute: u64) {
+    let start = i * SESSION_EVERY;
+    if minute < start {
+        return;
+    }
+    let id = claude_id(i);
+    let session = registry.get(&id);
+    if i % 5 == 4 {
+        assert!(session.is_none(), "{id} should be filtered out");
+        return;
+    }
+    let last_active = start + SESSION_MINUTES - 1;
+    if minute <= last_active {
+        let session = session.unwrap_or_else(|| panic!("{id} is working but missing"));
+        assert_ne!(session.state, SessionState::Stale, "{id} is working");
+        return;
+    }
+    if i % 3 == 0 {
+        assert!(session.is_none(), "{id} ended");
+        return;
+    }
+    // Stale once quiet past the timeout, archived two hours after that;
+    // eviction may drop it at any point after going stale
+    let quiet = minute - last_active;
+    let stale_after = STALE_TIMEOUT.as_secs() / 60 + 1;
+    match session {
+        Some(session) if quiet < stale_after => {
+            assert_ne!(
+                session.state,
+                SessionState::Stale,
+                "{id} only just went quiet"
+            );
+        }
+        Some(session) if quiet < stale_after + REMOVE_STALE_AFTER_MINUTES => {
+            assert_eq!(session.state, SessionState::Stale, "{id}, quiet {quiet}min");
+            assert!(!session.archived, "{id} archived early");
+        }
+        Some(session) => assert!(session.archived, "{id} should be archived"),
+        None => assert!(quiet >= stale_after, "{id} evicted while live"),
+    }
+}
+
+/// Fail if anything the registry holds outgrew what its live sessions need
+fn check_footprint(footprint: Footprint) {
+    let live = (SESSION_MINUTES / SESSION_EVERY + 4) as usize;
+    assert!(footprint.sessions <= MAX_SESSIONS + live, "{footprint:?}");
+    assert!(footprint.tools <= 4 * live, "{footprint:?}");
+    assert!(footprint.held_events <= 32, "{footprint:?}");
+    assert!(footprint.deliveries <= 4 * live, "{footprint:?}");
+    assert!(
+        footprint.unlisted <= 1024 + footprint.sessions,
+        "{footprint:?}"
+    );
+    // Verdicts are kept up to a day for sessions that never end
+    let per_day = (24 * 60 / SESSION_EVERY + 24 + 8) as usize;
+    assert!(footprint.filtered <= per_day, "{footprint:?}");
+}
+
+fn open_fds() -> usize {
+    std::fs::read_dir("/proc/self/fd")
+        .or_else(|_| std::fs::read_dir("/dev/fd"))
+        .map(|dir| dir.count())
+        .unwrap_or(0)
+}
+
+/// Resident set size in KiB (`VmRSS`), where `/proc` has it
+fn resident_kb() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    line.split_whitespace().nth(1)?.parse().ok()
+}
+
+fn today_rollout_dir(codex_home: &Path) -> PathBuf {
+    let today = chrono::Local::now().date_naive();
+    codex_home
+        .join("sessions")
+        .join(format!("{:04}", today.year()))
+        .join(format!("{:02}", today.month()))
+        .join(format!("{:02}", today.day()))
+}
+
+/// A Codex rollout being written
+struct Rollout {
+    file: std::fs::File,
+    turn: u64,
+}
+
+impl Rollout {
+    fn create(dir: &Path, i: u64) -> Self {
+        let id = codex_id(i);
+        let path = dir.join(format!("rollout-2026-01-01T00-00-00-{id}.jsonl"));
+        let mut file = std::fs::File::create(path).unwrap();
+        let meta = serde_json::json!({
+            "type": "session_meta",
+            "payload": { "id": id, "cwd": format!("/code/codex-{i}") },
+        });
+        writeln!(file, "{meta}").unwrap();
+        Self { file, turn: 0 }
+    }
+
+    /// Append a turn that runs a command; the last one is left running, so
+    /// the session goes stale after its rollout is abandoned
+    fn grow(&mut self) {
+        self.turn += 1;
+        let call_id = format!("call_{}", self.turn);
+        let lines = [
+            serde_json::json!({ "type": "event_msg", "payload": { "type": "task_started" } }),
+            serde_json::json!({
+                "type": "response_item",
+                "payload": {
+                    "type": "function_call",
+                    "name": "exec_command",
+                    "arguments": "{\"cmd\": \"cargo build\"}",
+                    "call_id": call_id,
+                },
+            }),
+            serde_json::json!({
+                "type": "response_item",
+                "payload": { "type": "function_call_output", "call_id": call_id, "output": "ok" },
+            }),
+        ];
+        for line in lines {
+            writeln!(self.file, "{line}").unwrap();
+        }
+        self.file.flush().unwrap();
+    }
+}
+
+/// Send `events` as one hook process would: connect, write, hang up
+async fn hook(events: &[AgentEvent]) {
+    let mut stream = UnixStream::connect(aura::ipc::socket_path()).await.unwrap();
+    for event in events {
+        let line = serde_json::to_string(event).unwrap() + "\n";
+        stream.write_all(line.as_bytes()).await.unwrap();
+    }
+    stream.shutdown().await.unwrap();
+}
+
+/// Wait (in real time) until `check` holds for the registry
+async fn wait_for(registry: &SharedRegistry, what: &str, check: impl Fn(&SessionRegistry) -> bool) {
+    let deadline = tokio::time::Instant::now() + DELIVERY_TIMEOUT;
+    while !registry.read(&check).unwrap_or(false) {
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "timed out waiting for {what}"
+        );
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn daemon_stays_bounded_over_simulated_days() {
+    let days: u64 = std::env::var("AURA_SOAK_DAYS")
+        .ok()
+        .and_then(|days| days.parse().ok())
+        .unwrap_or(1);
+
+    let runtime_dir = tempfile::tempdir().unwrap();
+    let codex_home = tempfile::tempdir().unwrap();
+    // `set_var` is `unsafe` on Rust 2024; this is the binary's only test and
+    // nothing else has started reading the environment yet
+    unsafe {
+        std::env::set_var(aura::paths::RUNTIME_DIR_ENV, runtime_dir.path());
+    }
+    let rollout_dir = today_rollout_dir(codex_home.path());
+    std::fs::create_dir_all(&rollout_dir).unwrap();
+
+    let clock = Arc::new(ManualClock::new());
+    let mut registry = SessionRegistry::with_clock(SharedClock::new(clock.clone()));
+    registry.set_retention(Retention {
+        remove_stale_after: Some(Duration::from_secs(REMOVE_STALE_AFTER_MINUTES * 60)),
+        max_sessions: MAX_SESSIONS,
+    });
+    let deny: FilterRule =
+        serde_json::from_value(serde_json::json!({ "action": "deny", "cwd": PRIVATE_CWD }))
+            .unwrap();
+    registry.set_filters(vec![deny]);
+    let registry = SharedRegistry::new(registry);
+    let mut changes = registry.observe().unwrap();
+
+    // The daemon's services, as `aura --headless` runs them
+    let events = Coalescer::spawn(registry.clone());
+    tokio::spawn(aura::server::start(registry.clone(), events.clone()));
+    let codex = aura::agents::codex::spawn_with_homes(vec![AgentHome {
+        path: codex_home.path().display().to_string(),
+        profile: None,
+    }]);
+    let mut codex_rx = codex.subscribe();
+    let codex_events = events.clone();
+    tokio::spawn(async move {
+        while let Some(event) = codex_rx.recv().await {
+            codex_events.send(event, AgentType::Codex);
+        }
+    });
+    let socket = aura::ipc::socket_path();
+    let deadline = tokio::time::Instant::now() + DELIVERY_TIMEOUT;
+    while !socket.exists() {
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "server never listened"
+        );
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    let mut rollout: Option<Rollout> = None;
+    let mut codex_added = 0;
+    let mut baseline: Option<(usize, Option<u64>)> = None;
+    let sessions_started = |minute: u64| minute / SESSION_EVERY + 1;
+
+    for minute in 0..days * 24 * 60 {
+        clock.advance(Duration::from_secs(60));
+        let claude: Vec<AgentEvent> = (0..sessions_started(minute))
+            .flat_map(|i| claude_events(i, minute))
+            .collect();
+        // The stale detection task's pass, after the minute's events
+        registry.update(|registry| {
+            for event in claude {
+                registry.process_event(event);
+            }
+            registry.expire_pending();
+            registry.mark_stale(STALE_TIMEOUT);
+            registry.collect_garbage();
+        });
+
+        // A new Codex session every six hours, working every ten minutes
+        if minute % (6 * 60) == 0 {
+            rollout = Some(Rollout::create(&rollout_dir, minute / (6 * 60)));
+        }
+        if minute % 10 == 0
+            && let Some(rollout) = rollout.as_mut()
+        {
+            rollout.grow();
+        }
+
+        // A hook process per hour, through the socket and the coalescer
+        if minute % 60 == 30 {
+            let session_id = format!("h00c0000-0000-4000-8000-{minute:012x}");
+            hook(&[
+                AgentEvent::SessionStarted {
+                    session_id: session_id.clone(),
+                    cwd: "/code/hooked".into(),
+                    agent: AgentType::ClaudeCode,
+                    terminal: None,
+                },
+                AgentEvent::Idle {
+                    session_id: session_id.clone(),
+                    cwd: "/code/hooked".into(),
+                },
+            ])
+            .await;
+            wait_for(&registry, "a hook session", |r| r.has_session(&session_id)).await;
+            hook(&[AgentEvent::SessionEnded {
+                session_id: session_id.clone(),
+            }])
+            .await;
+            wait_for(&registry, "a hook session to end", |r| {
+                !r.has_session(&session_id)
+            })
+            .await;
+        }
+
+        // Observers must keep up, or their channel is the leak
+        while let Ok(change) = changes.try_recv() {
+            if let RegistryChange::Added(session) = change
+                && session.session_id.starts_with("0199c0de")
+            {
+                codex_added += 1;
+            }
+        }
+
+        if minute % 60 != 59 {
+            continue;
+        }
+        let hour = minute / 60 + 1;
+        registry
+            .read(|registry| {
+                for i in 0..sessions_started(minute) {
+                    check_schedule(registry, i, minute);
+                }
+                check_footprint(registry.footprint());
+            })
+            .unwrap();
+        let (fds, rss) = (open_fds(), resident_kb());
+        match baseline {
+            None if hour >= WARM_UP_HOURS => baseline = Some((fds, rss)),
+            None => {}
+            Some((base_fds, base_rss)) => {
+                assert!(
+                    fds <= base_fds + FD_SLACK,
+                    "fds grew from {base_fds} to {fds}"
+                );
+                if let (Some(base), Some(rss)) = (base_rss, rss) {
+                    assert!(
+                        rss <= base + RSS_SLACK_KB,
+                        "resident memory grew from {base} KiB to {rss} KiB by hour {hour}"
+                    );
+                }
+            }
+        }
+    }
+
+    // Codex sessions came through the watcher (in real time)
+    let deadline = tokio::time::Instant::now() + DELIVERY_TIMEOUT;
+    while codex_added == 0 {
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "no Codex session seen"
+        );
+        match tokio::time::timeout_at(deadline, changes.recv()).await {
+            Ok(Some(RegistryChange::Added(session)))
+                if session.session_id.starts_with("0199c0de") =>
+            {
+                codex_added += 1;
+            }
+            _ => {}
+        }
+    }
+
+    let totals = registry.read(SessionRegistry::gc_totals).unwrap();
+    assert!(totals.archived > 0, "nothing was archived: {totals:?}");
+    assert!(totals.evicted > 0, "nothing was evicted: {totals:?}");
+}
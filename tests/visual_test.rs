@@ -0,0 +1,137 @@
+//! Visual regression tests for the HUD
+//!
+//! Each case runs `aura --demo <scenario> --demo-theme <theme> --screenshot`,
+//! which draws the indicator and the session list with fixed sessions,
+//! animations off and opaque windows, and compares the PNG to
+//! `tests/visual/<scenario>-<theme>.png`. Pixels count as changed when their
+//! perceived color moves more than [`PIXEL_THRESHOLD`] (anti-aliasing and
+//! font smoothing stay below it); a case fails when more than
+//! [`MAX_CHANGED_RATIO`] of them changed, and leaves the new rendering in
+//! `target/visual-diff/` for review.
+//!
+//! Needs macOS with a display (`screencapture`), so it only builds with
+//! `cargo test --features visual-tests --test visual_test`. To accept a
+//! change, run it with `UPDATE_GOLDEN=1` and review the images before
+//! checking them in. Goldens are specific to the display's scale factor.
+
+#![cfg(target_os = "macos")]
+
+use image::{Rgba, RgbaImage};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const SCENARIOS: [&str; 4] = ["all", "running", "attention", "empty"];
+const THEMES: [&str; 2] = ["liquid-dark", "liquid-light"];
+
+/// Perceptual (YIQ) distance from which a pixel counts as changed, as a
+/// fraction of the largest possible distance
+const PIXEL_THRESHOLD: f64 = 0.1;
+/// Share of changed pixels a case tolerates
+const MAX_CHANGED_RATIO: f64 = 0.005;
+
+/// Largest squared YIQ distance (black vs white)
+const MAX_YIQ_DELTA: f64 = 35215.0;
+
+/// Squared YIQ distance of two pixels, alpha blended over white
+fn color_delta(a: Rgba<u8>, b: Rgba<u8>) -> f64 {
+    let blend = |pixel: Rgba<u8>| {
+        let alpha = f64::from(pixel[3]) / 255.0;
+        let channel = |c: u8| 255.0 + (f64::from(c) - 255.0) * alpha;
+        (channel(pixel[0]), channel(pixel[1]), channel(pixel[2]))
+    };
+    let ((r1, g1, b1), (r2, g2, b2)) = (blend(a), blend(b));
+    let y = |r: f64, g: f64, b: f64| r * 0.29889531 + g * 0.58662247 + b * 0.11448223;
+    let i = |r: f64, g: f64, b: f64| r * 0.59597799 - g * 0.27417610 - b * 0.32180189;
+    let q = |r: f64, g: f64, b: f64| r * 0.21147017 - g * 0.52261711 + b * 0.31114694;
+    let dy = y(r1, g1, b1) - y(r2, g2, b2);
+    let di = i(r1, g1, b1) - i(r2, g2, b2);
+    let dq = q(r1, g1, b1) - q(r2, g2, b2);
+    0.5053 * dy * dy + 0.299 * di * di + 0.1957 * dq * dq
+}
+
+/// Share of pixels that changed perceptibly; 1.0 when the sizes differ
+fn changed_ratio(expected: &RgbaImage, actual: &RgbaImage) -> f64 {
+    if expected.dimensions() != actual.dimensions() {
+        return 1.0;
+    }
+    let limit = PIXEL_THRESHOLD * PIXEL_THRESHOLD * MAX_YIQ_DELTA;
+    let changed = expected
+        .pixels()
+        .zip(actual.pixels())
+        .filter(|(a, b)| color_delta(**a, **b) > limit)
+        .count();
+    changed as f64 / f64::from(expected.width() * expected.height()).max(1.0)
+}
+
+fn render(scenario: &str, theme: &str, output: &Path) {
+    let status = Command::new(env!("CARGO_BIN_EXE_aura"))
+        .args(["--demo", scenario, "--demo-theme", theme, "--screenshot"])
+        .arg(output)
+        .status()
+        .expect("run aura --demo");
+    assert!(
+        status.success(),
+        "aura --demo {scenario} exited with {status}"
+    );
+}
+
+fn root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+#[test]
+fn hud_matches_the_goldens() {
+    let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+    let diff_dir = root().join("target/visual-diff");
+    std::fs::create_dir_all(&diff_dir).unwrap();
+    std::fs::create_dir_all(root().join("tests/visual")).unwrap();
+
+    let mut failures = Vec::new();
+    for scenario in SCENARIOS {
+        for theme in THEMES {
+            let case = format!("{scenario}-{theme}");
+            let golden = root().join("tests/visual").join(format!("{case}.png"));
+            let actual_path = diff_dir.join(format!("{case}.png"));
+            render(scenario, theme, &actual_path);
+            if update {
+                std::fs::copy(&actual_path, &golden).unwrap();
+                continue;
+            }
+            let Ok(expected) = image::open(&golden) else {
+                failures.push(format!("{case}: no golden, run with UPDATE_GOLDEN=1"));
+                continue;
+            };
+            let actual = image::open(&actual_path).unwrap().to_rgba8();
+            let ratio = changed_ratio(&expected.to_rgba8(), &actual);
+            if ratio > MAX_CHANGED_RATIO {
+                failures.push(format!(
+                    "{case}: {:.2}% of pixels changed, see {}",
+                    ratio * 100.0,
+                    actual_path.display()
+                ));
+            }
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn diff_ignores_small_shifts_but_not_changed_content() {
+    let base = RgbaImage::from_fn(10, 10, |x, _| {
+        if x < 5 {
+            Rgba([20, 20, 20, 255])
+        } else {
+            Rgba([240, 240, 240, 255])
+        }
+    });
+    let mut smoothed = base.clone();
+    smoothed.put_pixel(4, 4, Rgba([24, 22, 20, 255]));
+    assert_eq!(changed_ratio(&base, &smoothed), 0.0);
+
+    let mut recolored = base.clone();
+    for y in 0..10 {
+        recolored.put_pixel(0, y, Rgba([220, 40, 40, 255]));
+    }
+    assert!((changed_ratio(&base, &recolored) - 0.1).abs() < 1e-9);
+    assert_eq!(changed_ratio(&base, &RgbaImage::new(5, 5)), 1.0);
+}